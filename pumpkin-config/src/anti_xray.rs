@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+/// Keeps clients from seeing ores through walls with x-ray mods, by changing the blocks of the
+/// chunks sent to them. Blocks touching air, liquids or other blocks which aren't full cubes are
+/// always sent like they are, so this only changes what can't be seen anyway.
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct AntiXrayConfig {
+    pub enabled: bool,
+    pub engine_mode: AntiXrayEngineMode,
+    /// Blocks at or above this y coordinate are always sent like they are
+    pub max_block_height: i32,
+    /// The blocks which get hidden, without the `minecraft:` namespace
+    pub hidden_blocks: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum AntiXrayEngineMode {
+    /// Hidden blocks are sent as the stone, deepslate or netherrack around them
+    #[default]
+    HideOres,
+    /// Hidden blocks and the stone, deepslate and netherrack around them are sent as random
+    /// hidden blocks, so there are way too many fake ores to find the real ones. This sends
+    /// more data than hiding ores.
+    Randomize,
+}
+
+impl Default for AntiXrayConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            engine_mode: AntiXrayEngineMode::default(),
+            max_block_height: 64,
+            hidden_blocks: [
+                "coal_ore",
+                "deepslate_coal_ore",
+                "copper_ore",
+                "deepslate_copper_ore",
+                "iron_ore",
+                "deepslate_iron_ore",
+                "gold_ore",
+                "deepslate_gold_ore",
+                "redstone_ore",
+                "deepslate_redstone_ore",
+                "lapis_ore",
+                "deepslate_lapis_ore",
+                "diamond_ore",
+                "deepslate_diamond_ore",
+                "emerald_ore",
+                "deepslate_emerald_ore",
+                "nether_gold_ore",
+                "nether_quartz_ore",
+                "ancient_debris",
+            ]
+            .map(String::from)
+            .to_vec(),
+        }
+    }
+}
@@ -8,6 +8,9 @@ pub struct ChunkConfig {
     pub compression: ChunkCompression,
     pub format: ChunkFormat,
     pub write_in_place: bool,
+    /// Radius in chunks around the world spawn which is generated and saved in the background
+    /// on startup, 0 to not pregenerate anything
+    pub pregen_radius: u32,
 }
 
 #[derive(Deserialize, Serialize, Clone)]
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EntityCollisionConfig {
+    /// Whether overlapping mobs push each other apart.
+    pub push_entities: bool,
+    /// Whether mobs stuck inside a block are pushed out into a free adjacent block.
+    pub push_out_of_blocks: bool,
+    /// How many entities can be crammed together before they start taking damage. Specifying `0`
+    /// disables cramming damage.
+    pub max_entity_cramming: u32,
+}
+
+impl Default for EntityCollisionConfig {
+    fn default() -> Self {
+        Self {
+            push_entities: true,
+            push_out_of_blocks: true,
+            max_entity_cramming: 24,
+        }
+    }
+}
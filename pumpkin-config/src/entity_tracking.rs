@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// How far away (in blocks) players can see entities, per kind of entity.
+/// The ranges are additionally capped by the player's view distance.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct EntityTrackingConfig {
+    /// Tracking range of other players.
+    pub players: u32,
+    /// Tracking range of mobs, hostile or not.
+    pub mobs: u32,
+    /// Tracking range of items, experience orbs, item frames and paintings.
+    pub misc: u32,
+    /// Tracking range of everything else, e.g. projectiles and primed TNT.
+    pub other: u32,
+}
+
+impl Default for EntityTrackingConfig {
+    fn default() -> Self {
+        Self {
+            players: 48,
+            mobs: 48,
+            misc: 32,
+            other: 64,
+        }
+    }
+}
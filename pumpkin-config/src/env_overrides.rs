@@ -0,0 +1,125 @@
+//! Lets environment variables override config fields, which is handy for containers.
+//!
+//! `PUMPKIN_MAX_PLAYERS=50` sets `max_players`, and `__` goes into nested tables, like
+//! `PUMPKIN_NETWORKING__RCON__ENABLED=true`. Only fields a config has are overridden, so the
+//! variables meant for one config file are skipped while loading the other.
+
+use serde::{Serialize, de::DeserializeOwned};
+use toml::{Table, Value};
+
+const ENV_PREFIX: &str = "PUMPKIN_";
+const PATH_SEPARATOR: &str = "__";
+
+/// Applies the `PUMPKIN_` variables of `vars` to `config`, or explains which one is malformed.
+pub(crate) fn apply_env_overrides<T: Serialize + DeserializeOwned>(
+    config: T,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Result<T, String> {
+    let mut overrides = vars
+        .into_iter()
+        .filter_map(|(name, raw)| {
+            let path = name.strip_prefix(ENV_PREFIX)?.to_lowercase();
+            Some((name, path, raw))
+        })
+        .peekable();
+    if overrides.peek().is_none() {
+        return Ok(config);
+    }
+
+    let mut root = Value::try_from(&config).map_err(|err| err.to_string())?;
+    for (name, path, raw) in overrides {
+        let Some(field) = field_mut(&mut root, &path) else {
+            continue;
+        };
+        *field = parse_like(field, &raw).ok_or_else(|| {
+            format!(
+                "Couldn't parse environment variable {name}={raw:?}, expected a {}",
+                field.type_str()
+            )
+        })?;
+        // Check every variable on its own so the error can tell which one is wrong
+        if let Err(err) = root.clone().try_into::<T>() {
+            return Err(format!(
+                "Invalid value in environment variable {name}={raw:?}: {}",
+                err.message()
+            ));
+        }
+        log::debug!("Overriding config field {path} from {name}");
+    }
+    root.try_into().map_err(|err| err.message().to_string())
+}
+
+fn field_mut<'a>(root: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    path.split(PATH_SEPARATOR)
+        .try_fold(root, |value, key| value.as_table_mut()?.get_mut(key))
+}
+
+/// Parses `raw` as the same kind of TOML value as `current`.
+fn parse_like(current: &Value, raw: &str) -> Option<Value> {
+    Some(match current {
+        Value::String(_) => Value::String(raw.to_string()),
+        Value::Integer(_) => Value::Integer(raw.trim().parse().ok()?),
+        Value::Float(_) => Value::Float(raw.trim().parse().ok()?),
+        Value::Boolean(_) => Value::Boolean(raw.trim().parse().ok()?),
+        // Arrays, tables and dates are written like in a TOML file
+        _ => toml::from_str::<Table>(&format!("value = {raw}"))
+            .ok()?
+            .remove("value")?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{AdvancedConfiguration, BasicConfiguration};
+
+    use super::apply_env_overrides;
+
+    fn vars(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn env_vars_override_config_fields() {
+        let config = apply_env_overrides(
+            BasicConfiguration::default(),
+            vars(&[
+                ("PUMPKIN_MAX_PLAYERS", "42"),
+                ("PUMPKIN_SERVER_ADDRESS", "127.0.0.1:25566"),
+                // Belongs to the other config, or to nothing at all
+                ("PUMPKIN_CHUNK__PREGEN_RADIUS", "8"),
+                ("PATH", "/usr/bin"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.max_players, 42);
+        assert_eq!(config.server_address.to_string(), "127.0.0.1:25566");
+
+        let config = apply_env_overrides(
+            AdvancedConfiguration::default(),
+            vars(&[
+                ("PUMPKIN_CHUNK__PREGEN_RADIUS", "8"),
+                ("PUMPKIN_NETWORKING__RCON__ENABLED", "true"),
+            ]),
+        )
+        .unwrap();
+        assert_eq!(config.chunk.pregen_radius, 8);
+        assert!(config.networking.rcon.enabled);
+
+        let err = apply_env_overrides(
+            BasicConfiguration::default(),
+            vars(&[("PUMPKIN_MAX_PLAYERS", "lots")]),
+        )
+        .err()
+        .unwrap();
+        assert!(err.contains("PUMPKIN_MAX_PLAYERS"));
+        let err = apply_env_overrides(
+            BasicConfiguration::default(),
+            vars(&[("PUMPKIN_SERVER_ADDRESS", "localhost")]),
+        )
+        .err()
+        .unwrap();
+        assert!(err.contains("PUMPKIN_SERVER_ADDRESS"));
+    }
+}
@@ -0,0 +1,38 @@
+use serde::{Deserialize, Serialize};
+
+/// What happens when a player joins the server for the first time. `{player}` in the commands and
+/// texts is replaced by the player's name. Nothing happens by default, like in vanilla.
+#[derive(Deserialize, Serialize, Default)]
+#[serde(default)]
+pub struct FirstJoinConfig {
+    /// Commands run as new players, without the leading `/`.
+    pub commands: Vec<String>,
+    /// A chat message sent to new players. Empty to send none.
+    pub welcome_message: String,
+    /// A title shown to new players. Empty to show none.
+    pub welcome_title: String,
+    /// The subtitle shown along with the title.
+    pub welcome_subtitle: String,
+    /// The starter kit given to new players.
+    pub kit: Vec<KitItem>,
+    /// Where new players spawn instead of the world spawn.
+    pub spawn: Option<FirstJoinSpawn>,
+}
+
+#[derive(Deserialize, Serialize, Clone)]
+pub struct KitItem {
+    /// The item's id, like `minecraft:stone_sword`.
+    pub item: String,
+    pub count: u32,
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+pub struct FirstJoinSpawn {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    #[serde(default)]
+    pub yaw: f32,
+    #[serde(default)]
+    pub pitch: f32,
+}
@@ -1,4 +1,5 @@
 use chunk::ChunkConfig;
+use env_overrides::apply_env_overrides;
 use fun::FunConfig;
 use log::warn;
 use logging::LoggingConfig;
@@ -19,23 +20,35 @@ pub mod networking;
 
 pub mod resource_pack;
 
+pub use anti_xray::{AntiXrayConfig, AntiXrayEngineMode};
 pub use chat::ChatConfig;
 pub use commands::CommandsConfig;
+pub use entity_collision::EntityCollisionConfig;
+pub use entity_tracking::EntityTrackingConfig;
+pub use first_join::{FirstJoinConfig, FirstJoinSpawn, KitItem};
 pub use networking::auth::AuthenticationConfig;
 pub use networking::compression::CompressionConfig;
 pub use networking::lan_broadcast::LANBroadcastConfig;
+pub use networking::metrics::MetricsConfig;
 pub use networking::rcon::RCONConfig;
 pub use pvp::PVPConfig;
 pub use server_links::ServerLinksConfig;
+pub use spawn_limits::SpawnLimitsConfig;
 
+mod anti_xray;
 mod commands;
 
 mod chat;
 pub mod chunk;
+mod entity_collision;
+mod entity_tracking;
+mod env_overrides;
+mod first_join;
 pub mod op;
 mod player_data;
 mod pvp;
 mod server_links;
+mod spawn_limits;
 pub mod whitelist;
 
 use networking::NetworkingConfig;
@@ -100,9 +113,14 @@ pub struct AdvancedConfiguration {
     pub commands: CommandsConfig,
     pub chat: ChatConfig,
     pub pvp: PVPConfig,
+    pub entity_tracking: EntityTrackingConfig,
+    pub entity_collision: EntityCollisionConfig,
     pub server_links: ServerLinksConfig,
     pub player_data: PlayerDataConfig,
+    pub first_join: FirstJoinConfig,
+    pub spawn_limits: SpawnLimitsConfig,
     pub fun: FunConfig,
+    pub anti_xray: AntiXrayConfig,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -126,6 +144,8 @@ pub struct BasicConfiguration {
     pub allow_nether: bool,
     /// Whether the server is in hardcore mode.
     pub hardcore: bool,
+    /// Whether players respawn right away instead of seeing the death screen.
+    pub immediate_respawn: bool,
     /// Whether online mode is enabled. Requires valid Minecraft accounts.
     pub online_mode: bool,
     /// Whether packet encryption is enabled. Required when online mode is enabled.
@@ -146,12 +166,28 @@ pub struct BasicConfiguration {
     pub favicon_path: String,
     /// The default level name
     pub default_level_name: String,
+    /// How new chunks are generated: `normal` terrain, `flat` layers or an empty `void`.
+    pub level_type: LevelType,
+    /// The superflat preset of flat worlds, like
+    /// `minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block;minecraft:plains`. Empty for the
+    /// classic flat world.
+    pub generator_settings: String,
+    /// Whether the worlds only live in memory. Chunks are generated on demand and nothing is
+    /// saved, so every restart starts from a fresh world. Useful for minigames and testing.
+    pub in_memory_world: bool,
     /// Whether chat messages should be signed or not
     pub allow_chat_reports: bool,
+    /// Whether players without a chat session are kicked when chatting while chat messages are
+    /// signed. Otherwise their messages are sent unsigned.
+    pub enforce_secure_chat: bool,
     /// Whether to enable the whitelist
     pub white_list: bool,
     /// Whether to enforce the whitelist
     pub enforce_whitelist: bool,
+    /// Minutes a player may stay idle before being kicked. Specifying `0` disables the kick.
+    pub player_idle_timeout: u32,
+    /// Whether operators are never kicked for being idle
+    pub idle_timeout_exempts_ops: bool,
 }
 
 impl Default for BasicConfiguration {
@@ -166,6 +202,7 @@ impl Default for BasicConfiguration {
             op_permission_level: PermissionLvl::Four,
             allow_nether: true,
             hardcore: false,
+            immediate_respawn: false,
             online_mode: true,
             encryption: true,
             motd: "A blazingly fast Pumpkin server!".to_string(),
@@ -176,17 +213,51 @@ impl Default for BasicConfiguration {
             use_favicon: true,
             favicon_path: "icon.png".to_string(),
             default_level_name: "world".to_string(),
+            level_type: LevelType::Normal,
+            generator_settings: String::new(),
+            in_memory_world: false,
             allow_chat_reports: false,
+            enforce_secure_chat: true,
             white_list: false,
             enforce_whitelist: false,
+            player_idle_timeout: 0,
+            idle_timeout_exempts_ops: false,
         }
     }
 }
 
+/// How the chunks of new worlds are generated.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum LevelType {
+    #[default]
+    Normal,
+    Flat,
+    Void,
+}
+
 impl BasicConfiguration {
     pub fn get_world_path(&self) -> PathBuf {
         format!("./{}", self.default_level_name).parse().unwrap()
     }
+
+    /// Reads the configuration file again, for the settings which can change while the server is
+    /// running. Unlike at startup, a missing or broken file is an error rather than a panic.
+    pub fn reload() -> Result<Self, String> {
+        let exec_dir = env::current_dir().map_err(|err| err.to_string())?;
+        Self::read(&exec_dir.join(CONFIG_ROOT_FOLDER).join(Self::get_path()))
+    }
+
+    fn read(path: &Path) -> Result<Self, String> {
+        let file_content = fs::read_to_string(path)
+            .map_err(|err| format!("Couldn't read configuration file at {path:?}: {err}"))?;
+        let config = toml::from_str(&file_content)
+            .map_err(|err| format!("Couldn't parse config at {path:?}: {}", err.message()))?;
+        let vars = env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        });
+        apply_env_overrides(config, vars)
+    }
 }
 
 trait LoadConfiguration {
@@ -225,6 +296,11 @@ trait LoadConfiguration {
             content
         };
 
+        let vars = env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        });
+        let config = apply_env_overrides(config, vars).unwrap_or_else(|err| panic!("{err}"));
+
         config.validate();
         config
     }
@@ -29,6 +29,9 @@ pub struct CompressionInfo {
     /// `1` = Optimize for the best speed of encoding.
     /// `9` = Optimize for the size of data being encoded.
     pub level: u32,
+    /// Whether large packets which barely shrink when compressed are sent uncompressed instead,
+    /// saving the CPU time of compressing them.
+    pub skip_incompressible: bool,
 }
 
 impl Default for CompressionInfo {
@@ -36,6 +39,7 @@ impl Default for CompressionInfo {
         Self {
             threshold: 256,
             level: 4,
+            skip_incompressible: true,
         }
     }
 }
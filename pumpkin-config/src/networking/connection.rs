@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct ConnectionConfig {
+    /// The time in milliseconds a client may take to send its next packet before playing (during
+    /// handshake, status, login and configuration) until it gets disconnected.
+    pub pre_play_timeout: u32,
+    /// The largest packet in bytes a client may send, larger ones disconnect it. Can't be raised
+    /// above the protocol limit of 2097152.
+    pub max_packet_size: u32,
+}
+
+impl Default for ConnectionConfig {
+    fn default() -> Self {
+        Self {
+            pre_play_timeout: 30000,
+            max_packet_size: 2097152,
+        }
+    }
+}
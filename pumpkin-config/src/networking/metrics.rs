@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use std::net::{Ipv4Addr, SocketAddr};
+
+#[derive(Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct MetricsConfig {
+    /// Whether the Prometheus metrics endpoint is enabled.
+    pub enabled: bool,
+    /// The network address and port where metrics can be scraped from.
+    pub address: SocketAddr,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: SocketAddr::new(Ipv4Addr::new(127, 0, 0, 1).into(), 9225),
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use auth::AuthenticationConfig;
+use connection::ConnectionConfig;
+use metrics::MetricsConfig;
 use proxy::ProxyConfig;
 use query::QueryConfig;
 use rcon::RCONConfig;
@@ -8,7 +10,9 @@ use crate::{CompressionConfig, LANBroadcastConfig};
 
 pub mod auth;
 pub mod compression;
+pub mod connection;
 pub mod lan_broadcast;
+pub mod metrics;
 pub mod proxy;
 pub mod query;
 pub mod rcon;
@@ -21,4 +25,6 @@ pub struct NetworkingConfig {
     pub proxy: ProxyConfig,
     pub packet_compression: CompressionConfig,
     pub lan_broadcast: LANBroadcastConfig,
+    pub metrics: MetricsConfig,
+    pub connection: ConnectionConfig,
 }
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// How many mobs of each kind spawn naturally around the players, aka the mob caps.
+/// The caps grow with the number of chunks around players, they are per 289 chunks like vanilla.
+/// A cap of 0 keeps that kind of mob from spawning naturally.
+#[derive(Deserialize, Serialize)]
+#[serde(default)]
+pub struct SpawnLimitsConfig {
+    /// Whether mobs spawn naturally at all.
+    pub enabled: bool,
+    pub monsters: u32,
+    pub creatures: u32,
+    pub ambient: u32,
+    pub axolotls: u32,
+    pub underground_water_creatures: u32,
+    pub water_creatures: u32,
+    pub water_ambient: u32,
+    /// How many ticks pass between attempts to spawn animals, other mobs try every tick.
+    pub ticks_per_creature_spawn: u32,
+}
+
+impl Default for SpawnLimitsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            monsters: 70,
+            creatures: 10,
+            ambient: 15,
+            axolotls: 5,
+            underground_water_creatures: 5,
+            water_creatures: 5,
+            water_ambient: 20,
+            ticks_per_creature_spawn: 400,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use std::fmt;
+
+use heck::ToPascalCase;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use serde::{
+    Deserialize, Deserializer,
+    de::{MapAccess, Visitor},
+};
+
+/// The attributes in the order they are registered, which is also their network id.
+struct OrderedAttributes(Vec<(String, f64)>);
+
+impl<'de> Deserialize<'de> for OrderedAttributes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct AttributesVisitor;
+
+        impl<'de> Visitor<'de> for AttributesVisitor {
+            type Value = OrderedAttributes;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of attributes to their default value")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut attributes = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    attributes.push(entry);
+                }
+                Ok(OrderedAttributes(attributes))
+            }
+        }
+
+        deserializer.deserialize_map(AttributesVisitor)
+    }
+}
+
+pub(crate) fn build() -> TokenStream {
+    println!("cargo:rerun-if-changed=../assets/attributes.json");
+
+    let attributes: OrderedAttributes =
+        serde_json::from_str(include_str!("../../assets/attributes.json"))
+            .expect("Failed to parse attributes.json");
+
+    let mut variants = TokenStream::new();
+    let mut all = TokenStream::new();
+    let mut type_from_name = TokenStream::new();
+    let mut type_to_name = TokenStream::new();
+    let mut default_values = TokenStream::new();
+
+    for (name, default_value) in &attributes.0 {
+        let const_ident = format_ident!("{}", name.to_pascal_case());
+
+        variants.extend(quote! {
+            #const_ident,
+        });
+        all.extend(quote! {
+            Self::#const_ident,
+        });
+        type_from_name.extend(quote! {
+            #name => Some(Self::#const_ident),
+        });
+        type_to_name.extend(quote! {
+            Self::#const_ident => #name,
+        });
+        default_values.extend(quote! {
+            Self::#const_ident => #default_value,
+        });
+    }
+    let count = attributes.0.len();
+
+    quote! {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[repr(u8)]
+        pub enum Attribute {
+            #variants
+        }
+
+        impl Attribute {
+            pub const ALL: [Self; #count] = [#all];
+
+            #[doc = r" Try to parse an `Attribute` from a resource location string."]
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name.strip_prefix("minecraft:").unwrap_or(name) {
+                    #type_from_name
+                    _ => None
+                }
+            }
+
+            pub const fn to_name(&self) -> &'static str {
+                match self {
+                    #type_to_name
+                }
+            }
+
+            #[doc = r" The id of the attribute in the `minecraft:attribute` registry."]
+            pub const fn id(&self) -> u8 {
+                *self as u8
+            }
+
+            #[doc = r" The base value used when an entity type doesn't define its own."]
+            pub const fn default_value(&self) -> f64 {
+                match self {
+                    #default_values
+                }
+            }
+        }
+    }
+}
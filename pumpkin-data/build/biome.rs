@@ -1,6 +1,6 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use heck::ToShoutySnakeCase;
+use heck::{ToShoutySnakeCase, ToUpperCamelCase};
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use serde::Deserialize;
@@ -14,9 +14,43 @@ struct Biome {
     temperature_modifier: Option<TemperatureModifier>,
     //carvers: Vec<String>,
     features: Vec<Vec<String>>,
+    spawners: BTreeMap<String, Vec<SpawnEntry>>,
     id: u8,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SpawnEntry {
+    r#type: String,
+    min_count: u32,
+    max_count: u32,
+    weight: u32,
+}
+
+impl SpawnEntry {
+    fn into_token_stream(self) -> TokenStream {
+        let entity_type = format_ident!(
+            "{}",
+            self.r#type
+                .strip_prefix("minecraft:")
+                .unwrap()
+                .to_shouty_snake_case()
+        );
+        let min_count = self.min_count;
+        let max_count = self.max_count;
+        let weight = self.weight;
+
+        quote! {
+            SpawnEntry {
+                entity_type: EntityType::#entity_type,
+                min_count: #min_count,
+                max_count: #max_count,
+                weight: #weight
+            }
+        }
+    }
+}
+
 #[derive(Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 enum TemperatureModifier {
@@ -115,7 +149,7 @@ pub(crate) fn build() -> TokenStream {
     let mut name_to_type = TokenStream::new();
     let mut id_to_type = TokenStream::new();
 
-    for (name, biome) in biomes.iter() {
+    for (name, biome) in biomes.into_iter() {
         // let full_name = format!("minecraft:{name}");
         let format_name = format_ident!("{}", name.to_shouty_snake_case());
         let has_precipitation = biome.has_precipitation;
@@ -133,6 +167,14 @@ pub(crate) fn build() -> TokenStream {
             TemperatureModifier::None => quote! { TemperatureModifier::None },
         };
         let index = LitInt::new(&biome.id.to_string(), Span::call_site());
+        let spawners = biome.spawners.into_iter().map(|(group, entries)| {
+            let group = format_ident!("{}", group.to_upper_camel_case());
+            let entries = entries
+                .into_iter()
+                .map(SpawnEntry::into_token_stream)
+                .collect::<Vec<_>>();
+            quote! { (SpawnGroup::#group, &[#(#entries),*]) }
+        });
 
         variants.extend([quote! {
             pub const #format_name: Biome = Biome {
@@ -144,7 +186,8 @@ pub(crate) fn build() -> TokenStream {
                     #temperature_modifier,
                     #downfall
                ),
-               features: &[#(&[#(#features),*]),*]
+               features: &[#(&[#(#features),*]),*],
+               spawners: &[#(#spawners),*]
             };
         }]);
 
@@ -159,6 +202,7 @@ pub(crate) fn build() -> TokenStream {
         use pumpkin_util::biome::{TemperatureModifier, Weather};
         use serde::{de, Deserializer};
         use std::{fmt, hash::{Hasher, Hash}};
+        use crate::entity::EntityType;
 
         #[derive(Debug)]
         pub struct Biome {
@@ -166,7 +210,32 @@ pub(crate) fn build() -> TokenStream {
             pub registry_id: &'static str,
             pub weather: Weather,
             // carvers: &'static [&str],
-            pub features: &'static [&'static [&'static str]]
+            pub features: &'static [&'static [&'static str]],
+            /// Which mobs spawn naturally in this biome
+            pub spawners: &'static [(SpawnGroup, &'static [SpawnEntry])]
+        }
+
+        /// The kinds of mobs which count towards separate mob caps.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum SpawnGroup {
+            Monster,
+            Creature,
+            Ambient,
+            Axolotls,
+            UndergroundWaterCreature,
+            WaterCreature,
+            WaterAmbient,
+            Misc,
+        }
+
+        /// A mob which spawns naturally in packs of `min_count` to `max_count`, chosen with a
+        /// chance proportional to its `weight`.
+        #[derive(Debug)]
+        pub struct SpawnEntry {
+            pub entity_type: EntityType,
+            pub min_count: u32,
+            pub max_count: u32,
+            pub weight: u32,
         }
 
         impl PartialEq for Biome {
@@ -220,6 +289,14 @@ pub(crate) fn build() -> TokenStream {
         impl Biome {
             #variants
 
+            /// The mobs of `group` which spawn naturally in this biome.
+            pub fn spawn_entries(&self, group: SpawnGroup) -> &'static [SpawnEntry] {
+                self.spawners
+                    .iter()
+                    .find(|(spawn_group, _)| *spawn_group == group)
+                    .map_or(&[], |(_, entries)| entries)
+            }
+
             pub fn from_name(name: &str) -> Option<&'static Self> {
                 match name {
                     #name_to_type
@@ -597,7 +597,7 @@ pub enum LootConditionStruct {
         properties: HashMap<String, String>,
     },
     #[serde(rename = "minecraft:match_tool")]
-    MatchTool,
+    MatchTool { predicate: MatchToolPredicateStruct },
     #[serde(rename = "minecraft:table_bonus")]
     TableBonus,
     #[serde(rename = "minecraft:survives_explosion")]
@@ -618,6 +618,30 @@ pub enum LootConditionStruct {
     EnchantmentActiveCheck,
 }
 
+#[derive(Deserialize, Clone, Debug)]
+pub struct MatchToolPredicateStruct {
+    /// An item id or an item tag starting with `#`
+    items: Option<String>,
+    predicates: Option<MatchToolSubPredicatesStruct>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct MatchToolSubPredicatesStruct {
+    #[serde(rename = "minecraft:enchantments", default)]
+    enchantments: Vec<EnchantmentPredicateStruct>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnchantmentPredicateStruct {
+    enchantments: String,
+    levels: EnchantmentLevelsStruct,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnchantmentLevelsStruct {
+    min: i32,
+}
+
 impl ToTokens for LootConditionStruct {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let name = match self {
@@ -638,7 +662,23 @@ impl ToTokens for LootConditionStruct {
                     .collect();
                 quote! { LootCondition::BlockStateProperty { block: #block, properties: &[#(#properties),*] } }
             }
-            LootConditionStruct::MatchTool => quote! { LootCondition::MatchTool },
+            LootConditionStruct::MatchTool { predicate } => {
+                let items = match &predicate.items {
+                    Some(items) => quote! { Some(#items) },
+                    None => quote! { None },
+                };
+                let enchantments: Vec<_> = predicate
+                    .predicates
+                    .iter()
+                    .flat_map(|predicates| &predicates.enchantments)
+                    .map(|enchantment| {
+                        let id = &enchantment.enchantments;
+                        let min = enchantment.levels.min;
+                        quote! { (#id, #min) }
+                    })
+                    .collect();
+                quote! { LootCondition::MatchTool { items: #items, enchantments: &[#(#enchantments),*] } }
+            }
             LootConditionStruct::TableBonus => quote! { LootCondition::TableBonus },
             LootConditionStruct::SurvivesExplosion => quote! { LootCondition::SurvivesExplosion },
             LootConditionStruct::DamageSourceProperties => {
@@ -4,6 +4,7 @@ use std::{env, fs, path::Path, process::Command};
 use heck::ToPascalCase;
 use proc_macro2::TokenStream;
 
+mod attribute;
 mod biome;
 mod block;
 mod chunk_status;
@@ -40,6 +41,7 @@ pub fn main() {
     write_generated_file(scoreboard_slot::build(), "scoreboard_slot.rs");
     write_generated_file(world_event::build(), "world_event.rs");
     write_generated_file(entity_type::build(), "entity_type.rs");
+    write_generated_file(attribute::build(), "attribute.rs");
     write_generated_file(noise_parameter::build(), "noise_parameter.rs");
     write_generated_file(biome::build(), "biome.rs");
     write_generated_file(damage_type::build(), "damage_type.rs");
@@ -21,6 +21,8 @@ pub struct ItemComponents {
     pub max_stack_size: u8,
     #[serde(rename = "minecraft:jukebox_playable")]
     pub jukebox_playable: Option<String>,
+    #[serde(rename = "minecraft:provides_trim_material")]
+    pub provides_trim_material: Option<String>,
     #[serde(rename = "minecraft:damage")]
     pub damage: Option<u16>,
     #[serde(rename = "minecraft:max_damage")]
@@ -44,6 +46,14 @@ impl ToTokens for ItemComponents {
             None => quote! { None },
         };
 
+        let provides_trim_material = match &self.provides_trim_material {
+            Some(material) => {
+                let material = LitStr::new(material, Span::call_site());
+                quote! { Some(#material) }
+            }
+            None => quote! { None },
+        };
+
         let item_name = {
             let text = self.item_name.clone().get_text();
             let item_name = LitStr::new(&text, Span::call_site());
@@ -173,6 +183,7 @@ impl ToTokens for ItemComponents {
                 item_name: #item_name,
                 max_stack_size: #max_stack_size,
                 jukebox_playable: #jukebox_playable,
+                provides_trim_material: #provides_trim_material,
                 damage: #damage,
                 max_damage: #max_damage,
                 attribute_modifiers: #attribute_modifiers,
@@ -281,6 +292,7 @@ pub(crate) fn build() -> TokenStream {
             pub item_name: &'static str,
             pub max_stack_size: u8,
             pub jukebox_playable: Option<&'static str>,
+            pub provides_trim_material: Option<&'static str>,
             pub damage: Option<u16>,
             pub max_damage: Option<u16>,
             pub attribute_modifiers: Option<&'static [Modifier]>,
@@ -37,6 +37,7 @@ pub mod entity {
     include!(concat!(env!("OUT_DIR"), "/spawn_egg.rs"));
     include!(concat!(env!("OUT_DIR"), "/entity_type.rs"));
     include!(concat!(env!("OUT_DIR"), "/entity_pose.rs"));
+    include!(concat!(env!("OUT_DIR"), "/attribute.rs"));
 }
 
 pub mod world {
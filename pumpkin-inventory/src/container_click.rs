@@ -155,7 +155,7 @@ impl DropType {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MouseDragType {
     Left,
     Right,
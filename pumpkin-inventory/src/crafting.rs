@@ -0,0 +1,95 @@
+use pumpkin_data::item::Item;
+use pumpkin_world::block::entities::sign::DyeColor;
+use pumpkin_world::item::ItemStack;
+
+/// The result of the special crafting recipes which depend on the components of their
+/// ingredients, rather than on a fixed pattern. The shape of the grid doesn't matter to them.
+pub fn special_recipe_result(input: &[Option<&ItemStack>]) -> Option<ItemStack> {
+    let ingredients: Vec<&ItemStack> = input.iter().flatten().copied().collect();
+    armor_dye(&ingredients).or_else(|| shield_decoration(&ingredients))
+}
+
+/// `minecraft:crafting_special_armordye`: one dyeable item with any amount of dyes.
+fn armor_dye(ingredients: &[&ItemStack]) -> Option<ItemStack> {
+    let mut dyeable = None;
+    let mut dyes = Vec::new();
+    for ingredient in ingredients {
+        if let Some(dye) = DyeColor::from_dye_item(ingredient.item.registry_key) {
+            dyes.push(dye);
+        } else if ingredient.is_dyeable() && dyeable.is_none() {
+            dyeable = Some(*ingredient);
+        } else {
+            return None;
+        }
+    }
+
+    let mut result = dyeable?.copy_with_count(1);
+    result.dye(&dyes).then_some(result)
+}
+
+/// `minecraft:crafting_special_shielddecoration`: a plain shield with a banner, whose patterns
+/// end up on the shield.
+fn shield_decoration(ingredients: &[&ItemStack]) -> Option<ItemStack> {
+    let [first, second] = ingredients else {
+        return None;
+    };
+    let (shield, banner) = if first.item.id == Item::SHIELD.id {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    if shield.item.id != Item::SHIELD.id
+        || !shield.patch.banner_patterns.is_empty()
+        || banner.item.id == Item::SHIELD.id
+        || !banner.has_banner_patterns()
+    {
+        return None;
+    }
+
+    let mut result = shield.copy_with_count(1);
+    for layer in &banner.patch.banner_patterns {
+        result.add_banner_pattern(&layer.pattern, layer.color);
+    }
+    Some(result)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_world::block::entities::sign::DyeColor;
+    use pumpkin_world::item::ItemStack;
+
+    use super::special_recipe_result;
+
+    #[test]
+    fn leather_armor_is_dyed_by_crafting() {
+        let boots = ItemStack::new(1, Item::LEATHER_BOOTS);
+        let dye = ItemStack::new(3, Item::RED_DYE);
+
+        let result = special_recipe_result(&[Some(&boots), None, Some(&dye)]).unwrap();
+        assert_eq!(result.item, Item::LEATHER_BOOTS);
+        assert_eq!(
+            result.patch.dyed_color,
+            Some(DyeColor::Red.texture_diffuse_color())
+        );
+
+        // Nothing but the armor and dyes may be in the grid
+        let stone = ItemStack::new(1, Item::STONE);
+        assert!(special_recipe_result(&[Some(&boots), Some(&dye), Some(&stone)]).is_none());
+        assert!(special_recipe_result(&[Some(&boots)]).is_none());
+    }
+
+    #[test]
+    fn banner_patterns_are_put_on_shields() {
+        let mut banner = ItemStack::new(1, Item::WHITE_BANNER);
+        banner.add_banner_pattern("stripe_bottom", DyeColor::Blue);
+        let shield = ItemStack::new(1, Item::SHIELD);
+
+        let result = special_recipe_result(&[Some(&banner), Some(&shield)]).unwrap();
+        assert_eq!(result.item, Item::SHIELD);
+        assert_eq!(result.patch.banner_patterns, banner.patch.banner_patterns);
+
+        // Decorated shields stay as they are
+        assert!(special_recipe_result(&[Some(&banner), Some(&result)]).is_none());
+    }
+}
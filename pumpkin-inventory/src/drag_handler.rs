@@ -70,6 +70,20 @@ impl DragHandler {
         if player != drag.player {
             Err(InventoryError::MultiplePlayersDragging)?
         }
+        let drag = Drag {
+            player: drag.player,
+            drag_type: drag.drag_type,
+            slots: drag
+                .slots
+                .iter()
+                .copied()
+                .filter(|slot| {
+                    maybe_carried_item
+                        .as_ref()
+                        .is_some_and(|item| container.accepts_item(*slot, item))
+                })
+                .collect(),
+        };
         let mut slots = container.all_slots();
         let Some(carried_item) = maybe_carried_item else {
             return Ok(());
@@ -96,10 +110,7 @@ impl DragHandler {
                                 carried_item.item_count += 1;
                             }
                         } else {
-                            *slots[slot] = Some(ItemStack {
-                                item: carried_item.item.clone(),
-                                item_count: 1,
-                            })
+                            *slots[slot] = Some(carried_item.copy_with_count(1))
                         }
                     }
                 });
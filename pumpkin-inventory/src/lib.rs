@@ -4,14 +4,17 @@ use pumpkin_data::screen::WindowType;
 use pumpkin_world::item::ItemStack;
 
 pub mod container_click;
+mod crafting;
 pub mod drag_handler;
 mod error;
 mod open_container;
 pub mod player;
+mod smithing;
 pub mod window_property;
 
 pub use error::InventoryError;
 pub use open_container::*;
+pub use smithing::{RecipeLoadError, SmithingTable, reload_smithing_recipes, smithing_result};
 
 pub struct ContainerStruct<const SLOTS: usize>([Option<ItemStack>; SLOTS]);
 
@@ -28,6 +31,11 @@ pub trait Container: Sync + Send {
         mouse_click: MouseClick,
         taking_crafted: bool,
     ) -> Result<(), InventoryError> {
+        if let Some(carried) = carried_item.as_ref() {
+            if !taking_crafted && !self.accepts_item(slot, carried) {
+                return Ok(());
+            }
+        }
         let all_slots = self.all_slots();
         if slot > all_slots.len() {
             Err(InventoryError::InvalidSlot)?
@@ -44,6 +52,9 @@ pub trait Container: Sync + Send {
             }
             return Ok(());
         }
+        if mouse_click == MouseClick::Right && handle_bundle_click(carried_item, all_slots[slot]) {
+            return Ok(());
+        }
         handle_item_change(carried_item, all_slots[slot], mouse_click);
 
         Ok(())
@@ -51,6 +62,11 @@ pub trait Container: Sync + Send {
 
     fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]>;
 
+    /// Whether the given item may be put into the slot, shulker boxes for example don't accept other shulker boxes.
+    fn accepts_item(&self, _slot: usize, _item: &ItemStack) -> bool {
+        true
+    }
+
     fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]>;
 
     fn clear_all_slots(&mut self) {
@@ -144,6 +160,37 @@ pub fn handle_item_take(
     }
     *carried_item = Some(new_item);
 }
+/// Right clicking with or on a bundle puts the other item into it, or takes the last inserted
+/// item out of it when the other side is empty. Returns whether the click was handled.
+pub fn handle_bundle_click(
+    carried_slot: &mut Option<ItemStack>,
+    current_slot: &mut Option<ItemStack>,
+) -> bool {
+    let (bundle_slot, other) = if carried_slot.as_ref().is_some_and(ItemStack::is_bundle) {
+        (carried_slot, current_slot)
+    } else if current_slot.as_ref().is_some_and(ItemStack::is_bundle) {
+        (current_slot, carried_slot)
+    } else {
+        return false;
+    };
+    let Some(bundle) = bundle_slot.as_mut() else {
+        return false;
+    };
+    match other {
+        Some(stack) => {
+            if !stack.fits_in_bundle() {
+                return false;
+            }
+            bundle.bundle_insert(stack);
+            if stack.is_empty() {
+                *other = None;
+            }
+        }
+        None => *other = bundle.bundle_remove_last(),
+    }
+    true
+}
+
 pub fn handle_item_change(
     carried_slot: &mut Option<ItemStack>,
     current_slot: &mut Option<ItemStack>,
@@ -164,10 +211,7 @@ pub fn handle_item_change(
                 std::mem::swap(carried_slot, current_slot);
             }
             MouseClick::Right => {
-                let new_stack = ItemStack {
-                    item: carried_item_stack.item.clone(),
-                    item_count: 1,
-                };
+                let new_stack = carried_item_stack.copy_with_count(1);
                 *current_slot = Some(new_stack);
                 carried_item_stack.item_count -= 1;
                 if carried_item_stack.item_count == 0 {
@@ -270,6 +314,15 @@ impl<'a> Container for OptionallyCombinedContainer<'a, 'a> {
         }
     }
 
+    fn accepts_item(&self, slot: usize, item: &ItemStack) -> bool {
+        match &self.container {
+            Some(container) if slot < container.all_slots_ref().len() => {
+                container.accepts_item(slot, item)
+            }
+            _ => true,
+        }
+    }
+
     fn craft(&mut self) -> bool {
         match &mut self.container {
             Some(container) => container.craft(),
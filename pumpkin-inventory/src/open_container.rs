@@ -1,8 +1,12 @@
 use crate::Container;
+use crate::crafting::special_recipe_result;
 use pumpkin_data::Block;
+use pumpkin_data::item::Item;
 use pumpkin_data::screen::WindowType;
+use pumpkin_data::tag::Tagable;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_world::item::ItemStack;
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -13,6 +17,9 @@ pub struct OpenContainer {
     container: Arc<Mutex<Box<dyn Container>>>,
     location: Option<BlockPos>,
     block: Option<Block>,
+    /// Whether the items only live in this container, like for chests, so it has to be kept
+    /// while it holds any. Ender chests and shulker boxes keep their items elsewhere.
+    owns_contents: bool,
 }
 
 impl OpenContainer {
@@ -49,9 +56,31 @@ impl OpenContainer {
             container: Arc::new(Mutex::new(Box::new(C::default()))),
             location,
             block,
+            owns_contents: true,
         }
     }
 
+    /// Opens an already existing container, used for storage that lives outside of the block like ender chests.
+    pub fn with_container(
+        player_id: i32,
+        location: Option<BlockPos>,
+        block: Option<Block>,
+        container: Arc<Mutex<Box<dyn Container>>>,
+    ) -> Self {
+        Self {
+            players: vec![player_id],
+            container,
+            location,
+            block,
+            owns_contents: false,
+        }
+    }
+
+    /// Whether this is a view of the given container.
+    pub fn holds(&self, container: &Arc<Mutex<Box<dyn Container>>>) -> bool {
+        Arc::ptr_eq(&self.container, container)
+    }
+
     pub fn is_location(&self, try_position: BlockPos) -> bool {
         if let Some(location) = self.location {
             location == try_position
@@ -87,6 +116,37 @@ impl OpenContainer {
     pub fn get_block(&self) -> Option<Block> {
         self.block.clone()
     }
+
+    /// Whether nobody views the container anymore and dropping it loses no items.
+    pub async fn is_unused(&self) -> bool {
+        self.players.is_empty()
+            && (!self.owns_contents
+                || self
+                    .container
+                    .lock()
+                    .await
+                    .all_slots_ref()
+                    .iter()
+                    .all(Option::is_none))
+    }
+}
+
+/// Removes the player from the viewers of the container with the given id, dropping the container
+/// once it is unused. Returns whether it was dropped.
+pub async fn leave_container(
+    open_containers: &mut HashMap<u64, OpenContainer>,
+    id: u64,
+    player_id: i32,
+) -> bool {
+    let Some(container) = open_containers.get_mut(&id) else {
+        return false;
+    };
+    container.remove_player(player_id);
+    if !container.is_unused().await {
+        return false;
+    }
+    open_containers.remove(&id);
+    true
 }
 #[derive(Default)]
 pub struct ChestContainer([Option<ItemStack>; 27]);
@@ -113,6 +173,76 @@ impl Container for ChestContainer {
     }
 }
 
+#[derive(Default)]
+pub struct EnderChestContainer([Option<ItemStack>; 27]);
+
+impl EnderChestContainer {
+    pub fn new() -> Self {
+        Self([const { None }; 27])
+    }
+}
+
+impl Container for EnderChestContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Generic9x3
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Ender Chest"
+    }
+    fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]> {
+        self.0.iter_mut().collect()
+    }
+
+    fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+}
+
+#[derive(Default)]
+pub struct ShulkerBoxContainer([Option<ItemStack>; 27]);
+
+impl ShulkerBoxContainer {
+    pub fn new() -> Self {
+        Self([const { None }; 27])
+    }
+
+    /// Creates the container from the contents of a shulker box, empty stacks become empty slots.
+    pub fn from_items(items: [ItemStack; 27]) -> Self {
+        Self(items.map(|stack| (!stack.is_empty()).then_some(stack)))
+    }
+
+    pub fn items(&self) -> [ItemStack; 27] {
+        self.0.clone().map(|slot| slot.unwrap_or(ItemStack::EMPTY))
+    }
+
+    pub fn is_shulker_box(item: &Item) -> bool {
+        item.is_tagged_with("#minecraft:shulker_boxes")
+            .unwrap_or(false)
+    }
+}
+
+impl Container for ShulkerBoxContainer {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::ShulkerBox
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Shulker Box"
+    }
+    fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]> {
+        self.0.iter_mut().collect()
+    }
+
+    fn accepts_item(&self, _slot: usize, item: &ItemStack) -> bool {
+        !Self::is_shulker_box(&item.item)
+    }
+
+    fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]> {
+        self.0.iter().map(|slot| slot.as_ref()).collect()
+    }
+}
+
 #[derive(Default)]
 pub struct CraftingTable {
     input: [[Option<ItemStack>; 3]; 3],
@@ -160,26 +290,10 @@ impl Container for CraftingTable {
     }
 
     fn craft(&mut self) -> bool {
-        // TODO: Is there a better way to do this?
-        let _check = [
-            [
-                self.input[0][0].as_ref(),
-                self.input[0][1].as_ref(),
-                self.input[0][2].as_ref(),
-            ],
-            [
-                self.input[1][0].as_ref(),
-                self.input[1][1].as_ref(),
-                self.input[1][2].as_ref(),
-            ],
-            [
-                self.input[2][0].as_ref(),
-                self.input[2][1].as_ref(),
-                self.input[2][2].as_ref(),
-            ],
-        ];
-
-        let new_output = None; //check_if_matches_crafting(check);
+        let check: Vec<_> = self.input.iter().flatten().map(|i| i.as_ref()).collect();
+
+        // TODO: Shaped and shapeless recipes
+        let new_output = special_recipe_result(&check);
         let result = new_output != self.output
             || self.input.iter().flatten().any(|s| s.is_some())
             || new_output.is_some();
@@ -231,3 +345,119 @@ impl Container for Furnace {
         Box::new([self.cook.as_ref(), self.fuel.as_ref(), self.output.as_ref()])
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use pumpkin_data::Block;
+    use pumpkin_data::item::Item;
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_world::item::ItemStack;
+    use tokio::sync::Mutex;
+
+    use std::collections::HashMap;
+
+    use super::{
+        ChestContainer, EnderChestContainer, OpenContainer, ShulkerBoxContainer, leave_container,
+    };
+    use crate::Container;
+    use crate::container_click::MouseClick;
+
+    #[tokio::test]
+    async fn ender_chest_is_shared_between_locations() {
+        let ender_chest: Arc<Mutex<Box<dyn Container>>> =
+            Arc::new(Mutex::new(Box::new(EnderChestContainer::new())));
+        let first = OpenContainer::with_container(
+            1,
+            Some(BlockPos::new(0, 64, 0)),
+            None,
+            ender_chest.clone(),
+        );
+        let mut carried = Some(ItemStack::new(5, Item::DIAMOND));
+        first
+            .try_open(1)
+            .unwrap()
+            .lock()
+            .await
+            .handle_item_change(&mut carried, 3, MouseClick::Left, false)
+            .unwrap();
+        assert!(carried.is_none());
+
+        let second = OpenContainer::with_container(
+            1,
+            Some(BlockPos::new(100, 64, 100)),
+            None,
+            ender_chest.clone(),
+        );
+        assert!(second.holds(&ender_chest));
+        let container = second.try_open(1).unwrap().lock().await;
+        let stack = container.all_slots_ref()[3].unwrap();
+        assert_eq!(stack.item.id, Item::DIAMOND.id);
+        assert_eq!(stack.item_count, 5);
+    }
+
+    #[test]
+    fn shulker_box_rejects_shulker_boxes() {
+        let mut container = ShulkerBoxContainer::new();
+        let mut carried = Some(ItemStack::new(1, Item::RED_SHULKER_BOX));
+        container
+            .handle_item_change(&mut carried, 0, MouseClick::Left, false)
+            .unwrap();
+        assert!(carried.is_some());
+        assert!(container.all_slots_ref()[0].is_none());
+
+        let mut carried = Some(ItemStack::new(1, Item::DIAMOND));
+        container
+            .handle_item_change(&mut carried, 0, MouseClick::Left, false)
+            .unwrap();
+        assert!(carried.is_none());
+        assert_eq!(container.items()[0].item.id, Item::DIAMOND.id);
+    }
+
+    #[tokio::test]
+    async fn leaving_drops_containers_without_viewers() {
+        let chest = BlockPos::new(4, 64, 4);
+        let mut open_containers = HashMap::new();
+        let mut container = OpenContainer::new_empty_container::<ChestContainer>(
+            1,
+            Some(chest),
+            Some(Block::CHEST),
+        );
+        container.add_player(2);
+        open_containers.insert(7, container);
+
+        // One of the viewers disconnects, the other one still looks inside
+        assert!(!leave_container(&mut open_containers, 7, 1).await);
+        assert_eq!(open_containers[&7].all_player_ids(), vec![2]);
+
+        // A chest with items is kept around, it is the only place they are stored
+        let mut carried = Some(ItemStack::new(1, Item::DIAMOND));
+        open_containers[&7]
+            .try_open(2)
+            .unwrap()
+            .lock()
+            .await
+            .handle_item_change(&mut carried, 0, MouseClick::Left, false)
+            .unwrap();
+        assert!(!leave_container(&mut open_containers, 7, 2).await);
+        assert_eq!(open_containers[&7].get_number_of_players(), 0);
+
+        // An empty one is dropped once the last viewer leaves
+        open_containers.get_mut(&7).unwrap().add_player(2);
+        open_containers[&7].clear_all_slots().await;
+        assert!(leave_container(&mut open_containers, 7, 2).await);
+        assert!(open_containers.is_empty());
+
+        // Ender chests keep their items with the player
+        let ender_chest: Arc<Mutex<Box<dyn Container>>> =
+            Arc::new(Mutex::new(Box::new(EnderChestContainer::new())));
+        *ender_chest.lock().await.all_slots()[0] = Some(ItemStack::new(1, Item::DIAMOND));
+        open_containers.insert(
+            8,
+            OpenContainer::with_container(1, Some(chest), Some(Block::ENDER_CHEST), ender_chest),
+        );
+        assert!(leave_container(&mut open_containers, 8, 1).await);
+        assert!(open_containers.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::container_click::MouseClick;
+use crate::crafting::special_recipe_result;
 use crate::{Container, InventoryError, WindowType, handle_item_change};
 use pumpkin_data::item::Item;
 use pumpkin_world::item::ItemStack;
@@ -249,6 +250,46 @@ impl PlayerInventory {
         self.get_slot_filtered(&|item_stack| item_stack.is_none())
     }
 
+    /// Puts the stack into the inventory, filling up stacks of the same item first. Returns what
+    /// didn't fit.
+    pub fn insert_stack(&mut self, stack: ItemStack) -> Option<ItemStack> {
+        self.insert_stack_tracked(stack).0
+    }
+
+    /// Like [`Self::insert_stack`], but also returns the slots which changed, so they can be sent
+    /// to the client.
+    pub fn insert_stack_tracked(
+        &mut self,
+        mut stack: ItemStack,
+    ) -> (Option<ItemStack>, Vec<usize>) {
+        let max_stack = stack.get_max_stack_size();
+        let mut changed_slots = Vec::new();
+        while stack.item_count > 0 {
+            let Some(slot) = self
+                .get_slot_filtered(&|item_stack| {
+                    item_stack.is_some_and(|item_stack| {
+                        item_stack.are_items_and_components_equal(&stack)
+                            && item_stack.item_count < max_stack
+                    })
+                })
+                // Only stacks of the same item go into the offhand
+                .or_else(|| self.get_empty_slot().filter(|slot| *slot != SLOT_OFFHAND))
+            else {
+                break;
+            };
+            let item_stack = self.get_slot(slot).expect("We just got this slot");
+            if let Some(item_stack) = item_stack {
+                let moved = (max_stack - item_stack.item_count).min(stack.item_count);
+                item_stack.increment(moved);
+                stack.decrement(moved);
+            } else {
+                *item_stack = Some(stack.split(max_stack));
+            }
+            changed_slots.push(slot);
+        }
+        ((stack.item_count > 0).then_some(stack), changed_slots)
+    }
+
     pub fn get_empty_slot_no_order(&self) -> Option<usize> {
         self.items
             .iter()
@@ -362,12 +403,10 @@ impl Container for PlayerInventory {
     }
 
     fn craft(&mut self) -> bool {
-        let v1 = [self.crafting[0].as_ref(), self.crafting[1].as_ref(), None];
-        let v2 = [self.crafting[2].as_ref(), self.crafting[3].as_ref(), None];
-        let v3 = [const { None }; 3];
-        let _together = [v1, v2, v3];
+        let check: Vec<_> = self.crafting.iter().map(|s| s.as_ref()).collect();
 
-        self.crafting_output = None; //check_if_matches_crafting(together);
+        // TODO: Shaped and shapeless recipes
+        self.crafting_output = special_recipe_result(&check);
         self.crafting.iter().any(|s| s.is_some())
     }
 
@@ -378,4 +417,65 @@ impl Container for PlayerInventory {
     fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
         (SLOT_CRAFT_INPUT_START..=SLOT_CRAFT_INPUT_END).contains(slot)
     }
+
+    fn recipe_used(&mut self) {
+        for slot in &mut self.crafting {
+            if let Some(item) = slot {
+                item.decrement(1);
+                if item.is_empty() {
+                    *slot = None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_world::item::ItemStack;
+
+    use super::{PlayerInventory, SLOT_HOTBAR_START, SLOT_INV_END, SLOT_INV_START};
+
+    #[test]
+    fn insert_stack_fills_partial_stacks_and_returns_overflow() {
+        let mut inventory = PlayerInventory::new();
+        let selected = inventory.get_selected_slot();
+        inventory
+            .set_slot(selected, Some(ItemStack::new(60, Item::STONE)), false)
+            .unwrap();
+        inventory
+            .set_slot(SLOT_INV_START, Some(ItemStack::new(10, Item::STONE)), false)
+            .unwrap();
+
+        let (leftover, changed_slots) =
+            inventory.insert_stack_tracked(ItemStack::new(20, Item::STONE));
+        assert!(leftover.is_none());
+        assert_eq!(changed_slots, [selected, SLOT_INV_START]);
+        let count = |inventory: &mut PlayerInventory, slot| {
+            inventory
+                .get_slot(slot)
+                .unwrap()
+                .as_ref()
+                .map_or(0, |stack| stack.item_count)
+        };
+        assert_eq!(count(&mut inventory, selected), 64);
+        assert_eq!(count(&mut inventory, SLOT_INV_START), 26);
+
+        // Fill everything else up, so only 38 more stone fit
+        for slot in SLOT_INV_START + 1..=SLOT_INV_END {
+            inventory
+                .set_slot(slot, Some(ItemStack::new(64, Item::DIRT)), false)
+                .unwrap();
+        }
+        for slot in SLOT_HOTBAR_START + 1..SLOT_HOTBAR_START + 9 {
+            inventory
+                .set_slot(slot, Some(ItemStack::new(64, Item::DIRT)), false)
+                .unwrap();
+        }
+        let leftover = inventory.insert_stack(ItemStack::new(50, Item::STONE));
+        assert_eq!(leftover.map(|stack| stack.item_count), Some(12));
+        assert_eq!(count(&mut inventory, SLOT_INV_START), 64);
+        assert!(inventory.offhand_slot().is_none());
+    }
 }
@@ -0,0 +1,439 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+
+use pumpkin_data::item::Item;
+use pumpkin_data::screen::WindowType;
+use pumpkin_data::tag::Tagable;
+use pumpkin_world::item::ItemStack;
+use pumpkin_world::item::component::ArmorTrim;
+use pumpkin_world::item::registry::DataRegistry;
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::Container;
+
+#[derive(Deserialize)]
+struct RecipeResult {
+    id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+enum SmithingRecipe {
+    /// Turns the base into another item, keeping its components (e.g. netherite upgrades)
+    #[serde(rename = "minecraft:smithing_transform")]
+    Transform {
+        template: String,
+        base: String,
+        addition: String,
+        result: RecipeResult,
+    },
+    /// Adds a trim to armor, the addition decides the trim material
+    #[serde(rename = "minecraft:smithing_trim")]
+    Trim {
+        template: String,
+        base: String,
+        addition: String,
+        pattern: String,
+    },
+    #[serde(other)]
+    Other,
+}
+
+/// The built-in recipes, followed by the ones loaded by [`reload_smithing_recipes`].
+static SMITHING_RECIPES: LazyLock<RwLock<Vec<SmithingRecipe>>> =
+    LazyLock::new(|| RwLock::new(builtin_recipes()));
+
+fn builtin_recipes() -> Vec<SmithingRecipe> {
+    let recipes: Vec<SmithingRecipe> =
+        serde_json::from_str(include_str!("../../assets/recipes.json"))
+            .expect("Could not parse recipes.json");
+    recipes
+        .into_iter()
+        .filter(|recipe| !matches!(recipe, SmithingRecipe::Other))
+        .collect()
+}
+
+#[derive(Debug, Error)]
+pub enum RecipeLoadError {
+    #[error("couldn't read {0:?}: {1}")]
+    Io(PathBuf, io::Error),
+    #[error("couldn't parse {0:?}: {1}")]
+    Parse(PathBuf, serde_json::Error),
+    #[error("{0:?} refers to the unknown item or trim pattern {1}")]
+    UnknownEntry(PathBuf, String),
+}
+
+/// Checks that every item a recipe names exists, tags are resolved when matching.
+fn validate_recipe(path: &Path, recipe: &SmithingRecipe) -> Result<(), RecipeLoadError> {
+    let unknown = |entry: &str| RecipeLoadError::UnknownEntry(path.to_path_buf(), entry.into());
+    let item_exists = |ingredient: &str| {
+        ingredient.starts_with('#')
+            || Item::from_registry_key(ingredient.strip_prefix("minecraft:").unwrap_or(ingredient))
+                .is_some()
+    };
+    let (template, base, addition) = match recipe {
+        SmithingRecipe::Transform {
+            template,
+            base,
+            addition,
+            result,
+        } => {
+            if !item_exists(&result.id) || result.id.starts_with('#') {
+                return Err(unknown(&result.id));
+            }
+            (template, base, addition)
+        }
+        SmithingRecipe::Trim {
+            template,
+            base,
+            addition,
+            pattern,
+        } => {
+            if !DataRegistry::TrimPattern.contains(pattern) {
+                return Err(unknown(pattern));
+            }
+            (template, base, addition)
+        }
+        SmithingRecipe::Other => return Ok(()),
+    };
+    for ingredient in [template, base, addition] {
+        if !item_exists(ingredient) {
+            return Err(unknown(ingredient));
+        }
+    }
+    Ok(())
+}
+
+/// Reads the smithing recipes in the `*.json` files of `dir`, one recipe per file, and replaces
+/// the loaded recipes with them and the built-in ones. Recipes from `dir` are matched first, so
+/// they take precedence. If any file is invalid, the loaded recipes stay untouched.
+/// A missing directory means there are no extra recipes. Returns how many recipes are loaded.
+pub fn reload_smithing_recipes(dir: &Path) -> Result<usize, RecipeLoadError> {
+    let mut files = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| RecipeLoadError::Io(dir.to_path_buf(), err))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+        Err(err) => return Err(RecipeLoadError::Io(dir.to_path_buf(), err)),
+    };
+    files.retain(|path| {
+        path.extension()
+            .is_some_and(|extension| extension == "json")
+    });
+    files.sort();
+
+    let mut recipes = Vec::new();
+    for path in files {
+        let content =
+            fs::read_to_string(&path).map_err(|err| RecipeLoadError::Io(path.clone(), err))?;
+        let recipe: SmithingRecipe = serde_json::from_str(&content)
+            .map_err(|err| RecipeLoadError::Parse(path.clone(), err))?;
+        validate_recipe(&path, &recipe)?;
+        // Other recipe types are valid, but only smithing recipes can be used yet
+        if !matches!(recipe, SmithingRecipe::Other) {
+            recipes.push(recipe);
+        }
+    }
+    recipes.extend(builtin_recipes());
+
+    let count = recipes.len();
+    *SMITHING_RECIPES
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = recipes;
+    Ok(count)
+}
+
+/// Whether the item matches a recipe ingredient, which is either an item or an item tag.
+fn ingredient_matches(ingredient: &str, item: &Item) -> bool {
+    if ingredient.starts_with('#') {
+        item.is_tagged_with(ingredient).unwrap_or(false)
+    } else {
+        ingredient.strip_prefix("minecraft:").unwrap_or(ingredient) == item.registry_key
+    }
+}
+
+/// The item crafted from the given smithing table inputs, if any recipe matches.
+pub fn smithing_result(
+    template: &ItemStack,
+    base: &ItemStack,
+    addition: &ItemStack,
+) -> Option<ItemStack> {
+    if template.is_empty() || base.is_empty() || addition.is_empty() {
+        return None;
+    }
+    let recipes = SMITHING_RECIPES
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    recipes.iter().find_map(|recipe| match recipe {
+        SmithingRecipe::Transform {
+            template: template_ingredient,
+            base: base_ingredient,
+            addition: addition_ingredient,
+            result,
+        } => {
+            if !ingredient_matches(template_ingredient, &template.item)
+                || !ingredient_matches(base_ingredient, &base.item)
+                || !ingredient_matches(addition_ingredient, &addition.item)
+            {
+                return None;
+            }
+            let item = Item::from_registry_key(
+                result.id.strip_prefix("minecraft:").unwrap_or(&result.id),
+            )?;
+            // Enchantments, damage and trims carry over to the upgraded item
+            let mut upgraded = base.copy_with_count(1);
+            upgraded.item = item;
+            Some(upgraded)
+        }
+        SmithingRecipe::Trim {
+            template: template_ingredient,
+            base: base_ingredient,
+            addition: addition_ingredient,
+            pattern,
+        } => {
+            if !ingredient_matches(template_ingredient, &template.item)
+                || !ingredient_matches(base_ingredient, &base.item)
+                || !ingredient_matches(addition_ingredient, &addition.item)
+            {
+                return None;
+            }
+            let material = addition.item.components.provides_trim_material?;
+            if !DataRegistry::TrimMaterial.contains(material)
+                || !DataRegistry::TrimPattern.contains(pattern)
+            {
+                return None;
+            }
+            let trim = ArmorTrim {
+                material: material
+                    .strip_prefix("minecraft:")
+                    .unwrap_or(material)
+                    .to_string(),
+                pattern: pattern
+                    .strip_prefix("minecraft:")
+                    .unwrap_or(pattern)
+                    .to_string(),
+            };
+            // Applying the same trim again would only waste the inputs
+            if base.patch.trim.as_ref() == Some(&trim) {
+                return None;
+            }
+            let mut trimmed = base.copy_with_count(1);
+            trimmed.patch.trim = Some(trim);
+            Some(trimmed)
+        }
+        SmithingRecipe::Other => None,
+    })
+}
+
+#[derive(Default)]
+pub struct SmithingTable {
+    template: Option<ItemStack>,
+    base: Option<ItemStack>,
+    addition: Option<ItemStack>,
+    output: Option<ItemStack>,
+}
+
+impl SmithingTable {
+    const SLOT_TEMPLATE: usize = 0;
+    const SLOT_ADDITION: usize = 2;
+    const SLOT_OUTPUT: usize = 3;
+}
+
+impl Container for SmithingTable {
+    fn window_type(&self) -> &'static WindowType {
+        &WindowType::Smithing
+    }
+
+    fn window_name(&self) -> &'static str {
+        "Smithing Table"
+    }
+
+    fn all_slots(&mut self) -> Box<[&mut Option<ItemStack>]> {
+        Box::new([
+            &mut self.template,
+            &mut self.base,
+            &mut self.addition,
+            &mut self.output,
+        ])
+    }
+
+    fn all_slots_ref(&self) -> Box<[Option<&ItemStack>]> {
+        Box::new([
+            self.template.as_ref(),
+            self.base.as_ref(),
+            self.addition.as_ref(),
+            self.output.as_ref(),
+        ])
+    }
+
+    fn all_combinable_slots(&self) -> Box<[Option<&ItemStack>]> {
+        Box::new([
+            self.template.as_ref(),
+            self.base.as_ref(),
+            self.addition.as_ref(),
+        ])
+    }
+
+    fn all_combinable_slots_mut(&mut self) -> Box<[&mut Option<ItemStack>]> {
+        Box::new([&mut self.template, &mut self.base, &mut self.addition])
+    }
+
+    fn craft(&mut self) -> bool {
+        let new_output = match (&self.template, &self.base, &self.addition) {
+            (Some(template), Some(base), Some(addition)) => {
+                smithing_result(template, base, addition)
+            }
+            _ => None,
+        };
+        let changed = !match (&new_output, &self.output) {
+            (Some(new), Some(old)) => {
+                new.are_items_and_components_equal(old) && new.item_count == old.item_count
+            }
+            (None, None) => true,
+            _ => false,
+        };
+        self.output = new_output;
+        changed
+    }
+
+    fn crafting_output_slot(&self) -> Option<usize> {
+        Some(Self::SLOT_OUTPUT)
+    }
+
+    fn slot_in_crafting_input_slots(&self, slot: &usize) -> bool {
+        (Self::SLOT_TEMPLATE..=Self::SLOT_ADDITION).contains(slot)
+    }
+
+    fn recipe_used(&mut self) {
+        for slot in [&mut self.template, &mut self.base, &mut self.addition] {
+            if let Some(item) = slot {
+                if item.item_count > 1 {
+                    item.item_count -= 1;
+                } else {
+                    *slot = None;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_world::item::ItemStack;
+    use pumpkin_world::item::component::ArmorTrim;
+
+    use super::{RecipeLoadError, SmithingTable, reload_smithing_recipes, smithing_result};
+    use crate::Container;
+
+    #[test]
+    fn netherite_upgrade_keeps_enchantments() {
+        let mut chestplate = ItemStack::new(1, Item::DIAMOND_CHESTPLATE);
+        chestplate.patch.enchantments =
+            vec![("protection".to_string(), 4), ("unbreaking".to_string(), 3)];
+        chestplate.patch.damage = Some(100);
+
+        let mut table = SmithingTable {
+            template: Some(ItemStack::new(2, Item::NETHERITE_UPGRADE_SMITHING_TEMPLATE)),
+            base: Some(chestplate.clone()),
+            addition: Some(ItemStack::new(1, Item::NETHERITE_INGOT)),
+            ..Default::default()
+        };
+        assert!(table.craft());
+
+        let upgraded = table.crafted_item_slot().unwrap();
+        assert_eq!(upgraded.item.id, Item::NETHERITE_CHESTPLATE.id);
+        assert_eq!(upgraded.patch, chestplate.patch);
+        assert_eq!(
+            upgraded.patch.enchantment_level("minecraft:protection"),
+            Some(4)
+        );
+
+        // Taking the result uses up one of each input
+        table.output = None;
+        table.recipe_used();
+        assert_eq!(table.template.as_ref().unwrap().item_count, 1);
+        assert!(table.base.is_none());
+        assert!(table.addition.is_none());
+        assert!(!table.craft());
+    }
+
+    #[test]
+    fn trims_need_a_trim_material() {
+        let template = ItemStack::new(1, Item::COAST_ARMOR_TRIM_SMITHING_TEMPLATE);
+        let helmet = ItemStack::new(1, Item::IRON_HELMET);
+
+        let trimmed =
+            smithing_result(&template, &helmet, &ItemStack::new(1, Item::GOLD_INGOT)).unwrap();
+        assert_eq!(
+            trimmed.patch.trim,
+            Some(ArmorTrim {
+                material: "gold".to_string(),
+                pattern: "coast".to_string(),
+            })
+        );
+
+        assert!(smithing_result(&template, &helmet, &ItemStack::new(1, Item::STONE)).is_none());
+        assert!(
+            smithing_result(
+                &template,
+                &ItemStack::new(1, Item::DIAMOND_SWORD),
+                &ItemStack::new(1, Item::GOLD_INGOT)
+            )
+            .is_none()
+        );
+    }
+
+    #[test]
+    fn reloaded_recipes_are_craftable() {
+        let dir = temp_dir::TempDir::new().unwrap();
+        let template = ItemStack::new(1, Item::NETHERITE_UPGRADE_SMITHING_TEMPLATE);
+        let base = ItemStack::new(1, Item::IRON_SWORD);
+        let addition = ItemStack::new(1, Item::DIAMOND);
+        assert!(smithing_result(&template, &base, &addition).is_none());
+
+        let recipe = dir.path().join("iron_to_diamond_sword.json");
+        let write_recipe = |result: &str| {
+            std::fs::write(
+                &recipe,
+                format!(
+                    r#"{{
+                        "type": "minecraft:smithing_transform",
+                        "template": "minecraft:netherite_upgrade_smithing_template",
+                        "base": "minecraft:iron_sword",
+                        "addition": "minecraft:diamond",
+                        "result": {{ "id": "{result}" }}
+                    }}"#
+                ),
+            )
+            .unwrap();
+        };
+
+        write_recipe("minecraft:diamond_sword");
+        reload_smithing_recipes(dir.path()).unwrap();
+        let crafted = smithing_result(&template, &base, &addition).unwrap();
+        assert_eq!(crafted.item.id, Item::DIAMOND_SWORD.id);
+
+        // A broken recipe is reported and the previous recipes stay in use
+        write_recipe("minecraft:not_an_item");
+        assert!(matches!(
+            reload_smithing_recipes(dir.path()),
+            Err(RecipeLoadError::UnknownEntry(_, _))
+        ));
+        let crafted = smithing_result(&template, &base, &addition).unwrap();
+        assert_eq!(crafted.item.id, Item::DIAMOND_SWORD.id);
+
+        write_recipe("minecraft:golden_sword");
+        reload_smithing_recipes(dir.path()).unwrap();
+        let crafted = smithing_result(&template, &base, &addition).unwrap();
+        assert_eq!(crafted.item.id, Item::GOLDEN_SWORD.id);
+
+        std::fs::remove_file(&recipe).unwrap();
+        reload_smithing_recipes(dir.path()).unwrap();
+        assert!(smithing_result(&template, &base, &addition).is_none());
+    }
+}
@@ -159,13 +159,27 @@ pub fn packet(input: TokenStream, item: TokenStream) -> TokenStream {
     let name = &ast.ident;
     let (impl_generics, ty_generics, _) = ast.generics.split_for_impl();
 
-    let input: proc_macro2::TokenStream = input.into();
+    // `#[packet(ID)]` or `#[packet(ID, incompressible)]`
+    let args = syn::punctuated::Punctuated::<Expr, syn::Token![,]>::parse_terminated
+        .parse(input)
+        .unwrap();
+    let mut args = args.into_iter();
+    let id = args.next().expect("Packets need an id");
+    let compression = match args.next() {
+        None => quote! {},
+        Some(Expr::Path(path)) if path.path.is_ident("incompressible") => quote! {
+            const COMPRESSION: crate::CompressionPolicy = crate::CompressionPolicy::Never;
+        },
+        Some(_) => panic!("Unknown packet option, expected `incompressible`"),
+    };
+
     let item: proc_macro2::TokenStream = item.into();
 
     let code = quote! {
         #item
         impl #impl_generics crate::ser::packet::Packet for #name #ty_generics {
-            const PACKET_ID: i32 = #input;
+            const PACKET_ID: i32 = #id;
+            #compression
         }
     };
 
@@ -19,6 +19,17 @@ impl NbtCompound {
         }
     }
 
+    /// The bytes this compound allocated on the heap, including all nested tags. This is an
+    /// estimate, allocator overhead isn't known.
+    pub fn heap_size(&self) -> usize {
+        self.child_tags.capacity() * size_of::<(String, NbtTag)>()
+            + self
+                .child_tags
+                .iter()
+                .map(|(name, tag)| name.capacity() + tag.heap_size())
+                .sum::<usize>()
+    }
+
     pub fn skip_content<R>(reader: &mut NbtReadHelper<R>) -> Result<(), Error>
     where
         R: Read,
@@ -293,3 +304,37 @@ impl From<NbtCompound> for NbtTag {
         NbtTag::Compound(value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::tag::NbtTag;
+
+    use super::NbtCompound;
+
+    #[test]
+    fn heap_size_grows_with_tags() {
+        let entry = size_of::<(String, NbtTag)>();
+        let mut compound = NbtCompound {
+            child_tags: Vec::with_capacity(4),
+        };
+        assert_eq!(compound.heap_size(), 4 * entry);
+
+        // Numbers live inline, only the name is allocated
+        compound.put_int("int", 1);
+        assert_eq!(compound.heap_size(), 4 * entry + 3);
+
+        compound.put_string("name", "a".repeat(100));
+        assert_eq!(compound.heap_size(), 4 * entry + 3 + 4 + 100);
+
+        compound.put("longs", NbtTag::LongArray(vec![0; 10].into()));
+        assert_eq!(compound.heap_size(), 4 * entry + 3 + 4 + 100 + 5 + 80);
+
+        // Nested compounds and lists count everything they hold
+        let size = compound.heap_size();
+        let mut outer = NbtCompound {
+            child_tags: Vec::with_capacity(1),
+        };
+        outer.put_list("list", vec![NbtTag::Compound(compound)].into_boxed_slice());
+        assert_eq!(outer.heap_size(), entry + 4 + size_of::<NbtTag>() + size);
+    }
+}
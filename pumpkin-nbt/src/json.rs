@@ -0,0 +1,253 @@
+//! Lossless conversion between NBT and JSON.
+//!
+//! JSON has a single number type, so only the most common NBT types map to plain JSON values:
+//!
+//! | NBT                     | JSON                                              |
+//! |-------------------------|---------------------------------------------------|
+//! | `Int`                   | number, e.g. `3`                                  |
+//! | `Double`                | number with a fraction, e.g. `3.0`                |
+//! | `String`                | string                                            |
+//! | `List`                  | array                                             |
+//! | `Compound`              | object                                            |
+//! | `End`                   | `null`                                            |
+//! | `Byte`/`Short`/`Long`   | `{"$type": "byte", "value": 3}`                   |
+//! | `Float`                 | `{"$type": "float", "value": 3.0}`                |
+//! | `ByteArray`             | `{"$type": "byte_array", "value": [-1, 2]}`       |
+//! | `IntArray`/`LongArray`  | `{"$type": "int_array", "value": [1, 2]}`         |
+//!
+//! Non-finite floats and doubles are tagged with their value as a string, e.g.
+//! `{"$type": "double", "value": "NaN"}`. A compound which has a `$type` key itself is wrapped as
+//! `{"$type": "compound", "value": {...}}` so it can't be mistaken for a tagged value.
+//!
+//! Compound keys are written in alphabetical order. When reading JSON, plain integers which don't
+//! fit into an `Int` become a `Long` and booleans become a `Byte`.
+
+use serde_json::{Map, Number, Value};
+
+use crate::{Error, compound::NbtCompound, tag::NbtTag};
+
+const TYPE_KEY: &str = "$type";
+const VALUE_KEY: &str = "value";
+
+fn tagged(ty: &str, value: Value) -> Value {
+    let mut object = Map::new();
+    object.insert(TYPE_KEY.to_string(), Value::String(ty.to_string()));
+    object.insert(VALUE_KEY.to_string(), value);
+    Value::Object(object)
+}
+
+fn float_value(float: f64) -> Value {
+    Number::from_f64(float).map_or_else(|| Value::String(float.to_string()), Value::Number)
+}
+
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidJson(message.into())
+}
+
+fn as_i64(value: &Value) -> Result<i64, Error> {
+    value
+        .as_i64()
+        .ok_or_else(|| invalid(format!("expected an integer, found {value}")))
+}
+
+fn as_int<T: TryFrom<i64>>(value: &Value) -> Result<T, Error> {
+    let int = as_i64(value)?;
+    T::try_from(int).map_err(|_| invalid(format!("{int} is out of range")))
+}
+
+fn as_f64(value: &Value) -> Result<f64, Error> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(string) => string.parse().ok(),
+        _ => None,
+    }
+    .ok_or_else(|| invalid(format!("expected a number, found {value}")))
+}
+
+fn as_array<T>(
+    value: &Value,
+    convert: impl Fn(&Value) -> Result<T, Error>,
+) -> Result<Box<[T]>, Error> {
+    value
+        .as_array()
+        .ok_or_else(|| invalid(format!("expected an array, found {value}")))?
+        .iter()
+        .map(convert)
+        .collect()
+}
+
+impl NbtTag {
+    pub fn to_json(&self) -> Value {
+        match self {
+            NbtTag::End => Value::Null,
+            NbtTag::Byte(byte) => tagged("byte", (*byte).into()),
+            NbtTag::Short(short) => tagged("short", (*short).into()),
+            NbtTag::Int(int) => (*int).into(),
+            NbtTag::Long(long) => tagged("long", (*long).into()),
+            NbtTag::Float(float) => tagged("float", float_value((*float).into())),
+            NbtTag::Double(double) => match Number::from_f64(*double) {
+                Some(number) => Value::Number(number),
+                None => tagged("double", float_value(*double)),
+            },
+            NbtTag::ByteArray(bytes) => tagged(
+                "byte_array",
+                bytes.iter().map(|byte| Value::from(*byte as i8)).collect(),
+            ),
+            NbtTag::String(string) => Value::String(string.clone()),
+            NbtTag::List(list) => Value::Array(list.iter().map(NbtTag::to_json).collect()),
+            NbtTag::Compound(compound) => compound.to_json(),
+            NbtTag::IntArray(ints) => tagged("int_array", ints.iter().copied().collect()),
+            NbtTag::LongArray(longs) => tagged("long_array", longs.iter().copied().collect()),
+        }
+    }
+
+    pub fn from_json(value: &Value) -> Result<NbtTag, Error> {
+        Ok(match value {
+            Value::Null => NbtTag::End,
+            Value::Bool(bool) => NbtTag::Byte(i8::from(*bool)),
+            Value::Number(number) => {
+                if let Some(int) = number.as_i64() {
+                    i32::try_from(int).map_or(NbtTag::Long(int), NbtTag::Int)
+                } else {
+                    NbtTag::Double(as_f64(value)?)
+                }
+            }
+            Value::String(string) => NbtTag::String(string.clone()),
+            Value::Array(array) => NbtTag::List(
+                array
+                    .iter()
+                    .map(NbtTag::from_json)
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Object(object) => {
+                let Some(ty) = object.get(TYPE_KEY) else {
+                    return NbtCompound::from_json_object(value).map(NbtTag::Compound);
+                };
+                let inner = object
+                    .get(VALUE_KEY)
+                    .ok_or_else(|| invalid(format!("{TYPE_KEY} without a {VALUE_KEY}")))?;
+                match ty.as_str() {
+                    Some("byte") => NbtTag::Byte(as_int(inner)?),
+                    Some("short") => NbtTag::Short(as_int(inner)?),
+                    Some("int") => NbtTag::Int(as_int(inner)?),
+                    Some("long") => NbtTag::Long(as_i64(inner)?),
+                    Some("float") => NbtTag::Float(as_f64(inner)? as f32),
+                    Some("double") => NbtTag::Double(as_f64(inner)?),
+                    Some("byte_array") => NbtTag::ByteArray(as_array(inner, |byte| {
+                        as_int::<i8>(byte).map(|byte| byte as u8)
+                    })?),
+                    Some("int_array") => NbtTag::IntArray(as_array(inner, as_int)?),
+                    Some("long_array") => NbtTag::LongArray(as_array(inner, as_i64)?),
+                    Some("compound") => NbtTag::Compound(NbtCompound::from_json_object(inner)?),
+                    _ => return Err(invalid(format!("unknown {TYPE_KEY} {ty}"))),
+                }
+            }
+        })
+    }
+}
+
+impl NbtCompound {
+    /// Converts the compound to JSON, see the [module documentation](crate::json) for the mapping.
+    pub fn to_json(&self) -> Value {
+        let object: Map<_, _> = self
+            .child_tags
+            .iter()
+            .map(|(key, tag)| (key.clone(), tag.to_json()))
+            .collect();
+        if object.contains_key(TYPE_KEY) {
+            tagged("compound", Value::Object(object))
+        } else {
+            Value::Object(object)
+        }
+    }
+
+    /// Reads a compound from JSON as written by [`NbtCompound::to_json`].
+    pub fn from_json(value: &Value) -> Result<NbtCompound, Error> {
+        match NbtTag::from_json(value)? {
+            NbtTag::Compound(compound) => Ok(compound),
+            _ => Err(invalid(format!("expected a compound, found {value}"))),
+        }
+    }
+
+    fn from_json_object(value: &Value) -> Result<NbtCompound, Error> {
+        value
+            .as_object()
+            .ok_or_else(|| invalid(format!("expected an object, found {value}")))?
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), NbtTag::from_json(value)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use crate::{compound::NbtCompound, tag::NbtTag};
+
+    #[test]
+    fn all_tag_types_survive_json_round_trip() {
+        let mut nested = NbtCompound::new();
+        nested.put_string("$type", "not a type hint".to_string());
+        nested.put_byte("flag", 1);
+
+        let mut compound = NbtCompound::new();
+        compound.put_byte("byte", -3);
+        compound.put_short("short", 300);
+        compound.put_int("int", 70_000);
+        compound.put_long("long", i64::MAX);
+        compound.put_float("float", 0.1);
+        compound.put_double("double", 2.0);
+        compound.put_double("nan", f64::NAN);
+        compound.put_float("infinity", f32::INFINITY);
+        compound.put(
+            "byte_array",
+            NbtTag::ByteArray(vec![0, 127, 128, 255].into()),
+        );
+        compound.put_string("string", "Pumpkin".to_string());
+        compound.put_list(
+            "list",
+            vec![NbtTag::Short(1), NbtTag::Short(2)].into_boxed_slice(),
+        );
+        compound.put_list("empty", Box::new([]));
+        compound.put_component("compound", nested);
+        compound.put("int_array", NbtTag::IntArray(vec![i32::MIN, 0, 1].into()));
+        compound.put("long_array", NbtTag::LongArray(vec![i64::MIN, 2].into()));
+
+        let json = compound.to_json();
+        assert_eq!(json["int"], json!(70_000));
+        assert_eq!(json["long"], json!({ "$type": "long", "value": i64::MAX }));
+        assert_eq!(
+            json["byte_array"],
+            json!({ "$type": "byte_array", "value": [0, 127, -128, -1] })
+        );
+
+        // Go through a string as well to make sure the conversion doesn't rely on number details
+        let text = serde_json::to_string(&json).unwrap();
+        let read = NbtCompound::from_json(&serde_json::from_str(&text).unwrap()).unwrap();
+
+        // NaN never equals itself, so check it separately
+        assert!(read.get_double("nan").unwrap().is_nan());
+        let without_nan = |compound: NbtCompound| {
+            let mut tags: Vec<_> = compound
+                .into_iter()
+                .filter(|(key, _)| key != "nan")
+                .collect();
+            tags.sort_by(|(a, _), (b, _)| a.cmp(b));
+            tags
+        };
+        assert_eq!(without_nan(read), without_nan(compound));
+    }
+
+    #[test]
+    fn plain_json_is_accepted() {
+        let compound = NbtCompound::from_json(&json!({ "small": 1, "big": 1_i64 << 40 })).unwrap();
+        assert_eq!(compound.get_int("small"), Some(1));
+        assert_eq!(compound.get_long("big"), Some(1 << 40));
+
+        assert!(NbtCompound::from_json(&json!([1, 2])).is_err());
+        assert!(
+            NbtCompound::from_json(&json!({ "byte": { "$type": "byte", "value": 300 } })).is_err()
+        );
+    }
+}
@@ -14,7 +14,9 @@ use thiserror::Error;
 
 pub mod compound;
 pub mod deserializer;
+pub mod json;
 pub mod nbt_compress;
+pub mod path;
 pub mod serializer;
 pub mod tag;
 
@@ -55,6 +57,8 @@ pub enum Error {
     NegativeLength(i32),
     #[error("Length too large: {0}")]
     LargeLength(usize),
+    #[error("Invalid NBT JSON: {0}")]
+    InvalidJson(String),
 }
 
 impl ser::Error for Error {
@@ -0,0 +1,413 @@
+//! Paths into NBT data like `Inventory[0].components` or `Pos[]`, as used by the `/data` command.
+
+use std::{fmt::Display, str::FromStr};
+
+use thiserror::Error;
+
+use crate::{compound::NbtCompound, tag::NbtTag};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum NbtPathError {
+    #[error("Invalid NBT path element at {0}")]
+    Invalid(usize),
+    #[error("Found no elements matching {0}")]
+    NothingFound(String),
+    #[error("Expected a list")]
+    ExpectedList,
+    #[error("Expected a compound")]
+    ExpectedCompound,
+    #[error("Invalid list index {0}")]
+    InvalidIndex(i32),
+    #[error("Lists can only hold elements of one type")]
+    WrongListType,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PathNode {
+    /// A named tag of a compound
+    Key(String),
+    /// An element of a list, negative indices count from the end
+    Index(i32),
+    /// Every element of a list, `[]`
+    AllElements,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NbtPath {
+    nodes: Vec<PathNode>,
+    source: String,
+}
+
+fn is_key_char(c: char) -> bool {
+    !matches!(c, ' ' | '"' | '\'' | '[' | ']' | '.' | '{' | '}')
+}
+
+impl FromStr for NbtPath {
+    type Err = NbtPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut nodes = Vec::new();
+        let mut chars = s.char_indices().peekable();
+        loop {
+            match chars.peek().copied() {
+                Some((_, quote @ ('"' | '\''))) => {
+                    chars.next();
+                    let mut key = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '\\')) => match chars.next() {
+                                Some((_, c)) => key.push(c),
+                                None => return Err(NbtPathError::Invalid(s.len())),
+                            },
+                            Some((_, c)) if c == quote => break,
+                            Some((_, c)) => key.push(c),
+                            None => return Err(NbtPathError::Invalid(s.len())),
+                        }
+                    }
+                    nodes.push(PathNode::Key(key));
+                }
+                Some((_, c)) if is_key_char(c) => {
+                    let mut key = String::new();
+                    while let Some((_, c)) = chars.next_if(|(_, c)| is_key_char(*c)) {
+                        key.push(c);
+                    }
+                    nodes.push(PathNode::Key(key));
+                }
+                Some((_, '[')) => {}
+                Some((i, _)) => return Err(NbtPathError::Invalid(i)),
+                None => return Err(NbtPathError::Invalid(s.len())),
+            }
+
+            while let Some((start, _)) = chars.next_if(|(_, c)| *c == '[') {
+                let mut index = String::new();
+                while let Some((_, c)) = chars.next_if(|(_, c)| *c != ']') {
+                    index.push(c);
+                }
+                if chars.next().is_none() {
+                    return Err(NbtPathError::Invalid(s.len()));
+                }
+                nodes.push(if index.is_empty() {
+                    PathNode::AllElements
+                } else {
+                    // Compound filters like `[{id:"minecraft:stone"}]` aren't supported
+                    PathNode::Index(index.parse().map_err(|_| NbtPathError::Invalid(start))?)
+                });
+            }
+
+            match chars.next() {
+                None => break,
+                Some((_, '.')) => {}
+                Some((i, _)) => return Err(NbtPathError::Invalid(i)),
+            }
+        }
+        Ok(Self {
+            nodes,
+            source: s.to_string(),
+        })
+    }
+}
+
+impl Display for NbtPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// The position of `index` in a list of `len` elements.
+fn list_index(len: usize, index: i32) -> Option<usize> {
+    let index = if index < 0 {
+        len as i64 + i64::from(index)
+    } else {
+        i64::from(index)
+    };
+    (0..len as i64).contains(&index).then_some(index as usize)
+}
+
+fn select<'a>(node: &PathNode, tag: &'a NbtTag, found: &mut Vec<&'a NbtTag>) {
+    match (node, tag) {
+        (PathNode::Key(key), NbtTag::Compound(compound)) => found.extend(compound.get(key)),
+        (PathNode::Index(index), NbtTag::List(list)) => {
+            found.extend(list_index(list.len(), *index).map(|index| &list[index]));
+        }
+        (PathNode::AllElements, NbtTag::List(list)) => found.extend(list.iter()),
+        _ => {}
+    }
+}
+
+/// Calls `apply` on every tag matching `nodes`, returning how many there were. With `create`,
+/// missing compound entries on the way are added, the last one with the value `create` returns.
+fn visit_mut(
+    nodes: &[PathNode],
+    tag: &mut NbtTag,
+    create: Option<fn() -> NbtTag>,
+    apply: &mut dyn FnMut(&mut NbtTag) -> Result<(), NbtPathError>,
+) -> Result<usize, NbtPathError> {
+    let Some((node, rest)) = nodes.split_first() else {
+        apply(tag)?;
+        return Ok(1);
+    };
+    match (node, tag) {
+        (PathNode::Key(key), NbtTag::Compound(compound)) => {
+            let position = match compound.child_tags.iter().position(|(name, _)| name == key) {
+                Some(position) => position,
+                None => {
+                    let value = match (rest.first(), create) {
+                        (None, Some(create)) => create(),
+                        (Some(PathNode::Key(_)), Some(_)) => NbtTag::Compound(NbtCompound::new()),
+                        _ => return Ok(0),
+                    };
+                    compound.child_tags.push((key.clone(), value));
+                    compound.child_tags.len() - 1
+                }
+            };
+            visit_mut(rest, &mut compound.child_tags[position].1, create, apply)
+        }
+        (PathNode::Index(index), NbtTag::List(list)) => match list_index(list.len(), *index) {
+            Some(index) => visit_mut(rest, &mut list[index], create, apply),
+            None => Ok(0),
+        },
+        (PathNode::AllElements, NbtTag::List(list)) => {
+            let mut count = 0;
+            for element in list.iter_mut() {
+                count += visit_mut(rest, element, create, apply)?;
+            }
+            Ok(count)
+        }
+        _ => Ok(0),
+    }
+}
+
+fn merge_compounds(target: &mut NbtCompound, source: &NbtCompound) {
+    for (key, value) in &source.child_tags {
+        match target.child_tags.iter_mut().find(|(name, _)| name == key) {
+            Some((_, NbtTag::Compound(target))) if matches!(value, NbtTag::Compound(_)) => {
+                if let NbtTag::Compound(source) = value {
+                    merge_compounds(target, source);
+                }
+            }
+            Some((_, target)) => *target = value.clone(),
+            None => target.child_tags.push((key.clone(), value.clone())),
+        }
+    }
+}
+
+impl NbtPath {
+    fn nothing_found(&self) -> NbtPathError {
+        NbtPathError::NothingFound(self.source.clone())
+    }
+
+    /// All tags in `root` matching the path.
+    pub fn get<'a>(&self, root: &'a NbtTag) -> Result<Vec<&'a NbtTag>, NbtPathError> {
+        let mut current = vec![root];
+        for node in &self.nodes {
+            let mut found = Vec::new();
+            for tag in current {
+                select(node, tag, &mut found);
+            }
+            current = found;
+        }
+        if current.is_empty() {
+            return Err(self.nothing_found());
+        }
+        Ok(current)
+    }
+
+    /// Replaces the tags matching the path with `value`, adding it if the path leads to a
+    /// missing compound entry. Returns how many tags were set.
+    pub fn set(&self, root: &mut NbtTag, value: &NbtTag) -> Result<usize, NbtPathError> {
+        if let Some((PathNode::Index(_) | PathNode::AllElements, parent)) = self.nodes.split_last()
+        {
+            let parent = Self {
+                nodes: parent.to_vec(),
+                source: self.source.clone(),
+            };
+            let mixes_types = |tag: &&NbtTag| {
+                matches!(tag, NbtTag::List(list) if list
+                    .first()
+                    .is_some_and(|element| element.get_type_id() != value.get_type_id()))
+            };
+            if parent.get(root)?.iter().any(mixes_types) {
+                return Err(NbtPathError::WrongListType);
+            }
+        }
+        let count = visit_mut(&self.nodes, root, Some(|| NbtTag::End), &mut |tag| {
+            *tag = value.clone();
+            Ok(())
+        })?;
+        if count == 0 {
+            return Err(self.nothing_found());
+        }
+        Ok(count)
+    }
+
+    /// Inserts `values` at `index` into the lists matching the path, where a negative index
+    /// counts from the end so `-1` appends. A missing list is created. Returns how many lists
+    /// were changed.
+    pub fn insert(
+        &self,
+        root: &mut NbtTag,
+        index: i32,
+        values: &[NbtTag],
+    ) -> Result<usize, NbtPathError> {
+        let count = visit_mut(
+            &self.nodes,
+            root,
+            Some(|| NbtTag::List(Box::default())),
+            &mut |tag| {
+                let NbtTag::List(list) = tag else {
+                    return Err(NbtPathError::ExpectedList);
+                };
+                let position = if index < 0 {
+                    list.len() as i64 + i64::from(index) + 1
+                } else {
+                    i64::from(index)
+                };
+                if !(0..=list.len() as i64).contains(&position) {
+                    return Err(NbtPathError::InvalidIndex(index));
+                }
+                let element_type = list.first().or(values.first()).map(NbtTag::get_type_id);
+                if values
+                    .iter()
+                    .any(|value| Some(value.get_type_id()) != element_type)
+                {
+                    return Err(NbtPathError::WrongListType);
+                }
+                let mut elements = std::mem::take(list).into_vec();
+                let position = position as usize;
+                elements.splice(position..position, values.iter().cloned());
+                *list = elements.into_boxed_slice();
+                Ok(())
+            },
+        )?;
+        if count == 0 {
+            return Err(self.nothing_found());
+        }
+        Ok(count)
+    }
+
+    /// Merges the compounds `values` into the compounds matching the path, nested compounds are
+    /// merged as well. A missing compound is created. Returns how many compounds were changed.
+    pub fn merge(&self, root: &mut NbtTag, values: &[NbtTag]) -> Result<usize, NbtPathError> {
+        if values
+            .iter()
+            .any(|value| !matches!(value, NbtTag::Compound(_)))
+        {
+            return Err(NbtPathError::ExpectedCompound);
+        }
+        let count = visit_mut(
+            &self.nodes,
+            root,
+            Some(|| NbtTag::Compound(NbtCompound::new())),
+            &mut |tag| {
+                let NbtTag::Compound(compound) = tag else {
+                    return Err(NbtPathError::ExpectedCompound);
+                };
+                for value in values {
+                    if let NbtTag::Compound(value) = value {
+                        merge_compounds(compound, value);
+                    }
+                }
+                Ok(())
+            },
+        )?;
+        if count == 0 {
+            return Err(self.nothing_found());
+        }
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{compound::NbtCompound, tag::NbtTag};
+
+    use super::{NbtPath, NbtPathError};
+
+    fn path(path: &str) -> NbtPath {
+        path.parse().unwrap()
+    }
+
+    fn entity(x: f64, health: f32) -> NbtTag {
+        let mut nbt = NbtCompound::new();
+        nbt.put(
+            "Pos",
+            NbtTag::List(vec![x.into(), 64.0.into(), 0.5.into()].into_boxed_slice()),
+        );
+        nbt.put_float("Health", health);
+        NbtTag::Compound(nbt)
+    }
+
+    #[test]
+    fn parses_keys_indices_and_quotes() {
+        assert!("Inventory[0].components".parse::<NbtPath>().is_ok());
+        assert!(r#""custom name".Pos[-1]"#.parse::<NbtPath>().is_ok());
+        assert!("Pos[]".parse::<NbtPath>().is_ok());
+        assert_eq!("Pos..x".parse::<NbtPath>(), Err(NbtPathError::Invalid(4)));
+        assert_eq!("Pos[x]".parse::<NbtPath>(), Err(NbtPathError::Invalid(3)));
+        assert_eq!("".parse::<NbtPath>(), Err(NbtPathError::Invalid(0)));
+    }
+
+    #[test]
+    fn copies_between_entities() {
+        let source = entity(12.5, 7.0);
+        let mut target = entity(-3.0, 20.0);
+
+        // data modify ... set from ... Pos[0]
+        let values: Vec<NbtTag> = path("Pos[0]")
+            .get(&source)
+            .unwrap()
+            .into_iter()
+            .cloned()
+            .collect();
+        path("Pos[0]").set(&mut target, &values[0]).unwrap();
+        path("Health")
+            .set(&mut target, &NbtTag::Float(7.0))
+            .unwrap();
+        let NbtTag::Compound(target_nbt) = &target else {
+            unreachable!()
+        };
+        assert_eq!(target_nbt.get_list("Pos").unwrap()[0], NbtTag::Double(12.5));
+        assert_eq!(target_nbt.get_float("Health"), Some(7.0));
+
+        // append, prepend and insert keep the list to a single type
+        path("Pos").insert(&mut target, -1, &values).unwrap();
+        path("Pos").insert(&mut target, 0, &values).unwrap();
+        let pos = path("Pos[]").get(&target).unwrap();
+        assert_eq!(pos.len(), 5);
+        assert_eq!(pos[0], &NbtTag::Double(12.5));
+        assert_eq!(pos[4], &NbtTag::Double(12.5));
+        assert_eq!(
+            path("Pos").insert(&mut target, 7, &values),
+            Err(NbtPathError::InvalidIndex(7))
+        );
+        assert_eq!(
+            path("Pos").insert(&mut target, 0, &[NbtTag::Int(1)]),
+            Err(NbtPathError::WrongListType)
+        );
+        assert_eq!(
+            path("Pos[1]").set(&mut target, &NbtTag::Int(1)),
+            Err(NbtPathError::WrongListType)
+        );
+        assert_eq!(
+            path("Health").insert(&mut target, 0, &values),
+            Err(NbtPathError::ExpectedList)
+        );
+
+        // merge adds and replaces entries, missing compounds are created
+        path("data")
+            .merge(&mut target, std::slice::from_ref(&source))
+            .unwrap();
+        assert_eq!(
+            path("data.Health").get(&target).unwrap(),
+            vec![&NbtTag::Float(7.0)]
+        );
+        assert_eq!(
+            path("Health").merge(&mut target, &values),
+            Err(NbtPathError::ExpectedCompound)
+        );
+        assert_eq!(
+            path("Motion[0]").get(&target),
+            Err(NbtPathError::NothingFound("Motion[0]".to_string()))
+        );
+    }
+}
@@ -267,6 +267,21 @@ impl NbtTag {
         }
     }
 
+    /// The bytes this tag allocated on the heap, not counting the tag itself.
+    pub fn heap_size(&self) -> usize {
+        match self {
+            NbtTag::ByteArray(bytes) => bytes.len(),
+            NbtTag::String(string) => string.capacity(),
+            NbtTag::List(list) => {
+                size_of_val::<[NbtTag]>(list) + list.iter().map(NbtTag::heap_size).sum::<usize>()
+            }
+            NbtTag::Compound(compound) => compound.heap_size(),
+            NbtTag::IntArray(ints) => size_of_val::<[i32]>(ints),
+            NbtTag::LongArray(longs) => size_of_val::<[i64]>(longs),
+            _ => 0,
+        }
+    }
+
     pub fn extract_byte(&self) -> Option<i8> {
         match self {
             NbtTag::Byte(byte) => Some(*byte),
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use pumpkin_protocol::{ClientPacket, client::play::CChunkData};
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_world::chunk::{
+    ChunkData, ChunkHeightmaps, ChunkLightEngine, ChunkSections, format::LightContainer,
+};
+
+/// The chunks sent to a player joining with this view distance
+const VIEW_DISTANCE: i32 = 10;
+
+fn filled_chunk(position: Vector2<i32>) -> ChunkData {
+    let mut section = ChunkSections::empty(-64, 384).unwrap();
+    for (index, sub_chunk) in section.sections.iter_mut().enumerate() {
+        for x in 0..16 {
+            for y in 0..16 {
+                for z in 0..16 {
+                    let id = (x * 7 + y * 13 + z + index) % 40 + 1;
+                    sub_chunk.block_states.set(x, y, z, id as u16);
+                }
+            }
+        }
+    }
+
+    let light_sections = section.sections.len() + 2;
+    let light = || {
+        (0..light_sections)
+            .map(|_| LightContainer::new_filled(15))
+            .collect()
+    };
+
+    ChunkData {
+        section,
+        heightmap: ChunkHeightmaps::default(),
+        position,
+        block_ticks: Vec::new(),
+        fluid_ticks: Vec::new(),
+        block_entities: HashMap::new(),
+        light_engine: ChunkLightEngine {
+            sky_light: light(),
+            block_light: light(),
+        },
+        dirty: false,
+    }
+}
+
+fn bench_join_chunks(c: &mut Criterion) {
+    let chunks: Vec<_> = (-VIEW_DISTANCE..=VIEW_DISTANCE)
+        .flat_map(|x| (-VIEW_DISTANCE..=VIEW_DISTANCE).map(move |z| Vector2::new(x, z)))
+        .map(filled_chunk)
+        .collect();
+
+    let mut buf = Vec::new();
+    c.bench_function("join chunk data", |b| {
+        b.iter(|| {
+            buf.clear();
+            for chunk in &chunks {
+                CChunkData::new(chunk, None)
+                    .write_packet_data(&mut buf)
+                    .unwrap();
+            }
+        });
+    });
+}
+
+criterion_group!(benches, bench_join_chunks);
+criterion_main!(benches);
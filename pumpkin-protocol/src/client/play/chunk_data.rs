@@ -8,22 +8,56 @@ use pumpkin_macros::packet;
 use pumpkin_nbt::END_ID;
 use pumpkin_util::math::position::get_local_cord;
 use pumpkin_world::chunk::format::LightContainer;
-use pumpkin_world::chunk::{ChunkData, palette::NetworkPalette};
+use pumpkin_world::chunk::{ChunkData, anti_xray::AntiXray, palette::NetworkPalette};
 use std::io::Write;
 
 #[packet(PLAY_LEVEL_CHUNK_WITH_LIGHT)]
-pub struct CChunkData<'a>(pub &'a ChunkData);
+pub struct CChunkData<'a> {
+    chunk: &'a ChunkData,
+    /// Hides the ores of the sent blocks, without changing the chunk itself
+    anti_xray: Option<&'a AntiXray>,
+}
+
+impl<'a> CChunkData<'a> {
+    pub fn new(chunk: &'a ChunkData, anti_xray: Option<&'a AntiXray>) -> Self {
+        Self { chunk, anti_xray }
+    }
+}
+
+/// Writes a long array, either with a few bulk writes or with one write per long. Both produce the
+/// same bytes, the per long writes are only kept around to check that.
+fn write_longs(
+    write: &mut impl Write,
+    longs: &[i64],
+    bulk_longs: bool,
+) -> Result<(), WritingError> {
+    if bulk_longs {
+        write.write_i64_be_slice(longs)
+    } else {
+        for long in longs {
+            write.write_i64_be(*long)?;
+        }
+        Ok(())
+    }
+}
 
 impl ClientPacket for CChunkData<'_> {
     fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        // Chunks are the bulk of what gets sent on join, so the long arrays are copied as a whole
+        self.write_data(write, true)
+    }
+}
+
+impl CChunkData<'_> {
+    fn write_data(&self, write: impl Write, bulk_longs: bool) -> Result<(), WritingError> {
         let mut write = write;
 
         // Chunk X
-        write.write_i32_be(self.0.position.x)?;
+        write.write_i32_be(self.chunk.position.x)?;
         // Chunk Z
-        write.write_i32_be(self.0.position.z)?;
+        write.write_i32_be(self.chunk.position.z)?;
 
-        let heightmaps = &self.0.heightmap;
+        let heightmaps = &self.chunk.heightmap;
         // the heighmap is a map, we put 2 values in so the size is 2
         write.write_var_int(&VarInt(2))?;
 
@@ -31,26 +65,27 @@ impl ClientPacket for CChunkData<'_> {
         write.write_var_int(&VarInt(1))?;
         // write long array
         write.write_var_int(&VarInt(heightmaps.world_surface.len() as i32))?;
-        for mb in &heightmaps.world_surface {
-            write.write_i64_be(*mb)?;
-        }
+        write_longs(&mut write, &heightmaps.world_surface, bulk_longs)?;
         // heighmap index
         write.write_var_int(&VarInt(4))?;
         // write long array
         write.write_var_int(&VarInt(heightmaps.motion_blocking.len() as i32))?;
-        for mb in &heightmaps.motion_blocking {
-            write.write_i64_be(*mb)?;
-        }
+        write_longs(&mut write, &heightmaps.motion_blocking, bulk_longs)?;
 
         {
             let mut blocks_and_biomes_buf = Vec::new();
-            for section in &self.0.section.sections {
+            for (index, section) in self.chunk.section.sections.iter().enumerate() {
+                let obfuscated = self
+                    .anti_xray
+                    .and_then(|anti_xray| anti_xray.obfuscate_section(&self.chunk.section, index));
+                let block_states = obfuscated.as_ref().unwrap_or(&section.block_states);
+
                 // Block count
-                let non_empty_block_count = section.block_states.non_air_block_count() as i16;
+                let non_empty_block_count = block_states.non_air_block_count() as i16;
                 blocks_and_biomes_buf.write_i16_be(non_empty_block_count)?;
 
                 // This is a bit messy, but we dont have access to VarInt in pumpkin-world
-                let network_repr = section.block_states.convert_network();
+                let network_repr = block_states.convert_network();
                 blocks_and_biomes_buf.write_u8_be(network_repr.bits_per_entry)?;
                 match network_repr.palette {
                     NetworkPalette::Single(registry_id) => {
@@ -72,9 +107,11 @@ impl ClientPacket for CChunkData<'_> {
                     NetworkPalette::Direct => {}
                 }
 
-                for packed in network_repr.packed_data {
-                    blocks_and_biomes_buf.write_i64_be(packed)?;
-                }
+                write_longs(
+                    &mut blocks_and_biomes_buf,
+                    &network_repr.packed_data,
+                    bulk_longs,
+                )?;
 
                 let network_repr = section.biomes.convert_network();
                 blocks_and_biomes_buf.write_u8_be(network_repr.bits_per_entry)?;
@@ -100,9 +137,11 @@ impl ClientPacket for CChunkData<'_> {
 
                 // NOTE: Not updated in wiki; i64 array length is now determined by the bits per entry
                 //data_buf.write_var_int(&network_repr.packed_data.len().into())?;
-                for packed in network_repr.packed_data {
-                    blocks_and_biomes_buf.write_i64_be(packed)?;
-                }
+                write_longs(
+                    &mut blocks_and_biomes_buf,
+                    &network_repr.packed_data,
+                    bulk_longs,
+                )?;
             }
             write.write_var_int(&blocks_and_biomes_buf.len().try_into().map_err(|_| {
                 WritingError::Message(format!(
@@ -114,8 +153,8 @@ impl ClientPacket for CChunkData<'_> {
         }
 
         // TODO: block entities
-        write.write_var_int(&VarInt(self.0.block_entities.len() as i32))?;
-        for block_entity in self.0.block_entities.values() {
+        write.write_var_int(&VarInt(self.chunk.block_entities.len() as i32))?;
+        for block_entity in self.chunk.block_entities.values() {
             let chunk_data_nbt = block_entity.chunk_data_nbt();
             let pos = block_entity.get_position();
             let block_entity_id = block_entity.get_id();
@@ -131,52 +170,59 @@ impl ClientPacket for CChunkData<'_> {
         }
 
         {
-            // todo: these masks are 64 bits long, we should use a bitset instead of a u64
-            //  in higher maps
-            let mut sky_light_empty_mask = 0;
-            let mut block_light_empty_mask = 0;
-            let mut sky_light_mask = 0;
-            let mut block_light_mask = 0;
-            for light_index in 0..self.0.light_engine.sky_light.len() {
-                if let LightContainer::Full(_) = &self.0.light_engine.sky_light[light_index] {
-                    sky_light_mask |= 1 << light_index;
+            // One bit per section, tall worlds need more than a single long
+            let mask_len = self.chunk.light_engine.sky_light.len().div_ceil(64);
+            let mut sky_light_empty_mask = vec![0i64; mask_len];
+            let mut block_light_empty_mask = vec![0i64; mask_len];
+            let mut sky_light_mask = vec![0i64; mask_len];
+            let mut block_light_mask = vec![0i64; mask_len];
+            for light_index in 0..self.chunk.light_engine.sky_light.len() {
+                let (word, bit) = (light_index / 64, 1 << (light_index % 64));
+                if let LightContainer::Full(_) = &self.chunk.light_engine.sky_light[light_index] {
+                    sky_light_mask[word] |= bit;
                 } else {
-                    sky_light_empty_mask |= 1 << light_index;
+                    sky_light_empty_mask[word] |= bit;
                 }
 
-                if let LightContainer::Full(_) = &self.0.light_engine.block_light[light_index] {
-                    block_light_mask |= 1 << light_index;
+                if let LightContainer::Full(_) = &self.chunk.light_engine.block_light[light_index] {
+                    block_light_mask[word] |= bit;
                 } else {
-                    block_light_empty_mask |= 1 << light_index;
+                    block_light_empty_mask[word] |= bit;
                 }
             }
+            let sky_light_count: u32 = sky_light_mask.iter().map(|word| word.count_ones()).sum();
+            let block_light_count: u32 =
+                block_light_mask.iter().map(|word| word.count_ones()).sum();
             // Sky Light Mask
             // All of the chunks, this is not optimal and uses way more data than needed but will be
             // overhauled with a full lighting system.
 
             // Sky Light Mask
-            write.write_bitset(&BitSet(Box::new([sky_light_mask])))?;
+            write.write_bitset(&BitSet(sky_light_mask.into_boxed_slice()))?;
             // Block Light Mask
-            write.write_bitset(&BitSet(Box::new([block_light_mask])))?;
+            write.write_bitset(&BitSet(block_light_mask.into_boxed_slice()))?;
             // Empty Sky Light Mask
-            write.write_bitset(&BitSet(Box::new([sky_light_empty_mask])))?;
+            write.write_bitset(&BitSet(sky_light_empty_mask.into_boxed_slice()))?;
             // Empty Block Light Mask
-            write.write_bitset(&BitSet(Box::new([block_light_empty_mask])))?;
+            write.write_bitset(&BitSet(block_light_empty_mask.into_boxed_slice()))?;
 
             let light_data_size: VarInt = LightContainer::ARRAY_SIZE.try_into().unwrap();
             // Sky light
-            write.write_var_int(&VarInt(sky_light_mask.count_ones() as i32))?;
-            for light_index in 0..self.0.light_engine.sky_light.len() {
-                if let LightContainer::Full(data) = &self.0.light_engine.sky_light[light_index] {
+            write.write_var_int(&VarInt(sky_light_count as i32))?;
+            for light_index in 0..self.chunk.light_engine.sky_light.len() {
+                if let LightContainer::Full(data) = &self.chunk.light_engine.sky_light[light_index]
+                {
                     write.write_var_int(&light_data_size)?;
                     write.write_slice(data)?;
                 }
             }
 
             // Block Light
-            write.write_var_int(&VarInt(block_light_mask.count_ones() as i32))?;
-            for light_index in 0..self.0.light_engine.block_light.len() {
-                if let LightContainer::Full(data) = &self.0.light_engine.block_light[light_index] {
+            write.write_var_int(&VarInt(block_light_count as i32))?;
+            for light_index in 0..self.chunk.light_engine.block_light.len() {
+                if let LightContainer::Full(data) =
+                    &self.chunk.light_engine.block_light[light_index]
+                {
                     write.write_var_int(&light_data_size)?;
                     write.write_slice(data)?;
                 }
@@ -185,3 +231,179 @@ impl ClientPacket for CChunkData<'_> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pumpkin_config::AntiXrayConfig;
+    use pumpkin_data::Block;
+    use pumpkin_util::math::vector2::Vector2;
+    use pumpkin_world::BlockStateId;
+    use pumpkin_world::chunk::{
+        ChunkData, ChunkHeightmaps, ChunkLightEngine, ChunkSections, anti_xray::AntiXray,
+        format::LightContainer, palette::BlockPalette,
+    };
+
+    use super::CChunkData;
+    use crate::ClientPacket;
+    use crate::ser::NetworkReadExt;
+
+    fn sample_chunk() -> ChunkData {
+        let mut section = ChunkSections::empty(-64, 384).unwrap();
+        for (index, sub_chunk) in section.sections.iter_mut().enumerate().take(8) {
+            // Few different blocks in the lower sections, more than a palette can hold above
+            let kinds = if index < 4 { 10 } else { 400 };
+            for x in 0..16 {
+                for y in 0..16 {
+                    for z in 0..16 {
+                        let id = (x * 7 + y * 13 + z + index) % kinds + 1;
+                        sub_chunk.block_states.set(x, y, z, id as u16);
+                    }
+                }
+            }
+            sub_chunk.biomes.set(0, 0, 0, index as u8);
+        }
+
+        let mut heightmap = ChunkHeightmaps::default();
+        for (index, long) in heightmap.world_surface.iter_mut().enumerate() {
+            *long = (index as i64).wrapping_mul(0x0123_4567_89AB_CDEF);
+        }
+        for (index, long) in heightmap.motion_blocking.iter_mut().enumerate() {
+            *long = -(index as i64);
+        }
+
+        let light_sections = section.sections.len() + 2;
+        let light = |filled: usize| {
+            (0..light_sections)
+                .map(|index| {
+                    if index % filled == 0 {
+                        LightContainer::new_filled(15)
+                    } else {
+                        LightContainer::new_empty(0)
+                    }
+                })
+                .collect()
+        };
+
+        ChunkData {
+            section,
+            heightmap,
+            position: Vector2::new(3, -7),
+            block_ticks: Vec::new(),
+            fluid_ticks: Vec::new(),
+            block_entities: HashMap::new(),
+            light_engine: ChunkLightEngine {
+                sky_light: light(2),
+                block_light: light(3),
+            },
+            dirty: false,
+        }
+    }
+
+    #[test]
+    fn bulk_long_writes_match_per_long_writes() {
+        let chunk = sample_chunk();
+        let packet = CChunkData::new(&chunk, None);
+
+        let mut bulk = Vec::new();
+        packet.write_packet_data(&mut bulk).unwrap();
+        let mut per_long = Vec::new();
+        packet.write_data(&mut per_long, false).unwrap();
+
+        assert_eq!(bulk, per_long);
+    }
+
+    /// A chunk of stone up to y 0, with a diamond ore inside of it and one next to a cave
+    fn ore_chunk() -> ChunkData {
+        let mut chunk = sample_chunk();
+        let mut section = ChunkSections::empty(-64, 384).unwrap();
+        for sub_chunk in section.sections.iter_mut().take(4) {
+            sub_chunk.block_states = BlockPalette::Homogeneous(Block::STONE.default_state_id);
+        }
+        section.set_block_absolute_y(5, -40, 5, Block::DIAMOND_ORE.default_state_id);
+        section.set_block_absolute_y(8, -40, 8, Block::DIAMOND_ORE.default_state_id);
+        section.set_block_absolute_y(8, -39, 8, Block::CAVE_AIR.default_state_id);
+        chunk.section = section;
+        chunk
+    }
+
+    /// Reads a paletted container, `y, z, x` ordered
+    fn read_container(read: &mut &[u8], entries: usize, max_palette_bits: usize) -> Vec<u16> {
+        let bits = read.get_u8_be().unwrap() as usize;
+        if bits == 0 {
+            let value = read.get_var_int().unwrap().0 as u16;
+            return vec![value; entries];
+        }
+        let palette = (bits <= max_palette_bits).then(|| {
+            let len = read.get_var_int().unwrap().0;
+            (0..len)
+                .map(|_| read.get_var_int().unwrap().0 as u16)
+                .collect::<Vec<_>>()
+        });
+        let per_long = 64 / bits;
+        let longs = (0..entries.div_ceil(per_long))
+            .map(|_| read.get_i64_be().unwrap() as u64)
+            .collect::<Vec<_>>();
+        (0..entries)
+            .map(|index| {
+                let value =
+                    (longs[index / per_long] >> (index % per_long * bits)) & ((1 << bits) - 1);
+                palette
+                    .as_ref()
+                    .map_or(value as u16, |palette| palette[value as usize])
+            })
+            .collect()
+    }
+
+    /// The blocks of every section like the client gets them
+    fn sent_blocks(packet: &CChunkData) -> Vec<Vec<BlockStateId>> {
+        let mut data = Vec::new();
+        packet.write_packet_data(&mut data).unwrap();
+        let mut read = data.as_slice();
+
+        // Chunk X and Z
+        read.get_i64_be().unwrap();
+        for _ in 0..read.get_var_int().unwrap().0 {
+            read.get_var_int().unwrap();
+            for _ in 0..read.get_var_int().unwrap().0 {
+                read.get_i64_be().unwrap();
+            }
+        }
+        // Size of the sections
+        read.get_var_int().unwrap();
+        (0..packet.chunk.section.sections.len())
+            .map(|_| {
+                read.get_i16_be().unwrap();
+                let blocks = read_container(&mut read, 4096, 8);
+                read_container(&mut read, 64, 3);
+                blocks
+            })
+            .collect()
+    }
+
+    #[test]
+    fn anti_xray_only_hides_ores_which_cant_be_seen() {
+        let anti_xray = AntiXray::new(&AntiXrayConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let chunk = ore_chunk();
+        // Both ores are 8 blocks up in the second section
+        let hidden = (8 * 16 + 5) * 16 + 5;
+        let exposed = (8 * 16 + 8) * 16 + 8;
+
+        let sent = sent_blocks(&CChunkData::new(&chunk, Some(&anti_xray)));
+        assert_eq!(sent[1][hidden], Block::STONE.default_state_id);
+        assert_eq!(sent[1][exposed], Block::DIAMOND_ORE.default_state_id);
+
+        // Without anti-xray, and on the server, the ore is still there
+        let sent = sent_blocks(&CChunkData::new(&chunk, None));
+        assert_eq!(sent[1][hidden], Block::DIAMOND_ORE.default_state_id);
+        assert_eq!(
+            chunk.section.get_block_absolute_y(5, -40, 5),
+            Some(Block::DIAMOND_ORE.default_state_id)
+        );
+    }
+}
@@ -21,11 +21,52 @@ impl CEntityAnimation {
     }
 }
 
-#[derive(Debug)]
+/// Id 1 used to be the hurt animation, which is now sent with `CHurtAnimation`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Animation {
     SwingMainArm,
     LeaveBed = 2,
     SwingOffhand,
     CriticalEffect,
-    MagicCriticaleffect,
+    MagicCriticalEffect,
+}
+
+impl Animation {
+    /// The animation for the hand of a `SSwingArm` packet. The client picks the arm of the main
+    /// hand itself, so left handed players don't need special treatment.
+    pub const fn from_swing_hand(hand: i32) -> Option<Self> {
+        match hand {
+            0 => Some(Self::SwingMainArm),
+            1 => Some(Self::SwingOffhand),
+            _ => None,
+        }
+    }
+
+    pub const fn is_swing(self) -> bool {
+        matches!(self, Self::SwingMainArm | Self::SwingOffhand)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::packet::clientbound::PLAY_ANIMATE;
+
+    use super::{Animation, CEntityAnimation};
+    use crate::{ClientPacket, codec::var_int::VarInt};
+
+    #[test]
+    fn swing_arm_hands_map_to_animation_ids() {
+        let encode = |hand| {
+            let animation = Animation::from_swing_hand(hand).unwrap();
+            let mut data = Vec::new();
+            CEntityAnimation::new(VarInt(7), animation as u8)
+                .write(&mut data)
+                .unwrap();
+            data
+        };
+        assert_eq!(encode(0), vec![PLAY_ANIMATE as u8, 7, 0]);
+        assert_eq!(encode(1), vec![PLAY_ANIMATE as u8, 7, 3]);
+        assert_eq!(Animation::from_swing_hand(2), None);
+        assert!(!Animation::CriticalEffect.is_swing());
+    }
 }
@@ -0,0 +1,21 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_ENTITY_LINK;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+/// Tells the client which entity holds the leash of another one.
+#[derive(Serialize)]
+#[packet(PLAY_SET_ENTITY_LINK)]
+pub struct CLinkEntities {
+    attached_entity_id: i32,
+    /// 0 once the leash was removed
+    holding_entity_id: i32,
+}
+
+impl CLinkEntities {
+    pub fn new(attached_entity_id: i32, holding_entity_id: i32) -> Self {
+        Self {
+            attached_entity_id,
+            holding_entity_id,
+        }
+    }
+}
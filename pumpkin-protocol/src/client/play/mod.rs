@@ -33,12 +33,13 @@ mod head_rot;
 mod hurt_animation;
 mod initialize_world_border;
 mod keep_alive;
-mod level_event;
+mod link_entities;
 mod login;
 mod multi_block_update;
 mod open_screen;
 mod open_sign_editor;
 mod particle;
+mod ping;
 mod ping_response;
 mod player_abilities;
 mod player_action;
@@ -50,6 +51,7 @@ mod remove_entities;
 mod remove_mob_effect;
 mod reset_score;
 mod respawn;
+mod server_data;
 mod server_links;
 mod set_border_center;
 mod set_border_lerp_size;
@@ -59,28 +61,38 @@ mod set_border_warning_distance;
 mod set_container_content;
 mod set_container_property;
 mod set_container_slot;
+mod set_default_spawn_position;
 mod set_equipment;
 mod set_experience;
 mod set_health;
 mod set_held_item;
+mod set_passengers;
+mod set_simulation_distance;
 mod set_time;
 mod set_title;
 mod sound_effect;
 mod spawn_entity;
+mod start_configuration;
 mod stop_sound;
 mod store_cookie;
 mod subtitle;
 mod system_chat_message;
 mod take_item;
 mod teleport_entity;
+mod ticking_state;
+mod ticking_step;
+mod title_animation;
 mod transfer;
 mod unload_chunk;
+mod update_advancements;
+mod update_attributes;
 mod update_entity_pos;
 mod update_entity_pos_rot;
 mod update_entity_rot;
 mod update_mob_effect;
 mod update_objectives;
 mod update_score;
+mod update_teams;
 mod worldevent;
 
 pub use acknowledge_block::*;
@@ -118,12 +130,13 @@ pub use head_rot::*;
 pub use hurt_animation::*;
 pub use initialize_world_border::*;
 pub use keep_alive::*;
-pub use level_event::*;
+pub use link_entities::*;
 pub use login::*;
 pub use multi_block_update::*;
 pub use open_screen::*;
 pub use open_sign_editor::*;
 pub use particle::*;
+pub use ping::*;
 pub use ping_response::*;
 pub use player_abilities::*;
 pub use player_action::*;
@@ -135,6 +148,7 @@ pub use remove_entities::*;
 pub use remove_mob_effect::*;
 pub use reset_score::*;
 pub use respawn::*;
+pub use server_data::*;
 pub use server_links::*;
 pub use set_border_center::*;
 pub use set_border_lerp_size::*;
@@ -144,26 +158,36 @@ pub use set_border_warning_distance::*;
 pub use set_container_content::*;
 pub use set_container_property::*;
 pub use set_container_slot::*;
+pub use set_default_spawn_position::*;
 pub use set_equipment::*;
 pub use set_experience::*;
 pub use set_health::*;
 pub use set_held_item::*;
+pub use set_passengers::*;
+pub use set_simulation_distance::*;
 pub use set_time::*;
 pub use set_title::*;
 pub use sound_effect::*;
 pub use spawn_entity::*;
+pub use start_configuration::*;
 pub use stop_sound::*;
 pub use store_cookie::*;
 pub use subtitle::*;
 pub use system_chat_message::*;
 pub use take_item::*;
 pub use teleport_entity::*;
+pub use ticking_state::*;
+pub use ticking_step::*;
+pub use title_animation::*;
 pub use transfer::*;
 pub use unload_chunk::*;
+pub use update_advancements::*;
+pub use update_attributes::*;
 pub use update_entity_pos::*;
 pub use update_entity_pos_rot::*;
 pub use update_entity_rot::*;
 pub use update_mob_effect::*;
 pub use update_objectives::*;
 pub use update_score::*;
+pub use update_teams::*;
 pub use worldevent::*;
@@ -0,0 +1,16 @@
+use pumpkin_data::packet::clientbound::PLAY_PING;
+use pumpkin_macros::packet;
+use serde::{Deserialize, Serialize};
+
+/// Asks the client to answer with a `SPong` carrying the same id, e.g. to measure latency.
+#[derive(Serialize, Deserialize)]
+#[packet(PLAY_PING)]
+pub struct CPing {
+    pub id: i32,
+}
+
+impl CPing {
+    pub fn new(id: i32) -> Self {
+        Self { id }
+    }
+}
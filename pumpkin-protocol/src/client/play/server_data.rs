@@ -0,0 +1,63 @@
+use std::io::Write;
+
+use pumpkin_data::packet::clientbound::PLAY_SERVER_DATA;
+use pumpkin_macros::packet;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    ClientPacket,
+    ser::{NetworkWriteExt, WritingError},
+};
+
+/// The MOTD and icon clients show for the server they are playing on. The icon makes up most of
+/// the packet and is a PNG, which is compressed already, so the packet never is.
+#[packet(PLAY_SERVER_DATA, incompressible)]
+pub struct CServerData {
+    motd: TextComponent,
+    /// The raw PNG bytes of the server icon
+    icon: Option<Vec<u8>>,
+}
+
+impl CServerData {
+    pub fn new(motd: TextComponent, icon: Option<Vec<u8>>) -> Self {
+        Self { motd, icon }
+    }
+}
+
+impl ClientPacket for CServerData {
+    fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        let mut write = write;
+
+        write.write_slice(&self.motd.encode())?;
+        write.write_option_list(self.icon.as_deref(), |write, byte| write.write_u8_be(*byte))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::packet::clientbound::PLAY_SERVER_DATA;
+    use pumpkin_util::text::TextComponent;
+
+    use super::CServerData;
+    use crate::{ClientPacket, CompressionPolicy, ser::packet::Packet};
+
+    #[test]
+    fn server_data_has_motd_and_icon_flag() {
+        let motd = TextComponent::text("A Minecraft Server");
+        let encoded_motd = motd.encode();
+
+        let mut data = Vec::new();
+        CServerData::new(motd.clone(), Some(vec![1, 2, 3]))
+            .write(&mut data)
+            .unwrap();
+        assert_eq!(data[0], PLAY_SERVER_DATA as u8);
+        assert_eq!(&data[1..=encoded_motd.len()], &*encoded_motd);
+        assert_eq!(&data[encoded_motd.len() + 1..], &[1, 3, 1, 2, 3]);
+
+        let mut data = Vec::new();
+        CServerData::new(motd, None).write(&mut data).unwrap();
+        assert_eq!(&data[encoded_motd.len() + 1..], &[0]);
+
+        assert_eq!(CServerData::COMPRESSION, CompressionPolicy::Never);
+    }
+}
@@ -0,0 +1,18 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_DEFAULT_SPAWN_POSITION;
+use pumpkin_macros::packet;
+use pumpkin_util::math::position::BlockPos;
+use serde::Serialize;
+
+/// Tells the client where the world spawn is, compasses point there.
+#[derive(Serialize)]
+#[packet(PLAY_SET_DEFAULT_SPAWN_POSITION)]
+pub struct CSetDefaultSpawnPosition {
+    location: BlockPos,
+    angle: f32,
+}
+
+impl CSetDefaultSpawnPosition {
+    pub fn new(location: BlockPos, angle: f32) -> Self {
+        Self { location, angle }
+    }
+}
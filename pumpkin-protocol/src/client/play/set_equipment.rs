@@ -36,10 +36,11 @@ impl ClientPacket for CSetEquipment {
         for i in 0..self.equipment.len() {
             let equipment = &self.equipment[i];
             let slot = &equipment.0;
+            // The top bit marks that another entry follows
             if i != self.equipment.len() - 1 {
-                write.write_i8_be(-128)?;
+                write.write_u8_be(*slot as u8 | 0x80)?;
             } else {
-                write.write_i8_be(*slot as i8)?;
+                write.write_u8_be(*slot as u8)?;
             }
             let mut serializer = Serializer::new(&mut write);
             equipment
@@ -62,3 +63,26 @@ pub enum EquipmentSlot {
     Head,
     Body,
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::item::ItemStack;
+
+    use super::{CSetEquipment, EquipmentSlot};
+    use crate::{ClientPacket, codec::var_int::VarInt};
+
+    #[test]
+    fn all_but_last_entry_mark_continuation() {
+        let packet = CSetEquipment::new(
+            VarInt(5),
+            vec![
+                (EquipmentSlot::Feet, ItemStack::EMPTY.into()),
+                (EquipmentSlot::Head, ItemStack::EMPTY.into()),
+            ],
+        );
+        let mut data = Vec::new();
+        packet.write_packet_data(&mut data).unwrap();
+        // entity id, feet with continuation, empty stack, head, empty stack
+        assert_eq!(data, vec![5, 0x82, 0, 5, 0]);
+    }
+}
@@ -0,0 +1,22 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_PASSENGERS;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+/// Replaces all passengers of a vehicle, an empty list dismounts everyone.
+#[derive(Serialize)]
+#[packet(PLAY_SET_PASSENGERS)]
+pub struct CSetPassengers {
+    vehicle_id: VarInt,
+    passenger_ids: Vec<VarInt>,
+}
+
+impl CSetPassengers {
+    pub fn new(vehicle_id: VarInt, passenger_ids: Vec<VarInt>) -> Self {
+        Self {
+            vehicle_id,
+            passenger_ids,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_SIMULATION_DISTANCE;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::VarInt;
+
+#[derive(Serialize)]
+#[packet(PLAY_SET_SIMULATION_DISTANCE)]
+pub struct CSetSimulationDistance {
+    simulation_distance: VarInt,
+}
+
+impl CSetSimulationDistance {
+    pub fn new(simulation_distance: VarInt) -> Self {
+        Self {
+            simulation_distance,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use pumpkin_data::packet::clientbound::PLAY_START_CONFIGURATION;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+/// Sends the client back to the configuration state, it answers with
+/// `SConfigurationAcknowledged`.
+#[derive(Serialize)]
+#[packet(PLAY_START_CONFIGURATION)]
+pub struct CStartConfiguration;
@@ -0,0 +1,22 @@
+use pumpkin_data::packet::clientbound::PLAY_SET_TITLES_ANIMATION;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+/// Sets how long titles fade in, stay and fade out, in ticks.
+#[derive(Serialize)]
+#[packet(PLAY_SET_TITLES_ANIMATION)]
+pub struct CTitleAnimation {
+    fade_in: i32,
+    stay: i32,
+    fade_out: i32,
+}
+
+impl CTitleAnimation {
+    pub const fn new(fade_in: i32, stay: i32, fade_out: i32) -> Self {
+        Self {
+            fade_in,
+            stay,
+            fade_out,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use std::borrow::Cow;
+use std::io::Write;
+
+use pumpkin_data::packet::clientbound::PLAY_UPDATE_ADVANCEMENTS;
+use pumpkin_macros::packet;
+use pumpkin_world::advancement::{
+    Advancement, AdvancementDisplay, AdvancementFrame, ProgressUpdate,
+};
+use serde::Serialize;
+
+use crate::ClientPacket;
+use crate::codec::item_stack_seralizer::ItemStackSerializer;
+use crate::ser::{NetworkWriteExt, WritingError, serializer::Serializer};
+
+const FLAG_BACKGROUND: i32 = 0x01;
+const FLAG_SHOW_TOAST: i32 = 0x02;
+const FLAG_HIDDEN: i32 = 0x04;
+
+#[packet(PLAY_UPDATE_ADVANCEMENTS)]
+pub struct CUpdateAdvancements<'a> {
+    reset: bool,
+    added: &'a [&'a Advancement],
+    removed: &'a [&'a str],
+    progress: &'a [ProgressUpdate<'a>],
+    /// Whether the client shows toasts for advancements done in this update
+    show_advancements: bool,
+}
+
+impl<'a> CUpdateAdvancements<'a> {
+    pub fn new(
+        reset: bool,
+        added: &'a [&'a Advancement],
+        removed: &'a [&'a str],
+        progress: &'a [ProgressUpdate<'a>],
+        show_advancements: bool,
+    ) -> Self {
+        Self {
+            reset,
+            added,
+            removed,
+            progress,
+            show_advancements,
+        }
+    }
+}
+
+fn write_display(write: &mut impl Write, display: &AdvancementDisplay) -> Result<(), WritingError> {
+    write.write_slice(&display.title.encode())?;
+    write.write_slice(&display.description.encode())?;
+    let mut serializer = Serializer::new(&mut *write);
+    ItemStackSerializer(Cow::Borrowed(&display.icon)).serialize(&mut serializer)?;
+    let frame = match display.frame {
+        AdvancementFrame::Task => 0,
+        AdvancementFrame::Challenge => 1,
+        AdvancementFrame::Goal => 2,
+    };
+    write.write_var_int(&frame.into())?;
+
+    let mut flags = 0;
+    if display.background.is_some() {
+        flags |= FLAG_BACKGROUND;
+    }
+    if display.show_toast {
+        flags |= FLAG_SHOW_TOAST;
+    }
+    if display.hidden {
+        flags |= FLAG_HIDDEN;
+    }
+    write.write_i32_be(flags)?;
+    // Only written when flagged, without a present flag of its own
+    if let Some(background) = &display.background {
+        write.write_string(background)?;
+    }
+    write.write_f32_be(display.x)?;
+    write.write_f32_be(display.y)
+}
+
+impl ClientPacket for CUpdateAdvancements<'_> {
+    fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        let mut write = write;
+
+        write.write_bool(self.reset)?;
+        write.write_list(self.added, |write, advancement| {
+            write.write_string(&advancement.id)?;
+            write.write_option(&advancement.parent, |write, parent| {
+                write.write_string(parent)
+            })?;
+            write.write_option(&advancement.display, write_display)?;
+            write.write_list(&advancement.requirements, |write, requirement| {
+                write.write_list(requirement, |write, criterion| {
+                    write.write_string(criterion)
+                })
+            })?;
+            write.write_bool(advancement.sends_telemetry_event)
+        })?;
+        write.write_list(self.removed, |write, id| write.write_string(id))?;
+        write.write_list(self.progress, |write, progress| {
+            write.write_string(progress.id)?;
+            write.write_list(&progress.criteria, |write, (criterion, obtained)| {
+                write.write_string(criterion)?;
+                write.write_option(obtained, |write, obtained| write.write_i64_be(*obtained))
+            })
+        })?;
+        write.write_bool(self.show_advancements)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::advancement::ProgressUpdate;
+
+    use super::CUpdateAdvancements;
+    use crate::ClientPacket;
+
+    #[test]
+    fn progress_lists_every_criterion() {
+        let progress = [ProgressUpdate {
+            id: "minecraft:story/root",
+            criteria: vec![("crafting_table", Some(20)), ("other", None)],
+        }];
+        let packet = CUpdateAdvancements::new(false, &[], &[], &progress, true);
+        let mut data = Vec::new();
+        packet.write_packet_data(&mut data).unwrap();
+
+        // Not reset, nothing added or removed, progress of one advancement
+        let mut expected = vec![0, 0, 0, 1, 20];
+        expected.extend(b"minecraft:story/root");
+        expected.extend([2, 14]);
+        expected.extend(b"crafting_table");
+        expected.push(1);
+        expected.extend(20i64.to_be_bytes());
+        expected.push(5);
+        expected.extend(b"other");
+        // Not obtained, then show advancements
+        expected.extend([0, 1]);
+        assert_eq!(data, expected);
+    }
+}
@@ -0,0 +1,58 @@
+use std::io::Write;
+
+use pumpkin_data::packet::clientbound::PLAY_UPDATE_ATTRIBUTES;
+use pumpkin_macros::packet;
+
+use crate::{
+    ClientPacket,
+    codec::var_int::VarInt,
+    ser::{NetworkWriteExt, WritingError},
+};
+
+#[packet(PLAY_UPDATE_ATTRIBUTES)]
+pub struct CUpdateAttributes {
+    entity_id: VarInt,
+    properties: Vec<AttributeProperty>,
+}
+
+impl CUpdateAttributes {
+    pub fn new(entity_id: VarInt, properties: Vec<AttributeProperty>) -> Self {
+        Self {
+            entity_id,
+            properties,
+        }
+    }
+}
+
+/// The base value and modifiers of an attribute, the client calculates the value itself.
+pub struct AttributeProperty {
+    /// The id of the attribute in the `minecraft:attribute` registry
+    pub attribute: VarInt,
+    pub base: f64,
+    pub modifiers: Vec<AttributeModifierProperty>,
+}
+
+pub struct AttributeModifierProperty {
+    /// The namespaced id of the modifier (e.g. `minecraft:sprinting`)
+    pub id: String,
+    pub amount: f64,
+    /// 0: add value, 1: add multiplied base, 2: add multiplied total
+    pub operation: u8,
+}
+
+impl ClientPacket for CUpdateAttributes {
+    fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        let mut write = write;
+
+        write.write_var_int(&self.entity_id)?;
+        write.write_list(&self.properties, |write, property| {
+            write.write_var_int(&property.attribute)?;
+            write.write_f64_be(property.base)?;
+            write.write_list(&property.modifiers, |write, modifier| {
+                write.write_string(&modifier.id)?;
+                write.write_f64_be(modifier.amount)?;
+                write.write_u8_be(modifier.operation)
+            })
+        })
+    }
+}
@@ -0,0 +1,166 @@
+use std::io::Write;
+
+use pumpkin_data::packet::clientbound::PLAY_SET_PLAYER_TEAM;
+use pumpkin_macros::packet;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::{
+    ClientPacket, VarInt,
+    ser::{NetworkWriteExt, WritingError},
+};
+
+/// The color id clients use for teams without a color
+const NO_COLOR: i32 = 21;
+const FRIENDLY_FIRE_FLAG: u8 = 0x01;
+const SEE_FRIENDLY_INVISIBLES_FLAG: u8 = 0x02;
+
+#[packet(PLAY_SET_PLAYER_TEAM)]
+pub struct CUpdateTeams {
+    team_name: String,
+    method: TeamMethod,
+}
+
+impl CUpdateTeams {
+    pub fn new(team_name: String, method: TeamMethod) -> Self {
+        Self { team_name, method }
+    }
+}
+
+pub enum TeamMethod {
+    /// Creates the team with its members
+    Create(TeamInfo, Vec<String>),
+    Remove,
+    Update(TeamInfo),
+    AddEntities(Vec<String>),
+    RemoveEntities(Vec<String>),
+}
+
+impl TeamMethod {
+    const fn id(&self) -> u8 {
+        match self {
+            Self::Create(..) => 0,
+            Self::Remove => 1,
+            Self::Update(_) => 2,
+            Self::AddEntities(_) => 3,
+            Self::RemoveEntities(_) => 4,
+        }
+    }
+}
+
+pub struct TeamInfo {
+    pub display_name: TextComponent,
+    pub friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+    pub name_tag_visibility: TeamVisibility,
+    pub collision_rule: CollisionRule,
+    pub color: Option<NamedColor>,
+    pub prefix: TextComponent,
+    pub suffix: TextComponent,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TeamVisibility {
+    #[default]
+    Always,
+    Never,
+    HideForOtherTeams,
+    HideForOwnTeam,
+}
+
+impl TeamVisibility {
+    pub const ALL: [Self; 4] = [
+        Self::Always,
+        Self::Never,
+        Self::HideForOtherTeams,
+        Self::HideForOwnTeam,
+    ];
+
+    /// The name used in commands and saved scoreboards.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Never => "never",
+            Self::HideForOtherTeams => "hideForOtherTeams",
+            Self::HideForOwnTeam => "hideForOwnTeam",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|visibility| visibility.name() == name)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum CollisionRule {
+    #[default]
+    Always,
+    Never,
+    PushOtherTeams,
+    PushOwnTeam,
+}
+
+impl CollisionRule {
+    pub const ALL: [Self; 4] = [
+        Self::Always,
+        Self::Never,
+        Self::PushOtherTeams,
+        Self::PushOwnTeam,
+    ];
+
+    /// The name used in commands and saved scoreboards.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::Never => "never",
+            Self::PushOtherTeams => "pushOtherTeams",
+            Self::PushOwnTeam => "pushOwnTeam",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rule| rule.name() == name)
+    }
+}
+
+fn write_info(write: &mut impl Write, info: &TeamInfo) -> Result<(), WritingError> {
+    let mut flags = 0;
+    if info.friendly_fire {
+        flags |= FRIENDLY_FIRE_FLAG;
+    }
+    if info.see_friendly_invisibles {
+        flags |= SEE_FRIENDLY_INVISIBLES_FLAG;
+    }
+    write.write_slice(&info.display_name.encode())?;
+    write.write_u8_be(flags)?;
+    write.write_var_int(&VarInt(info.name_tag_visibility as i32))?;
+    write.write_var_int(&VarInt(info.collision_rule as i32))?;
+    write.write_var_int(&VarInt(info.color.map_or(NO_COLOR, |color| color as i32)))?;
+    write.write_slice(&info.prefix.encode())?;
+    write.write_slice(&info.suffix.encode())
+}
+
+fn write_entities(write: &mut impl Write, entities: &[String]) -> Result<(), WritingError> {
+    write.write_list(entities, |write, entity| write.write_string(entity))
+}
+
+impl ClientPacket for CUpdateTeams {
+    fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError> {
+        let mut write = write;
+
+        write.write_string(&self.team_name)?;
+        write.write_u8_be(self.method.id())?;
+        match &self.method {
+            TeamMethod::Create(info, entities) => {
+                write_info(&mut write, info)?;
+                write_entities(&mut write, entities)
+            }
+            TeamMethod::Remove => Ok(()),
+            TeamMethod::Update(info) => write_info(&mut write, info),
+            TeamMethod::AddEntities(entities) | TeamMethod::RemoveEntities(entities) => {
+                write_entities(&mut write, entities)
+            }
+        }
+    }
+}
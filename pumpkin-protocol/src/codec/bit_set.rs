@@ -16,11 +16,7 @@ impl BitSet {
             WritingError::Message(format!("{} isn't representable as a VarInt", self.0.len()))
         })?)?;
 
-        for b in &self.0 {
-            write.write_i64_be(*b)?;
-        }
-
-        Ok(())
+        write.write_i64_be_slice(&self.0)
     }
 
     pub fn decode(read: &mut impl Read) -> Result<Self, ReadingError> {
@@ -1,11 +1,14 @@
 use std::borrow::Cow;
 
 use crate::VarInt;
+use crate::codec::data_component::DataComponent;
+use crate::ser::{NetworkWriteExt, WritingError, network_serialize_no_prefix};
 use pumpkin_data::item::Item;
-use pumpkin_world::item::ItemStack;
+use pumpkin_world::item::{ItemStack, component::ItemComponentPatch, registry::DataRegistry};
 use serde::{
     Deserialize, Serialize, Serializer,
     de::{self, SeqAccess},
+    ser,
 };
 
 #[derive(Debug, Clone)]
@@ -33,7 +36,7 @@ impl<'de> Deserialize<'de> for ItemStackSerializer<'static> {
                     .ok_or(de::Error::custom("Failed to decode VarInt"))?;
 
                 let slot = if item_count.0 == 0 {
-                    ItemStackSerializer(Cow::Borrowed(&ItemStack::EMPTY))
+                    ItemStackSerializer(Cow::Owned(ItemStack::EMPTY))
                 } else {
                     let item_id = seq
                         .next_element::<VarInt>()?
@@ -78,27 +81,152 @@ impl Serialize for ItemStackSerializer<'_> {
         if self.0.is_empty() {
             VarInt(0).serialize(serializer)
         } else {
-            // TODO: Components
-
             #[derive(Serialize)]
             struct NetworkRepr {
                 item_count: VarInt,
                 item_id: VarInt,
                 components_to_add: VarInt,
                 components_to_remove: VarInt,
+                #[serde(serialize_with = "network_serialize_no_prefix")]
+                components: Vec<u8>,
             }
 
+            let (components_to_add, components) =
+                write_components(&self.0.patch).map_err(ser::Error::custom)?;
+
             NetworkRepr {
                 item_count: self.0.item_count.into(),
                 item_id: self.0.item.id.into(),
-                components_to_add: 0.into(),
+                components_to_add: components_to_add.into(),
                 components_to_remove: 0.into(),
+                components,
             }
             .serialize(serializer)
         }
     }
 }
 
+/// Writes the added components of the patch, returning how many were written.
+fn write_components(patch: &ItemComponentPatch) -> Result<(i32, Vec<u8>), WritingError> {
+    let mut count = 0;
+    let mut buf = Vec::new();
+
+    if let Some(damage) = patch.damage {
+        buf.write_var_int(&VarInt(DataComponent::Damage.id()))?;
+        buf.write_var_int(&VarInt(damage))?;
+        count += 1;
+    }
+
+    if let Some(name) = &patch.custom_name {
+        buf.write_var_int(&VarInt(DataComponent::CustomName.id()))?;
+        buf.write_slice(&name.encode())?;
+        count += 1;
+    }
+
+    if !patch.lore.is_empty() {
+        buf.write_var_int(&VarInt(DataComponent::Lore.id()))?;
+        buf.write_var_int(&VarInt(patch.lore.len() as i32))?;
+        for line in &patch.lore {
+            buf.write_slice(&line.encode())?;
+        }
+        count += 1;
+    }
+
+    let enchantments: Vec<_> = patch
+        .enchantments
+        .iter()
+        .filter_map(|(enchantment, level)| {
+            Some((DataRegistry::Enchantment.id(enchantment)?, *level))
+        })
+        .collect();
+    if !enchantments.is_empty() {
+        buf.write_var_int(&VarInt(DataComponent::Enchantments.id()))?;
+        buf.write_var_int(&VarInt(enchantments.len() as i32))?;
+        for (id, level) in enchantments {
+            buf.write_var_int(&VarInt(id))?;
+            buf.write_var_int(&VarInt(level))?;
+        }
+        count += 1;
+    }
+
+    if let Some(color) = patch.dyed_color {
+        buf.write_var_int(&VarInt(DataComponent::DyedColor.id()))?;
+        buf.write_i32_be(color)?;
+        count += 1;
+    }
+
+    if !patch.bundle_contents.is_empty() {
+        buf.write_var_int(&VarInt(DataComponent::BundleContents.id()))?;
+        buf.write_var_int(&VarInt(patch.bundle_contents.len() as i32))?;
+        for stack in &patch.bundle_contents {
+            write_nested_stack(&mut buf, stack)?;
+        }
+        count += 1;
+    }
+
+    let trim = patch.trim.as_ref().and_then(|trim| {
+        Some((
+            DataRegistry::TrimMaterial.id(&trim.material)?,
+            DataRegistry::TrimPattern.id(&trim.pattern)?,
+        ))
+    });
+    if let Some((material, pattern)) = trim {
+        buf.write_var_int(&VarInt(DataComponent::Trim.id()))?;
+        // Registry holders: the id + 1, as 0 would be followed by an inlined entry
+        buf.write_var_int(&VarInt(material + 1))?;
+        buf.write_var_int(&VarInt(pattern + 1))?;
+        count += 1;
+    }
+
+    if let Some(tracker) = &patch.lodestone_tracker {
+        buf.write_var_int(&VarInt(DataComponent::LodestoneTracker.id()))?;
+        buf.write_bool(tracker.target.is_some())?;
+        if let Some((dimension, pos)) = &tracker.target {
+            buf.write_string(dimension)?;
+            buf.write_i64_be(pos.to_i64())?;
+        }
+        buf.write_bool(tracker.tracked)?;
+        count += 1;
+    }
+
+    if !patch.banner_patterns.is_empty() {
+        buf.write_var_int(&VarInt(DataComponent::BannerPatterns.id()))?;
+        buf.write_var_int(&VarInt(patch.banner_patterns.len() as i32))?;
+        for layer in &patch.banner_patterns {
+            // Direct holder: 0 followed by the inlined pattern
+            buf.write_var_int(&VarInt(0))?;
+            buf.write_string(&format!("minecraft:{}", layer.pattern))?;
+            buf.write_string(&format!("block.minecraft.banner.{}", layer.pattern))?;
+            buf.write_var_int(&VarInt(layer.color as i32))?;
+        }
+        count += 1;
+    }
+
+    if !patch.container.is_empty() {
+        buf.write_var_int(&VarInt(DataComponent::Container.id()))?;
+        buf.write_var_int(&VarInt(patch.container.len() as i32))?;
+        for stack in &patch.container {
+            write_nested_stack(&mut buf, stack)?;
+        }
+        count += 1;
+    }
+
+    Ok((count, buf))
+}
+
+/// Writes an item stack which is part of another item's components, empty stacks are allowed.
+fn write_nested_stack(buf: &mut Vec<u8>, stack: &ItemStack) -> Result<(), WritingError> {
+    if stack.is_empty() {
+        return buf.write_var_int(&VarInt(0));
+    }
+    let (components_to_add, components) = write_components(&stack.patch)?;
+    buf.write_var_int(&VarInt(stack.item_count.into()))?;
+    buf.write_var_int(&VarInt(stack.item.id.into()))?;
+    buf.write_var_int(&VarInt(components_to_add))?;
+    buf.write_var_int(&VarInt(0))?;
+    buf.write_slice(&components)
+}
+
 impl ItemStackSerializer<'_> {
     pub fn to_stack(self) -> ItemStack {
         self.0.into_owned()
@@ -115,7 +243,7 @@ impl From<Option<ItemStack>> for ItemStackSerializer<'_> {
     fn from(item: Option<ItemStack>) -> Self {
         match item {
             Some(item) => ItemStackSerializer::from(item),
-            None => ItemStackSerializer(Cow::Borrowed(&ItemStack::EMPTY)),
+            None => ItemStackSerializer(Cow::Owned(ItemStack::EMPTY)),
         }
     }
 }
@@ -1,4 +1,5 @@
 pub mod bit_set;
+pub mod data_component;
 pub mod identifier;
 pub mod item_stack_seralizer;
 pub mod var_int;
@@ -23,7 +23,7 @@ pub struct VarInt(pub VarIntType);
 
 impl VarInt {
     /// The maximum number of bytes a `VarInt` can occupy.
-    const MAX_SIZE: NonZeroUsize = NonZeroUsize::new(5).unwrap();
+    pub(crate) const MAX_SIZE: NonZeroUsize = NonZeroUsize::new(5).unwrap();
 
     /// Returns the exact number of bytes this VarInt will write when
     /// [`Encode::encode`] is called, assuming no error occurs.
@@ -35,7 +35,8 @@ impl VarInt {
     }
 
     pub fn encode(&self, write: &mut impl Write) -> Result<(), WritingError> {
-        let mut val = self.0;
+        // Shifted unsigned, negative values would never reach 0 otherwise
+        let mut val = self.0 as u32;
         for _ in 0..Self::MAX_SIZE.get() {
             let b: u8 = val as u8 & 0b01111111;
             val >>= 7;
@@ -84,7 +85,7 @@ impl VarInt {
         &self,
         write: &mut (impl AsyncWrite + Unpin),
     ) -> Result<(), WritingError> {
-        let mut val = self.0;
+        let mut val = self.0 as u32;
         for _ in 0..Self::MAX_SIZE.get() {
             let b: u8 = val as u8 & 0b01111111;
             val >>= 7;
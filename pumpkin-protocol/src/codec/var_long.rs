@@ -33,7 +33,7 @@ impl VarLong {
     }
 
     pub fn encode(&self, write: &mut impl Write) -> Result<(), WritingError> {
-        let mut x = self.0;
+        let mut x = self.0 as u64;
         for _ in 0..Self::MAX_SIZE.get() {
             let byte = (x & 0x7F) as u8;
             x >>= 7;
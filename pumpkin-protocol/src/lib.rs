@@ -19,11 +19,14 @@ pub mod client;
 pub mod codec;
 pub mod packet_decoder;
 pub mod packet_encoder;
+#[cfg(all(test, feature = "packets"))]
+mod packet_id_check;
 #[cfg(feature = "query")]
 pub mod query;
 pub mod ser;
 #[cfg(feature = "serverbound")]
 pub mod server;
+pub mod strict;
 
 pub const MAX_PACKET_SIZE: u64 = 2097152;
 pub const MAX_PACKET_DATA_SIZE: usize = 8388608;
@@ -43,6 +46,16 @@ pub type CompressionThreshold = usize;
 /// increase CPU usage.
 pub type CompressionLevel = u32;
 
+/// Whether a packet type may be compressed once compression is enabled.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum CompressionPolicy {
+    /// Compressed when it reaches the threshold and compression actually makes it smaller.
+    #[default]
+    Threshold,
+    /// Never compressed, for payloads which are already compressed or otherwise incompressible.
+    Never,
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ConnectionState {
     HandShake,
@@ -332,6 +345,12 @@ pub struct RawPacket {
 pub trait ClientPacket: Packet {
     fn write_packet_data(&self, write: impl Write) -> Result<(), WritingError>;
 
+    /// Decodes `data`, written by [`ClientPacket::write_packet_data`], back to check it, see
+    /// [`strict::check_packet`]. Packets with a hand written layout aren't decoded.
+    fn check_packet_data(&self, _data: &[u8]) -> Result<(), strict::MalformedPacket> {
+        Ok(())
+    }
+
     fn write(&self, write: impl Write) -> Result<(), WritingError> {
         let mut write = write;
         write.write_var_int(&VarInt(Self::PACKET_ID))?;
@@ -343,7 +362,7 @@ pub trait ServerPacket: Packet + Sized {
     fn read(read: impl Read) -> Result<Self, ReadingError>;
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct StatusResponse {
     /// The version on which the server is running. (Optional)
     pub version: Option<Version>,
@@ -356,7 +375,7 @@ pub struct StatusResponse {
     /// Whether players are forced to use secure chat.
     pub enforce_secure_chat: bool,
 }
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct Version {
     /// The name of the version (e.g. 1.21.4)
     pub name: String,
@@ -364,7 +383,7 @@ pub struct Version {
     pub protocol: u32,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct Players {
     /// The maximum player count that the server allows.
     pub max: u32,
@@ -375,7 +394,7 @@ pub struct Players {
     pub sample: Vec<Sample>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 pub struct Sample {
     /// The player's name.
     pub name: String,
@@ -74,6 +74,7 @@ impl<R: AsyncRead + Unpin> AsyncRead for DecryptionReader<R> {
 pub struct NetworkDecoder<R: AsyncRead + Unpin> {
     reader: DecryptionReader<R>,
     compression: Option<CompressionThreshold>,
+    max_packet_size: u64,
 }
 
 impl<R: AsyncRead + Unpin> NetworkDecoder<R> {
@@ -81,9 +82,16 @@ impl<R: AsyncRead + Unpin> NetworkDecoder<R> {
         Self {
             reader: DecryptionReader::None(reader),
             compression: None,
+            max_packet_size: MAX_PACKET_SIZE,
         }
     }
 
+    /// Lowers the largest frame accepted from the client, it can't be raised above
+    /// [`MAX_PACKET_SIZE`].
+    pub fn set_max_packet_size(&mut self, size: u64) {
+        self.max_packet_size = size.min(MAX_PACKET_SIZE);
+    }
+
     pub fn set_compression(&mut self, threshold: CompressionThreshold) {
         self.compression = Some(threshold);
     }
@@ -105,24 +113,27 @@ impl<R: AsyncRead + Unpin> NetworkDecoder<R> {
                 err => PacketDecodeError::MalformedLength(err.to_string()),
             })?;
 
+        // Negative lengths wrap around and are rejected too
         let packet_len = packet_len.0 as u64;
 
-        if !(0..=MAX_PACKET_SIZE).contains(&packet_len) {
-            Err(PacketDecodeError::OutOfBounds)?
+        if packet_len > self.max_packet_size {
+            Err(PacketDecodeError::OutOfBounds(packet_len))?
         }
 
         let mut bounded_reader = (&mut self.reader).take(packet_len);
 
+        let mut decompressed = None;
         let mut reader = if let Some(threshold) = self.compression {
             let decompressed_length = VarInt::decode_async(&mut bounded_reader).await?;
             let raw_packet_length = packet_len as usize - decompressed_length.written_size();
             let decompressed_length = decompressed_length.0 as usize;
 
-            if !(0..=MAX_PACKET_DATA_SIZE).contains(&decompressed_length) {
-                Err(PacketDecodeError::TooLong)?
+            if decompressed_length > MAX_PACKET_DATA_SIZE {
+                Err(PacketDecodeError::TooLong(decompressed_length))?
             }
 
             if decompressed_length > 0 {
+                decompressed = Some(decompressed_length);
                 DecompressionReader::Decompress(ZlibDecoder::new(BufReader::new(bounded_reader)))
             } else {
                 // Validate that we are not less than the compression threshold
@@ -141,14 +152,33 @@ impl<R: AsyncRead + Unpin> NetworkDecoder<R> {
 
         let packet_id = VarInt::decode_async(&mut reader)
             .await
-            .map_err(|_| PacketDecodeError::DecodeID)?
-            .0;
+            .map_err(|_| PacketDecodeError::DecodeID)?;
 
         let mut payload = Vec::new();
-        reader
-            .read_to_end(&mut payload)
-            .await
-            .map_err(|err| PacketDecodeError::FailedDecompression(err.to_string()))?;
+        match decompressed {
+            Some(decompressed_length) => {
+                // Never inflate more than the client declared, otherwise a few compressed bytes
+                // could expand into gigabytes
+                let expected = decompressed_length.saturating_sub(packet_id.written_size());
+                reader
+                    .take(expected as u64 + 1)
+                    .read_to_end(&mut payload)
+                    .await
+                    .map_err(|err| PacketDecodeError::FailedDecompression(err.to_string()))?;
+                if payload.len() != expected {
+                    Err(PacketDecodeError::WrongDecompressedLength(
+                        decompressed_length,
+                    ))?
+                }
+            }
+            None => {
+                reader
+                    .read_to_end(&mut payload)
+                    .await
+                    .map_err(|err| PacketDecodeError::FailedDecompression(err.to_string()))?;
+            }
+        }
+        let packet_id = packet_id.0;
 
         Ok(RawPacket {
             id: packet_id,
@@ -161,16 +191,18 @@ impl<R: AsyncRead + Unpin> NetworkDecoder<R> {
 pub enum PacketDecodeError {
     #[error("failed to decode packet ID")]
     DecodeID,
-    #[error("packet exceeds maximum length")]
-    TooLong,
-    #[error("packet length is out of bounds")]
-    OutOfBounds,
+    #[error("decompressed packet length {0} exceeds the maximum")]
+    TooLong(usize),
+    #[error("packet length {0} is out of bounds")]
+    OutOfBounds(u64),
     #[error("malformed packet length VarInt: {0}")]
     MalformedLength(String),
     #[error("failed to decompress packet: {0}")]
     FailedDecompression(String), // Updated to include error details
     #[error("packet is uncompressed but greater than the threshold")]
     NotCompressed,
+    #[error("packet does not decompress to its declared length of {0}")]
+    WrongDecompressedLength(usize),
     #[error("the connection has closed")]
     ConnectionClosed,
 }
@@ -437,4 +469,76 @@ mod tests {
         assert_eq!(raw_packet.id, packet_id);
         assert_eq!(raw_packet.payload.as_ref(), payload);
     }
+
+    /// Test that a frame claiming more than the maximum length is rejected before reading it
+    #[tokio::test]
+    async fn test_decode_rejects_oversized_frame() {
+        let mut packet = Vec::new();
+        packet
+            .write_var_int(&VarInt(MAX_PACKET_SIZE as i32 + 1))
+            .unwrap();
+        // Only the length arrives, the decoder must not wait for or allocate the rest
+        let mut decoder = NetworkDecoder::new(packet.as_slice());
+        assert!(matches!(
+            decoder.get_raw_packet().await,
+            Err(PacketDecodeError::OutOfBounds(len)) if len == MAX_PACKET_SIZE + 1
+        ));
+
+        let mut packet = Vec::new();
+        packet.write_var_int(&VarInt(-1)).unwrap();
+        let mut decoder = NetworkDecoder::new(packet.as_slice());
+        assert!(matches!(
+            decoder.get_raw_packet().await,
+            Err(PacketDecodeError::OutOfBounds(_))
+        ));
+
+        // A configured limit applies to frames below the protocol maximum
+        let packet = build_packet(1, &[0; 100], false, None, None);
+        let mut decoder = NetworkDecoder::new(packet.as_slice());
+        decoder.set_max_packet_size(50);
+        assert!(matches!(
+            decoder.get_raw_packet().await,
+            Err(PacketDecodeError::OutOfBounds(101))
+        ));
+    }
+
+    /// Test that a tiny compressed packet inflating far beyond its declared length is rejected
+    #[tokio::test]
+    async fn test_decode_rejects_zip_bomb() {
+        let packet_id = 3;
+        let mut data = Vec::new();
+        data.write_var_int(&VarInt(packet_id)).unwrap();
+        data.resize(MAX_PACKET_DATA_SIZE * 4, 0);
+        let compressed = compress_zlib(&data);
+
+        // Claims to be small, but inflates to four times the maximum
+        let mut buffer = Vec::new();
+        buffer.write_var_int(&VarInt(64)).unwrap();
+        buffer.write_slice(&compressed).unwrap();
+        let mut packet = Vec::new();
+        packet.write_var_int(&VarInt(buffer.len() as i32)).unwrap();
+        packet.write_slice(&buffer).unwrap();
+
+        let mut decoder = NetworkDecoder::new(packet.as_slice());
+        decoder.set_compression(16);
+        assert!(matches!(
+            decoder.get_raw_packet().await,
+            Err(PacketDecodeError::WrongDecompressedLength(64))
+        ));
+
+        // Declaring the real length is rejected before inflating anything
+        let mut buffer = Vec::new();
+        buffer.write_var_int(&VarInt(data.len() as i32)).unwrap();
+        buffer.write_slice(&compressed).unwrap();
+        let mut packet = Vec::new();
+        packet.write_var_int(&VarInt(buffer.len() as i32)).unwrap();
+        packet.write_slice(&buffer).unwrap();
+
+        let mut decoder = NetworkDecoder::new(packet.as_slice());
+        decoder.set_compression(16);
+        assert!(matches!(
+            decoder.get_raw_packet().await,
+            Err(PacketDecodeError::TooLong(len)) if len == data.len()
+        ));
+    }
 }
@@ -5,8 +5,8 @@ use thiserror::Error;
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
 use crate::{
-    Aes128Cfb8Enc, CompressionLevel, CompressionThreshold, MAX_PACKET_DATA_SIZE, MAX_PACKET_SIZE,
-    StreamEncryptor, VarInt,
+    Aes128Cfb8Enc, CompressionLevel, CompressionPolicy, CompressionThreshold, MAX_PACKET_DATA_SIZE,
+    MAX_PACKET_SIZE, StreamEncryptor, VarInt,
 };
 
 // raw -> compress -> encrypt
@@ -83,6 +83,8 @@ pub struct NetworkEncoder<W: AsyncWrite + Unpin> {
     writer: EncryptionWriter<W>,
     // compression and compression threshold
     compression: Option<(CompressionThreshold, CompressionLevel)>,
+    /// Whether large packets which barely shrink are sent uncompressed
+    skip_incompressible: bool,
 }
 
 impl<W: AsyncWrite + Unpin> NetworkEncoder<W> {
@@ -90,6 +92,7 @@ impl<W: AsyncWrite + Unpin> NetworkEncoder<W> {
         Self {
             writer: EncryptionWriter::None(writer),
             compression: None,
+            skip_incompressible: true,
         }
     }
 
@@ -97,6 +100,10 @@ impl<W: AsyncWrite + Unpin> NetworkEncoder<W> {
         self.compression = Some(compression_info);
     }
 
+    pub fn set_skip_incompressible(&mut self, skip_incompressible: bool) {
+        self.skip_incompressible = skip_incompressible;
+    }
+
     /// NOTE: Encryption can only be set; a minecraft stream cannot go back to being unencrypted
     pub fn set_encryption(&mut self, key: &[u8; 16]) {
         if matches!(self.writer, EncryptionWriter::Encrypt(_)) {
@@ -137,6 +144,19 @@ impl<W: AsyncWrite + Unpin> NetworkEncoder<W> {
     /// -   `Packet ID`: The ID of the packet.
     /// -   `Data`: The packet's data.
     pub async fn write_packet(&mut self, packet_data: Bytes) -> Result<(), PacketEncodeError> {
+        self.write_packet_with_policy(packet_data, CompressionPolicy::Threshold)
+            .await
+    }
+
+    /// Like [`Self::write_packet`], but packets are only compressed if the `policy` allows it.
+    ///
+    /// Unless disabled, large packets which barely shrink when compressing a sample of them are
+    /// sent uncompressed as well, even above the threshold, which the protocol allows.
+    pub async fn write_packet_with_policy(
+        &mut self,
+        packet_data: Bytes,
+        policy: CompressionPolicy,
+    ) -> Result<(), PacketEncodeError> {
         // We need to know the length of the compressed buffer and serde is not async :(
         // We need to write to a buffer here 😔
 
@@ -152,29 +172,21 @@ impl<W: AsyncWrite + Unpin> NetworkEncoder<W> {
         })?;
 
         if let Some((compression_threshold, compression_level)) = self.compression {
-            if data_len >= compression_threshold {
+            let compressed = if policy == CompressionPolicy::Threshold
+                && data_len >= compression_threshold
+                && !(self.skip_incompressible
+                    && is_incompressible(&packet_data, compression_level).await?)
+            {
+                Some(compress(&packet_data, compression_level).await?)
+            } else {
+                None
+            };
+
+            if let Some(compressed_buf) = compressed {
                 // Pushed before data:
                 // Length of (Data Length) + length of compressed (Packet ID + Data)
                 // Length of uncompressed (Packet ID + Data)
 
-                // TODO: We need the compressed length at the beginning of the packet so we need to write to
-                // buf here :( Is there a magic way to find a compressed length?
-                let mut compressed_buf = Vec::new();
-                let mut compressor = ZlibEncoder::with_quality(
-                    &mut compressed_buf,
-                    Level::Precise(compression_level as i32),
-                );
-
-                compressor
-                    .write_all(&packet_data)
-                    .await
-                    .map_err(|err| PacketEncodeError::Message(err.to_string()))?;
-                compressor
-                    .flush()
-                    .await
-                    .map_err(|err| PacketEncodeError::Message(err.to_string()))?;
-                debug_assert!(!compressed_buf.is_empty());
-
                 let full_packet_len_var_int: VarInt = (data_len_var_int.written_size()
                     + compressed_buf.len())
                 .try_into()
@@ -267,6 +279,47 @@ impl<W: AsyncWrite + Unpin> NetworkEncoder<W> {
     }
 }
 
+/// Large payloads get a sample of this size compressed first, so compressing payloads which
+/// hardly shrink (e.g. already compressed data) can be skipped.
+const COMPRESSION_SAMPLE_SIZE: usize = 1024;
+
+/// Whether a sample from the middle of `data` shrinks by less than a tenth when compressed.
+async fn is_incompressible(
+    data: &[u8],
+    compression_level: CompressionLevel,
+) -> Result<bool, PacketEncodeError> {
+    if data.len() < 4 * COMPRESSION_SAMPLE_SIZE {
+        return Ok(false);
+    }
+    let start = (data.len() - COMPRESSION_SAMPLE_SIZE) / 2;
+    let sample = &data[start..start + COMPRESSION_SAMPLE_SIZE];
+    let compressed = compress(sample, compression_level).await?;
+    Ok(compressed.len() * 10 > sample.len() * 9)
+}
+
+async fn compress(
+    data: &[u8],
+    compression_level: CompressionLevel,
+) -> Result<Vec<u8>, PacketEncodeError> {
+    // TODO: We need the compressed length at the beginning of the packet so we need to write to
+    // buf here :( Is there a magic way to find a compressed length?
+    let mut compressed_buf = Vec::new();
+    let mut compressor = ZlibEncoder::with_quality(
+        &mut compressed_buf,
+        Level::Precise(compression_level as i32),
+    );
+    compressor
+        .write_all(data)
+        .await
+        .map_err(|err| PacketEncodeError::Message(err.to_string()))?;
+    compressor
+        .flush()
+        .await
+        .map_err(|err| PacketEncodeError::Message(err.to_string()))?;
+    debug_assert!(!compressed_buf.is_empty());
+    Ok(compressed_buf)
+}
+
 #[derive(Error, Debug)]
 #[error("Invalid compression Level")]
 pub struct CompressionLevelError;
@@ -652,4 +705,69 @@ mod tests {
 
         assert_eq!(buffer, expected_payload);
     }
+
+    /// Test that payloads which don't compress are sent uncompressed, even above the threshold
+    #[tokio::test]
+    async fn test_encode_incompressible_payload_uncompressed() {
+        // Pseudo-random bytes, which zlib can't shrink
+        let mut state = 0x2545_f491_u32;
+        let data = (0..8192)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect();
+        let packet = MaxSizePacket { data };
+
+        let packet_bytes = build_packet_with_encoder(&packet, Some((256, 6)), None).await;
+        let mut buffer = &packet_bytes[..];
+
+        let packet_length = decode_varint(&mut buffer).expect("Failed to decode packet length");
+        assert_eq!(packet_length as usize, buffer.len());
+        let data_length = decode_varint(&mut buffer).expect("Failed to decode data length");
+        assert_eq!(
+            data_length, 0,
+            "Incompressible payload should not be compressed"
+        );
+        let decoded_packet_id = decode_varint(&mut buffer).expect("Failed to decode packet ID");
+        assert_eq!(decoded_packet_id, MaxSizePacket::PACKET_ID);
+
+        let mut expected_payload = Vec::new();
+        packet.write_packet_data(&mut expected_payload).unwrap();
+        assert_eq!(buffer, expected_payload);
+    }
+
+    /// Test that packet types which are never compressed skip compression
+    #[tokio::test]
+    async fn test_encode_never_compressed_packet() {
+        #[derive(Serialize)]
+        #[packet(STATUS_STATUS_RESPONSE, incompressible)]
+        struct PrecompressedPacket {
+            data: Vec<u8>,
+        }
+        assert_eq!(PrecompressedPacket::COMPRESSION, CompressionPolicy::Never);
+
+        let packet = PrecompressedPacket {
+            data: vec![0xAB; 4096],
+        };
+        let mut packet_buf = Vec::new();
+        packet.write(&mut packet_buf).unwrap();
+
+        let mut buf = Vec::new();
+        let mut encoder = NetworkEncoder::new(&mut buf);
+        encoder.set_compression((256, 6));
+        encoder
+            .write_packet_with_policy(packet_buf.clone().into(), PrecompressedPacket::COMPRESSION)
+            .await
+            .unwrap();
+
+        let mut buffer = &buf[..];
+        let packet_length = decode_varint(&mut buffer).expect("Failed to decode packet length");
+        assert_eq!(packet_length as usize, buffer.len());
+        let data_length = decode_varint(&mut buffer).expect("Failed to decode data length");
+        assert_eq!(data_length, 0);
+        assert_eq!(buffer, packet_buf);
+    }
 }
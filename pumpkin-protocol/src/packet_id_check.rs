@@ -0,0 +1,293 @@
+//! Checks that no two packets of the same connection state and direction share an id, since the id
+//! is the only thing the other side has to tell them apart.
+//!
+//! The ids are the `PACKET_ID`s the `#[packet]` attribute generates, so every packet has to be
+//! listed here. This check is what found `CLevelEvent`, a second packet for the id of
+//! `CWorldEvent`, which was removed.
+
+use std::collections::BTreeMap;
+
+use crate::ser::packet::Packet;
+
+/// The names and ids of packet types.
+macro_rules! packet_ids {
+    ($($packet:ident),* $(,)?) => {
+        vec![$((stringify!($packet), $packet::PACKET_ID)),*]
+    };
+}
+
+/// Every id which is used by more than one packet, along with the names of those packets.
+fn find_collisions<'a>(
+    packets: impl IntoIterator<Item = (&'a str, i32)>,
+) -> Vec<(i32, Vec<&'a str>)> {
+    let mut by_id: BTreeMap<i32, Vec<&str>> = BTreeMap::new();
+    for (name, id) in packets {
+        by_id.entry(id).or_default().push(name);
+    }
+    by_id
+        .into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .collect()
+}
+
+fn client_config() -> Vec<(&'static str, i32)> {
+    use crate::client::config::*;
+
+    packet_ids![
+        CConfigAddResourcePack,
+        CConfigDisconnect,
+        CConfigServerLinks,
+        CCookieRequest,
+        CFinishConfig,
+        CKnownPacks,
+        CPluginMessage,
+        CRegistryData,
+        CStoreCookie,
+        CTransfer,
+        CUpdateTags
+    ]
+}
+
+fn client_login() -> Vec<(&'static str, i32)> {
+    use crate::client::login::*;
+
+    packet_ids![
+        CEncryptionRequest,
+        CLoginCookieRequest,
+        CLoginDisconnect,
+        CLoginPluginRequest,
+        CLoginSuccess,
+        CSetCompression
+    ]
+}
+
+fn client_play() -> Vec<(&'static str, i32)> {
+    use crate::client::play::*;
+
+    packet_ids![
+        CAcknowledgeBlockChange,
+        CActionBar,
+        CBlockAction,
+        CBlockEntityData,
+        CBlockUpdate,
+        CBossEvent,
+        CCenterChunk,
+        CChangeDifficulty,
+        CChunkBatchEnd,
+        CChunkBatchStart,
+        CChunkData,
+        CClearTitle,
+        CCloseContainer,
+        CCombatDeath,
+        CCommandSuggestions,
+        CCommands,
+        CDamageEvent,
+        CDisguisedChatMessage,
+        CDisplayObjective,
+        CEntityAnimation,
+        CEntityPositionSync,
+        CEntitySoundEffect,
+        CEntityStatus,
+        CEntityVelocity,
+        CExplosion,
+        CGameEvent,
+        CHeadRot,
+        CHurtAnimation,
+        CInitializeWorldBorder,
+        CKeepAlive,
+        CLinkEntities,
+        CLogin,
+        CMultiBlockUpdate,
+        COpenScreen,
+        COpenSignEditor,
+        CParticle,
+        CPing,
+        CPingResponse,
+        CPlayCookieRequest,
+        CPlayDisconnect,
+        CPlayServerLinks,
+        CPlayerAbilities,
+        CPlayerChatMessage,
+        CPlayerInfoUpdate,
+        CPlayerPosition,
+        CRemoveEntities,
+        CRemoveMobEffect,
+        CRemovePlayerInfo,
+        CResetScore,
+        CRespawn,
+        CServerData,
+        CSetBlockDestroyStage,
+        CSetBorderCenter,
+        CSetBorderLerpSize,
+        CSetBorderSize,
+        CSetBorderWarningDelay,
+        CSetBorderWarningDistance,
+        CSetContainerContent,
+        CSetContainerProperty,
+        CSetContainerSlot,
+        CSetDefaultSpawnPosition,
+        CSetEntityMetadata,
+        CSetEquipment,
+        CSetExperience,
+        CSetHealth,
+        CSetHeldItem,
+        CSetPassengers,
+        CSetSimulationDistance,
+        CSoundEffect,
+        CSpawnEntity,
+        CStartConfiguration,
+        CStopSound,
+        CStoreCookie,
+        CSubtitle,
+        CSystemChatMessage,
+        CTakeItemEntity,
+        CTeleportEntity,
+        CTickingState,
+        CTickingStep,
+        CTitleAnimation,
+        CTitleText,
+        CTransfer,
+        CUnloadChunk,
+        CUpdateAdvancements,
+        CUpdateAttributes,
+        CUpdateEntityPos,
+        CUpdateEntityPosRot,
+        CUpdateEntityRot,
+        CUpdateMobEffect,
+        CUpdateObjectives,
+        CUpdateScore,
+        CUpdateTeams,
+        CUpdateTime,
+        CWorldEvent
+    ]
+}
+
+fn client_status() -> Vec<(&'static str, i32)> {
+    use crate::client::status::*;
+
+    packet_ids![CPingResponse, CStatusResponse]
+}
+
+fn server_config() -> Vec<(&'static str, i32)> {
+    use crate::server::config::*;
+
+    packet_ids![
+        SAcknowledgeFinishConfig,
+        SClientInformationConfig,
+        SConfigCookieResponse,
+        SConfigResourcePack,
+        SKnownPacks,
+        SPluginMessage
+    ]
+}
+
+fn server_handshake() -> Vec<(&'static str, i32)> {
+    use crate::server::handshake::*;
+
+    packet_ids![SHandShake]
+}
+
+fn server_login() -> Vec<(&'static str, i32)> {
+    use crate::server::login::*;
+
+    packet_ids![
+        SEncryptionResponse,
+        SLoginAcknowledged,
+        SLoginCookieResponse,
+        SLoginPluginResponse,
+        SLoginStart
+    ]
+}
+
+fn server_play() -> Vec<(&'static str, i32)> {
+    use crate::server::play::*;
+
+    packet_ids![
+        SChatAck,
+        SChatCommand,
+        SChatCommandSigned,
+        SChatMessage,
+        SChunkBatch,
+        SClickContainer,
+        SClientCommand,
+        SClientInformationPlay,
+        SClientTickEnd,
+        SCloseContainer,
+        SCommandSuggestion,
+        SConfigurationAcknowledged,
+        SConfirmTeleport,
+        SCookieResponse,
+        SInteract,
+        SKeepAlive,
+        SPickItemFromBlock,
+        SPickItemFromEntity,
+        SPlayPingRequest,
+        SPlayerAbilities,
+        SPlayerAction,
+        SPlayerCommand,
+        SPlayerInput,
+        SPlayerLoaded,
+        SPlayerPosition,
+        SPlayerPositionRotation,
+        SPlayerRotation,
+        SPlayerSession,
+        SPong,
+        SSetCreativeSlot,
+        SSetHeldItem,
+        SSetPlayerGround,
+        SSwingArm,
+        SUpdateSign,
+        SUseItem,
+        SUseItemOn
+    ]
+}
+
+fn server_status() -> Vec<(&'static str, i32)> {
+    use crate::server::status::*;
+
+    packet_ids![SStatusPingRequest, SStatusRequest]
+}
+
+#[test]
+fn packet_ids_are_unique() {
+    for (packets, ids) in [
+        ("config clientbound", client_config()),
+        ("login clientbound", client_login()),
+        ("play clientbound", client_play()),
+        ("status clientbound", client_status()),
+        ("config serverbound", server_config()),
+        ("handshake serverbound", server_handshake()),
+        ("login serverbound", server_login()),
+        ("play serverbound", server_play()),
+        ("status serverbound", server_status()),
+    ] {
+        let collisions = find_collisions(ids);
+        assert!(
+            collisions.is_empty(),
+            "{packets} packets share ids: {collisions:?}"
+        );
+    }
+}
+
+mod fixture {
+    use pumpkin_data::packet::clientbound::PLAY_SET_TIME;
+    use pumpkin_macros::packet;
+
+    /// Accidentally uses the id of `CUpdateTime`.
+    #[packet(PLAY_SET_TIME)]
+    pub struct CCollidingTime;
+}
+
+#[test]
+fn colliding_packets_are_found() {
+    use crate::client::play::{CTitleText, CUpdateTime};
+    use fixture::CCollidingTime;
+    use pumpkin_data::packet::clientbound::PLAY_SET_TIME;
+
+    let collisions = find_collisions(packet_ids![CUpdateTime, CTitleText, CCollidingTime]);
+    assert_eq!(
+        collisions,
+        vec![(PLAY_SET_TIME, vec!["CUpdateTime", "CCollidingTime"])]
+    );
+    assert!(find_collisions(packet_ids![CUpdateTime]).is_empty());
+}
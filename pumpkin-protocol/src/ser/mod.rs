@@ -13,7 +13,7 @@ pub mod packet;
 pub mod serializer;
 
 // TODO: This is a bit hacky
-const NO_PREFIX_MARKER: &str = "__network_no_prefix";
+pub(crate) const NO_PREFIX_MARKER: &str = "__network_no_prefix";
 
 pub fn network_serialize_no_prefix<T, S>(input: T, serializer: S) -> Result<S::Ok, S::Error>
 where
@@ -78,6 +78,14 @@ pub trait NetworkReadExt {
         &mut self,
         parse: impl Fn(&mut Self) -> Result<G, ReadingError>,
     ) -> Result<Vec<G>, ReadingError>;
+
+    /// Reads a list behind a present flag, keeping an absent list apart from an empty one.
+    fn get_option_list<G>(
+        &mut self,
+        parse: impl Fn(&mut Self) -> Result<G, ReadingError>,
+    ) -> Result<Option<Vec<G>>, ReadingError> {
+        self.get_option(|read| read.get_list(parse))
+    }
 }
 
 impl<R: Read> NetworkReadExt for R {
@@ -273,6 +281,9 @@ pub trait NetworkWriteExt {
     fn write_f32_be(&mut self, data: f32) -> Result<(), WritingError>;
     fn write_f64_be(&mut self, data: f64) -> Result<(), WritingError>;
     fn write_slice(&mut self, data: &[u8]) -> Result<(), WritingError>;
+    /// Writes the longs back to back without a length prefix. Same bytes as calling
+    /// `write_i64_be` for each of them, but the writer only gets called once per 64 longs.
+    fn write_i64_be_slice(&mut self, data: &[i64]) -> Result<(), WritingError>;
 
     fn write_bool(&mut self, data: bool) -> Result<(), WritingError> {
         if data {
@@ -324,6 +335,15 @@ pub trait NetworkWriteExt {
         Ok(())
     }
 
+    /// Writes a present flag and, if there is a list, its length and items.
+    fn write_option_list<G>(
+        &mut self,
+        list: Option<&[G]>,
+        writer: impl Fn(&mut Self, &G) -> Result<(), WritingError>,
+    ) -> Result<(), WritingError> {
+        self.write_option(&list, |write, list| write.write_list(list, &writer))
+    }
+
     fn write_nbt(&mut self, data: &NbtTag) -> Result<(), WritingError>;
 }
 
@@ -382,6 +402,19 @@ impl<W: Write> NetworkWriteExt for W {
         self.write_all(data).map_err(WritingError::IoError)
     }
 
+    fn write_i64_be_slice(&mut self, data: &[i64]) -> Result<(), WritingError> {
+        // Converted on the stack, a few longs at a time, so there is no allocation per array
+        const LONGS: usize = 64;
+        let mut bytes = [0; LONGS * size_of::<i64>()];
+        for longs in data.chunks(LONGS) {
+            for (long, bytes) in longs.iter().zip(bytes.chunks_exact_mut(size_of::<i64>())) {
+                bytes.copy_from_slice(&long.to_be_bytes());
+            }
+            self.write_slice(&bytes[..size_of_val(longs)])?;
+        }
+        Ok(())
+    }
+
     fn write_var_int(&mut self, data: &VarInt) -> Result<(), WritingError> {
         data.encode(self)
     }
@@ -455,9 +488,46 @@ mod test {
 
     use crate::{
         VarInt,
-        ser::{deserializer, serializer},
+        ser::{NetworkReadExt, NetworkWriteExt, deserializer, serializer},
     };
 
+    #[test]
+    fn i64_slice_matches_single_writes() {
+        let longs = [0, 1, -1, i64::MIN, i64::MAX, 0x0123_4567_89AB_CDEF];
+        let mut single = Vec::new();
+        for long in longs {
+            single.write_i64_be(long).unwrap();
+        }
+        let mut bulk = Vec::new();
+        bulk.write_i64_be_slice(&longs).unwrap();
+        assert_eq!(single, bulk);
+    }
+
+    #[test]
+    fn option_list_keeps_empty_and_absent_apart() {
+        for list in [Some(vec![3, -1, 300]), Some(Vec::new()), None] {
+            let mut bytes = Vec::new();
+            bytes
+                .write_option_list(list.as_deref(), |write, v| write.write_var_int(&VarInt(*v)))
+                .unwrap();
+            let read = Cursor::new(&bytes)
+                .get_option_list(|read| Ok(read.get_var_int()?.0))
+                .unwrap();
+            assert_eq!(read, list);
+        }
+
+        let mut empty = Vec::new();
+        empty
+            .write_option_list::<i32>(Some(&[]), |_, _| unreachable!())
+            .unwrap();
+        assert_eq!(empty, [1, 0]);
+        let mut absent = Vec::new();
+        absent
+            .write_option_list::<i32>(None, |_, _| unreachable!())
+            .unwrap();
+        assert_eq!(absent, [0]);
+    }
+
     #[test]
     fn test_i32_reserialize() {
         #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Debug)]
@@ -1,6 +1,10 @@
 use serde::{Serialize, de::DeserializeOwned};
 
-use crate::{ClientPacket, ServerPacket, codec::var_int::VarIntType};
+use crate::{
+    ClientPacket, CompressionPolicy, ServerPacket,
+    codec::var_int::VarIntType,
+    strict::{self, MalformedPacket},
+};
 
 use super::{
     Read, ReadingError, Write, WritingError, deserializer,
@@ -9,6 +13,7 @@ use super::{
 
 pub trait Packet {
     const PACKET_ID: VarIntType;
+    const COMPRESSION: CompressionPolicy = CompressionPolicy::Threshold;
 }
 
 impl<P> ClientPacket for P
@@ -19,6 +24,10 @@ where
         let mut serializer = serializer::Serializer::new(write);
         self.serialize(&mut serializer)
     }
+
+    fn check_packet_data(&self, data: &[u8]) -> Result<(), MalformedPacket> {
+        strict::check_fields(self, data)
+    }
 }
 
 impl<P> ServerPacket for P
@@ -1,12 +1,48 @@
+use std::io::Read;
+
 use pumpkin_data::packet::serverbound::CONFIG_SELECT_KNOWN_PACKS;
 use pumpkin_macros::packet;
-use serde::Serialize;
 
-use crate::VarInt;
+use crate::{
+    ServerPacket,
+    ser::{NetworkReadExt, ReadingError},
+};
+
+/// The Notchian (vanilla) server rejects responses with more packs than this.
+const MAX_KNOWN_PACKS: usize = 64;
+
+/// A data pack the client already has, so it doesn't need to be sent its registry entries.
+pub struct ClientKnownPack {
+    pub namespace: String,
+    pub id: String,
+    pub version: String,
+}
 
-#[derive(serde::Deserialize, Serialize)]
 #[packet(CONFIG_SELECT_KNOWN_PACKS)]
 pub struct SKnownPacks {
-    pub known_pack_count: VarInt,
-    // known_packs: &'a [KnownPack]
+    pub known_packs: Vec<ClientKnownPack>,
+}
+
+impl ServerPacket for SKnownPacks {
+    fn read(read: impl Read) -> Result<Self, ReadingError> {
+        let mut read = read;
+
+        let count = read.get_var_int()?.0;
+        let count = usize::try_from(count)
+            .map_err(|_| ReadingError::Message(format!("negative known pack count {count}")))?;
+        if count > MAX_KNOWN_PACKS {
+            return Err(ReadingError::TooLarge("SKnownPacks".to_string()));
+        }
+
+        let known_packs = (0..count)
+            .map(|_| {
+                Ok(ClientKnownPack {
+                    namespace: read.get_string()?,
+                    id: read.get_string()?,
+                    version: read.get_string()?,
+                })
+            })
+            .collect::<Result<_, ReadingError>>()?;
+        Ok(Self { known_packs })
+    }
 }
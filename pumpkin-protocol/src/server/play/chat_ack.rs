@@ -0,0 +1,13 @@
+use pumpkin_data::packet::serverbound::PLAY_CHAT_ACK;
+use pumpkin_macros::packet;
+use serde::Deserialize;
+
+use crate::VarInt;
+
+/// Sent when the client has seen too many signed messages without sending a chat message which
+/// acknowledges them.
+#[derive(Deserialize)]
+#[packet(PLAY_CHAT_ACK)]
+pub struct SChatAck {
+    pub offset: VarInt,
+}
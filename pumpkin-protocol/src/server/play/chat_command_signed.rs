@@ -0,0 +1,112 @@
+use std::io::Read;
+
+use pumpkin_data::packet::serverbound::PLAY_CHAT_COMMAND_SIGNED;
+use pumpkin_macros::packet;
+use serde::Serialize;
+
+use crate::{
+    ServerPacket, VarInt,
+    ser::{NetworkReadExt, ReadingError},
+};
+
+/// The most message arguments a client signs in one command. Vanilla: 8
+pub const MAX_ARGUMENT_SIGNATURES: usize = 8;
+
+/// A command the client sent with signatures for its message arguments, like the message of `/msg`.
+#[derive(Serialize)]
+#[packet(PLAY_CHAT_COMMAND_SIGNED)]
+pub struct SChatCommandSigned {
+    pub command: String,
+    pub timestamp: i64,
+    pub salt: i64,
+    pub argument_signatures: Box<[ArgumentSignature]>,
+    pub message_count: VarInt,
+    pub acknowledged: Box<[u8]>, // Bitset fixed 20 bits
+    pub checksum: u8,
+}
+
+#[derive(Serialize)]
+pub struct ArgumentSignature {
+    /// The name of the signed argument in the command tree
+    pub name: String,
+    pub signature: Box<[u8]>, // always 256
+}
+
+impl ServerPacket for SChatCommandSigned {
+    fn read(read: impl Read) -> Result<Self, ReadingError> {
+        let mut read = read;
+
+        let command = read.get_string_bounded(32767)?;
+        let timestamp = read.get_i64_be()?;
+        let salt = read.get_i64_be()?;
+
+        let count = read.get_var_int()?.0;
+        if !(0..=MAX_ARGUMENT_SIGNATURES as i32).contains(&count) {
+            return Err(ReadingError::TooLarge(format!(
+                "{count} argument signatures, at most {MAX_ARGUMENT_SIGNATURES} are allowed"
+            )));
+        }
+        let argument_signatures = (0..count)
+            .map(|_| {
+                Ok(ArgumentSignature {
+                    name: read.get_string_bounded(16)?,
+                    signature: read.read_boxed_slice(256)?,
+                })
+            })
+            .collect::<Result<_, ReadingError>>()?;
+
+        Ok(Self {
+            command,
+            timestamp,
+            salt,
+            argument_signatures,
+            message_count: read.get_var_int()?,
+            acknowledged: read.get_fixed_bitset(20)?,
+            checksum: read.get_u8_be()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        ServerPacket, VarInt,
+        ser::{NetworkWriteExt, ReadingError},
+    };
+
+    use super::SChatCommandSigned;
+
+    fn write_command(signatures: i32) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.write_string("msg Steve hello").unwrap();
+        buf.write_i64_be(1_700_000_000_000).unwrap();
+        buf.write_i64_be(42).unwrap();
+        buf.write_var_int(&VarInt(signatures)).unwrap();
+        for _ in 0..signatures {
+            buf.write_string("message").unwrap();
+            buf.write_slice(&[7; 256]).unwrap();
+        }
+        buf.write_var_int(&VarInt(1)).unwrap();
+        buf.write_slice(&[1, 0, 0]).unwrap();
+        buf.write_u8_be(9).unwrap();
+        buf
+    }
+
+    #[test]
+    fn reads_argument_signatures() {
+        let packet = SChatCommandSigned::read(write_command(1).as_slice()).unwrap();
+        assert_eq!(packet.command, "msg Steve hello");
+        assert_eq!(packet.salt, 42);
+        assert_eq!(packet.argument_signatures.len(), 1);
+        assert_eq!(packet.argument_signatures[0].name, "message");
+        assert_eq!(*packet.argument_signatures[0].signature, [7; 256]);
+        assert_eq!(packet.message_count.0, 1);
+        assert_eq!(*packet.acknowledged, [1, 0, 0]);
+        assert_eq!(packet.checksum, 9);
+
+        assert!(matches!(
+            SChatCommandSigned::read(write_command(9).as_slice()),
+            Err(ReadingError::TooLarge(_))
+        ));
+    }
+}
@@ -0,0 +1,5 @@
+use pumpkin_data::packet::serverbound::PLAY_CONFIGURATION_ACKNOWLEDGED;
+use pumpkin_macros::packet;
+
+#[packet(PLAY_CONFIGURATION_ACKNOWLEDGED)]
+pub struct SConfigurationAcknowledged;
@@ -1,4 +1,6 @@
+mod chat_ack;
 mod chat_command;
+mod chat_command_signed;
 mod chat_message;
 mod chunk_batch;
 mod click_container;
@@ -7,6 +9,7 @@ mod client_information;
 mod client_tick_end;
 mod close_container;
 mod command_suggestion;
+mod configuration_acknowledged;
 mod confirm_teleport;
 mod cookie_response;
 mod interact;
@@ -23,6 +26,7 @@ mod player_position;
 mod player_position_rotation;
 mod player_rotation;
 mod player_session;
+mod pong;
 mod set_creative_slot;
 mod set_held_item;
 mod swing_arm;
@@ -30,7 +34,9 @@ mod update_sign;
 mod use_item;
 mod use_item_on;
 
+pub use chat_ack::*;
 pub use chat_command::*;
+pub use chat_command_signed::*;
 pub use chat_message::*;
 pub use chunk_batch::*;
 pub use click_container::*;
@@ -39,6 +45,7 @@ pub use client_information::*;
 pub use client_tick_end::*;
 pub use close_container::*;
 pub use command_suggestion::*;
+pub use configuration_acknowledged::*;
 pub use confirm_teleport::*;
 pub use cookie_response::*;
 pub use interact::*;
@@ -55,6 +62,7 @@ pub use player_position::*;
 pub use player_position_rotation::*;
 pub use player_rotation::*;
 pub use player_session::*;
+pub use pong::*;
 pub use set_creative_slot::*;
 pub use set_held_item::*;
 pub use swing_arm::*;
@@ -0,0 +1,9 @@
+use pumpkin_data::packet::serverbound::PLAY_PONG;
+use pumpkin_macros::packet;
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+#[packet(PLAY_PONG)]
+pub struct SPong {
+    pub id: i32,
+}
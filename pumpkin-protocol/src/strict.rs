@@ -0,0 +1,528 @@
+//! Checks of outgoing packets for developers. Pumpkin only runs them when it is built with the
+//! `protocol_strict` feature.
+//!
+//! Every packet is checked for what holds for all of them: the id reads back, serializing again
+//! gives the same bytes and the packet isn't larger than clients accept. Packets serialized with
+//! serde are also decoded back field by field along their layout, checking that every field reads
+//! back as what was written and that the VarInts and strings are in the range clients accept.
+//! Packets with a hand written layout can't be decoded without knowing it.
+
+use std::fmt::Display;
+
+use serde::{
+    Serialize,
+    ser::{self, Impossible},
+};
+use thiserror::Error;
+
+use crate::{ClientPacket, MAX_PACKET_DATA_SIZE, codec::var_int::VarInt, ser::NO_PREFIX_MARKER};
+
+/// The most characters a string may have, like the default of vanilla's `writeUtf`
+pub const MAX_STRING_LENGTH: usize = i16::MAX as usize;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum MalformedPacket {
+    #[error("The packet doesn't start with a valid id")]
+    InvalidId,
+    #[error("The packet starts with id {found} instead of {expected}")]
+    WrongId { expected: i32, found: i32 },
+    #[error("The packet is {0} bytes, clients accept at most {MAX_PACKET_DATA_SIZE}")]
+    TooLarge(usize),
+    #[error("Serializing the packet again gives different bytes")]
+    NotDeterministic,
+    #[error("Serializing the packet again failed: {0}")]
+    Unserializable(String),
+    #[error("The field at byte {offset} doesn't read back as what was written")]
+    Mismatch { offset: usize },
+    #[error("The VarInt at byte {offset} is longer than 5 bytes or overflows")]
+    InvalidVarInt { offset: usize },
+    #[error("The string at byte {offset} is longer than {MAX_STRING_LENGTH} characters")]
+    StringTooLong { offset: usize },
+    #[error("The string at byte {offset} isn't valid UTF-8")]
+    InvalidString { offset: usize },
+    #[error("The packet ends in the middle of a field")]
+    Truncated,
+    #[error("{0} bytes are left after the last field")]
+    TrailingBytes(usize),
+}
+
+impl ser::Error for MalformedPacket {
+    fn custom<T: Display>(msg: T) -> Self {
+        Self::Unserializable(msg.to_string())
+    }
+}
+
+/// Checks `data`, the serialized `packet` including its id, before it is sent.
+pub fn check_packet<P: ClientPacket>(packet: &P, data: &[u8]) -> Result<(), MalformedPacket> {
+    let mut payload = data;
+    let id = VarInt::decode(&mut payload).map_err(|_| MalformedPacket::InvalidId)?;
+    if id.0 != P::PACKET_ID {
+        return Err(MalformedPacket::WrongId {
+            expected: P::PACKET_ID,
+            found: id.0,
+        });
+    }
+    if data.len() > MAX_PACKET_DATA_SIZE {
+        return Err(MalformedPacket::TooLarge(data.len()));
+    }
+
+    packet.check_packet_data(payload)?;
+
+    let mut again = Vec::with_capacity(data.len());
+    packet
+        .write(&mut again)
+        .map_err(|err| MalformedPacket::Unserializable(err.to_string()))?;
+    if again != data {
+        return Err(MalformedPacket::NotDeterministic);
+    }
+    Ok(())
+}
+
+/// Decodes `data`, the serialized `value` without a packet id, back along the layout serde gives
+/// `value`, see the general notes of [`crate::ser::serializer::Serializer`]. The offsets of the
+/// errors are counted from the start of `data`.
+pub fn check_fields<T: Serialize + ?Sized>(value: &T, data: &[u8]) -> Result<(), MalformedPacket> {
+    let mut checker = FieldChecker {
+        data,
+        offset: 0,
+        no_prefix: false,
+    };
+    value.serialize(&mut checker)?;
+    match checker.remaining() {
+        0 => Ok(()),
+        left => Err(MalformedPacket::TrailingBytes(left)),
+    }
+}
+
+/// A serde serializer which reads every field back from serialized data instead of writing it.
+struct FieldChecker<'a> {
+    data: &'a [u8],
+    offset: usize,
+    /// Whether the next sequence is written without a length, see
+    /// [`crate::ser::network_serialize_no_prefix`]
+    no_prefix: bool,
+}
+
+impl FieldChecker<'_> {
+    fn remaining(&self) -> usize {
+        self.data.len() - self.offset
+    }
+
+    fn take(&mut self, len: usize) -> Result<&[u8], MalformedPacket> {
+        if len > self.remaining() {
+            return Err(MalformedPacket::Truncated);
+        }
+        let bytes = &self.data[self.offset..self.offset + len];
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn expect(&mut self, expected: &[u8]) -> Result<(), MalformedPacket> {
+        let offset = self.offset;
+        if self.take(expected.len())? == expected {
+            Ok(())
+        } else {
+            Err(MalformedPacket::Mismatch { offset })
+        }
+    }
+
+    fn read_var_int(&mut self) -> Result<i32, MalformedPacket> {
+        let offset = self.offset;
+        let mut value = 0;
+        for i in 0..VarInt::MAX_SIZE.get() {
+            let byte = self.take(1)?[0];
+            // The last byte only has room for the 4 highest bits
+            if i == VarInt::MAX_SIZE.get() - 1 && byte > 0x0F {
+                break;
+            }
+            value |= (i32::from(byte) & 0x7F) << (i * 7);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(MalformedPacket::InvalidVarInt { offset })
+    }
+
+    /// Reads a VarInt which has to be `expected`, like the length of a list or an enum index.
+    fn expect_var_int(&mut self, expected: usize) -> Result<(), MalformedPacket> {
+        let offset = self.offset;
+        let value = self.read_var_int()?;
+        if usize::try_from(value).is_ok_and(|value| value == expected) {
+            Ok(())
+        } else {
+            Err(MalformedPacket::Mismatch { offset })
+        }
+    }
+
+    fn expect_str(&mut self, expected: &str) -> Result<(), MalformedPacket> {
+        let offset = self.offset;
+        let len = self.read_var_int()?;
+        let Ok(len) = usize::try_from(len) else {
+            return Err(MalformedPacket::Mismatch { offset });
+        };
+        // Every character takes up at most 3 bytes in Java's modified UTF-8
+        if len > MAX_STRING_LENGTH * 3 {
+            return Err(MalformedPacket::StringTooLong { offset });
+        }
+        let Ok(string) = str::from_utf8(self.take(len)?) else {
+            return Err(MalformedPacket::InvalidString { offset });
+        };
+        // Java counts the characters of a string in UTF-16
+        if string.encode_utf16().count() > MAX_STRING_LENGTH {
+            return Err(MalformedPacket::StringTooLong { offset });
+        }
+        if string != expected {
+            return Err(MalformedPacket::Mismatch { offset });
+        }
+        Ok(())
+    }
+
+    fn unsupported(what: &str) -> MalformedPacket {
+        MalformedPacket::Unserializable(format!("{what} can't be serialized for the network"))
+    }
+}
+
+impl<'a> ser::Serializer for &mut FieldChecker<'a> {
+    type Ok = ();
+    type Error = MalformedPacket;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Impossible<(), MalformedPacket>;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Impossible<(), MalformedPacket>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), MalformedPacket>;
+
+    fn serialize_bool(self, v: bool) -> Result<(), MalformedPacket> {
+        self.expect(&[u8::from(v)])
+    }
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), MalformedPacket> {
+        self.expect(v)
+    }
+    fn serialize_char(self, _v: char) -> Result<(), MalformedPacket> {
+        Err(FieldChecker::unsupported("A char"))
+    }
+    fn serialize_f32(self, v: f32) -> Result<(), MalformedPacket> {
+        self.expect(&v.to_be_bytes())
+    }
+    fn serialize_f64(self, v: f64) -> Result<(), MalformedPacket> {
+        self.expect(&v.to_be_bytes())
+    }
+    fn serialize_i8(self, v: i8) -> Result<(), MalformedPacket> {
+        self.expect(&v.to_be_bytes())
+    }
+    fn serialize_i16(self, v: i16) -> Result<(), MalformedPacket> {
+        self.expect(&v.to_be_bytes())
+    }
+    fn serialize_i32(self, v: i32) -> Result<(), MalformedPacket> {
+        self.expect(&v.to_be_bytes())
+    }
+    fn serialize_i64(self, v: i64) -> Result<(), MalformedPacket> {
+        self.expect(&v.to_be_bytes())
+    }
+    fn serialize_u8(self, v: u8) -> Result<(), MalformedPacket> {
+        self.expect(&v.to_be_bytes())
+    }
+    fn serialize_u16(self, v: u16) -> Result<(), MalformedPacket> {
+        self.expect(&v.to_be_bytes())
+    }
+    fn serialize_u32(self, v: u32) -> Result<(), MalformedPacket> {
+        self.expect(&v.to_be_bytes())
+    }
+    fn serialize_u64(self, v: u64) -> Result<(), MalformedPacket> {
+        self.expect(&v.to_be_bytes())
+    }
+    fn serialize_str(self, v: &str) -> Result<(), MalformedPacket> {
+        self.expect_str(v)
+    }
+    fn serialize_none(self) -> Result<(), MalformedPacket> {
+        self.expect(&[0])
+    }
+    fn serialize_some<T>(self, value: &T) -> Result<(), MalformedPacket>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.expect(&[1])?;
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<(), MalformedPacket> {
+        Err(FieldChecker::unsupported("A unit"))
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), MalformedPacket> {
+        Ok(())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), MalformedPacket> {
+        self.expect_var_int(variant_index as usize)
+    }
+    fn serialize_newtype_struct<T>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), MalformedPacket>
+    where
+        T: ?Sized + Serialize,
+    {
+        if name == "TextComponent" {
+            let mut nbt = Vec::new();
+            let mut nbt_serializer = pumpkin_nbt::serializer::Serializer::new(&mut nbt, None);
+            value
+                .serialize(&mut nbt_serializer)
+                .map_err(|err| MalformedPacket::Unserializable(err.to_string()))?;
+            self.expect(&nbt)
+        } else {
+            self.no_prefix = name == NO_PREFIX_MARKER;
+            value.serialize(self)
+        }
+    }
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), MalformedPacket>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.expect_var_int(variant_index as usize)?;
+        value.serialize(self)
+    }
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, MalformedPacket> {
+        if std::mem::take(&mut self.no_prefix) {
+            return Ok(self);
+        }
+        let Some(len) = len else {
+            return Err(FieldChecker::unsupported(
+                "A sequence without a known length",
+            ));
+        };
+        self.expect_var_int(len)?;
+        Ok(self)
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self, MalformedPacket> {
+        Ok(self)
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, MalformedPacket> {
+        Err(FieldChecker::unsupported("A tuple struct"))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, MalformedPacket> {
+        self.expect_var_int(variant_index as usize)?;
+        Ok(self)
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, MalformedPacket> {
+        Err(FieldChecker::unsupported("A map"))
+    }
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, MalformedPacket> {
+        Ok(self)
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, MalformedPacket> {
+        Err(FieldChecker::unsupported("A struct variant"))
+    }
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl ser::SerializeSeq for &mut FieldChecker<'_> {
+    type Ok = ();
+    type Error = MalformedPacket;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), MalformedPacket>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), MalformedPacket> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for &mut FieldChecker<'_> {
+    type Ok = ();
+    type Error = MalformedPacket;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), MalformedPacket>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), MalformedPacket> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleVariant for &mut FieldChecker<'_> {
+    type Ok = ();
+    type Error = MalformedPacket;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), MalformedPacket>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), MalformedPacket> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for &mut FieldChecker<'_> {
+    type Ok = ();
+    type Error = MalformedPacket;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), MalformedPacket>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), MalformedPacket> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::text::{TextComponent, color::NamedColor};
+    use serde::Serialize;
+
+    use crate::{
+        ClientPacket, MAX_PACKET_DATA_SIZE,
+        client::{config::CPluginMessage, play::CSystemChatMessage},
+        codec::var_int::VarInt,
+        ser::{NetworkWriteExt, packet::Packet},
+    };
+
+    use super::{MAX_STRING_LENGTH, MalformedPacket, check_packet};
+
+    fn serialize(packet: &impl ClientPacket) -> Vec<u8> {
+        let mut data = Vec::new();
+        packet.write(&mut data).unwrap();
+        data
+    }
+
+    #[derive(Serialize)]
+    struct TestPacket {
+        name: String,
+        values: Vec<VarInt>,
+        flag: Option<bool>,
+    }
+
+    impl Packet for TestPacket {
+        const PACKET_ID: i32 = 0x01;
+    }
+
+    fn test_packet(name: &str) -> TestPacket {
+        TestPacket {
+            name: name.to_string(),
+            values: vec![VarInt(-1), VarInt(300)],
+            flag: Some(true),
+        }
+    }
+
+    #[test]
+    fn oversized_and_mislabeled_packets_are_flagged() {
+        let packet = CPluginMessage::new("minecraft:brand", b"Pumpkin");
+        assert_eq!(check_packet(&packet, &serialize(&packet)), Ok(()));
+
+        let payload = vec![0; MAX_PACKET_DATA_SIZE];
+        let packet = CPluginMessage::new("minecraft:brand", &payload);
+        assert!(matches!(
+            check_packet(&packet, &serialize(&packet)),
+            Err(MalformedPacket::TooLarge(_))
+        ));
+
+        let packet = CPluginMessage::new("minecraft:brand", b"Pumpkin");
+        let mut data = Vec::new();
+        data.write_var_int(&VarInt(0x7f)).unwrap();
+        assert!(matches!(
+            check_packet(&packet, &data),
+            Err(MalformedPacket::WrongId { found: 0x7f, .. })
+        ));
+    }
+
+    #[test]
+    fn fields_are_decoded_back() {
+        let packet = test_packet("Pumpkin");
+        let data = serialize(&packet);
+        assert_eq!(check_packet(&packet, &data), Ok(()));
+
+        let text = TextComponent::text("Hello").color_named(NamedColor::Gold);
+        let chat = CSystemChatMessage::new(&text, false);
+        assert_eq!(check_packet(&chat, &serialize(&chat)), Ok(()));
+
+        // The option flag may only be 0 or 1. Offsets are counted after the id, which is one byte
+        let mut corrupted = data.clone();
+        *corrupted.last_mut().unwrap() = 2;
+        assert_eq!(
+            check_packet(&packet, &corrupted),
+            Err(MalformedPacket::Mismatch {
+                offset: data.len() - 2
+            })
+        );
+
+        assert_eq!(
+            check_packet(&packet, &data[..data.len() - 1]),
+            Err(MalformedPacket::Truncated)
+        );
+        let mut longer = data.clone();
+        longer.push(0);
+        assert_eq!(
+            check_packet(&packet, &longer),
+            Err(MalformedPacket::TrailingBytes(1))
+        );
+    }
+
+    #[test]
+    fn var_ints_and_strings_are_range_checked() {
+        let packet = test_packet("Pumpkin");
+        let mut data = Vec::new();
+        data.write_var_int(&VarInt(TestPacket::PACKET_ID)).unwrap();
+        // A string length which runs over 5 bytes
+        data.extend([0xFF; 6]);
+        assert_eq!(
+            check_packet(&packet, &data),
+            Err(MalformedPacket::InvalidVarInt { offset: 0 })
+        );
+
+        // Written without the bound of `write_string`, like a hand written packet could
+        let name = "a".repeat(MAX_STRING_LENGTH + 1);
+        let packet = test_packet(&name);
+        let mut data = Vec::new();
+        data.write_var_int(&VarInt(TestPacket::PACKET_ID)).unwrap();
+        data.write_var_int(&VarInt(name.len() as i32)).unwrap();
+        data.extend(name.as_bytes());
+        assert_eq!(
+            check_packet(&packet, &data),
+            Err(MalformedPacket::StringTooLong { offset: 0 })
+        );
+    }
+}
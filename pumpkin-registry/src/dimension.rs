@@ -1,4 +1,11 @@
+use std::{fs, io, path::Path};
+
+use indexmap::IndexMap;
+use pumpkin_protocol::{client::config::RegistryEntry, codec::identifier::Identifier};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{DimensionType, Registry, SYNCED_REGISTRIES};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dimension {
@@ -48,8 +55,213 @@ pub struct MonsterSpawnLightLevelTagged {
     r#type: String,
 }
 
+impl Dimension {
+    /// Parses a dimension type definition in the vanilla datapack format.
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    /// The lowest y coordinate blocks can be at
+    pub fn min_y(&self) -> i32 {
+        self.min_y
+    }
+
+    /// The amount of blocks from the bottom to the top of the dimension
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// The amount of blocks from the bottom up to which portals and chorus fruit may take
+    /// entities, the nether's roof is above it
+    pub fn logical_height(&self) -> i32 {
+        self.logical_height
+    }
+
+    pub fn ambient_light(&self) -> f32 {
+        self.ambient_light
+    }
+
+    pub fn has_skylight(&self) -> bool {
+        self.has_skylight
+    }
+
+    /// How much further a step in this dimension goes in the overworld
+    pub fn coordinate_scale(&self) -> f64 {
+        self.coordinate_scale
+    }
+
+    /// Whether players can set their spawn at respawn anchors, otherwise they explode
+    pub fn respawn_anchor_works(&self) -> bool {
+        self.respawn_anchor_works
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum DimensionLoadError {
+    #[error("Failed to read dimension type {0}: {1}")]
+    Io(String, io::Error),
+    #[error("Invalid dimension type {0}: {1}")]
+    Json(String, serde_json::Error),
+}
+
+/// The `dimension_type` registry sent to clients: the vanilla dimension types followed by the
+/// custom ones, in the order they were registered.
+#[derive(Debug, Clone)]
+pub struct DimensionTypes {
+    types: IndexMap<Identifier, Dimension>,
+}
+
+impl Default for DimensionTypes {
+    fn default() -> Self {
+        Self {
+            types: SYNCED_REGISTRIES
+                .dimension_type
+                .iter()
+                .map(|(name, dimension)| (Identifier::vanilla(name), dimension.clone()))
+                .collect(),
+        }
+    }
+}
+
+impl DimensionTypes {
+    /// Adds a dimension type, replacing the one with the same id.
+    pub fn register(&mut self, id: Identifier, dimension: Dimension) {
+        self.types.insert(id, dimension);
+    }
+
+    /// Registers every dimension type found in `folder`, which is laid out like a datapack's
+    /// `dimension_type` folder split by namespace: `<namespace>/<name>.json`. Returns the new types.
+    pub fn load_folder(&mut self, folder: &Path) -> Result<Vec<DimensionType>, DimensionLoadError> {
+        let mut loaded = Vec::new();
+        if !folder.is_dir() {
+            return Ok(loaded);
+        }
+        let read_error =
+            |path: &Path, error| DimensionLoadError::Io(path.display().to_string(), error);
+        for namespace in fs::read_dir(folder).map_err(|error| read_error(folder, error))? {
+            let namespace = namespace.map_err(|error| read_error(folder, error))?.path();
+            if !namespace.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(&namespace).map_err(|error| read_error(&namespace, error))? {
+                let file = file.map_err(|error| read_error(&namespace, error))?.path();
+                if file.extension().is_none_or(|extension| extension != "json") {
+                    continue;
+                }
+                let (Some(namespace), Some(name)) = (
+                    namespace.file_name().and_then(|name| name.to_str()),
+                    file.file_stem().and_then(|name| name.to_str()),
+                ) else {
+                    continue;
+                };
+                let id = Identifier {
+                    namespace: namespace.to_string(),
+                    path: name.to_string(),
+                };
+                let json = fs::read_to_string(&file).map_err(|error| read_error(&file, error))?;
+                let dimension = Dimension::from_json(&json)
+                    .map_err(|error| DimensionLoadError::Json(id.to_string(), error))?;
+                self.register(id.clone(), dimension);
+                loaded.push(DimensionType::from_id(id));
+            }
+        }
+        Ok(loaded)
+    }
+
+    pub fn get(&self, dimension_type: &DimensionType) -> Option<&Dimension> {
+        self.types.get(&dimension_type.name())
+    }
+
+    /// The id of the dimension type in the synced registry, as used by the login and respawn
+    /// packets.
+    pub fn registry_id(&self, dimension_type: &DimensionType) -> Option<i32> {
+        self.types
+            .get_index_of(&dimension_type.name())
+            .map(|index| index as i32)
+    }
+
+    pub fn registry(&self) -> Registry {
+        Registry {
+            registry_id: Identifier::vanilla("dimension_type"),
+            registry_entries: self
+                .types
+                .iter()
+                .map(|(id, dimension)| {
+                    let mut entry = RegistryEntry::from_nbt(&id.path, dimension);
+                    entry.entry_id = id.clone();
+                    entry
+                })
+                .collect(),
+        }
+    }
+}
+
 impl From<i32> for MonsterSpawnLightLevel {
     fn from(value: i32) -> Self {
         Self::Int(value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::codec::identifier::Identifier;
+    use serde::Deserialize;
+
+    use super::{Dimension, DimensionTypes};
+    use crate::DimensionType;
+
+    const TALL: &str = r##"{
+        "infiniburn": "#minecraft:infiniburn_overworld",
+        "effects": "minecraft:overworld",
+        "ambient_light": 0.5,
+        "piglin_safe": false,
+        "has_raids": true,
+        "monster_spawn_light_level": 0,
+        "monster_spawn_block_light_limit": 0,
+        "respawn_anchor_works": false,
+        "min_y": -128,
+        "height": 512,
+        "logical_height": 512,
+        "ultrawarm": false,
+        "natural": true,
+        "coordinate_scale": 2.0,
+        "bed_works": true,
+        "has_skylight": false,
+        "has_ceiling": false
+    }"##;
+
+    #[test]
+    fn custom_dimension_type_is_synced() {
+        let id = Identifier {
+            namespace: "example".to_string(),
+            path: "tall".to_string(),
+        };
+        let mut types = DimensionTypes::default();
+        types.register(id.clone(), Dimension::from_json(TALL).unwrap());
+
+        let tall = DimensionType::from_id(id.clone());
+        assert_eq!(tall, DimensionType::Custom(id.clone()));
+        let dimension = types.get(&tall).unwrap();
+        assert_eq!(dimension.min_y(), -128);
+        assert_eq!(dimension.height(), 512);
+        assert!(!dimension.has_skylight());
+
+        // The vanilla types keep their ids
+        assert_eq!(types.registry_id(&DimensionType::Overworld), Some(0));
+        assert_eq!(types.registry_id(&DimensionType::TheNether), Some(3));
+        assert_eq!(types.registry_id(&tall), Some(4));
+
+        let registry = types.registry();
+        let entry = &registry.registry_entries[4];
+        assert_eq!(entry.entry_id, id);
+        #[derive(Deserialize)]
+        struct Bounds {
+            min_y: i32,
+            height: i32,
+        }
+        let synced: Bounds =
+            pumpkin_nbt::deserializer::from_bytes_unnamed(entry.data.as_deref().unwrap()).unwrap();
+        assert_eq!(synced.min_y, -128);
+        assert_eq!(synced.height, 512);
+    }
+}
@@ -12,3 +12,10 @@ pub struct JukeboxSong {
 struct Description {
     translate: String,
 }
+
+impl JukeboxSong {
+    /// The signal strength a comparator reads from a jukebox playing this song.
+    pub fn comparator_output(&self) -> i32 {
+        self.comparator_output
+    }
+}
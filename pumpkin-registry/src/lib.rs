@@ -8,6 +8,7 @@ use chicken::ChickenVariant;
 use cow::CowVariant;
 use damage_type::DamageType;
 use dimension::Dimension;
+pub use dimension::{DimensionLoadError, DimensionTypes};
 use enchantment::Enchantment;
 use frog::FrogVariant;
 use indexmap::IndexMap;
@@ -72,12 +73,14 @@ pub struct SyncedRegistry {
     instrument: IndexMap<String, Instrument>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DimensionType {
     Overworld,
     OverworldCaves,
     TheEnd,
     TheNether,
+    /// A dimension type loaded from JSON, see `DimensionTypes`.
+    Custom(Identifier),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,12 +96,26 @@ impl DimensionType {
             Self::OverworldCaves => Identifier::vanilla("overworld_caves"),
             Self::TheEnd => Identifier::vanilla("the_end"),
             Self::TheNether => Identifier::vanilla("the_nether"),
+            Self::Custom(id) => id.clone(),
         }
     }
+
+    pub fn from_id(id: Identifier) -> Self {
+        if id.namespace == "minecraft" {
+            match id.path.as_str() {
+                "overworld" => return Self::Overworld,
+                "overworld_caves" => return Self::OverworldCaves,
+                "the_end" => return Self::TheEnd,
+                "the_nether" => return Self::TheNether,
+                _ => {}
+            }
+        }
+        Self::Custom(id)
+    }
 }
 
 impl Registry {
-    pub fn get_synced() -> Vec<Self> {
+    pub fn get_synced(dimension_types: &DimensionTypes) -> Vec<Self> {
         let registry_entries = SYNCED_REGISTRIES
             .biome
             .iter()
@@ -212,15 +229,7 @@ impl Registry {
             registry_entries,
         };
 
-        let registry_entries = SYNCED_REGISTRIES
-            .dimension_type
-            .iter()
-            .map(|(name, nbt)| RegistryEntry::from_nbt(name, nbt))
-            .collect();
-        let dimension_type = Registry {
-            registry_id: Identifier::vanilla("dimension_type"),
-            registry_entries,
-        };
+        let dimension_type = dimension_types.registry();
 
         let registry_entries = SYNCED_REGISTRIES
             .damage_type
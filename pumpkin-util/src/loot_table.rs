@@ -48,7 +48,12 @@ pub enum LootCondition {
         block: &'static str,
         properties: &'static [(&'static str, &'static str)],
     },
-    MatchTool,
+    /// The tool must be one of `items`, an item id or an item tag starting with `#`, and have
+    /// all `enchantments` with at least the given level.
+    MatchTool {
+        items: Option<&'static str>,
+        enchantments: &'static [(&'static str, i32)],
+    },
     TableBonus,
     SurvivesExplosion,
     DamageSourceProperties,
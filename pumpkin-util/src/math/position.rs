@@ -136,6 +136,13 @@ impl BlockPos {
         })
     }
 
+    /// Packs the position into a long the way the protocol sends it, see [`Self::from_i64`].
+    pub fn to_i64(&self) -> i64 {
+        ((self.0.x as i64 & 0x3FFFFFF) << 38)
+            | ((self.0.z as i64 & 0x3FFFFFF) << 12)
+            | (self.0.y as i64 & 0xFFF)
+    }
+
     pub fn floored(x: f64, y: f64, z: f64) -> Self {
         Self(Vector3::new(
             x.floor() as i32,
@@ -177,10 +184,7 @@ impl Serialize for BlockPos {
     where
         S: serde::Serializer,
     {
-        let long = ((self.0.x as i64 & 0x3FFFFFF) << 38)
-            | ((self.0.z as i64 & 0x3FFFFFF) << 12)
-            | (self.0.y as i64 & 0xFFF);
-        serializer.serialize_i64(long)
+        serializer.serialize_i64(self.to_i64())
     }
 }
 
@@ -1,9 +1,13 @@
-use crate::{text::color::ARGBColor, translation::get_translation_en_us};
+use crate::{
+    text::color::ARGBColor,
+    translation::{get_translation_en_us, translate},
+};
 use click::ClickEvent;
 use color::Color;
 use colored::Colorize;
 use core::str;
 use hover::HoverEvent;
+use pumpkin_nbt::{deserializer::NbtReadHelper, tag::NbtTag};
 use serde::de::{Error, MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize};
 use std::borrow::Cow;
@@ -129,6 +133,33 @@ impl TextComponentBase {
         }
         text
     }
+
+    /// Resolves the text in `locale` without any styling, for clients which can't translate it
+    /// themselves.
+    pub fn to_locale_string(self, locale: &str) -> String {
+        let mut text = match self.content {
+            TextContent::Text { text } => text.into_owned(),
+            TextContent::Translate {
+                translate: key,
+                with,
+            } => {
+                let args: Vec<String> = with
+                    .into_iter()
+                    .map(|arg| arg.to_locale_string(locale))
+                    .collect();
+                translate(&key, locale, &args)
+            }
+            TextContent::EntityNames {
+                selector,
+                separator: _,
+            } => selector.into_owned(),
+            TextContent::Keybind { keybind } => keybind.into_owned(),
+        };
+        for child in self.extra {
+            text += &child.to_locale_string(locale);
+        }
+        text
+    }
 }
 
 impl TextComponent {
@@ -219,6 +250,10 @@ impl TextComponent {
     pub fn to_pretty_console(self) -> String {
         self.0.to_pretty_console()
     }
+
+    pub fn to_locale_string(self, locale: &str) -> String {
+        self.0.to_locale_string(locale)
+    }
 }
 
 impl TextComponent {
@@ -231,6 +266,18 @@ impl TextComponent {
         buf.into_boxed_slice()
     }
 
+    /// The text as an NBT text component, the way vanilla stores text in items and world data.
+    pub fn to_nbt(&self) -> NbtTag {
+        let bytes = self.encode();
+        NbtTag::deserialize(&mut NbtReadHelper::new(&bytes[..]))
+            .expect("Encoded text components are always valid NBT")
+    }
+
+    /// Reads an NBT text component, see [`TextComponent::to_nbt`]. A string tag is plain text.
+    pub fn from_nbt(tag: &NbtTag) -> Self {
+        serde_json::from_value(nbt_to_json(tag)).unwrap_or_else(|_| Self::text(""))
+    }
+
     pub fn color(mut self, color: Color) -> Self {
         self.0.style.color = Some(color);
         self
@@ -308,6 +355,30 @@ impl TextComponent {
     }
 }
 
+/// Text components only hold booleans, numbers, strings, lists and compounds, so they read from
+/// NBT like from JSON once bytes are turned back into booleans.
+fn nbt_to_json(tag: &NbtTag) -> serde_json::Value {
+    match tag {
+        NbtTag::Byte(byte) => serde_json::Value::Bool(*byte != 0),
+        NbtTag::Short(short) => (*short).into(),
+        NbtTag::Int(int) => (*int).into(),
+        NbtTag::Long(long) => (*long).into(),
+        NbtTag::Float(float) => f64::from(*float).into(),
+        NbtTag::Double(double) => (*double).into(),
+        NbtTag::String(string) => string.clone().into(),
+        NbtTag::List(list) => list.iter().map(nbt_to_json).collect(),
+        NbtTag::Compound(compound) => compound
+            .child_tags
+            .iter()
+            .map(|(key, tag)| (key.clone(), nbt_to_json(tag)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        NbtTag::End | NbtTag::ByteArray(_) | NbtTag::IntArray(_) | NbtTag::LongArray(_) => {
+            serde_json::Value::Null
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(untagged)]
 pub enum TextContent {
@@ -332,7 +403,7 @@ pub enum TextContent {
 
 #[cfg(test)]
 mod test {
-    use pumpkin_nbt::serializer::to_bytes_unnamed;
+    use pumpkin_nbt::{serializer::to_bytes_unnamed, tag::NbtTag};
 
     use crate::text::{TextComponent, color::NamedColor};
 
@@ -358,4 +429,21 @@ mod test {
 
         assert_eq!(bytes, expected_bytes);
     }
+
+    #[test]
+    fn text_reads_back_from_nbt() {
+        let text = TextComponent::text("Sword")
+            .color_named(NamedColor::Gold)
+            .bold()
+            .add_child(TextComponent::translate("item.minecraft.diamond_sword", []));
+        let nbt = text.to_nbt();
+        assert!(matches!(nbt, NbtTag::Compound(_)));
+        assert_eq!(TextComponent::from_nbt(&nbt), text);
+
+        // Vanilla writes plain text as a string
+        assert_eq!(
+            TextComponent::from_nbt(&NbtTag::String("Sword".to_string())),
+            TextComponent::text("Sword")
+        );
+    }
 }
@@ -2,15 +2,115 @@ use std::{collections::HashMap, sync::LazyLock};
 
 use crate::text::TextComponentBase;
 
+/// The language everything falls back to, and the one the console is in
+pub const DEFAULT_LOCALE: &str = "en_us";
+
 const EN_US_JSON: &str = include_str!("../../assets/en_us.json");
+const DE_DE_JSON: &str = include_str!("../../assets/de_de.json");
 
 pub static EN_US: LazyLock<HashMap<String, String>> =
     LazyLock::new(|| serde_json::from_str(EN_US_JSON).expect("Could not parse en_us.json."));
 
+/// Every other bundled language, these only cover some of the keys
+static LANGUAGES: LazyLock<HashMap<&'static str, HashMap<String, String>>> = LazyLock::new(|| {
+    HashMap::from([(
+        "de_de",
+        serde_json::from_str(DE_DE_JSON).expect("Could not parse de_de.json."),
+    )])
+});
+
+fn lookup(key: &str, locale: &str) -> Option<&'static str> {
+    LANGUAGES
+        .get(locale.to_lowercase().as_str())
+        .and_then(|lang| lang.get(key))
+        .or_else(|| EN_US.get(key))
+        .map(String::as_str)
+}
+
+/// Fills the `%s`, `%1$s` and `%%` placeholders of a translation with `args`.
+fn format(translation: &str, args: &[String]) -> String {
+    let mut result = String::with_capacity(translation.len());
+    let mut next_arg = 0;
+    let mut rest = translation;
+    while let Some(start) = rest.find('%') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        if let Some(after) = rest.strip_prefix('%') {
+            result.push('%');
+            rest = after;
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix('s') {
+            result.push_str(args.get(next_arg).map_or("", String::as_str));
+            next_arg += 1;
+            rest = after;
+            continue;
+        }
+        let digits = rest.bytes().take_while(u8::is_ascii_digit).count();
+        if digits > 0 && rest[digits..].starts_with("$s") {
+            let index = rest[..digits].parse::<usize>().unwrap_or(0);
+            result.push_str(
+                index
+                    .checked_sub(1)
+                    .and_then(|index| args.get(index))
+                    .map_or("", String::as_str),
+            );
+            rest = &rest[digits + 2..];
+            continue;
+        }
+        // Not a placeholder we know, keep it as it is
+        result.push('%');
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Looks `key` up in `locale`, then in `en_us`, and fills in `args`.
+///
+/// Returns `None` if neither of them knows the key.
+pub fn get_translation(key: &str, locale: &str, args: &[String]) -> Option<String> {
+    lookup(key, locale).map(|translation| format(translation, args))
+}
+
+/// Resolves `key` for someone using `locale`, falling back to `en_us` and then to the key itself.
+pub fn translate(key: &str, locale: &str, args: &[String]) -> String {
+    get_translation(key, locale, args).unwrap_or_else(|| key.to_string())
+}
+
 pub fn get_translation_en_us(key: &str, with: Vec<TextComponentBase>) -> Option<String> {
-    let mut translation = EN_US.get(key)?.clone();
-    for replace in &with {
-        translation = translation.replacen("%s", &replace.clone().to_pretty_console(), 1);
+    let args: Vec<String> = with
+        .into_iter()
+        .map(TextComponentBase::to_pretty_console)
+        .collect();
+    get_translation(key, DEFAULT_LOCALE, &args)
+}
+
+#[cfg(test)]
+mod test {
+    use super::translate;
+
+    #[test]
+    fn resolves_keys_in_player_locale() {
+        let args = ["Steve".to_string()];
+        assert_eq!(
+            translate("multiplayer.player.joined", "en_us", &args),
+            "Steve joined the game"
+        );
+        assert_eq!(
+            translate("multiplayer.player.joined", "de_de", &args),
+            "Steve hat das Spiel betreten"
+        );
+        assert_eq!(
+            translate("death.attack.generic", "de_DE", &args),
+            "Steve starb"
+        );
+
+        // Falls back to English, then to the key
+        assert_eq!(
+            translate("multiplayer.player.left", "xx_xx", &args),
+            "Steve left the game"
+        );
+        assert_eq!(translate("chat.square_brackets", "de_de", &args), "[Steve]");
+        assert_eq!(translate("no.such.key", "de_de", &args), "no.such.key");
     }
-    Some(translation)
 }
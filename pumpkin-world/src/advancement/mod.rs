@@ -0,0 +1,618 @@
+//! Advancements, both the bundled vanilla ones and those of the world's data packs, and the
+//! progress players make on them.
+//!
+//! Criteria are written like in vanilla data packs, but only a handful of triggers are
+//! implemented so far. Criteria with other triggers are never obtained.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use pumpkin_data::item::Item;
+use pumpkin_data::tag::{RegistryKey, get_registry_key_tags};
+use pumpkin_util::text::TextComponent;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::item::ItemStack;
+
+mod progress;
+
+pub use progress::{AdvancementProgress, AdvancementUpdate, PlayerAdvancements, ProgressUpdate};
+
+const VANILLA_ADVANCEMENTS_JSON: &str = include_str!("../../../assets/advancements.json");
+
+#[derive(Debug, thiserror::Error)]
+pub enum AdvancementLoadError {
+    #[error("Failed to read advancement {0}: {1}")]
+    Io(String, io::Error),
+    #[error("Invalid advancement {0}: {1}")]
+    Json(String, serde_json::Error),
+}
+
+/// Every advancement of the server, by id.
+#[derive(Debug, Default)]
+pub struct Advancements {
+    advancements: BTreeMap<String, Advancement>,
+}
+
+impl Advancements {
+    /// The advancements bundled with the server.
+    #[must_use]
+    pub fn vanilla() -> Self {
+        let json: BTreeMap<String, Value> = serde_json::from_str(VANILLA_ADVANCEMENTS_JSON)
+            .expect("Could not parse advancements.json");
+        let mut advancements = Self::default();
+        for (id, json) in json {
+            let advancement = Advancement::from_json(id, json)
+                .unwrap_or_else(|error| panic!("Invalid bundled advancement: {error}"));
+            advancements.insert(advancement);
+        }
+        advancements
+    }
+
+    /// Adds the advancements of every data pack in `folder`, like the world's `datapacks` folder.
+    /// They are laid out like in vanilla: `<pack>/data/<namespace>/advancement/<path>.json`, and
+    /// replace bundled advancements with the same id. Returns how many were loaded.
+    pub fn load_datapacks(&mut self, folder: &Path) -> Result<usize, AdvancementLoadError> {
+        if !folder.is_dir() {
+            return Ok(0);
+        }
+        let read_error =
+            |path: &Path, error| AdvancementLoadError::Io(path.display().to_string(), error);
+        let mut loaded = 0;
+        for pack in fs::read_dir(folder).map_err(|error| read_error(folder, error))? {
+            let data = pack
+                .map_err(|error| read_error(folder, error))?
+                .path()
+                .join("data");
+            if !data.is_dir() {
+                continue;
+            }
+            for namespace in fs::read_dir(&data).map_err(|error| read_error(&data, error))? {
+                let namespace = namespace.map_err(|error| read_error(&data, error))?.path();
+                let folder = namespace.join("advancement");
+                let Some(namespace) = namespace.file_name().and_then(|name| name.to_str()) else {
+                    continue;
+                };
+                let mut files = Vec::new();
+                json_files(&folder, &mut files).map_err(|error| read_error(&folder, error))?;
+                for file in files {
+                    let Some(path) = file
+                        .strip_prefix(&folder)
+                        .ok()
+                        .and_then(|path| path.with_extension("").to_str().map(str::to_string))
+                    else {
+                        continue;
+                    };
+                    let id = format!("{namespace}:{}", path.replace('\\', "/"));
+                    let json =
+                        fs::read_to_string(&file).map_err(|error| read_error(&file, error))?;
+                    let advancement = serde_json::from_str(&json)
+                        .and_then(|json| Advancement::from_json(id.clone(), json))
+                        .map_err(|error| AdvancementLoadError::Json(id, error))?;
+                    self.insert(advancement);
+                    loaded += 1;
+                }
+            }
+        }
+        Ok(loaded)
+    }
+
+    pub fn insert(&mut self, advancement: Advancement) {
+        self.advancements
+            .insert(advancement.id.clone(), advancement);
+        self.lay_out();
+    }
+
+    #[must_use]
+    pub fn get(&self, id: &str) -> Option<&Advancement> {
+        self.advancements.get(id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Advancement> {
+        self.advancements.values()
+    }
+
+    /// Places the advancements of every tab in a tree, from its root on the left to the leaves on
+    /// the right, one leaf per row.
+    fn lay_out(&mut self) {
+        let mut children: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        let mut roots = Vec::new();
+        for advancement in self.advancements.values() {
+            match advancement.parent.as_deref() {
+                Some(parent) if self.advancements.contains_key(parent) => {
+                    children.entry(parent).or_default().push(&advancement.id);
+                }
+                _ => roots.push(advancement.id.as_str()),
+            }
+        }
+
+        fn place<'a>(
+            id: &'a str,
+            column: f32,
+            row: &mut f32,
+            children: &BTreeMap<&str, Vec<&'a str>>,
+            positions: &mut Vec<(&'a str, f32, f32)>,
+        ) {
+            positions.push((id, column, *row));
+            match children.get(id) {
+                Some(children_ids) => {
+                    for child in children_ids {
+                        place(child, column + 1.0, row, children, positions);
+                    }
+                }
+                None => *row += 1.0,
+            }
+        }
+        let mut positions = Vec::new();
+        for root in roots {
+            place(root, 0.0, &mut 0.0, &children, &mut positions);
+        }
+
+        let positions = positions
+            .into_iter()
+            .map(|(id, x, y)| (id.to_string(), x, y))
+            .collect::<Vec<_>>();
+        for (id, x, y) in positions {
+            if let Some(display) = self
+                .advancements
+                .get_mut(&id)
+                .and_then(|advancement| advancement.display.as_mut())
+            {
+                display.x = x;
+                display.y = y;
+            }
+        }
+    }
+}
+
+fn json_files(folder: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if !folder.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            json_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+pub struct Advancement {
+    pub id: String,
+    pub parent: Option<String>,
+    /// Advancements without a display, like recipe unlocks, aren't shown to the player
+    pub display: Option<AdvancementDisplay>,
+    pub criteria: BTreeMap<String, Criterion>,
+    /// The advancement is done once one criterion of every requirement is obtained
+    pub requirements: Vec<Vec<String>>,
+    pub sends_telemetry_event: bool,
+}
+
+#[derive(Deserialize)]
+struct AdvancementJson {
+    parent: Option<String>,
+    display: Option<AdvancementDisplayJson>,
+    criteria: BTreeMap<String, CriterionJson>,
+    #[serde(default)]
+    requirements: Option<Vec<Vec<String>>>,
+    #[serde(default)]
+    sends_telemetry_event: bool,
+}
+
+impl Advancement {
+    pub fn from_json(id: String, json: Value) -> Result<Self, serde_json::Error> {
+        let json: AdvancementJson = serde_json::from_value(json)?;
+        let display = json.display.map(AdvancementDisplay::try_from).transpose()?;
+        let criteria = json
+            .criteria
+            .into_iter()
+            .map(|(name, criterion)| Ok((name, Criterion::try_from(criterion)?)))
+            .collect::<Result<BTreeMap<_, _>, serde_json::Error>>()?;
+        // Without requirements, every criterion is needed
+        let requirements = json
+            .requirements
+            .unwrap_or_else(|| criteria.keys().map(|name| vec![name.clone()]).collect());
+        Ok(Self {
+            id,
+            parent: json.parent,
+            display,
+            criteria,
+            requirements,
+            sends_telemetry_event: json.sends_telemetry_event,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvancementFrame {
+    #[default]
+    Task,
+    Challenge,
+    Goal,
+}
+
+impl AdvancementFrame {
+    /// The message announcing that a player made an advancement with this frame.
+    #[must_use]
+    pub fn chat_translation_key(self) -> &'static str {
+        match self {
+            Self::Task => "chat.type.advancement.task",
+            Self::Challenge => "chat.type.advancement.challenge",
+            Self::Goal => "chat.type.advancement.goal",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AdvancementDisplay {
+    pub title: TextComponent,
+    pub description: TextComponent,
+    pub icon: ItemStack,
+    pub frame: AdvancementFrame,
+    /// The texture behind the tab, only set on roots
+    pub background: Option<String>,
+    pub show_toast: bool,
+    pub announce_to_chat: bool,
+    /// Hidden advancements are only shown once they are done
+    pub hidden: bool,
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Deserialize)]
+struct AdvancementDisplayJson {
+    icon: IconJson,
+    title: TextComponent,
+    description: TextComponent,
+    #[serde(default)]
+    frame: AdvancementFrame,
+    background: Option<String>,
+    #[serde(default = "default_true")]
+    show_toast: bool,
+    #[serde(default = "default_true")]
+    announce_to_chat: bool,
+    #[serde(default)]
+    hidden: bool,
+}
+
+#[derive(Deserialize)]
+struct IconJson {
+    id: String,
+    #[serde(default = "default_count")]
+    count: u8,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_count() -> u8 {
+    1
+}
+
+impl TryFrom<AdvancementDisplayJson> for AdvancementDisplay {
+    type Error = serde_json::Error;
+
+    fn try_from(json: AdvancementDisplayJson) -> Result<Self, Self::Error> {
+        let item = Item::from_registry_key(strip_namespace(&json.icon.id)).ok_or_else(|| {
+            serde::de::Error::custom(format!("Unknown icon item {}", json.icon.id))
+        })?;
+        Ok(Self {
+            title: json.title,
+            description: json.description,
+            icon: ItemStack::new(json.icon.count, item),
+            frame: json.frame,
+            background: json.background,
+            show_toast: json.show_toast,
+            announce_to_chat: json.announce_to_chat,
+            hidden: json.hidden,
+            x: 0.0,
+            y: 0.0,
+        })
+    }
+}
+
+/// What a player did, which may obtain criteria.
+#[derive(Clone, Copy, Debug)]
+pub enum AdvancementTrigger<'a> {
+    /// The inventory changed and now holds these items
+    InventoryChanged {
+        items: &'a [&'a str],
+    },
+    /// Checked every second, with the biome the player is in
+    Location {
+        biome: &'a str,
+    },
+    PlayerKilledEntity {
+        entity_type: &'a str,
+    },
+    ChangedDimension {
+        from: &'a str,
+        to: &'a str,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Criterion {
+    /// `minecraft:inventory_changed`, every item list has an item in the inventory. An empty list
+    /// matches any item.
+    InventoryChanged { items: Vec<Ids> },
+    /// `minecraft:location`
+    Location { biomes: Option<Ids> },
+    /// `minecraft:player_killed_entity`
+    PlayerKilledEntity { entity_type: Option<Ids> },
+    /// `minecraft:changed_dimension`
+    ChangedDimension {
+        from: Option<String>,
+        to: Option<String>,
+    },
+    /// A trigger which isn't implemented, like `minecraft:impossible`
+    Other(String),
+}
+
+impl Criterion {
+    #[must_use]
+    pub fn matches(&self, trigger: &AdvancementTrigger) -> bool {
+        match (self, trigger) {
+            (
+                Self::InventoryChanged { items },
+                AdvancementTrigger::InventoryChanged { items: held },
+            ) => items.iter().all(|ids| {
+                held.iter()
+                    .any(|item| ids.is_empty() || ids.matches(&RegistryKey::Item, item))
+            }),
+            (Self::Location { biomes }, AdvancementTrigger::Location { biome }) => biomes
+                .as_ref()
+                .is_none_or(|biomes| biomes.matches(&RegistryKey::WorldgenBiome, biome)),
+            (
+                Self::PlayerKilledEntity { entity_type },
+                AdvancementTrigger::PlayerKilledEntity {
+                    entity_type: killed,
+                },
+            ) => entity_type
+                .as_ref()
+                .is_none_or(|types| types.matches(&RegistryKey::EntityType, killed)),
+            (
+                Self::ChangedDimension { from, to },
+                AdvancementTrigger::ChangedDimension {
+                    from: left,
+                    to: entered,
+                },
+            ) => {
+                let same = |expected: &Option<String>, actual: &str| {
+                    expected
+                        .as_deref()
+                        .is_none_or(|expected| strip_namespace(expected) == strip_namespace(actual))
+                };
+                same(from, left) && same(to, entered)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CriterionJson {
+    trigger: String,
+    #[serde(default)]
+    conditions: Value,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct InventoryChangedConditions {
+    items: Vec<ItemPredicate>,
+}
+
+#[derive(Deserialize)]
+struct ItemPredicate {
+    items: Option<Ids>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct LocationConditions {
+    player: Vec<EntityCondition>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PlayerKilledEntityConditions {
+    entity: Vec<EntityCondition>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ChangedDimensionConditions {
+    from: Option<String>,
+    to: Option<String>,
+}
+
+/// An `entity_properties` loot condition, other conditions are ignored.
+#[derive(Deserialize)]
+struct EntityCondition {
+    predicate: Option<EntityPredicate>,
+}
+
+#[derive(Deserialize)]
+struct EntityPredicate {
+    #[serde(rename = "type")]
+    entity_type: Option<Ids>,
+    location: Option<LocationPredicate>,
+}
+
+#[derive(Deserialize)]
+struct LocationPredicate {
+    biomes: Option<Ids>,
+}
+
+fn predicates(conditions: Vec<EntityCondition>) -> impl Iterator<Item = EntityPredicate> {
+    conditions
+        .into_iter()
+        .filter_map(|condition| condition.predicate)
+}
+
+impl TryFrom<CriterionJson> for Criterion {
+    type Error = serde_json::Error;
+
+    fn try_from(json: CriterionJson) -> Result<Self, Self::Error> {
+        fn conditions<T: for<'de> Deserialize<'de> + Default>(
+            json: Value,
+        ) -> Result<T, serde_json::Error> {
+            if json.is_null() {
+                Ok(T::default())
+            } else {
+                serde_json::from_value(json)
+            }
+        }
+
+        Ok(match strip_namespace(&json.trigger) {
+            "inventory_changed" => {
+                let conditions: InventoryChangedConditions = conditions(json.conditions)?;
+                Self::InventoryChanged {
+                    items: conditions
+                        .items
+                        .into_iter()
+                        .map(|predicate| predicate.items.unwrap_or_default())
+                        .collect(),
+                }
+            }
+            "location" => {
+                let conditions: LocationConditions = conditions(json.conditions)?;
+                Self::Location {
+                    biomes: predicates(conditions.player)
+                        .find_map(|predicate| predicate.location?.biomes),
+                }
+            }
+            "player_killed_entity" => {
+                let conditions: PlayerKilledEntityConditions = conditions(json.conditions)?;
+                Self::PlayerKilledEntity {
+                    entity_type: predicates(conditions.entity)
+                        .find_map(|predicate| predicate.entity_type),
+                }
+            }
+            "changed_dimension" => {
+                let conditions: ChangedDimensionConditions = conditions(json.conditions)?;
+                Self::ChangedDimension {
+                    from: conditions.from,
+                    to: conditions.to,
+                }
+            }
+            _ => Self::Other(json.trigger),
+        })
+    }
+}
+
+/// Ids like in data packs: a single id, a `#tag`, or a list of ids.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(from = "OneOrMany")]
+pub struct Ids(Vec<String>);
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl From<OneOrMany> for Ids {
+    fn from(ids: OneOrMany) -> Self {
+        match ids {
+            OneOrMany::One(id) => Self(vec![id]),
+            OneOrMany::Many(ids) => Self(ids),
+        }
+    }
+}
+
+impl Ids {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether `id` is one of the ids, or in one of the tags of `registry`.
+    #[must_use]
+    pub fn matches(&self, registry: &RegistryKey, id: &str) -> bool {
+        let id = strip_namespace(id);
+        self.0.iter().any(|entry| match entry.strip_prefix('#') {
+            Some(tag) => {
+                let tag = if tag.contains(':') {
+                    tag.to_string()
+                } else {
+                    format!("minecraft:{tag}")
+                };
+                get_registry_key_tags(registry)
+                    .iter()
+                    .any(|(name, values)| *name == tag && values.contains(&id))
+            }
+            None => strip_namespace(entry) == id,
+        })
+    }
+}
+
+fn strip_namespace(id: &str) -> &str {
+    id.strip_prefix("minecraft:").unwrap_or(id)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AdvancementTrigger, Advancements};
+
+    #[test]
+    fn vanilla_criteria_match_their_triggers() {
+        let advancements = Advancements::vanilla();
+
+        let stone_age = advancements.get("minecraft:story/mine_stone").unwrap();
+        let get_stone = &stone_age.criteria["get_stone"];
+        // Matched through the stone tool materials tag
+        assert!(get_stone.matches(&AdvancementTrigger::InventoryChanged {
+            items: &["dirt", "minecraft:cobbled_deepslate"],
+        }));
+        assert!(!get_stone.matches(&AdvancementTrigger::InventoryChanged { items: &["dirt"] }));
+        assert!(!get_stone.matches(&AdvancementTrigger::PlayerKilledEntity {
+            entity_type: "cobblestone",
+        }));
+
+        let monster_hunter = advancements.get("minecraft:adventure/kill_a_mob").unwrap();
+        assert_eq!(monster_hunter.requirements.len(), 1);
+        assert!(monster_hunter.criteria["zombie"].matches(
+            &AdvancementTrigger::PlayerKilledEntity {
+                entity_type: "zombie",
+            }
+        ));
+        assert!(
+            !monster_hunter.criteria["zombie"]
+                .matches(&AdvancementTrigger::PlayerKilledEntity { entity_type: "cow" })
+        );
+
+        let hot_tourist_destinations = advancements.get("minecraft:nether/explore_nether").unwrap();
+        assert_eq!(hot_tourist_destinations.requirements.len(), 5);
+        assert!(hot_tourist_destinations.criteria["warped_forest"].matches(
+            &AdvancementTrigger::Location {
+                biome: "warped_forest",
+            }
+        ));
+
+        // Children are placed right of their parents
+        let root = advancements
+            .get("minecraft:story/root")
+            .unwrap()
+            .display
+            .as_ref()
+            .unwrap();
+        let display = stone_age.display.as_ref().unwrap();
+        assert_eq!((root.x, root.y), (0.0, 0.0));
+        assert_eq!((display.x, display.y), (1.0, 0.0));
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use chrono::DateTime;
+use serde_json::{Map, Value, json};
+
+use super::{Advancement, AdvancementTrigger, Advancements};
+use crate::world_info::MAXIMUM_SUPPORTED_WORLD_DATA_VERSION;
+
+/// The criteria of an advancement a player obtained, with when they did in milliseconds since
+/// the Unix epoch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AdvancementProgress {
+    criteria: BTreeMap<String, i64>,
+}
+
+impl AdvancementProgress {
+    #[must_use]
+    pub fn obtained(&self, criterion: &str) -> Option<i64> {
+        self.criteria.get(criterion).copied()
+    }
+
+    #[must_use]
+    pub fn is_done(&self, advancement: &Advancement) -> bool {
+        !advancement.requirements.is_empty()
+            && advancement.requirements.iter().all(|requirement| {
+                requirement
+                    .iter()
+                    .any(|criterion| self.criteria.contains_key(criterion))
+            })
+    }
+}
+
+/// What the client has to be told about a player's advancements.
+#[derive(Debug)]
+pub struct AdvancementUpdate<'a> {
+    /// Whether the client should forget the advancements it knows first, as it does when
+    /// it's the first update
+    pub reset: bool,
+    pub added: Vec<&'a Advancement>,
+    pub progress: Vec<ProgressUpdate<'a>>,
+    /// The advancements done since the last update, which get announced
+    pub done: Vec<&'a Advancement>,
+}
+
+/// Every criterion of an advancement, with when it was obtained.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ProgressUpdate<'a> {
+    pub id: &'a str,
+    pub criteria: Vec<(&'a str, Option<i64>)>,
+}
+
+/// The advancements of a player, and which of them the client doesn't know about yet.
+#[derive(Debug)]
+pub struct PlayerAdvancements {
+    advancements: Arc<Advancements>,
+    progress: BTreeMap<String, AdvancementProgress>,
+    /// Advancements whose progress changed since the last update
+    changed: BTreeSet<String>,
+    /// Advancements done since the last update
+    done: Vec<String>,
+    sent_advancements: bool,
+}
+
+impl PlayerAdvancements {
+    #[must_use]
+    pub fn new(advancements: Arc<Advancements>) -> Self {
+        Self {
+            advancements,
+            progress: BTreeMap::new(),
+            changed: BTreeSet::new(),
+            done: Vec::new(),
+            sent_advancements: false,
+        }
+    }
+
+    #[must_use]
+    pub fn progress(&self, id: &str) -> Option<&AdvancementProgress> {
+        self.progress.get(id)
+    }
+
+    #[must_use]
+    pub fn is_done(&self, id: &str) -> bool {
+        self.advancements.get(id).is_some_and(|advancement| {
+            self.progress
+                .get(id)
+                .is_some_and(|progress| progress.is_done(advancement))
+        })
+    }
+
+    /// Obtains every criterion `trigger` matches at `now`, in milliseconds since the Unix epoch.
+    /// Returns the advancements which got done by this.
+    pub fn trigger(&mut self, trigger: &AdvancementTrigger, now: i64) -> Vec<&Advancement> {
+        let mut done = Vec::new();
+        for advancement in self.advancements.iter() {
+            let progress = self.progress.entry(advancement.id.clone()).or_default();
+            if progress.is_done(advancement) {
+                continue;
+            }
+            let mut changed = false;
+            for (name, criterion) in &advancement.criteria {
+                if !progress.criteria.contains_key(name) && criterion.matches(trigger) {
+                    progress.criteria.insert(name.clone(), now);
+                    changed = true;
+                }
+            }
+            if changed {
+                self.changed.insert(advancement.id.clone());
+                if progress.is_done(advancement) {
+                    self.done.push(advancement.id.clone());
+                    done.push(advancement);
+                }
+            }
+        }
+        done
+    }
+
+    /// The update to send to the client, or `None` if it's up to date.
+    pub fn take_update(&mut self) -> Option<AdvancementUpdate<'_>> {
+        let reset = !self.sent_advancements;
+        if !reset && self.changed.is_empty() {
+            return None;
+        }
+        self.sent_advancements = true;
+        let changed = std::mem::take(&mut self.changed);
+        let done = std::mem::take(&mut self.done)
+            .iter()
+            .filter_map(|id| self.advancements.get(id))
+            .collect();
+
+        let added = if reset {
+            self.advancements.iter().collect()
+        } else {
+            Vec::new()
+        };
+        // The first update has the progress of every advancement
+        let progress = self
+            .advancements
+            .iter()
+            .filter(|advancement| reset || changed.contains(&advancement.id))
+            .map(|advancement| {
+                let progress = self.progress.get(&advancement.id);
+                let criteria = advancement
+                    .criteria
+                    .keys()
+                    .map(|name| {
+                        let obtained = progress.and_then(|progress| progress.obtained(name));
+                        (name.as_str(), obtained)
+                    })
+                    .collect();
+                ProgressUpdate {
+                    id: &advancement.id,
+                    criteria,
+                }
+            })
+            .collect();
+        Some(AdvancementUpdate {
+            reset,
+            added,
+            progress,
+            done,
+        })
+    }
+
+    /// The progress as saved in `advancements/<uuid>.json`, laid out like vanilla does.
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        let mut json = Map::new();
+        for (id, progress) in &self.progress {
+            if progress.criteria.is_empty() {
+                continue;
+            }
+            let criteria = progress
+                .criteria
+                .iter()
+                .map(|(name, obtained)| (name.clone(), Value::String(format_date(*obtained))))
+                .collect();
+            let done = self
+                .advancements
+                .get(id)
+                .is_some_and(|advancement| progress.is_done(advancement));
+            json.insert(
+                id.clone(),
+                json!({ "criteria": Value::Object(criteria), "done": done }),
+            );
+        }
+        json.insert(
+            "DataVersion".to_string(),
+            MAXIMUM_SUPPORTED_WORLD_DATA_VERSION.into(),
+        );
+        Value::Object(json)
+    }
+
+    /// Loads the progress saved by [`Self::to_json`]. Criteria with unreadable dates are skipped.
+    pub fn read_json(&mut self, json: &Value) {
+        let Some(json) = json.as_object() else {
+            return;
+        };
+        for (id, progress) in json {
+            let Some(criteria) = progress.get("criteria").and_then(Value::as_object) else {
+                continue;
+            };
+            let criteria = criteria
+                .iter()
+                .filter_map(|(name, obtained)| {
+                    Some((name.clone(), parse_date(obtained.as_str()?)?))
+                })
+                .collect();
+            self.progress
+                .insert(id.clone(), AdvancementProgress { criteria });
+        }
+    }
+}
+
+/// Vanilla saves when criteria were obtained like `2025-03-25 17:08:44 +0000`.
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %z";
+
+fn format_date(millis: i64) -> String {
+    DateTime::from_timestamp_millis(millis)
+        .unwrap_or_default()
+        .format(DATE_FORMAT)
+        .to_string()
+}
+
+fn parse_date(date: &str) -> Option<i64> {
+    DateTime::parse_from_str(date, DATE_FORMAT)
+        .ok()
+        .map(|date| date.timestamp_millis())
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use super::{PlayerAdvancements, ProgressUpdate};
+    use crate::advancement::{AdvancementTrigger, Advancements};
+
+    const ROOT: &str = "minecraft:story/root";
+
+    #[test]
+    fn triggered_criterion_completes_advancement() {
+        let vanilla = Arc::new(Advancements::vanilla());
+        let mut advancements = PlayerAdvancements::new(vanilla.clone());
+
+        // The client learns about every advancement first
+        let update = advancements.take_update().unwrap();
+        assert!(update.reset);
+        assert!(
+            update
+                .added
+                .iter()
+                .any(|advancement| advancement.id == ROOT)
+        );
+        assert!(advancements.take_update().is_none());
+
+        let done = advancements.trigger(
+            &AdvancementTrigger::InventoryChanged { items: &["dirt"] },
+            10,
+        );
+        assert!(done.is_empty());
+        assert!(advancements.take_update().is_none());
+
+        let done = advancements.trigger(
+            &AdvancementTrigger::InventoryChanged {
+                items: &["dirt", "crafting_table"],
+            },
+            20,
+        );
+        let done = done
+            .iter()
+            .map(|advancement| advancement.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(done, [ROOT]);
+        assert!(advancements.is_done(ROOT));
+
+        // Then only gets sent the new progress
+        let update = advancements.take_update().unwrap();
+        assert!(!update.reset);
+        assert!(update.added.is_empty());
+        assert_eq!(update.done.len(), 1);
+        assert_eq!(update.done[0].id, ROOT);
+        assert_eq!(
+            update.progress,
+            [ProgressUpdate {
+                id: ROOT,
+                criteria: vec![("crafting_table", Some(20))],
+            }]
+        );
+
+        // Done advancements aren't done again
+        let done = advancements.trigger(
+            &AdvancementTrigger::InventoryChanged {
+                items: &["crafting_table"],
+            },
+            30,
+        );
+        assert!(done.is_empty());
+        assert!(advancements.take_update().is_none());
+
+        // Saved like vanilla does, whole seconds only
+        let json = advancements.to_json();
+        assert_eq!(
+            json[ROOT]["criteria"]["crafting_table"],
+            "1970-01-01 00:00:00 +0000"
+        );
+        assert_eq!(json[ROOT]["done"], true);
+        let mut loaded = PlayerAdvancements::new(vanilla);
+        loaded.read_json(&json);
+        assert!(loaded.is_done(ROOT));
+        assert_eq!(
+            loaded.progress(ROOT).unwrap().obtained("crafting_table"),
+            Some(0)
+        );
+    }
+}
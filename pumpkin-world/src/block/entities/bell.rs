@@ -0,0 +1,114 @@
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::position::BlockPos;
+
+use super::BlockEntity;
+use crate::block::BlockDirection;
+
+/// How long the bell swings after it was rung.
+pub const RING_TICKS: u16 = 50;
+/// How long raiders near a ringing bell keep glowing.
+pub const HIGHLIGHT_TICKS: u16 = 60;
+/// Raiders within this range of a bell are highlighted when it rings.
+pub const HIGHLIGHT_RANGE: f64 = 48.0;
+
+/// The block action telling clients to swing a bell, its parameter is the ring direction.
+pub const RING_ACTION: u8 = 1;
+
+pub struct BellBlockEntity {
+    pub position: BlockPos,
+    /// The side the bell was hit on while it is swinging
+    pub ring_direction: Option<BlockDirection>,
+    /// Whether the bell is currently highlighting raiders
+    pub highlighting: bool,
+}
+
+impl BellBlockEntity {
+    pub const ID: &'static str = "minecraft:bell";
+
+    pub fn new(position: BlockPos) -> Self {
+        Self {
+            position,
+            ring_direction: None,
+            highlighting: false,
+        }
+    }
+
+    /// A bell swinging away from the side it was hit on.
+    pub fn ringing(position: BlockPos, direction: BlockDirection) -> Self {
+        Self {
+            position,
+            ring_direction: Some(direction),
+            highlighting: false,
+        }
+    }
+
+    pub fn is_ringing(&self) -> bool {
+        self.ring_direction.is_some()
+    }
+
+    /// The block action type and parameter which make clients play the swing animation.
+    pub fn ring_action(&self) -> Option<(u8, u8)> {
+        self.ring_direction
+            .map(|direction| (RING_ACTION, direction as u8))
+    }
+}
+
+impl BlockEntity for BellBlockEntity {
+    fn identifier(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn from_nbt(nbt: &NbtCompound, position: BlockPos) -> Self
+    where
+        Self: Sized,
+    {
+        let ring_direction = nbt
+            .get_byte("RingDirection")
+            .and_then(|direction| BlockDirection::try_from(i32::from(direction)).ok());
+        Self {
+            position,
+            ring_direction,
+            highlighting: nbt.get_bool("Highlighting").unwrap_or(false),
+        }
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        if let Some(direction) = self.ring_direction {
+            nbt.put_byte("RingDirection", direction as i8);
+        }
+        nbt.put_bool("Highlighting", self.highlighting);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::{BellBlockEntity, RING_ACTION};
+    use crate::block::BlockDirection;
+    use crate::block::entities::BlockEntity;
+
+    #[test]
+    fn ringing_keeps_its_direction() {
+        let position = BlockPos::new(3, 64, -7);
+        assert_eq!(BellBlockEntity::new(position).ring_action(), None);
+
+        let bell = BellBlockEntity::ringing(position, BlockDirection::East);
+        assert!(bell.is_ringing());
+        assert_eq!(
+            bell.ring_action(),
+            Some((RING_ACTION, BlockDirection::East as u8))
+        );
+
+        let mut nbt = NbtCompound::new();
+        bell.write_nbt(&mut nbt);
+        let loaded = BellBlockEntity::from_nbt(&nbt, position);
+        assert_eq!(loaded.ring_direction, Some(BlockDirection::East));
+        assert!(!loaded.highlighting);
+    }
+}
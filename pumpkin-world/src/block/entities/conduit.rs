@@ -0,0 +1,184 @@
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use uuid::Uuid;
+
+use super::BlockEntity;
+
+/// Blocks which can be part of a conduit's frame.
+pub const CONDUIT_FRAME_BLOCKS: [&str; 4] = [
+    "prismarine",
+    "prismarine_bricks",
+    "sea_lantern",
+    "dark_prismarine",
+];
+
+/// The amount of frame blocks needed for the conduit to activate.
+pub const MIN_ACTIVE_FRAME_BLOCKS: usize = 16;
+/// The amount of frame blocks of a complete frame, needed to attack hostile mobs.
+pub const FULL_FRAME_BLOCKS: usize = 42;
+/// The range in which hostile mobs are attacked by a fully powered conduit.
+pub const ATTACK_RANGE: i32 = 8;
+
+pub struct ConduitBlockEntity {
+    pub position: BlockPos,
+    pub active: bool,
+    /// The hostile mob the conduit is currently attacking
+    pub target: Option<Uuid>,
+}
+
+impl ConduitBlockEntity {
+    pub const ID: &'static str = "minecraft:conduit";
+
+    pub fn new(position: BlockPos) -> Self {
+        Self {
+            position,
+            active: false,
+            target: None,
+        }
+    }
+
+    /// All positions, relative to the conduit, which can hold frame blocks.
+    /// These are the three 5x5 rings around the conduit on each axis.
+    pub fn frame_offsets() -> impl Iterator<Item = Vector3<i32>> {
+        (-2..=2).flat_map(|x: i32| {
+            (-2..=2).flat_map(move |y: i32| {
+                (-2..=2).filter_map(move |z: i32| {
+                    let (ax, ay, az) = (x.abs(), y.abs(), z.abs());
+                    let outside_water_cube = ax > 1 || ay > 1 || az > 1;
+                    let on_ring = (x == 0 && (ay == 2 || az == 2))
+                        || (y == 0 && (ax == 2 || az == 2))
+                        || (z == 0 && (ax == 2 || ay == 2));
+                    (outside_water_cube && on_ring).then_some(Vector3::new(x, y, z))
+                })
+            })
+        })
+    }
+
+    /// Counts the frame blocks around the conduit, given lookups by offset relative to the conduit.
+    ///
+    /// Returns 0 if the conduit isn't surrounded by a 3x3x3 cube of water.
+    pub fn count_frame_blocks(
+        is_water: impl Fn(Vector3<i32>) -> bool,
+        is_frame_block: impl Fn(Vector3<i32>) -> bool,
+    ) -> usize {
+        let submerged =
+            (-1..=1).all(|x| (-1..=1).all(|y| (-1..=1).all(|z| is_water(Vector3::new(x, y, z)))));
+        if !submerged {
+            return 0;
+        }
+        Self::frame_offsets()
+            .filter(|offset| is_frame_block(*offset))
+            .count()
+    }
+
+    pub fn is_active_frame(frame_blocks: usize) -> bool {
+        frame_blocks >= MIN_ACTIVE_FRAME_BLOCKS
+    }
+
+    /// The horizontal range of the conduit power effect; each 7 frame blocks add 16 blocks.
+    pub fn effect_range(frame_blocks: usize) -> i32 {
+        (frame_blocks / 7 * 16) as i32
+    }
+
+    /// Whether an entity at the given position gets conduit power. The range is unlimited vertically
+    /// above the conduit.
+    pub fn is_in_effect_range(&self, range: i32, position: Vector3<f64>) -> bool {
+        let center = self.position.0;
+        let range = f64::from(range);
+        (position.x - f64::from(center.x) - 0.5).abs() <= range + 0.5
+            && (position.z - f64::from(center.z) - 0.5).abs() <= range + 0.5
+            && position.y >= f64::from(center.y) - range
+    }
+}
+
+const ACTIVE: &str = "Active";
+const TARGET: &str = "Target";
+
+impl BlockEntity for ConduitBlockEntity {
+    fn identifier(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn from_nbt(nbt: &NbtCompound, position: BlockPos) -> Self
+    where
+        Self: Sized,
+    {
+        let target = nbt
+            .get_int_array(TARGET)
+            .filter(|target| target.len() == 4)
+            .map(|target| {
+                let most = (i64::from(target[0]) << 32) | i64::from(target[1] as u32);
+                let least = (i64::from(target[2]) << 32) | i64::from(target[3] as u32);
+                Uuid::from_u64_pair(most as u64, least as u64)
+            });
+        Self {
+            position,
+            active: nbt.get_bool(ACTIVE).unwrap_or(false),
+            target,
+        }
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put_bool(ACTIVE, self.active);
+        if let Some(target) = self.target {
+            let (most, least) = target.as_u64_pair();
+            nbt.put(
+                TARGET,
+                pumpkin_nbt::tag::NbtTag::IntArray(
+                    vec![
+                        (most >> 32) as i32,
+                        most as i32,
+                        (least >> 32) as i32,
+                        least as i32,
+                    ]
+                    .into_boxed_slice(),
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::{ConduitBlockEntity, FULL_FRAME_BLOCKS};
+
+    #[test]
+    fn full_frame_has_42_blocks() {
+        assert_eq!(
+            ConduitBlockEntity::frame_offsets().count(),
+            FULL_FRAME_BLOCKS
+        );
+    }
+
+    #[test]
+    fn minimal_frame_activates() {
+        // A single 5x5 ring on the vertical X plane: 16 blocks
+        let ring: Vec<_> = ConduitBlockEntity::frame_offsets()
+            .filter(|offset| offset.z == 0 && (offset.x.abs() == 2 || offset.y.abs() == 2))
+            .collect();
+        assert_eq!(ring.len(), 16);
+
+        let frame_blocks =
+            ConduitBlockEntity::count_frame_blocks(|_| true, |offset| ring.contains(&offset));
+        assert!(ConduitBlockEntity::is_active_frame(frame_blocks));
+        let range = ConduitBlockEntity::effect_range(frame_blocks);
+        assert_eq!(range, 32);
+
+        let conduit = ConduitBlockEntity::new(BlockPos::new(0, 60, 0));
+        assert!(conduit.is_in_effect_range(range, Vector3::new(10.0, 58.0, -20.0)));
+        assert!(!conduit.is_in_effect_range(range, Vector3::new(40.0, 60.0, 0.0)));
+
+        // Not submerged
+        let dry = ConduitBlockEntity::count_frame_blocks(
+            |offset| offset != Vector3::new(0, 1, 0),
+            |offset| ring.contains(&offset),
+        );
+        assert!(!ConduitBlockEntity::is_active_frame(dry));
+    }
+}
@@ -0,0 +1,49 @@
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::position::BlockPos;
+
+use super::BlockEntity;
+use crate::item::ItemStack;
+
+pub struct JukeboxBlockEntity {
+    pub position: BlockPos,
+    /// The music disc inside the jukebox, if any.
+    pub record: Option<ItemStack>,
+}
+
+impl JukeboxBlockEntity {
+    pub const ID: &'static str = "minecraft:jukebox";
+
+    pub fn new(position: BlockPos, record: Option<ItemStack>) -> Self {
+        Self { position, record }
+    }
+
+    /// Reads the music disc of a jukebox block entity from its NBT.
+    pub fn read_record(nbt: &NbtCompound) -> Option<ItemStack> {
+        ItemStack::read_item_stack(nbt.get_compound("RecordItem")?)
+    }
+}
+
+impl BlockEntity for JukeboxBlockEntity {
+    fn identifier(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn from_nbt(nbt: &NbtCompound, position: BlockPos) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(position, Self::read_record(nbt))
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        if let Some(record) = &self.record {
+            let mut item = NbtCompound::new();
+            record.write_item_stack(&mut item);
+            nbt.put_component("RecordItem", item);
+        }
+    }
+}
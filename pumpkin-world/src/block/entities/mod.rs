@@ -1,15 +1,23 @@
 use std::sync::Arc;
 
 use bed::BedBlockEntity;
+use bell::BellBlockEntity;
 use chest::ChestBlockEntity;
 use comparator::ComparatorBlockEntity;
+use conduit::ConduitBlockEntity;
+use jukebox::JukeboxBlockEntity;
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_util::math::position::BlockPos;
+use shulker_box::ShulkerBoxBlockEntity;
 use sign::SignBlockEntity;
 
 pub mod bed;
+pub mod bell;
 pub mod chest;
 pub mod comparator;
+pub mod conduit;
+pub mod jukebox;
+pub mod shulker_box;
 pub mod sign;
 
 pub trait BlockEntity: Send + Sync {
@@ -53,9 +61,19 @@ pub fn block_entity_from_nbt(nbt: &NbtCompound) -> Option<Arc<dyn BlockEntity>>
         ChestBlockEntity::ID => Some(Arc::new(block_entity_from_generic::<ChestBlockEntity>(nbt))),
         SignBlockEntity::ID => Some(Arc::new(block_entity_from_generic::<SignBlockEntity>(nbt))),
         BedBlockEntity::ID => Some(Arc::new(block_entity_from_generic::<BedBlockEntity>(nbt))),
+        BellBlockEntity::ID => Some(Arc::new(block_entity_from_generic::<BellBlockEntity>(nbt))),
         ComparatorBlockEntity::ID => Some(Arc::new(block_entity_from_generic::<
             ComparatorBlockEntity,
         >(nbt))),
+        ConduitBlockEntity::ID => Some(Arc::new(block_entity_from_generic::<ConduitBlockEntity>(
+            nbt,
+        ))),
+        JukeboxBlockEntity::ID => Some(Arc::new(block_entity_from_generic::<JukeboxBlockEntity>(
+            nbt,
+        ))),
+        ShulkerBoxBlockEntity::ID => Some(Arc::new(block_entity_from_generic::<
+            ShulkerBoxBlockEntity,
+        >(nbt))),
         _ => None,
     }
 }
@@ -0,0 +1,78 @@
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::position::BlockPos;
+
+use super::BlockEntity;
+use crate::item::ItemStack;
+
+pub struct ShulkerBoxBlockEntity {
+    pub position: BlockPos,
+    /// The contents by slot, empty slots are [`ItemStack::EMPTY`]
+    pub items: [ItemStack; Self::SLOTS],
+}
+
+impl ShulkerBoxBlockEntity {
+    pub const ID: &'static str = "minecraft:shulker_box";
+    pub const SLOTS: usize = 27;
+
+    pub fn new(position: BlockPos, items: [ItemStack; Self::SLOTS]) -> Self {
+        Self { position, items }
+    }
+
+    pub fn empty(position: BlockPos) -> Self {
+        Self::new(position, [const { ItemStack::EMPTY }; Self::SLOTS])
+    }
+
+    /// Reads the contents of a shulker box block entity from its NBT.
+    pub fn read_items(nbt: &NbtCompound) -> [ItemStack; Self::SLOTS] {
+        let mut items = [const { ItemStack::EMPTY }; Self::SLOTS];
+        for tag in nbt.get_list("Items").into_iter().flatten() {
+            let Some(item) = tag.extract_compound() else {
+                continue;
+            };
+            let (Some(slot), Some(stack)) =
+                (item.get_byte("Slot"), ItemStack::read_item_stack(item))
+            else {
+                continue;
+            };
+            if let Some(slot) = items.get_mut(slot as usize) {
+                *slot = stack;
+            }
+        }
+        items
+    }
+}
+
+impl BlockEntity for ShulkerBoxBlockEntity {
+    fn identifier(&self) -> &'static str {
+        Self::ID
+    }
+
+    fn get_position(&self) -> BlockPos {
+        self.position
+    }
+
+    fn from_nbt(nbt: &NbtCompound, position: BlockPos) -> Self
+    where
+        Self: Sized,
+    {
+        Self::new(position, Self::read_items(nbt))
+    }
+
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let items: Vec<_> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, stack)| !stack.is_empty())
+            .map(|(slot, stack)| {
+                let mut item = NbtCompound::new();
+                item.put_byte("Slot", slot as i8);
+                stack.write_item_stack(&mut item);
+                NbtTag::Compound(item)
+            })
+            .collect();
+        if !items.is_empty() {
+            nbt.put_list("Items", items.into_boxed_slice());
+        }
+    }
+}
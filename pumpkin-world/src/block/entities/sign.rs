@@ -3,7 +3,7 @@ use num_derive::FromPrimitive;
 use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_util::math::position::BlockPos;
 
-#[derive(Clone, Default, FromPrimitive)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, FromPrimitive)]
 #[repr(i8)]
 pub enum DyeColor {
     White = 0,
@@ -25,6 +25,41 @@ pub enum DyeColor {
     Black = 15,
 }
 
+impl DyeColor {
+    /// The RGB color used when tinting textures, like dyed leather armor or banner patterns.
+    pub const fn texture_diffuse_color(self) -> i32 {
+        match self {
+            DyeColor::White => 0xF9FFFE,
+            DyeColor::Orange => 0xF9801D,
+            DyeColor::Magenta => 0xC74EBD,
+            DyeColor::LightBlue => 0x3AB3DA,
+            DyeColor::Yellow => 0xFED83D,
+            DyeColor::Lime => 0x80C71F,
+            DyeColor::Pink => 0xF38BAA,
+            DyeColor::Gray => 0x474F52,
+            DyeColor::LightGray => 0x9D9D97,
+            DyeColor::Cyan => 0x169C9C,
+            DyeColor::Purple => 0x8932B8,
+            DyeColor::Blue => 0x3C44AA,
+            DyeColor::Brown => 0x835432,
+            DyeColor::Green => 0x5E7C16,
+            DyeColor::Red => 0xB02E26,
+            DyeColor::Black => 0x1D1D21,
+        }
+    }
+
+    /// Returns the color of a dye item by its registry key (e.g. `red_dye`).
+    pub fn from_dye_item(registry_key: &str) -> Option<Self> {
+        let color = registry_key.strip_suffix("_dye")?;
+        if color == "black" {
+            return Some(DyeColor::Black);
+        }
+        // `From<String>` falls back to black for unknown colors
+        let dye = DyeColor::from(color.to_string());
+        (dye != DyeColor::Black).then_some(dye)
+    }
+}
+
 impl From<DyeColor> for String {
     fn from(value: DyeColor) -> Self {
         match value {
@@ -0,0 +1,225 @@
+use std::collections::{HashMap, HashSet};
+
+use pumpkin_config::{AntiXrayConfig, AntiXrayEngineMode};
+use pumpkin_data::{
+    Block,
+    block_properties::{get_block, get_state_by_state_id},
+};
+
+use crate::BlockStateId;
+
+use super::{
+    ChunkSections,
+    palette::{BlockPalette, PalettedContainer},
+};
+
+const NEIGHBOURS: [(i32, i32, i32); 6] = [
+    (-1, 0, 0),
+    (1, 0, 0),
+    (0, -1, 0),
+    (0, 1, 0),
+    (0, 0, -1),
+    (0, 0, 1),
+];
+
+/// Changes the blocks of chunks sent to clients so they can't see ores through walls, see
+/// [`AntiXrayConfig`]. The chunks themselves are never changed, so this doesn't affect gameplay.
+#[derive(Debug)]
+pub struct AntiXray {
+    engine_mode: AntiXrayEngineMode,
+    max_block_height: i32,
+    /// Every state of the hidden blocks, with the state they are sent as when hiding ores
+    hidden: HashMap<BlockStateId, BlockStateId>,
+    /// Every state of the blocks which are randomized along with the hidden ones
+    fillers: HashSet<BlockStateId>,
+    /// What randomized blocks are sent as
+    fake_blocks: Box<[BlockStateId]>,
+}
+
+impl AntiXray {
+    /// `None` if anti-xray is disabled or there is nothing to hide.
+    #[must_use]
+    pub fn new(config: &AntiXrayConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        let mut hidden = HashMap::new();
+        let mut fake_blocks = Vec::new();
+        for name in &config.hidden_blocks {
+            let Some(block) = get_block(name) else {
+                log::warn!("Unknown block {name} in the anti-xray hidden blocks");
+                continue;
+            };
+            let filler = filler_of(&block).default_state_id;
+            hidden.extend(block.states.iter().map(|state| (state.id, filler)));
+            fake_blocks.push(block.default_state_id);
+        }
+        if hidden.is_empty() {
+            return None;
+        }
+        let fillers = [Block::STONE, Block::DEEPSLATE, Block::NETHERRACK]
+            .iter()
+            .flat_map(|block| block.states.iter().map(|state| state.id))
+            .collect();
+        Some(Self {
+            engine_mode: config.engine_mode,
+            max_block_height: config.max_block_height,
+            hidden,
+            fillers,
+            fake_blocks: fake_blocks.into_boxed_slice(),
+        })
+    }
+
+    /// Whether a block at `y` may be sent as a different block than it is, so it has to be sent
+    /// again once it gets exposed.
+    #[must_use]
+    pub fn is_obfuscated(&self, block_state_id: BlockStateId, y: i32) -> bool {
+        y < self.max_block_height
+            && (self.hidden.contains_key(&block_state_id)
+                || (self.engine_mode == AntiXrayEngineMode::Randomize
+                    && self.fillers.contains(&block_state_id)))
+    }
+
+    /// The blocks of section `index` of `sections` as they are sent to clients, or `None` if
+    /// they are sent like they are.
+    #[must_use]
+    pub fn obfuscate_section(
+        &self,
+        sections: &ChunkSections,
+        index: usize,
+    ) -> Option<BlockPalette> {
+        let section_y = sections.min_y() + (index * BlockPalette::SIZE) as i32;
+        if section_y >= self.max_block_height {
+            return None;
+        }
+        let block_states = &sections.sections[index].block_states;
+        if let PalettedContainer::Homogeneous(block_state_id) = block_states {
+            // Like the air above the ground, there is nothing to hide
+            if !self.is_obfuscated(*block_state_id, section_y) {
+                return None;
+            }
+        }
+
+        let mut obfuscated: Option<BlockPalette> = None;
+        let mut see_through = HashMap::new();
+        for y in 0..BlockPalette::SIZE {
+            let block_y = section_y + y as i32;
+            if block_y >= self.max_block_height {
+                break;
+            }
+            for z in 0..BlockPalette::SIZE {
+                for x in 0..BlockPalette::SIZE {
+                    let block_state_id = block_states.get(x, y, z);
+                    if !self.is_obfuscated(block_state_id, block_y)
+                        || is_exposed(sections, &mut see_through, x, block_y, z)
+                    {
+                        continue;
+                    }
+                    let sent = match self.engine_mode {
+                        AntiXrayEngineMode::HideOres => self.hidden[&block_state_id],
+                        AntiXrayEngineMode::Randomize => {
+                            self.fake_blocks
+                                [fake_block_index(x, block_y, z, self.fake_blocks.len())]
+                        }
+                    };
+                    if sent != block_state_id {
+                        obfuscated
+                            .get_or_insert_with(|| block_states.clone())
+                            .set(x, y, z, sent);
+                    }
+                }
+            }
+        }
+        obfuscated
+    }
+}
+
+/// Whether clients can see a block through `block_state_id`, which exposes the blocks next to it.
+#[must_use]
+pub fn is_see_through(block_state_id: BlockStateId) -> bool {
+    get_state_by_state_id(block_state_id).is_none_or(|state| !state.is_full_cube())
+}
+
+/// What a hidden block looks like from the outside, so it blends in with the blocks around it.
+fn filler_of(block: &Block) -> Block {
+    if block.name.starts_with("deepslate") {
+        Block::DEEPSLATE
+    } else if block.name.starts_with("nether") || block == &Block::ANCIENT_DEBRIS {
+        Block::NETHERRACK
+    } else {
+        Block::STONE
+    }
+}
+
+/// Whether a block of a chunk can be seen by clients. The blocks of other chunks aren't known
+/// here, so those at the sides of a chunk count as exposed.
+fn is_exposed(
+    sections: &ChunkSections,
+    see_through: &mut HashMap<BlockStateId, bool>,
+    x: usize,
+    y: i32,
+    z: usize,
+) -> bool {
+    NEIGHBOURS.iter().any(|(offset_x, offset_y, offset_z)| {
+        let (Some(x), Some(z)) = (
+            x.checked_add_signed(*offset_x as isize),
+            z.checked_add_signed(*offset_z as isize),
+        ) else {
+            return true;
+        };
+        if x >= BlockPalette::SIZE || z >= BlockPalette::SIZE {
+            return true;
+        }
+        // Nothing can be seen from below or above the world
+        sections
+            .get_block_absolute_y(x, y + offset_y, z)
+            .is_some_and(|neighbour| {
+                *see_through
+                    .entry(neighbour)
+                    .or_insert_with(|| is_see_through(neighbour))
+            })
+    })
+}
+
+/// Picks a fake block for a position, always the same one so chunks look the same when they are
+/// sent again. Real ores get the fake block of their position too, so they don't stand out.
+fn fake_block_index(x: usize, y: i32, z: usize, len: usize) -> usize {
+    let position = (x as u64) | ((z as u64) << 4) | (u64::from(y as u32) << 8);
+    let hash = position.wrapping_mul(0x9E37_79B9_7F4A_7C15);
+    ((hash ^ (hash >> 32)) % len as u64) as usize
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::{AntiXrayConfig, AntiXrayEngineMode};
+    use pumpkin_data::Block;
+
+    use super::AntiXray;
+    use crate::chunk::{ChunkSections, palette::BlockPalette};
+
+    fn stone_sections() -> ChunkSections {
+        let mut sections = ChunkSections::empty(-64, 384).unwrap();
+        for section in sections.sections.iter_mut().take(4) {
+            section.block_states = BlockPalette::Homogeneous(Block::DEEPSLATE.default_state_id);
+        }
+        sections
+    }
+
+    #[test]
+    fn randomize_hides_stone_among_fake_ores() {
+        let anti_xray = AntiXray::new(&AntiXrayConfig {
+            enabled: true,
+            engine_mode: AntiXrayEngineMode::Randomize,
+            ..Default::default()
+        })
+        .unwrap();
+        let sections = stone_sections();
+
+        let obfuscated = anti_xray.obfuscate_section(&sections, 1).unwrap();
+        assert_ne!(obfuscated.get(8, 8, 8), Block::DEEPSLATE.default_state_id);
+        // The sides of a chunk may be seen from the next one
+        assert_eq!(obfuscated.get(0, 8, 8), Block::DEEPSLATE.default_state_id);
+        // Just air up there
+        assert!(anti_xray.obfuscate_section(&sections, 6).is_none());
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use pumpkin_data::{Block, chunk::ChunkStatus};
 use pumpkin_nbt::{compound::NbtCompound, from_bytes, nbt_long_array};
@@ -11,6 +11,7 @@ use super::{
     ChunkData, ChunkHeightmaps, ChunkLightEngine, ChunkParsingError, ChunkSections, ScheduledTick,
     SubChunk, TickPriority,
     palette::{BiomePalette, BlockPalette},
+    world_height_sections,
 };
 
 pub mod anvil;
@@ -82,8 +83,18 @@ impl ChunkData {
             )));
         }
 
+        // Sections in between may be left out, those are empty. Sections just outside the world
+        // bounds only carry light.
+        let section_count = chunk_data
+            .sections
+            .iter()
+            .filter(|section| section.block_states.is_some())
+            .map(|section| i32::from(section.y) - chunk_data.min_y_section + 1)
+            .max()
+            .unwrap_or(0)
+            .max(0);
         let light_engine = ChunkLightEngine {
-            block_light: (0..chunk_data.sections.len() + 2)
+            block_light: (0..section_count as usize + 2)
                 .map(|index| {
                     chunk_data
                         .sections
@@ -96,7 +107,7 @@ impl ChunkData {
                         .unwrap_or_default()
                 })
                 .collect(),
-            sky_light: (0..chunk_data.sections.len() + 2)
+            sky_light: (0..section_count as usize + 2)
                 .map(|index| {
                     chunk_data
                         .sections
@@ -110,22 +121,42 @@ impl ChunkData {
                 })
                 .collect(),
         };
-        let sub_chunks = chunk_data
+
+        let mut block_sections: BTreeMap<i32, _> = chunk_data
             .sections
             .into_iter()
-            .filter(|section| section.y >= chunk_data.min_y_section as i8)
-            .map(|section| SubChunk {
-                block_states: section
-                    .block_states
-                    .map(BlockPalette::from_disk_nbt)
-                    .unwrap_or_default(),
-                biomes: section
-                    .biomes
-                    .map(BiomePalette::from_disk_nbt)
-                    .unwrap_or_default(),
+            .filter(|section| {
+                section.y >= chunk_data.min_y_section as i8 && section.block_states.is_some()
             })
+            .map(|section| (i32::from(section.y), section))
             .collect();
         let min_y = section_coords::section_to_block(chunk_data.min_y_section);
+        let height = section_count as u32 * BlockPalette::SIZE as u32;
+        if world_height_sections(min_y, height).is_err() {
+            return Err(ChunkParsingError::ErrorDeserializingChunk(format!(
+                "Chunk {},{} has invalid sections for a world starting at y {min_y}",
+                position.x, position.z
+            )));
+        }
+
+        let sub_chunks = (0..section_count)
+            .map(|index| {
+                let Some(section) = block_sections.remove(&(chunk_data.min_y_section + index))
+                else {
+                    return SubChunk::default();
+                };
+                SubChunk {
+                    block_states: section
+                        .block_states
+                        .map(BlockPalette::from_disk_nbt)
+                        .unwrap_or_default(),
+                    biomes: section
+                        .biomes
+                        .map(BiomePalette::from_disk_nbt)
+                        .unwrap_or_default(),
+                }
+            })
+            .collect();
         let section = ChunkSections::new(sub_chunks, min_y);
 
         Ok(ChunkData {
@@ -342,3 +373,57 @@ struct ChunkNbt {
     #[serde(rename = "isLightOn")]
     light_correct: bool,
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_nbt::{from_bytes, to_bytes};
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::ChunkNbt;
+    use crate::chunk::{
+        ChunkData, ChunkHeightmaps, ChunkLightEngine, ChunkSections, format::anvil::chunk_to_bytes,
+    };
+
+    #[test]
+    fn left_out_sections_are_empty() {
+        let mut chunk = ChunkData {
+            section: ChunkSections::empty(-64, 384).unwrap(),
+            heightmap: ChunkHeightmaps::empty(384),
+            position: Vector2::new(0, 0),
+            block_ticks: Default::default(),
+            fluid_ticks: Default::default(),
+            block_entities: Default::default(),
+            light_engine: ChunkLightEngine {
+                sky_light: (0..26).map(|_| Default::default()).collect(),
+                block_light: (0..26).map(|_| Default::default()).collect(),
+            },
+            dirty: false,
+        };
+        chunk.set_block_absolute_y(1, -64, 2, Block::STONE.default_state_id);
+        chunk.set_block_absolute_y(1, 319, 2, Block::DIRT.default_state_id);
+
+        // Without the sections between the bottom and the top one
+        let mut nbt: ChunkNbt = from_bytes(&chunk_to_bytes(&chunk).unwrap()[..]).unwrap();
+        nbt.sections
+            .retain(|section| section.y == -4 || section.y == 19);
+        let mut bytes = Vec::new();
+        to_bytes(&nbt, &mut bytes).unwrap();
+
+        let loaded = ChunkData::from_bytes(&bytes, Vector2::new(0, 0)).unwrap();
+        assert_eq!(loaded.section.sections.len(), 24);
+        assert_eq!(loaded.light_engine.sky_light.len(), 26);
+        assert_eq!(
+            loaded.section.get_block_absolute_y(1, -64, 2),
+            Some(Block::STONE.default_state_id)
+        );
+        assert_eq!(
+            loaded.section.get_block_absolute_y(1, 319, 2),
+            Some(Block::DIRT.default_state_id)
+        );
+        assert_eq!(
+            loaded.section.get_block_absolute_y(1, 0, 2),
+            Some(Block::AIR.default_state_id)
+        );
+    }
+}
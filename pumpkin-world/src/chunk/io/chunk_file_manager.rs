@@ -388,4 +388,19 @@ where
         // so there is no **operation** ongoing
         let _ = join_all(tasks).await;
     }
+
+    async fn write_all(&self) {
+        let locks = self.file_locks.read().await;
+        let writes = locks.iter().map(async |(path, loader)| {
+            // Files which were never loaded have nothing to write
+            let Some(serializer) = loader.internal.get() else {
+                return;
+            };
+            log::debug!("Writing file for {:?}", path);
+            if let Err(err) = serializer.read().await.write(path.clone()).await {
+                error!("Failed to write {:?}: {}", path, err);
+            }
+        });
+        join_all(writes).await;
+    }
 }
@@ -0,0 +1,66 @@
+use async_trait::async_trait;
+use dashmap::DashMap;
+use pumpkin_util::math::vector2::Vector2;
+
+use crate::{
+    chunk::{ChunkReadingError, ChunkWritingError},
+    level::{LevelFolder, SyncChunk},
+};
+
+use super::{ChunkIO, LoadedData};
+
+/// Keeps chunks in memory instead of writing them to disk, for levels which are thrown away
+/// once the server stops, like test worlds or minigame lobbies.
+///
+/// Unloaded chunks are kept here so changes to them survive until they are loaded again.
+/// Chunks which were never saved are reported as missing, so they get generated.
+#[derive(Default)]
+pub struct MemoryChunkIO {
+    chunks: DashMap<Vector2<i32>, SyncChunk>,
+}
+
+#[async_trait]
+impl ChunkIO for MemoryChunkIO {
+    type Data = SyncChunk;
+
+    async fn fetch_chunks(
+        &self,
+        _folder: &LevelFolder,
+        chunk_coords: &[Vector2<i32>],
+        stream: tokio::sync::mpsc::Sender<LoadedData<Self::Data, ChunkReadingError>>,
+    ) {
+        for position in chunk_coords {
+            let data = match self.chunks.get(position) {
+                Some(chunk) => LoadedData::Loaded(chunk.value().clone()),
+                None => LoadedData::Missing(*position),
+            };
+            if stream.send(data).await.is_err() {
+                // The receiver stopped listening
+                return;
+            }
+        }
+    }
+
+    async fn save_chunks(
+        &self,
+        _folder: &LevelFolder,
+        chunks_data: Vec<(Vector2<i32>, Self::Data)>,
+    ) -> Result<(), ChunkWritingError> {
+        for (position, chunk) in chunks_data {
+            self.chunks.insert(position, chunk);
+        }
+        Ok(())
+    }
+
+    async fn watch_chunks(&self, _folder: &LevelFolder, _chunks: &[Vector2<i32>]) {}
+
+    async fn unwatch_chunks(&self, _folder: &LevelFolder, _chunks: &[Vector2<i32>]) {}
+
+    async fn clear_watched_chunks(&self) {}
+
+    async fn clean_up_log(&self) {}
+
+    async fn block_and_await_ongoing_tasks(&self) {}
+
+    async fn write_all(&self) {}
+}
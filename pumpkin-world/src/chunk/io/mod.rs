@@ -8,6 +8,7 @@ use super::{ChunkReadingError, ChunkWritingError};
 use crate::level::LevelFolder;
 
 pub mod chunk_file_manager;
+pub mod memory;
 
 /// The result of loading a chunk data.
 ///
@@ -79,6 +80,9 @@ where
 
     /// Ensure that all ongoing operations are finished
     async fn block_and_await_ongoing_tasks(&self);
+
+    /// Writes every file with saved chunks to disk, even those of chunks which are still loaded
+    async fn write_all(&self);
 }
 
 /// Trait to serialize and deserialize the chunk data to and from bytes.
@@ -1,6 +1,7 @@
 use crate::block::entities::BlockEntity;
 use palette::{BiomePalette, BlockPalette};
-use pumpkin_nbt::nbt_long_array;
+use pumpkin_data::block_properties::{blocks_movement, get_state_by_state_id};
+use pumpkin_nbt::{compound::NbtCompound, nbt_long_array};
 use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
@@ -8,7 +9,9 @@ use thiserror::Error;
 
 use crate::BlockStateId;
 use crate::chunk::format::LightContainer;
+use crate::generation::biome_coords;
 
+pub mod anti_xray;
 pub mod format;
 pub mod io;
 pub mod palette;
@@ -18,6 +21,37 @@ pub const CHUNK_WIDTH: usize = BlockPalette::SIZE;
 pub const CHUNK_AREA: usize = CHUNK_WIDTH * CHUNK_WIDTH;
 pub const BIOME_VOLUME: usize = BiomePalette::VOLUME;
 pub const SUBCHUNK_VOLUME: usize = CHUNK_AREA * CHUNK_WIDTH;
+const SUBCHUNK_WIDTH: i32 = CHUNK_WIDTH as i32;
+
+/// The lowest minimum y a dimension may have, see `DimensionType` in vanilla.
+pub const MIN_WORLD_Y: i32 = -2032;
+/// The highest (exclusive) y a dimension may reach.
+pub const MAX_WORLD_Y: i32 = 2032;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum WorldHeightError {
+    #[error("Minimum y {0} must be a multiple of 16")]
+    UnalignedMinY(i32),
+    #[error("World height {0} must be a positive multiple of 16")]
+    UnalignedHeight(u32),
+    #[error("World from y {min_y} with height {height} exceeds the range -2032..2032")]
+    OutOfRange { min_y: i32, height: u32 },
+}
+
+/// Checks that the vertical bounds of a world can be split into chunk sections, returning the
+/// amount of sections.
+pub fn world_height_sections(min_y: i32, height: u32) -> Result<usize, WorldHeightError> {
+    if min_y % SUBCHUNK_WIDTH != 0 {
+        return Err(WorldHeightError::UnalignedMinY(min_y));
+    }
+    if height == 0 || !height.is_multiple_of(SUBCHUNK_WIDTH as u32) {
+        return Err(WorldHeightError::UnalignedHeight(height));
+    }
+    if min_y < MIN_WORLD_Y || i64::from(min_y) + i64::from(height) > i64::from(MAX_WORLD_Y) {
+        return Err(WorldHeightError::OutOfRange { min_y, height });
+    }
+    Ok(height as usize / CHUNK_WIDTH)
+}
 
 #[derive(Error, Debug)]
 pub enum ChunkReadingError {
@@ -123,10 +157,7 @@ pub struct ChunkData {
 
 /// Represents pure block data for a chunk.
 /// Subchunks are vertical portions of a chunk. They are 16 blocks tall.
-/// There are currently 24 subchunks per chunk.
-///
-/// A chunk can be:
-/// - Subchunks: 24 separate subchunks are stored.
+/// The amount of subchunks depends on the height of the dimension, 24 in the overworld.
 #[derive(Debug)]
 pub struct ChunkSections {
     pub sections: Box<[SubChunk]>,
@@ -180,36 +211,133 @@ pub struct ChunkHeightmaps {
     pub motion_blocking: Box<[i64]>,
 }
 
-/// The Heightmap for a completely empty chunk
+/// The Heightmap for a completely empty overworld chunk
 impl Default for ChunkHeightmaps {
     fn default() -> Self {
+        Self::empty(384)
+    }
+}
+
+impl ChunkHeightmaps {
+    /// The Heightmap for a completely empty chunk of the given height
+    pub fn empty(height: u32) -> Self {
+        let (_, entries_per_long) = heightmap_layout(height);
+        let longs = CHUNK_AREA.div_ceil(entries_per_long);
         Self {
-            // 9 bits per entry
-            // 0 packed into an i64 7 times.
-            motion_blocking: vec![0; 37].into_boxed_slice(),
-            world_surface: vec![0; 37].into_boxed_slice(),
+            motion_blocking: vec![0; longs].into_boxed_slice(),
+            world_surface: vec![0; longs].into_boxed_slice(),
         }
     }
 }
 
+/// The bits per entry and entries per long of the heightmaps of a chunk of the given height.
+/// Each entry needs to store 0..=height, entries don't span multiple longs.
+fn heightmap_layout(height: u32) -> (usize, usize) {
+    let bits_per_entry = (u32::BITS - height.leading_zeros()) as usize;
+    (bits_per_entry, 64 / bits_per_entry)
+}
+
+/// The stored height of column `index` (`x + z * 16`): one above its highest occupied block,
+/// counted from the bottom of the world, `0` for an empty column.
+fn heightmap_entry(heightmap: &[i64], height: u32, index: usize) -> u32 {
+    let (bits_per_entry, entries_per_long) = heightmap_layout(height);
+    let long = heightmap[index / entries_per_long] as u64;
+    let shift = (index % entries_per_long) * bits_per_entry;
+    ((long >> shift) & ((1 << bits_per_entry) - 1)) as u32
+}
+
+fn set_heightmap_entry(heightmap: &mut [i64], height: u32, index: usize, value: u32) {
+    let (bits_per_entry, entries_per_long) = heightmap_layout(height);
+    let long = &mut heightmap[index / entries_per_long];
+    let shift = (index % entries_per_long) * bits_per_entry;
+    let mask = ((1u64 << bits_per_entry) - 1) << shift;
+    *long = ((*long as u64 & !mask) | ((u64::from(value) << shift) & mask)) as i64;
+}
+
+/// Whether a block counts for the `WORLD_SURFACE` heightmap
+fn is_world_surface(block_state_id: BlockStateId) -> bool {
+    get_state_by_state_id(block_state_id).is_some_and(|state| !state.is_air())
+}
+
+/// Whether a block counts for the `MOTION_BLOCKING` heightmap
+fn is_motion_blocking(block_state_id: BlockStateId) -> bool {
+    get_state_by_state_id(block_state_id)
+        .is_some_and(|state| blocks_movement(&state) || state.is_liquid())
+}
+
 impl ChunkSections {
     pub fn new(sections: Box<[SubChunk]>, min_y: i32) -> Self {
         Self { sections, min_y }
     }
 
+    /// Creates empty sections covering the given vertical bounds.
+    pub fn empty(min_y: i32, height: u32) -> Result<Self, WorldHeightError> {
+        let section_count = world_height_sections(min_y, height)?;
+        let sections = (0..section_count).map(|_| SubChunk::default()).collect();
+        Ok(Self::new(sections, min_y))
+    }
+
+    /// The lowest y coordinate blocks can be at
+    pub fn min_y(&self) -> i32 {
+        self.min_y
+    }
+
+    /// The amount of blocks from the bottom to the top of the sections
+    pub fn height(&self) -> u32 {
+        (self.sections.len() * BlockPalette::SIZE) as u32
+    }
+
+    /// The highest y coordinate blocks can be at
+    pub fn max_y(&self) -> i32 {
+        self.min_y + self.height() as i32 - 1
+    }
+
+    pub fn is_y_in_bounds(&self, y: i32) -> bool {
+        (self.min_y..=self.max_y()).contains(&y)
+    }
+
     pub fn get_block_absolute_y(
         &self,
         relative_x: usize,
         y: i32,
         relative_z: usize,
     ) -> Option<BlockStateId> {
-        let y = y - self.min_y;
-        if y < 0 {
-            None
-        } else {
-            let relative_y = y as usize;
-            self.get_relative_block(relative_x, relative_y, relative_z)
+        if !self.is_y_in_bounds(y) {
+            return None;
         }
+        let relative_y = (y - self.min_y) as usize;
+        self.get_relative_block(relative_x, relative_y, relative_z)
+    }
+
+    /// The positions of the blocks with any of the given states, relative to the chunk apart from
+    /// the absolute y. Sections without any of them are skipped without looking at their blocks.
+    pub fn find_blocks(&self, block_states: &[BlockStateId]) -> Vec<(usize, i32, usize)> {
+        self.find_blocks_where(|state| block_states.contains(&state))
+    }
+
+    /// Like [`Self::find_blocks`], for the blocks whose state matches `predicate`.
+    pub fn find_blocks_where(
+        &self,
+        predicate: impl Fn(BlockStateId) -> bool,
+    ) -> Vec<(usize, i32, usize)> {
+        let mut found = Vec::new();
+        for (index, section) in self.sections.iter().enumerate() {
+            let palette = &section.block_states;
+            if !palette.any(&predicate) {
+                continue;
+            }
+            let section_y = self.min_y + (index * BlockPalette::SIZE) as i32;
+            for y in 0..BlockPalette::SIZE {
+                for z in 0..BlockPalette::SIZE {
+                    for x in 0..BlockPalette::SIZE {
+                        if predicate(palette.get(x, y, z)) {
+                            found.push((x, section_y + y as i32, z));
+                        }
+                    }
+                }
+            }
+        }
+        found
     }
 
     pub fn set_block_absolute_y(
@@ -219,9 +347,10 @@ impl ChunkSections {
         relative_z: usize,
         block_state: BlockStateId,
     ) {
-        let y = y - self.min_y;
-        debug_assert!(y > 0);
-        let relative_y = y as usize;
+        if !self.is_y_in_bounds(y) {
+            return;
+        }
+        let relative_y = (y - self.min_y) as usize;
 
         self.set_relative_block(relative_x, relative_y, relative_z, block_state);
     }
@@ -280,6 +409,20 @@ impl ChunkSections {
         }
     }
 
+    /// Gets the biome id at the given block, biomes are stored for 4x4x4 blocks each
+    pub fn get_biome_absolute_y(&self, relative_x: usize, y: i32, relative_z: usize) -> Option<u8> {
+        if !self.is_y_in_bounds(y) {
+            return None;
+        }
+        let biome_y = biome_coords::from_block((y - self.min_y) as usize);
+        let section = self.sections.get(biome_y / BiomePalette::SIZE)?;
+        Some(section.biomes.get(
+            biome_coords::from_block(relative_x),
+            biome_y % BiomePalette::SIZE,
+            biome_coords::from_block(relative_z),
+        ))
+    }
+
     /// Sets the given block in the chunk, returning the old block
     pub fn set_relative_biome(
         &mut self,
@@ -343,11 +486,135 @@ impl ChunkData {
             .set_relative_block(relative_x, relative_y, relative_z, block_state_id);
     }
 
-    #[expect(dead_code)]
-    fn calculate_heightmap(&self) -> ChunkHeightmaps {
-        // figure out how LongArray is formatted
-        // figure out how to find out if block is motion blocking
-        todo!()
+    /// Sets the given block in the chunk and keeps the heightmaps of its column up to date
+    pub fn set_block_absolute_y(
+        &mut self,
+        relative_x: usize,
+        y: i32,
+        relative_z: usize,
+        block_state_id: BlockStateId,
+    ) {
+        if !self.section.is_y_in_bounds(y) {
+            return;
+        }
+        self.section
+            .set_block_absolute_y(relative_x, y, relative_z, block_state_id);
+        self.update_heightmap(relative_x, y, relative_z, block_state_id, false);
+        self.update_heightmap(relative_x, y, relative_z, block_state_id, true);
+    }
+
+    /// Raises a heightmap when a block was placed above the column's surface, or scans the
+    /// column for the new surface when its highest block was removed.
+    fn update_heightmap(
+        &mut self,
+        relative_x: usize,
+        y: i32,
+        relative_z: usize,
+        block_state_id: BlockStateId,
+        motion_blocking: bool,
+    ) {
+        let (heightmap, counts): (&mut [i64], fn(BlockStateId) -> bool) = if motion_blocking {
+            (&mut self.heightmap.motion_blocking, is_motion_blocking)
+        } else {
+            (&mut self.heightmap.world_surface, is_world_surface)
+        };
+        let min_y = self.section.min_y();
+        let height = self.section.height();
+        let index = relative_x + relative_z * CHUNK_WIDTH;
+        let stored = heightmap_entry(heightmap, height, index);
+        let placed = (y - min_y + 1) as u32;
+
+        if counts(block_state_id) {
+            if placed > stored {
+                set_heightmap_entry(heightmap, height, index, placed);
+            }
+        } else if placed == stored {
+            let surface = (min_y..y)
+                .rev()
+                .find(|&below| {
+                    self.section
+                        .get_block_absolute_y(relative_x, below, relative_z)
+                        .is_some_and(counts)
+                })
+                .map_or(0, |below| (below - min_y + 1) as u32);
+            set_heightmap_entry(heightmap, height, index, surface);
+        }
+    }
+
+    /// The `WORLD_SURFACE` height of the given column: one above its highest block counted from the bottom of the world
+    pub fn world_surface_height(&self, relative_x: usize, relative_z: usize) -> u32 {
+        heightmap_entry(
+            &self.heightmap.world_surface,
+            self.section.height(),
+            relative_x + relative_z * CHUNK_WIDTH,
+        )
+    }
+
+    /// The `MOTION_BLOCKING` height of the given column: one above its highest block counted from the bottom of the world
+    pub fn motion_blocking_height(&self, relative_x: usize, relative_z: usize) -> u32 {
+        heightmap_entry(
+            &self.heightmap.motion_blocking,
+            self.section.height(),
+            relative_x + relative_z * CHUNK_WIDTH,
+        )
+    }
+
+    /// Computes the heightmaps from the blocks of every column, for chunks which were filled
+    /// without keeping them up to date.
+    pub fn calculate_heightmaps(&mut self) {
+        let height = self.section.height();
+        let mut heightmaps = ChunkHeightmaps::empty(height);
+        let mut counted = HashMap::new();
+        for relative_z in 0..CHUNK_WIDTH {
+            for relative_x in 0..CHUNK_WIDTH {
+                let index = relative_x + relative_z * CHUNK_WIDTH;
+                let mut world_surface = None;
+                for relative_y in (0..height as usize).rev() {
+                    let Some(block_state_id) = self
+                        .section
+                        .get_relative_block(relative_x, relative_y, relative_z)
+                    else {
+                        continue;
+                    };
+                    let (surface, motion_blocking) =
+                        *counted.entry(block_state_id).or_insert_with(|| {
+                            (
+                                is_world_surface(block_state_id),
+                                is_motion_blocking(block_state_id),
+                            )
+                        });
+                    let entry = relative_y as u32 + 1;
+                    if surface && world_surface.is_none() {
+                        world_surface = Some(entry);
+                    }
+                    if motion_blocking {
+                        set_heightmap_entry(&mut heightmaps.motion_blocking, height, index, entry);
+                        break;
+                    }
+                }
+                if let Some(world_surface) = world_surface {
+                    set_heightmap_entry(
+                        &mut heightmaps.world_surface,
+                        height,
+                        index,
+                        world_surface,
+                    );
+                }
+            }
+        }
+        self.heightmap = heightmaps;
+    }
+
+    /// Estimates the heap memory the NBT of the block entities of this chunk takes up.
+    pub fn block_entities_nbt_size(&self) -> usize {
+        self.block_entities
+            .values()
+            .map(|block_entity| {
+                let mut nbt = NbtCompound::new();
+                block_entity.write_internal(&mut nbt);
+                nbt.heap_size()
+            })
+            .sum()
     }
 }
 
@@ -366,3 +633,112 @@ pub enum ChunkSerializingError {
     #[error("Error serializing chunk: {0}")]
     ErrorSerializingChunk(pumpkin_nbt::Error),
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::{
+        ChunkData, ChunkHeightmaps, ChunkLightEngine, ChunkSections, WorldHeightError,
+        world_height_sections,
+    };
+
+    #[test]
+    fn custom_height_bounds() {
+        // A taller and deeper world than the overworld
+        let mut sections = ChunkSections::empty(-128, 512).unwrap();
+        assert_eq!(sections.sections.len(), 32);
+        assert_eq!(sections.min_y(), -128);
+        assert_eq!(sections.max_y(), 383);
+
+        sections.set_block_absolute_y(3, -128, 5, 1);
+        sections.set_block_absolute_y(15, 383, 0, 2);
+        assert_eq!(sections.get_block_absolute_y(3, -128, 5), Some(1));
+        assert_eq!(sections.get_block_absolute_y(15, 383, 0), Some(2));
+
+        // Outside of the bounds nothing is stored
+        sections.set_block_absolute_y(0, 384, 0, 3);
+        assert_eq!(sections.get_block_absolute_y(0, 384, 0), None);
+        assert_eq!(sections.get_block_absolute_y(0, -129, 0), None);
+    }
+
+    #[test]
+    fn blocks_are_found_by_state() {
+        let mut sections = ChunkSections::empty(-64, 384).unwrap();
+        let portal = Block::NETHER_PORTAL.default_state_id;
+        sections.set_block_absolute_y(3, 70, 5, portal);
+        sections.set_block_absolute_y(3, 71, 5, portal);
+        sections.set_block_absolute_y(0, -64, 0, Block::STONE.default_state_id);
+
+        assert_eq!(
+            sections.find_blocks(&[portal]),
+            vec![(3, 70, 5), (3, 71, 5)]
+        );
+        assert!(
+            sections
+                .find_blocks(&[Block::OBSIDIAN.default_state_id])
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn invalid_height_bounds() {
+        assert_eq!(world_height_sections(-64, 384), Ok(24));
+        assert_eq!(
+            world_height_sections(-60, 384),
+            Err(WorldHeightError::UnalignedMinY(-60))
+        );
+        assert_eq!(
+            world_height_sections(0, 100),
+            Err(WorldHeightError::UnalignedHeight(100))
+        );
+        assert!(matches!(
+            world_height_sections(2000, 64),
+            Err(WorldHeightError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn heightmap_size_depends_on_height() {
+        // 9 bits per entry, 7 entries per long
+        assert_eq!(ChunkHeightmaps::empty(384).world_surface.len(), 37);
+        // 10 bits per entry, 6 entries per long
+        assert_eq!(ChunkHeightmaps::empty(512).world_surface.len(), 43);
+    }
+
+    #[test]
+    fn heightmaps_follow_placed_and_broken_blocks() {
+        let mut chunk = ChunkData {
+            section: ChunkSections::empty(-64, 384).unwrap(),
+            heightmap: ChunkHeightmaps::empty(384),
+            position: Vector2::new(0, 0),
+            block_ticks: Default::default(),
+            fluid_ticks: Default::default(),
+            block_entities: Default::default(),
+            light_engine: ChunkLightEngine::default(),
+            dirty: false,
+        };
+        for y in -64..=-60 {
+            chunk.set_block_absolute_y(4, y, 7, Block::STONE.default_state_id);
+        }
+        assert_eq!(chunk.world_surface_height(4, 7), 5);
+        assert_eq!(chunk.motion_blocking_height(4, 7), 5);
+        assert_eq!(chunk.world_surface_height(5, 7), 0);
+
+        // A torch is part of the surface but doesn't block motion
+        chunk.set_block_absolute_y(4, -59, 7, Block::TORCH.default_state_id);
+        chunk.set_block_absolute_y(4, 100, 7, Block::STONE.default_state_id);
+        assert_eq!(chunk.world_surface_height(4, 7), 165);
+        assert_eq!(chunk.motion_blocking_height(4, 7), 165);
+
+        chunk.set_block_absolute_y(4, 100, 7, Block::AIR.default_state_id);
+        assert_eq!(chunk.world_surface_height(4, 7), 6);
+        assert_eq!(chunk.motion_blocking_height(4, 7), 5);
+
+        // Breaking a block below the surface changes nothing
+        chunk.set_block_absolute_y(4, -62, 7, Block::AIR.default_state_id);
+        assert_eq!(chunk.world_surface_height(4, 7), 6);
+        assert_eq!(chunk.motion_blocking_height(4, 7), 5);
+    }
+}
@@ -16,7 +16,7 @@ use super::format::{
 /// 3d array indexed by y,z,x
 type AbstractCube<T, const DIM: usize> = [[[T; DIM]; DIM]; DIM];
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HeterogeneousPaletteData<V: Hash + Eq + Copy, const DIM: usize> {
     cube: Box<AbstractCube<V, DIM>>,
     counts: HashMap<V, u16>,
@@ -55,7 +55,7 @@ impl<V: Hash + Eq + Copy, const DIM: usize> HeterogeneousPaletteData<V, DIM> {
 
 /// A paletted container is a cube of registry ids. It uses a custom compression scheme based on how
 /// may distinct registry ids are in the cube.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum PalettedContainer<V: Hash + Eq + Copy + Default, const DIM: usize> {
     Homogeneous(V),
     Heterogeneous(Box<HeterogeneousPaletteData<V, DIM>>),
@@ -215,6 +215,14 @@ impl<V: Hash + Eq + Copy + Default, const DIM: usize> PalettedContainer<V, DIM>
         }
     }
 
+    /// Whether any entry of the cube matches `predicate`, without looking at every entry.
+    pub fn any(&self, predicate: impl Fn(V) -> bool) -> bool {
+        match self {
+            Self::Homogeneous(registry_id) => predicate(*registry_id),
+            Self::Heterogeneous(data) => data.counts.keys().any(|value| predicate(*value)),
+        }
+    }
+
     pub fn for_each<F>(&self, mut f: F)
     where
         F: FnMut(V),
@@ -420,6 +428,8 @@ impl BlockPalette {
         )
     }
 
+    /// The saved palette only has the blocks which are still in the section, packed with as few
+    /// bits as possible, no matter how many entries the palette it was loaded from had.
     pub fn to_disk_nbt(&self) -> ChunkSectionBlockStates {
         let bits_per_entry = self.bits_per_entry().max(BLOCK_DISK_MIN_BITS);
         let (palette, packed_data) = self.to_palette_and_packed_data(bits_per_entry);
@@ -485,3 +495,51 @@ const BIOME_DISK_MIN_BITS: u8 = 0;
 const BIOME_NETWORK_MIN_MAP_BITS: u8 = 1;
 const BIOME_NETWORK_MAX_MAP_BITS: u8 = 3;
 pub(crate) const BIOME_NETWORK_MAX_BITS: u8 = 7;
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::encompassing_bits;
+
+    use super::BlockPalette;
+
+    fn pack(indices: impl Iterator<Item = usize>, bits_per_entry: u8) -> Box<[i64]> {
+        let indices: Vec<_> = indices.collect();
+        indices
+            .chunks(64 / bits_per_entry as usize)
+            .map(|chunk| {
+                chunk.iter().enumerate().fold(0, |acc, (index, key)| {
+                    acc | (*key as i64) << (bits_per_entry as usize * index)
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn unused_palette_entries_are_dropped_on_save() {
+        // A palette of 20 blocks where only 3 are still in use, like after many edits
+        let palette: Vec<u16> = (1..=20).collect();
+        let bits_per_entry = encompassing_bits(palette.len());
+        assert_eq!(bits_per_entry, 5);
+        let used = [0, 7, 19];
+        let packed = pack(
+            (0..BlockPalette::VOLUME).map(|i| used[i % used.len()]),
+            bits_per_entry,
+        );
+        let loaded = BlockPalette::from_palette_and_packed_data(&palette, &packed, 4);
+
+        let saved = loaded.to_disk_nbt();
+        assert_eq!(saved.palette.len(), used.len());
+        // 3 entries fit in 2 bits, but the disk format needs at least 4
+        let saved_data = saved.data.clone().unwrap();
+        assert_eq!(saved_data.len(), BlockPalette::VOLUME / (64 / 4));
+        assert!(saved_data.len() < packed.len());
+
+        let reloaded = BlockPalette::from_disk_nbt(saved);
+        let mut index = 0;
+        reloaded.for_each(|state_id| {
+            assert_eq!(state_id, palette[used[index % used.len()]]);
+            index += 1;
+        });
+        assert_eq!(index, BlockPalette::VOLUME);
+    }
+}
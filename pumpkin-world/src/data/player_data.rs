@@ -1,6 +1,7 @@
 use pumpkin_config::advanced_config;
 use pumpkin_nbt::compound::NbtCompound;
-use std::fs::{File, create_dir_all};
+use serde_json::Value;
+use std::fs::{self, File, create_dir_all};
 use std::io;
 use std::path::PathBuf;
 use uuid::Uuid;
@@ -22,6 +23,8 @@ pub enum PlayerDataError {
     Io(#[from] io::Error),
     #[error("NBT error: {0}")]
     Nbt(String),
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
 }
 
 impl PlayerDataStorage {
@@ -44,6 +47,16 @@ impl PlayerDataStorage {
         }
     }
 
+    /// A storage which never touches the disk, for in-memory worlds. Players start fresh
+    /// every time they join.
+    #[must_use]
+    pub fn disabled() -> Self {
+        Self {
+            data_path: PathBuf::new(),
+            save_enabled: false,
+        }
+    }
+
     #[must_use]
     pub fn get_data_path(&self) -> &PathBuf {
         &self.data_path
@@ -63,6 +76,43 @@ impl PlayerDataStorage {
         self.get_data_path().join(format!("{uuid}.dat"))
     }
 
+    /// Returns the path of a player's advancements, in the `advancements` folder next to the
+    /// player data like vanilla has it.
+    pub fn get_advancements_path(&self, uuid: &Uuid) -> PathBuf {
+        self.get_data_path()
+            .with_file_name("advancements")
+            .join(format!("{uuid}.json"))
+    }
+
+    /// Loads the advancement progress of a player, `None` if there is none yet.
+    pub fn load_advancements(&self, uuid: &Uuid) -> Result<Option<Value>, PlayerDataError> {
+        if !self.is_save_enabled() {
+            return Ok(None);
+        }
+        let path = self.get_advancements_path(uuid);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    /// Saves the advancement progress of a player.
+    pub fn save_advancements(
+        &self,
+        uuid: &Uuid,
+        advancements: &Value,
+    ) -> Result<(), PlayerDataError> {
+        if !self.is_save_enabled() {
+            return Ok(());
+        }
+        let path = self.get_advancements_path(uuid);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(advancements)?)?;
+        Ok(())
+    }
+
     /// Loads player data from NBT file or cache.
     ///
     /// This function first checks if player data exists in the cache.
@@ -0,0 +1,150 @@
+use pumpkin_data::Block;
+use pumpkin_data::tag::Tagable;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::random::{RandomGenerator, RandomImpl};
+use serde::Deserialize;
+
+use super::StateProvider;
+use crate::{
+    block::RawBlockState,
+    generation::{height_limit::HeightLimitView, proto_chunk::ProtoChunk},
+};
+
+const WIDTH: i32 = 16;
+const HEIGHT: i32 = 8;
+/// The lower half of the lake is filled with the fluid, the upper half is cleared.
+const FLUID_HEIGHT: i32 = 4;
+
+/// A pool of fluid made of a few overlapping blobs, walled in by the barrier where needed.
+#[derive(Deserialize)]
+pub struct LakeConfig {
+    fluid: StateProvider,
+    barrier: StateProvider,
+}
+
+struct LakeShape {
+    cells: Vec<bool>,
+}
+
+impl LakeShape {
+    fn generate(random: &mut RandomGenerator) -> Self {
+        let mut cells = vec![false; (WIDTH * WIDTH * HEIGHT) as usize];
+        let blobs = random.next_bounded_i32(4) + 4;
+        for _ in 0..blobs {
+            let size_x = random.next_f64() * 6.0 + 3.0;
+            let size_y = random.next_f64() * 4.0 + 2.0;
+            let size_z = random.next_f64() * 6.0 + 3.0;
+            let center_x = random.next_f64() * (16.0 - size_x - 2.0) + 1.0 + size_x / 2.0;
+            let center_y = random.next_f64() * (8.0 - size_y - 4.0) + 2.0 + size_y / 2.0;
+            let center_z = random.next_f64() * (16.0 - size_z - 2.0) + 1.0 + size_z / 2.0;
+
+            for x in 1..WIDTH - 1 {
+                for z in 1..WIDTH - 1 {
+                    for y in 1..HEIGHT - 1 {
+                        let dx = (f64::from(x) - center_x) / (size_x / 2.0);
+                        let dy = (f64::from(y) - center_y) / (size_y / 2.0);
+                        let dz = (f64::from(z) - center_z) / (size_z / 2.0);
+                        if dx * dx + dy * dy + dz * dz < 1.0 {
+                            cells[Self::index(x, y, z)] = true;
+                        }
+                    }
+                }
+            }
+        }
+        Self { cells }
+    }
+
+    fn index(x: i32, y: i32, z: i32) -> usize {
+        ((x * WIDTH + z) * HEIGHT + y) as usize
+    }
+
+    fn contains(&self, x: i32, y: i32, z: i32) -> bool {
+        (0..WIDTH).contains(&x)
+            && (0..HEIGHT).contains(&y)
+            && (0..WIDTH).contains(&z)
+            && self.cells[Self::index(x, y, z)]
+    }
+
+    /// Cells outside the lake touching it, these have to hold the fluid in.
+    fn is_border(&self, x: i32, y: i32, z: i32) -> bool {
+        !self.contains(x, y, z)
+            && (self.contains(x + 1, y, z)
+                || self.contains(x - 1, y, z)
+                || self.contains(x, y + 1, z)
+                || self.contains(x, y - 1, z)
+                || self.contains(x, y, z + 1)
+                || self.contains(x, y, z - 1))
+    }
+}
+
+impl LakeConfig {
+    /// Lakes are as wide as a chunk, so they are placed in the chunk containing `origin` and
+    /// never reach into its neighbours.
+    pub fn generate(
+        &self,
+        chunk: &mut ProtoChunk,
+        random: &mut RandomGenerator,
+        origin: Vector3<i32>,
+    ) -> bool {
+        let Some(fluid) = self.fluid.get() else {
+            return false;
+        };
+        if origin.y <= chunk.bottom_y() as i32 + FLUID_HEIGHT {
+            return false;
+        }
+        let corner = Vector3::new(origin.x & !15, origin.y - FLUID_HEIGHT, origin.z & !15);
+        let top = Vector3::new(corner.x, corner.y + HEIGHT - 1, corner.z);
+        if !chunk.contains(&corner) || !chunk.contains(&top) {
+            return false;
+        }
+
+        let shape = LakeShape::generate(random);
+        let cells = || {
+            (0..WIDTH).flat_map(|x| {
+                (0..WIDTH).flat_map(move |z| (0..HEIGHT).map(move |y| Vector3::new(x, y, z)))
+            })
+        };
+
+        // Don't spill into open air or other fluids
+        for cell in cells().filter(|cell| shape.is_border(cell.x, cell.y, cell.z)) {
+            let state = chunk.get_block_state(&(corner + cell)).to_state();
+            if cell.y >= FLUID_HEIGHT && state.is_liquid() {
+                return false;
+            }
+            if cell.y < FLUID_HEIGHT && !state.is_solid() && state.id != fluid.state_id {
+                return false;
+            }
+        }
+
+        for cell in cells().filter(|cell| shape.contains(cell.x, cell.y, cell.z)) {
+            let state = if cell.y >= FLUID_HEIGHT {
+                Block::CAVE_AIR.default_state_id
+            } else {
+                fluid.state_id
+            };
+            chunk.set_block_state(
+                &(corner + cell),
+                RawBlockState { state_id: state }.to_state(),
+            );
+        }
+
+        if let Some(barrier) = self.barrier.get() {
+            for cell in cells().filter(|cell| shape.is_border(cell.x, cell.y, cell.z)) {
+                if cell.y >= FLUID_HEIGHT && random.next_bounded_i32(2) == 0 {
+                    continue;
+                }
+                let pos = corner + cell;
+                let current = chunk.get_block_state(&pos);
+                if current.to_state().is_solid()
+                    && !current
+                        .to_block()
+                        .is_tagged_with("minecraft:lava_pool_stone_cannot_replace")
+                        .unwrap_or(false)
+                {
+                    chunk.set_block_state(&pos, barrier.to_state());
+                }
+            }
+        }
+        true
+    }
+}
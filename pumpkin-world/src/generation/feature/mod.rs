@@ -0,0 +1,350 @@
+use std::{collections::HashMap, sync::LazyLock};
+
+use lake::LakeConfig;
+use ore::OreConfig;
+use placement::{PlacementContext, PlacementModifier};
+use pumpkin_util::math::{java_string_hash, vector2::Vector2, vector3::Vector3};
+use pumpkin_util::random::{RandomGenerator, RandomImpl, xoroshiro128::Xoroshiro};
+use serde::{Deserialize, de::DeserializeOwned};
+use tree::TreeConfig;
+
+use super::{
+    height_limit::HeightLimitView,
+    positions::chunk_pos::{start_block_x, start_block_z},
+    proto_chunk::ProtoChunk,
+};
+use crate::block::{BlockStateCodec, RawBlockState};
+
+mod lake;
+mod ore;
+pub mod placement;
+mod tree;
+
+/// The feature steps of a biome, in the order they are placed.
+pub const FEATURE_STEPS: usize = 11;
+
+/// Features which don't need the terrain around them, like ores, are also placed from the
+/// neighbouring chunks this far away so they can reach into the generated chunk.
+const FEATURE_BORDER_CHUNKS: i32 = 1;
+
+pub static PLACED_FEATURES: LazyLock<HashMap<String, PlacedFeature>> =
+    LazyLock::new(|| load_features(include_str!("../../../../assets/placed_feature.json")));
+
+pub static CONFIGURED_FEATURES: LazyLock<HashMap<String, ConfiguredFeature>> =
+    LazyLock::new(|| load_features(include_str!("../../../../assets/gen_features.json")));
+
+/// Only a part of the vanilla features can be parsed, the others are skipped.
+fn load_features<T: DeserializeOwned>(json: &str) -> HashMap<String, T> {
+    let features: HashMap<String, serde_json::Value> =
+        serde_json::from_str(json).expect("Could not parse features registry.");
+    features
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, serde_json::from_value(value).ok()?)))
+        .collect()
+}
+
+fn strip_namespace(name: &str) -> &str {
+    name.strip_prefix("minecraft:").unwrap_or(name)
+}
+
+/// Where and how often a configured feature is placed.
+#[derive(Deserialize)]
+pub struct PlacedFeature {
+    feature: ConfiguredFeatureRef,
+    placement: Vec<PlacementModifier>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ConfiguredFeatureRef {
+    Named(String),
+    Inline(Box<ConfiguredFeature>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PlacedFeatureRef {
+    Named(String),
+    Inline(Box<PlacedFeature>),
+}
+
+impl ConfiguredFeatureRef {
+    fn get(&self) -> Option<&ConfiguredFeature> {
+        match self {
+            Self::Named(name) => CONFIGURED_FEATURES.get(strip_namespace(name)),
+            Self::Inline(feature) => Some(feature),
+        }
+    }
+}
+
+impl PlacedFeatureRef {
+    fn get(&self) -> Option<&PlacedFeature> {
+        match self {
+            Self::Named(name) => PLACED_FEATURES.get(strip_namespace(name)),
+            Self::Inline(feature) => Some(feature),
+        }
+    }
+}
+
+/// What a feature places, like an ore vein or a tree.
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+pub enum ConfiguredFeature {
+    #[serde(rename = "minecraft:ore")]
+    Ore { config: OreConfig },
+    #[serde(rename = "minecraft:tree")]
+    Tree { config: TreeConfig },
+    #[serde(rename = "minecraft:lake")]
+    Lake { config: LakeConfig },
+    #[serde(rename = "minecraft:random_selector")]
+    RandomSelector { config: RandomSelectorConfig },
+    #[serde(other)]
+    Unsupported,
+}
+
+/// Picks the first feature whose chance hits, or the default one.
+#[derive(Deserialize)]
+pub struct RandomSelectorConfig {
+    features: Vec<WeightedFeature>,
+    default: PlacedFeatureRef,
+}
+
+#[derive(Deserialize)]
+struct WeightedFeature {
+    feature: PlacedFeatureRef,
+    chance: f32,
+}
+
+/// Provides the blocks placed by a feature.
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+pub enum StateProvider {
+    #[serde(rename = "minecraft:simple_state_provider")]
+    Simple { state: BlockStateCodec },
+    #[serde(other)]
+    Unsupported,
+}
+
+impl StateProvider {
+    pub fn get(&self) -> Option<RawBlockState> {
+        match self {
+            Self::Simple { state } => RawBlockState::new(&state.name),
+            Self::Unsupported => None,
+        }
+    }
+}
+
+impl ConfiguredFeature {
+    /// Whether the feature depends on the terrain around it, such features are only placed from
+    /// the chunk being generated and have to keep away from its edges.
+    fn needs_terrain(&self) -> bool {
+        !matches!(self, Self::Ore { .. })
+    }
+
+    fn generate(
+        &self,
+        chunk: &mut ProtoChunk,
+        random: &mut RandomGenerator,
+        context: &PlacementContext,
+        origin: Vector3<i32>,
+    ) -> bool {
+        match self {
+            Self::Ore { config } => config.generate(chunk, random, origin),
+            Self::Tree { config } => config.generate(chunk, random, origin),
+            Self::Lake { config } => config.generate(chunk, random, origin),
+            Self::RandomSelector { config } => {
+                let chosen = config
+                    .features
+                    .iter()
+                    .find(|entry| random.next_f32() < entry.chance)
+                    .map_or(&config.default, |entry| &entry.feature);
+                chosen
+                    .get()
+                    .is_some_and(|feature| feature.generate(chunk, random, context, origin))
+            }
+            Self::Unsupported => false,
+        }
+    }
+}
+
+impl PlacedFeature {
+    fn needs_terrain(&self) -> bool {
+        self.placement.iter().any(PlacementModifier::needs_terrain)
+            || self
+                .feature
+                .get()
+                .is_none_or(ConfiguredFeature::needs_terrain)
+    }
+
+    /// Places the feature at all positions its placement modifiers produce from `origin`.
+    fn generate(
+        &self,
+        chunk: &mut ProtoChunk,
+        random: &mut RandomGenerator,
+        context: &PlacementContext,
+        origin: Vector3<i32>,
+    ) -> bool {
+        let Some(feature) = self.feature.get() else {
+            return false;
+        };
+        if !self.placement.iter().all(PlacementModifier::is_supported) {
+            return false;
+        }
+
+        let mut positions = vec![origin];
+        for modifier in &self.placement {
+            positions = positions
+                .into_iter()
+                .flat_map(|pos| modifier.get_positions(chunk, random, context, pos))
+                .collect();
+        }
+
+        let mut placed = false;
+        for pos in positions {
+            placed |= feature.generate(chunk, random, context, pos);
+        }
+        placed
+    }
+}
+
+/// The seed of the random used to decorate a chunk, the same way vanilla derives it.
+fn population_seed(world_seed: u64, block_x: i32, block_z: i32) -> i64 {
+    let mut random = Xoroshiro::from_seed(world_seed);
+    let a = random.next_i64() | 1;
+    let b = random.next_i64() | 1;
+    (i64::from(block_x).wrapping_mul(a)).wrapping_add(i64::from(block_z).wrapping_mul(b))
+        ^ world_seed as i64
+}
+
+/// Every feature gets its own random, depending only on the seed, the chunk it is placed from and
+/// the feature itself. This way it is placed the same no matter which chunk is being generated.
+fn decorator_random(
+    world_seed: u64,
+    chunk_pos: &Vector2<i32>,
+    feature: &str,
+    step: usize,
+) -> RandomGenerator {
+    let seed = population_seed(
+        world_seed,
+        start_block_x(chunk_pos),
+        start_block_z(chunk_pos),
+    )
+    .wrapping_add(i64::from(java_string_hash(feature)))
+    .wrapping_add(10000 * step as i64);
+    RandomGenerator::Xoroshiro(Xoroshiro::from_seed(seed as u64))
+}
+
+/// Places the features of all biomes in the chunk, step by step.
+pub fn place_features(chunk: &mut ProtoChunk, world_seed: u64) {
+    let chunk_pos = chunk.chunk_pos();
+    for step in 0..FEATURE_STEPS {
+        for name in chunk.step_features(step) {
+            let Some(feature) = PLACED_FEATURES.get(strip_namespace(name)) else {
+                continue;
+            };
+            let needs_terrain = feature.needs_terrain();
+            let (border, margin) = if needs_terrain {
+                (0, TreeConfig::MAX_FOLIAGE_RADIUS)
+            } else {
+                (FEATURE_BORDER_CHUNKS, 0)
+            };
+            let context = PlacementContext {
+                feature: name,
+                step,
+                margin,
+            };
+
+            for dx in -border..=border {
+                for dz in -border..=border {
+                    let origin_chunk = Vector2::new(chunk_pos.x + dx, chunk_pos.z + dz);
+                    let mut random = decorator_random(world_seed, &origin_chunk, name, step);
+                    let origin = Vector3::new(
+                        start_block_x(&origin_chunk),
+                        chunk.bottom_y() as i32,
+                        start_block_z(&origin_chunk),
+                    );
+                    feature.generate(chunk, &mut random, &context, origin);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::LazyLock;
+
+    use pumpkin_data::{Block, noise_router::OVERWORLD_BASE_NOISE_ROUTER};
+    use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
+
+    use crate::generation::{
+        GlobalRandomConfig,
+        height_limit::HeightLimitView,
+        noise_router::proto_noise_router::ProtoNoiseRouters,
+        proto_chunk::ProtoChunk,
+        settings::{GENERATION_SETTINGS, GeneratorSetting},
+    };
+
+    use super::PLACED_FEATURES;
+
+    const SEED: u64 = 0;
+    static RANDOM_CONFIG: LazyLock<GlobalRandomConfig> =
+        LazyLock::new(|| GlobalRandomConfig::new(SEED, false));
+    static BASE_NOISE_ROUTER: LazyLock<ProtoNoiseRouters> =
+        LazyLock::new(|| ProtoNoiseRouters::generate(&OVERWORLD_BASE_NOISE_ROUTER, &RANDOM_CONFIG));
+
+    fn find_blocks(chunk: &ProtoChunk, blocks: &[Block]) -> Vec<Vector3<i32>> {
+        let mut found = Vec::new();
+        for x in 0..16 {
+            for z in 0..16 {
+                for y in chunk.bottom_y() as i32..chunk.top_y() as i32 {
+                    let pos = Vector3::new(x, y, z);
+                    if blocks.contains(&chunk.get_block_state(&pos).to_block()) {
+                        found.push(pos);
+                    }
+                }
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn ores_are_placed_in_their_height_ranges() {
+        assert!(PLACED_FEATURES.contains_key("ore_diamond"));
+        assert!(PLACED_FEATURES.contains_key("ore_coal_lower"));
+
+        let settings = GENERATION_SETTINGS
+            .get(&GeneratorSetting::Overworld)
+            .unwrap();
+        let mut chunk = ProtoChunk::new(
+            Vector2::new(0, 0),
+            &BASE_NOISE_ROUTER,
+            &RANDOM_CONFIG,
+            settings,
+        );
+        chunk.populate_biomes();
+        chunk.populate_noise();
+        chunk.build_surface();
+
+        let diamonds = [Block::DIAMOND_ORE, Block::DEEPSLATE_DIAMOND_ORE];
+        let coal = [Block::COAL_ORE, Block::DEEPSLATE_COAL_ORE];
+        let iron = [Block::IRON_ORE, Block::DEEPSLATE_IRON_ORE];
+        assert!(find_blocks(&chunk, &diamonds).is_empty());
+
+        chunk.place_features();
+
+        // Diamonds are placed up to y 16, veins may reach a few blocks further
+        let diamond_ores = find_blocks(&chunk, &diamonds);
+        assert!(!diamond_ores.is_empty());
+        assert!(diamond_ores.iter().all(|pos| pos.y <= 21));
+        assert!(diamond_ores.len() < 200, "{} diamonds", diamond_ores.len());
+
+        // Coal is placed from y 0 upwards
+        let coal_ores = find_blocks(&chunk, &coal);
+        assert!(!coal_ores.is_empty());
+        assert!(coal_ores.iter().all(|pos| pos.y >= -6));
+
+        let iron_ores = find_blocks(&chunk, &iron).len();
+        assert!((20..1000).contains(&iron_ores), "{iron_ores} iron ores");
+    }
+}
@@ -0,0 +1,147 @@
+use std::{collections::HashSet, f64::consts::PI};
+
+use pumpkin_data::tag::Tagable;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::random::{RandomGenerator, RandomImpl};
+use serde::Deserialize;
+
+use crate::{
+    block::{BlockStateCodec, RawBlockState},
+    generation::proto_chunk::ProtoChunk,
+};
+
+/// A vein of ore blocks replacing the stone around it.
+#[derive(Deserialize)]
+pub struct OreConfig {
+    targets: Vec<OreTarget>,
+    size: i32,
+    discard_chance_on_air_exposure: f32,
+}
+
+#[derive(Deserialize)]
+struct OreTarget {
+    target: RuleTest,
+    state: BlockStateCodec,
+}
+
+/// Which blocks an ore may replace.
+#[derive(Deserialize)]
+#[serde(tag = "predicate_type")]
+enum RuleTest {
+    #[serde(rename = "minecraft:tag_match")]
+    TagMatch { tag: String },
+    #[serde(rename = "minecraft:block_match")]
+    BlockMatch { block: String },
+    #[serde(other)]
+    Unsupported,
+}
+
+impl RuleTest {
+    fn test(&self, state: RawBlockState) -> bool {
+        match self {
+            Self::TagMatch { tag } => state.to_block().is_tagged_with(tag).unwrap_or(false),
+            Self::BlockMatch { block } => {
+                state.to_block().name == block.strip_prefix("minecraft:").unwrap_or(block)
+            }
+            Self::Unsupported => false,
+        }
+    }
+}
+
+impl OreConfig {
+    /// Places the vein along a random line through `origin`, made of spheres which are thicker in
+    /// the middle.
+    ///
+    /// Veins may reach into the neighbouring chunks, only the blocks in the generated chunk are set.
+    /// The random is used the same way no matter which chunk is generated, so veins line up at the
+    /// chunk borders.
+    pub fn generate(
+        &self,
+        chunk: &mut ProtoChunk,
+        random: &mut RandomGenerator,
+        origin: Vector3<i32>,
+    ) -> bool {
+        let size = f64::from(self.size);
+        let angle = f64::from(random.next_f32()) * PI;
+        let spread = size / 8.0;
+        let start_x = f64::from(origin.x) + angle.sin() * spread;
+        let end_x = f64::from(origin.x) - angle.sin() * spread;
+        let start_z = f64::from(origin.z) + angle.cos() * spread;
+        let end_z = f64::from(origin.z) - angle.cos() * spread;
+        let start_y = f64::from(origin.y + random.next_bounded_i32(3) - 2);
+        let end_y = f64::from(origin.y + random.next_bounded_i32(3) - 2);
+
+        let mut visited = HashSet::new();
+        let mut placed = false;
+        for i in 0..self.size {
+            let progress = f64::from(i) / size;
+            let x = start_x + (end_x - start_x) * progress;
+            let y = start_y + (end_y - start_y) * progress;
+            let z = start_z + (end_z - start_z) * progress;
+            let thickness = random.next_f64() * size / 16.0;
+            let radius = ((PI * progress).sin() + 1.0) * thickness + 1.0;
+            let radius = radius / 2.0;
+
+            for block_x in (x - radius).floor() as i32..=(x + radius).floor() as i32 {
+                let dx = (f64::from(block_x) + 0.5 - x) / radius;
+                for block_y in (y - radius).floor() as i32..=(y + radius).floor() as i32 {
+                    let dy = (f64::from(block_y) + 0.5 - y) / radius;
+                    for block_z in (z - radius).floor() as i32..=(z + radius).floor() as i32 {
+                        let dz = (f64::from(block_z) + 0.5 - z) / radius;
+                        if dx * dx + dy * dy + dz * dz >= 1.0 {
+                            continue;
+                        }
+                        let pos = Vector3::new(block_x, block_y, block_z);
+                        if !visited.insert(pos) {
+                            continue;
+                        }
+                        let discard_roll = if self.discard_chance_on_air_exposure > 0.0 {
+                            random.next_f32()
+                        } else {
+                            1.0
+                        };
+                        if chunk.contains(&pos) && self.try_place(chunk, pos, discard_roll) {
+                            placed = true;
+                        }
+                    }
+                }
+            }
+        }
+        placed
+    }
+
+    fn try_place(&self, chunk: &mut ProtoChunk, pos: Vector3<i32>, discard_roll: f32) -> bool {
+        let current = chunk.get_block_state(&pos);
+        let Some(target) = self
+            .targets
+            .iter()
+            .find(|target| target.target.test(current))
+        else {
+            return false;
+        };
+        if discard_roll < self.discard_chance_on_air_exposure && is_exposed_to_air(chunk, pos) {
+            return false;
+        }
+        let Some(state) = RawBlockState::new(&target.state.name) else {
+            return false;
+        };
+        chunk.set_block_state(&pos, state.to_state());
+        true
+    }
+}
+
+fn is_exposed_to_air(chunk: &ProtoChunk, pos: Vector3<i32>) -> bool {
+    [
+        Vector3::new(1, 0, 0),
+        Vector3::new(-1, 0, 0),
+        Vector3::new(0, 1, 0),
+        Vector3::new(0, -1, 0),
+        Vector3::new(0, 0, 1),
+        Vector3::new(0, 0, -1),
+    ]
+    .into_iter()
+    .map(|offset| pos + offset)
+    .any(|neighbor| {
+        chunk.contains(&neighbor) && chunk.get_block_state(&neighbor).to_state().is_air()
+    })
+}
@@ -0,0 +1,183 @@
+use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
+use pumpkin_util::random::{RandomGenerator, RandomImpl};
+use serde::Deserialize;
+
+use crate::generation::{
+    height_limit::HeightLimitView, height_provider::HeightProvider, proto_chunk::ProtoChunk,
+};
+
+/// Where and how often a feature is placed, applied one after the other to the origin of a chunk.
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+pub enum PlacementModifier {
+    #[serde(rename = "minecraft:count")]
+    Count { count: IntProvider },
+    #[serde(rename = "minecraft:in_square")]
+    InSquare,
+    #[serde(rename = "minecraft:height_range")]
+    HeightRange { height: HeightProvider },
+    #[serde(rename = "minecraft:heightmap")]
+    Heightmap { heightmap: HeightmapType },
+    #[serde(rename = "minecraft:rarity_filter")]
+    RarityFilter { chance: u32 },
+    #[serde(rename = "minecraft:biome")]
+    Biome,
+    #[serde(rename = "minecraft:surface_water_depth_filter")]
+    SurfaceWaterDepthFilter { max_water_depth: i32 },
+    /// The predicates only check for things like saplings surviving, which the features check
+    /// themselves
+    #[serde(rename = "minecraft:block_predicate_filter")]
+    BlockPredicateFilter,
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HeightmapType {
+    WorldSurfaceWg,
+    WorldSurface,
+    OceanFloorWg,
+    OceanFloor,
+    MotionBlocking,
+    MotionBlockingNoLeaves,
+}
+
+/// What a placed feature is placed for, used by the biome filter.
+pub struct PlacementContext<'a> {
+    pub feature: &'a str,
+    pub step: usize,
+    /// How far positions in a chunk have to stay away from its edges
+    pub margin: i32,
+}
+
+impl PlacementModifier {
+    pub fn is_supported(&self) -> bool {
+        !matches!(self, Self::Unsupported)
+    }
+
+    /// Whether this modifier needs the terrain around the position, which is only known for the
+    /// chunk being generated.
+    pub fn needs_terrain(&self) -> bool {
+        matches!(
+            self,
+            Self::Heightmap { .. } | Self::SurfaceWaterDepthFilter { .. }
+        )
+    }
+
+    pub fn get_positions(
+        &self,
+        chunk: &ProtoChunk,
+        random: &mut RandomGenerator,
+        context: &PlacementContext,
+        pos: Vector3<i32>,
+    ) -> Vec<Vector3<i32>> {
+        match self {
+            Self::Count { count } => vec![pos; count.get(random).max(0) as usize],
+            Self::InSquare => {
+                let size = 16 - context.margin * 2;
+                let x = pos.x + context.margin + random.next_bounded_i32(size);
+                let z = pos.z + context.margin + random.next_bounded_i32(size);
+                vec![Vector3::new(x, pos.y, z)]
+            }
+            Self::HeightRange { height } => {
+                let y = height.get(random, chunk.bottom_y(), chunk.height());
+                vec![Vector3::new(pos.x, y, pos.z)]
+            }
+            Self::Heightmap { heightmap } => {
+                let column = Vector2::new(pos.x, pos.z);
+                if !chunk.contains_column(&column) {
+                    return Vec::new();
+                }
+                let y = chunk.heightmap_height(*heightmap, &column);
+                if y > chunk.bottom_y() as i32 {
+                    vec![Vector3::new(pos.x, y, pos.z)]
+                } else {
+                    Vec::new()
+                }
+            }
+            Self::RarityFilter { chance } => {
+                if random.next_f32() < 1.0 / *chance as f32 {
+                    vec![pos]
+                } else {
+                    Vec::new()
+                }
+            }
+            Self::Biome => {
+                if chunk.has_feature_at(&pos, context.feature, context.step) {
+                    vec![pos]
+                } else {
+                    Vec::new()
+                }
+            }
+            Self::SurfaceWaterDepthFilter { max_water_depth } => {
+                let column = Vector2::new(pos.x, pos.z);
+                if !chunk.contains_column(&column) {
+                    return Vec::new();
+                }
+                let depth = chunk.heightmap_height(HeightmapType::WorldSurfaceWg, &column)
+                    - chunk.heightmap_height(HeightmapType::OceanFloorWg, &column);
+                if depth <= *max_water_depth {
+                    vec![pos]
+                } else {
+                    Vec::new()
+                }
+            }
+            Self::BlockPredicateFilter => vec![pos],
+            Self::Unsupported => Vec::new(),
+        }
+    }
+}
+
+/// An integer which is either constant or sampled using the chunk's random, so that features are
+/// placed the same way for the same seed.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum IntProvider {
+    Constant(i32),
+    Object(IntDistribution),
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type")]
+pub enum IntDistribution {
+    #[serde(rename = "minecraft:uniform")]
+    Uniform {
+        min_inclusive: i32,
+        max_inclusive: i32,
+    },
+    #[serde(rename = "minecraft:weighted_list")]
+    WeightedList { distribution: Vec<WeightedInt> },
+}
+
+#[derive(Deserialize)]
+pub struct WeightedInt {
+    data: i32,
+    weight: i32,
+}
+
+impl IntProvider {
+    pub fn get(&self, random: &mut RandomGenerator) -> i32 {
+        match self {
+            Self::Constant(value) => *value,
+            Self::Object(IntDistribution::Uniform {
+                min_inclusive,
+                max_inclusive,
+            }) => random.next_inbetween_i32(*min_inclusive, *max_inclusive),
+            Self::Object(IntDistribution::WeightedList { distribution }) => {
+                let total: i32 = distribution.iter().map(|entry| entry.weight).sum();
+                if total <= 0 {
+                    return 0;
+                }
+                let mut chosen = random.next_bounded_i32(total);
+                for entry in distribution {
+                    chosen -= entry.weight;
+                    if chosen < 0 {
+                        return entry.data;
+                    }
+                }
+                0
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use pumpkin_data::tag::Tagable;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::random::{RandomGenerator, RandomImpl};
+use serde::Deserialize;
+
+use super::{StateProvider, placement::IntProvider};
+use crate::{block::RawBlockState, generation::proto_chunk::ProtoChunk};
+
+/// A tree with a straight trunk and a blob of leaves on top.
+///
+/// Every tree is grown this way for now, the different trunk and foliage placers only change its
+/// size.
+#[derive(Deserialize)]
+pub struct TreeConfig {
+    trunk_provider: StateProvider,
+    foliage_provider: StateProvider,
+    dirt_provider: StateProvider,
+    trunk_placer: TrunkPlacer,
+    foliage_placer: FoliagePlacer,
+}
+
+#[derive(Deserialize)]
+struct TrunkPlacer {
+    base_height: i32,
+    height_rand_a: i32,
+    height_rand_b: i32,
+}
+
+#[derive(Deserialize)]
+struct FoliagePlacer {
+    radius: IntProvider,
+    #[serde(default = "default_foliage_height")]
+    height: i32,
+}
+
+fn default_foliage_height() -> i32 {
+    3
+}
+
+impl TreeConfig {
+    /// The largest radius of leaves around the trunk, trees have to keep this far away from the
+    /// chunk edges.
+    pub const MAX_FOLIAGE_RADIUS: i32 = 3;
+
+    pub fn generate(
+        &self,
+        chunk: &mut ProtoChunk,
+        random: &mut RandomGenerator,
+        origin: Vector3<i32>,
+    ) -> bool {
+        let (Some(trunk), Some(foliage), Some(dirt)) = (
+            self.trunk_provider.get(),
+            self.foliage_provider.get(),
+            self.dirt_provider.get(),
+        ) else {
+            return false;
+        };
+
+        let height = self.trunk_placer.base_height
+            + random.next_bounded_i32(self.trunk_placer.height_rand_a + 1)
+            + random.next_bounded_i32(self.trunk_placer.height_rand_b + 1);
+        let radius = self
+            .foliage_placer
+            .radius
+            .get(random)
+            .clamp(0, Self::MAX_FOLIAGE_RADIUS);
+
+        let ground = Vector3::new(origin.x, origin.y - 1, origin.z);
+        let top = Vector3::new(origin.x, origin.y + height, origin.z);
+        if !chunk.contains(&ground) || !chunk.contains(&top) {
+            return false;
+        }
+        let ground_block = chunk.get_block_state(&ground).to_block();
+        if !ground_block
+            .is_tagged_with("minecraft:dirt")
+            .unwrap_or(false)
+        {
+            return false;
+        }
+        if !(0..=height).all(|y| {
+            can_replace(chunk.get_block_state(&Vector3::new(origin.x, origin.y + y, origin.z)))
+        }) {
+            return false;
+        }
+
+        chunk.set_block_state(&ground, dirt.to_state());
+        for y in 0..height {
+            chunk.set_block_state(
+                &Vector3::new(origin.x, origin.y + y, origin.z),
+                trunk.to_state(),
+            );
+        }
+
+        // The layers get wider from the top down
+        for layer in -self.foliage_placer.height..=0 {
+            let layer_radius = (radius - 1 - layer / 2).clamp(0, Self::MAX_FOLIAGE_RADIUS);
+            for dx in -layer_radius..=layer_radius {
+                for dz in -layer_radius..=layer_radius {
+                    let corner = dx.abs() == layer_radius && dz.abs() == layer_radius;
+                    if corner && layer_radius > 0 && (random.next_bounded_i32(2) == 0 || layer == 0)
+                    {
+                        continue;
+                    }
+                    let pos = Vector3::new(top.x + dx, top.y + layer, top.z + dz);
+                    if chunk.contains(&pos) && can_replace(chunk.get_block_state(&pos)) {
+                        chunk.set_block_state(&pos, foliage.to_state());
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
+fn can_replace(state: RawBlockState) -> bool {
+    state.to_state().is_air()
+        || state
+            .to_block()
+            .is_tagged_with("minecraft:replaceable_by_trees")
+            .unwrap_or(false)
+}
@@ -4,7 +4,8 @@ use crate::chunk::ChunkData;
 use crate::generation::Seed;
 
 pub trait GeneratorInit {
-    fn new(seed: Seed) -> Self;
+    /// `min_y` and `height` are the vertical bounds of the dimension the chunks are generated for.
+    fn new(seed: Seed, min_y: i32, height: u32) -> Self;
 }
 
 pub trait WorldGenerator: Sync + Send {
@@ -6,13 +6,17 @@ use super::y_offset::YOffset;
 #[derive(Deserialize)]
 #[serde(tag = "type")]
 pub enum HeightProvider {
+    #[serde(rename = "minecraft:uniform")]
     Uniform(UniformHeightProvider),
+    #[serde(rename = "minecraft:trapezoid")]
+    Trapezoid(TrapezoidHeightProvider),
 }
 
 impl HeightProvider {
     pub fn get(&self, random: &mut RandomGenerator, min_y: i8, height: u16) -> i32 {
         match self {
             HeightProvider::Uniform(uniform) => uniform.get(random, min_y, height),
+            HeightProvider::Trapezoid(trapezoid) => trapezoid.get(random, min_y, height),
         }
     }
 }
@@ -31,3 +35,29 @@ impl UniformHeightProvider {
         random.next_bounded_i32(max - min + 1) + min
     }
 }
+
+/// Picks heights in the range, most likely in the middle of it and less likely towards the ends.
+#[derive(Deserialize)]
+pub struct TrapezoidHeightProvider {
+    min_inclusive: YOffset,
+    max_inclusive: YOffset,
+    #[serde(default)]
+    plateau: i32,
+}
+
+impl TrapezoidHeightProvider {
+    pub fn get(&self, random: &mut RandomGenerator, min_y: i8, height: u16) -> i32 {
+        let min = self.min_inclusive.get_y(min_y, height) as i32;
+        let max = self.max_inclusive.get_y(min_y, height) as i32;
+        if min > max {
+            return min;
+        }
+
+        let range = max - min;
+        if self.plateau >= range {
+            return random.next_inbetween_i32(min, max);
+        }
+        let slope = (range - self.plateau) / 2;
+        min + random.next_inbetween_i32(0, range - slope) + random.next_inbetween_i32(0, slope)
+    }
+}
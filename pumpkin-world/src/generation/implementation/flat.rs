@@ -0,0 +1,225 @@
+use pumpkin_data::{Block, chunk::Biome};
+use pumpkin_util::math::vector2::Vector2;
+use thiserror::Error;
+
+use crate::{
+    BlockStateId,
+    chunk::{
+        ChunkData, ChunkHeightmaps, ChunkLightEngine, ChunkSections,
+        format::LightContainer,
+        palette::{BiomePalette, BlockPalette},
+    },
+    generation::{WorldGenerator, biome_coords},
+};
+
+/// The preset of the "Classic Flat" world in vanilla.
+pub const CLASSIC_FLAT_PRESET: &str =
+    "minecraft:bedrock,2*minecraft:dirt,minecraft:grass_block;minecraft:plains";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FlatPresetError {
+    #[error("Unknown block {0}")]
+    UnknownBlock(String),
+    #[error("Unknown biome {0}")]
+    UnknownBiome(String),
+    #[error("Invalid layer height in {0}")]
+    InvalidHeight(String),
+}
+
+/// The layers of a superflat world, from the bottom of the dimension up, and its biome.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FlatSettings {
+    pub layers: Vec<(BlockStateId, u32)>,
+    pub biome: &'static Biome,
+}
+
+impl Default for FlatSettings {
+    fn default() -> Self {
+        Self::from_preset(CLASSIC_FLAT_PRESET).expect("The classic flat preset is valid")
+    }
+}
+
+fn strip_namespace(name: &str) -> &str {
+    name.strip_prefix("minecraft:").unwrap_or(name)
+}
+
+impl FlatSettings {
+    /// A world without any blocks.
+    #[must_use]
+    pub fn void() -> Self {
+        Self {
+            layers: Vec::new(),
+            biome: &Biome::THE_VOID,
+        }
+    }
+
+    /// Parses a vanilla superflat preset like `minecraft:bedrock,2*minecraft:dirt;minecraft:plains`:
+    /// the layers from the bottom up, each optionally repeated, then the biome. Anything after the
+    /// biome, like structures, is ignored.
+    pub fn from_preset(preset: &str) -> Result<Self, FlatPresetError> {
+        let mut parts = preset.trim().split(';');
+        let layers = parts
+            .next()
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|layer| !layer.is_empty())
+            .map(|layer| {
+                let (height, block) = match layer.split_once('*') {
+                    Some((height, block)) => (
+                        height
+                            .trim()
+                            .parse()
+                            .map_err(|_| FlatPresetError::InvalidHeight(layer.to_string()))?,
+                        block.trim(),
+                    ),
+                    None => (1, layer),
+                };
+                let block = Block::from_registry_key(strip_namespace(block))
+                    .ok_or_else(|| FlatPresetError::UnknownBlock(block.to_string()))?;
+                Ok::<_, FlatPresetError>((block.default_state_id, height))
+            })
+            .collect::<Result<_, _>>()?;
+        let biome = match parts.next().map(str::trim) {
+            Some(biome) if !biome.is_empty() => Biome::from_name(strip_namespace(biome))
+                .ok_or_else(|| FlatPresetError::UnknownBiome(biome.to_string()))?,
+            _ => &Biome::PLAINS,
+        };
+        Ok(Self { layers, biome })
+    }
+
+    /// The block at `y` blocks above the bottom of the dimension.
+    #[must_use]
+    pub fn block_at(&self, y: u32) -> BlockStateId {
+        let mut top = 0;
+        for (block, height) in &self.layers {
+            top += height;
+            if y < top {
+                return *block;
+            }
+        }
+        Block::AIR.default_state_id
+    }
+
+    fn total_height(&self) -> u32 {
+        self.layers.iter().map(|(_, height)| height).sum()
+    }
+}
+
+/// Generates layers of blocks, like vanilla's superflat worlds, and void worlds without any.
+pub struct FlatGenerator {
+    settings: FlatSettings,
+    min_y: i32,
+    height: u32,
+}
+
+impl FlatGenerator {
+    #[must_use]
+    pub fn new(settings: FlatSettings, min_y: i32, height: u32) -> Self {
+        Self {
+            settings,
+            min_y,
+            height,
+        }
+    }
+}
+
+impl WorldGenerator for FlatGenerator {
+    fn generate_chunk(&self, at: &Vector2<i32>) -> ChunkData {
+        let mut sections = ChunkSections::empty(self.min_y, self.height)
+            .expect("Dimension has invalid world bounds");
+
+        for y in 0..biome_coords::from_block(self.height) {
+            for z in 0..BiomePalette::SIZE {
+                for x in 0..BiomePalette::SIZE {
+                    sections.set_relative_biome(x, y as usize, z, self.settings.biome.id);
+                }
+            }
+        }
+
+        for y in 0..self.settings.total_height().min(self.height) {
+            let block = self.settings.block_at(y);
+            for z in 0..BlockPalette::SIZE {
+                for x in 0..BlockPalette::SIZE {
+                    sections.set_relative_block(x, y as usize, z, block);
+                }
+            }
+        }
+
+        let mut chunk = ChunkData {
+            light_engine: ChunkLightEngine {
+                sky_light: (0..sections.sections.len() + 2)
+                    .map(|_| LightContainer::new_filled(15))
+                    .collect(),
+                block_light: (0..sections.sections.len() + 2)
+                    .map(|_| LightContainer::new_empty(15))
+                    .collect(),
+            },
+            section: sections,
+            heightmap: ChunkHeightmaps::empty(self.height),
+            position: *at,
+            dirty: true,
+            block_ticks: Default::default(),
+            fluid_ticks: Default::default(),
+            block_entities: Default::default(),
+        };
+        chunk.calculate_heightmaps();
+        chunk
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, chunk::Biome};
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::{FlatGenerator, FlatPresetError, FlatSettings};
+    use crate::generation::WorldGenerator;
+
+    #[test]
+    fn superflat_chunk_follows_the_layers() {
+        let settings = FlatSettings::from_preset(
+            "minecraft:bedrock,3*minecraft:stone,2*dirt,minecraft:grass_block;minecraft:desert",
+        )
+        .unwrap();
+        assert_eq!(settings.biome, &Biome::DESERT);
+
+        let generator = FlatGenerator::new(settings, -64, 384);
+        let chunk = generator.generate_chunk(&Vector2::new(3, -2));
+        let block_at = |y| chunk.section.get_block_absolute_y(5, y, 9);
+        assert_eq!(block_at(-64), Some(Block::BEDROCK.default_state_id));
+        for y in -63..=-61 {
+            assert_eq!(block_at(y), Some(Block::STONE.default_state_id));
+        }
+        assert_eq!(block_at(-60), Some(Block::DIRT.default_state_id));
+        assert_eq!(block_at(-59), Some(Block::DIRT.default_state_id));
+        assert_eq!(block_at(-58), Some(Block::GRASS_BLOCK.default_state_id));
+        assert_eq!(block_at(-57), Some(Block::AIR.default_state_id));
+        assert_eq!(block_at(319), Some(Block::AIR.default_state_id));
+        assert_eq!(chunk.world_surface_height(5, 9), 7);
+        assert_eq!(chunk.motion_blocking_height(5, 9), 7);
+    }
+
+    #[test]
+    fn bad_presets_are_rejected() {
+        assert_eq!(
+            FlatSettings::from_preset("minecraft:bedrock,minecraft:cheese"),
+            Err(FlatPresetError::UnknownBlock(
+                "minecraft:cheese".to_string()
+            ))
+        );
+        assert_eq!(
+            FlatSettings::from_preset("x*minecraft:dirt"),
+            Err(FlatPresetError::InvalidHeight(
+                "x*minecraft:dirt".to_string()
+            ))
+        );
+        assert_eq!(
+            FlatSettings::from_preset("minecraft:dirt;minecraft:moon"),
+            Err(FlatPresetError::UnknownBiome("minecraft:moon".to_string()))
+        );
+
+        let void = FlatSettings::void();
+        assert_eq!(void.block_at(0), Block::AIR.default_state_id);
+    }
+}
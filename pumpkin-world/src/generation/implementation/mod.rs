@@ -1,3 +1,5 @@
+pub mod flat;
+
 use pumpkin_data::noise_router::OVERWORLD_BASE_NOISE_ROUTER;
 use pumpkin_util::math::{vector2::Vector2, vector3::Vector3};
 
@@ -10,7 +12,7 @@ use crate::chunk::ChunkLightEngine;
 use crate::chunk::format::LightContainer;
 use crate::{
     chunk::{
-        ChunkData, ChunkSections, SubChunk,
+        ChunkData, ChunkHeightmaps, ChunkSections,
         palette::{BiomePalette, BlockPalette},
     },
     generation::{
@@ -21,10 +23,12 @@ use crate::{
 pub struct VanillaGenerator {
     random_config: GlobalRandomConfig,
     base_router: ProtoNoiseRouters,
+    min_y: i32,
+    height: u32,
 }
 
 impl GeneratorInit for VanillaGenerator {
-    fn new(seed: Seed) -> Self {
+    fn new(seed: Seed, min_y: i32, height: u32) -> Self {
         let random_config = GlobalRandomConfig::new(seed.0, false);
         // TODO: The generation settings contains (part of?) the noise routers too; do we keep the separate or
         // use only the generation settings?
@@ -32,6 +36,8 @@ impl GeneratorInit for VanillaGenerator {
         Self {
             random_config,
             base_router,
+            min_y,
+            height,
         }
     }
 }
@@ -43,9 +49,8 @@ impl WorldGenerator for VanillaGenerator {
             .get(&GeneratorSetting::Overworld)
             .unwrap();
 
-        let sub_chunks = generation_settings.shape.height as usize / BlockPalette::SIZE;
-        let sections = (0..sub_chunks).map(|_| SubChunk::default()).collect();
-        let mut sections = ChunkSections::new(sections, generation_settings.shape.min_y as i32);
+        let mut sections = ChunkSections::empty(self.min_y, self.height)
+            .expect("Dimension has invalid world bounds");
 
         let mut proto_chunk = ProtoChunk::new(
             *at,
@@ -56,12 +61,19 @@ impl WorldGenerator for VanillaGenerator {
         proto_chunk.populate_biomes();
         proto_chunk.populate_noise();
         proto_chunk.build_surface();
+        proto_chunk.place_features();
+
+        // The terrain only covers the generation shape, the rest of the dimension stays empty
+        let shape_min_y = i32::from(generation_settings.shape.min_y);
+        let shape_max_y = shape_min_y + i32::from(generation_settings.shape.height) - 1;
+        let biome_min_y = biome_coords::from_block(shape_min_y);
+        let biome_max_y = biome_coords::from_block(shape_max_y);
 
-        for y in 0..biome_coords::from_block(generation_settings.shape.height) {
+        for y in 0..biome_coords::from_block(self.height) {
             for z in 0..BiomePalette::SIZE {
                 for x in 0..BiomePalette::SIZE {
-                    let absolute_y =
-                        biome_coords::from_block(generation_settings.shape.min_y as i32) + y as i32;
+                    let absolute_y = (biome_coords::from_block(self.min_y) + y as i32)
+                        .clamp(biome_min_y, biome_max_y);
                     let biome =
                         proto_chunk.get_biome(&Vector3::new(x as i32, absolute_y, z as i32));
                     sections.set_relative_biome(x, y as usize, z, biome.id);
@@ -69,13 +81,15 @@ impl WorldGenerator for VanillaGenerator {
             }
         }
 
-        for y in 0..generation_settings.shape.height {
+        let min_y = self.min_y.max(shape_min_y);
+        let max_y = sections.max_y().min(shape_max_y);
+        for absolute_y in min_y..=max_y {
+            let y = (absolute_y - self.min_y) as usize;
             for z in 0..BlockPalette::SIZE {
                 for x in 0..BlockPalette::SIZE {
-                    let absolute_y = generation_settings.shape.min_y as i32 + y as i32;
                     let block =
                         proto_chunk.get_block_state(&Vector3::new(x as i32, absolute_y, z as i32));
-                    sections.set_relative_block(x, y as usize, z, block.state_id);
+                    sections.set_relative_block(x, y, z, block.state_id);
                 }
             }
         }
@@ -90,7 +104,7 @@ impl WorldGenerator for VanillaGenerator {
                     .collect(),
             },
             section: sections,
-            heightmap: Default::default(),
+            heightmap: ChunkHeightmaps::empty(self.height),
             position: *at,
             dirty: true,
             block_ticks: Default::default(),
@@ -99,3 +113,29 @@ impl WorldGenerator for VanillaGenerator {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::VanillaGenerator;
+    use crate::generation::{Seed, WorldGenerator, generator::GeneratorInit};
+
+    #[test]
+    fn chunks_fill_the_dimension_bounds() {
+        let generator = VanillaGenerator::new(Seed(0), -128, 512);
+        let chunk = generator.generate_chunk(&Vector2::new(0, 0));
+        assert_eq!(chunk.section.min_y(), -128);
+        assert_eq!(chunk.section.height(), 512);
+
+        // Below and above the generated terrain the dimension is empty
+        let air = Block::AIR.default_state_id;
+        assert_eq!(chunk.section.get_block_absolute_y(0, -128, 0), Some(air));
+        assert_eq!(chunk.section.get_block_absolute_y(0, 383, 0), Some(air));
+        assert_eq!(
+            chunk.section.get_block_absolute_y(0, -64, 0),
+            Some(Block::BEDROCK.default_state_id)
+        );
+    }
+}
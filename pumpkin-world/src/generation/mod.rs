@@ -5,6 +5,7 @@ mod biome;
 mod blender;
 pub mod carver;
 pub mod chunk_noise;
+pub mod feature;
 mod generator;
 pub mod height_limit;
 pub mod height_provider;
@@ -22,16 +23,57 @@ pub mod y_offset;
 use derive_getters::Getters;
 pub use generator::WorldGenerator;
 use implementation::VanillaGenerator;
+pub use implementation::flat::{FlatGenerator, FlatSettings};
+use pumpkin_config::LevelType;
 use pumpkin_util::random::{
     RandomDeriver, RandomDeriverImpl, RandomImpl, legacy_rand::LegacyRand, xoroshiro128::Xoroshiro,
 };
 pub use seed::Seed;
+use settings::{GENERATION_SETTINGS, GeneratorSetting};
 
 use generator::GeneratorInit;
 
 pub fn get_world_gen(seed: Seed) -> Box<dyn WorldGenerator> {
-    // TODO decide which WorldGenerator to pick based on config.
-    Box::new(VanillaGenerator::new(seed))
+    let shape = &GENERATION_SETTINGS
+        .get(&GeneratorSetting::Overworld)
+        .unwrap()
+        .shape;
+    get_dimension_world_gen(seed, shape.min_y.into(), shape.height.into())
+}
+
+/// The world generator for a dimension with the given vertical bounds. Parts of the dimension
+/// outside of the generated terrain are left empty.
+pub fn get_dimension_world_gen(seed: Seed, min_y: i32, height: u32) -> Box<dyn WorldGenerator> {
+    Box::new(VanillaGenerator::new(seed, min_y, height))
+}
+
+/// The overworld generator for the configured level type. `generator_settings` is the superflat
+/// preset of flat worlds, an invalid one falls back to the classic flat world like in vanilla.
+pub fn get_configured_world_gen(
+    level_type: LevelType,
+    generator_settings: &str,
+    seed: Seed,
+) -> Box<dyn WorldGenerator> {
+    let shape = &GENERATION_SETTINGS
+        .get(&GeneratorSetting::Overworld)
+        .unwrap()
+        .shape;
+    let (min_y, height) = (shape.min_y.into(), shape.height.into());
+    match level_type {
+        LevelType::Normal => get_dimension_world_gen(seed, min_y, height),
+        LevelType::Flat => {
+            let settings = if generator_settings.trim().is_empty() {
+                FlatSettings::default()
+            } else {
+                FlatSettings::from_preset(generator_settings).unwrap_or_else(|err| {
+                    log::error!("Invalid superflat preset {generator_settings:?}: {err}");
+                    FlatSettings::default()
+                })
+            };
+            Box::new(FlatGenerator::new(settings, min_y, height))
+        }
+        LevelType::Void => Box::new(FlatGenerator::new(FlatSettings::void(), min_y, height)),
+    }
 }
 
 #[derive(Getters)]
@@ -19,6 +19,7 @@ use super::{
     aquifer_sampler::{FluidLevel, FluidLevelSampler, FluidLevelSamplerImpl},
     biome_coords,
     chunk_noise::{CHUNK_DIM, ChunkNoiseGenerator, LAVA_BLOCK, WATER_BLOCK},
+    feature::{self, placement::HeightmapType},
     height_limit::HeightLimitView,
     noise_router::{
         multi_noise_sampler::{MultiNoiseSampler, MultiNoiseSamplerBuilderOptions},
@@ -202,6 +203,67 @@ impl<'a> ProtoChunk<'a> {
         self.settings
     }
 
+    pub fn chunk_pos(&self) -> Vector2<i32> {
+        self.chunk_pos
+    }
+
+    /// Whether the column at the global block position is part of this chunk.
+    pub fn contains_column(&self, pos: &Vector2<i32>) -> bool {
+        section_coords::block_to_section(pos.x) == self.chunk_pos.x
+            && section_coords::block_to_section(pos.z) == self.chunk_pos.z
+    }
+
+    /// Whether the global block position is part of this chunk.
+    pub fn contains(&self, pos: &Vector3<i32>) -> bool {
+        self.contains_column(&Vector2::new(pos.x, pos.z)) && !self.out_of_height(pos.y as i16)
+    }
+
+    /// The height of the first block above the heightmap's top block in the column.
+    pub fn heightmap_height(&self, heightmap: HeightmapType, pos: &Vector2<i32>) -> i32 {
+        match heightmap {
+            HeightmapType::WorldSurfaceWg | HeightmapType::WorldSurface => {
+                self.top_block_height_exclusive(pos)
+            }
+            HeightmapType::OceanFloorWg | HeightmapType::OceanFloor => {
+                self.ocean_floor_height_exclusive(pos)
+            }
+            HeightmapType::MotionBlocking => self.top_motion_blocking_block_height_exclusive(pos),
+            HeightmapType::MotionBlockingNoLeaves => {
+                self.top_motion_blocking_block_no_leaves_height_exclusive(pos)
+            }
+        }
+    }
+
+    /// The features of the given step of all biomes in this chunk, each one only once.
+    pub fn step_features(&self, step: usize) -> Vec<&'static str> {
+        let mut features = Vec::new();
+        for biome in self.flat_biome_map.iter() {
+            for feature in biome.features.get(step).copied().unwrap_or_default() {
+                if !features.contains(feature) {
+                    features.push(*feature);
+                }
+            }
+        }
+        features
+    }
+
+    /// Whether the biome at the global block position has the feature. Positions outside of this
+    /// chunk are left to the chunk they are in.
+    pub fn has_feature_at(&self, pos: &Vector3<i32>, feature: &str, step: usize) -> bool {
+        if !self.contains(pos) {
+            return true;
+        }
+        let biome = self.get_biome(&Vector3::new(
+            biome_coords::from_block(pos.x),
+            biome_coords::from_block(pos.y),
+            biome_coords::from_block(pos.z),
+        ));
+        biome
+            .features
+            .get(step)
+            .is_some_and(|features| features.contains(&feature))
+    }
+
     fn maybe_update_surface_height_map(&mut self, pos: &Vector3<i32>) {
         let local_x = (pos.x & 15) as usize;
         let local_z = (pos.z & 15) as usize;
@@ -637,6 +699,14 @@ impl<'a> ProtoChunk<'a> {
         }
     }
 
+    /// Places ores, trees, lakes and the other decoration features, if they are enabled.
+    pub fn place_features(&mut self) {
+        if !self.settings.features_enabled {
+            return;
+        }
+        feature::place_features(self, self.random_config.seed);
+    }
+
     fn start_cell_x(&self) -> i32 {
         self.start_block_x() / self.noise_sampler.horizontal_cell_block_count() as i32
     }
@@ -31,6 +31,13 @@ pub struct GenerationSettings {
     pub shape: GenerationShapeConfig,
     pub surface_rule: MaterialRule,
     pub default_block: BlockStateCodec,
+    /// Whether ores, trees, lakes and other features are placed after the terrain is shaped
+    #[serde(default = "default_features_enabled")]
+    pub features_enabled: bool,
+}
+
+fn default_features_enabled() -> bool {
+    true
 }
 #[derive(Deserialize)]
 pub struct GenerationShapeConfig {
@@ -15,14 +15,14 @@ impl YOffset {
             YOffset::BelowTop(below_top) => {
                 height as i16 - 1 + min_y as i16 - below_top.below_top as i16
             }
-            YOffset::Absolute(absolute) => absolute.absolute as i16,
+            YOffset::Absolute(absolute) => absolute.absolute,
         }
     }
 }
 
 #[derive(Deserialize)]
 pub struct Absolute {
-    absolute: u16,
+    absolute: i16,
 }
 
 #[derive(Deserialize)]
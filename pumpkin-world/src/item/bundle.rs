@@ -0,0 +1,132 @@
+use pumpkin_data::tag::Tagable;
+
+use crate::item::ItemStack;
+
+const BUNDLES_TAG: &str = "#minecraft:bundles";
+const SHULKER_BOXES_TAG: &str = "#minecraft:shulker_boxes";
+
+impl ItemStack {
+    /// How much a bundle can hold. A full stack of any item weighs exactly this much, so a
+    /// bundle holds 64 stone, 16 ender pearls or a single sword.
+    pub const BUNDLE_CAPACITY: u32 = 64;
+
+    pub fn is_bundle(&self) -> bool {
+        self.item.is_tagged_with(BUNDLES_TAG).expect(
+            "This is a default minecraft tag that should have been gotten from the extractor",
+        )
+    }
+
+    /// Whether the stack can be put into a bundle. Bundles and shulker boxes can't.
+    pub fn fits_in_bundle(&self) -> bool {
+        !self.is_empty()
+            && !self.is_bundle()
+            && !self.item.is_tagged_with(SHULKER_BOXES_TAG).expect(
+                "This is a default minecraft tag that should have been gotten from the extractor",
+            )
+    }
+
+    /// How much a single item of the stack weighs inside a bundle.
+    pub fn bundle_weight_per_item(&self) -> u32 {
+        Self::BUNDLE_CAPACITY / u32::from(self.get_max_stack_size().max(1))
+    }
+
+    /// How much the contents of this bundle weigh.
+    pub fn bundle_weight(&self) -> u32 {
+        self.patch
+            .bundle_contents
+            .iter()
+            .map(|stack| stack.bundle_weight_per_item() * u32::from(stack.item_count))
+            .sum()
+    }
+
+    /// Moves as many items of `stack` into this bundle as fit, returning how many were moved.
+    pub fn bundle_insert(&mut self, stack: &mut ItemStack) -> u8 {
+        if !self.is_bundle() || !stack.fits_in_bundle() {
+            return 0;
+        }
+        let space = Self::BUNDLE_CAPACITY.saturating_sub(self.bundle_weight());
+        let fitting = space / stack.bundle_weight_per_item();
+        let amount = u8::try_from(fitting)
+            .unwrap_or(u8::MAX)
+            .min(stack.item_count);
+        if amount == 0 {
+            return 0;
+        }
+
+        let mut inserted = stack.split(amount);
+        let contents = &mut self.patch.bundle_contents;
+        // Like vanilla, merging into an equal stack moves it to the top of the bundle
+        if let Some(index) = contents
+            .iter()
+            .position(|existing| existing.are_items_and_components_equal(&inserted))
+        {
+            inserted.increment(contents.remove(index).item_count);
+        }
+        contents.insert(0, inserted);
+        amount
+    }
+
+    /// Takes the most recently inserted stack out of this bundle.
+    pub fn bundle_remove_last(&mut self) -> Option<ItemStack> {
+        if self.patch.bundle_contents.is_empty() {
+            return None;
+        }
+        Some(self.patch.bundle_contents.remove(0))
+    }
+
+    /// Empties this bundle, returning its contents.
+    pub fn bundle_take_all(&mut self) -> Vec<ItemStack> {
+        std::mem::take(&mut self.patch.bundle_contents)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+
+    use crate::item::ItemStack;
+
+    #[test]
+    fn bundle_fills_up_to_capacity_and_empties_last_in_first_out() {
+        let mut bundle = ItemStack::new(1, Item::BUNDLE);
+
+        let mut stone = ItemStack::new(32, Item::STONE);
+        assert_eq!(bundle.bundle_insert(&mut stone), 32);
+        assert!(stone.is_empty());
+
+        // Ender pearls stack to 16, so each of them weighs 4
+        let mut pearls = ItemStack::new(4, Item::ENDER_PEARL);
+        assert_eq!(bundle.bundle_insert(&mut pearls), 4);
+        assert_eq!(bundle.bundle_weight(), 48);
+
+        // A sword alone would fill the bundle
+        let mut sword = ItemStack::new(1, Item::IRON_SWORD);
+        assert_eq!(bundle.bundle_insert(&mut sword), 0);
+        assert_eq!(sword.item_count, 1);
+
+        let mut dirt = ItemStack::new(20, Item::DIRT);
+        assert_eq!(bundle.bundle_insert(&mut dirt), 16);
+        assert_eq!(dirt.item_count, 4);
+        assert_eq!(bundle.bundle_weight(), ItemStack::BUNDLE_CAPACITY);
+
+        let mut empty = ItemStack::new(1, Item::BUNDLE);
+        let mut shulker_box = ItemStack::new(1, Item::SHULKER_BOX);
+        assert_eq!(empty.bundle_insert(&mut shulker_box), 0);
+        let mut nested = ItemStack::new(1, Item::RED_BUNDLE);
+        assert_eq!(empty.bundle_insert(&mut nested), 0);
+
+        let taken: Vec<_> = std::iter::from_fn(|| bundle.bundle_remove_last())
+            .map(|stack| (stack.item.id, stack.item_count))
+            .collect();
+        assert_eq!(
+            taken,
+            [
+                (Item::DIRT.id, 16),
+                (Item::ENDER_PEARL.id, 4),
+                (Item::STONE.id, 32),
+            ]
+        );
+        assert_eq!(bundle.bundle_weight(), 0);
+        assert!(bundle.patch.is_empty());
+    }
+}
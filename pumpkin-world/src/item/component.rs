@@ -0,0 +1,359 @@
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::text::TextComponent;
+
+use crate::block::entities::sign::DyeColor;
+
+use super::ItemStack;
+
+/// The maximum amount of pattern layers a banner or shield can hold.
+pub const MAX_BANNER_PATTERNS: usize = 6;
+
+/// The maximum amount of slots the contents of a container item can have.
+pub const MAX_CONTAINER_SLOTS: usize = 256;
+
+/// The maximum amount of lines the lore of an item can have.
+pub const MAX_LORE_LINES: usize = 256;
+
+/// Components of an `ItemStack` that differ from the item's default components.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ItemComponentPatch {
+    /// The RGB color of dyeable items like leather armor
+    pub dyed_color: Option<i32>,
+    /// The pattern layers of banners and shields, from bottom to top
+    pub banner_patterns: Vec<BannerPatternLayer>,
+    /// How much durability the item lost
+    pub damage: Option<i32>,
+    /// Enchantments without namespace (e.g. `sharpness`) and their level
+    pub enchantments: Vec<(String, i32)>,
+    /// The trim of armor pieces
+    pub trim: Option<ArmorTrim>,
+    /// The items inside container items like shulker boxes, by slot. Empty slots are
+    /// [`ItemStack::EMPTY`] and there are no trailing empty slots.
+    pub container: Vec<ItemStack>,
+    /// The name given to the item, e.g. by an anvil
+    pub custom_name: Option<TextComponent>,
+    /// Extra lines shown in the item's tooltip
+    pub lore: Vec<TextComponent>,
+    /// The items inside a bundle, the most recently inserted one first
+    pub bundle_contents: Vec<ItemStack>,
+    /// The lodestone a compass points to
+    pub lodestone_tracker: Option<LodestoneTracker>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArmorTrim {
+    /// The `trim_material` registry entry without namespace (e.g. `gold`)
+    pub material: String,
+    /// The `trim_pattern` registry entry without namespace (e.g. `coast`)
+    pub pattern: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LodestoneTracker {
+    /// The dimension (e.g. `minecraft:overworld`) and position of the lodestone, compasses
+    /// without a target spin
+    pub target: Option<(String, BlockPos)>,
+    /// Whether the target is forgotten once the lodestone is gone. Compasses bound by using them
+    /// on a lodestone are tracked, commands can give out ones which aren't.
+    pub tracked: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BannerPatternLayer {
+    /// The banner pattern without namespace (e.g. `stripe_bottom`)
+    pub pattern: String,
+    pub color: DyeColor,
+}
+
+impl ItemComponentPatch {
+    pub const EMPTY: Self = Self {
+        dyed_color: None,
+        banner_patterns: Vec::new(),
+        damage: None,
+        enchantments: Vec::new(),
+        trim: None,
+        container: Vec::new(),
+        custom_name: None,
+        lore: Vec::new(),
+        bundle_contents: Vec::new(),
+        lodestone_tracker: None,
+    };
+
+    pub fn is_empty(&self) -> bool {
+        self.dyed_color.is_none()
+            && self.banner_patterns.is_empty()
+            && self.damage.is_none()
+            && self.enchantments.is_empty()
+            && self.trim.is_none()
+            && self.container.is_empty()
+            && self.custom_name.is_none()
+            && self.lore.is_empty()
+            && self.bundle_contents.is_empty()
+            && self.lodestone_tracker.is_none()
+    }
+
+    pub fn write_nbt(&self, compound: &mut NbtCompound) {
+        if let Some(color) = self.dyed_color {
+            compound.put_int("minecraft:dyed_color", color);
+        }
+        if !self.banner_patterns.is_empty() {
+            let layers = self
+                .banner_patterns
+                .iter()
+                .map(|layer| {
+                    let mut layer_compound = NbtCompound::new();
+                    layer_compound.put_string("pattern", format!("minecraft:{}", layer.pattern));
+                    layer_compound.put_string("color", layer.color.into());
+                    NbtTag::Compound(layer_compound)
+                })
+                .collect();
+            compound.put_list("minecraft:banner_patterns", layers);
+        }
+        if let Some(damage) = self.damage {
+            compound.put_int("minecraft:damage", damage);
+        }
+        if !self.enchantments.is_empty() {
+            let mut levels = NbtCompound::new();
+            for (enchantment, level) in &self.enchantments {
+                levels.put_int(&format!("minecraft:{enchantment}"), *level);
+            }
+            compound.put_component("minecraft:enchantments", levels);
+        }
+        if let Some(trim) = &self.trim {
+            let mut trim_compound = NbtCompound::new();
+            trim_compound.put_string("material", format!("minecraft:{}", trim.material));
+            trim_compound.put_string("pattern", format!("minecraft:{}", trim.pattern));
+            compound.put_component("minecraft:trim", trim_compound);
+        }
+        if !self.container.is_empty() {
+            let slots = self
+                .container
+                .iter()
+                .enumerate()
+                .filter(|(_, stack)| !stack.is_empty())
+                .map(|(slot, stack)| {
+                    let mut item = NbtCompound::new();
+                    stack.write_item_stack(&mut item);
+                    let mut slot_compound = NbtCompound::new();
+                    slot_compound.put_int("slot", slot as i32);
+                    slot_compound.put_component("item", item);
+                    NbtTag::Compound(slot_compound)
+                })
+                .collect();
+            compound.put_list("minecraft:container", slots);
+        }
+        if let Some(name) = &self.custom_name {
+            compound.put("minecraft:custom_name", name.to_nbt());
+        }
+        if !self.lore.is_empty() {
+            let lines = self.lore.iter().map(TextComponent::to_nbt).collect();
+            compound.put_list("minecraft:lore", lines);
+        }
+        if !self.bundle_contents.is_empty() {
+            let items = self
+                .bundle_contents
+                .iter()
+                .map(|stack| {
+                    let mut item = NbtCompound::new();
+                    stack.write_item_stack(&mut item);
+                    NbtTag::Compound(item)
+                })
+                .collect();
+            compound.put_list("minecraft:bundle_contents", items);
+        }
+        if let Some(tracker) = &self.lodestone_tracker {
+            let mut tracker_compound = NbtCompound::new();
+            if let Some((dimension, pos)) = &tracker.target {
+                let mut target = NbtCompound::new();
+                target.put("pos", NbtTag::IntArray([pos.0.x, pos.0.y, pos.0.z].into()));
+                target.put_string("dimension", dimension.clone());
+                tracker_compound.put_component("target", target);
+            }
+            tracker_compound.put_bool("tracked", tracker.tracked);
+            compound.put_component("minecraft:lodestone_tracker", tracker_compound);
+        }
+    }
+
+    pub fn read_nbt(compound: &NbtCompound) -> Self {
+        let dyed_color = compound.get_int("minecraft:dyed_color").or_else(|| {
+            compound
+                .get_compound("minecraft:dyed_color")
+                .and_then(|color| color.get_int("rgb"))
+        });
+        let banner_patterns = compound
+            .get_list("minecraft:banner_patterns")
+            .map(|layers| {
+                layers
+                    .iter()
+                    .filter_map(|layer| {
+                        let layer = layer.extract_compound()?;
+                        let pattern = layer.get_string("pattern")?;
+                        let color = layer.get_string("color")?;
+                        Some(BannerPatternLayer {
+                            pattern: pattern
+                                .strip_prefix("minecraft:")
+                                .unwrap_or(pattern)
+                                .to_string(),
+                            color: DyeColor::from(color.clone()),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let damage = compound.get_int("minecraft:damage");
+        let enchantments = compound
+            .get_compound("minecraft:enchantments")
+            .map(|enchantments| {
+                // Before 1.21.5 the levels were nested
+                let levels = enchantments.get_compound("levels").unwrap_or(enchantments);
+                levels
+                    .child_tags
+                    .iter()
+                    .filter_map(|(enchantment, level)| {
+                        Some((strip_namespace(enchantment), level.extract_int()?))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let trim = compound.get_compound("minecraft:trim").and_then(|trim| {
+            Some(ArmorTrim {
+                material: strip_namespace(trim.get_string("material")?),
+                pattern: strip_namespace(trim.get_string("pattern")?),
+            })
+        });
+        let container = compound
+            .get_list("minecraft:container")
+            .map(|slots| {
+                container_from_slots(slots.iter().filter_map(|slot| {
+                    let slot = slot.extract_compound()?;
+                    let index = usize::try_from(slot.get_int("slot")?).ok()?;
+                    Some((
+                        index,
+                        ItemStack::read_item_stack(slot.get_compound("item")?)?,
+                    ))
+                }))
+            })
+            .unwrap_or_default();
+        let custom_name = compound
+            .get("minecraft:custom_name")
+            .map(TextComponent::from_nbt);
+        let lore = compound
+            .get_list("minecraft:lore")
+            .map(|lines| {
+                lines
+                    .iter()
+                    .take(MAX_LORE_LINES)
+                    .map(TextComponent::from_nbt)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let bundle_contents = compound
+            .get_list("minecraft:bundle_contents")
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| ItemStack::read_item_stack(item.extract_compound()?))
+                    .filter(|stack| !stack.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let lodestone_tracker =
+            compound
+                .get_compound("minecraft:lodestone_tracker")
+                .map(|tracker| LodestoneTracker {
+                    target: tracker.get_compound("target").and_then(|target| {
+                        let [x, y, z] = target.get_int_array("pos")? else {
+                            return None;
+                        };
+                        let dimension = target.get_string("dimension")?;
+                        Some((dimension.clone(), BlockPos::new(*x, *y, *z)))
+                    }),
+                    tracked: tracker.get_bool("tracked").unwrap_or(true),
+                });
+
+        Self {
+            dyed_color,
+            banner_patterns,
+            damage,
+            enchantments,
+            trim,
+            container,
+            custom_name,
+            lore,
+            bundle_contents,
+            lodestone_tracker,
+        }
+    }
+
+    pub fn enchantment_level(&self, enchantment: &str) -> Option<i32> {
+        let enchantment = enchantment
+            .strip_prefix("minecraft:")
+            .unwrap_or(enchantment);
+        self.enchantments
+            .iter()
+            .find(|(name, _)| name == enchantment)
+            .map(|(_, level)| *level)
+    }
+}
+
+/// Collects container contents from `(slot, stack)` pairs, dropping slots out of range.
+pub fn container_from_slots(slots: impl IntoIterator<Item = (usize, ItemStack)>) -> Vec<ItemStack> {
+    let mut container = Vec::new();
+    for (slot, stack) in slots {
+        if slot >= MAX_CONTAINER_SLOTS || stack.is_empty() {
+            continue;
+        }
+        if container.len() <= slot {
+            container.resize(slot + 1, ItemStack::EMPTY);
+        }
+        container[slot] = stack;
+    }
+    container
+}
+
+fn strip_namespace(id: &str) -> String {
+    id.strip_prefix("minecraft:").unwrap_or(id).to_string()
+}
+
+/// Mixes the given dyes into an (optional) existing color, the same way vanilla does when
+/// crafting leather armor with dyes.
+pub fn mix_dye_colors(existing: Option<i32>, dyes: &[DyeColor]) -> i32 {
+    let mut red = 0;
+    let mut green = 0;
+    let mut blue = 0;
+    let mut total_max = 0;
+    let mut count = 0;
+
+    let colors = existing
+        .into_iter()
+        .chain(dyes.iter().map(|dye| dye.texture_diffuse_color()));
+    for color in colors {
+        let r = (color >> 16) & 0xFF;
+        let g = (color >> 8) & 0xFF;
+        let b = color & 0xFF;
+        total_max += r.max(g).max(b);
+        red += r;
+        green += g;
+        blue += b;
+        count += 1;
+    }
+
+    if count == 0 {
+        return 0;
+    }
+
+    let red = red / count;
+    let green = green / count;
+    let blue = blue / count;
+    let average_max = total_max as f32 / count as f32;
+    let max_average = red.max(green).max(blue) as f32;
+    if max_average == 0.0 {
+        return 0;
+    }
+
+    let red = (red as f32 * average_max / max_average) as i32;
+    let green = (green as f32 * average_max / max_average) as i32;
+    let blue = (blue as f32 * average_max / max_average) as i32;
+    (red << 16) | (green << 8) | blue
+}
@@ -0,0 +1,89 @@
+use pumpkin_data::item::Item;
+use pumpkin_util::math::position::BlockPos;
+
+use crate::item::ItemStack;
+use crate::item::component::LodestoneTracker;
+
+impl ItemStack {
+    pub fn is_compass(&self) -> bool {
+        self.item.id == Item::COMPASS.id
+    }
+
+    /// Makes a compass point to the lodestone at `pos` in `dimension` (e.g. `minecraft:overworld`)
+    /// until the lodestone is destroyed.
+    pub fn bind_to_lodestone(&mut self, dimension: &str, pos: BlockPos) {
+        self.patch.lodestone_tracker = Some(LodestoneTracker {
+            target: Some((dimension.to_string(), pos)),
+            tracked: true,
+        });
+    }
+
+    /// The dimension and position of the lodestone this compass points to.
+    pub fn lodestone_target(&self) -> Option<(&str, BlockPos)> {
+        let (dimension, pos) = self.patch.lodestone_tracker.as_ref()?.target.as_ref()?;
+        Some((dimension, *pos))
+    }
+
+    /// Lets a compass which tracks the destroyed lodestone at `pos` in `dimension` spin.
+    /// Returns `false` if the compass doesn't point there or isn't tracked.
+    pub fn forget_lodestone(&mut self, dimension: &str, pos: BlockPos) -> bool {
+        let Some(tracker) = &mut self.patch.lodestone_tracker else {
+            return false;
+        };
+        let points_there = tracker
+            .target
+            .as_ref()
+            .is_some_and(|(target_dimension, target_pos)| {
+                target_dimension == dimension && *target_pos == pos
+            });
+        if !tracker.tracked || !points_there {
+            return false;
+        }
+        tracker.target = None;
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_util::math::position::BlockPos;
+
+    use crate::item::ItemStack;
+
+    #[test]
+    fn compass_bound_to_lodestone_stores_target() {
+        let pos = BlockPos::new(12, -40, -300);
+        let mut compass = ItemStack::new(1, Item::COMPASS);
+        compass.bind_to_lodestone("minecraft:the_nether", pos);
+
+        let mut nbt = NbtCompound::new();
+        compass.write_item_stack(&mut nbt);
+        let tracker = nbt
+            .get_compound("components")
+            .and_then(|components| components.get_compound("minecraft:lodestone_tracker"))
+            .unwrap();
+        assert_eq!(tracker.get_bool("tracked"), Some(true));
+        let target = tracker.get_compound("target").unwrap();
+        assert_eq!(target.get_int_array("pos"), Some(&[12, -40, -300][..]));
+        assert_eq!(
+            target.get_string("dimension").unwrap(),
+            "minecraft:the_nether"
+        );
+
+        let mut read = ItemStack::read_item_stack(&nbt).unwrap();
+        assert_eq!(read.lodestone_target(), Some(("minecraft:the_nether", pos)));
+
+        // Other lodestones don't matter, destroying this one makes the compass spin
+        assert!(!read.forget_lodestone("minecraft:overworld", pos));
+        assert!(read.forget_lodestone("minecraft:the_nether", pos));
+        assert_eq!(read.lodestone_target(), None);
+        assert!(read.patch.lodestone_tracker.is_some());
+
+        // Compasses which aren't tracked keep pointing there
+        compass.patch.lodestone_tracker.as_mut().unwrap().tracked = false;
+        assert!(!compass.forget_lodestone("minecraft:the_nether", pos));
+        assert!(compass.lodestone_target().is_some());
+    }
+}
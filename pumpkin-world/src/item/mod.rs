@@ -1,8 +1,18 @@
 use pumpkin_data::item::Item;
-use pumpkin_data::tag::{RegistryKey, get_tag_values};
+use pumpkin_data::tag::{RegistryKey, Tagable, get_tag_values};
 use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::text::TextComponent;
 
+use crate::block::entities::sign::DyeColor;
+use component::{
+    BannerPatternLayer, ItemComponentPatch, MAX_BANNER_PATTERNS, MAX_LORE_LINES, mix_dye_colors,
+};
+
+mod bundle;
 mod categories;
+pub mod component;
+mod lodestone;
+pub mod registry;
 
 #[derive(serde::Deserialize, Debug, Clone, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -19,6 +29,8 @@ pub struct ItemStack {
     pub item_count: u8,
     // TODO: Should this be a ref? all of our items are const
     pub item: Item,
+    /// Components which override the item's defaults
+    pub patch: ItemComponentPatch,
 }
 
 impl PartialEq for ItemStack {
@@ -31,10 +43,15 @@ impl ItemStack {
     pub const EMPTY: ItemStack = ItemStack {
         item_count: 0,
         item: Item::AIR,
+        patch: ItemComponentPatch::EMPTY,
     };
 
     pub fn new(item_count: u8, item: Item) -> Self {
-        Self { item_count, item }
+        Self {
+            item_count,
+            item,
+            patch: ItemComponentPatch::default(),
+        }
     }
 
     pub fn get_max_stack_size(&self) -> u8 {
@@ -75,7 +92,79 @@ impl ItemStack {
     }
 
     pub fn are_items_and_components_equal(&self, other: &Self) -> bool {
-        self.item == other.item //TODO: && self.item.components == other.item.components
+        self.item == other.item && self.patch == other.patch
+    }
+
+    pub fn is_dyeable(&self) -> bool {
+        self.item
+            .is_tagged_with("minecraft:dyeable")
+            .unwrap_or(false)
+    }
+
+    pub fn has_banner_patterns(&self) -> bool {
+        self.item.id == Item::SHIELD.id
+            || self
+                .item
+                .is_tagged_with("minecraft:banners")
+                .unwrap_or(false)
+    }
+
+    /// Dyes a dyeable item, mixing the dyes with its current color.
+    /// Returns `false` if the item can't be dyed.
+    pub fn dye(&mut self, dyes: &[DyeColor]) -> bool {
+        if !self.is_dyeable() || dyes.is_empty() {
+            return false;
+        }
+        self.patch.dyed_color = Some(mix_dye_colors(self.patch.dyed_color, dyes));
+        true
+    }
+
+    /// Adds a pattern layer on top of a banner or shield.
+    /// Returns `false` if the item can't hold any more patterns.
+    pub fn add_banner_pattern(&mut self, pattern: &str, color: DyeColor) -> bool {
+        if !self.has_banner_patterns() || self.patch.banner_patterns.len() >= MAX_BANNER_PATTERNS {
+            return false;
+        }
+        self.patch.banner_patterns.push(BannerPatternLayer {
+            pattern: pattern
+                .strip_prefix("minecraft:")
+                .unwrap_or(pattern)
+                .to_string(),
+            color,
+        });
+        true
+    }
+
+    /// Washes the item like a water cauldron does: removes the dye color, or the top banner pattern.
+    /// Returns `false` if there was nothing to wash off.
+    pub fn wash(&mut self) -> bool {
+        if self.patch.dyed_color.take().is_some() {
+            return true;
+        }
+        self.patch.banner_patterns.pop().is_some()
+    }
+
+    pub fn custom_name(&self) -> Option<&TextComponent> {
+        self.patch.custom_name.as_ref()
+    }
+
+    pub fn set_custom_name(&mut self, name: TextComponent) {
+        self.patch.custom_name = Some(name);
+    }
+
+    /// Removes the custom name, so the item's default name is shown again.
+    pub fn clear_custom_name(&mut self) {
+        self.patch.custom_name = None;
+    }
+
+    pub fn lore(&self) -> &[TextComponent] {
+        &self.patch.lore
+    }
+
+    /// Replaces the lore of the item, lines past [`MAX_LORE_LINES`] are dropped.
+    pub fn set_lore(&mut self, mut lore: Vec<TextComponent>) {
+        lore.truncate(MAX_LORE_LINES);
+        self.patch.lore = lore;
     }
 
     /// Determines the mining speed for a block based on tool rules.
@@ -154,11 +243,9 @@ impl ItemStack {
         compound.put_int("count", self.item_count as i32);
 
         // Create a tag compound for additional data
-        let tag = NbtCompound::new();
+        let mut tag = NbtCompound::new();
+        self.patch.write_nbt(&mut tag);
 
-        // TODO: Store custom data like enchantments, display name, etc. would go here
-
-        // Store custom data like enchantments, display name, etc. would go here
         compound.put_component("components", tag);
     }
 
@@ -175,13 +262,83 @@ impl ItemStack {
         let count = compound.get_int("count")? as u8;
 
         // Create the item stack
-        let item_stack = Self::new(count, item);
+        let mut item_stack = Self::new(count, item);
 
         // Process any additional data in the components compound
-        if let Some(_tag) = compound.get_compound("components") {
+        if let Some(tag) = compound.get_compound("components") {
+            item_stack.patch = ItemComponentPatch::read_nbt(tag);
             // TODO: Process additional components like damage, enchantments, etc.
         }
 
         Some(item_stack)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_util::text::TextComponent;
+
+    use super::ItemStack;
+    use crate::block::entities::sign::DyeColor;
+
+    #[test]
+    fn dye_leather_boots() {
+        let mut boots = ItemStack::new(1, Item::LEATHER_BOOTS);
+        assert!(boots.dye(&[DyeColor::Red]));
+        assert_eq!(
+            boots.patch.dyed_color,
+            Some(DyeColor::Red.texture_diffuse_color())
+        );
+
+        assert!(boots.wash());
+        assert_eq!(boots.patch.dyed_color, None);
+
+        let mut stone = ItemStack::new(1, Item::STONE);
+        assert!(!stone.dye(&[DyeColor::Red]));
+    }
+
+    #[test]
+    fn banner_pattern_nbt() {
+        let mut banner = ItemStack::new(1, Item::WHITE_BANNER);
+        assert!(banner.add_banner_pattern("minecraft:stripe_bottom", DyeColor::Blue));
+        assert!(banner.add_banner_pattern("creeper", DyeColor::Lime));
+
+        let mut nbt = NbtCompound::new();
+        banner.write_item_stack(&mut nbt);
+        let components = nbt.get_compound("components").unwrap();
+        let layers = components.get_list("minecraft:banner_patterns").unwrap();
+        assert_eq!(layers.len(), 2);
+        let top = layers[1].extract_compound().unwrap();
+        assert_eq!(top.get_string("pattern").unwrap(), "minecraft:creeper");
+        assert_eq!(top.get_string("color").unwrap(), "lime");
+
+        let read = ItemStack::read_item_stack(&nbt).unwrap();
+        assert_eq!(read.patch, banner.patch);
+    }
+
+    #[test]
+    fn custom_name_and_lore_nbt() {
+        let mut sword = ItemStack::new(1, Item::DIAMOND_SWORD);
+        sword.set_custom_name(TextComponent::text("Excalibur").bold());
+        sword.set_lore(vec![
+            TextComponent::text("Pulled from a stone"),
+            TextComponent::text("Very sharp").italic(),
+        ]);
+
+        let mut nbt = NbtCompound::new();
+        sword.write_item_stack(&mut nbt);
+        let components = nbt.get_compound("components").unwrap();
+        // Stored as a text component, not as JSON
+        let name = components.get_compound("minecraft:custom_name").unwrap();
+        assert_eq!(name.get_string("text").unwrap(), "Excalibur");
+        assert_eq!(name.get_bool("bold"), Some(true));
+        assert_eq!(components.get_list("minecraft:lore").unwrap().len(), 2);
+
+        let read = ItemStack::read_item_stack(&nbt).unwrap();
+        assert_eq!(read.custom_name(), sword.custom_name());
+        assert_eq!(read.lore(), sword.lore());
+        assert_eq!(read.patch, sword.patch);
+    }
+}
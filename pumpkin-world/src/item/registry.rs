@@ -0,0 +1,48 @@
+use std::{collections::BTreeMap, sync::LazyLock};
+
+use serde::{Deserialize, de::IgnoredAny};
+
+/// The entry names of the data driven registries items refer to. The client gets the vanilla
+/// entries of these from its known packs, which are ordered by name, so the network id of an entry
+/// is its position in the sorted names.
+#[derive(Deserialize)]
+struct DataRegistryNames {
+    enchantment: BTreeMap<String, IgnoredAny>,
+    trim_material: BTreeMap<String, IgnoredAny>,
+    trim_pattern: BTreeMap<String, IgnoredAny>,
+}
+
+static DATA_REGISTRY_NAMES: LazyLock<DataRegistryNames> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../../../assets/synced_registries.json"))
+        .expect("Could not parse synced_registries.json registry.")
+});
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataRegistry {
+    Enchantment,
+    TrimMaterial,
+    TrimPattern,
+}
+
+impl DataRegistry {
+    fn names(self) -> &'static BTreeMap<String, IgnoredAny> {
+        match self {
+            Self::Enchantment => &DATA_REGISTRY_NAMES.enchantment,
+            Self::TrimMaterial => &DATA_REGISTRY_NAMES.trim_material,
+            Self::TrimPattern => &DATA_REGISTRY_NAMES.trim_pattern,
+        }
+    }
+
+    /// The network id of an entry, the name may have the `minecraft:` namespace.
+    pub fn id(self, name: &str) -> Option<i32> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        self.names()
+            .keys()
+            .position(|entry| entry == name)
+            .map(|id| id as i32)
+    }
+
+    pub fn contains(self, name: &str) -> bool {
+        self.id(name).is_some()
+    }
+}
@@ -3,8 +3,9 @@ use std::{
     path::PathBuf,
     sync::{
         Arc,
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
     },
+    time::{Duration, Instant},
 };
 
 use dashmap::{DashMap, Entry};
@@ -22,10 +23,11 @@ use crate::{
     chunk::{
         ChunkData, ChunkParsingError, ChunkReadingError, ScheduledTick, TickPriority,
         format::{anvil::AnvilChunkFile, linear::LinearFile},
-        io::{ChunkIO, LoadedData, chunk_file_manager::ChunkFileManager},
+        io::{ChunkIO, LoadedData, chunk_file_manager::ChunkFileManager, memory::MemoryChunkIO},
     },
-    generation::{Seed, WorldGenerator, get_world_gen},
+    generation::{Seed, WorldGenerator, get_dimension_world_gen, get_world_gen},
     lock::{LevelLocker, anvil::AnvilLevelLocker},
+    structure,
     world_info::{
         LevelData, WorldInfoError, WorldInfoReader, WorldInfoWriter,
         anvil::{AnvilLevelInfo, LEVEL_DAT_BACKUP_FILE_NAME, LEVEL_DAT_FILE_NAME},
@@ -45,9 +47,13 @@ pub type SyncChunk = Arc<RwLock<ChunkData>>;
 /// For more details on world generation, refer to the `WorldGenerator` module.
 pub struct Level {
     pub seed: Seed,
-    pub level_info: LevelData,
+    /// The contents of `level.dat`, written back when the level is saved.
+    pub level_info: RwLock<LevelData>,
     world_info_writer: Arc<dyn WorldInfoWriter>,
     level_folder: LevelFolder,
+    /// Whether the level is saved to `level_folder`. In-memory levels never touch the disk and
+    /// are gone once the server stops.
+    persistent: bool,
 
     // Holds this level's spawn chunks, which are always loaded
     spawn_chunks: Arc<DashMap<Vector2<i32>, SyncChunk>>,
@@ -56,12 +62,15 @@ pub struct Level {
     // from the loaded chunks map and sent to the underlying ChunkIO
     loaded_chunks: Arc<DashMap<Vector2<i32>, SyncChunk>>,
     chunk_watchers: Arc<DashMap<Vector2<i32>, usize>>,
+    nbt_size: Arc<NbtSizeEstimate>,
+    /// Whether chunks are written when they unload, turned off by `/save-off`
+    unload_saving: AtomicBool,
 
     chunk_saver: Arc<dyn ChunkIO<Data = SyncChunk>>,
     world_gen: Arc<dyn WorldGenerator>,
     // Gets unlocked when dropped
     // TODO: Make this a trait
-    _locker: Arc<AnvilLevelLocker>,
+    _locker: Option<Arc<AnvilLevelLocker>>,
     block_ticks: Arc<Mutex<Vec<ScheduledTick>>>,
     fluid_ticks: Arc<Mutex<Vec<ScheduledTick>>>,
     /// Tracks tasks associated with this world instance
@@ -70,14 +79,146 @@ pub struct Level {
     pub shutdown_notifier: Notify,
 }
 
+/// A running estimate of the heap memory the block entity NBT of the loaded chunks takes up, kept
+/// per chunk so unloading a chunk takes away exactly what loading it added.
+#[derive(Default)]
+struct NbtSizeEstimate {
+    chunks: DashMap<Vector2<i32>, usize>,
+    total: AtomicUsize,
+}
+
+impl NbtSizeEstimate {
+    fn set(&self, pos: Vector2<i32>, size: usize) {
+        // Add before subtracting so the total never wraps around
+        self.total.fetch_add(size, Ordering::Relaxed);
+        if let Some(old_size) = self.chunks.insert(pos, size) {
+            self.total.fetch_sub(old_size, Ordering::Relaxed);
+        }
+    }
+
+    fn remove(&self, pos: &Vector2<i32>) {
+        if let Some((_, size)) = self.chunks.remove(pos) {
+            self.total.fetch_sub(size, Ordering::Relaxed);
+        }
+    }
+
+    fn clear(&self) {
+        self.chunks.clear();
+        self.total.store(0, Ordering::Relaxed);
+    }
+}
+
 #[derive(Clone)]
 pub struct LevelFolder {
     pub root_folder: PathBuf,
     pub region_folder: PathBuf,
 }
 
+/// How many chunks [`Level::pregenerate`] queues at once. Every batch is saved and unloaded before
+/// the next one starts, so this bounds both the generation queue and the memory it takes.
+const PREGEN_BATCH_SIZE: usize = 64;
+
+/// How far along a [`Level::pregenerate`] is.
+#[derive(Clone, Copy, Debug)]
+pub struct PregenProgress {
+    pub done: usize,
+    pub total: usize,
+    pub elapsed: Duration,
+}
+
+impl PregenProgress {
+    /// Chunks per second so far.
+    #[must_use]
+    pub fn rate(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 {
+            self.done as f64 / seconds
+        } else {
+            0.0
+        }
+    }
+
+    /// How long the rest takes at the current rate, unknown until something is done.
+    #[must_use]
+    pub fn eta(&self) -> Option<Duration> {
+        let rate = self.rate();
+        (rate > 0.0).then(|| Duration::from_secs_f64((self.total - self.done) as f64 / rate))
+    }
+}
+
+/// All chunks in the rectangle between the chunks `from` and `to`, both included, row by row so
+/// that chunks of the same region file are next to each other.
+#[must_use]
+pub fn chunks_between(from: Vector2<i32>, to: Vector2<i32>) -> Vec<Vector2<i32>> {
+    let (min_x, max_x) = (from.x.min(to.x), from.x.max(to.x));
+    let (min_z, max_z) = (from.z.min(to.z), from.z.max(to.z));
+    (min_z..=max_z)
+        .flat_map(|z| (min_x..=max_x).map(move |x| Vector2::new(x, z)))
+        .collect()
+}
+
 impl Level {
     pub fn from_root_folder(root_folder: PathBuf) -> Self {
+        Self::new(root_folder, get_world_gen)
+    }
+
+    /// Loads the level of a dimension whose vertical bounds may differ from the overworld's.
+    pub fn from_root_folder_with_height(root_folder: PathBuf, min_y: i32, height: u32) -> Self {
+        Self::new(root_folder, |seed| {
+            get_dimension_world_gen(seed, min_y, height)
+        })
+    }
+
+    /// Loads a level whose new chunks come from a different generator than the vanilla terrain.
+    pub fn from_root_folder_with_generator(
+        root_folder: PathBuf,
+        world_gen: impl FnOnce(Seed) -> Box<dyn WorldGenerator>,
+    ) -> Self {
+        Self::new(root_folder, world_gen)
+    }
+
+    /// A level which lives only in memory, like a test world or a minigame lobby. Nothing is
+    /// read from or written to disk: chunks are generated on demand and never persisted, and
+    /// saving does nothing.
+    pub fn in_memory(level_info: LevelData) -> Self {
+        Self::new_in_memory(level_info, get_world_gen)
+    }
+
+    /// An in-memory level of a dimension whose vertical bounds may differ from the overworld's.
+    pub fn in_memory_with_height(level_info: LevelData, min_y: i32, height: u32) -> Self {
+        Self::new_in_memory(level_info, |seed| {
+            get_dimension_world_gen(seed, min_y, height)
+        })
+    }
+
+    /// An in-memory level whose chunks come from a different generator than the vanilla terrain.
+    pub fn in_memory_with_generator(
+        level_info: LevelData,
+        world_gen: impl FnOnce(Seed) -> Box<dyn WorldGenerator>,
+    ) -> Self {
+        Self::new_in_memory(level_info, world_gen)
+    }
+
+    fn new_in_memory(
+        level_info: LevelData,
+        world_gen: impl FnOnce(Seed) -> Box<dyn WorldGenerator>,
+    ) -> Self {
+        let seed = Seed(level_info.world_gen_settings.seed as u64);
+        log::info!("Creating in-memory world with seed: {}", seed.0 as i64);
+        Self::with_storage(
+            seed,
+            level_info,
+            world_gen(seed).into(),
+            LevelFolder {
+                root_folder: PathBuf::new(),
+                region_folder: PathBuf::new(),
+            },
+            Arc::new(MemoryChunkIO::default()),
+            None,
+        )
+    }
+
+    fn new(root_folder: PathBuf, world_gen: impl FnOnce(Seed) -> Box<dyn WorldGenerator>) -> Self {
         // If we are using an already existing world we want to read the seed from the level.dat, If not we want to check if there is a seed in the config, if not lets create a random one
         let region_folder = root_folder.join("region");
         if !region_folder.exists() {
@@ -122,7 +263,7 @@ impl Level {
         );
 
         let seed = Seed(level_info.world_gen_settings.seed as u64);
-        let world_gen = get_world_gen(seed).into();
+        let world_gen = world_gen(seed).into();
 
         let chunk_saver: Arc<dyn ChunkIO<Data = SyncChunk>> = match advanced_config().chunk.format {
             //ChunkFormat::Anvil => (Arc::new(AnvilChunkFormat), Arc::new(AnvilChunkFormat)),
@@ -130,17 +271,39 @@ impl Level {
             ChunkFormat::Anvil => Arc::new(ChunkFileManager::<AnvilChunkFile>::default()),
         };
 
+        Self::with_storage(
+            seed,
+            level_info,
+            world_gen,
+            level_folder,
+            chunk_saver,
+            Some(Arc::new(locker)),
+        )
+    }
+
+    fn with_storage(
+        seed: Seed,
+        level_info: LevelData,
+        world_gen: Arc<dyn WorldGenerator>,
+        level_folder: LevelFolder,
+        chunk_saver: Arc<dyn ChunkIO<Data = SyncChunk>>,
+        locker: Option<Arc<AnvilLevelLocker>>,
+    ) -> Self {
         Self {
             seed,
             world_gen,
             world_info_writer: Arc::new(AnvilLevelInfo),
             level_folder,
+            // Only levels on disk are locked
+            persistent: locker.is_some(),
             chunk_saver,
             spawn_chunks: Arc::new(DashMap::new()),
             loaded_chunks: Arc::new(DashMap::new()),
             chunk_watchers: Arc::new(DashMap::new()),
-            level_info,
-            _locker: Arc::new(locker),
+            nbt_size: Arc::default(),
+            unload_saving: AtomicBool::new(true),
+            level_info: RwLock::new(level_info),
+            _locker: locker,
             tasks: TaskTracker::new(),
             shutdown_notifier: Notify::new(),
             block_ticks: Arc::new(Mutex::new(Vec::new())),
@@ -177,15 +340,23 @@ impl Level {
             .map(|chunk| (*chunk.key(), chunk.value().clone()))
             .collect::<Vec<_>>();
         self.loaded_chunks.clear();
+        self.nbt_size.clear();
 
         // TODO: I think the chunk_saver should be at the server level
         self.chunk_saver.clear_watched_chunks().await;
         self.write_chunks(chunks_to_write).await;
 
         // then lets save the world info
+        self.write_level_info().await;
+    }
+
+    async fn write_level_info(&self) {
+        if !self.persistent {
+            return;
+        }
         let result = self
             .world_info_writer
-            .write_world_info(self.level_info.clone(), &self.level_folder);
+            .write_world_info(self.level_info.read().await.clone(), &self.level_folder);
 
         // Lets not stop the overall save for this
         if let Err(err) = result {
@@ -193,10 +364,122 @@ impl Level {
         }
     }
 
+    /// Saves the loaded chunks which changed and `level.dat` without unloading anything. Returns
+    /// how many chunks are saved, along with the writing to disk, which is up to the caller to
+    /// wait for or run in the background.
+    pub async fn save(self: &Arc<Self>) -> (usize, impl Future<Output = ()> + Send + 'static) {
+        // Don't hold the map across awaits
+        let loaded_chunks = self
+            .loaded_chunks
+            .iter()
+            .map(|chunk| (*chunk.key(), chunk.value().clone()))
+            .collect::<Vec<_>>();
+        let mut changed_chunks = Vec::new();
+        for (coord, chunk) in loaded_chunks {
+            if chunk.read().await.dirty {
+                changed_chunks.push((coord, chunk));
+            }
+        }
+        let count = changed_chunks.len();
+
+        let level = self.clone();
+        let save = async move {
+            level.write_loaded_chunks(changed_chunks).await;
+            level.write_level_info().await;
+        };
+        (count, save)
+    }
+
+    /// Turns writing chunks when they unload on or off, like `/save-off` and `/save-on`, returning
+    /// whether it changed. While it is off, chunks nobody watches stay loaded instead, and are
+    /// unloaded once it is turned on again.
+    pub async fn set_unload_saving(self: &Arc<Self>, enabled: bool) -> bool {
+        if self.unload_saving.swap(enabled, Ordering::Relaxed) == enabled {
+            return false;
+        }
+        if enabled {
+            let loaded = self
+                .loaded_chunks
+                .iter()
+                .map(|chunk| *chunk.key())
+                .collect::<Vec<_>>();
+            self.clean_chunks(&loaded).await;
+        }
+        true
+    }
+
+    /// Like [`Level::write_chunks`], but for chunks which stay loaded: their scheduled ticks are
+    /// copied instead of taken from the level, and the files are written even though they are
+    /// still watched.
+    async fn write_loaded_chunks(&self, chunks_to_write: Vec<(Vector2<i32>, SyncChunk)>) {
+        {
+            let block_ticks = self.block_ticks.lock().await;
+            let fluid_ticks = self.fluid_ticks.lock().await;
+            for (coord, chunk) in &chunks_to_write {
+                let in_chunk = |tick: &&ScheduledTick| {
+                    tick.block_pos.chunk_and_chunk_relative_position().0 == *coord
+                };
+                let mut chunk_data = chunk.write().await;
+                chunk_data.block_ticks = block_ticks.iter().filter(in_chunk).cloned().collect();
+                chunk_data.fluid_ticks = fluid_ticks.iter().filter(in_chunk).cloned().collect();
+            }
+        }
+
+        if let Err(error) = self
+            .chunk_saver
+            .save_chunks(&self.level_folder, chunks_to_write)
+            .await
+        {
+            log::error!("Failed writing Chunk to disk {}", error);
+        }
+        self.chunk_saver.write_all().await;
+    }
+
+    /// The file a structure with the given id is saved to, see [`structure::structure_file`].
+    pub fn structure_file(&self, id: &str) -> Option<PathBuf> {
+        if !self.persistent {
+            return None;
+        }
+        structure::structure_file(&self.level_folder.root_folder, id)
+    }
+
+    /// The file of world data with the given name, e.g. `data/scoreboard.dat`.
+    pub fn data_file(&self, name: &str) -> Option<PathBuf> {
+        if !self.persistent {
+            return None;
+        }
+        Some(
+            self.level_folder
+                .root_folder
+                .join("data")
+                .join(format!("{name}.dat")),
+        )
+    }
+
+    /// Whether the level is saved to disk, see [`Level::in_memory`].
+    pub const fn is_persistent(&self) -> bool {
+        self.persistent
+    }
+
     pub fn loaded_chunk_count(&self) -> usize {
         self.loaded_chunks.len()
     }
 
+    /// Estimates the heap memory the NBT of the block entities in all loaded chunks takes up,
+    /// to help tuning the chunk cache. This is kept up to date as chunks are loaded and unloaded
+    /// and as block entities are added and removed, so it's cheap to read.
+    pub fn loaded_nbt_heap_size(&self) -> usize {
+        self.nbt_size.total.load(Ordering::Relaxed)
+    }
+
+    /// Updates [`Level::loaded_nbt_heap_size`] after the block entities of `chunk` changed.
+    pub fn block_entities_changed(&self, chunk: &ChunkData) {
+        if self.loaded_chunks.contains_key(&chunk.position) {
+            self.nbt_size
+                .set(chunk.position, chunk.block_entities_nbt_size());
+        }
+    }
+
     pub async fn clean_up_log(&self) {
         self.chunk_saver.clean_up_log().await;
     }
@@ -278,6 +561,10 @@ impl Level {
     }
 
     pub async fn clean_chunks(self: &Arc<Self>, chunks: &[Vector2<i32>]) {
+        // Nothing may be written, so the chunks have to stay loaded
+        if !self.unload_saving.load(Ordering::Relaxed) {
+            return;
+        }
         // Care needs to be take here because of interweaving case:
         // 1) Remove chunk from cache
         // 2) Another player wants same chunk
@@ -310,13 +597,16 @@ impl Level {
             // Only after we have written the chunks to the serializer do we remove them from the
             // cache
             for (pos, _) in chunks_to_remove {
-                let _ = level.loaded_chunks.remove_if(&pos, |_, _| {
+                let removed = level.loaded_chunks.remove_if(&pos, |_, _| {
                     // Recheck that there is no one watching
                     level
                         .chunk_watchers
                         .get(&pos)
                         .is_none_or(|count| count.is_zero())
                 });
+                if removed.is_some() {
+                    level.nbt_size.remove(&pos);
+                }
             }
         });
     }
@@ -331,8 +621,14 @@ impl Level {
 
     pub fn clean_memory(&self) {
         self.chunk_watchers.retain(|_, watcher| !watcher.is_zero());
-        self.loaded_chunks
-            .retain(|at, _| self.chunk_watchers.get(at).is_some());
+        let unload_saving = self.unload_saving.load(Ordering::Relaxed);
+        self.loaded_chunks.retain(|at, _| {
+            let kept = !unload_saving || self.chunk_watchers.get(at).is_some();
+            if !kept {
+                self.nbt_size.remove(at);
+            }
+            kept
+        });
 
         // if the difference is too big, we can shrink the loaded chunks
         // (1024 chunks is the equivalent to a 32x32 chunks area)
@@ -434,13 +730,22 @@ impl Level {
         // We expect best case scenario to have all cached
         let mut remaining_chunks = Vec::new();
         for chunk in chunks {
-            let is_ok = if let Some(chunk) = self.loaded_chunks.get(chunk) {
-                send_chunk(false, chunk.value().clone(), &channel)
-            } else if let Some(spawn_chunk) = self.spawn_chunks.get(chunk) {
+            let loaded = self
+                .loaded_chunks
+                .get(chunk)
+                .map(|chunk| chunk.value().clone());
+            let spawn_chunk = self
+                .spawn_chunks
+                .get(chunk)
+                .map(|chunk| chunk.value().clone());
+            let is_ok = if let Some(chunk) = loaded {
+                send_chunk(false, chunk, &channel)
+            } else if let Some(spawn_chunk) = spawn_chunk {
                 // Also clone the arc into the loaded chunks
-                self.loaded_chunks
-                    .insert(*chunk, spawn_chunk.value().clone());
-                send_chunk(false, spawn_chunk.value().clone(), &channel)
+                self.loaded_chunks.insert(*chunk, spawn_chunk.clone());
+                self.nbt_size
+                    .set(*chunk, spawn_chunk.read().await.block_entities_nbt_size());
+                send_chunk(false, spawn_chunk, &channel)
             } else {
                 remaining_chunks.push(*chunk);
                 true
@@ -463,6 +768,7 @@ impl Level {
 
         let load_channel = channel.clone();
         let loaded_chunks = self.loaded_chunks.clone();
+        let nbt_size = self.nbt_size.clone();
         let level_block_ticks = self.block_ticks.clone();
         let level_fluid_ticks = self.fluid_ticks.clone();
         let handle_load = async move {
@@ -483,11 +789,14 @@ impl Level {
                         level_fluid_ticks.extend(fluid_ticks);
                         drop(level_fluid_ticks);
 
-                        let value = loaded_chunks
-                            .entry(position)
-                            .or_insert(chunk)
-                            .value()
-                            .clone();
+                        let nbt_bytes = chunk.read().await.block_entities_nbt_size();
+                        let value = match loaded_chunks.entry(position) {
+                            Entry::Occupied(occupied) => occupied.get().clone(),
+                            Entry::Vacant(vacant) => {
+                                nbt_size.set(position, nbt_bytes);
+                                vacant.insert(chunk).value().clone()
+                            }
+                        };
                         send_chunk(false, value, &load_channel)
                     }
                     LoadedData::Missing(pos) => generate_bridge_send.send(pos).await.is_ok(),
@@ -520,6 +829,7 @@ impl Level {
         };
 
         let loaded_chunks = self.loaded_chunks.clone();
+        let nbt_size = self.nbt_size.clone();
         let world_gen = self.world_gen.clone();
         let handle_generate = async move {
             let continue_to_generate = Arc::new(AtomicBool::new(true));
@@ -529,6 +839,7 @@ impl Level {
                 }
 
                 let loaded_chunks = loaded_chunks.clone();
+                let nbt_size = nbt_size.clone();
                 let world_gen = world_gen.clone();
                 let channel = channel.clone();
                 let cloned_continue_to_generate = continue_to_generate.clone();
@@ -543,6 +854,7 @@ impl Level {
                         .or_insert_with(|| {
                             // Avoid possible duplicating work by doing this within the dashmap lock
                             let generated_chunk = world_gen.generate_chunk(&pos);
+                            nbt_size.set(pos, generated_chunk.block_entities_nbt_size());
                             Arc::new(RwLock::new(generated_chunk))
                         })
                         .value()
@@ -568,6 +880,70 @@ impl Level {
         tracker.wait().await;
     }
 
+    /// Loads or generates `chunks` and saves them right away, [`PREGEN_BATCH_SIZE`] at a time.
+    /// Generation runs on rayon like any other chunk load, so players keep getting their chunks
+    /// in between batches. Chunks nobody watches are unloaded again after being saved.
+    ///
+    /// `on_progress` is called after every batch and `cancel` is checked before each one.
+    /// Returns how many chunks were done.
+    pub async fn pregenerate(
+        self: &Arc<Self>,
+        chunks: &[Vector2<i32>],
+        cancel: &AtomicBool,
+        mut on_progress: impl FnMut(PregenProgress),
+    ) -> usize {
+        let start = Instant::now();
+        let mut done = 0;
+        for batch in chunks.chunks(PREGEN_BATCH_SIZE) {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let (send, mut recv) = mpsc::unbounded_channel();
+            let fetcher = self.fetch_chunks(batch, send);
+            let handler = async {
+                let mut fetched = Vec::with_capacity(batch.len());
+                while let Some((chunk, _)) = recv.recv().await {
+                    let pos = chunk.read().await.position;
+                    fetched.push((pos, chunk));
+                }
+                fetched
+            };
+            let ((), fetched) = tokio::join!(fetcher, handler);
+            done += fetched.len();
+
+            // Watched chunks are saved with the rest of the world, and their scheduled ticks
+            // must stay in the level
+            let unwatched = fetched
+                .into_iter()
+                .filter(|(pos, _)| {
+                    self.chunk_watchers
+                        .get(pos)
+                        .is_none_or(|count| count.is_zero())
+                })
+                .collect::<Vec<_>>();
+            let positions = unwatched.iter().map(|(pos, _)| *pos).collect::<Vec<_>>();
+            self.write_chunks(unwatched).await;
+            for pos in positions {
+                let removed = self.loaded_chunks.remove_if(&pos, |_, _| {
+                    self.chunk_watchers
+                        .get(&pos)
+                        .is_none_or(|count| count.is_zero())
+                });
+                if removed.is_some() {
+                    self.nbt_size.remove(&pos);
+                }
+            }
+
+            on_progress(PregenProgress {
+                done,
+                total: chunks.len(),
+                elapsed: start.elapsed(),
+            });
+        }
+        done
+    }
+
     pub fn try_get_chunk(
         &self,
         coordinates: Vector2<i32>,
@@ -646,3 +1022,173 @@ impl Level {
         });
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::{
+        path::Path,
+        sync::{Arc, atomic::AtomicBool},
+    };
+
+    use pumpkin_util::math::{position::BlockPos, vector2::Vector2};
+    use temp_dir::TempDir;
+    use tokio::sync::mpsc;
+
+    use crate::{
+        block::entities::sign::SignBlockEntity,
+        generation::{FlatGenerator, FlatSettings},
+        world_info::{LevelData, anvil::LEVEL_DAT_FILE_NAME},
+    };
+
+    use super::{Level, SyncChunk, chunks_between};
+
+    async fn fetch_chunk(level: &Arc<Level>, position: Vector2<i32>) -> SyncChunk {
+        let (send, mut recv) = mpsc::unbounded_channel();
+        level.fetch_chunks(&[position], send).await;
+        recv.recv().await.expect("chunk was not fetched").0
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn in_memory_level_keeps_changes_without_files() {
+        let level = Arc::new(Level::in_memory(LevelData::default()));
+        assert!(!level.is_persistent());
+
+        let position = Vector2::new(0, 0);
+        let chunk = fetch_chunk(&level, position).await;
+        chunk
+            .write()
+            .await
+            .section
+            .set_block_absolute_y(1, 100, 2, 1);
+
+        // Unloading the chunk keeps it in memory
+        level.clean_chunk(&position).await;
+        level.shutdown().await;
+        assert_eq!(level.loaded_chunk_count(), 0);
+
+        let chunk = fetch_chunk(&level, position).await;
+        assert_eq!(
+            chunk.read().await.section.get_block_absolute_y(1, 100, 2),
+            Some(1)
+        );
+
+        assert!(!Path::new("region").exists());
+        assert!(!Path::new(LEVEL_DAT_FILE_NAME).exists());
+        assert!(level.structure_file("test").is_none());
+    }
+
+    #[tokio::test]
+    async fn chunks_stay_loaded_while_unload_saving_is_off() {
+        let level = Arc::new(Level::in_memory(LevelData::default()));
+        assert!(level.set_unload_saving(false).await);
+        assert!(!level.set_unload_saving(false).await);
+
+        let position = Vector2::new(0, 0);
+        let chunk = fetch_chunk(&level, position).await;
+        chunk
+            .write()
+            .await
+            .section
+            .set_block_absolute_y(1, 100, 2, 1);
+        level.clean_chunk(&position).await;
+        level.clean_memory();
+        assert_eq!(level.loaded_chunk_count(), 1);
+
+        // Turning it back on unloads what nobody watches
+        assert!(level.set_unload_saving(true).await);
+        level.shutdown().await;
+        assert_eq!(level.loaded_chunk_count(), 0);
+        let chunk = fetch_chunk(&level, position).await;
+        assert_eq!(
+            chunk.read().await.section.get_block_absolute_y(1, 100, 2),
+            Some(1)
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn nbt_size_follows_loaded_block_entities() {
+        let level = Arc::new(Level::in_memory(LevelData::default()));
+        let position = Vector2::new(0, 0);
+        let chunk = fetch_chunk(&level, position).await;
+        assert_eq!(level.loaded_nbt_heap_size(), 0);
+
+        let sign_pos = BlockPos::new(1, 100, 2);
+        let sign = SignBlockEntity::new(
+            sign_pos,
+            true,
+            [
+                "Hello".to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ],
+        );
+        {
+            let mut chunk = chunk.write().await;
+            chunk.block_entities.insert(sign_pos, Arc::new(sign));
+            level.block_entities_changed(&chunk);
+        }
+        let size = level.loaded_nbt_heap_size();
+        assert!(size > 0);
+
+        level.clean_chunk(&position).await;
+        level.shutdown().await;
+        assert_eq!(level.loaded_nbt_heap_size(), 0);
+
+        // Loading the chunk again counts its block entities again
+        fetch_chunk(&level, position).await;
+        assert_eq!(level.loaded_nbt_heap_size(), size);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pregenerated_chunks_are_saved_and_unloaded() {
+        let temp_dir = TempDir::new().unwrap();
+        let open = || {
+            Arc::new(Level::from_root_folder_with_generator(
+                temp_dir.path().to_path_buf(),
+                |_| Box::new(FlatGenerator::new(FlatSettings::default(), -64, 384)),
+            ))
+        };
+        // Crosses the corner of four region files
+        let chunks = chunks_between(Vector2::new(-2, -2), Vector2::new(2, 2));
+        assert_eq!(chunks.len(), 25);
+
+        let level = open();
+        let cancel = Arc::new(AtomicBool::new(false));
+        let pregen = {
+            let level = level.clone();
+            let chunks = chunks.clone();
+            tokio::spawn(async move {
+                let mut reports = Vec::new();
+                let done = level
+                    .pregenerate(&chunks, &cancel, |progress| reports.push(progress))
+                    .await;
+                (done, reports)
+            })
+        };
+        let (done, reports) = pregen.await.unwrap();
+        assert_eq!(done, chunks.len());
+        let last = reports.last().unwrap();
+        assert_eq!((last.done, last.total), (25, 25));
+        assert_eq!(last.eta(), Some(std::time::Duration::ZERO));
+        assert_eq!(level.loaded_chunk_count(), 0);
+
+        for region in ["r.-1.-1.mca", "r.-1.0.mca", "r.0.-1.mca", "r.0.0.mca"] {
+            assert!(temp_dir.path().join("region").join(region).exists());
+        }
+
+        // Opening the world again loads every chunk instead of generating it
+        level.shutdown().await;
+        drop(level);
+        let level = open();
+        let (send, mut recv) = mpsc::unbounded_channel();
+        level.fetch_chunks(&chunks, send).await;
+        let mut fetched = 0;
+        while let Some((_, is_new)) = recv.recv().await {
+            assert!(!is_new);
+            fetched += 1;
+        }
+        assert_eq!(fetched, chunks.len());
+        level.shutdown().await;
+    }
+}
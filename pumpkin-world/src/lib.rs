@@ -1,6 +1,7 @@
 use generation::settings::GenerationSettings;
 use pumpkin_util::math::vector2::Vector2;
 
+pub mod advancement;
 pub mod biome;
 pub mod block;
 pub mod chunk;
@@ -12,6 +13,7 @@ mod generation;
 pub mod item;
 pub mod level;
 mod lock;
+pub mod structure;
 pub mod world_info;
 
 pub type BlockId = u16;
@@ -43,8 +45,9 @@ macro_rules! read_data_from_file {
 
 // TODO: is there a way to do in-file benches?
 pub use generation::{
-    GlobalRandomConfig, noise_router::proto_noise_router::ProtoNoiseRouters,
-    proto_chunk::ProtoChunk, settings::GENERATION_SETTINGS, settings::GeneratorSetting,
+    GlobalRandomConfig, get_configured_world_gen,
+    noise_router::proto_noise_router::ProtoNoiseRouters, proto_chunk::ProtoChunk,
+    settings::GENERATION_SETTINGS, settings::GeneratorSetting,
 };
 
 pub fn bench_create_and_populate_noise(
@@ -0,0 +1,515 @@
+use std::{
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use pumpkin_data::Block;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_util::random::{RandomImpl, get_seed, legacy_rand::LegacyRand};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::{BlockStateId, block::state::RawBlockState, chunk::format::PaletteBlockEntry};
+
+pub mod transform;
+
+pub use transform::{BlockMirror, BlockRotation, transform_state};
+
+#[derive(Error, Debug)]
+pub enum StructureError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Invalid structure NBT: {0}")]
+    Nbt(String),
+    #[error("Structure size must have 3 non-negative components")]
+    InvalidSize,
+    #[error("Block {index} refers to palette entry {state}, which does not exist")]
+    MissingPaletteEntry { index: usize, state: i32 },
+    #[error("Unknown block {0}")]
+    UnknownBlock(String),
+}
+
+#[derive(Deserialize)]
+struct StructureNbt {
+    size: Vec<i32>,
+    #[serde(default)]
+    palette: Vec<PaletteBlockEntry>,
+    /// Some structures (e.g. shipwrecks) come with several palettes to pick from
+    #[serde(default)]
+    palettes: Vec<Vec<PaletteBlockEntry>>,
+    #[serde(default)]
+    blocks: Vec<StructureBlockNbt>,
+    #[serde(default)]
+    entities: Vec<StructureEntityNbt>,
+}
+
+#[derive(Deserialize)]
+struct StructureBlockNbt {
+    state: i32,
+    pos: Vec<i32>,
+    nbt: Option<NbtCompound>,
+}
+
+#[derive(Deserialize)]
+struct StructureEntityNbt {
+    pos: Vec<f64>,
+    nbt: NbtCompound,
+}
+
+/// A single block of a structure, relative to the structure's origin.
+#[derive(Debug, Clone)]
+pub struct StructureBlock {
+    pub pos: Vector3<i32>,
+    pub state_id: BlockStateId,
+    /// The block entity data, without a position
+    pub nbt: Option<NbtCompound>,
+}
+
+impl StructureBlock {
+    pub fn block(&self) -> Block {
+        Block::from_state_id(self.state_id).unwrap_or(Block::AIR)
+    }
+}
+
+/// An entity of a structure, relative to the structure's origin.
+#[derive(Debug, Clone)]
+pub struct StructureEntity {
+    pub pos: Vector3<f64>,
+    pub yaw: f32,
+    pub pitch: f32,
+    /// The saved entity, including its `id`
+    pub nbt: NbtCompound,
+}
+
+impl StructureEntity {
+    /// The entity type, e.g. `minecraft:armor_stand`.
+    pub fn id(&self) -> Option<&str> {
+        self.nbt.get_string("id").map(String::as_str)
+    }
+}
+
+/// How a structure is placed, matching the options of a structure block in load mode.
+#[derive(Debug, Clone, Copy)]
+pub struct StructurePlaceSettings {
+    pub rotation: BlockRotation,
+    pub mirror: BlockMirror,
+    /// The chance of each block to be placed, from 0 to 1
+    pub integrity: f32,
+    /// The seed deciding which blocks are removed when the integrity is below 1, or 0 for a
+    /// random one
+    pub seed: i64,
+}
+
+impl Default for StructurePlaceSettings {
+    fn default() -> Self {
+        Self {
+            rotation: BlockRotation::None,
+            mirror: BlockMirror::None,
+            integrity: 1.0,
+            seed: 0,
+        }
+    }
+}
+
+/// A structure saved by a structure block, in the vanilla `.nbt` format.
+#[derive(Debug, Clone)]
+pub struct StructureTemplate {
+    pub size: Vector3<i32>,
+    /// All blocks, except structure voids, which leave the world untouched
+    pub blocks: Vec<StructureBlock>,
+    pub entities: Vec<StructureEntity>,
+}
+
+impl StructureTemplate {
+    /// Loads a GZipped structure file, like the ones in `generated/<namespace>/structures`.
+    pub fn from_file(path: &Path) -> Result<Self, StructureError> {
+        Self::read(File::open(path)?)
+    }
+
+    /// Reads a GZipped structure.
+    pub fn read(input: impl Read) -> Result<Self, StructureError> {
+        let nbt: StructureNbt = pumpkin_nbt::nbt_compress::from_gzip_bytes(input)
+            .map_err(|err| StructureError::Nbt(err.to_string()))?;
+        Self::from_structure_nbt(nbt)
+    }
+
+    fn from_structure_nbt(nbt: StructureNbt) -> Result<Self, StructureError> {
+        let [x, y, z] = nbt.size[..] else {
+            return Err(StructureError::InvalidSize);
+        };
+        if x < 0 || y < 0 || z < 0 {
+            return Err(StructureError::InvalidSize);
+        }
+        let palette = if nbt.palette.is_empty() {
+            nbt.palettes.into_iter().next().unwrap_or_default()
+        } else {
+            nbt.palette
+        };
+
+        let mut states = Vec::with_capacity(palette.len());
+        for entry in &palette {
+            let state = if get_block_name(&entry.name) == "structure_void" {
+                None
+            } else {
+                let state = RawBlockState::from_palette(entry)
+                    .ok_or_else(|| StructureError::UnknownBlock(entry.name.clone()))?;
+                Some(state.state_id)
+            };
+            states.push(state);
+        }
+
+        let mut blocks = Vec::with_capacity(nbt.blocks.len());
+        for (index, block) in nbt.blocks.into_iter().enumerate() {
+            let state = usize::try_from(block.state)
+                .ok()
+                .and_then(|state| states.get(state))
+                .ok_or(StructureError::MissingPaletteEntry {
+                    index,
+                    state: block.state,
+                })?;
+            let Some(state_id) = *state else {
+                continue;
+            };
+            let [x, y, z] = block.pos[..] else {
+                return Err(StructureError::Nbt(format!(
+                    "Block {index} has an invalid position"
+                )));
+            };
+            blocks.push(StructureBlock {
+                pos: Vector3::new(x, y, z),
+                state_id,
+                nbt: block.nbt,
+            });
+        }
+
+        let mut entities = Vec::with_capacity(nbt.entities.len());
+        for (index, entity) in nbt.entities.into_iter().enumerate() {
+            let [x, y, z] = entity.pos[..] else {
+                return Err(StructureError::Nbt(format!(
+                    "Entity {index} has an invalid position"
+                )));
+            };
+            let rotation = entity.nbt.get_list("Rotation").unwrap_or_default();
+            let angle = |i: usize| {
+                rotation
+                    .get(i)
+                    .and_then(NbtTag::extract_float)
+                    .unwrap_or_default()
+            };
+            entities.push(StructureEntity {
+                pos: Vector3::new(x, y, z),
+                yaw: angle(0),
+                pitch: angle(1),
+                nbt: entity.nbt,
+            });
+        }
+
+        Ok(Self {
+            size: Vector3::new(x, y, z),
+            blocks,
+            entities,
+        })
+    }
+
+    /// Returns the blocks to place, with their transformed positions relative to the placement
+    /// origin and their transformed states.
+    pub fn placed_blocks(
+        &self,
+        settings: &StructurePlaceSettings,
+    ) -> impl Iterator<Item = StructureBlock> + '_ {
+        let seed = if settings.seed == 0 {
+            get_seed()
+        } else {
+            settings.seed as u64
+        };
+        let mut random = LegacyRand::from_seed(seed);
+        let integrity = settings.integrity;
+        let settings = *settings;
+        self.blocks
+            .iter()
+            .filter(move |_| integrity >= 1.0 || random.next_f32() <= integrity)
+            .map(move |block| StructureBlock {
+                pos: settings
+                    .rotation
+                    .rotate_pos(settings.mirror.mirror_pos(block.pos)),
+                state_id: transform_state(block.state_id, settings.mirror, settings.rotation),
+                nbt: block.nbt.clone(),
+            })
+    }
+
+    /// Returns the entities to spawn, with their transformed positions relative to the placement
+    /// origin and their transformed yaw.
+    pub fn placed_entities(
+        &self,
+        settings: &StructurePlaceSettings,
+    ) -> impl Iterator<Item = StructureEntity> + '_ {
+        let settings = *settings;
+        self.entities.iter().map(move |entity| StructureEntity {
+            pos: settings
+                .rotation
+                .rotate_vec(settings.mirror.mirror_vec(entity.pos)),
+            yaw: settings
+                .rotation
+                .rotate_yaw(settings.mirror.mirror_yaw(entity.yaw)),
+            pitch: entity.pitch,
+            nbt: entity.nbt.clone(),
+        })
+    }
+}
+
+/// Where structure blocks save structures: `generated/<namespace>/structures/<path>.nbt` in the
+/// world folder.
+///
+/// Returns `None` for ids which are no valid resource locations, which could also escape the
+/// folder.
+pub fn structure_file(world_folder: &Path, id: &str) -> Option<PathBuf> {
+    let (namespace, path) = id.split_once(':').unwrap_or(("minecraft", id));
+    let is_valid = |part: &str, allowed: &[char]| {
+        !part.is_empty()
+            && part.chars().all(|c| {
+                c.is_ascii_lowercase()
+                    || c.is_ascii_digit()
+                    || "_-.".contains(c)
+                    || allowed.contains(&c)
+            })
+    };
+    if !is_valid(namespace, &[])
+        || !is_valid(path, &['/'])
+        || path
+            .split('/')
+            .any(|segment| segment.is_empty() || segment == "." || segment == "..")
+    {
+        return None;
+    }
+    Some(
+        world_folder
+            .join("generated")
+            .join(namespace)
+            .join("structures")
+            .join(format!("{path}.nbt")),
+    )
+}
+
+fn get_block_name(name: &str) -> &str {
+    name.strip_prefix("minecraft:").unwrap_or(name)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use pumpkin_data::Block;
+    use pumpkin_nbt::{
+        compound::NbtCompound, nbt_compress::write_gzip_compound_tag_to_bytes, tag::NbtTag,
+    };
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{
+        BlockMirror, BlockRotation, StructureError, StructurePlaceSettings, StructureTemplate,
+        structure_file,
+    };
+
+    fn int_list(values: [i32; 3]) -> NbtTag {
+        NbtTag::List(values.into_iter().map(NbtTag::Int).collect())
+    }
+
+    fn palette_entry(name: &str, properties: &[(&str, &str)]) -> NbtTag {
+        let mut entry = NbtCompound::new();
+        entry.put_string("Name", name.to_string());
+        if !properties.is_empty() {
+            let mut props = NbtCompound::new();
+            for (key, value) in properties {
+                props.put_string(key, (*value).to_string());
+            }
+            entry.put_component("Properties", props);
+        }
+        NbtTag::Compound(entry)
+    }
+
+    fn block(state: i32, pos: [i32; 3]) -> NbtTag {
+        let mut block = NbtCompound::new();
+        block.put_int("state", state);
+        block.put("pos", int_list(pos));
+        NbtTag::Compound(block)
+    }
+
+    /// An east facing stair in front of a stone block, with a structure void on top.
+    fn stair_structure() -> Vec<u8> {
+        let mut nbt = NbtCompound::new();
+        nbt.put_int("DataVersion", 4189);
+        nbt.put("size", int_list([2, 2, 1]));
+        nbt.put_list(
+            "palette",
+            vec![
+                palette_entry("minecraft:stone", &[]),
+                palette_entry(
+                    "minecraft:oak_stairs",
+                    &[
+                        ("facing", "east"),
+                        ("half", "bottom"),
+                        ("shape", "inner_left"),
+                        ("waterlogged", "false"),
+                    ],
+                ),
+                palette_entry("minecraft:structure_void", &[]),
+            ]
+            .into_boxed_slice(),
+        );
+        nbt.put_list(
+            "blocks",
+            vec![
+                block(0, [0, 0, 0]),
+                block(1, [1, 0, 0]),
+                block(2, [0, 1, 0]),
+            ]
+            .into_boxed_slice(),
+        );
+        write_gzip_compound_tag_to_bytes(&nbt).unwrap()
+    }
+
+    fn prop(block: &super::StructureBlock, key: &str) -> String {
+        block
+            .block()
+            .properties(block.state_id)
+            .unwrap()
+            .to_props()
+            .into_iter()
+            .find(|(name, _)| name == key)
+            .unwrap()
+            .1
+    }
+
+    #[test]
+    fn structure_voids_are_skipped() {
+        let template = StructureTemplate::read(&stair_structure()[..]).unwrap();
+        assert_eq!(template.size, Vector3::new(2, 2, 1));
+        assert_eq!(template.blocks.len(), 2);
+        assert_eq!(template.blocks[0].state_id, Block::STONE.default_state_id);
+    }
+
+    #[test]
+    fn rotated_stairs_face_the_rotated_direction() {
+        let template = StructureTemplate::read(&stair_structure()[..]).unwrap();
+        let settings = StructurePlaceSettings {
+            rotation: BlockRotation::Clockwise90,
+            ..Default::default()
+        };
+        let blocks: Vec<_> = template.placed_blocks(&settings).collect();
+        let stairs = &blocks[1];
+        assert_eq!(stairs.block().id, Block::OAK_STAIRS.id);
+        // One block east of the origin turns into one block south
+        assert_eq!(stairs.pos, Vector3::new(0, 0, 1));
+        assert_eq!(prop(stairs, "facing"), "south");
+        assert_eq!(prop(stairs, "shape"), "inner_left");
+
+        let settings = StructurePlaceSettings {
+            mirror: BlockMirror::FrontBack,
+            ..Default::default()
+        };
+        let blocks: Vec<_> = template.placed_blocks(&settings).collect();
+        assert_eq!(blocks[1].pos, Vector3::new(-1, 0, 0));
+        assert_eq!(prop(&blocks[1], "facing"), "west");
+        assert_eq!(prop(&blocks[1], "shape"), "inner_right");
+    }
+
+    #[test]
+    fn entities_are_rotated_with_the_structure() {
+        let mut armor_stand = NbtCompound::new();
+        armor_stand.put_string("id", "minecraft:armor_stand".to_string());
+        armor_stand.put_list(
+            "Rotation",
+            vec![NbtTag::Float(-90.0), NbtTag::Float(0.0)].into_boxed_slice(),
+        );
+        let mut entity = NbtCompound::new();
+        entity.put_list(
+            "pos",
+            vec![
+                NbtTag::Double(1.5),
+                NbtTag::Double(0.0),
+                NbtTag::Double(0.25),
+            ]
+            .into_boxed_slice(),
+        );
+        entity.put("blockPos", int_list([1, 0, 0]));
+        entity.put_component("nbt", armor_stand);
+
+        let mut nbt = NbtCompound::new();
+        nbt.put("size", int_list([2, 1, 1]));
+        nbt.put_list("palette", vec![].into_boxed_slice());
+        nbt.put_list(
+            "entities",
+            vec![NbtTag::Compound(entity)].into_boxed_slice(),
+        );
+        let bytes = write_gzip_compound_tag_to_bytes(&nbt).unwrap();
+        let template = StructureTemplate::read(&bytes[..]).unwrap();
+        assert_eq!(template.entities[0].id(), Some("minecraft:armor_stand"));
+
+        let settings = StructurePlaceSettings {
+            rotation: BlockRotation::Clockwise90,
+            ..Default::default()
+        };
+        let entity = template.placed_entities(&settings).next().unwrap();
+        // Like the stairs, east of the origin turns into south, still facing away from it
+        assert_eq!(entity.pos, Vector3::new(0.75, 0.0, 1.5));
+        assert_eq!(entity.yaw, 0.0);
+
+        let settings = StructurePlaceSettings {
+            mirror: BlockMirror::FrontBack,
+            ..Default::default()
+        };
+        let entity = template.placed_entities(&settings).next().unwrap();
+        assert_eq!(entity.pos, Vector3::new(-0.5, 0.0, 0.25));
+        assert_eq!(entity.yaw, 90.0);
+    }
+
+    #[test]
+    fn integrity_removes_blocks_deterministically() {
+        let template = StructureTemplate::read(&stair_structure()[..]).unwrap();
+        let settings = StructurePlaceSettings {
+            integrity: 0.0,
+            seed: 42,
+            ..Default::default()
+        };
+        assert_eq!(template.placed_blocks(&settings).count(), 0);
+
+        let settings = StructurePlaceSettings {
+            integrity: 0.5,
+            seed: 42,
+            ..Default::default()
+        };
+        let first: Vec<_> = template.placed_blocks(&settings).map(|b| b.pos).collect();
+        let second: Vec<_> = template.placed_blocks(&settings).map(|b| b.pos).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn structure_files_stay_in_the_generated_folder() {
+        let world = Path::new("world");
+        assert_eq!(
+            structure_file(world, "village/house"),
+            Some(world.join("generated/minecraft/structures/village/house.nbt"))
+        );
+        assert_eq!(
+            structure_file(world, "maps:arena"),
+            Some(world.join("generated/maps/structures/arena.nbt"))
+        );
+        assert_eq!(structure_file(world, "../../etc/passwd"), None);
+        assert_eq!(structure_file(world, "maps:Arena"), None);
+        assert_eq!(structure_file(world, "maps:"), None);
+    }
+
+    #[test]
+    fn missing_palette_entry_is_an_error() {
+        let mut nbt = NbtCompound::new();
+        nbt.put("size", int_list([1, 1, 1]));
+        nbt.put_list("palette", vec![].into_boxed_slice());
+        nbt.put_list("blocks", vec![block(3, [0, 0, 0])].into_boxed_slice());
+        let bytes = write_gzip_compound_tag_to_bytes(&nbt).unwrap();
+        assert!(matches!(
+            StructureTemplate::read(&bytes[..]),
+            Err(StructureError::MissingPaletteEntry { index: 0, state: 3 })
+        ));
+    }
+}
@@ -0,0 +1,265 @@
+use pumpkin_data::Block;
+use pumpkin_util::math::vector3::Vector3;
+
+use crate::BlockStateId;
+
+/// Clockwise rotation around the Y axis, as used by structure blocks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlockRotation {
+    #[default]
+    None,
+    Clockwise90,
+    Clockwise180,
+    CounterClockwise90,
+}
+
+impl BlockRotation {
+    pub const ALL: [Self; 4] = [
+        Self::None,
+        Self::Clockwise90,
+        Self::Clockwise180,
+        Self::CounterClockwise90,
+    ];
+
+    /// The amount of clockwise quarter turns.
+    pub const fn quarter_turns(self) -> u8 {
+        match self {
+            Self::None => 0,
+            Self::Clockwise90 => 1,
+            Self::Clockwise180 => 2,
+            Self::CounterClockwise90 => 3,
+        }
+    }
+
+    /// Rotates a position relative to the origin of a structure.
+    pub const fn rotate_pos(self, pos: Vector3<i32>) -> Vector3<i32> {
+        match self {
+            Self::None => pos,
+            Self::Clockwise90 => Vector3::new(-pos.z, pos.y, pos.x),
+            Self::Clockwise180 => Vector3::new(-pos.x, pos.y, -pos.z),
+            Self::CounterClockwise90 => Vector3::new(pos.z, pos.y, -pos.x),
+        }
+    }
+
+    /// Rotates an entity position relative to the origin of a structure. Unlike
+    /// [`Self::rotate_pos`] this keeps the position inside its rotated block.
+    pub fn rotate_vec(self, pos: Vector3<f64>) -> Vector3<f64> {
+        match self {
+            Self::None => pos,
+            Self::Clockwise90 => Vector3::new(1.0 - pos.z, pos.y, pos.x),
+            Self::Clockwise180 => Vector3::new(1.0 - pos.x, pos.y, 1.0 - pos.z),
+            Self::CounterClockwise90 => Vector3::new(pos.z, pos.y, 1.0 - pos.x),
+        }
+    }
+
+    /// Rotates the yaw of an entity, in degrees.
+    pub fn rotate_yaw(self, yaw: f32) -> f32 {
+        (yaw + f32::from(self.quarter_turns()) * 90.0) % 360.0
+    }
+
+    /// Rotates one of the 16 rotation steps used by signs, banners and skulls.
+    pub const fn rotate_step(self, step: u8) -> u8 {
+        (step + self.quarter_turns() * 4) % 16
+    }
+
+    fn rotate_direction(self, direction: &str) -> Option<&'static str> {
+        let mut direction = horizontal_direction(direction)?;
+        for _ in 0..self.quarter_turns() {
+            direction = match direction {
+                "north" => "east",
+                "east" => "south",
+                "south" => "west",
+                _ => "north",
+            };
+        }
+        Some(direction)
+    }
+}
+
+/// Mirroring of a structure, named after the structure block's options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlockMirror {
+    #[default]
+    None,
+    /// Flips the Z axis.
+    LeftRight,
+    /// Flips the X axis.
+    FrontBack,
+}
+
+impl BlockMirror {
+    pub const fn mirror_pos(self, pos: Vector3<i32>) -> Vector3<i32> {
+        match self {
+            Self::None => pos,
+            Self::LeftRight => Vector3::new(pos.x, pos.y, -pos.z),
+            Self::FrontBack => Vector3::new(-pos.x, pos.y, pos.z),
+        }
+    }
+
+    /// Mirrors an entity position, keeping it inside its mirrored block.
+    pub fn mirror_vec(self, pos: Vector3<f64>) -> Vector3<f64> {
+        match self {
+            Self::None => pos,
+            Self::LeftRight => Vector3::new(pos.x, pos.y, 1.0 - pos.z),
+            Self::FrontBack => Vector3::new(1.0 - pos.x, pos.y, pos.z),
+        }
+    }
+
+    /// Mirrors the yaw of an entity, in degrees, where 0 is south.
+    pub fn mirror_yaw(self, yaw: f32) -> f32 {
+        match self {
+            Self::None => yaw,
+            Self::LeftRight => 180.0 - yaw,
+            Self::FrontBack => -yaw,
+        }
+    }
+
+    /// Mirrors one of the 16 rotation steps used by signs, banners and skulls, where 0 is south.
+    pub const fn mirror_step(self, step: u8) -> u8 {
+        match self {
+            Self::None => step,
+            Self::LeftRight => (24 - step) % 16,
+            Self::FrontBack => (16 - step) % 16,
+        }
+    }
+
+    fn mirror_direction(self, direction: &str) -> Option<&'static str> {
+        let direction = horizontal_direction(direction)?;
+        Some(match (self, direction) {
+            (Self::LeftRight, "north") => "south",
+            (Self::LeftRight, "south") => "north",
+            (Self::FrontBack, "east") => "west",
+            (Self::FrontBack, "west") => "east",
+            _ => direction,
+        })
+    }
+}
+
+fn horizontal_direction(direction: &str) -> Option<&'static str> {
+    ["north", "east", "south", "west"]
+        .into_iter()
+        .find(|horizontal| *horizontal == direction)
+}
+
+/// A single step of transforming the properties of a block state.
+#[derive(Clone, Copy)]
+enum Transformer {
+    Mirror(BlockMirror),
+    Rotate(BlockRotation),
+}
+
+impl Transformer {
+    fn direction(self, direction: &str) -> Option<&'static str> {
+        match self {
+            Self::Mirror(mirror) => mirror.mirror_direction(direction),
+            Self::Rotate(rotation) => rotation.rotate_direction(direction),
+        }
+    }
+
+    fn transform(self, props: &mut [(String, String)]) {
+        // Connections to the neighbours (fences, walls, redstone...) move with their direction
+        let connections: Vec<_> = props
+            .iter()
+            .filter(|(key, _)| horizontal_direction(key).is_some())
+            .map(|(key, value)| (self.direction(key).unwrap(), value.clone()))
+            .collect();
+        for (key, value) in props.iter_mut() {
+            if let Some((_, connection)) = connections.iter().find(|(to, _)| *to == key.as_str()) {
+                value.clone_from(connection);
+                continue;
+            }
+            let transformed = match key.as_str() {
+                "facing" => self.direction(value).map(str::to_string),
+                "rotation" => value.parse::<u8>().ok().map(|step| {
+                    match self {
+                        Self::Mirror(mirror) => mirror.mirror_step(step),
+                        Self::Rotate(rotation) => rotation.rotate_step(step),
+                    }
+                    .to_string()
+                }),
+                "axis" => match self {
+                    Self::Rotate(rotation) if rotation.quarter_turns() % 2 == 1 => {
+                        match value.as_str() {
+                            "x" => Some("z".to_string()),
+                            "z" => Some("x".to_string()),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                },
+                // Stairs and rails
+                "shape" => self.shape(value),
+                // Doors and chests
+                "hinge" | "type" => match self {
+                    Self::Mirror(BlockMirror::None) | Self::Rotate(_) => None,
+                    Self::Mirror(_) => match value.as_str() {
+                        "left" => Some("right".to_string()),
+                        "right" => Some("left".to_string()),
+                        _ => None,
+                    },
+                },
+                _ => None,
+            };
+            if let Some(transformed) = transformed {
+                *value = transformed;
+            }
+        }
+    }
+
+    fn shape(self, shape: &str) -> Option<String> {
+        if let Some(slope) = shape.strip_prefix("ascending_") {
+            return Some(format!("ascending_{}", self.direction(slope)?));
+        }
+        if let Some((first, second)) = shape.split_once('_') {
+            if let (Some(first), Some(second)) = (self.direction(first), self.direction(second)) {
+                // Rails name the north/south part first, and straight rails north or east first
+                let is_z = |direction: &str| direction == "north" || direction == "south";
+                let (first, second) = match (first, second) {
+                    ("south", "north") | ("west", "east") => (second, first),
+                    (first, second) if !is_z(first) && is_z(second) => (second, first),
+                    pair => pair,
+                };
+                return Some(format!("{first}_{second}"));
+            }
+            if let Self::Mirror(BlockMirror::LeftRight | BlockMirror::FrontBack) = self {
+                return match second {
+                    "left" => Some(format!("{first}_right")),
+                    "right" => Some(format!("{first}_left")),
+                    _ => None,
+                };
+            }
+        }
+        None
+    }
+}
+
+/// Mirrors and then rotates a block state, like a structure block does when loading.
+pub fn transform_state(
+    state_id: BlockStateId,
+    mirror: BlockMirror,
+    rotation: BlockRotation,
+) -> BlockStateId {
+    if mirror == BlockMirror::None && rotation == BlockRotation::None {
+        return state_id;
+    }
+    let Some(block) = Block::from_state_id(state_id) else {
+        return state_id;
+    };
+    let Some(properties) = block.properties(state_id) else {
+        return state_id;
+    };
+    let mut props = properties.to_props();
+    if mirror != BlockMirror::None {
+        Transformer::Mirror(mirror).transform(&mut props);
+    }
+    if rotation != BlockRotation::None {
+        Transformer::Rotate(rotation).transform(&mut props);
+    }
+    let props = props
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect();
+    block
+        .from_properties(props)
+        .map_or(state_id, |properties| properties.to_state_id(&block))
+}
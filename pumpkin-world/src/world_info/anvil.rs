@@ -123,7 +123,9 @@ mod test {
     use crate::{
         global_path,
         level::LevelFolder,
-        world_info::{DataPacks, LevelData, WorldGenSettings, WorldInfoError, WorldVersion},
+        world_info::{
+            DataPacks, GameRules, LevelData, WorldGenSettings, WorldInfoError, WorldVersion,
+        },
     };
 
     use super::{AnvilLevelInfo, LEVEL_DAT_FILE_NAME, LevelDat, WorldInfoReader, WorldInfoWriter};
@@ -173,6 +175,11 @@ mod test {
             },
             data_version: 4189,
             day_time: 1727,
+            time: 1727,
+            game_rules: GameRules {
+                do_daylight_cycle: true,
+                announce_advancements: true,
+            },
             difficulty: Difficulty::Normal,
             difficulty_locked: false,
             world_gen_settings: WorldGenSettings { seed: 1 },
@@ -1,5 +1,5 @@
 use pumpkin_config::BASIC_CONFIG;
-use pumpkin_util::{Difficulty, serde_enum_as_integer};
+use pumpkin_util::{Difficulty, math::position::BlockPos, serde_enum_as_integer};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -11,11 +11,11 @@ pub mod anvil;
 pub const MINIMUM_SUPPORTED_WORLD_DATA_VERSION: i32 = 4325; // 1.21.5
 pub const MAXIMUM_SUPPORTED_WORLD_DATA_VERSION: i32 = 4325; // 1.21.5
 
-pub(crate) trait WorldInfoReader {
+pub trait WorldInfoReader {
     fn read_world_info(&self, level_folder: &LevelFolder) -> Result<LevelData, WorldInfoError>;
 }
 
-pub(crate) trait WorldInfoWriter: Sync + Send {
+pub trait WorldInfoWriter: Sync + Send {
     fn write_world_info(
         &self,
         info: LevelData,
@@ -58,6 +58,12 @@ pub struct LevelData {
     pub data_version: i32,
     // The time of day. 0 is sunrise, 6000 is mid day, 12000 is sunset, 18000 is mid night, 24000 is the next day's 0. This value keeps counting past 24000 and does not reset to 0.
     pub day_time: i64,
+    // The number of ticks since the level was created. Unlike the time of day, it can't be changed and keeps counting while the daylight cycle is stopped.
+    #[serde(default)]
+    pub time: i64,
+    // The game rules of the level.
+    #[serde(default)]
+    pub game_rules: GameRules,
     // The current difficulty setting.
     #[serde(with = "serde_enum_as_integer")]
     pub difficulty: Difficulty,
@@ -87,12 +93,64 @@ pub struct LevelData {
     // TODO: Implement the rest of the fields
 }
 
+impl LevelData {
+    /// The world spawn and the yaw players face there.
+    pub fn spawn_point(&self) -> (BlockPos, f32) {
+        (
+            BlockPos::new(self.spawn_x, self.spawn_y, self.spawn_z),
+            self.spawn_angle,
+        )
+    }
+
+    pub fn set_spawn_point(&mut self, pos: BlockPos, angle: f32) {
+        self.spawn_x = pos.0.x;
+        self.spawn_y = pos.0.y;
+        self.spawn_z = pos.0.z;
+        self.spawn_angle = angle;
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct WorldGenSettings {
     // the numerical seed of the world
     pub seed: i64,
 }
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameRules {
+    // Whether the time of day advances. Like all game rules, it is stored as a string.
+    #[serde(with = "bool_as_string")]
+    pub do_daylight_cycle: bool,
+    // Whether players making an advancement is announced in chat
+    #[serde(with = "bool_as_string")]
+    pub announce_advancements: bool,
+    // TODO: Implement the rest of the game rules
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        Self {
+            do_daylight_cycle: true,
+            announce_advancements: true,
+        }
+    }
+}
+
+mod bool_as_string {
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(value: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(if *value { "true" } else { "false" })
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 #[serde(rename_all = "PascalCase")]
 pub struct DataPacks {
@@ -159,6 +217,8 @@ impl Default for LevelData {
             },
             data_version: MAXIMUM_SUPPORTED_WORLD_DATA_VERSION,
             day_time: 0,
+            time: 0,
+            game_rules: GameRules::default(),
             difficulty: Difficulty::Normal,
             difficulty_locked: false,
             world_gen_settings: Default::default(),
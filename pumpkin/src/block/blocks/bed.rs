@@ -7,13 +7,14 @@ use pumpkin_data::tag::RegistryKey;
 use pumpkin_data::tag::get_tag_values;
 use pumpkin_util::GameMode;
 use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::text::TextComponent;
 use pumpkin_world::BlockStateId;
 use pumpkin_world::block::BlockDirection;
 use std::sync::Arc;
 
 use crate::block::BlockIsReplacing;
 use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
-use crate::entity::player::Player;
+use crate::entity::player::{Player, RespawnPoint};
 use crate::world::BlockFlags;
 use pumpkin_protocol::server::play::SUseItemOn;
 
@@ -129,12 +130,40 @@ impl PumpkinBlock for BedBlock {
 
     async fn normal_use(
         &self,
-        _block: &Block,
-        _player: &Player,
-        _location: BlockPos,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
         _server: &Server,
-        _world: &Arc<World>,
+        world: &Arc<World>,
     ) {
-        // Sleep
+        // TODO: Sleep
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return;
+        };
+        let bed_props = BedProperties::from_state_id(state_id, block);
+        let head_pos = if bed_props.part == BedPart::Head {
+            location
+        } else {
+            location.offset(bed_props.facing.to_offset())
+        };
+
+        let respawn_point = RespawnPoint {
+            dimension: world.dimension_type.clone(),
+            position: head_pos,
+            angle: player.living_entity.entity.yaw.load(),
+            forced: false,
+        };
+        let mut current = player.respawn_point.lock().await;
+        let moved = current.as_ref().is_none_or(|current| {
+            current.dimension != respawn_point.dimension
+                || current.position != respawn_point.position
+        });
+        *current = Some(respawn_point);
+        drop(current);
+        if moved {
+            player
+                .send_system_message(&TextComponent::translate("block.minecraft.set_spawn", []))
+                .await;
+        }
     }
 }
@@ -0,0 +1,260 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_data::block_properties::{Attachment, BellLikeProperties, BlockProperties};
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_data::tag::{RegistryKey, get_tag_values};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::client::play::CBlockAction;
+use pumpkin_protocol::codec::var_int::VarInt;
+use pumpkin_protocol::server::play::SUseItemOn;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::block::BlockDirection;
+use pumpkin_world::block::entities::BlockEntity;
+use pumpkin_world::block::entities::bell::{
+    BellBlockEntity, HIGHLIGHT_RANGE, HIGHLIGHT_TICKS, RING_TICKS,
+};
+use pumpkin_world::chunk::TickPriority;
+
+use crate::block::BlockIsReplacing;
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::block::registry::BlockActionResult;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::{BlockFlags, World};
+
+use super::redstone::block_receives_redstone_power;
+
+/// Bells are loud, they can be heard from far away.
+const RING_VOLUME: f32 = 2.0;
+
+#[pumpkin_block("minecraft:bell")]
+pub struct BellBlock;
+
+impl BellBlock {
+    /// Reads back the state we stored in the block entity when the bell was last rung.
+    async fn current_state(world: &World, pos: &BlockPos) -> BellBlockEntity {
+        let Some(block_entity) = world.get_block_entity(pos).await else {
+            return BellBlockEntity::new(*pos);
+        };
+        let mut nbt = NbtCompound::new();
+        block_entity.write_nbt(&mut nbt);
+        BellBlockEntity::from_nbt(&nbt, *pos)
+    }
+
+    /// Swings the bell away from the side it was hit on and plays its sound.
+    async fn ring(world: &Arc<World>, block: &Block, pos: BlockPos, direction: BlockDirection) {
+        let bell = BellBlockEntity::ringing(pos, direction);
+        if let Some((action, param)) = bell.ring_action() {
+            world
+                .broadcast_packet_all(&CBlockAction::new(
+                    pos,
+                    action,
+                    param,
+                    VarInt(block.id.into()),
+                ))
+                .await;
+        }
+        world
+            .play_sound_raw(
+                Sound::BlockBellUse as u16,
+                SoundCategory::Blocks,
+                &pos.to_f64(),
+                RING_VOLUME,
+                1.0,
+            )
+            .await;
+        world.add_block_entity(Arc::new(bell)).await;
+        world
+            .schedule_block_tick(block, pos, RING_TICKS, TickPriority::Normal)
+            .await;
+    }
+
+    /// Makes all raiders near the bell glow, returns whether there were any.
+    async fn highlight_raiders(world: &World, pos: &BlockPos, glowing: bool) -> bool {
+        let raiders = get_tag_values(RegistryKey::EntityType, "minecraft:raiders").unwrap_or(&[]);
+        let center = pos.to_f64();
+        let entities: Vec<_> = world.entities.read().await.values().cloned().collect();
+        let mut found = false;
+        for entity in entities {
+            let base = entity.get_entity();
+            if !raiders.contains(&base.entity_type.resource_name)
+                || base.pos.load().squared_distance_to_vec(center)
+                    > HIGHLIGHT_RANGE * HIGHLIGHT_RANGE
+            {
+                continue;
+            }
+            base.set_glowing(glowing).await;
+            found = true;
+        }
+        found
+    }
+}
+
+/// The side of the bell a player hits, which is the one facing them.
+fn hit_side(player: &Player) -> BlockDirection {
+    BlockDirection::from_cardinal_direction(player.living_entity.entity.get_horizontal_facing())
+        .opposite()
+}
+
+#[async_trait]
+impl PumpkinBlock for BellBlock {
+    async fn on_place(
+        &self,
+        _server: &Server,
+        world: &World,
+        player: &Player,
+        block: &Block,
+        block_pos: &BlockPos,
+        face: BlockDirection,
+        _replacing: BlockIsReplacing,
+        _use_item_on: &SUseItemOn,
+    ) -> BlockStateId {
+        let mut props = BellLikeProperties::default(block);
+        match face {
+            BlockDirection::Up => props.attachment = Attachment::Ceiling,
+            BlockDirection::Down => props.attachment = Attachment::Floor,
+            _ => props.attachment = Attachment::SingleWall,
+        }
+        props.facing = if face.is_horizontal() {
+            face.opposite().to_cardinal_direction()
+        } else {
+            player.living_entity.entity.get_horizontal_facing()
+        };
+        props.powered = block_receives_redstone_power(world, block_pos).await;
+        props.to_state_id(block)
+    }
+
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        world: &Arc<World>,
+    ) {
+        Self::ring(world, block, location, hit_side(player)).await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        _server: &Server,
+        world: &Arc<World>,
+    ) -> BlockActionResult {
+        Self::ring(world, block, location, hit_side(player)).await;
+        BlockActionResult::Consume
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        world: &Arc<World>,
+        block: &Block,
+        block_pos: &BlockPos,
+        _source_block: &Block,
+        _notify: bool,
+    ) {
+        let state = world.get_block_state(block_pos).await.unwrap();
+        let mut props = BellLikeProperties::from_state_id(state.id, block);
+        let is_receiving_power = block_receives_redstone_power(world, block_pos).await;
+        if props.powered == is_receiving_power {
+            return;
+        }
+
+        props.powered = is_receiving_power;
+        world
+            .set_block_state(
+                block_pos,
+                props.to_state_id(block),
+                BlockFlags::NOTIFY_LISTENERS,
+            )
+            .await;
+        // Only the rising edge rings the bell
+        if is_receiving_power {
+            let direction = BlockDirection::from_cardinal_direction(props.facing);
+            Self::ring(world, block, *block_pos, direction).await;
+        }
+    }
+
+    async fn on_scheduled_tick(&self, world: &Arc<World>, block: &Block, pos: &BlockPos) {
+        let mut bell = Self::current_state(world, pos).await;
+        if bell.is_ringing() {
+            // The bell stopped swinging, now point out any raiders nearby
+            bell.ring_direction = None;
+            bell.highlighting = Self::highlight_raiders(world, pos, true).await;
+            if bell.highlighting {
+                world
+                    .schedule_block_tick(block, *pos, HIGHLIGHT_TICKS, TickPriority::Normal)
+                    .await;
+            }
+        } else if bell.highlighting {
+            Self::highlight_raiders(world, pos, false).await;
+            bell.highlighting = false;
+        }
+        world.add_block_entity(Arc::new(bell)).await;
+    }
+
+    async fn on_state_replaced(
+        &self,
+        world: &Arc<World>,
+        _block: &Block,
+        location: BlockPos,
+        _old_state_id: BlockStateId,
+        _moved: bool,
+    ) {
+        world.remove_block_entity(&location).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::packet::clientbound::{PLAY_BLOCK_EVENT, PLAY_SOUND};
+    use pumpkin_data::sound::{Sound, SoundCategory};
+    use pumpkin_protocol::codec::var_int::VarInt;
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_world::block::BlockDirection;
+    use pumpkin_world::block::entities::bell::{BellBlockEntity, RING_ACTION};
+
+    use super::BellBlock;
+    use crate::test_utils::{test_player, test_server};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ringing_plays_bell_sound_towards_hit_direction() {
+        let server = test_server();
+        let world = server.worlds.read().await[0].clone();
+        let (_alice, mut packets) = test_player(&server, "alice").await;
+
+        BellBlock::ring(
+            &world,
+            &Block::BELL,
+            BlockPos::new(0, 64, 0),
+            BlockDirection::South,
+        )
+        .await;
+        let (action, _) = packets.try_recv().unwrap();
+        assert_eq!(i32::from(action[0]), PLAY_BLOCK_EVENT);
+        let (sound, _) = packets.try_recv().unwrap();
+        assert_eq!(i32::from(sound[0]), PLAY_SOUND);
+        let mut expected = Vec::new();
+        VarInt(Sound::BlockBellUse as i32 + 1)
+            .encode(&mut expected)
+            .unwrap();
+        expected.push(SoundCategory::Blocks as u8);
+        assert_eq!(&sound[1..=expected.len()], &expected[..]);
+
+        let bell = BellBlockEntity::ringing(BlockPos::new(0, 64, 0), BlockDirection::South);
+        assert_eq!(bell.ring_direction, Some(BlockDirection::South));
+        assert_eq!(
+            bell.ring_action(),
+            Some((RING_ACTION, BlockDirection::South as u8))
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_data::block_properties::{BlockProperties, BubbleColumnLikeProperties};
+use pumpkin_data::entity::EntityType;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::block::BlockDirection;
+use pumpkin_world::chunk::TickPriority;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::EntityBase;
+use crate::world::{BlockFlags, World};
+
+/// How many ticks after water appears above a magma block or soul sand its column forms. Vanilla: 20
+pub const COLUMN_SOURCE_DELAY: u16 = 20;
+/// How many ticks after its source changed a column follows. Vanilla: 5
+const COLUMN_UPDATE_DELAY: u16 = 5;
+
+/// The vertical velocity of an entity in a bubble column after one tick. Columns above magma
+/// blocks drag entities down, columns above soul sand push them up, both stronger at the surface.
+pub fn bubble_velocity(velocity_y: f64, drag: bool, at_surface: bool) -> f64 {
+    match (drag, at_surface) {
+        (true, true) => (velocity_y - 0.03).max(-0.9),
+        (true, false) => (velocity_y - 0.03).max(-0.3),
+        (false, true) => (velocity_y + 0.1).min(1.8),
+        (false, false) => (velocity_y + 0.06).min(0.7),
+    }
+}
+
+/// The bubble column a block makes above it, `None` if it makes none.
+fn column_above(block: &Block, state_id: BlockStateId) -> Option<BlockStateId> {
+    let drag = if *block == Block::MAGMA_BLOCK {
+        true
+    } else if *block == Block::SOUL_SAND {
+        false
+    } else if *block == Block::BUBBLE_COLUMN {
+        return Some(state_id);
+    } else {
+        return None;
+    };
+    Some(BubbleColumnLikeProperties { drag }.to_state_id(&Block::BUBBLE_COLUMN))
+}
+
+/// Only water sources and other columns can hold a bubble column.
+fn can_hold_column(state_id: BlockStateId) -> bool {
+    state_id == Block::WATER.default_state_id
+        || Block::from_state_id(state_id).is_some_and(|block| block == Block::BUBBLE_COLUMN)
+}
+
+/// Rebuilds the bubble column from `pos` upwards after its source changed, the column turns back
+/// into water if the block below doesn't make bubbles anymore.
+pub async fn update_column(world: &Arc<World>, pos: &BlockPos) {
+    let Ok(state) = world.get_block_state(pos).await else {
+        return;
+    };
+    if !can_hold_column(state.id) {
+        return;
+    }
+    let Ok((below, below_state)) = world.get_block_and_block_state(&pos.down()).await else {
+        return;
+    };
+    let column = column_above(&below, below_state.id).unwrap_or(Block::WATER.default_state_id);
+
+    world
+        .set_block_state(pos, column, BlockFlags::NOTIFY_LISTENERS)
+        .await;
+    let mut pos = pos.up();
+    while let Ok(state) = world.get_block_state(&pos).await {
+        // The rest of the column is already up to date
+        if !can_hold_column(state.id) || state.id == column {
+            break;
+        }
+        world
+            .set_block_state(&pos, column, BlockFlags::NOTIFY_LISTENERS)
+            .await;
+        pos = pos.up();
+    }
+}
+
+/// Schedules forming the column above a magma block or soul sand once water appears above it.
+pub async fn schedule_column_update(
+    world: &World,
+    block: &Block,
+    pos: &BlockPos,
+    direction: BlockDirection,
+    neighbor_state: BlockStateId,
+) {
+    if direction == BlockDirection::Up && neighbor_state == Block::WATER.default_state_id {
+        world
+            .schedule_block_tick(block, *pos, COLUMN_SOURCE_DELAY, TickPriority::Normal)
+            .await;
+    }
+}
+
+#[pumpkin_block("minecraft:bubble_column")]
+pub struct BubbleColumnBlock;
+
+#[async_trait]
+impl PumpkinBlock for BubbleColumnBlock {
+    async fn on_entity_inside(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        pos: &BlockPos,
+        entity: &dyn EntityBase,
+    ) {
+        let base = entity.get_entity();
+        // Players move themselves, their client already applies the column
+        if base.entity_type == EntityType::PLAYER {
+            return;
+        }
+        let Ok(state) = world.get_block_state(pos).await else {
+            return;
+        };
+        let drag = BubbleColumnLikeProperties::from_state_id(state.id, block).drag;
+        let at_surface = world
+            .get_block_state(&pos.up())
+            .await
+            .is_ok_and(|state| state.is_air());
+        let velocity = base.velocity.load();
+        let pushed = Vector3::new(
+            velocity.x,
+            bubble_velocity(velocity.y, drag, at_surface),
+            velocity.z,
+        );
+        // No need to tell the clients about entities already at full speed
+        if pushed != velocity {
+            base.set_velocity(pushed).await;
+        }
+    }
+
+    async fn get_state_for_neighbor_update(
+        &self,
+        world: &World,
+        block: &Block,
+        state: BlockStateId,
+        pos: &BlockPos,
+        direction: BlockDirection,
+        _neighbor_pos: &BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        let column_continues = Block::from_state_id(neighbor_state)
+            .is_some_and(|neighbor| neighbor == Block::BUBBLE_COLUMN);
+        if direction == BlockDirection::Down
+            || (direction == BlockDirection::Up
+                && !column_continues
+                && can_hold_column(neighbor_state))
+        {
+            world
+                .schedule_block_tick(block, *pos, COLUMN_UPDATE_DELAY, TickPriority::Normal)
+                .await;
+        }
+        state
+    }
+
+    async fn on_scheduled_tick(&self, world: &Arc<World>, _block: &Block, pos: &BlockPos) {
+        update_column(world, pos).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+
+    use super::{bubble_velocity, can_hold_column, column_above};
+
+    #[test]
+    fn columns_push_submerged_entities() {
+        // Soul sand lifts a sinking entity, faster at the surface
+        let mut velocity = -0.1;
+        for _ in 0..5 {
+            velocity = bubble_velocity(velocity, false, false);
+        }
+        assert!(velocity > 0.0);
+        assert!((bubble_velocity(0.0, false, true) - 0.1).abs() < 1e-9);
+        assert!((bubble_velocity(0.69, false, false) - 0.7).abs() < 1e-9);
+
+        // Magma drags entities down, but not faster than a limit
+        let mut velocity = 0.2;
+        for _ in 0..100 {
+            velocity = bubble_velocity(velocity, true, false);
+        }
+        assert!((velocity + 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn columns_form_above_magma_and_soul_sand() {
+        let magma = column_above(&Block::MAGMA_BLOCK, Block::MAGMA_BLOCK.default_state_id);
+        let soul_sand = column_above(&Block::SOUL_SAND, Block::SOUL_SAND.default_state_id);
+        assert!(magma.is_some_and(can_hold_column));
+        assert!(soul_sand.is_some_and(can_hold_column));
+        assert_ne!(magma, soul_sand);
+        // A column above a column continues it
+        assert_eq!(column_above(&Block::BUBBLE_COLUMN, magma.unwrap()), magma);
+        assert_eq!(
+            column_above(&Block::STONE, Block::STONE.default_state_id),
+            None
+        );
+        assert!(!can_hold_column(Block::STONE.default_state_id));
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::block_properties::{
+    BlockProperties, EnumVariants, Integer1To3, WaterCauldronLikeProperties,
+};
+use pumpkin_data::item::Item;
+use pumpkin_data::{Block, BlockState};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::block::registry::BlockActionResult;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::{BlockFlags, World};
+
+#[pumpkin_block("minecraft:water_cauldron")]
+pub struct WaterCauldronBlock;
+
+impl WaterCauldronBlock {
+    /// Lowers the water level by one, emptying the cauldron at the lowest level.
+    async fn decrement_level(
+        block: &Block,
+        state: &BlockState,
+        location: BlockPos,
+        world: &Arc<World>,
+    ) {
+        let props = WaterCauldronLikeProperties::from_state_id(state.id, block);
+        let new_state = if props.level == Integer1To3::L1 {
+            Block::CAULDRON.default_state_id
+        } else {
+            WaterCauldronLikeProperties {
+                level: Integer1To3::from_index(props.level.to_index() - 1),
+            }
+            .to_state_id(block)
+        };
+        world
+            .set_block_state(&location, new_state, BlockFlags::NOTIFY_ALL)
+            .await;
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for WaterCauldronBlock {
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        _server: &Server,
+        world: &Arc<World>,
+    ) -> BlockActionResult {
+        let washed = {
+            let mut inventory = player.inventory().lock().await;
+            let Some(stack) = inventory.held_item_mut() else {
+                return BlockActionResult::Continue;
+            };
+            (stack.is_dyeable() || stack.has_banner_patterns()) && stack.wash()
+        };
+        if !washed {
+            return BlockActionResult::Continue;
+        }
+
+        player.send_inventory().await;
+        if let Ok(state) = world.get_block_state(&location).await {
+            Self::decrement_level(block, &state, location, world).await;
+        }
+        BlockActionResult::Consume
+    }
+}
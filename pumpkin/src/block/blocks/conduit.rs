@@ -0,0 +1,229 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_data::damage::DamageType;
+use pumpkin_data::entity::{EffectType, EntityType};
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::BlockStateId;
+use pumpkin_world::block::entities::BlockEntity;
+use pumpkin_world::block::entities::conduit::{
+    ATTACK_RANGE, CONDUIT_FRAME_BLOCKS, ConduitBlockEntity, FULL_FRAME_BLOCKS,
+};
+use pumpkin_world::chunk::TickPriority;
+use uuid::Uuid;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::effect::Effect;
+use crate::world::World;
+
+/// The conduit updates its frame, effects and target every 2 seconds.
+const UPDATE_INTERVAL: u16 = 40;
+/// Long enough to last until the next update, with some slack.
+const EFFECT_DURATION: i32 = 260;
+const ATTACK_DAMAGE: f32 = 4.0;
+
+const HOSTILE_WATER_MOBS: [EntityType; 4] = [
+    EntityType::DROWNED,
+    EntityType::GUARDIAN,
+    EntityType::ELDER_GUARDIAN,
+    EntityType::ZOMBIE,
+];
+
+#[pumpkin_block("minecraft:conduit")]
+pub struct ConduitBlock;
+
+impl ConduitBlock {
+    async fn is_water(world: &World, pos: &BlockPos) -> bool {
+        let Ok(block) = world.get_block(pos).await else {
+            return false;
+        };
+        if block == Block::WATER {
+            return true;
+        }
+        let Ok(state_id) = world.get_block_state_id(pos).await else {
+            return false;
+        };
+        block.properties(state_id).is_some_and(|props| {
+            props
+                .to_props()
+                .iter()
+                .any(|(key, value)| key == "waterlogged" && value == "true")
+        })
+    }
+
+    async fn count_frame_blocks(world: &World, pos: &BlockPos) -> usize {
+        // The lookups are async, so collect them first and let the block entity do the counting
+        let mut water = Vec::new();
+        for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    let offset = Vector3::new(x, y, z);
+                    if Self::is_water(world, &pos.offset(offset)).await {
+                        water.push(offset);
+                    }
+                }
+            }
+        }
+        let mut frame = Vec::new();
+        for offset in ConduitBlockEntity::frame_offsets() {
+            if let Ok(block) = world.get_block(&pos.offset(offset)).await {
+                let name = block.name.strip_prefix("minecraft:").unwrap_or(block.name);
+                if CONDUIT_FRAME_BLOCKS.contains(&name) {
+                    frame.push(offset);
+                }
+            }
+        }
+        ConduitBlockEntity::count_frame_blocks(
+            |offset| water.contains(&offset),
+            |offset| frame.contains(&offset),
+        )
+    }
+
+    /// Reads back the state we stored in the block entity on the last update.
+    async fn previous_state(world: &World, pos: &BlockPos) -> ConduitBlockEntity {
+        let Some(block_entity) = world.get_block_entity(pos).await else {
+            return ConduitBlockEntity::new(*pos);
+        };
+        let mut nbt = NbtCompound::new();
+        block_entity.write_nbt(&mut nbt);
+        ConduitBlockEntity::from_nbt(&nbt, *pos)
+    }
+
+    async fn apply_effects(world: &World, conduit: &ConduitBlockEntity, frame_blocks: usize) {
+        let range = ConduitBlockEntity::effect_range(frame_blocks);
+        let players: Vec<_> = world.players.read().await.values().cloned().collect();
+        for player in players {
+            let position = player.living_entity.entity.pos.load();
+            if !conduit.is_in_effect_range(range, position)
+                || !player.living_entity.is_in_water().await
+            {
+                continue;
+            }
+            player
+                .add_effect(Effect {
+                    r#type: EffectType::ConduitPower,
+                    duration: EFFECT_DURATION,
+                    amplifier: 0,
+                    ambient: true,
+                    show_particles: true,
+                    show_icon: true,
+                    blend: false,
+                })
+                .await;
+        }
+    }
+
+    /// Attacks the closest hostile mob in water, keeping the previous target while it is in range.
+    async fn attack_target(
+        world: &World,
+        pos: &BlockPos,
+        previous_target: Option<Uuid>,
+    ) -> Option<Uuid> {
+        let center = pos.to_f64();
+        let range = f64::from(ATTACK_RANGE);
+        let mut candidates = Vec::new();
+        for (uuid, entity) in world.entities.read().await.iter() {
+            let base = entity.get_entity();
+            if !HOSTILE_WATER_MOBS
+                .iter()
+                .any(|mob| mob.id == base.entity_type.id)
+            {
+                continue;
+            }
+            let distance = base.pos.load().squared_distance_to_vec(center);
+            if distance <= range * range {
+                candidates.push((*uuid, entity.clone(), distance));
+            }
+        }
+
+        let mut in_water = Vec::new();
+        for (uuid, entity, distance) in candidates {
+            let Some(living) = entity.get_living_entity() else {
+                continue;
+            };
+            if living.is_in_water().await {
+                in_water.push((uuid, entity, distance));
+            }
+        }
+
+        let (uuid, target, _) = in_water
+            .iter()
+            .find(|(uuid, _, _)| Some(*uuid) == previous_target)
+            .or_else(|| {
+                in_water
+                    .iter()
+                    .min_by(|a, b| a.2.partial_cmp(&b.2).unwrap_or(std::cmp::Ordering::Equal))
+            })?;
+        target.damage(ATTACK_DAMAGE, DamageType::MAGIC).await;
+        world
+            .play_block_sound(Sound::BlockConduitAttackTarget, SoundCategory::Blocks, *pos)
+            .await;
+        Some(*uuid)
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for ConduitBlock {
+    async fn placed(
+        &self,
+        world: &Arc<World>,
+        block: &Block,
+        _state_id: BlockStateId,
+        pos: &BlockPos,
+        _old_state_id: BlockStateId,
+        _notify: bool,
+    ) {
+        world
+            .add_block_entity(Arc::new(ConduitBlockEntity::new(*pos)))
+            .await;
+        world
+            .schedule_block_tick(block, *pos, UPDATE_INTERVAL, TickPriority::Normal)
+            .await;
+    }
+
+    async fn on_scheduled_tick(&self, world: &Arc<World>, block: &Block, pos: &BlockPos) {
+        let previous = Self::previous_state(world, pos).await;
+        let frame_blocks = Self::count_frame_blocks(world, pos).await;
+        let active = ConduitBlockEntity::is_active_frame(frame_blocks);
+
+        let mut conduit = ConduitBlockEntity::new(*pos);
+        conduit.active = active;
+        if active {
+            Self::apply_effects(world, &conduit, frame_blocks).await;
+            if frame_blocks >= FULL_FRAME_BLOCKS {
+                conduit.target = Self::attack_target(world, pos, previous.target).await;
+            }
+        }
+
+        if active != previous.active {
+            let sound = if active {
+                Sound::BlockConduitActivate
+            } else {
+                Sound::BlockConduitDeactivate
+            };
+            world
+                .play_block_sound(sound, SoundCategory::Blocks, *pos)
+                .await;
+        }
+
+        world.add_block_entity(Arc::new(conduit)).await;
+        world
+            .schedule_block_tick(block, *pos, UPDATE_INTERVAL, TickPriority::Normal)
+            .await;
+    }
+
+    async fn on_state_replaced(
+        &self,
+        world: &Arc<World>,
+        _block: &Block,
+        location: BlockPos,
+        _old_state_id: BlockStateId,
+        _moved: bool,
+    ) {
+        world.remove_block_entity(&location).await;
+    }
+}
@@ -0,0 +1,163 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::item::Item;
+use pumpkin_data::screen::WindowType;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_data::{Block, BlockState};
+use pumpkin_inventory::OpenContainer;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::{client::play::CBlockAction, codec::var_int::VarInt};
+use pumpkin_util::math::position::BlockPos;
+
+use crate::block::registry::BlockActionResult;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::{block::pumpkin_block::PumpkinBlock, world::World};
+
+#[pumpkin_block("minecraft:ender_chest")]
+pub struct EnderChestBlock;
+
+#[async_trait]
+impl PumpkinBlock for EnderChestBlock {
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        _world: &Arc<World>,
+    ) {
+        self.open_ender_chest(block, player, location, server).await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        server: &Server,
+        _world: &Arc<World>,
+    ) -> BlockActionResult {
+        self.open_ender_chest(block, player, location, server).await;
+        BlockActionResult::Consume
+    }
+
+    async fn broken(
+        &self,
+        block: &Block,
+        player: &Arc<Player>,
+        location: BlockPos,
+        server: &Server,
+        _world: Arc<World>,
+        _state: BlockState,
+    ) {
+        // The contents belong to the players, so they are kept when the chest is broken
+        if let Some(all_container_ids) = server.get_all_container_ids(location, block.clone()).await
+        {
+            let mut open_containers = server.open_containers.write().await;
+            for individual_id in all_container_ids {
+                if let Some(container) = open_containers.get_mut(&u64::from(individual_id)) {
+                    super::close_all_in_container(player, container).await;
+                    container.clear_all_players();
+                    container.set_location(None).await;
+                }
+            }
+        }
+    }
+
+    async fn close(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        container: &mut OpenContainer,
+    ) {
+        container.remove_player(player.entity_id());
+        // TODO: Other players looking into the same chest should keep the lid open,
+        // their containers can't be counted here since the open containers are locked.
+        let viewers = container.get_number_of_players();
+        if viewers == 0 {
+            player
+                .world()
+                .await
+                .play_block_sound(Sound::BlockEnderChestClose, SoundCategory::Blocks, location)
+                .await;
+        }
+        play_lid_action(block, location, server, viewers).await;
+    }
+}
+
+impl EnderChestBlock {
+    pub async fn open_ender_chest(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+    ) {
+        let entity_id = player.entity_id();
+        {
+            let mut open_containers = server.open_containers.write().await;
+            let existing_id = open_containers
+                .iter()
+                .find(|(_, container)| container.holds(&player.ender_chest))
+                .map(|(id, _)| *id);
+            if let Some(id) = existing_id {
+                let container = open_containers.get_mut(&id).unwrap();
+                container.add_player(entity_id);
+                container.set_location(Some(location)).await;
+                player.open_container.store(Some(id));
+            } else {
+                let new_id = server.new_container_id();
+                log::debug!("Creating new ender chest container id: {new_id}");
+                open_containers.insert(
+                    new_id.into(),
+                    OpenContainer::with_container(
+                        entity_id,
+                        Some(location),
+                        Some(block.clone()),
+                        player.ender_chest.clone(),
+                    ),
+                );
+                player.open_container.store(Some(new_id.into()));
+            }
+        }
+        player.open_container(server, WindowType::Generic9x3).await;
+
+        let viewers = viewers_at(server, block, location).await;
+        if viewers == 1 {
+            player
+                .world()
+                .await
+                .play_block_sound(Sound::BlockEnderChestOpen, SoundCategory::Blocks, location)
+                .await;
+        }
+        play_lid_action(block, location, server, viewers).await;
+    }
+}
+
+async fn viewers_at(server: &Server, block: &Block, location: BlockPos) -> usize {
+    let Some(container_ids) = server.get_all_container_ids(location, block.clone()).await else {
+        return 0;
+    };
+    let open_containers = server.open_containers.read().await;
+    container_ids
+        .into_iter()
+        .filter_map(|id| open_containers.get(&u64::from(id)))
+        .map(OpenContainer::get_number_of_players)
+        .sum()
+}
+
+async fn play_lid_action(block: &Block, location: BlockPos, server: &Server, viewers: usize) {
+    server
+        .broadcast_packet_all(&CBlockAction::new(
+            location,
+            1,
+            viewers as u8,
+            VarInt(block.id.into()),
+        ))
+        .await;
+}
@@ -0,0 +1,92 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::chunk::TickPriority;
+
+use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
+use crate::entity::falling_block::{FallingBlockEntity, can_fall_through};
+use crate::world::World;
+
+/// How many ticks a block waits before it starts falling.
+const FALL_DELAY: u16 = 2;
+
+/// Blocks affected by gravity, like sand, gravel, anvils and concrete powder.
+pub struct FallingBlock;
+
+impl BlockMetadata for FallingBlock {
+    fn namespace(&self) -> &'static str {
+        "minecraft"
+    }
+
+    fn ids(&self) -> &'static [&'static str] {
+        &[
+            "sand",
+            "red_sand",
+            "gravel",
+            "anvil",
+            "chipped_anvil",
+            "damaged_anvil",
+            "white_concrete_powder",
+            "orange_concrete_powder",
+            "magenta_concrete_powder",
+            "light_blue_concrete_powder",
+            "yellow_concrete_powder",
+            "lime_concrete_powder",
+            "pink_concrete_powder",
+            "gray_concrete_powder",
+            "light_gray_concrete_powder",
+            "cyan_concrete_powder",
+            "purple_concrete_powder",
+            "blue_concrete_powder",
+            "brown_concrete_powder",
+            "green_concrete_powder",
+            "red_concrete_powder",
+            "black_concrete_powder",
+        ]
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for FallingBlock {
+    async fn placed(
+        &self,
+        world: &Arc<World>,
+        block: &Block,
+        _state_id: BlockStateId,
+        pos: &BlockPos,
+        _old_state_id: BlockStateId,
+        _notify: bool,
+    ) {
+        world
+            .schedule_block_tick(block, *pos, FALL_DELAY, TickPriority::Normal)
+            .await;
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        world: &Arc<World>,
+        block: &Block,
+        pos: &BlockPos,
+        _source_block: &Block,
+        _notify: bool,
+    ) {
+        world
+            .schedule_block_tick(block, *pos, FALL_DELAY, TickPriority::Normal)
+            .await;
+    }
+
+    async fn on_scheduled_tick(&self, world: &Arc<World>, _block: &Block, pos: &BlockPos) {
+        let Ok((below, below_state)) = world.get_block_and_block_state(&pos.down()).await else {
+            return;
+        };
+        if !can_fall_through(&below, &below_state) {
+            return;
+        }
+        if let Ok(state_id) = world.get_block_state_id(pos).await {
+            FallingBlockEntity::spawn_from_block(world, pos, state_id).await;
+        }
+    }
+}
@@ -1,9 +1,13 @@
+use std::sync::Arc;
+
 use crate::block::BlockIsReplacing;
+use crate::block::registry::BlockActionResult;
 use crate::entity::player::Player;
 use async_trait::async_trait;
 use pumpkin_data::Block;
 use pumpkin_data::BlockState;
 use pumpkin_data::block_properties::BlockProperties;
+use pumpkin_data::item::Item;
 use pumpkin_data::tag::RegistryKey;
 use pumpkin_data::tag::Tagable;
 use pumpkin_data::tag::get_tag_values;
@@ -62,6 +66,39 @@ impl PumpkinBlock for FenceBlock {
         let fence_props = FenceProperties::from_state_id(state_id, block);
         compute_fence_state(fence_props, world, block, block_pos).await
     }
+
+    async fn normal_use(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        world: &Arc<World>,
+    ) {
+        world
+            .tie_leashes_to_fence(player.entity_id(), location)
+            .await;
+    }
+
+    async fn use_with_item(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        _server: &Server,
+        world: &Arc<World>,
+    ) -> BlockActionResult {
+        // Players tie their leashed mobs to the fence no matter what they hold
+        if world
+            .tie_leashes_to_fence(player.entity_id(), location)
+            .await
+        {
+            BlockActionResult::Consume
+        } else {
+            BlockActionResult::Continue
+        }
+    }
 }
 
 pub async fn compute_fence_state(
@@ -45,9 +45,9 @@ impl PumpkinBlock for FireBlock {
             return;
         }
 
-        let dimension = world.dimension_type;
+        let dimension = &world.dimension_type;
         // First lets check if we are in OverWorld or Nether, its not possible to place an Nether portal in other dimensions in Vanilla
-        if dimension == DimensionType::Overworld || dimension == DimensionType::TheNether {
+        if *dimension == DimensionType::Overworld || *dimension == DimensionType::TheNether {
             if let Some(portal) = NetherPortal::get_new_portal(world, pos, HorizontalAxis::X).await
             {
                 portal.create(world).await;
@@ -9,12 +9,17 @@ use async_trait::async_trait;
 use pumpkin_data::item::Item;
 use pumpkin_data::world::WorldEvent;
 use pumpkin_data::{
-    Block, BlockState,
+    Block,
     block_properties::{BlockProperties, JukeboxLikeProperties},
 };
 use pumpkin_macros::pumpkin_block;
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_registry::SYNCED_REGISTRIES;
+use pumpkin_util::GameMode;
 use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::block::entities::jukebox::JukeboxBlockEntity;
+use pumpkin_world::item::ItemStack;
 
 #[pumpkin_block("minecraft:jukebox")]
 pub struct JukeboxBlock;
@@ -48,6 +53,52 @@ impl JukeboxBlock {
             .sync_world_event(WorldEvent::JukeboxStopsPlaying, position, 0)
             .await;
     }
+
+    /// Stops the music and pops the disc out of the top of the jukebox.
+    async fn eject_record(&self, block: &Block, position: BlockPos, world: &Arc<World>) {
+        if let Some(record) = take_record(world, position).await {
+            crate::block::drop_stack(world, &position.up(), record).await;
+        }
+        self.stop_music(block, position, world).await;
+    }
+}
+
+/// The world event and its data which make clients play the song of the given disc.
+fn disc_inserted_event(item: &Item) -> Option<(WorldEvent, i32)> {
+    let jukebox_playable = item.components.jukebox_playable.as_ref()?;
+    let song = jukebox_playable.split(':').nth(1)?;
+    let Some(jukebox_song) = SYNCED_REGISTRIES.jukebox_song.get_index_of(song) else {
+        log::error!("Jukebox playable song not registered!");
+        return None;
+    };
+    Some((WorldEvent::JukeboxStartsPlaying, jukebox_song as i32))
+}
+
+/// The comparator signal of a jukebox playing the given disc.
+fn disc_comparator_output(item: &Item) -> u8 {
+    item.components
+        .jukebox_playable
+        .as_ref()
+        .and_then(|jukebox_playable| jukebox_playable.split(':').nth(1))
+        .and_then(|song| SYNCED_REGISTRIES.jukebox_song.get(song))
+        .map_or(0, |song| song.comparator_output().clamp(0, 15) as u8)
+}
+
+/// The music disc in the jukebox at `position`, leaving it in place.
+async fn current_record(world: &World, position: BlockPos) -> Option<ItemStack> {
+    let block_entity = world.get_block_entity(&position).await?;
+    if block_entity.identifier() != JukeboxBlockEntity::ID {
+        return None;
+    }
+    let mut nbt = NbtCompound::new();
+    block_entity.write_nbt(&mut nbt);
+    JukeboxBlockEntity::read_record(&nbt)
+}
+
+async fn take_record(world: &World, position: BlockPos) -> Option<ItemStack> {
+    let record = current_record(world, position).await?;
+    world.remove_block_entity(&position).await;
+    Some(record)
 }
 
 #[async_trait]
@@ -55,14 +106,14 @@ impl PumpkinBlock for JukeboxBlock {
     async fn normal_use(
         &self,
         block: &Block,
-        player: &Player,
+        _player: &Player,
         location: BlockPos,
         _server: &Server,
-        _world: &Arc<World>,
+        world: &Arc<World>,
     ) {
-        // For now just stop the music at this position
-        let world = &player.living_entity.entity.world.read().await;
-        self.stop_music(block, location, world).await;
+        if self.has_record(block, location, world).await {
+            self.eject_record(block, location, world).await;
+        }
     }
 
     async fn use_with_item(
@@ -72,55 +123,91 @@ impl PumpkinBlock for JukeboxBlock {
         location: BlockPos,
         item: &Item,
         _server: &Server,
-        _world: &Arc<World>,
+        world: &Arc<World>,
     ) -> BlockActionResult {
-        let world = &player.living_entity.entity.world.read().await;
-
         // if the jukebox already has a record, stop playing
         if self.has_record(block, location, world).await {
-            self.stop_music(block, location, world).await;
+            self.eject_record(block, location, world).await;
             return BlockActionResult::Consume;
         }
 
-        let Some(jukebox_playable) = &item.components.jukebox_playable else {
-            return BlockActionResult::Continue;
-        };
-
-        let Some(song) = jukebox_playable.split(':').nth(1) else {
+        let Some((event, song)) = disc_inserted_event(item) else {
             return BlockActionResult::Continue;
         };
 
-        let Some(jukebox_song) = SYNCED_REGISTRIES.jukebox_song.get_index_of(song) else {
-            log::error!("Jukebox playable song not registered!");
-            return BlockActionResult::Continue;
+        let record = {
+            let mut inventory = player.inventory().lock().await;
+            let Some(stack) = inventory.held_item_mut() else {
+                return BlockActionResult::Continue;
+            };
+            let record = stack.copy_with_count(1);
+            if player.gamemode.load() != GameMode::Creative {
+                inventory.decrease_current_stack(1);
+            }
+            record
         };
-
-        //TODO: Update block nbt
+        player.send_inventory().await;
 
         self.set_record(true, block, location, world).await;
         world
-            .sync_world_event(
-                WorldEvent::JukeboxStartsPlaying,
-                location,
-                jukebox_song as i32,
-            )
+            .add_block_entity(Arc::new(JukeboxBlockEntity::new(location, Some(record))))
             .await;
+        world.sync_world_event(event, location, song).await;
 
         BlockActionResult::Consume
     }
 
-    async fn broken(
+    fn has_comparator_output(&self, _block: &Block) -> bool {
+        true
+    }
+
+    async fn get_comparator_output(&self, _block: &Block, world: &World, pos: &BlockPos) -> u8 {
+        current_record(world, *pos)
+            .await
+            .map_or(0, |record| disc_comparator_output(&record.item))
+    }
+
+    async fn on_state_replaced(
         &self,
+        world: &Arc<World>,
         _block: &Block,
-        _player: &Arc<Player>,
-        position: BlockPos,
-        _server: &Server,
-        world: Arc<World>,
-        _state: BlockState,
+        location: BlockPos,
+        _old_state_id: BlockStateId,
+        _moved: bool,
     ) {
-        // For now just stop the music at this position
+        if let Some(record) = take_record(world, location).await {
+            crate::block::drop_stack(world, &location, record).await;
+        }
         world
-            .sync_world_event(WorldEvent::JukeboxStopsPlaying, position, 0)
+            .sync_world_event(WorldEvent::JukeboxStopsPlaying, location, 0)
             .await;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_data::world::WorldEvent;
+    use pumpkin_registry::SYNCED_REGISTRIES;
+
+    use super::{disc_comparator_output, disc_inserted_event};
+
+    #[test]
+    fn inserting_disc_starts_its_song() {
+        let (event, song) = disc_inserted_event(&Item::MUSIC_DISC_CAT).unwrap();
+        assert_eq!(event as i32, WorldEvent::JukeboxStartsPlaying as i32);
+        assert_eq!(
+            song,
+            SYNCED_REGISTRIES.jukebox_song.get_index_of("cat").unwrap() as i32
+        );
+        assert!(disc_inserted_event(&Item::STONE).is_none());
+    }
+
+    #[test]
+    fn playing_disc_emits_its_comparator_signal() {
+        assert_eq!(disc_comparator_output(&Item::MUSIC_DISC_13), 1);
+        assert_eq!(disc_comparator_output(&Item::MUSIC_DISC_CAT), 2);
+        assert_eq!(disc_comparator_output(&Item::MUSIC_DISC_RELIC), 14);
+        assert_eq!(disc_comparator_output(&Item::STONE), 0);
+    }
+}
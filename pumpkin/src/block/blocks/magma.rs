@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_data::damage::DamageType;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::block::BlockDirection;
+use pumpkin_world::chunk::TickPriority;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::entity::EntityBase;
+use crate::world::World;
+
+use super::bubble_column::{COLUMN_SOURCE_DELAY, schedule_column_update, update_column};
+
+/// The damage entities standing on a magma block take each time their hurt cooldown ran out.
+const HOT_FLOOR_DAMAGE: f32 = 1.0;
+
+/// Whether an entity standing on a magma block gets burned. Sneaking carefully over it or being
+/// immune to fire keeps it safe.
+pub fn burns(living: bool, sneaking: bool, fire_immune: bool) -> bool {
+    living && !sneaking && !fire_immune
+}
+
+#[pumpkin_block("minecraft:magma_block")]
+pub struct MagmaBlock;
+
+#[async_trait]
+impl PumpkinBlock for MagmaBlock {
+    async fn on_entity_step(
+        &self,
+        _block: &Block,
+        _world: &Arc<World>,
+        _pos: &BlockPos,
+        entity: &dyn EntityBase,
+    ) {
+        let base = entity.get_entity();
+        if burns(
+            entity.get_living_entity().is_some(),
+            base.sneaking.load(std::sync::atomic::Ordering::Relaxed),
+            base.entity_type.fire_immune,
+        ) {
+            entity.damage(HOT_FLOOR_DAMAGE, DamageType::HOT_FLOOR).await;
+        }
+    }
+
+    async fn placed(
+        &self,
+        world: &Arc<World>,
+        block: &Block,
+        _state_id: BlockStateId,
+        pos: &BlockPos,
+        _old_state_id: BlockStateId,
+        _notify: bool,
+    ) {
+        world
+            .schedule_block_tick(block, *pos, COLUMN_SOURCE_DELAY, TickPriority::Normal)
+            .await;
+    }
+
+    async fn get_state_for_neighbor_update(
+        &self,
+        world: &World,
+        block: &Block,
+        state: BlockStateId,
+        pos: &BlockPos,
+        direction: BlockDirection,
+        _neighbor_pos: &BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        schedule_column_update(world, block, pos, direction, neighbor_state).await;
+        state
+    }
+
+    async fn on_scheduled_tick(&self, world: &Arc<World>, _block: &Block, pos: &BlockPos) {
+        update_column(world, &pos.up()).await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::burns;
+
+    #[test]
+    fn magma_burns_unless_sneaking_or_fire_immune() {
+        assert!(burns(true, false, false));
+        assert!(!burns(true, true, false));
+        assert!(!burns(true, false, true));
+        // Items and other non-living entities don't take damage
+        assert!(!burns(false, false, false));
+    }
+}
@@ -6,11 +6,17 @@ use pumpkin_util::math::position::BlockPos;
 use crate::{entity::player::Player, server::Server};
 
 pub mod bed;
+pub mod bell;
+pub mod bubble_column;
 pub mod cactus;
+pub mod cauldron;
 pub mod chest;
+pub mod conduit;
 pub mod crafting_table;
 pub mod dirt_path;
 pub mod doors;
+pub mod ender_chest;
+pub mod falling;
 pub mod farmland;
 pub mod fence_gates;
 pub mod fences;
@@ -20,10 +26,16 @@ pub mod glass_panes;
 pub mod iron_bars;
 pub mod jukebox;
 pub mod logs;
+pub mod magma;
 pub mod nether_portal;
+pub mod note_block;
 pub mod redstone;
+pub mod respawn_anchor;
+pub mod shulker_box;
 pub mod signs;
 pub mod slabs;
+pub mod smithing_table;
+pub mod soul_sand;
 pub mod stairs;
 pub mod sugar_cane;
 pub mod tnt;
@@ -42,12 +54,15 @@ pub async fn standard_on_broken_with_container(
     if let Some(all_container_ids) = server.get_all_container_ids(location, block.clone()).await {
         let mut open_containers = server.open_containers.write().await;
         for individual_id in all_container_ids {
-            if let Some(container) = open_containers.get_mut(&u64::from(individual_id)) {
+            let id = u64::from(individual_id);
+            if let Some(container) = open_containers.get_mut(&id) {
                 container.clear_all_slots().await;
                 player.open_container.store(None);
                 close_all_in_container(player, container).await;
                 container.clear_all_players();
             }
+            // Nobody can use the container of a broken block anymore
+            open_containers.remove(&id);
         }
     }
 }
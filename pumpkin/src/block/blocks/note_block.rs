@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_data::block_properties::{
+    BlockProperties, EnumVariants, Instrument, Integer0To24, NoteBlockLikeProperties,
+};
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_protocol::{client::play::CBlockAction, codec::var_int::VarInt};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::block::registry::BlockActionResult;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::{BlockFlags, World};
+
+use super::redstone::block_receives_redstone_power;
+
+#[pumpkin_block("minecraft:note_block")]
+pub struct NoteBlock;
+
+impl NoteBlock {
+    async fn tune(&self, block: &Block, location: BlockPos, world: &Arc<World>) {
+        let state = world.get_block_state(&location).await.unwrap();
+        let mut props = NoteBlockLikeProperties::from_state_id(state.id, block);
+        props.note = Integer0To24::from_index((props.note.to_index() + 1) % 25);
+        world
+            .set_block_state(
+                &location,
+                props.to_state_id(block),
+                BlockFlags::NOTIFY_LISTENERS,
+            )
+            .await;
+        self.play_note(block, location, world).await;
+    }
+
+    /// Plays the note of the block's instrument and pitch, unless something is on top of it.
+    async fn play_note(&self, block: &Block, location: BlockPos, world: &World) {
+        let state = world.get_block_state(&location).await.unwrap();
+        let props = NoteBlockLikeProperties::from_state_id(state.id, block);
+        if !is_mob_head(props.instrument)
+            && !world
+                .get_block_state(&location.up())
+                .await
+                .is_ok_and(|above| above.is_air())
+        {
+            return;
+        }
+        let Some(sound) = instrument_sound(props.instrument) else {
+            return;
+        };
+        let pitch = if is_mob_head(props.instrument) {
+            1.0
+        } else {
+            note_pitch(props.note.to_index())
+        };
+
+        world
+            .broadcast_packet_all(&CBlockAction::new(location, 0, 0, VarInt(block.id.into())))
+            .await;
+        let position = Vector3::new(
+            f64::from(location.0.x) + 0.5,
+            f64::from(location.0.y) + 0.5,
+            f64::from(location.0.z) + 0.5,
+        );
+        world
+            .play_sound_raw(sound as u16, SoundCategory::Records, &position, 3.0, pitch)
+            .await;
+    }
+}
+
+fn is_mob_head(instrument: Instrument) -> bool {
+    matches!(
+        instrument,
+        Instrument::Zombie
+            | Instrument::Skeleton
+            | Instrument::Creeper
+            | Instrument::Dragon
+            | Instrument::WitherSkeleton
+            | Instrument::Piglin
+            | Instrument::CustomHead
+    )
+}
+
+fn instrument_sound(instrument: Instrument) -> Option<Sound> {
+    let name = match instrument {
+        // Custom heads play the sound stored in the head itself
+        Instrument::CustomHead => return None,
+        Instrument::Dragon => "block.note_block.imitate.ender_dragon".to_string(),
+        instrument if is_mob_head(instrument) => {
+            format!("block.note_block.imitate.{}", instrument.to_value())
+        }
+        instrument => format!("block.note_block.{}", instrument.to_value()),
+    };
+    Sound::from_name(&name)
+}
+
+/// The pitch of a note from 0 to 24, spanning two octaves around the normal pitch.
+fn note_pitch(note: u16) -> f32 {
+    2f32.powf((f32::from(note) - 12.0) / 12.0)
+}
+
+#[async_trait]
+impl PumpkinBlock for NoteBlock {
+    async fn normal_use(
+        &self,
+        block: &Block,
+        _player: &Player,
+        location: BlockPos,
+        _server: &Server,
+        world: &Arc<World>,
+    ) {
+        self.tune(block, location, world).await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        _player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        _server: &Server,
+        world: &Arc<World>,
+    ) -> BlockActionResult {
+        self.tune(block, location, world).await;
+        BlockActionResult::Consume
+    }
+
+    async fn on_neighbor_update(
+        &self,
+        world: &Arc<World>,
+        block: &Block,
+        block_pos: &BlockPos,
+        _source_block: &Block,
+        _notify: bool,
+    ) {
+        let state = world.get_block_state(block_pos).await.unwrap();
+        let mut props = NoteBlockLikeProperties::from_state_id(state.id, block);
+        let is_receiving_power = block_receives_redstone_power(world, block_pos).await;
+        if props.powered == is_receiving_power {
+            return;
+        }
+        if is_receiving_power {
+            self.play_note(block, *block_pos, world).await;
+        }
+        props.powered = is_receiving_power;
+        world
+            .set_block_state(
+                block_pos,
+                props.to_state_id(block),
+                BlockFlags::NOTIFY_LISTENERS,
+            )
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::block_properties::Instrument;
+    use pumpkin_data::sound::Sound;
+
+    use super::{instrument_sound, note_pitch};
+
+    #[test]
+    fn note_value_sets_pitch() {
+        assert!((note_pitch(0) - 0.5).abs() < f32::EPSILON);
+        assert!((note_pitch(12) - 1.0).abs() < f32::EPSILON);
+        assert!((note_pitch(24) - 2.0).abs() < f32::EPSILON);
+        // One semitone up
+        assert!((note_pitch(13) - 1.059_463_1).abs() < 1e-6);
+
+        assert_eq!(
+            instrument_sound(Instrument::Harp).map(|sound| sound as u16),
+            Some(Sound::BlockNoteBlockHarp as u16)
+        );
+        assert_eq!(
+            instrument_sound(Instrument::Dragon).map(|sound| sound as u16),
+            Some(Sound::BlockNoteBlockImitateEnderDragon as u16)
+        );
+        assert!(instrument_sound(Instrument::CustomHead).is_none());
+    }
+}
@@ -1,16 +1,104 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use pumpkin_data::block_properties::{Axis, BlockProperties, EnumVariants, Integer0To15};
+use pumpkin_data::entity::EntityType;
 use pumpkin_data::{Block, BlockState};
 use pumpkin_macros::pumpkin_block;
-
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::math::vector3::Vector3;
 use pumpkin_world::block::BlockDirection;
+use pumpkin_world::chunk::TickPriority;
 
 use crate::block::pumpkin_block::PumpkinBlock;
+use crate::world::{BlockFlags, World};
+
+type TargetProperties = pumpkin_data::block_properties::LightWeightedPressurePlateLikeProperties;
+
+/// How long a target stays powered after arrows and tridents hit it
+const ARROW_PULSE_TICKS: u16 = 20;
+/// How long a target stays powered after other projectiles hit it
+const PULSE_TICKS: u16 = 8;
 
 #[pumpkin_block("minecraft:target")]
 pub struct TargetBlock;
 
+impl TargetBlock {
+    /// Lets the target at `pos` emit a pulse after a projectile of `projectile` type hit it at
+    /// `hit`, on a face across `axis`.
+    pub async fn hit(
+        world: &Arc<World>,
+        pos: &BlockPos,
+        hit: Vector3<f64>,
+        axis: Axis,
+        projectile: EntityType,
+    ) {
+        let Ok((block, state)) = world.get_block_and_block_state(pos).await else {
+            return;
+        };
+        if block != Block::TARGET {
+            return;
+        }
+        let mut props = TargetProperties::from_state_id(state.id, &block);
+        props.power = Integer0To15::from_index(hit_power(hit, axis).into());
+        world
+            .set_block_state(pos, props.to_state_id(&block), BlockFlags::NOTIFY_ALL)
+            .await;
+        // A second hit changes the power, but doesn't make the pulse any longer
+        if !world.is_block_tick_scheduled(pos, &block).await {
+            world
+                .schedule_block_tick(&block, *pos, pulse_ticks(projectile), TickPriority::Normal)
+                .await;
+        }
+        world.update_neighbors(pos, None).await;
+    }
+}
+
+/// The power of a target hit at `hit`, on a face across `axis`: 15 right in the middle, less
+/// further out, but always at least 1.
+#[must_use]
+pub fn hit_power(hit: Vector3<f64>, axis: Axis) -> u8 {
+    let off_center = |coordinate: f64| (coordinate.rem_euclid(1.0) - 0.5).abs();
+    let (x, y, z) = (off_center(hit.x), off_center(hit.y), off_center(hit.z));
+    let distance = match axis {
+        Axis::X => y.max(z),
+        Axis::Y => x.max(z),
+        Axis::Z => x.max(y),
+    };
+    let closeness = ((0.5 - distance) / 0.5).clamp(0.0, 1.0);
+    ((15.0 * closeness).ceil() as u8).max(1)
+}
+
+/// How many ticks a target hit by a projectile of `projectile` type stays powered.
+#[must_use]
+pub fn pulse_ticks(projectile: EntityType) -> u16 {
+    if projectile == EntityType::ARROW
+        || projectile == EntityType::SPECTRAL_ARROW
+        || projectile == EntityType::TRIDENT
+    {
+        ARROW_PULSE_TICKS
+    } else {
+        PULSE_TICKS
+    }
+}
+
 #[async_trait]
 impl PumpkinBlock for TargetBlock {
+    async fn on_scheduled_tick(&self, world: &Arc<World>, block: &Block, pos: &BlockPos) {
+        let Ok(state) = world.get_block_state(pos).await else {
+            return;
+        };
+        let mut props = TargetProperties::from_state_id(state.id, block);
+        if props.power == Integer0To15::L0 {
+            return;
+        }
+        props.power = Integer0To15::L0;
+        world
+            .set_block_state(pos, props.to_state_id(block), BlockFlags::NOTIFY_ALL)
+            .await;
+        world.update_neighbors(pos, None).await;
+    }
+
     async fn emits_redstone_power(
         &self,
         _block: &Block,
@@ -19,4 +107,41 @@ impl PumpkinBlock for TargetBlock {
     ) -> bool {
         true
     }
+
+    async fn get_weak_redstone_power(
+        &self,
+        block: &Block,
+        _world: &World,
+        _block_pos: &BlockPos,
+        state: &BlockState,
+        _direction: BlockDirection,
+    ) -> u8 {
+        TargetProperties::from_state_id(state.id, block)
+            .power
+            .to_index() as u8
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::block_properties::Axis;
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{ARROW_PULSE_TICKS, hit_power, pulse_ticks};
+
+    #[test]
+    fn arrow_near_bullseye_gives_strong_pulse() {
+        // An arrow hitting the north face of the target at 10, 64, -5 close to its center
+        let power = hit_power(Vector3::new(10.52, 64.47, -5.0), Axis::Z);
+        assert!(power >= 14);
+        assert_eq!(hit_power(Vector3::new(10.5, 64.5, -5.0), Axis::Z), 15);
+        // The edge of the target still gives a little power
+        assert_eq!(hit_power(Vector3::new(10.99, 64.5, -5.0), Axis::Z), 1);
+        assert!(hit_power(Vector3::new(-3.8, 70.0, 2.2), Axis::Y) < power);
+
+        // Arrows keep the target powered for longer
+        assert_eq!(pulse_ticks(EntityType::ARROW), ARROW_PULSE_TICKS);
+        assert!(pulse_ticks(EntityType::SNOWBALL) < ARROW_PULSE_TICKS);
+    }
 }
@@ -0,0 +1,217 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_data::block_properties::{
+    BlockProperties, EnumVariants, Integer0To4, RespawnAnchorLikeProperties,
+};
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::GameMode;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::BlockStateId;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::block::registry::BlockActionResult;
+use crate::entity::player::{Player, RespawnPoint};
+use crate::server::Server;
+use crate::world::{BlockFlags, World};
+
+/// Respawn anchors used where they don't work blow up a bit stronger than TNT.
+const EXPLOSION_POWER: f32 = 5.0;
+
+/// What using a respawn anchor does.
+#[derive(Debug, PartialEq, Eq)]
+enum AnchorUse {
+    /// Glowstone adds a charge
+    Charge,
+    SetSpawn,
+    Explode,
+    /// An empty anchor does nothing, the held item is used instead
+    Pass,
+}
+
+fn anchor_use(charges: Integer0To4, holds_glowstone: bool, anchor_works: bool) -> AnchorUse {
+    if holds_glowstone && charges != Integer0To4::L4 {
+        AnchorUse::Charge
+    } else if charges == Integer0To4::L0 {
+        AnchorUse::Pass
+    } else if anchor_works {
+        AnchorUse::SetSpawn
+    } else {
+        AnchorUse::Explode
+    }
+}
+
+/// The state of the anchor with `change` charges more, clamped to 0..=4.
+fn with_charges(state_id: BlockStateId, change: i16) -> BlockStateId {
+    let mut props = RespawnAnchorLikeProperties::from_state_id(state_id, &Block::RESPAWN_ANCHOR);
+    let charges = (props.charges.to_index() as i16 + change).clamp(0, 4);
+    props.charges = Integer0To4::from_index(charges as u16);
+    props.to_state_id(&Block::RESPAWN_ANCHOR)
+}
+
+#[pumpkin_block("minecraft:respawn_anchor")]
+pub struct RespawnAnchorBlock;
+
+impl RespawnAnchorBlock {
+    pub fn charges(state_id: BlockStateId) -> Integer0To4 {
+        RespawnAnchorLikeProperties::from_state_id(state_id, &Block::RESPAWN_ANCHOR).charges
+    }
+
+    /// Uses up a charge of the anchor at `pos` when a player respawns at it.
+    pub async fn deplete(world: &Arc<World>, pos: &BlockPos) {
+        let Ok(state_id) = world.get_block_state_id(pos).await else {
+            return;
+        };
+        world
+            .set_block_state(pos, with_charges(state_id, -1), BlockFlags::NOTIFY_ALL)
+            .await;
+        world
+            .play_block_sound(
+                Sound::BlockRespawnAnchorDeplete,
+                SoundCategory::Blocks,
+                *pos,
+            )
+            .await;
+    }
+
+    async fn set_spawn(player: &Player, world: &World, location: BlockPos) {
+        let respawn_point = RespawnPoint {
+            dimension: world.dimension_type.clone(),
+            position: location,
+            angle: player.living_entity.entity.yaw.load(),
+            forced: false,
+        };
+        let mut current = player.respawn_point.lock().await;
+        if current.as_ref() == Some(&respawn_point) {
+            return;
+        }
+        *current = Some(respawn_point);
+        drop(current);
+        world
+            .play_block_sound(
+                Sound::BlockRespawnAnchorSetSpawn,
+                SoundCategory::Blocks,
+                location,
+            )
+            .await;
+        player
+            .send_system_message(&TextComponent::translate("block.minecraft.set_spawn", []))
+            .await;
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for RespawnAnchorBlock {
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        world: &Arc<World>,
+    ) {
+        self.use_with_item(block, player, location, &Item::AIR, server, world)
+            .await;
+    }
+
+    async fn use_with_item(
+        &self,
+        _block: &Block,
+        player: &Player,
+        location: BlockPos,
+        item: &Item,
+        server: &Server,
+        world: &Arc<World>,
+    ) -> BlockActionResult {
+        let Ok(state_id) = world.get_block_state_id(&location).await else {
+            return BlockActionResult::Continue;
+        };
+        match anchor_use(
+            Self::charges(state_id),
+            *item == Item::GLOWSTONE,
+            world.respawn_anchor_works,
+        ) {
+            AnchorUse::Pass => return BlockActionResult::Continue,
+            AnchorUse::Charge => {
+                if player.gamemode.load() != GameMode::Creative {
+                    player.inventory().lock().await.decrease_current_stack(1);
+                    player.send_inventory().await;
+                }
+                world
+                    .set_block_state(&location, with_charges(state_id, 1), BlockFlags::NOTIFY_ALL)
+                    .await;
+                world
+                    .play_block_sound(
+                        Sound::BlockRespawnAnchorCharge,
+                        SoundCategory::Blocks,
+                        location,
+                    )
+                    .await;
+            }
+            AnchorUse::SetSpawn => Self::set_spawn(player, world, location).await,
+            AnchorUse::Explode => {
+                world
+                    .set_block_state(
+                        &location,
+                        Block::AIR.default_state_id,
+                        BlockFlags::NOTIFY_ALL,
+                    )
+                    .await;
+                let mut center = location.to_f64();
+                center.y += 0.5;
+                world.explode(server, center, EXPLOSION_POWER).await;
+            }
+        }
+        BlockActionResult::Consume
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+    use pumpkin_data::block_properties::Integer0To4;
+
+    use super::{AnchorUse, RespawnAnchorBlock, anchor_use, with_charges};
+
+    #[test]
+    fn anchor_charges_sets_spawn_and_explodes_outside_the_nether() {
+        let empty = Block::RESPAWN_ANCHOR.default_state_id;
+        assert_eq!(RespawnAnchorBlock::charges(empty), Integer0To4::L0);
+        assert_eq!(anchor_use(Integer0To4::L0, false, true), AnchorUse::Pass);
+
+        // Charging with glowstone, the anchor lights up more with every charge
+        assert_eq!(anchor_use(Integer0To4::L0, true, true), AnchorUse::Charge);
+        let mut anchor = empty;
+        for _ in 0..5 {
+            anchor = with_charges(anchor, 1);
+        }
+        assert_eq!(RespawnAnchorBlock::charges(anchor), Integer0To4::L4);
+        assert_eq!(anchor_use(Integer0To4::L4, true, true), AnchorUse::SetSpawn);
+        let luminance = |state_id| {
+            pumpkin_data::block_properties::get_state_by_state_id(state_id)
+                .unwrap()
+                .luminance
+        };
+        assert_eq!(luminance(empty), 0);
+        assert_eq!(luminance(anchor), 15);
+
+        // Respawning uses up a charge
+        let anchor = with_charges(anchor, -1);
+        assert_eq!(RespawnAnchorBlock::charges(anchor), Integer0To4::L3);
+        assert_eq!(
+            anchor_use(RespawnAnchorBlock::charges(anchor), false, true),
+            AnchorUse::SetSpawn
+        );
+
+        // In the overworld and the end a charged anchor explodes
+        assert_eq!(
+            anchor_use(RespawnAnchorBlock::charges(anchor), false, false),
+            AnchorUse::Explode
+        );
+        assert_eq!(anchor_use(Integer0To4::L0, false, false), AnchorUse::Pass);
+    }
+}
@@ -0,0 +1,266 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::block_properties::BlockProperties;
+use pumpkin_data::item::Item;
+use pumpkin_data::screen::WindowType;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_data::tag::{RegistryKey, get_tag_values};
+use pumpkin_data::{Block, BlockState};
+use pumpkin_inventory::{Container, OpenContainer, ShulkerBoxContainer};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::server::play::SUseItemOn;
+use pumpkin_protocol::{client::play::CBlockAction, codec::var_int::VarInt};
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::block::BlockDirection;
+use pumpkin_world::block::entities::shulker_box::ShulkerBoxBlockEntity;
+use pumpkin_world::item::ItemStack;
+use tokio::sync::Mutex;
+
+use crate::block::BlockIsReplacing;
+use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
+use crate::block::registry::BlockActionResult;
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+
+type ShulkerBoxProperties = pumpkin_data::block_properties::EndRodLikeProperties;
+
+pub struct ShulkerBoxBlock;
+impl BlockMetadata for ShulkerBoxBlock {
+    fn namespace(&self) -> &'static str {
+        "minecraft"
+    }
+
+    fn ids(&self) -> &'static [&'static str] {
+        get_tag_values(RegistryKey::Block, "minecraft:shulker_boxes").unwrap()
+    }
+}
+
+#[async_trait]
+impl PumpkinBlock for ShulkerBoxBlock {
+    async fn on_place(
+        &self,
+        _server: &Server,
+        _world: &World,
+        _player: &Player,
+        block: &Block,
+        _block_pos: &BlockPos,
+        face: BlockDirection,
+        _replacing: BlockIsReplacing,
+        _use_item_on: &SUseItemOn,
+    ) -> BlockStateId {
+        let mut props = ShulkerBoxProperties::default(block);
+        props.facing = face.opposite().to_facing();
+        props.to_state_id(block)
+    }
+
+    async fn placed(
+        &self,
+        world: &Arc<World>,
+        _block: &Block,
+        _state_id: BlockStateId,
+        pos: &BlockPos,
+        _old_state_id: BlockStateId,
+        _notify: bool,
+    ) {
+        world
+            .add_block_entity(Arc::new(ShulkerBoxBlockEntity::empty(*pos)))
+            .await;
+    }
+
+    async fn player_placed(
+        &self,
+        world: &Arc<World>,
+        _block: &Block,
+        _state_id: u16,
+        pos: &BlockPos,
+        _face: BlockDirection,
+        player: &Player,
+    ) {
+        let contents = {
+            let inventory = player.inventory().lock().await;
+            let Some(held) = inventory.held_item() else {
+                return;
+            };
+            if held.patch.container.is_empty() {
+                return;
+            }
+            let mut items = [const { ItemStack::EMPTY }; ShulkerBoxBlockEntity::SLOTS];
+            for (slot, stack) in items.iter_mut().zip(&held.patch.container) {
+                *slot = stack.clone();
+            }
+            items
+        };
+        world
+            .add_block_entity(Arc::new(ShulkerBoxBlockEntity::new(*pos, contents)))
+            .await;
+    }
+
+    async fn on_state_replaced(
+        &self,
+        world: &Arc<World>,
+        _block: &Block,
+        location: BlockPos,
+        _old_state_id: BlockStateId,
+        _moved: bool,
+    ) {
+        world.remove_block_entity(&location).await;
+    }
+
+    async fn normal_use(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        world: &Arc<World>,
+    ) {
+        self.open_shulker_box(block, player, location, server, world)
+            .await;
+    }
+
+    async fn use_with_item(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        _item: &Item,
+        server: &Server,
+        world: &Arc<World>,
+    ) -> BlockActionResult {
+        self.open_shulker_box(block, player, location, server, world)
+            .await;
+        BlockActionResult::Consume
+    }
+
+    async fn broken(
+        &self,
+        block: &Block,
+        player: &Arc<Player>,
+        location: BlockPos,
+        server: &Server,
+        _world: Arc<World>,
+        _state: BlockState,
+    ) {
+        // The contents were already put into the dropped item
+        super::standard_on_broken_with_container(block, player, location, server).await;
+    }
+
+    async fn close(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        container: &mut OpenContainer,
+    ) {
+        if let Some(opened) = container.try_open(player.entity_id()) {
+            let opened = opened.lock().await;
+            Self::save_contents(&*player.world().await, location, opened.as_ref()).await;
+        }
+        container.remove_player(player.entity_id());
+
+        let viewers = container.get_number_of_players();
+        if viewers == 0 {
+            player
+                .world()
+                .await
+                .play_block_sound(Sound::BlockShulkerBoxClose, SoundCategory::Blocks, location)
+                .await;
+        }
+        play_lid_action(block, location, server, viewers).await;
+    }
+}
+
+impl ShulkerBoxBlock {
+    pub async fn open_shulker_box(
+        &self,
+        block: &Block,
+        player: &Player,
+        location: BlockPos,
+        server: &Server,
+        world: &Arc<World>,
+    ) {
+        let entity_id = player.entity_id();
+        let viewers = {
+            let existing_id = server.get_container_id(location, block.clone()).await;
+            let mut open_containers = server.open_containers.write().await;
+            let existing = existing_id
+                .and_then(|id| Some((id, open_containers.get_mut(&u64::from(id))?)))
+                .filter(|(_, container)| container.get_number_of_players() > 0);
+            if let Some((id, container)) = existing {
+                container.add_player(entity_id);
+                player.open_container.store(Some(id.into()));
+                container.get_number_of_players()
+            } else {
+                // Nobody is looking inside, so the block entity holds the latest contents
+                let contents = Self::read_contents(world, location).await;
+                let container: Box<dyn Container> =
+                    Box::new(ShulkerBoxContainer::from_items(contents));
+                let id = existing_id.unwrap_or_else(|| server.new_container_id());
+                open_containers.insert(
+                    id.into(),
+                    OpenContainer::with_container(
+                        entity_id,
+                        Some(location),
+                        Some(block.clone()),
+                        Arc::new(Mutex::new(container)),
+                    ),
+                );
+                player.open_container.store(Some(id.into()));
+                1
+            }
+        };
+        player.open_container(server, WindowType::ShulkerBox).await;
+
+        if viewers == 1 {
+            world
+                .play_block_sound(Sound::BlockShulkerBoxOpen, SoundCategory::Blocks, location)
+                .await;
+        }
+        play_lid_action(block, location, server, viewers).await;
+    }
+
+    async fn read_contents(
+        world: &World,
+        location: BlockPos,
+    ) -> [ItemStack; ShulkerBoxBlockEntity::SLOTS] {
+        let mut nbt = NbtCompound::new();
+        if let Some(block_entity) = world.get_block_entity(&location).await {
+            block_entity.write_nbt(&mut nbt);
+        }
+        ShulkerBoxBlockEntity::read_items(&nbt)
+    }
+
+    /// Stores the contents of an open shulker box in its block entity.
+    pub async fn save_contents(world: &World, location: BlockPos, container: &dyn Container) {
+        let Some(block_entity) = world.get_block_entity(&location).await else {
+            return;
+        };
+        if block_entity.identifier() != ShulkerBoxBlockEntity::ID {
+            return;
+        }
+        let mut items = [const { ItemStack::EMPTY }; ShulkerBoxBlockEntity::SLOTS];
+        for (slot, stack) in items.iter_mut().zip(container.all_slots_ref()) {
+            if let Some(stack) = stack {
+                *slot = stack.clone();
+            }
+        }
+        world
+            .add_block_entity(Arc::new(ShulkerBoxBlockEntity::new(location, items)))
+            .await;
+    }
+}
+
+async fn play_lid_action(block: &Block, location: BlockPos, server: &Server, viewers: usize) {
+    server
+        .broadcast_packet_all(&CBlockAction::new(
+            location,
+            1,
+            viewers as u8,
+            VarInt(block.id.into()),
+        ))
+        .await;
+}
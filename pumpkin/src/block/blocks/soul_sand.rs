@@ -0,0 +1,52 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_macros::pumpkin_block;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::BlockStateId;
+use pumpkin_world::block::BlockDirection;
+use pumpkin_world::chunk::TickPriority;
+
+use crate::block::pumpkin_block::PumpkinBlock;
+use crate::world::World;
+
+use super::bubble_column::{COLUMN_SOURCE_DELAY, schedule_column_update, update_column};
+
+#[pumpkin_block("minecraft:soul_sand")]
+pub struct SoulSandBlock;
+
+#[async_trait]
+impl PumpkinBlock for SoulSandBlock {
+    async fn placed(
+        &self,
+        world: &Arc<World>,
+        block: &Block,
+        _state_id: BlockStateId,
+        pos: &BlockPos,
+        _old_state_id: BlockStateId,
+        _notify: bool,
+    ) {
+        world
+            .schedule_block_tick(block, *pos, COLUMN_SOURCE_DELAY, TickPriority::Normal)
+            .await;
+    }
+
+    async fn get_state_for_neighbor_update(
+        &self,
+        world: &World,
+        block: &Block,
+        state: BlockStateId,
+        pos: &BlockPos,
+        direction: BlockDirection,
+        _neighbor_pos: &BlockPos,
+        neighbor_state: BlockStateId,
+    ) -> BlockStateId {
+        schedule_column_update(world, block, pos, direction, neighbor_state).await;
+        state
+    }
+
+    async fn on_scheduled_tick(&self, world: &Arc<World>, _block: &Block, pos: &BlockPos) {
+        update_column(world, &pos.up()).await;
+    }
+}
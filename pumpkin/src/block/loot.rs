@@ -1,4 +1,4 @@
-use pumpkin_data::item::Item;
+use pumpkin_data::{item::Item, tag::Tagable};
 use pumpkin_util::loot_table::{
     LootCondition, LootFunctionNumberProvider, LootFunctionTypes, LootPoolEntry,
     LootPoolEntryTypes, LootTable,
@@ -6,21 +6,42 @@ use pumpkin_util::loot_table::{
 use pumpkin_world::item::ItemStack;
 use rand::Rng;
 
-pub(super) trait LootTableExt {
-    fn get_loot(&self, block_props: &[(String, String)]) -> Vec<ItemStack>;
+/// What the loot table is rolled for.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct LootContext<'a> {
+    /// The properties of the broken block
+    pub block_props: &'a [(String, String)],
+    /// The tool the block was broken or the fish was caught with
+    pub tool: Option<&'a ItemStack>,
+    /// Adds `bonus_rolls` to the rolls of each pool per point
+    pub luck: f32,
+}
+
+impl<'a> LootContext<'a> {
+    pub fn block(block_props: &'a [(String, String)], tool: Option<&'a ItemStack>) -> Self {
+        Self {
+            block_props,
+            tool,
+            luck: 0.0,
+        }
+    }
+}
+
+pub(crate) trait LootTableExt {
+    fn get_loot(&self, rng: &mut impl Rng, context: &LootContext) -> Vec<ItemStack>;
 }
 
 impl LootTableExt for LootTable {
-    fn get_loot(&self, block_props: &[(String, String)]) -> Vec<ItemStack> {
+    fn get_loot(&self, rng: &mut impl Rng, context: &LootContext) -> Vec<ItemStack> {
         let mut stacks = Vec::new();
 
         if let Some(pools) = self.pools {
             for pool in pools {
-                let rolls = pool.rolls.round() + pool.bonus_rolls.floor(); // TODO: multiply by luck
+                let rolls = pool.rolls.round() + (pool.bonus_rolls * context.luck).floor();
 
                 for _ in 0..(rolls as i32) {
                     for entry in pool.entries {
-                        if let Some(loot) = entry.get_loot(block_props) {
+                        if let Some(loot) = entry.get_loot(rng, context) {
                             stacks.extend(loot);
                         }
                     }
@@ -33,23 +54,23 @@ impl LootTableExt for LootTable {
 }
 
 trait LootPoolEntryExt {
-    fn get_loot(&self, block_props: &[(String, String)]) -> Option<Vec<ItemStack>>;
+    fn get_loot(&self, rng: &mut impl Rng, context: &LootContext) -> Option<Vec<ItemStack>>;
 }
 
 impl LootPoolEntryExt for LootPoolEntry {
-    fn get_loot(&self, block_props: &[(String, String)]) -> Option<Vec<ItemStack>> {
+    fn get_loot(&self, rng: &mut impl Rng, context: &LootContext) -> Option<Vec<ItemStack>> {
         if let Some(conditions) = self.conditions {
-            if !conditions.iter().all(|cond| cond.is_fulfilled(block_props)) {
+            if !conditions.iter().all(|cond| cond.is_fulfilled(context)) {
                 return None;
             }
         }
 
-        let mut stacks = self.content.get_stacks(block_props);
+        let mut stacks = self.content.get_stacks(rng, context);
 
         if let Some(functions) = self.functions {
             for function in functions {
                 if let Some(conditions) = function.conditions {
-                    if !conditions.iter().all(|cond| cond.is_fulfilled(block_props)) {
+                    if !conditions.iter().all(|cond| cond.is_fulfilled(context)) {
                         continue;
                     }
                 }
@@ -58,9 +79,9 @@ impl LootPoolEntryExt for LootPoolEntry {
                     LootFunctionTypes::SetCount { count, add } => {
                         for stack in &mut stacks {
                             if *add {
-                                stack.item_count += count.generate().round() as u8;
+                                stack.item_count += count.generate(rng).round() as u8;
                             } else {
-                                stack.item_count = count.generate().round() as u8;
+                                stack.item_count = count.generate(rng).round() as u8;
                             }
                         }
                     }
@@ -106,11 +127,11 @@ impl LootPoolEntryExt for LootPoolEntry {
 }
 
 trait LootPoolEntryTypesExt {
-    fn get_stacks(&self, block_props: &[(String, String)]) -> Vec<ItemStack>;
+    fn get_stacks(&self, rng: &mut impl Rng, context: &LootContext) -> Vec<ItemStack>;
 }
 
 impl LootPoolEntryTypesExt for LootPoolEntryTypes {
-    fn get_stacks(&self, block_props: &[(String, String)]) -> Vec<ItemStack> {
+    fn get_stacks(&self, rng: &mut impl Rng, context: &LootContext) -> Vec<ItemStack> {
         match self {
             Self::Empty => Vec::new(),
             Self::Item(item_entry) => {
@@ -120,12 +141,12 @@ impl LootPoolEntryTypesExt for LootPoolEntryTypes {
             Self::LootTable => todo!(),
             Self::Dynamic => todo!(),
             Self::Tag => todo!(),
+            // Only the first child whose conditions pass is used
             Self::Alternatives(alternative_entry) => alternative_entry
                 .children
                 .iter()
-                .filter_map(|entry| entry.get_loot(block_props))
-                .flatten()
-                .collect(),
+                .find_map(|entry| entry.get_loot(rng, context))
+                .unwrap_or_default(),
             Self::Sequence => todo!(),
             Self::Group => todo!(),
         }
@@ -133,36 +154,56 @@ impl LootPoolEntryTypesExt for LootPoolEntryTypes {
 }
 
 trait LootConditionExt {
-    fn is_fulfilled(&self, block_props: &[(String, String)]) -> bool;
+    fn is_fulfilled(&self, context: &LootContext) -> bool;
 }
 
 impl LootConditionExt for LootCondition {
     // TODO: This is trash. Make this right
-    fn is_fulfilled(&self, block_props: &[(String, String)]) -> bool {
+    fn is_fulfilled(&self, context: &LootContext) -> bool {
         match self {
             Self::SurvivesExplosion => true,
             Self::BlockStateProperty {
                 block: _,
                 properties,
-            } => properties
-                .iter()
-                .all(|(key, value)| block_props.iter().any(|(k, v)| k == key && v == value)),
+            } => properties.iter().all(|(key, value)| {
+                context
+                    .block_props
+                    .iter()
+                    .any(|(k, v)| k == key && v == value)
+            }),
+            Self::MatchTool {
+                items,
+                enchantments,
+            } => context.tool.is_some_and(|tool| {
+                let item_matches = items.is_none_or(|items| match items.strip_prefix('#') {
+                    Some(tag) => tool.item.is_tagged_with(tag) == Some(true),
+                    None => {
+                        items.strip_prefix("minecraft:").unwrap_or(items) == tool.item.registry_key
+                    }
+                });
+                item_matches
+                    && enchantments.iter().all(|(enchantment, min)| {
+                        tool.patch
+                            .enchantment_level(enchantment)
+                            .is_some_and(|level| level >= *min)
+                    })
+            }),
             _ => false,
         }
     }
 }
 
 trait LootFunctionNumberProviderExt {
-    fn generate(&self) -> f32;
+    fn generate(&self, rng: &mut impl Rng) -> f32;
 }
 
 impl LootFunctionNumberProviderExt for LootFunctionNumberProvider {
-    fn generate(&self) -> f32 {
+    fn generate(&self, rng: &mut impl Rng) -> f32 {
         match self {
             Self::Constant { value } => *value,
-            Self::Uniform { min, max } => rand::thread_rng().gen_range(*min..=*max),
+            Self::Uniform { min, max } => rng.gen_range(*min..=*max),
             Self::Binomial { n, p } => (0..n.floor() as u32).fold(0.0, |c, _| {
-                if rand::thread_rng().gen_bool(f64::from(*p)) {
+                if rng.gen_bool(f64::from(*p)) {
                     c + 1.0
                 } else {
                     c
@@ -1,7 +1,13 @@
 use blocks::bed::BedBlock;
+use blocks::bell::BellBlock;
+use blocks::bubble_column::BubbleColumnBlock;
 use blocks::cactus::CactusBlock;
+use blocks::cauldron::WaterCauldronBlock;
+use blocks::conduit::ConduitBlock;
 use blocks::dirt_path::DirtPathBlock;
 use blocks::doors::DoorBlock;
+use blocks::ender_chest::EnderChestBlock;
+use blocks::falling::FallingBlock;
 use blocks::farmland::FarmLandBlock;
 use blocks::fence_gates::FenceGateBlock;
 use blocks::fences::FenceBlock;
@@ -10,7 +16,9 @@ use blocks::fire::soul_fire::SoulFireBlock;
 use blocks::glass_panes::GlassPaneBlock;
 use blocks::iron_bars::IronBarsBlock;
 use blocks::logs::LogBlock;
+use blocks::magma::MagmaBlock;
 use blocks::nether_portal::NetherPortalBlock;
+use blocks::note_block::NoteBlock;
 use blocks::redstone::buttons::ButtonBlock;
 use blocks::redstone::observer::ObserverBlock;
 use blocks::redstone::piston::PistonBlock;
@@ -24,8 +32,11 @@ use blocks::redstone::redstone_torch::RedstoneTorchBlock;
 use blocks::redstone::redstone_wire::RedstoneWireBlock;
 use blocks::redstone::repeater::RepeaterBlock;
 use blocks::redstone::target_block::TargetBlock;
+use blocks::respawn_anchor::RespawnAnchorBlock;
+use blocks::shulker_box::ShulkerBoxBlock;
 use blocks::signs::SignBlock;
 use blocks::slabs::SlabBlock;
+use blocks::soul_sand::SoulSandBlock;
 use blocks::stairs::StairBlock;
 use blocks::sugar_cane::SugarCaneBlock;
 use blocks::torches::TorchBlock;
@@ -35,17 +46,23 @@ use blocks::{
 };
 use fluids::lava::FlowingLava;
 use fluids::water::FlowingWater;
-use loot::LootTableExt;
+use loot::{LootContext, LootTableExt};
 use pumpkin_data::block_properties::Integer0To15;
 use pumpkin_data::entity::EntityType;
 use pumpkin_data::{Block, BlockState};
 
+use pumpkin_inventory::ShulkerBoxContainer;
+use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_world::BlockStateId;
+use pumpkin_world::block::entities::BlockEntity;
+use pumpkin_world::block::entities::shulker_box::ShulkerBoxBlockEntity;
 use pumpkin_world::item::ItemStack;
+use pumpkin_world::item::component::container_from_slots;
 use rand::Rng;
 
+use crate::block::blocks::smithing_table::SmithingTableBlock;
 use crate::block::registry::BlockRegistry;
 use crate::entity::item::ItemEntity;
 use crate::world::World;
@@ -55,7 +72,7 @@ use std::sync::Arc;
 
 pub(crate) mod blocks;
 mod fluids;
-mod loot;
+pub(crate) mod loot;
 pub mod pumpkin_block;
 pub mod pumpkin_fluid;
 pub mod registry;
@@ -67,10 +84,16 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     // Blocks
     manager.register(BedBlock);
     manager.register(CactusBlock);
+    manager.register(WaterCauldronBlock);
+    manager.register(BellBlock);
+    manager.register(BubbleColumnBlock);
+    manager.register(ConduitBlock);
     manager.register(ChestBlock);
     manager.register(CraftingTableBlock);
     manager.register(DirtPathBlock);
     manager.register(DoorBlock);
+    manager.register(EnderChestBlock);
+    manager.register(FallingBlock);
     manager.register(FarmLandBlock);
     manager.register(FenceGateBlock);
     manager.register(FenceBlock);
@@ -79,14 +102,20 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     manager.register(IronBarsBlock);
     manager.register(JukeboxBlock);
     manager.register(LogBlock);
+    manager.register(MagmaBlock);
     manager.register(SignBlock);
     manager.register(SlabBlock);
+    manager.register(SoulSandBlock);
+    manager.register(ShulkerBoxBlock);
+    manager.register(SmithingTableBlock);
     manager.register(StairBlock);
     manager.register(SugarCaneBlock);
     manager.register(TNTBlock);
     manager.register(TorchBlock);
     manager.register(WallBlock);
     manager.register(NetherPortalBlock);
+    manager.register(NoteBlock);
+    manager.register(RespawnAnchorBlock);
 
     // Fire
     manager.register(SoulFireBlock);
@@ -116,18 +145,25 @@ pub fn default_registry() -> Arc<BlockRegistry> {
     Arc::new(manager)
 }
 
+/// Drops the loot of a broken block. `tool` is what it was broken with, if anything.
 pub async fn drop_loot(
     world: &Arc<World>,
     block: &Block,
     pos: &BlockPos,
     experience: bool,
     state_id: BlockStateId,
+    block_entity: Option<Arc<dyn BlockEntity>>,
+    tool: Option<&ItemStack>,
 ) {
     if let Some(loot_table) = &block.loot_table {
         let props =
             Block::properties(block, state_id).map_or_else(Vec::new, |props| props.to_props());
 
-        for stack in loot_table.get_loot(&props) {
+        let loot = loot_table.get_loot(&mut rand::thread_rng(), &LootContext::block(&props, tool));
+        for mut stack in loot {
+            if let Some(block_entity) = &block_entity {
+                copy_block_entity_data(block_entity.as_ref(), &mut stack);
+            }
             drop_stack(world, pos, stack).await;
         }
     }
@@ -143,7 +179,23 @@ pub async fn drop_loot(
     }
 }
 
-async fn drop_stack(world: &Arc<World>, pos: &BlockPos, stack: ItemStack) {
+/// Keeps the data of block entities which survive being broken, like the contents of shulker boxes.
+fn copy_block_entity_data(block_entity: &dyn BlockEntity, stack: &mut ItemStack) {
+    if block_entity.identifier() != ShulkerBoxBlockEntity::ID
+        || !ShulkerBoxContainer::is_shulker_box(&stack.item)
+    {
+        return;
+    }
+    let mut nbt = NbtCompound::new();
+    block_entity.write_nbt(&mut nbt);
+    stack.patch.container = container_from_slots(
+        ShulkerBoxBlockEntity::read_items(&nbt)
+            .into_iter()
+            .enumerate(),
+    );
+}
+
+pub(crate) async fn drop_stack(world: &Arc<World>, pos: &BlockPos, stack: ItemStack) {
     let height = EntityType::ITEM.dimension[1] / 2.0;
     let pos = Vector3::new(
         f64::from(pos.0.x) + 0.5 + rand::thread_rng().gen_range(-0.25..0.25),
@@ -152,8 +204,7 @@ async fn drop_stack(world: &Arc<World>, pos: &BlockPos, stack: ItemStack) {
     );
 
     let entity = world.create_entity(pos, EntityType::ITEM);
-    let item_entity =
-        Arc::new(ItemEntity::new(entity, stack.item.id, u32::from(stack.item_count)).await);
+    let item_entity = Arc::new(ItemEntity::from_stack(entity, stack).await);
     world.spawn_entity(item_entity.clone()).await;
     item_entity.send_meta_packet().await;
 }
@@ -174,6 +225,41 @@ pub async fn calc_block_breaking(player: &Player, state: &BlockState, block_name
     player.get_mining_speed(block_name).await / hardness / i as f32
 }
 
+/// Returns the value of the property `name` in the given state of `block`,
+/// or `None` if the block doesn't have that property.
+#[must_use]
+pub fn get_block_property(block: &Block, state_id: BlockStateId, name: &str) -> Option<String> {
+    block
+        .properties(state_id)?
+        .to_props()
+        .into_iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value)
+}
+
+/// Returns the state of `block` which has the property `name` set to `value` and keeps
+/// all other properties of `state_id`. Returns `None` if the property or value doesn't exist.
+#[must_use]
+pub fn with_block_property(
+    block: &Block,
+    state_id: BlockStateId,
+    name: &str,
+    value: &str,
+) -> Option<BlockStateId> {
+    let mut props = block.properties(state_id)?.to_props();
+    let prop = props.iter_mut().find(|(key, _)| key == name)?;
+    if prop.1 == value {
+        return Some(state_id);
+    }
+    value.clone_into(&mut prop.1);
+
+    block
+        .states
+        .iter()
+        .map(|state| state.id)
+        .find(|&id| block.properties(id).is_some_and(|p| p.to_props() == props))
+}
+
 #[derive(PartialEq)]
 pub enum BlockIsReplacing {
     Itself(BlockStateId),
@@ -192,3 +278,56 @@ impl BlockIsReplacing {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::Block;
+
+    use super::{get_block_property, with_block_property};
+
+    #[test]
+    fn stair_facing_is_read() {
+        let block = Block::OAK_STAIRS;
+        let state = block.default_state_id;
+        assert_eq!(
+            get_block_property(&block, state, "facing").as_deref(),
+            Some("north")
+        );
+        assert_eq!(get_block_property(&block, state, "open"), None);
+
+        let east = with_block_property(&block, state, "facing", "east").unwrap();
+        assert_eq!(
+            get_block_property(&block, east, "facing").as_deref(),
+            Some("east")
+        );
+        // Other properties are kept
+        assert_eq!(
+            get_block_property(&block, east, "half"),
+            get_block_property(&block, state, "half")
+        );
+    }
+
+    #[test]
+    fn door_open_is_toggled() {
+        let block = Block::OAK_DOOR;
+        let closed = block.default_state_id;
+        assert_eq!(
+            get_block_property(&block, closed, "open").as_deref(),
+            Some("false")
+        );
+
+        let open = with_block_property(&block, closed, "open", "true").unwrap();
+        assert_ne!(open, closed);
+        assert_eq!(
+            get_block_property(&block, open, "open").as_deref(),
+            Some("true")
+        );
+        assert_eq!(
+            with_block_property(&block, open, "open", "false"),
+            Some(closed)
+        );
+
+        assert_eq!(with_block_property(&block, closed, "open", "maybe"), None);
+        assert_eq!(with_block_property(&block, closed, "lit", "true"), None);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::block::registry::BlockActionResult;
+use crate::entity::EntityBase;
 use crate::entity::player::Player;
 use crate::server::Server;
 use crate::world::{BlockFlags, World};
@@ -73,6 +74,26 @@ pub trait PumpkinBlock: Send + Sync {
 
     async fn random_tick(&self, _block: &Block, _world: &Arc<World>, _pos: &BlockPos) {}
 
+    /// Called every tick for each entity standing on the block
+    async fn on_entity_step(
+        &self,
+        _block: &Block,
+        _world: &Arc<World>,
+        _pos: &BlockPos,
+        _entity: &dyn EntityBase,
+    ) {
+    }
+
+    /// Called every tick for each entity whose feet are inside the block
+    async fn on_entity_inside(
+        &self,
+        _block: &Block,
+        _world: &Arc<World>,
+        _pos: &BlockPos,
+        _entity: &dyn EntityBase,
+    ) {
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn can_place_at(
         &self,
@@ -190,6 +211,16 @@ pub trait PumpkinBlock: Send + Sync {
     ) {
     }
 
+    /// Whether a comparator can read a signal from this block
+    fn has_comparator_output(&self, _block: &Block) -> bool {
+        false
+    }
+
+    /// The signal a comparator reads from this block, only used if [`Self::has_comparator_output`]
+    async fn get_comparator_output(&self, _block: &Block, _world: &World, _pos: &BlockPos) -> u8 {
+        0
+    }
+
     /// Sides where redstone connects to
     async fn emits_redstone_power(
         &self,
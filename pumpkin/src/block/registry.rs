@@ -1,4 +1,5 @@
 use crate::block::pumpkin_block::{BlockMetadata, PumpkinBlock};
+use crate::entity::EntityBase;
 use crate::entity::player::Player;
 use crate::server::Server;
 use crate::world::{BlockFlags, World};
@@ -54,6 +55,36 @@ impl BlockRegistry {
         }
     }
 
+    pub async fn on_entity_step(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        pos: &BlockPos,
+        entity: &dyn EntityBase,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_entity_step(block, world, pos, entity)
+                .await;
+        }
+    }
+
+    pub async fn on_entity_inside(
+        &self,
+        block: &Block,
+        world: &Arc<World>,
+        pos: &BlockPos,
+        entity: &dyn EntityBase,
+    ) {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            pumpkin_block
+                .on_entity_inside(block, world, pos, entity)
+                .await;
+        }
+    }
+
     pub async fn explode(&self, block: &Block, world: &Arc<World>, location: BlockPos) {
         let pumpkin_block = self.get_pumpkin_block(block);
         if let Some(pumpkin_block) = pumpkin_block {
@@ -438,4 +469,17 @@ impl BlockRegistry {
         }
         0
     }
+
+    pub fn has_comparator_output(&self, block: &Block) -> bool {
+        self.get_pumpkin_block(block)
+            .is_some_and(|pumpkin_block| pumpkin_block.has_comparator_output(block))
+    }
+
+    pub async fn get_comparator_output(&self, block: &Block, world: &World, pos: &BlockPos) -> u8 {
+        let pumpkin_block = self.get_pumpkin_block(block);
+        if let Some(pumpkin_block) = pumpkin_block {
+            return pumpkin_block.get_comparator_output(block, world, pos).await;
+        }
+        0
+    }
 }
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::command::CommandSender;
+use crate::command::dispatcher::CommandError;
+use crate::command::tree::RawArgs;
+use crate::entity::EntityBase;
+use crate::server::Server;
+
+use super::super::args::ArgumentConsumer;
+use super::entity::EntityArgumentConsumer;
+use super::{Arg, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
+
+/// For selecting a single entity of any kind, eg. using @s, a player name or entity uuid.
+///
+/// Unlike [`EntityArgumentConsumer`], entities which aren't players can be selected by their uuid.
+pub struct AnyEntityArgumentConsumer;
+
+impl GetClientSideArgParser for AnyEntityArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        EntityArgumentConsumer.get_client_side_parser()
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for AnyEntityArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        src: &CommandSender,
+        server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let s = *args.last()?;
+        if let Ok(uuid) = uuid::Uuid::parse_str(s) {
+            args.pop();
+            return server.get_entity_by_uuid(uuid).await.map(Arg::AnyEntity);
+        }
+        // Selectors and names only ever find players
+        match EntityArgumentConsumer.consume(src, server, args).await {
+            Some(Arg::Entity(player)) => Some(Arg::AnyEntity(player)),
+            _ => None,
+        }
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for AnyEntityArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "target"
+    }
+}
+
+impl<'a> FindArg<'a> for AnyEntityArgumentConsumer {
+    type Data = Arc<dyn EntityBase>;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::AnyEntity(data)) => Ok(data.clone()),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
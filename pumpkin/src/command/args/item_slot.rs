@@ -0,0 +1,128 @@
+use async_trait::async_trait;
+use pumpkin_inventory::player::{
+    SLOT_BOOT, SLOT_CHEST, SLOT_HELM, SLOT_HOTBAR_START, SLOT_INV_START, SLOT_LEG, SLOT_OFFHAND,
+};
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::command::{
+    CommandSender,
+    args::{Arg, ArgumentConsumer, DefaultNameArgConsumer, FindArg, GetClientSideArgParser},
+    dispatcher::CommandError,
+    tree::RawArgs,
+};
+use crate::server::Server;
+
+const MAINHAND: i32 = 98;
+const OFFHAND: i32 = 99;
+const FEET: i32 = 100;
+const LEGS: i32 = 101;
+const CHEST: i32 = 102;
+const HEAD: i32 = 103;
+
+/// A slot name like `container.5` or `armor.head`, consumed as the vanilla slot number.
+pub struct ItemSlotArgumentConsumer;
+
+impl GetClientSideArgParser for ItemSlotArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::ItemSlot
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for ItemSlotArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        parse_slot(args.pop()?).map(Arg::ItemSlot)
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for ItemSlotArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "slot"
+    }
+}
+
+impl<'a> FindArg<'a> for ItemSlotArgumentConsumer {
+    type Data = i32;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::ItemSlot(slot)) => Ok(*slot),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
+
+/// The vanilla slot number of a slot name.
+fn parse_slot(name: &str) -> Option<i32> {
+    let indexed = |prefix: &str, offset: i32, count: i32| {
+        let index: i32 = name.strip_prefix(prefix)?.parse().ok()?;
+        (0..count).contains(&index).then_some(offset + index)
+    };
+    match name {
+        "weapon" | "weapon.mainhand" => Some(MAINHAND),
+        "weapon.offhand" => Some(OFFHAND),
+        "armor.feet" => Some(FEET),
+        "armor.legs" => Some(LEGS),
+        "armor.chest" => Some(CHEST),
+        "armor.head" => Some(HEAD),
+        _ => indexed("container.", 0, 54)
+            .or_else(|| indexed("hotbar.", 0, 9))
+            .or_else(|| indexed("inventory.", 9, 27)),
+    }
+}
+
+/// The index in the player inventory of a vanilla slot number. Vanilla numbers the hotbar first,
+/// the player inventory starts with the armor. `held_slot` is the index of the selected slot.
+pub fn player_inventory_slot(slot: i32, held_slot: usize) -> Option<usize> {
+    Some(match slot {
+        0..=8 => SLOT_HOTBAR_START + slot as usize,
+        9..=35 => SLOT_INV_START + slot as usize - 9,
+        MAINHAND => held_slot,
+        OFFHAND => SLOT_OFFHAND,
+        FEET => SLOT_BOOT,
+        LEGS => SLOT_LEG,
+        CHEST => SLOT_CHEST,
+        HEAD => SLOT_HELM,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_inventory::player::{SLOT_HELM, SLOT_HOTBAR_START, SLOT_INV_START};
+
+    use super::{parse_slot, player_inventory_slot};
+
+    #[test]
+    fn slot_names_map_to_player_inventory() {
+        assert_eq!(parse_slot("container.5"), Some(5));
+        assert_eq!(parse_slot("inventory.0"), Some(9));
+        assert_eq!(parse_slot("hotbar.9"), None);
+        assert_eq!(parse_slot("container.x"), None);
+
+        let slot = |name| player_inventory_slot(parse_slot(name).unwrap(), SLOT_HOTBAR_START + 3);
+        assert_eq!(slot("hotbar.2"), Some(SLOT_HOTBAR_START + 2));
+        assert_eq!(slot("inventory.0"), Some(SLOT_INV_START));
+        assert_eq!(slot("weapon.mainhand"), Some(SLOT_HOTBAR_START + 3));
+        assert_eq!(slot("armor.head"), Some(SLOT_HELM));
+        assert_eq!(slot("container.40"), None);
+    }
+}
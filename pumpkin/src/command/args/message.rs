@@ -1,7 +1,5 @@
 use async_trait::async_trait;
-use pumpkin_protocol::client::play::{
-    ArgumentType, CommandSuggestion, StringProtoArgBehavior, SuggestionProviders,
-};
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
 
 use crate::{command::dispatcher::CommandError, server::Server};
 
@@ -18,7 +16,8 @@ pub struct MsgArgConsumer;
 
 impl GetClientSideArgParser for MsgArgConsumer {
     fn get_client_side_parser(&self) -> ArgumentType {
-        ArgumentType::String(StringProtoArgBehavior::GreedyPhrase)
+        // Clients sign arguments of this type when chat reports are enabled
+        ArgumentType::Message
     }
 
     fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
@@ -3,9 +3,10 @@ use std::{collections::HashMap, hash::Hash, sync::Arc};
 use async_trait::async_trait;
 use bounded_num::{NotInBounds, Number};
 use pumpkin_data::damage::DamageType;
-use pumpkin_data::entity::EffectType;
+use pumpkin_data::entity::{Attribute, EffectType};
 use pumpkin_data::particle::Particle;
 use pumpkin_data::sound::SoundCategory;
+use pumpkin_nbt::path::NbtPath;
 use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::{
@@ -19,8 +20,12 @@ use super::{
     tree::{CommandTree, RawArgs},
 };
 use crate::world::bossbar::{BossbarColor, BossbarDivisions};
-use crate::{entity::player::Player, server::Server};
+use crate::{
+    entity::{EntityBase, player::Player},
+    server::Server,
+};
 
+pub mod any_entity;
 pub mod block;
 pub mod bool;
 pub mod bossbar_color;
@@ -31,7 +36,9 @@ mod coordinate;
 pub mod entities;
 pub mod entity;
 pub mod gamemode;
+pub mod item_slot;
 pub mod message;
+pub mod nbt_path;
 pub mod players;
 pub mod position_2d;
 pub mod position_3d;
@@ -82,6 +89,7 @@ pub trait DefaultNameArgConsumer: ArgumentConsumer {
 pub enum Arg<'a> {
     Entities(Vec<Arc<Player>>),
     Entity(Arc<Player>),
+    AnyEntity(Arc<dyn EntityBase>),
     Players(Vec<Arc<Player>>),
     BlockPos(BlockPos),
     Pos3D(Vector3<f64>),
@@ -90,12 +98,15 @@ pub enum Arg<'a> {
     GameMode(GameMode),
     CommandTree(CommandTree),
     Item(&'a str),
+    /// A vanilla slot number, see [`item_slot`]
+    ItemSlot(i32),
     ResourceLocation(&'a str),
     Block(&'a str),
     BossbarColor(BossbarColor),
     BossbarStyle(BossbarDivisions),
     Particle(Particle),
     Msg(String),
+    NbtPath(NbtPath),
     TextComponent(TextComponent),
     Time(i32),
     Num(Result<Number, NotInBounds>),
@@ -105,6 +116,7 @@ pub enum Arg<'a> {
     SoundCategory(SoundCategory),
     DamageType(DamageType),
     Effect(EffectType),
+    Attribute(Attribute),
 }
 
 /// see [`crate::commands::tree::builder::argument`] and [`CommandTree::execute`]/[`crate::commands::tree::builder::NonLeafNodeBuilder::execute`]
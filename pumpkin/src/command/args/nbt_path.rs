@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+use pumpkin_nbt::path::NbtPath;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::{
+    command::{CommandSender, dispatcher::CommandError, tree::RawArgs},
+    server::Server,
+};
+
+use super::{Arg, ArgumentConsumer, DefaultNameArgConsumer, FindArg, GetClientSideArgParser};
+
+/// Consumes a path into NBT data like `Inventory[0].components`, see [`NbtPath`]
+pub struct NbtPathArgumentConsumer;
+
+impl GetClientSideArgParser for NbtPathArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::NbtPath
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for NbtPathArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        args.pop()?.parse().ok().map(Arg::NbtPath)
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for NbtPathArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "path"
+    }
+}
+
+impl<'a> FindArg<'a> for NbtPathArgumentConsumer {
+    type Data = &'a NbtPath;
+
+    fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::NbtPath(data)) => Ok(data),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use async_trait::async_trait;
+use pumpkin_data::entity::Attribute;
+use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+
+use crate::command::{
+    CommandSender,
+    args::{
+        Arg, ArgumentConsumer, ConsumedArgs, DefaultNameArgConsumer, FindArg,
+        GetClientSideArgParser,
+    },
+    dispatcher::CommandError,
+    tree::RawArgs,
+};
+use crate::server::Server;
+
+pub struct AttributeArgumentConsumer;
+
+impl GetClientSideArgParser for AttributeArgumentConsumer {
+    fn get_client_side_parser(&self) -> ArgumentType {
+        ArgumentType::Resource {
+            identifier: "attribute",
+        }
+    }
+
+    fn get_client_side_suggestion_type_override(&self) -> Option<SuggestionProviders> {
+        None
+    }
+}
+
+#[async_trait]
+impl ArgumentConsumer for AttributeArgumentConsumer {
+    async fn consume<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        _server: &'a Server,
+        args: &mut RawArgs<'a>,
+    ) -> Option<Arg<'a>> {
+        let attribute = Attribute::from_name(args.pop()?)?;
+        Some(Arg::Attribute(attribute))
+    }
+
+    async fn suggest<'a>(
+        &'a self,
+        _sender: &CommandSender,
+        _server: &'a Server,
+        _input: &'a str,
+    ) -> Result<Option<Vec<CommandSuggestion>>, CommandError> {
+        Ok(None)
+    }
+}
+
+impl DefaultNameArgConsumer for AttributeArgumentConsumer {
+    fn default_name(&self) -> &'static str {
+        "attribute"
+    }
+}
+
+impl<'a> FindArg<'a> for AttributeArgumentConsumer {
+    type Data = Attribute;
+
+    fn find_arg(args: &'a ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
+        match args.get(name) {
+            Some(Arg::Attribute(data)) => Ok(*data),
+            _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
+        }
+    }
+}
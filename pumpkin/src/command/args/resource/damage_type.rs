@@ -35,11 +35,9 @@ impl ArgumentConsumer for DamageTypeArgumentConsumer {
         _server: &'a Server,
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
+        // Only damage types from the registry are valid
         let name = args.pop()?;
-
-        // Create a static damage type first
-        let damage_type = DamageType::from_name(name)?;
-        // Find matching static damage type from values array
+        let damage_type = DamageType::from_name(name.strip_prefix("minecraft:").unwrap_or(name))?;
         Some(Arg::DamageType(damage_type))
     }
 
@@ -1,6 +1,10 @@
 use async_trait::async_trait;
 use pumpkin_data::sound::Sound;
-use pumpkin_protocol::client::play::{ArgumentType, CommandSuggestion, SuggestionProviders};
+use pumpkin_protocol::{
+    IdOr, SoundEvent,
+    client::play::{ArgumentType, CommandSuggestion, SuggestionProviders},
+    codec::identifier::Identifier,
+};
 
 use crate::{command::dispatcher::CommandError, server::Server};
 
@@ -33,7 +37,7 @@ impl ArgumentConsumer for SoundArgumentConsumer {
         args: &mut RawArgs<'a>,
     ) -> Option<Arg<'a>> {
         let s = args.pop()?;
-        Some(Arg::Block(s))
+        Some(Arg::ResourceLocation(s))
     }
 
     async fn suggest<'a>(
@@ -53,20 +57,96 @@ impl DefaultNameArgConsumer for SoundArgumentConsumer {
 }
 
 impl<'a> FindArg<'a> for SoundArgumentConsumer {
-    type Data = Sound;
+    type Data = CommandSound;
 
     fn find_arg(args: &'a super::ConsumedArgs, name: &str) -> Result<Self::Data, CommandError> {
         match args.get(name) {
-            Some(Arg::Block(name)) => Sound::from_name(&name.replace("minecraft:", ""))
-                .map_or_else(
-                    || {
-                        Err(CommandError::GeneralCommandIssue(format!(
-                            "Sound {name} does not exist."
-                        )))
-                    },
-                    Result::Ok,
-                ),
+            Some(Arg::ResourceLocation(name)) => CommandSound::parse(name).map_or_else(
+                || {
+                    Err(CommandError::GeneralCommandIssue(format!(
+                        "Sound {name} does not exist."
+                    )))
+                },
+                Result::Ok,
+            ),
             _ => Err(CommandError::InvalidConsumption(Some(name.to_string()))),
         }
     }
 }
+
+/// A sound given to a command. Sounds outside of the `minecraft` namespace are custom sounds,
+/// e.g. from a resource pack, which the server can't check.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CommandSound {
+    Vanilla(Sound),
+    Custom(Identifier),
+}
+
+impl CommandSound {
+    pub fn parse(name: &str) -> Option<Self> {
+        let (namespace, path) = name.split_once(':').unwrap_or(("minecraft", name));
+        let valid_namespace = namespace
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.'));
+        let valid_path = path
+            .chars()
+            .all(|c| matches!(c, 'a'..='z' | '0'..='9' | '_' | '-' | '.' | '/'));
+        if namespace.is_empty() || path.is_empty() || !valid_namespace || !valid_path {
+            return None;
+        }
+        if namespace == "minecraft" {
+            Sound::from_name(path).map(Self::Vanilla)
+        } else {
+            Some(Self::Custom(Identifier {
+                namespace: namespace.to_string(),
+                path: path.to_string(),
+            }))
+        }
+    }
+
+    pub fn identifier(&self) -> Identifier {
+        match self {
+            Self::Vanilla(sound) => Identifier::vanilla(sound.to_name()),
+            Self::Custom(identifier) => identifier.clone(),
+        }
+    }
+
+    /// The sound as sent in sound packets. Custom sounds use the default range.
+    pub fn to_event(&self) -> IdOr<SoundEvent> {
+        match self {
+            Self::Vanilla(sound) => IdOr::Id(*sound as u16),
+            Self::Custom(identifier) => IdOr::Value(SoundEvent {
+                sound_name: identifier.clone(),
+                range: None,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::sound::Sound;
+
+    use super::CommandSound;
+
+    #[test]
+    fn parses_vanilla_and_custom_sounds() {
+        let crit = Some(CommandSound::Vanilla(Sound::EntityPlayerAttackCrit));
+        assert_eq!(CommandSound::parse("entity.player.attack.crit"), crit);
+        assert_eq!(
+            CommandSound::parse("minecraft:entity.player.attack.crit"),
+            crit
+        );
+        assert_eq!(
+            CommandSound::parse("mypack:music/intro")
+                .unwrap()
+                .identifier()
+                .to_string(),
+            "mypack:music/intro"
+        );
+        assert_eq!(CommandSound::parse("minecraft:not.a.sound"), None);
+        assert_eq!(CommandSound::parse("mypack:Loud"), None);
+        assert_eq!(CommandSound::parse("my pack:sound"), None);
+        assert_eq!(CommandSound::parse("mypack:"), None);
+    }
+}
@@ -18,11 +18,7 @@ pub async fn send_c_commands_packet(player: &Arc<Player>, dispatcher: &CommandDi
             continue;
         };
 
-        let Some(permission) = dispatcher.get_permission_lvl(key) else {
-            continue;
-        };
-
-        if !cmd_src.has_permission_lvl(permission) {
+        if !dispatcher.has_permission(&cmd_src, key).await {
             continue;
         }
 
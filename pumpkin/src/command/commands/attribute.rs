@@ -0,0 +1,312 @@
+use async_trait::async_trait;
+use pumpkin_data::entity::Attribute;
+use pumpkin_data::item::Operation;
+use pumpkin_util::text::{
+    TextComponent,
+    color::{Color, NamedColor},
+};
+
+use crate::command::{
+    CommandError, CommandExecutor, CommandSender,
+    args::{
+        ConsumedArgs, FindArg, bounded_num::BoundedNumArgumentConsumer,
+        entity::EntityArgumentConsumer, resource::attribute::AttributeArgumentConsumer,
+        resource_location::ResourceLocationArgumentConsumer,
+    },
+    tree::{
+        CommandTree,
+        builder::{argument, literal},
+    },
+};
+use crate::entity::attributes::{AttributeModifier, default_base_value};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["attribute"];
+const DESCRIPTION: &str = "Queries, adds, removes or sets an entity attribute.";
+
+const ARG_TARGET: &str = "target";
+const ARG_ATTRIBUTE: &str = "attribute";
+const ARG_VALUE: &str = "value";
+const ARG_ID: &str = "id";
+
+fn value_consumer() -> BoundedNumArgumentConsumer<f64> {
+    BoundedNumArgumentConsumer::new().name(ARG_VALUE)
+}
+
+fn attribute_name(attribute: Attribute) -> TextComponent {
+    TextComponent::translate(format!("attribute.name.{}", attribute.to_name()), [])
+}
+
+async fn find_value(sender: &mut CommandSender, args: &ConsumedArgs<'_>) -> Option<f64> {
+    if let Ok(Ok(value)) = BoundedNumArgumentConsumer::<f64>::find_arg(args, ARG_VALUE) {
+        return Some(value);
+    }
+    sender
+        .send_message(
+            TextComponent::text("Invalid attribute value").color(Color::Named(NamedColor::Red)),
+        )
+        .await;
+    None
+}
+
+enum Query {
+    Value,
+    Base,
+    Modifier,
+}
+
+struct GetExecutor(Query);
+
+#[async_trait]
+impl CommandExecutor for GetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let attribute = AttributeArgumentConsumer::find_arg(args, ARG_ATTRIBUTE)?;
+        let target_name = TextComponent::text(target.gameprofile.name.clone());
+        let attributes = target.living_entity.attributes.lock().await;
+
+        let message = match self.0 {
+            Query::Value => TextComponent::translate(
+                "commands.attribute.value.get.success",
+                [
+                    attribute_name(attribute),
+                    target_name,
+                    TextComponent::text(attributes.value(attribute).to_string()),
+                ],
+            ),
+            Query::Base => TextComponent::translate(
+                "commands.attribute.base_value.get.success",
+                [
+                    attribute_name(attribute),
+                    target_name,
+                    TextComponent::text(attributes.base_value(attribute).to_string()),
+                ],
+            ),
+            Query::Modifier => {
+                let id = ResourceLocationArgumentConsumer::find_arg(args, ARG_ID)?;
+                let id = AttributeModifier::new(id, 0.0, Operation::AddValue).id;
+                match attributes.modifier(attribute, &id) {
+                    Some(modifier) => TextComponent::translate(
+                        "commands.attribute.modifier.value.get.success",
+                        [
+                            TextComponent::text(id),
+                            attribute_name(attribute),
+                            target_name,
+                            TextComponent::text(modifier.amount.to_string()),
+                        ],
+                    ),
+                    None => TextComponent::translate(
+                        "commands.attribute.failed.no_modifier",
+                        [
+                            attribute_name(attribute),
+                            target_name,
+                            TextComponent::text(id),
+                        ],
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                }
+            }
+        };
+        drop(attributes);
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+/// Sets the base value, or resets it to the default of the entity type.
+struct BaseSetExecutor(bool);
+
+#[async_trait]
+impl CommandExecutor for BaseSetExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let attribute = AttributeArgumentConsumer::find_arg(args, ARG_ATTRIBUTE)?;
+
+        let (value, key) = if self.0 {
+            (
+                default_base_value(&target.living_entity.entity.entity_type, attribute),
+                "commands.attribute.base_value.reset.success",
+            )
+        } else {
+            let Some(value) = find_value(sender, args).await else {
+                return Ok(());
+            };
+            (value, "commands.attribute.base_value.set.success")
+        };
+        target
+            .living_entity
+            .set_attribute_base(attribute, value)
+            .await;
+
+        sender
+            .send_message(TextComponent::translate(
+                key,
+                [
+                    attribute_name(attribute),
+                    TextComponent::text(target.gameprofile.name.clone()),
+                    TextComponent::text(value.to_string()),
+                ],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct ModifierAddExecutor(Operation);
+
+#[async_trait]
+impl CommandExecutor for ModifierAddExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let attribute = AttributeArgumentConsumer::find_arg(args, ARG_ATTRIBUTE)?;
+        let id = ResourceLocationArgumentConsumer::find_arg(args, ARG_ID)?;
+        let Some(amount) = find_value(sender, args).await else {
+            return Ok(());
+        };
+
+        let modifier = AttributeModifier::new(id, amount, self.0);
+        let id = TextComponent::text(modifier.id.clone());
+        let target_name = TextComponent::text(target.gameprofile.name.clone());
+        if target
+            .living_entity
+            .add_attribute_modifier(attribute, modifier)
+            .await
+        {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.attribute.modifier.add.success",
+                    [id, attribute_name(attribute), target_name],
+                ))
+                .await;
+        } else {
+            sender
+                .send_message(
+                    TextComponent::translate(
+                        "commands.attribute.failed.modifier_already_present",
+                        [id, attribute_name(attribute), target_name],
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+        }
+        Ok(())
+    }
+}
+
+struct ModifierRemoveExecutor;
+
+#[async_trait]
+impl CommandExecutor for ModifierRemoveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = EntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let attribute = AttributeArgumentConsumer::find_arg(args, ARG_ATTRIBUTE)?;
+        let id = ResourceLocationArgumentConsumer::find_arg(args, ARG_ID)?;
+        let id = AttributeModifier::new(id, 0.0, Operation::AddValue).id;
+
+        let target_name = TextComponent::text(target.gameprofile.name.clone());
+        if target
+            .living_entity
+            .remove_attribute_modifier(attribute, &id)
+            .await
+        {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.attribute.modifier.remove.success",
+                    [
+                        TextComponent::text(id),
+                        attribute_name(attribute),
+                        target_name,
+                    ],
+                ))
+                .await;
+        } else {
+            sender
+                .send_message(
+                    TextComponent::translate(
+                        "commands.attribute.failed.no_modifier",
+                        [
+                            attribute_name(attribute),
+                            target_name,
+                            TextComponent::text(id),
+                        ],
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+        }
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGET, EntityArgumentConsumer).then(
+            argument(ARG_ATTRIBUTE, AttributeArgumentConsumer)
+                .then(literal("get").execute(GetExecutor(Query::Value)))
+                .then(
+                    literal("base")
+                        .then(literal("get").execute(GetExecutor(Query::Base)))
+                        .then(literal("set").then(
+                            argument(ARG_VALUE, value_consumer()).execute(BaseSetExecutor(false)),
+                        ))
+                        .then(literal("reset").execute(BaseSetExecutor(true))),
+                )
+                .then(
+                    literal("modifier")
+                        .then(
+                            literal("add").then(
+                                argument(ARG_ID, ResourceLocationArgumentConsumer::new(false))
+                                    .then(
+                                        argument(ARG_VALUE, value_consumer())
+                                            .then(
+                                                literal("add_value").execute(ModifierAddExecutor(
+                                                    Operation::AddValue,
+                                                )),
+                                            )
+                                            .then(literal("add_multiplied_base").execute(
+                                                ModifierAddExecutor(Operation::AddMultipliedBase),
+                                            ))
+                                            .then(literal("add_multiplied_total").execute(
+                                                ModifierAddExecutor(Operation::AddMultipliedTotal),
+                                            )),
+                                    ),
+                            ),
+                        )
+                        .then(
+                            literal("remove").then(
+                                argument(ARG_ID, ResourceLocationArgumentConsumer::new(false))
+                                    .execute(ModifierRemoveExecutor),
+                            ),
+                        )
+                        .then(
+                            literal("value").then(
+                                literal("get").then(
+                                    argument(ARG_ID, ResourceLocationArgumentConsumer::new(false))
+                                        .execute(GetExecutor(Query::Modifier)),
+                                ),
+                            ),
+                        ),
+                ),
+        ),
+    )
+}
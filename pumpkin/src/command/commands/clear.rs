@@ -1,16 +1,20 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use pumpkin_data::item::Item;
 use pumpkin_inventory::Container;
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::text::click::ClickEvent;
 use pumpkin_util::text::color::NamedColor;
 use pumpkin_util::text::hover::HoverEvent;
+use pumpkin_world::item::ItemStack;
 
+use crate::command::args::bounded_num::{BoundedNumArgumentConsumer, NotInBounds};
 use crate::command::args::entities::EntitiesArgumentConsumer;
-use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::args::resource::item::ItemArgumentConsumer;
+use crate::command::args::{Arg, ConsumedArgs, FindArg, FindArgDefaultName};
 use crate::command::tree::CommandTree;
-use crate::command::tree::builder::{argument, require};
+use crate::command::tree::builder::{argument, argument_default_name, require};
 use crate::command::{CommandError, CommandExecutor, CommandSender};
 use crate::entity::player::Player;
 use CommandError::InvalidConsumption;
@@ -18,25 +22,65 @@ use CommandError::InvalidConsumption;
 const NAMES: [&str; 1] = ["clear"];
 const DESCRIPTION: &str = "Clear yours or targets inventory.";
 
-const ARG_TARGET: &str = "target";
+const ARG_TARGET: &str = "targets";
+const ARG_ITEM: &str = "item";
 
-async fn clear_player(target: &Player) -> usize {
-    let mut inventory = target.inventory().lock().await;
+fn max_count_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name("maxCount").min(0)
+}
 
-    let slots = inventory.all_slots();
-    let items_count = slots
-        .iter()
-        .filter_map(|slot| slot.as_ref().map(|slot| slot.item_count as usize))
-        .sum();
+/// Removes the items matching `item`, or all items without one, and returns how many matched.
+/// At most `max_count` items are removed, and with a `max_count` of 0 they are only counted.
+fn clear_slots(
+    slots: Box<[&mut Option<ItemStack>]>,
+    item: Option<&Item>,
+    max_count: Option<usize>,
+) -> usize {
+    let mut matched = 0;
     for slot in slots {
-        *slot = None;
+        let Some(stack) = slot.as_mut() else {
+            continue;
+        };
+        if item.is_some_and(|item| item.id != stack.item.id) {
+            continue;
+        }
+        let count = usize::from(stack.item_count);
+        let removed = match max_count {
+            Some(0) => {
+                matched += count;
+                continue;
+            }
+            Some(max_count) => count.min(max_count - matched),
+            None => count,
+        };
+        matched += removed;
+        if removed == count {
+            *slot = None;
+        } else {
+            stack.item_count -= removed as u8;
+        }
+        if max_count == Some(matched) {
+            break;
+        }
     }
+    matched
+}
+
+async fn clear_player(target: &Player, item: Option<&Item>, max_count: Option<usize>) -> usize {
+    let mut inventory = target.inventory().lock().await;
+    let items_count = clear_slots(inventory.all_slots(), item, max_count);
     drop(inventory);
-    target.set_container_content(None).await;
+    if items_count > 0 && max_count != Some(0) {
+        target.set_container_content(None).await;
+    }
     items_count
 }
 
-fn clear_command_text_output(item_count: usize, targets: &[Arc<Player>]) -> TextComponent {
+fn clear_command_text_output(
+    item_count: usize,
+    targets: &[Arc<Player>],
+    testing: bool,
+) -> TextComponent {
     match targets {
         [target] if item_count == 0 => TextComponent::translate(
             "clear.failed.single",
@@ -44,7 +88,11 @@ fn clear_command_text_output(item_count: usize, targets: &[Arc<Player>]) -> Text
         )
         .color_named(NamedColor::Red),
         [target] => TextComponent::translate(
-            "commands.clear.success.single",
+            if testing {
+                "commands.clear.test.single"
+            } else {
+                "commands.clear.success.single"
+            },
             [
                 TextComponent::text(item_count.to_string()),
                 TextComponent::text(target.gameprofile.name.clone())
@@ -64,7 +112,11 @@ fn clear_command_text_output(item_count: usize, targets: &[Arc<Player>]) -> Text
         )
         .color_named(NamedColor::Red),
         targets => TextComponent::translate(
-            "commands.clear.success.multiple",
+            if testing {
+                "commands.clear.test.multiple"
+            } else {
+                "commands.clear.success.multiple"
+            },
             [
                 TextComponent::text(item_count.to_string()),
                 TextComponent::text(targets.len().to_string()),
@@ -83,16 +135,37 @@ impl CommandExecutor for Executor {
         _server: &crate::server::Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        let Some(Arg::Entities(targets)) = args.get(&ARG_TARGET) else {
-            return Err(InvalidConsumption(Some(ARG_TARGET.into())));
+        let targets = match args.get(&ARG_TARGET) {
+            Some(Arg::Entities(targets)) => targets.clone(),
+            Some(_) => return Err(InvalidConsumption(Some(ARG_TARGET.into()))),
+            None => vec![sender.as_player().ok_or(CommandError::InvalidRequirement)?],
+        };
+        let item = if args.contains_key(ARG_ITEM) {
+            Some(ItemArgumentConsumer::find_arg(args, ARG_ITEM)?.1)
+        } else {
+            None
+        };
+        let max_count = match max_count_consumer().find_arg_default_name(args) {
+            Err(_) => None,
+            Ok(Ok(count)) => Some(count as usize),
+            Ok(Err(NotInBounds::LowerBound(_, min))) => {
+                return Err(CommandError::GeneralCommandIssue(format!(
+                    "Integer must not be less than {min}"
+                )));
+            }
+            Ok(Err(NotInBounds::UpperBound(_, max))) => {
+                return Err(CommandError::GeneralCommandIssue(format!(
+                    "Integer must not be more than {max}"
+                )));
+            }
         };
 
         let mut item_count = 0;
-        for target in targets {
-            item_count += clear_player(target).await;
+        for target in &targets {
+            item_count += clear_player(target, item.as_ref(), max_count).await;
         }
 
-        let msg = clear_command_text_output(item_count, targets);
+        let msg = clear_command_text_output(item_count, &targets, max_count == Some(0));
 
         sender.send_message(msg).await;
 
@@ -100,32 +173,74 @@ impl CommandExecutor for Executor {
     }
 }
 
-struct SelfExecutor;
+#[allow(clippy::redundant_closure_for_method_calls)] // causes lifetime issues
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            argument(ARG_TARGET, EntitiesArgumentConsumer)
+                .then(
+                    argument(ARG_ITEM, ItemArgumentConsumer)
+                        .then(argument_default_name(max_count_consumer()).execute(Executor))
+                        .execute(Executor),
+                )
+                .execute(Executor),
+        )
+        .then(require(|sender| sender.is_player()).execute(Executor))
+}
 
-#[async_trait]
-impl CommandExecutor for SelfExecutor {
-    async fn execute<'a>(
-        &self,
-        sender: &mut CommandSender,
-        _server: &crate::server::Server,
-        _args: &ConsumedArgs<'a>,
-    ) -> Result<(), CommandError> {
-        let target = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+#[cfg(test)]
+mod test {
+    use pumpkin_data::item::Item;
+    use pumpkin_world::item::ItemStack;
 
-        let item_count = clear_player(&target).await;
+    use super::clear_slots;
 
-        let hold_target = [target];
-        let msg = clear_command_text_output(item_count, &hold_target);
+    fn stacks() -> Vec<Option<ItemStack>> {
+        vec![
+            Some(ItemStack::new(10, Item::DIRT)),
+            None,
+            Some(ItemStack::new(5, Item::STONE)),
+            Some(ItemStack::new(7, Item::DIRT)),
+        ]
+    }
 
-        sender.send_message(msg).await;
+    fn slots(inventory: &mut [Option<ItemStack>]) -> Box<[&mut Option<ItemStack>]> {
+        inventory.iter_mut().collect()
+    }
 
-        Ok(())
+    #[test]
+    fn clearing_an_item_only_removes_matching_stacks() {
+        let mut inventory = stacks();
+        assert_eq!(
+            clear_slots(slots(&mut inventory), Some(&Item::DIRT), None),
+            17
+        );
+        assert!(inventory[0].is_none() && inventory[3].is_none());
+        assert_eq!(inventory[2].as_ref().unwrap().item_count, 5);
+
+        // A max count removes only part of the items
+        let mut inventory = stacks();
+        assert_eq!(
+            clear_slots(slots(&mut inventory), Some(&Item::DIRT), Some(12)),
+            12
+        );
+        assert!(inventory[0].is_none());
+        assert_eq!(inventory[3].as_ref().unwrap().item_count, 5);
+
+        let mut inventory = stacks();
+        assert_eq!(clear_slots(slots(&mut inventory), None, None), 22);
+        assert!(inventory.iter().all(Option::is_none));
     }
-}
 
-#[allow(clippy::redundant_closure_for_method_calls)] // causes lifetime issues
-pub fn init_command_tree() -> CommandTree {
-    CommandTree::new(NAMES, DESCRIPTION)
-        .then(argument(ARG_TARGET, EntitiesArgumentConsumer).execute(Executor))
-        .then(require(|sender| sender.is_player()).execute(SelfExecutor))
+    #[test]
+    fn max_count_zero_only_counts() {
+        let mut inventory = stacks();
+        assert_eq!(
+            clear_slots(slots(&mut inventory), Some(&Item::DIRT), Some(0)),
+            17
+        );
+        assert_eq!(inventory[0].as_ref().unwrap().item_count, 10);
+        assert_eq!(inventory[3].as_ref().unwrap().item_count, 7);
+        assert_eq!(clear_slots(slots(&mut inventory), None, Some(0)), 22);
+    }
 }
@@ -17,6 +17,7 @@ use crate::command::{
         builder::{argument, literal},
     },
 };
+use crate::entity::EntityBase;
 
 const NAMES: [&str; 1] = ["damage"];
 const DESCRIPTION: &str = "Deals damage to entities";
@@ -91,7 +92,6 @@ impl CommandExecutor for LocationExecutor {
         let location = Position3DArgumentConsumer::find_arg(args, ARG_LOCATION)?;
 
         let success = target
-            .living_entity
             .damage_with_context(amount, damage_type, Some(location), None, None)
             .await;
 
@@ -136,7 +136,6 @@ impl CommandExecutor for EntityExecutor {
         };
 
         let success = target
-            .living_entity
             .damage_with_context(
                 amount,
                 damage_type,
@@ -0,0 +1,383 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_nbt::path::NbtPathError;
+use pumpkin_nbt::tag::NbtTag;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::{Color, NamedColor};
+use pumpkin_world::block::entities::{BlockEntity, block_entity_from_nbt};
+
+use crate::command::args::any_entity::AnyEntityArgumentConsumer;
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::nbt_path::NbtPathArgumentConsumer;
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{NonLeafNodeBuilder, argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::{Entity, EntityBase, NBTStorage, mob};
+use crate::server::Server;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["data"];
+
+const DESCRIPTION: &str = "Modifies the NBT data of block entities and entities.";
+
+const ARG_TARGET: &str = "target";
+const ARG_TARGET_POS: &str = "targetPos";
+const ARG_TARGET_PATH: &str = "targetPath";
+const ARG_INDEX: &str = "index";
+const ARG_SOURCE_POS: &str = "sourcePos";
+const ARG_SOURCE: &str = "source";
+const ARG_SOURCE_PATH: &str = "sourcePath";
+
+/// Keys of block entity data which the block entity is identified by and can't be modified.
+const BLOCK_ENTITY_KEYS: [&str; 4] = ["id", "x", "y", "z"];
+
+fn index_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_INDEX)
+}
+
+#[derive(Clone, Copy)]
+enum Operation {
+    Set,
+    Append,
+    Prepend,
+    Insert,
+    Merge,
+}
+
+#[derive(Clone, Copy)]
+enum Source {
+    Block,
+    Entity,
+}
+
+fn error_message(err: NbtPathError) -> TextComponent {
+    let message = match err {
+        NbtPathError::NothingFound(path) => TextComponent::translate(
+            "arguments.nbtpath.nothing_found",
+            [TextComponent::text(path)],
+        ),
+        NbtPathError::ExpectedList => {
+            TextComponent::translate("commands.data.modify.expected_list", [])
+        }
+        NbtPathError::ExpectedCompound => {
+            TextComponent::translate("commands.data.modify.expected_object", [])
+        }
+        NbtPathError::InvalidIndex(index) => TextComponent::translate(
+            "commands.data.modify.invalid_index",
+            [TextComponent::text(index.to_string())],
+        ),
+        // Vanilla leaves the list as it is, so nothing changed
+        NbtPathError::WrongListType => TextComponent::translate("commands.data.merge.failed", []),
+        NbtPathError::Invalid(position) => TextComponent::translate(
+            "arguments.nbtpath.node.invalid",
+            [TextComponent::text(position.to_string())],
+        ),
+    };
+    message.color(Color::Named(NamedColor::Red))
+}
+
+/// What `/data modify` changes
+#[derive(Clone, Copy)]
+enum Target {
+    Block,
+    Entity,
+}
+
+/// A target of `/data modify` which exists
+enum TargetData {
+    Block(Arc<World>, BlockPos, Arc<dyn BlockEntity>),
+    Entity(Arc<dyn EntityBase>),
+}
+
+fn invalid(key: &'static str) -> TextComponent {
+    TextComponent::translate(key, []).color(Color::Named(NamedColor::Red))
+}
+
+impl TargetData {
+    async fn nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        match self {
+            Self::Block(_, _, block_entity) => block_entity.write_internal(&mut nbt),
+            Self::Entity(entity) => entity.nbt_storage().write_nbt(&mut nbt).await,
+        }
+        nbt
+    }
+
+    /// Replaces the data of the target with `nbt`, returning the message for the sender.
+    async fn apply(self, mut nbt: NbtCompound) -> TextComponent {
+        match self {
+            Self::Block(world, pos, block_entity) => {
+                // The block entity stays where and what it is
+                nbt.child_tags
+                    .retain(|(key, _)| !BLOCK_ENTITY_KEYS.contains(&key.as_str()));
+                nbt.put_string("id", block_entity.identifier().to_string());
+                nbt.put_int("x", pos.0.x);
+                nbt.put_int("y", pos.0.y);
+                nbt.put_int("z", pos.0.z);
+                let Some(block_entity) = block_entity_from_nbt(&nbt) else {
+                    return invalid("commands.data.block.invalid");
+                };
+                world.add_block_entity(block_entity).await;
+                TextComponent::translate(
+                    "commands.data.block.modified",
+                    [
+                        TextComponent::text(pos.0.x.to_string()),
+                        TextComponent::text(pos.0.y.to_string()),
+                        TextComponent::text(pos.0.z.to_string()),
+                    ],
+                )
+            }
+            Self::Entity(entity) => {
+                let base = entity.get_entity();
+                if base.entity_type == EntityType::PLAYER {
+                    return invalid("commands.data.entity.invalid");
+                }
+                if entity.get_mob_entity().is_some() {
+                    // Like block entities, mobs are replaced by one with the new data
+                    let world = base.world.read().await.clone();
+                    let mut mob = mob::from_entity(Entity::new(
+                        base.entity_uuid,
+                        world.clone(),
+                        base.pos.load(),
+                        base.entity_type,
+                        false,
+                    ))
+                    .await;
+                    mob.read_nbt(&mut nbt).await;
+                    world.remove_entity(base).await;
+                    world.spawn_entity(Arc::new(mob)).await;
+                } else {
+                    base.load_nbt(&nbt);
+                    base.teleport(base.pos.load(), base.yaw.load(), base.pitch.load())
+                        .await;
+                    base.set_velocity(base.velocity.load()).await;
+                }
+                TextComponent::translate(
+                    "commands.data.entity.modified",
+                    [TextComponent::text(base.entity_type.resource_name)],
+                )
+            }
+        }
+    }
+}
+
+struct Executor {
+    target: Target,
+    operation: Operation,
+    source: Source,
+}
+
+impl Executor {
+    async fn find_target(
+        &self,
+        sender: &CommandSender,
+        args: &ConsumedArgs<'_>,
+    ) -> Result<Result<TargetData, TextComponent>, CommandError> {
+        match self.target {
+            Target::Block => {
+                let pos = BlockPosArgumentConsumer::find_arg(args, ARG_TARGET_POS)?;
+                let world = sender
+                    .world()
+                    .await
+                    .ok_or(CommandError::InvalidRequirement)?;
+                Ok(match world.get_block_entity(&pos).await {
+                    Some(block_entity) => Ok(TargetData::Block(world, pos, block_entity)),
+                    None => Err(invalid("commands.data.block.invalid")),
+                })
+            }
+            Target::Entity => Ok(Ok(TargetData::Entity(AnyEntityArgumentConsumer::find_arg(
+                args, ARG_TARGET,
+            )?))),
+        }
+    }
+
+    async fn source_values(
+        &self,
+        sender: &CommandSender,
+        args: &ConsumedArgs<'_>,
+    ) -> Result<Result<Vec<NbtTag>, TextComponent>, CommandError> {
+        let mut nbt = NbtCompound::new();
+        match self.source {
+            Source::Block => {
+                let pos = BlockPosArgumentConsumer::find_arg(args, ARG_SOURCE_POS)?;
+                let world = sender
+                    .world()
+                    .await
+                    .ok_or(CommandError::InvalidRequirement)?;
+                let Some(block_entity) = world.get_block_entity(&pos).await else {
+                    return Ok(Err(invalid("commands.data.block.invalid")));
+                };
+                block_entity.write_internal(&mut nbt);
+            }
+            Source::Entity => {
+                let entity = AnyEntityArgumentConsumer::find_arg(args, ARG_SOURCE)?;
+                entity.nbt_storage().write_nbt(&mut nbt).await;
+            }
+        }
+        let root = NbtTag::Compound(nbt);
+        // Without a path the source's whole data is used
+        let Ok(path) = NbtPathArgumentConsumer::find_arg(args, ARG_SOURCE_PATH) else {
+            return Ok(Ok(vec![root]));
+        };
+        Ok(path
+            .get(&root)
+            .map(|values| values.into_iter().cloned().collect())
+            .map_err(error_message))
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let path = NbtPathArgumentConsumer::find_arg(args, ARG_TARGET_PATH)?;
+        let target = match self.find_target(sender, args).await? {
+            Ok(target) => target,
+            Err(message) => {
+                sender.send_message(message).await;
+                return Ok(());
+            }
+        };
+
+        let values = match self.source_values(sender, args).await? {
+            Ok(values) => values,
+            Err(message) => {
+                sender.send_message(message).await;
+                return Ok(());
+            }
+        };
+
+        let mut root = NbtTag::Compound(target.nbt().await);
+        let result = match self.operation {
+            Operation::Set => match values.last() {
+                Some(value) => path.set(&mut root, value),
+                None => Ok(0),
+            },
+            Operation::Append => path.insert(&mut root, -1, &values),
+            Operation::Prepend => path.insert(&mut root, 0, &values),
+            Operation::Insert => {
+                let Ok(index) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_INDEX)?
+                else {
+                    sender.send_message(invalid("parsing.int.invalid")).await;
+                    return Ok(());
+                };
+                path.insert(&mut root, index, &values)
+            }
+            Operation::Merge => path.merge(&mut root, &values),
+        };
+        if let Err(err) = result {
+            sender.send_message(error_message(err)).await;
+            return Ok(());
+        }
+
+        let NbtTag::Compound(nbt) = root else {
+            unreachable!("the path can't replace the root")
+        };
+        sender.send_message(target.apply(nbt).await).await;
+        Ok(())
+    }
+}
+
+fn from(target: Target, operation: Operation) -> NonLeafNodeBuilder {
+    let executor = |source| Executor {
+        target,
+        operation,
+        source,
+    };
+    literal("from")
+        .then(
+            literal("block").then(
+                argument(ARG_SOURCE_POS, BlockPosArgumentConsumer)
+                    .execute(executor(Source::Block))
+                    .then(
+                        argument(ARG_SOURCE_PATH, NbtPathArgumentConsumer)
+                            .execute(executor(Source::Block)),
+                    ),
+            ),
+        )
+        .then(
+            literal("entity").then(
+                argument(ARG_SOURCE, AnyEntityArgumentConsumer)
+                    .execute(executor(Source::Entity))
+                    .then(
+                        argument(ARG_SOURCE_PATH, NbtPathArgumentConsumer)
+                            .execute(executor(Source::Entity)),
+                    ),
+            ),
+        )
+}
+
+fn modify(target: Target) -> NonLeafNodeBuilder {
+    argument(ARG_TARGET_PATH, NbtPathArgumentConsumer)
+        .then(literal("set").then(from(target, Operation::Set)))
+        .then(literal("append").then(from(target, Operation::Append)))
+        .then(literal("prepend").then(from(target, Operation::Prepend)))
+        .then(
+            literal("insert")
+                .then(argument(ARG_INDEX, index_consumer()).then(from(target, Operation::Insert))),
+        )
+        .then(literal("merge").then(from(target, Operation::Merge)))
+}
+
+// TODO: Add get, merge and remove
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        literal("modify")
+            .then(literal("block").then(
+                argument(ARG_TARGET_POS, BlockPosArgumentConsumer).then(modify(Target::Block)),
+            ))
+            .then(literal("entity").then(
+                argument(ARG_TARGET, AnyEntityArgumentConsumer).then(modify(Target::Entity)),
+            )),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use crate::entity::mob;
+    use crate::test_utils::{run_console_command, test_server};
+
+    #[tokio::test]
+    async fn entity_data_is_copied_from_another_entity() {
+        let server = test_server();
+        let world = server.worlds.read().await[0].clone();
+        let pig = mob::from_type(EntityType::PIG, Vector3::new(0.5, 64.0, 0.5), &world).await;
+        let cow = mob::from_type(EntityType::COW, Vector3::new(4.5, 64.0, 4.5), &world).await;
+        pig.get_entity().set_rotation(90.0, 30.0);
+        world.spawn_entity(pig.clone()).await;
+        world.spawn_entity(cow.clone()).await;
+        let pig_uuid = pig.get_entity().entity_uuid;
+        let cow_uuid = cow.get_entity().entity_uuid;
+
+        let command =
+            format!("data modify entity {cow_uuid} Rotation set from entity {pig_uuid} Rotation");
+        let output = run_console_command(&server, &command).await;
+
+        let cow = world.entities.read().await[&cow_uuid].clone();
+        assert_eq!(cow.get_entity().entity_type, EntityType::COW);
+        assert_eq!(cow.get_entity().yaw.load(), 90.0);
+        assert_eq!(cow.get_entity().pitch.load(), 30.0);
+        assert!(output[0].contains("Modified entity data"));
+
+        // A list of floats can't take a double
+        let command =
+            format!("data modify entity {cow_uuid} Rotation append from entity {pig_uuid} Pos[0]");
+        let output = run_console_command(&server, &command).await;
+        assert!(output[0].contains("Nothing changed"));
+        let cow = world.entities.read().await[&cow_uuid].clone();
+        assert_eq!(cow.get_entity().yaw.load(), 90.0);
+    }
+}
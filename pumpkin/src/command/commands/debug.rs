@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+
+use crate::command::CommandSender::Player as PlayerSender;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::{Arg, ConsumedArgs};
+use crate::command::dispatcher::CommandError;
+use crate::command::dispatcher::CommandError::{InvalidConsumption, InvalidRequirement};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{NonLeafNodeBuilder, argument, literal, require};
+use crate::command::{CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["debug"];
+
+const DESCRIPTION: &str = "Debugging tools for server developers.";
+
+const ARG_TARGETS: &str = "targets";
+
+struct PacketLogExecutor {
+    enable: bool,
+    target_self: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for PacketLogExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets: Vec<Arc<Player>> = if self.target_self {
+            let PlayerSender(player) = sender else {
+                return Err(InvalidRequirement);
+            };
+            vec![player.clone()]
+        } else {
+            let Some(Arg::Players(targets)) = args.get(ARG_TARGETS) else {
+                return Err(InvalidConsumption(Some(ARG_TARGETS.into())));
+            };
+            targets.clone()
+        };
+
+        for target in targets {
+            let name = &target.gameprofile.name;
+            let log = &target.client.packet_log;
+            let msg = if self.enable {
+                match log.enable_file(name) {
+                    Ok(path) => TextComponent::text(format!(
+                        "Logging packets of {name} to {}",
+                        path.display()
+                    )),
+                    Err(err) => {
+                        return Err(CommandError::GeneralCommandIssue(format!(
+                            "Failed to create packet log for {name}: {err}"
+                        )));
+                    }
+                }
+            } else {
+                log.disable().await;
+                TextComponent::text(format!("Stopped logging packets of {name}"))
+            };
+            sender.send_message(msg.color_named(NamedColor::Gray)).await;
+        }
+
+        Ok(())
+    }
+}
+
+fn packet_log_toggle(name: &str, enable: bool) -> NonLeafNodeBuilder {
+    literal(name)
+        .then(
+            require(|sender| sender.is_player()).execute(PacketLogExecutor {
+                enable,
+                target_self: true,
+            }),
+        )
+        .then(
+            argument(ARG_TARGETS, PlayersArgumentConsumer).execute(PacketLogExecutor {
+                enable,
+                target_self: false,
+            }),
+        )
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)]
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        literal("packetlog")
+            .then(packet_log_toggle("on", true))
+            .then(packet_log_toggle("off", false)),
+    )
+}
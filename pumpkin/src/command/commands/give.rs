@@ -18,6 +18,9 @@ const DESCRIPTION: &str = "Give items to player(s).";
 
 const ARG_ITEM: &str = "item";
 
+/// How many stacks of an item can be given at once
+const MAX_STACKS: i32 = 100;
+
 fn item_count_consumer() -> BoundedNumArgumentConsumer<i32> {
     BoundedNumArgumentConsumer::new()
         .name("count")
@@ -59,6 +62,24 @@ impl CommandExecutor for Executor {
             }
         };
 
+        // Whatever doesn't fit into the inventory gets dropped, so don't flood the world with items
+        let max_count = i32::from(item.components.max_stack_size) * MAX_STACKS;
+        if item_count > max_count {
+            sender
+                .send_message(
+                    TextComponent::translate(
+                        "commands.give.failed.toomanyitems",
+                        [
+                            TextComponent::text(max_count.to_string()),
+                            item.translated_name(),
+                        ],
+                    )
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        }
+
         for target in targets {
             target.give_items(item.clone(), item_count as u32).await;
         }
@@ -124,20 +124,17 @@ impl CommandExecutor for BaseHelpExecutor {
         };
 
         let dispatcher = server.command_dispatcher.read().await;
-        let mut commands: Vec<&CommandTree> = dispatcher
-            .commands
-            .values()
-            .filter_map(|cmd| match cmd {
-                Command::Tree(tree) => Some(tree),
-                Command::Alias(_) => None,
-            })
-            .filter(|tree| {
-                dispatcher
-                    .permissions
-                    .get(&tree.names[0])
-                    .is_none_or(|perm| sender.has_permission_lvl(*perm))
-            })
-            .collect();
+        let mut commands: Vec<&CommandTree> = Vec::new();
+        for tree in dispatcher.commands.values().filter_map(|cmd| match cmd {
+            Command::Tree(tree) => Some(tree),
+            Command::Alias(_) => None,
+        }) {
+            if !dispatcher.permissions.contains_key(&tree.names[0])
+                || dispatcher.has_permission(sender, &tree.names[0]).await
+            {
+                commands.push(tree);
+            }
+        }
 
         commands.sort_by(|a, b| a.names[0].cmp(&b.names[0]));
 
@@ -56,3 +56,35 @@ fn get_player_names(players: Vec<Arc<Player>>) -> String {
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).execute(Executor)
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::BASIC_CONFIG;
+
+    use crate::test_utils::{run_console_command, test_player, test_server};
+
+    #[tokio::test]
+    async fn lists_online_players() {
+        let server = test_server();
+        let max = BASIC_CONFIG.max_players;
+        assert_eq!(
+            run_console_command(&server, "list").await,
+            [format!("There are 0 of a max of {max} players online: ")]
+        );
+
+        let (_alice, _alice_packets) = test_player(&server, "alice").await;
+        let (_bob, _bob_packets) = test_player(&server, "bob").await;
+        let output = run_console_command(&server, "list").await;
+        // Players aren't listed in any particular order
+        assert!(
+            output
+                == [format!(
+                    "There are 2 of a max of {max} players online: alice, bob"
+                )]
+                || output
+                    == [format!(
+                        "There are 2 of a max of {max} players online: bob, alice"
+                    )]
+        );
+    }
+}
@@ -0,0 +1,559 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_data::{Block, block_properties::get_block, entity::EntityType};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_util::{loot_table::LootTable, math::position::BlockPos, text::TextComponent};
+use pumpkin_world::{
+    BlockStateId, block::entities::shulker_box::ShulkerBoxBlockEntity, item::ItemStack,
+};
+use rand::Rng;
+
+use crate::block::loot::{LootContext, LootTableExt};
+use crate::command::args::bounded_num::{BoundedNumArgumentConsumer, NotInBounds};
+use crate::command::args::entity::EntityArgumentConsumer;
+use crate::command::args::item_slot::{ItemSlotArgumentConsumer, player_inventory_slot};
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::position_3d::Position3DArgumentConsumer;
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::resource::item::ItemArgumentConsumer;
+use crate::command::args::resource_location::ResourceLocationArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{NonLeafNodeBuilder, argument, argument_default_name, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::item::ItemEntity;
+use crate::server::Server;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["loot"];
+
+const DESCRIPTION: &str = "Drops items from a loot table.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_TARGET_POS: &str = "targetPos";
+const ARG_SLOT: &str = "slot";
+const ARG_LOOT_TABLE: &str = "loot_table";
+const ARG_ENTITY: &str = "entity";
+const ARG_POS: &str = "pos";
+const ARG_TOOL: &str = "tool";
+
+fn count_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name("count").min(0)
+}
+
+/// Where the rolled items go.
+#[derive(Clone, Copy)]
+enum Target {
+    Give,
+    Spawn,
+    /// Into the free space of a container
+    Insert,
+    /// Into consecutive slots of players, replacing what was there
+    ReplaceEntity,
+    /// Into consecutive slots of a container, replacing what was there
+    ReplaceBlock,
+}
+
+/// What the block is mined or the fish is caught with.
+#[derive(Clone, Copy)]
+enum Tool {
+    Hand,
+    Item,
+    MainHand,
+    OffHand,
+}
+
+/// Which loot table is rolled.
+#[derive(Clone, Copy)]
+enum Source {
+    Loot,
+    Kill,
+    Mine(Tool),
+    Fish(Tool),
+}
+
+/// The loot table with the given id. Only block loot tables are known to the server.
+fn find_loot_table(id: &str) -> Option<LootTable> {
+    let path = id.strip_prefix("minecraft:").unwrap_or(id);
+    get_block(path.strip_prefix("blocks/")?)?.loot_table
+}
+
+/// Rolls the loot table, leaving out the stacks which ended up empty.
+fn roll_loot(table: &LootTable, rng: &mut impl Rng, context: &LootContext) -> Vec<ItemStack> {
+    table
+        .get_loot(rng, context)
+        .into_iter()
+        .filter(|stack| stack.item_count > 0)
+        .collect()
+}
+
+/// The drops of a block mined with `tool`, `None` if the block has no loot table.
+fn block_loot(
+    block: &Block,
+    state_id: BlockStateId,
+    tool: Option<&ItemStack>,
+    rng: &mut impl Rng,
+) -> Option<Vec<ItemStack>> {
+    let table = block.loot_table.as_ref()?;
+    let props = block
+        .properties(state_id)
+        .map_or_else(Vec::new, |props| props.to_props());
+    Some(roll_loot(table, rng, &LootContext::block(&props, tool)))
+}
+
+/// Fills `count` slots with the stacks in order, the slots left over are emptied.
+fn replacements(stacks: &[ItemStack], count: usize) -> impl Iterator<Item = ItemStack> + '_ {
+    stacks
+        .iter()
+        .cloned()
+        .chain(std::iter::repeat(ItemStack::EMPTY))
+        .take(count)
+}
+
+/// Adds the stacks to matching stacks first, then to empty slots. What doesn't fit is lost.
+fn insert_stacks(slots: &mut [ItemStack], stacks: &[ItemStack]) {
+    for stack in stacks {
+        let mut remaining = stack.item_count;
+        for slot in slots.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            if !slot.is_empty() && slot.are_items_and_components_equal(stack) {
+                let added =
+                    remaining.min(slot.get_max_stack_size().saturating_sub(slot.item_count));
+                slot.increment(added);
+                remaining -= added;
+            }
+        }
+        for slot in slots.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+            if slot.is_empty() {
+                let added = remaining.min(stack.get_max_stack_size());
+                *slot = stack.copy_with_count(added);
+                remaining -= added;
+            }
+        }
+    }
+}
+
+struct Executor(Target, Source);
+
+impl Executor {
+    async fn tool(
+        sender: &CommandSender,
+        args: &ConsumedArgs<'_>,
+        tool: Tool,
+    ) -> Result<Option<ItemStack>, CommandError> {
+        Ok(match tool {
+            Tool::Hand => None,
+            Tool::Item => Some(ItemStack::new(
+                1,
+                ItemArgumentConsumer::find_arg(args, ARG_TOOL)?.1,
+            )),
+            Tool::MainHand | Tool::OffHand => {
+                let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+                let inventory = player.inventory().lock().await;
+                let held = if matches!(tool, Tool::MainHand) {
+                    inventory.held_item()
+                } else {
+                    inventory.offhand_slot()
+                };
+                held.cloned()
+            }
+        })
+    }
+
+    async fn roll(
+        &self,
+        sender: &mut CommandSender,
+        args: &ConsumedArgs<'_>,
+    ) -> Result<Option<Vec<ItemStack>>, CommandError> {
+        match self.1 {
+            Source::Loot => {
+                let id = ResourceLocationArgumentConsumer::find_arg(args, ARG_LOOT_TABLE)?;
+                let table = find_loot_table(id).ok_or_else(|| {
+                    CommandError::GeneralCommandIssue(format!("Unknown loot table: {id}"))
+                })?;
+                let context = LootContext::default();
+                Ok(Some(roll_loot(&table, &mut rand::thread_rng(), &context)))
+            }
+            Source::Kill => {
+                // Only players can be selected so far, and they have no loot table
+                let entity = EntityArgumentConsumer::find_arg(args, ARG_ENTITY)?;
+                sender
+                    .send_message(TextComponent::translate(
+                        "commands.drop.no_loot_table",
+                        [TextComponent::text(entity.gameprofile.name.clone())],
+                    ))
+                    .await;
+                Ok(None)
+            }
+            Source::Mine(tool) => {
+                let pos = BlockPosArgumentConsumer::find_arg(args, ARG_POS)?;
+                let tool = Self::tool(sender, args, tool).await?;
+                let world = sender
+                    .world()
+                    .await
+                    .ok_or(CommandError::InvalidRequirement)?;
+                let state_id = world
+                    .get_block_state_id(&pos)
+                    .await
+                    .map_err(|error| CommandError::OtherPumpkin(error.into()))?;
+                let block = Block::from_state_id(state_id).unwrap_or(Block::AIR);
+                let loot = block_loot(&block, state_id, tool.as_ref(), &mut rand::thread_rng());
+                if loot.is_none() {
+                    sender
+                        .send_message(TextComponent::translate(
+                            "commands.drop.no_loot_table.block",
+                            [TextComponent::translate(block.translation_key, [])],
+                        ))
+                        .await;
+                }
+                Ok(loot)
+            }
+            Source::Fish(tool) => {
+                let id = ResourceLocationArgumentConsumer::find_arg(args, ARG_LOOT_TABLE)?;
+                let table = find_loot_table(id).ok_or_else(|| {
+                    CommandError::GeneralCommandIssue(format!("Unknown loot table: {id}"))
+                })?;
+                let tool = Self::tool(sender, args, tool).await?;
+                // Luck of the Sea makes the rarer pools more likely
+                let luck = tool
+                    .as_ref()
+                    .and_then(|tool| tool.patch.enchantment_level("luck_of_the_sea"))
+                    .unwrap_or(0);
+                let context = LootContext {
+                    tool: tool.as_ref(),
+                    luck: luck as f32,
+                    ..Default::default()
+                };
+                Ok(Some(roll_loot(&table, &mut rand::thread_rng(), &context)))
+            }
+        }
+    }
+
+    /// The number of slots to replace, all rolled stacks by default.
+    fn replace_count(args: &ConsumedArgs<'_>, stacks: &[ItemStack]) -> Result<usize, CommandError> {
+        match count_consumer().find_arg_default_name(args) {
+            Err(_) => Ok(stacks.len()),
+            Ok(Ok(count)) => Ok(count as usize),
+            Ok(Err(NotInBounds::LowerBound(_, min))) => Err(CommandError::GeneralCommandIssue(
+                format!("Integer must not be less than {min}"),
+            )),
+            Ok(Err(NotInBounds::UpperBound(_, max))) => Err(CommandError::GeneralCommandIssue(
+                format!("Integer must not be more than {max}"),
+            )),
+        }
+    }
+
+    /// The contents of the container at `pos`, `None` if there is none.
+    async fn container(
+        sender: &CommandSender,
+        world: &World,
+        pos: BlockPos,
+    ) -> Option<[ItemStack; ShulkerBoxBlockEntity::SLOTS]> {
+        // Shulker boxes are the only block entities with contents so far
+        if let Some(block_entity) = world.get_block_entity(&pos).await {
+            if block_entity.identifier() == ShulkerBoxBlockEntity::ID {
+                let mut nbt = NbtCompound::new();
+                block_entity.write_nbt(&mut nbt);
+                return Some(ShulkerBoxBlockEntity::read_items(&nbt));
+            }
+        }
+        sender
+            .send_message(TextComponent::translate(
+                "commands.item.target.not_a_container",
+                [
+                    TextComponent::text(pos.0.x.to_string()),
+                    TextComponent::text(pos.0.y.to_string()),
+                    TextComponent::text(pos.0.z.to_string()),
+                ],
+            ))
+            .await;
+        None
+    }
+
+    /// Puts the stacks where they should go, returns whether it worked.
+    async fn deliver(
+        &self,
+        sender: &CommandSender,
+        args: &ConsumedArgs<'_>,
+        stacks: &[ItemStack],
+    ) -> Result<bool, CommandError> {
+        match self.0 {
+            Target::Give => {
+                let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+                for target in targets {
+                    for stack in stacks {
+                        target
+                            .give_items(stack.item.clone(), u32::from(stack.item_count))
+                            .await;
+                    }
+                }
+            }
+            Target::Spawn => {
+                let pos = Position3DArgumentConsumer::find_arg(args, ARG_TARGET_POS)?;
+                let world = sender
+                    .world()
+                    .await
+                    .ok_or(CommandError::InvalidRequirement)?;
+                for stack in stacks {
+                    let entity = world.create_entity(pos, EntityType::ITEM);
+                    let item_entity = Arc::new(ItemEntity::from_stack(entity, stack.clone()).await);
+                    world.spawn_entity(item_entity.clone()).await;
+                    item_entity.send_meta_packet().await;
+                }
+            }
+            Target::Insert | Target::ReplaceBlock => {
+                let pos = BlockPosArgumentConsumer::find_arg(args, ARG_TARGET_POS)?;
+                let world = sender
+                    .world()
+                    .await
+                    .ok_or(CommandError::InvalidRequirement)?;
+                let Some(mut items) = Self::container(sender, &world, pos).await else {
+                    return Ok(false);
+                };
+                if matches!(self.0, Target::Insert) {
+                    insert_stacks(&mut items, stacks);
+                } else {
+                    let slot = ItemSlotArgumentConsumer::find_arg(args, ARG_SLOT)?;
+                    let count = Self::replace_count(args, stacks)?;
+                    let start = usize::try_from(slot)
+                        .ok()
+                        .filter(|slot| *slot < items.len());
+                    let Some(start) = start else {
+                        sender
+                            .send_message(TextComponent::translate(
+                                "commands.item.target.no_such_slot",
+                                [TextComponent::text(slot.to_string())],
+                            ))
+                            .await;
+                        return Ok(false);
+                    };
+                    for (slot, stack) in items[start..].iter_mut().zip(replacements(stacks, count))
+                    {
+                        *slot = stack;
+                    }
+                }
+                world
+                    .add_block_entity(Arc::new(ShulkerBoxBlockEntity::new(pos, items)))
+                    .await;
+            }
+            Target::ReplaceEntity => {
+                let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+                let slot = ItemSlotArgumentConsumer::find_arg(args, ARG_SLOT)?;
+                let count = Self::replace_count(args, stacks)?;
+                for target in targets {
+                    let mut inventory = target.inventory().lock().await;
+                    let held_slot = inventory.get_selected_slot();
+                    for (offset, stack) in (0..).zip(replacements(stacks, count)) {
+                        let Some(index) = player_inventory_slot(slot + offset, held_slot) else {
+                            break;
+                        };
+                        let stack = (!stack.is_empty()).then_some(stack);
+                        // Armor slots only take armor
+                        let Ok(accepts) = inventory.slot_condition(index) else {
+                            break;
+                        };
+                        if stack.as_ref().is_none_or(|stack| accepts(stack)) {
+                            if let Ok(slot) = inventory.get_slot(index) {
+                                *slot = stack;
+                            }
+                        }
+                    }
+                    drop(inventory);
+                    target.set_container_content(None).await;
+                }
+            }
+        }
+        Ok(true)
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(stacks) = self.roll(sender, args).await? else {
+            return Ok(());
+        };
+        if !self.deliver(sender, args, &stacks).await? {
+            return Ok(());
+        }
+
+        let message = if let [stack] = stacks.as_slice() {
+            TextComponent::translate(
+                "commands.drop.success.single",
+                [
+                    TextComponent::text(stack.item_count.to_string()),
+                    stack.item.translated_name(),
+                ],
+            )
+        } else {
+            TextComponent::translate(
+                "commands.drop.success.multiple",
+                [TextComponent::text(stacks.len().to_string())],
+            )
+        };
+        sender.send_message(message).await;
+
+        Ok(())
+    }
+}
+
+/// The ways to pick the tool, which come last.
+fn tools(pos: NonLeafNodeBuilder, kind: Target, source: fn(Tool) -> Source) -> NonLeafNodeBuilder {
+    pos.execute(Executor(kind, source(Tool::Hand)))
+        .then(literal("mainhand").execute(Executor(kind, source(Tool::MainHand))))
+        .then(literal("offhand").execute(Executor(kind, source(Tool::OffHand))))
+        .then(argument(ARG_TOOL, ItemArgumentConsumer).execute(Executor(kind, source(Tool::Item))))
+}
+
+fn sources(target: NonLeafNodeBuilder, kind: Target) -> NonLeafNodeBuilder {
+    target
+        .then(
+            literal("loot").then(
+                argument(ARG_LOOT_TABLE, ResourceLocationArgumentConsumer::new(false))
+                    .execute(Executor(kind, Source::Loot)),
+            ),
+        )
+        .then(literal("kill").then(
+            argument(ARG_ENTITY, EntityArgumentConsumer).execute(Executor(kind, Source::Kill)),
+        ))
+        .then(literal("mine").then(tools(
+            argument(ARG_POS, BlockPosArgumentConsumer),
+            kind,
+            Source::Mine,
+        )))
+        .then(literal("fish").then(
+            argument(ARG_LOOT_TABLE, ResourceLocationArgumentConsumer::new(false)).then(tools(
+                argument(ARG_POS, BlockPosArgumentConsumer),
+                kind,
+                Source::Fish,
+            )),
+        ))
+}
+
+/// The slot to replace, optionally followed by how many slots.
+fn replace_slots(target: NonLeafNodeBuilder, kind: Target) -> NonLeafNodeBuilder {
+    target.then(
+        sources(argument(ARG_SLOT, ItemSlotArgumentConsumer), kind)
+            .then(sources(argument_default_name(count_consumer()), kind)),
+    )
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("give").then(sources(
+            argument(ARG_TARGETS, PlayersArgumentConsumer),
+            Target::Give,
+        )))
+        .then(literal("spawn").then(sources(
+            argument(ARG_TARGET_POS, Position3DArgumentConsumer),
+            Target::Spawn,
+        )))
+        .then(literal("insert").then(sources(
+            argument(ARG_TARGET_POS, BlockPosArgumentConsumer),
+            Target::Insert,
+        )))
+        .then(
+            literal("replace")
+                .then(literal("entity").then(replace_slots(
+                    argument(ARG_TARGETS, PlayersArgumentConsumer),
+                    Target::ReplaceEntity,
+                )))
+                .then(literal("block").then(replace_slots(
+                    argument(ARG_TARGET_POS, BlockPosArgumentConsumer),
+                    Target::ReplaceBlock,
+                ))),
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, item::Item};
+    use pumpkin_world::item::ItemStack;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use crate::block::loot::LootContext;
+
+    use super::{block_loot, find_loot_table, insert_stacks, replacements, roll_loot};
+
+    #[test]
+    fn fixed_seed_rolls_the_same_loot() {
+        let table = find_loot_table("minecraft:blocks/chorus_plant").unwrap();
+        let roll = |seed| {
+            roll_loot(
+                &table,
+                &mut StdRng::seed_from_u64(seed),
+                &LootContext::default(),
+            )
+            .into_iter()
+            .map(|stack| (stack.item.id, stack.item_count))
+            .collect::<Vec<_>>()
+        };
+        let loot = roll(7);
+        assert_eq!(loot, roll(7));
+        // Chorus plants drop up to one chorus fruit
+        assert!(loot.len() <= 1);
+        assert!(
+            loot.iter()
+                .all(|&(item, count)| item == Item::CHORUS_FRUIT.id && count == 1)
+        );
+        // Some seed drops the fruit
+        assert!((0..32).any(|seed| !roll(seed).is_empty()));
+
+        assert!(find_loot_table("minecraft:blocks/not_a_block").is_none());
+        assert!(find_loot_table("minecraft:entities/zombie").is_none());
+    }
+
+    #[test]
+    fn silk_touch_mines_stone_itself() {
+        let mine = |tool: &ItemStack| {
+            block_loot(
+                &Block::STONE,
+                Block::STONE.default_state_id,
+                Some(tool),
+                &mut rand::thread_rng(),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|stack| (stack.item.id, stack.item_count))
+            .collect::<Vec<_>>()
+        };
+
+        let mut pickaxe = ItemStack::new(1, Item::IRON_PICKAXE);
+        assert_eq!(mine(&pickaxe), [(Item::COBBLESTONE.id, 1)]);
+
+        pickaxe.patch.enchantments = vec![("silk_touch".to_string(), 1)];
+        assert_eq!(mine(&pickaxe), [(Item::STONE.id, 1)]);
+    }
+
+    #[test]
+    fn loot_fills_container_slots() {
+        let mut slots = [
+            ItemStack::new(60, Item::COBBLESTONE),
+            ItemStack::EMPTY,
+            ItemStack::new(1, Item::DIRT),
+        ];
+        insert_stacks(&mut slots, &[ItemStack::new(10, Item::COBBLESTONE)]);
+        assert_eq!(slots[0].item_count, 64);
+        assert_eq!(
+            (slots[1].item.id, slots[1].item_count),
+            (Item::COBBLESTONE.id, 6)
+        );
+
+        // Replacing more slots than there are stacks empties the rest
+        let replaced: Vec<_> = replacements(&[ItemStack::new(1, Item::STONE)], 3).collect();
+        assert_eq!(replaced.len(), 3);
+        assert_eq!(replaced[0].item.id, Item::STONE.id);
+        assert!(replaced[1..].iter().all(ItemStack::is_empty));
+    }
+}
@@ -2,12 +2,15 @@ use pumpkin_util::PermissionLvl;
 
 use super::dispatcher::CommandDispatcher;
 
+mod attribute;
 mod ban;
 mod banip;
 mod banlist;
 mod bossbar;
 mod clear;
 mod damage;
+mod data;
+mod debug;
 pub mod defaultgamemode;
 mod deop;
 mod effect;
@@ -19,23 +22,37 @@ mod help;
 mod kick;
 mod kill;
 mod list;
+mod loot;
 mod me;
 mod msg;
 mod op;
 mod pardon;
 mod pardonip;
 mod particle;
+mod place;
 mod playsound;
 mod plugin;
 mod plugins;
+pub mod pregen;
 mod pumpkin;
+mod reload;
+mod reply;
+mod ride;
+mod saveall;
+mod saveoff;
+mod saveon;
 mod say;
+mod scoreboard;
 mod seed;
 mod setblock;
+mod setworldspawn;
+mod spawnpoint;
 mod stop;
 mod stopsound;
 mod summon;
+mod team;
 mod teleport;
+mod tick;
 mod time;
 mod title;
 mod transfer;
@@ -58,20 +75,29 @@ pub fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(transfer::init_command_tree(), PermissionLvl::Zero);
     dispatcher.register(me::init_command_tree(), PermissionLvl::Zero);
     dispatcher.register(msg::init_command_tree(), PermissionLvl::Zero);
+    dispatcher.register(reply::init_command_tree(), PermissionLvl::Zero);
     // Two
     dispatcher.register(kill::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(worldborder::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(effect::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(teleport::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(team::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(time::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(give::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(loot::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(clear::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(setblock::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(data::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(setworldspawn::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(spawnpoint::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(seed::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(fill::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(place::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(scoreboard::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(playsound::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(tellraw::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(title::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(ride::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(summon::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(experience::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(weather::init_command_tree(), PermissionLvl::Two);
@@ -82,6 +108,8 @@ pub fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(gamemode::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(stopsound::init_command_tree(), PermissionLvl::Two);
     dispatcher.register(defaultgamemode::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(attribute::init_command_tree(), PermissionLvl::Two);
+    dispatcher.register(reload::init_command_tree(), PermissionLvl::Two);
     // Three
     dispatcher.register(op::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(deop::init_command_tree(), PermissionLvl::Three);
@@ -94,8 +122,14 @@ pub fn default_dispatcher() -> CommandDispatcher {
     dispatcher.register(pardon::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(pardonip::init_command_tree(), PermissionLvl::Three);
     dispatcher.register(whitelist::init_command_tree(), PermissionLvl::Three);
+    dispatcher.register(tick::init_command_tree(), PermissionLvl::Three);
     // Four
     dispatcher.register(stop::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(saveall::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(saveoff::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(saveon::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(debug::init_command_tree(), PermissionLvl::Four);
+    dispatcher.register(pregen::init_command_tree(), PermissionLvl::Four);
 
     #[cfg(feature = "dhat-heap")]
     dispatcher.register(profile::init_command_tree(), PermissionLvl::Four);
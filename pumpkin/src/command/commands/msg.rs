@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use pumpkin_data::world::{MSG_COMMAND_INCOMING, MSG_COMMAND_OUTGOING};
 use pumpkin_util::text::{TextComponent, click::ClickEvent, hover::HoverEvent};
@@ -11,6 +13,7 @@ use crate::command::{
     tree::CommandTree,
     tree::builder::{argument, argument_default_name},
 };
+use crate::entity::player::{Player, SignedMessage};
 use CommandError::InvalidConsumption;
 
 const NAMES: [&str; 3] = ["msg", "tell", "w"];
@@ -35,53 +38,122 @@ impl CommandExecutor for Executor {
         let targets = PlayersArgumentConsumer.find_arg_default_name(args)?;
         let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
 
-        for target in targets {
-            player
-                .send_message(
-                    &TextComponent::text(msg.clone()),
-                    MSG_COMMAND_OUTGOING,
-                    &TextComponent::text(player.gameprofile.name.clone()),
-                    Some(
-                        &TextComponent::text(target.gameprofile.name.clone())
-                            .hover_event(HoverEvent::show_entity(
-                                target.living_entity.entity.entity_uuid.to_string(),
-                                target.living_entity.entity.entity_type.resource_name.into(),
-                                Some(TextComponent::text(target.gameprofile.name.clone())),
-                            ))
-                            .click_event(ClickEvent::SuggestCommand {
-                                command: format!("/tell {} ", target.gameprofile.name.clone())
-                                    .into(),
-                            }),
-                    ),
-                )
-                .await;
-        }
-        for target in targets {
-            target
-                .send_message(
-                    &TextComponent::text(msg.clone()),
-                    MSG_COMMAND_INCOMING,
-                    &TextComponent::text(player.gameprofile.name.clone())
-                        .hover_event(HoverEvent::show_entity(
-                            player.living_entity.entity.entity_uuid.to_string(),
-                            player.living_entity.entity.entity_type.resource_name.into(),
-                            Some(TextComponent::text(player.gameprofile.name.clone())),
-                        ))
-                        .click_event(ClickEvent::SuggestCommand {
-                            command: format!("/tell {} ", player.gameprofile.name.clone()).into(),
-                        }),
-                    Some(&TextComponent::text(target.gameprofile.name.clone())),
-                )
-                .await;
-        }
+        whisper(&player, targets, msg).await;
 
         Ok(())
     }
 }
 
+fn player_name(player: &Player) -> TextComponent {
+    TextComponent::text(player.gameprofile.name.clone())
+        .hover_event(HoverEvent::show_entity(
+            player.living_entity.entity.entity_uuid.to_string(),
+            player.living_entity.entity.entity_type.resource_name.into(),
+            Some(TextComponent::text(player.gameprofile.name.clone())),
+        ))
+        .click_event(ClickEvent::SuggestCommand {
+            command: format!("/tell {} ", player.gameprofile.name).into(),
+        })
+}
+
+/// Sends a private message to the targets, echoing it back to the sender. Targets which hide chat
+/// messages don't receive it. A message the sender signed is forwarded with its signature.
+pub(super) async fn whisper(sender: &Player, targets: &[Arc<Player>], msg: &str) {
+    let message = TextComponent::text(msg.to_string());
+    let signed = sender
+        .signed_arguments
+        .lock()
+        .await
+        .get(ARG_MESSAGE)
+        .filter(|signed| signed.message == msg)
+        .cloned();
+    for target in targets {
+        send_whisper(
+            sender,
+            sender,
+            signed.as_ref(),
+            &message,
+            MSG_COMMAND_OUTGOING,
+            &TextComponent::text(sender.gameprofile.name.clone()),
+            Some(&player_name(target)),
+        )
+        .await;
+    }
+    for target in targets {
+        if !target.config.read().await.chat_mode.accepts_chat_messages() {
+            continue;
+        }
+        send_whisper(
+            target,
+            sender,
+            signed.as_ref(),
+            &message,
+            MSG_COMMAND_INCOMING,
+            &player_name(sender),
+            Some(&TextComponent::text(target.gameprofile.name.clone())),
+        )
+        .await;
+        *target.last_whisperer.lock().await = Some(sender.gameprofile.id);
+    }
+}
+
+async fn send_whisper(
+    recipient: &Player,
+    sender: &Player,
+    signed: Option<&SignedMessage>,
+    message: &TextComponent,
+    chat_type: u8,
+    sender_name: &TextComponent,
+    target_name: Option<&TextComponent>,
+) {
+    if let Some(signed) = signed {
+        recipient
+            .send_signed_message(sender, signed, None, chat_type, sender_name, target_name)
+            .await;
+    } else {
+        recipient
+            .send_message(message, chat_type, sender_name, target_name)
+            .await;
+    }
+}
+
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument_default_name(PlayersArgumentConsumer)
             .then(argument(ARG_MESSAGE, MsgArgConsumer).execute(Executor)),
     )
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::packet::clientbound::PLAY_DISGUISED_CHAT;
+
+    use crate::command::CommandSender;
+    use crate::entity::player::ChatMode;
+    use crate::test_utils::{run_command, test_player, test_server};
+
+    #[tokio::test]
+    async fn whispers_reach_only_the_target() {
+        let server = test_server();
+        let (alice, mut alice_packets) = test_player(&server, "alice").await;
+        let (bob, mut bob_packets) = test_player(&server, "bob").await;
+        let (_carol, mut carol_packets) = test_player(&server, "carol").await;
+
+        let mut sender = CommandSender::Player(alice.clone());
+        run_command(&server, &mut sender, "msg bob hello").await;
+
+        // The sender sees what they sent
+        let (echo, _) = alice_packets.try_recv().unwrap();
+        assert_eq!(i32::from(echo[0]), PLAY_DISGUISED_CHAT);
+        let (whisper, _) = bob_packets.try_recv().unwrap();
+        assert_eq!(i32::from(whisper[0]), PLAY_DISGUISED_CHAT);
+        assert!(carol_packets.try_recv().is_err());
+        assert_eq!(*bob.last_whisperer.lock().await, Some(alice.gameprofile.id));
+
+        // Players who hide chat messages don't get whispers either
+        bob.config.write().await.chat_mode = ChatMode::Hidden;
+        run_command(&server, &mut sender, "msg bob hello again").await;
+        assert!(alice_packets.try_recv().is_ok());
+        assert!(bob_packets.try_recv().is_err());
+    }
+}
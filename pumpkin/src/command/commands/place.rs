@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::structure::{
+    BlockMirror, BlockRotation, StructurePlaceSettings, StructureTemplate,
+};
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::resource_location::ResourceLocationArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{NonLeafNodeBuilder, argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+const NAMES: [&str; 1] = ["place"];
+
+const DESCRIPTION: &str = "Places a structure template.";
+
+const ARG_TEMPLATE: &str = "template";
+const ARG_POS: &str = "pos";
+const ARG_INTEGRITY: &str = "integrity";
+const ARG_SEED: &str = "seed";
+
+fn integrity_consumer() -> BoundedNumArgumentConsumer<f32> {
+    BoundedNumArgumentConsumer::new()
+        .name(ARG_INTEGRITY)
+        .min(0.0)
+        .max(1.0)
+}
+
+fn seed_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_SEED)
+}
+
+const fn rotation_name(rotation: BlockRotation) -> &'static str {
+    match rotation {
+        BlockRotation::None => "none",
+        BlockRotation::Clockwise90 => "clockwise_90",
+        BlockRotation::Clockwise180 => "180",
+        BlockRotation::CounterClockwise90 => "counterclockwise_90",
+    }
+}
+
+const fn mirror_name(mirror: BlockMirror) -> &'static str {
+    match mirror {
+        BlockMirror::None => "none",
+        BlockMirror::LeftRight => "left_right",
+        BlockMirror::FrontBack => "front_back",
+    }
+}
+
+struct TemplateExecutor(BlockRotation, BlockMirror);
+
+#[async_trait]
+impl CommandExecutor for TemplateExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let id = ResourceLocationArgumentConsumer::find_arg(args, ARG_TEMPLATE)?;
+        let pos = match BlockPosArgumentConsumer::find_arg(args, ARG_POS) {
+            Ok(pos) => pos,
+            Err(_) => {
+                let pos = sender.position().ok_or(CommandError::InvalidRequirement)?;
+                BlockPos::floored(pos.x, pos.y, pos.z)
+            }
+        };
+        let integrity = match BoundedNumArgumentConsumer::<f32>::find_arg(args, ARG_INTEGRITY) {
+            Ok(Ok(integrity)) => integrity,
+            _ => 1.0,
+        };
+        let seed = match BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_SEED) {
+            Ok(Ok(seed)) => seed,
+            _ => 0,
+        };
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let Some(path) = world.level.structure_file(id).filter(|path| path.is_file()) else {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.place.template.invalid",
+                    [TextComponent::text(id.to_string())],
+                ))
+                .await;
+            return Ok(());
+        };
+        let template = match StructureTemplate::from_file(&path) {
+            Ok(template) => template,
+            Err(err) => {
+                log::warn!("Failed to load structure {}: {err}", path.display());
+                sender
+                    .send_message(TextComponent::translate(
+                        "commands.place.template.failed",
+                        [],
+                    ))
+                    .await;
+                return Ok(());
+            }
+        };
+
+        let settings = StructurePlaceSettings {
+            rotation: self.0,
+            mirror: self.1,
+            integrity,
+            seed: seed.into(),
+        };
+        world.place_structure(&template, pos, &settings).await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.place.template.success",
+                [
+                    TextComponent::text(id.to_string()),
+                    TextComponent::text(pos.0.x.to_string()),
+                    TextComponent::text(pos.0.y.to_string()),
+                    TextComponent::text(pos.0.z.to_string()),
+                ],
+            ))
+            .await;
+
+        Ok(())
+    }
+}
+
+fn template_settings(rotation: BlockRotation, mirror: BlockMirror) -> NonLeafNodeBuilder {
+    literal(mirror_name(mirror))
+        .execute(TemplateExecutor(rotation, mirror))
+        .then(
+            argument(ARG_INTEGRITY, integrity_consumer())
+                .execute(TemplateExecutor(rotation, mirror))
+                .then(
+                    argument(ARG_SEED, seed_consumer()).execute(TemplateExecutor(rotation, mirror)),
+                ),
+        )
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut pos = argument(ARG_POS, BlockPosArgumentConsumer)
+        .execute(TemplateExecutor(BlockRotation::None, BlockMirror::None));
+    for rotation in BlockRotation::ALL {
+        let mut node =
+            literal(rotation_name(rotation)).execute(TemplateExecutor(rotation, BlockMirror::None));
+        for mirror in [
+            BlockMirror::None,
+            BlockMirror::LeftRight,
+            BlockMirror::FrontBack,
+        ] {
+            node = node.then(template_settings(rotation, mirror));
+        }
+        pos = pos.then(node);
+    }
+
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        literal("template").then(
+            argument(ARG_TEMPLATE, ResourceLocationArgumentConsumer::new(false))
+                .execute(TemplateExecutor(BlockRotation::None, BlockMirror::None))
+                .then(pos),
+        ),
+    )
+}
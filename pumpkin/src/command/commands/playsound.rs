@@ -1,12 +1,13 @@
 use async_trait::async_trait;
 use pumpkin_data::sound::SoundCategory;
-use pumpkin_util::text::TextComponent;
+use pumpkin_protocol::client::play::CSoundEffect;
+use pumpkin_util::{math::vector3::Vector3, text::TextComponent};
 use rand::{Rng, thread_rng};
 
 use crate::command::{
     CommandError, CommandExecutor, CommandSender,
     args::{
-        Arg, ConsumedArgs, FindArg, bounded_num::BoundedNumArgumentConsumer,
+        ConsumedArgs, FindArg, bounded_num::BoundedNumArgumentConsumer,
         players::PlayersArgumentConsumer, position_3d::Position3DArgumentConsumer,
         sound::SoundArgumentConsumer, sound_category::SoundCategoryArgumentConsumer,
     },
@@ -40,7 +41,7 @@ fn volume_consumer() -> BoundedNumArgumentConsumer<f32> {
 }
 
 // Pitch must be between 0.0 and 2.0
-// Values below 0.5 are treated as 0.5
+// The client treats values below 0.5 as 0.5
 fn pitch_consumer() -> BoundedNumArgumentConsumer<f32> {
     BoundedNumArgumentConsumer::new()
         .name(ARG_PITCH)
@@ -57,6 +58,29 @@ fn min_volume_consumer() -> BoundedNumArgumentConsumer<f32> {
         .max(1.0)
 }
 
+/// Where and how loud a sound played at `pos` is heard by a player at `listener`, like vanilla.
+/// Players out of the sound's range only hear it when a minimum volume is given, from 2 blocks
+/// away in the direction of the sound.
+fn sound_for_listener(
+    listener: Vector3<f64>,
+    pos: Vector3<f64>,
+    volume: f32,
+    min_volume: f32,
+) -> Option<(Vector3<f64>, f32)> {
+    // Louder sounds can be heard from further away
+    let range = 16.0 * f64::from(volume.max(1.0));
+    let offset = pos.sub(&listener);
+    let distance_squared = offset.length_squared();
+    if distance_squared <= range * range {
+        return Some((pos, volume));
+    }
+    if min_volume <= 0.0 {
+        return None;
+    }
+    let towards_sound = offset * (2.0 / distance_squared.sqrt());
+    Some((listener.add(&towards_sound), min_volume))
+}
+
 struct Executor;
 
 #[async_trait]
@@ -71,12 +95,8 @@ impl CommandExecutor for Executor {
         let sound = SoundArgumentConsumer::find_arg(args, ARG_SOUND)?;
 
         // Get optional sound category, defaults to Master
-        let source = args
-            .get(ARG_SOURCE)
-            .map_or(SoundCategory::Master, |arg| match arg {
-                Arg::SoundCategory(category) => *category,
-                _ => SoundCategory::Master,
-            });
+        let source = SoundCategoryArgumentConsumer::find_arg(args, ARG_SOURCE)
+            .map_or(SoundCategory::Master, |category| *category);
 
         // Get target players, defaults to sender if not specified
         let targets = if let Ok(players) = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS) {
@@ -87,23 +107,22 @@ impl CommandExecutor for Executor {
             return Ok(());
         };
 
-        // Get optional position, defaults to target's position
-        let position = Position3DArgumentConsumer::find_arg(args, ARG_POS).ok();
+        // Get optional position, defaults to the sender's position, or the target's position for the console
+        let position = Position3DArgumentConsumer::find_arg(args, ARG_POS)
+            .ok()
+            .or_else(|| sender.position());
 
-        // Get optional volume parameter
         let volume = match BoundedNumArgumentConsumer::<f32>::find_arg(args, ARG_VOLUME) {
             Ok(Ok(v)) => v,
             _ => 1.0, // Default volume
         };
 
-        // Get optional pitch parameter
         let pitch = match BoundedNumArgumentConsumer::<f32>::find_arg(args, ARG_PITCH) {
-            Ok(Ok(p)) => p.max(0.5), // Values below 0.5 are clamped
-            _ => 1.0,                // Default pitch
+            Ok(Ok(p)) => p,
+            _ => 1.0, // Default pitch
         };
 
-        // Get optional minimum volume (currently unused in implementation)
-        let _min_volume = match BoundedNumArgumentConsumer::<f32>::find_arg(args, ARG_MIN_VOLUME) {
+        let min_volume = match BoundedNumArgumentConsumer::<f32>::find_arg(args, ARG_MIN_VOLUME) {
             Ok(Ok(v)) => v,
             _ => 0.0, // Default minimum volume
         };
@@ -114,53 +133,54 @@ impl CommandExecutor for Executor {
         // Track how many players actually received the sound
         let mut players_who_heard = 0;
 
-        // Play sound for each target player
         for target in targets {
-            let pos = position.unwrap_or(target.living_entity.entity.pos.load());
-
-            // Check if player can hear the sound based on volume and distance
-            let player_pos = target.living_entity.entity.pos.load();
-            let distance = player_pos.squared_distance_to_vec(pos);
-            let max_distance = 16.0 * volume; // 16 blocks is base distance at volume 1.0
-
-            if distance <= max_distance.into() || _min_volume > 0.0 {
-                target
-                    .play_sound(sound as u16, source, &pos, volume, pitch, seed)
-                    .await;
-                players_who_heard += 1;
-            }
+            let listener = target.living_entity.entity.pos.load();
+            let Some((pos, volume)) =
+                sound_for_listener(listener, position.unwrap_or(listener), volume, min_volume)
+            else {
+                continue;
+            };
+            target
+                .client
+                .enqueue_packet(&CSoundEffect::new(
+                    sound.to_event(),
+                    source,
+                    &pos,
+                    volume,
+                    pitch,
+                    seed,
+                ))
+                .await;
+            players_who_heard += 1;
         }
 
-        // Send appropriate message based on results
         if players_who_heard == 0 {
             sender
                 .send_message(TextComponent::translate("commands.playsound.failed", []))
                 .await;
-        } else {
-            let sound_name = sound.to_name();
-            if players_who_heard == 1 {
-                sender
-                    .send_message(TextComponent::translate(
-                        "commands.playsound.success.single",
-                        [
-                            TextComponent::text(sound_name),
-                            TextComponent::text(targets[0].gameprofile.name.clone()),
-                        ],
-                    ))
-                    .await;
-            } else {
-                sender
-                    .send_message(TextComponent::translate(
-                        "commands.playsound.success.multiple",
-                        [
-                            TextComponent::text(sound_name),
-                            TextComponent::text(players_who_heard.to_string()),
-                        ],
-                    ))
-                    .await;
-            }
+            return Ok(());
         }
 
+        let sound_name = TextComponent::text(sound.identifier().to_string());
+        let text = if players_who_heard == 1 {
+            TextComponent::translate(
+                "commands.playsound.success.single",
+                [
+                    sound_name,
+                    TextComponent::text(targets[0].gameprofile.name.clone()),
+                ],
+            )
+        } else {
+            TextComponent::translate(
+                "commands.playsound.success.multiple",
+                [
+                    sound_name,
+                    TextComponent::text(players_who_heard.to_string()),
+                ],
+            )
+        };
+        sender.send_message(text).await;
+
         Ok(())
     }
 }
@@ -198,3 +218,98 @@ pub fn init_command_tree() -> CommandTree {
             .execute(Executor),
     )
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{packet::clientbound::PLAY_SOUND, sound::SoundCategory};
+    use pumpkin_util::{PermissionLvl, math::vector3::Vector3};
+
+    use super::sound_for_listener;
+    use crate::command::CommandSender;
+    use crate::test_utils::{run_command, run_console_command, test_player, test_server};
+
+    #[tokio::test]
+    async fn sound_is_enqueued_for_each_target() {
+        let server = test_server();
+        let (alice, alice_packets) = test_player(&server, "alice").await;
+        let (bob, bob_packets) = test_player(&server, "bob").await;
+        alice
+            .living_entity
+            .entity
+            .set_pos(Vector3::new(12.0, 64.0, 10.0));
+        bob.living_entity
+            .entity
+            .set_pos(Vector3::new(42.0, 64.0, 10.0));
+
+        let output = run_console_command(
+            &server,
+            "playsound minecraft:block.note_block.bell record @a 10 64 10 0.5 1.5 0.2",
+        )
+        .await;
+        assert_eq!(output.len(), 1);
+
+        // Whole x and z coordinates are centered on the block. Bob is out of range, so hears the
+        // sound at the minimum volume next to them
+        let targets = [
+            (alice_packets, [84, 512, 84], 0.5),
+            (bob_packets, [320, 512, 80], 0.2),
+        ];
+        for (mut outgoing, expected_pos, expected_volume) in targets {
+            let (packet, _) = outgoing.try_recv().unwrap();
+            assert_eq!(i32::from(packet[0]), PLAY_SOUND);
+            // Category, position, volume, pitch and seed end the packet
+            let tail = &packet[packet.len() - 29..];
+            assert_eq!(tail[0], SoundCategory::Records as u8);
+            let position: Vec<i32> = tail[1..13]
+                .chunks(4)
+                .map(|bytes| i32::from_be_bytes(bytes.try_into().unwrap()))
+                .collect();
+            assert_eq!(position, expected_pos);
+            assert_eq!(&tail[13..17], &f32::to_be_bytes(expected_volume));
+            assert_eq!(&tail[17..21], &1.5f32.to_be_bytes());
+        }
+
+        let pos = Vector3::new(10.0, 64.0, 10.0);
+        // Without a minimum volume, far away players don't hear it at all
+        assert_eq!(
+            sound_for_listener(Vector3::new(100.0, 64.0, 10.0), pos, 0.5, 0.0),
+            None
+        );
+        // Loud sounds reach further
+        assert!(sound_for_listener(Vector3::new(40.0, 64.0, 10.0), pos, 2.0, 0.0).is_some());
+    }
+
+    #[tokio::test]
+    async fn custom_sound_is_played_relative_to_the_sender() {
+        let server = test_server();
+        let (alice, mut packets) = test_player(&server, "alice").await;
+        alice
+            .living_entity
+            .entity
+            .set_pos(Vector3::new(12.0, 64.0, 10.0));
+        alice.permission_lvl.store(PermissionLvl::Two);
+
+        let mut sender = CommandSender::Player(alice.clone());
+        run_command(
+            &server,
+            &mut sender,
+            "playsound mypack:music/intro music @s ~ ~1 ~",
+        )
+        .await;
+
+        let (packet, _) = packets.try_recv().unwrap();
+        assert_eq!(i32::from(packet[0]), PLAY_SOUND);
+        // Custom sounds are sent inline instead of as a registry id
+        assert_eq!(packet[1], 0);
+        let name = b"mypack:music/intro";
+        assert!(packet.windows(name.len()).any(|window| window == name));
+        let tail = &packet[packet.len() - 29..];
+        assert_eq!(tail[0], SoundCategory::Music as u8);
+        let position: Vec<i32> = tail[1..13]
+            .chunks(4)
+            .map(|bytes| i32::from_be_bytes(bytes.try_into().unwrap()))
+            .collect();
+        assert_eq!(position, [96, 520, 80]);
+        assert_eq!(&tail[13..17], &1.0f32.to_be_bytes());
+    }
+}
@@ -0,0 +1,324 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use pumpkin_util::math::vector2::Vector2;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::{Color, NamedColor};
+use pumpkin_world::level::{PregenProgress, chunks_between};
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::position_2d::Position2DArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, argument_default_name, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::player::Player;
+use crate::server::Server;
+use crate::world::World;
+
+const NAMES: [&str; 1] = ["pregen"];
+
+const DESCRIPTION: &str = "Generates and saves the chunks of an area ahead of time.";
+
+const ARG_FROM: &str = "from";
+const ARG_TO: &str = "to";
+
+/// Larger areas are better pregenerated a part at a time
+pub const MAX_RADIUS: i32 = 1000;
+
+/// The most chunks one pregeneration may cover, as many as there are in the largest radius
+const MAX_CHUNKS: u64 = (2 * MAX_RADIUS + 1).pow(2) as u64;
+
+/// How often a running pregeneration reports its progress
+const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The cancel flag of the pregeneration which is running, there is only ever one at a time
+static RUNNING: Mutex<Option<Arc<AtomicBool>>> = Mutex::new(None);
+
+fn radius_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new()
+        .name("radius")
+        .min(0)
+        .max(MAX_RADIUS)
+}
+
+/// The chunks in a square of `radius` chunks around `center`, or `None` if the radius is
+/// larger than [`MAX_RADIUS`].
+#[must_use]
+pub fn chunks_around(center: Vector2<i32>, radius: i32) -> Option<Vec<Vector2<i32>>> {
+    if !(0..=MAX_RADIUS).contains(&radius) {
+        return None;
+    }
+    Some(chunks_between(
+        Vector2::new(
+            center.x.saturating_sub(radius),
+            center.z.saturating_sub(radius),
+        ),
+        Vector2::new(
+            center.x.saturating_add(radius),
+            center.z.saturating_add(radius),
+        ),
+    ))
+}
+
+/// The chunks between the corners `from` and `to`, or `None` if there are more of them than
+/// one pregeneration may cover.
+fn chunks_in_area(from: Vector2<i32>, to: Vector2<i32>) -> Option<Vec<Vector2<i32>>> {
+    let width = u64::from(from.x.abs_diff(to.x)) + 1;
+    let length = u64::from(from.z.abs_diff(to.z)) + 1;
+    (width * length <= MAX_CHUNKS).then(|| chunks_between(from, to))
+}
+
+/// The chunk of a block, `None` if it is too far out to have one.
+fn block_to_chunk(x: f64, z: f64) -> Option<Vector2<i32>> {
+    let to_chunk = |block: f64| {
+        let chunk = (block / 16.0).floor();
+        (f64::from(i32::MIN)..=f64::from(i32::MAX))
+            .contains(&chunk)
+            .then_some(chunk as i32)
+    };
+    Some(Vector2::new(to_chunk(x)?, to_chunk(z)?))
+}
+
+fn progress_message(progress: &PregenProgress) -> TextComponent {
+    let eta = progress
+        .eta()
+        .map_or_else(|| "?".to_string(), |eta| format!("{}s", eta.as_secs()));
+    TextComponent::text(format!(
+        "Pregenerated {}/{} chunks ({:.1}%), {:.1} chunks/s, ETA {eta}",
+        progress.done,
+        progress.total,
+        progress.done as f64 * 100.0 / progress.total.max(1) as f64,
+        progress.rate(),
+    ))
+}
+
+/// Progress goes to the log, and to the player who started the pregeneration if there is one.
+fn report(player: Option<&Arc<Player>>, message: TextComponent) {
+    log::info!("{}", message.clone().to_pretty_console());
+    if let Some(player) = player.cloned() {
+        tokio::spawn(async move {
+            player.send_system_message(&message).await;
+        });
+    }
+}
+
+/// Pregenerates `chunks` of `world` in the background. Returns false if another pregeneration is
+/// still running.
+pub fn start(
+    server: &Server,
+    world: Arc<World>,
+    chunks: Vec<Vector2<i32>>,
+    player: Option<Arc<Player>>,
+) -> bool {
+    let cancel = Arc::new(AtomicBool::new(false));
+    {
+        let mut running = RUNNING.lock().unwrap();
+        if running.is_some() {
+            return false;
+        }
+        *running = Some(cancel.clone());
+    }
+
+    server.spawn_task(async move {
+        let total = chunks.len();
+        let mut last_report = Instant::now();
+        let done = world
+            .level
+            .pregenerate(&chunks, &cancel, |progress| {
+                // Don't hold the server up when it stops
+                if crate::SHOULD_STOP.load(Ordering::Relaxed) {
+                    cancel.store(true, Ordering::Relaxed);
+                }
+                if last_report.elapsed() >= REPORT_INTERVAL {
+                    last_report = Instant::now();
+                    report(player.as_ref(), progress_message(&progress));
+                }
+            })
+            .await;
+
+        let message = if done < total {
+            format!("Pregeneration cancelled after {done}/{total} chunks")
+        } else {
+            format!("Pregenerated {total} chunks")
+        };
+        report(player.as_ref(), TextComponent::text(message));
+        *RUNNING.lock().unwrap() = None;
+    });
+    true
+}
+
+async fn start_for_sender(
+    sender: &CommandSender,
+    server: &Server,
+    world: Arc<World>,
+    chunks: Vec<Vector2<i32>>,
+) {
+    let total = chunks.len();
+    if start(server, world, chunks, sender.as_player()) {
+        sender
+            .send_message(TextComponent::text(format!(
+                "Pregenerating {total} chunks, use /pregen cancel to stop"
+            )))
+            .await;
+    } else {
+        sender
+            .send_message(
+                TextComponent::text("A pregeneration is already running")
+                    .color(Color::Named(NamedColor::Red)),
+            )
+            .await;
+    }
+}
+
+/// Players pregenerate the world they are in, the console the first world.
+async fn sender_world(sender: &CommandSender, server: &Server) -> Arc<World> {
+    match sender.world().await {
+        Some(world) => world,
+        None => server
+            .worlds
+            .read()
+            .await
+            .first()
+            .cloned()
+            .expect("There should always be at least one world"),
+    }
+}
+
+struct RadiusExecutor;
+
+#[async_trait]
+impl CommandExecutor for RadiusExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Ok(Ok(radius)) = radius_consumer().find_arg_default_name(args) else {
+            sender
+                .send_message(
+                    TextComponent::text(format!("The radius must be between 0 and {MAX_RADIUS}"))
+                        .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        };
+
+        let world = sender_world(sender, server).await;
+        // Around players, or around the world spawn
+        let center = match sender
+            .position()
+            .and_then(|pos| block_to_chunk(pos.x, pos.z))
+        {
+            Some(center) => center,
+            None => {
+                let (spawn, _) = world.spawn_point().await;
+                spawn.chunk_and_chunk_relative_position().0
+            }
+        };
+        let Some(chunks) = chunks_around(center, radius) else {
+            return Ok(());
+        };
+        start_for_sender(sender, server, world, chunks).await;
+        Ok(())
+    }
+}
+
+struct AreaExecutor;
+
+#[async_trait]
+impl CommandExecutor for AreaExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let from = Position2DArgumentConsumer::find_arg(args, ARG_FROM)?;
+        let to = Position2DArgumentConsumer::find_arg(args, ARG_TO)?;
+
+        let chunks = block_to_chunk(from.x, from.z)
+            .zip(block_to_chunk(to.x, to.z))
+            .and_then(|(from, to)| chunks_in_area(from, to));
+        let Some(chunks) = chunks else {
+            sender
+                .send_message(
+                    TextComponent::text(format!(
+                        "The area can't be larger than {MAX_CHUNKS} chunks"
+                    ))
+                    .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        };
+
+        let world = sender_world(sender, server).await;
+        start_for_sender(sender, server, world, chunks).await;
+        Ok(())
+    }
+}
+
+struct CancelExecutor;
+
+#[async_trait]
+impl CommandExecutor for CancelExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let cancel = RUNNING.lock().unwrap().clone();
+        let message = if let Some(cancel) = cancel {
+            cancel.store(true, Ordering::Relaxed);
+            TextComponent::text("Cancelling the pregeneration")
+        } else {
+            TextComponent::text("No pregeneration is running").color(Color::Named(NamedColor::Red))
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("cancel").execute(CancelExecutor))
+        .then(argument_default_name(radius_consumer()).execute(RadiusExecutor))
+        .then(
+            argument(ARG_FROM, Position2DArgumentConsumer)
+                .then(argument(ARG_TO, Position2DArgumentConsumer).execute(AreaExecutor)),
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::{MAX_RADIUS, block_to_chunk, chunks_around, chunks_in_area};
+
+    #[test]
+    fn areas_are_limited_like_the_radius() {
+        let center = Vector2::new(3, -2);
+        assert_eq!(chunks_around(center, 1).unwrap().len(), 9);
+        assert!(chunks_around(center, MAX_RADIUS + 1).is_none());
+        assert!(chunks_around(center, -1).is_none());
+
+        // The largest radius covers exactly as many chunks as an area may have
+        let largest = chunks_around(center, MAX_RADIUS).unwrap().len();
+        let corner = Vector2::new(center.x + 2 * MAX_RADIUS, center.z + 2 * MAX_RADIUS);
+        assert_eq!(chunks_in_area(center, corner).unwrap().len(), largest);
+        let too_wide = Vector2::new(corner.x + 1, corner.z);
+        assert!(chunks_in_area(center, too_wide).is_none());
+        assert!(chunks_in_area(Vector2::new(i32::MIN, 0), Vector2::new(i32::MAX, 0)).is_none());
+    }
+
+    #[test]
+    fn blocks_beyond_any_chunk_are_rejected() {
+        assert_eq!(block_to_chunk(-1.0, 31.5), Some(Vector2::new(-1, 1)));
+        assert_eq!(block_to_chunk(1e12, 0.0), None);
+        assert_eq!(block_to_chunk(f64::NAN, 0.0), None);
+    }
+}
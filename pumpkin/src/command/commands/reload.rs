@@ -0,0 +1,55 @@
+use async_trait::async_trait;
+use pumpkin_config::BasicConfiguration;
+use pumpkin_util::text::TextComponent;
+
+use crate::{
+    command::{
+        CommandError, CommandExecutor, CommandSender, args::ConsumedArgs, tree::CommandTree,
+    },
+    data::load_recipes,
+    server::Server,
+};
+
+const NAMES: [&str; 1] = ["reload"];
+
+const DESCRIPTION: &str =
+    "Reloads the smithing recipes from the data folder and the MOTD from the configuration.";
+
+/// Unlike vanilla, loot tables and tags are built into the server and stay as they are, so
+/// there is nothing else to resend to players.
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        // Nothing changes unless everything could be read
+        let config = BasicConfiguration::reload().map_err(|err| {
+            CommandError::GeneralCommandIssue(format!("Reload failed, keeping the old data: {err}"))
+        })?;
+        if let Err(err) = load_recipes() {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Reload failed, keeping the old data: {err}"
+            )));
+        }
+
+        // Open crafting screens may now have a different result
+        for player in server.get_all_players().await {
+            player.refresh_crafting_output(server).await;
+        }
+        server.set_motd(config.motd).await;
+
+        sender
+            .send_message(TextComponent::translate("commands.reload.success", []))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(Executor)
+}
@@ -0,0 +1,54 @@
+use async_trait::async_trait;
+
+use crate::command::{
+    CommandError, CommandExecutor, CommandSender,
+    args::{Arg, ConsumedArgs, message::MsgArgConsumer},
+    tree::CommandTree,
+    tree::builder::argument,
+};
+use CommandError::InvalidConsumption;
+
+use super::msg::whisper;
+
+const NAMES: [&str; 2] = ["r", "reply"];
+
+const DESCRIPTION: &str = "Replies to the last player who sent you a private message.";
+
+const ARG_MESSAGE: &str = "message";
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Msg(msg)) = args.get(ARG_MESSAGE) else {
+            return Err(InvalidConsumption(Some(ARG_MESSAGE.into())));
+        };
+        let player = sender.as_player().ok_or(CommandError::InvalidRequirement)?;
+
+        let Some(last_whisperer) = *player.last_whisperer.lock().await else {
+            return Err(CommandError::GeneralCommandIssue(
+                "There is nobody to reply to".to_string(),
+            ));
+        };
+        let Some(target) = server.get_player_by_uuid(last_whisperer).await else {
+            return Err(CommandError::GeneralCommandIssue(
+                "The player you want to reply to is no longer online".to_string(),
+            ));
+        };
+
+        whisper(&player, &[target], msg).await;
+
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(argument(ARG_MESSAGE, MsgArgConsumer).execute(Executor))
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+
+use crate::command::args::any_entity::AnyEntityArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::EntityId;
+use crate::entity::portal::RIDE_COOLDOWN;
+use crate::world::World;
+use crate::world::riding::RideError;
+
+const NAMES: [&str; 1] = ["ride"];
+const DESCRIPTION: &str = "Makes an entity start or stop riding another entity.";
+
+const ARG_TARGET: &str = "target";
+const ARG_VEHICLE: &str = "vehicle";
+
+/// The name of any entity in the world, players go by their own name.
+async fn entity_name(world: &World, id: EntityId) -> TextComponent {
+    if let Some(player) = world.get_player_by_id(id).await {
+        return TextComponent::text(player.gameprofile.name.clone());
+    }
+    match world.get_entity_by_id(id).await {
+        Some(entity) => TextComponent::text(entity.get_entity().entity_type.resource_name),
+        None => TextComponent::text(id.to_string()),
+    }
+}
+
+struct MountExecutor;
+
+#[async_trait]
+impl CommandExecutor for MountExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = AnyEntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let vehicle = AnyEntityArgumentConsumer::find_arg(args, ARG_VEHICLE)?;
+        let (target, vehicle) = (target.get_entity(), vehicle.get_entity());
+
+        let world = target.world.read().await.clone();
+        let target_name = entity_name(&world, target.entity_id).await;
+        let vehicle_name = entity_name(&world, vehicle.entity_id).await;
+        // Commands may mount onto any entity, so only the vanilla sanity checks apply
+        let message = if Arc::ptr_eq(&world, &*vehicle.world.read().await) {
+            match world
+                .mount_entity(target.entity_id, vehicle.entity_id)
+                .await
+            {
+                Ok(()) => TextComponent::translate(
+                    "commands.ride.mount.success",
+                    [target_name, vehicle_name],
+                ),
+                Err(RideError::AlreadyRiding(current)) => TextComponent::translate(
+                    "commands.ride.already_riding",
+                    [target_name, entity_name(&world, current).await],
+                )
+                .color_named(NamedColor::Red),
+                Err(RideError::Loop) => {
+                    TextComponent::translate("commands.ride.mount.failure.loop", [])
+                        .color_named(NamedColor::Red)
+                }
+            }
+        } else {
+            TextComponent::translate("commands.ride.mount.failure.wrong_dimension", [])
+                .color_named(NamedColor::Red)
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct DismountExecutor;
+
+#[async_trait]
+impl CommandExecutor for DismountExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let target = AnyEntityArgumentConsumer::find_arg(args, ARG_TARGET)?;
+        let target = target.get_entity();
+
+        let world = target.world.read().await.clone();
+        let target_name = entity_name(&world, target.entity_id).await;
+        let message = match world.dismount_entity(target.entity_id).await {
+            Some(vehicle) => {
+                target.ride_cooldown.set(RIDE_COOLDOWN);
+                TextComponent::translate(
+                    "commands.ride.dismount.success",
+                    [target_name, entity_name(&world, vehicle).await],
+                )
+            }
+            None => TextComponent::translate("commands.ride.not_riding", [target_name])
+                .color_named(NamedColor::Red),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).then(
+        argument(ARG_TARGET, AnyEntityArgumentConsumer)
+            .then(
+                literal("mount")
+                    .then(argument(ARG_VEHICLE, AnyEntityArgumentConsumer).execute(MountExecutor)),
+            )
+            .then(literal("dismount").execute(DismountExecutor)),
+    )
+}
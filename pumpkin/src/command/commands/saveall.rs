@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::literal;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["save-all"];
+
+const DESCRIPTION: &str = "Saves the server to disk.";
+
+struct Executor {
+    flush: bool,
+}
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        sender
+            .send_message(TextComponent::translate("commands.save.saving", []))
+            .await;
+        server.save_all(self.flush).await;
+        sender
+            .send_message(TextComponent::translate("commands.save.success", []))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .execute(Executor { flush: false })
+        .then(literal("flush").execute(Executor { flush: true }))
+}
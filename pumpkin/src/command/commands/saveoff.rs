@@ -0,0 +1,37 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::ConsumedArgs;
+use crate::command::tree::CommandTree;
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+
+const NAMES: [&str; 1] = ["save-off"];
+
+const DESCRIPTION: &str = "Disables automatic saving.";
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let message = if server.set_autosave(false).await {
+            "commands.save.disabled"
+        } else {
+            "commands.save.alreadyOff"
+        };
+        sender
+            .send_message(TextComponent::translate(message, []))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION).execute(Executor)
+}
@@ -0,0 +1,362 @@
+use async_trait::async_trait;
+use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
+use pumpkin_protocol::{client::play::RenderType, codec::var_int::VarInt};
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::textcomponent::TextComponentArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::world::scoreboard::{ScoreboardObjective, ScoreboardScore};
+
+const NAMES: [&str; 1] = ["scoreboard"];
+
+const DESCRIPTION: &str = "Manages scoreboard objectives and players.";
+
+const ARG_OBJECTIVE: &str = "objective";
+const ARG_CRITERIA: &str = "criteria";
+const ARG_DISPLAY_NAME: &str = "displayName";
+const ARG_TARGETS: &str = "targets";
+const ARG_SCORE: &str = "score";
+
+/// The display slots which can be set by name, the team sidebars are not supported yet.
+const DISPLAY_SLOTS: [(&str, ScoreboardDisplaySlot); 3] = [
+    ("list", ScoreboardDisplaySlot::List),
+    ("sidebar", ScoreboardDisplaySlot::Sidebar),
+    ("below_name", ScoreboardDisplaySlot::BelowName),
+];
+
+fn score_consumer() -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().name(ARG_SCORE)
+}
+
+fn unknown_objective(name: &str) -> TextComponent {
+    TextComponent::translate(
+        "arguments.objective.notFound",
+        [TextComponent::text(name.to_string())],
+    )
+    .color_named(NamedColor::Red)
+}
+
+struct AddObjectiveExecutor;
+
+#[async_trait]
+impl CommandExecutor for AddObjectiveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE)?;
+        let criteria = SimpleArgConsumer::find_arg(args, ARG_CRITERIA)?;
+        let display_name = TextComponentArgConsumer::find_arg(args, ARG_DISPLAY_NAME)
+            .unwrap_or_else(|_| TextComponent::text(name.to_string()));
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        if criteria != "dummy" {
+            return Err(CommandError::GeneralCommandIssue(format!(
+                "Unsupported objective criteria {criteria}, only dummy is supported"
+            )));
+        }
+
+        let mut scoreboard = world.scoreboard.lock().await;
+        if scoreboard.has_objective(name) {
+            drop(scoreboard);
+            sender
+                .send_message(
+                    TextComponent::translate("commands.scoreboard.objectives.add.duplicate", [])
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        }
+        scoreboard
+            .add_objective(
+                &world,
+                ScoreboardObjective::new(name, display_name.clone(), RenderType::Integer, None),
+            )
+            .await;
+        drop(scoreboard);
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.scoreboard.objectives.add.success",
+                [display_name],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct RemoveObjectiveExecutor;
+
+#[async_trait]
+impl CommandExecutor for RemoveObjectiveExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE)?;
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let mut scoreboard = world.scoreboard.lock().await;
+        let display_name = scoreboard.objective_display_name(name).cloned();
+        let removed = scoreboard.remove_objective(&world, name).await;
+        drop(scoreboard);
+
+        let message = match display_name {
+            Some(display_name) if removed => TextComponent::translate(
+                "commands.scoreboard.objectives.remove.success",
+                [display_name],
+            ),
+            _ => unknown_objective(name),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+/// Shows the objective argument in the slot, or clears the slot when there is no argument.
+struct SetDisplayExecutor(&'static str, ScoreboardDisplaySlot);
+
+#[async_trait]
+impl CommandExecutor for SetDisplayExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Self(slot_name, slot) = *self;
+        let objective = SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE).ok();
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let mut scoreboard = world.scoreboard.lock().await;
+        let display_name =
+            objective.and_then(|name| scoreboard.objective_display_name(name).cloned());
+        let set = scoreboard.set_display(&world, slot, objective).await;
+        drop(scoreboard);
+
+        let slot_name = TextComponent::text(slot_name);
+        let message = match (objective, display_name) {
+            (Some(name), _) if !set => unknown_objective(name),
+            (Some(_), Some(display_name)) => TextComponent::translate(
+                "commands.scoreboard.objectives.display.set",
+                [slot_name, display_name],
+            ),
+            _ => TextComponent::translate(
+                "commands.scoreboard.objectives.display.cleared",
+                [slot_name],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct SetScoreExecutor;
+
+#[async_trait]
+impl CommandExecutor for SetScoreExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let objective = SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE)?;
+        let Ok(score) = BoundedNumArgumentConsumer::<i32>::find_arg(args, ARG_SCORE)? else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_SCORE.into())));
+        };
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let mut scoreboard = world.scoreboard.lock().await;
+        let Some(display_name) = scoreboard.objective_display_name(objective).cloned() else {
+            drop(scoreboard);
+            sender.send_message(unknown_objective(objective)).await;
+            return Ok(());
+        };
+        for target in targets {
+            scoreboard
+                .update_score(
+                    &world,
+                    ScoreboardScore::new(
+                        &target.gameprofile.name,
+                        objective,
+                        VarInt(score),
+                        None,
+                        None,
+                    ),
+                )
+                .await;
+        }
+        drop(scoreboard);
+
+        let score = TextComponent::text(score.to_string());
+        let message = match targets {
+            [target] => TextComponent::translate(
+                "commands.scoreboard.players.set.success.single",
+                [
+                    display_name,
+                    TextComponent::text(target.gameprofile.name.clone()),
+                    score,
+                ],
+            ),
+            targets => TextComponent::translate(
+                "commands.scoreboard.players.set.success.multiple",
+                [
+                    display_name,
+                    TextComponent::text(targets.len().to_string()),
+                    score,
+                ],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+/// Resets the scores of the objective argument, or of all objectives when there is no argument.
+struct ResetScoreExecutor;
+
+#[async_trait]
+impl CommandExecutor for ResetScoreExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = PlayersArgumentConsumer::find_arg(args, ARG_TARGETS)?;
+        let objective = SimpleArgConsumer::find_arg(args, ARG_OBJECTIVE).ok();
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let mut scoreboard = world.scoreboard.lock().await;
+        let display_name = match objective {
+            Some(objective) => {
+                let Some(display_name) = scoreboard.objective_display_name(objective).cloned()
+                else {
+                    drop(scoreboard);
+                    sender.send_message(unknown_objective(objective)).await;
+                    return Ok(());
+                };
+                Some(display_name)
+            }
+            None => None,
+        };
+        let objectives: Vec<String> = match objective {
+            Some(objective) => vec![objective.to_string()],
+            None => scoreboard.objective_names().map(str::to_string).collect(),
+        };
+        for target in targets {
+            for objective in &objectives {
+                scoreboard
+                    .reset_score(&world, objective, &target.gameprofile.name)
+                    .await;
+            }
+        }
+        drop(scoreboard);
+
+        let targets_text = match targets {
+            [target] => TextComponent::text(target.gameprofile.name.clone()),
+            targets => TextComponent::text(targets.len().to_string()),
+        };
+        let amount = if targets.len() == 1 {
+            "single"
+        } else {
+            "multiple"
+        };
+        let message = match display_name {
+            Some(display_name) => TextComponent::translate(
+                format!("commands.scoreboard.players.reset.specific.{amount}"),
+                [display_name, targets_text],
+            ),
+            None => TextComponent::translate(
+                format!("commands.scoreboard.players.reset.all.{amount}"),
+                [targets_text],
+            ),
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    let mut set_display = literal("setdisplay");
+    for (slot_name, slot) in DISPLAY_SLOTS {
+        set_display = set_display.then(
+            literal(slot_name)
+                .execute(SetDisplayExecutor(slot_name, slot))
+                .then(
+                    argument(ARG_OBJECTIVE, SimpleArgConsumer)
+                        .execute(SetDisplayExecutor(slot_name, slot)),
+                ),
+        );
+    }
+
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("objectives")
+                .then(
+                    literal("add").then(
+                        argument(ARG_OBJECTIVE, SimpleArgConsumer).then(
+                            argument(ARG_CRITERIA, SimpleArgConsumer)
+                                .execute(AddObjectiveExecutor)
+                                .then(
+                                    argument(ARG_DISPLAY_NAME, TextComponentArgConsumer)
+                                        .execute(AddObjectiveExecutor),
+                                ),
+                        ),
+                    ),
+                )
+                .then(literal("remove").then(
+                    argument(ARG_OBJECTIVE, SimpleArgConsumer).execute(RemoveObjectiveExecutor),
+                ))
+                .then(set_display),
+        )
+        .then(
+            literal("players")
+                .then(
+                    literal("set").then(
+                        argument(ARG_TARGETS, PlayersArgumentConsumer).then(
+                            argument(ARG_OBJECTIVE, SimpleArgConsumer).then(
+                                argument(ARG_SCORE, score_consumer()).execute(SetScoreExecutor),
+                            ),
+                        ),
+                    ),
+                )
+                .then(
+                    literal("reset").then(
+                        argument(ARG_TARGETS, PlayersArgumentConsumer)
+                            .execute(ResetScoreExecutor)
+                            .then(
+                                argument(ARG_OBJECTIVE, SimpleArgConsumer)
+                                    .execute(ResetScoreExecutor),
+                            ),
+                    ),
+                ),
+        )
+}
@@ -0,0 +1,78 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+
+const NAMES: [&str; 1] = ["setworldspawn"];
+
+const DESCRIPTION: &str = "Sets the world spawn.";
+
+const ARG_POS: &str = "pos";
+const ARG_ANGLE: &str = "angle";
+
+fn angle_consumer() -> BoundedNumArgumentConsumer<f32> {
+    BoundedNumArgumentConsumer::new().name(ARG_ANGLE)
+}
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+        let pos = match BlockPosArgumentConsumer::find_arg(args, ARG_POS) {
+            Ok(pos) => pos,
+            Err(_) => sender
+                .as_player()
+                .ok_or(CommandError::InvalidRequirement)?
+                .living_entity
+                .entity
+                .block_pos
+                .load(),
+        };
+        let angle = match BoundedNumArgumentConsumer::<f32>::find_arg(args, ARG_ANGLE) {
+            Ok(Ok(angle)) => angle,
+            _ => 0.0,
+        };
+
+        world.set_spawn_point(pos, angle).await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.setworldspawn.success",
+                [
+                    TextComponent::text(pos.0.x.to_string()),
+                    TextComponent::text(pos.0.y.to_string()),
+                    TextComponent::text(pos.0.z.to_string()),
+                    TextComponent::text(angle.to_string()),
+                ],
+            ))
+            .await;
+
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)] // causes lifetime issues
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(Executor))
+        .then(
+            argument(ARG_POS, BlockPosArgumentConsumer)
+                .execute(Executor)
+                .then(argument(ARG_ANGLE, angle_consumer()).execute(Executor)),
+        )
+}
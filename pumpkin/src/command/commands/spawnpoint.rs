@@ -0,0 +1,117 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::position_block::BlockPosArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, require};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::entity::player::RespawnPoint;
+
+const NAMES: [&str; 1] = ["spawnpoint"];
+
+const DESCRIPTION: &str = "Sets the spawn point for a player.";
+
+const ARG_TARGETS: &str = "targets";
+const ARG_POS: &str = "pos";
+const ARG_ANGLE: &str = "angle";
+
+fn angle_consumer() -> BoundedNumArgumentConsumer<f32> {
+    BoundedNumArgumentConsumer::new().name(ARG_ANGLE)
+}
+
+struct Executor;
+
+#[async_trait]
+impl CommandExecutor for Executor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let targets = match PlayersArgumentConsumer::find_arg(args, ARG_TARGETS) {
+            Ok(targets) => targets.to_vec(),
+            Err(_) => vec![sender.as_player().ok_or(CommandError::InvalidRequirement)?],
+        };
+        let pos = match BlockPosArgumentConsumer::find_arg(args, ARG_POS) {
+            Ok(pos) => pos,
+            Err(_) => sender
+                .as_player()
+                .ok_or(CommandError::InvalidRequirement)?
+                .living_entity
+                .entity
+                .block_pos
+                .load(),
+        };
+        let angle = match BoundedNumArgumentConsumer::<f32>::find_arg(args, ARG_ANGLE) {
+            Ok(Ok(angle)) => angle,
+            _ => 0.0,
+        };
+        // The spawn point is in the sender's dimension, the console sets it in the target's
+        let sender_world = sender.world().await;
+
+        let mut dimension = None;
+        for target in &targets {
+            let world = match &sender_world {
+                Some(world) => world.clone(),
+                None => target.world().await,
+            };
+            *target.respawn_point.lock().await = Some(RespawnPoint {
+                dimension: world.dimension_type.clone(),
+                position: pos,
+                angle,
+                forced: true,
+            });
+            dimension = Some(world.dimension_type.name());
+        }
+        let Some(dimension) = dimension else {
+            return Ok(());
+        };
+
+        let coordinates = [
+            TextComponent::text(pos.0.x.to_string()),
+            TextComponent::text(pos.0.y.to_string()),
+            TextComponent::text(pos.0.z.to_string()),
+            TextComponent::text(angle.to_string()),
+            TextComponent::text(dimension.to_string()),
+        ];
+        let message = if let [target] = targets.as_slice() {
+            TextComponent::translate(
+                "commands.spawnpoint.success.single",
+                coordinates
+                    .into_iter()
+                    .chain([TextComponent::text(target.gameprofile.name.clone())])
+                    .collect::<Vec<_>>(),
+            )
+        } else {
+            TextComponent::translate(
+                "commands.spawnpoint.success.multiple",
+                coordinates
+                    .into_iter()
+                    .chain([TextComponent::text(targets.len().to_string())])
+                    .collect::<Vec<_>>(),
+            )
+        };
+        sender.send_message(message).await;
+
+        Ok(())
+    }
+}
+
+#[allow(clippy::redundant_closure_for_method_calls)] // causes lifetime issues
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(require(|sender| sender.is_player()).execute(Executor))
+        .then(
+            argument(ARG_TARGETS, PlayersArgumentConsumer)
+                .execute(Executor)
+                .then(
+                    argument(ARG_POS, BlockPosArgumentConsumer)
+                        .execute(Executor)
+                        .then(argument(ARG_ANGLE, angle_consumer()).execute(Executor)),
+                ),
+        )
+}
@@ -1,14 +1,18 @@
 use crate::command::{
     CommandExecutor, CommandSender,
     args::{
-        ConsumedArgs, FindArg, players::PlayersArgumentConsumer, sound::SoundArgumentConsumer,
+        ConsumedArgs, FindArg,
+        players::PlayersArgumentConsumer,
+        sound::{CommandSound, SoundArgumentConsumer},
         sound_category::SoundCategoryArgumentConsumer,
     },
     dispatcher::CommandError,
-    tree::{CommandTree, builder::argument},
+    tree::{
+        CommandTree,
+        builder::{argument, literal},
+    },
 };
 use async_trait::async_trait;
-use pumpkin_protocol::codec::identifier::Identifier;
 use pumpkin_util::text::TextComponent;
 
 const NAMES: [&str; 1] = ["stopsound"];
@@ -36,11 +40,7 @@ impl CommandExecutor for Executor {
         for target in targets {
             target
                 .stop_sound(
-                    sound
-                        .as_ref()
-                        .cloned()
-                        .map(|s| Identifier::vanilla(s.to_name()))
-                        .ok(),
+                    sound.as_ref().ok().map(CommandSound::identifier),
                     category.as_ref().map(|s| **s).ok(),
                 )
                 .await;
@@ -49,7 +49,7 @@ impl CommandExecutor for Executor {
             (Ok(c), Ok(s)) => TextComponent::translate(
                 "commands.stopsound.success.source.sound",
                 [
-                    TextComponent::text(s.to_name()),
+                    TextComponent::text(s.identifier().to_string()),
                     TextComponent::text(c.to_name()),
                 ],
             ),
@@ -59,7 +59,7 @@ impl CommandExecutor for Executor {
             ),
             (Err(_), Ok(s)) => TextComponent::translate(
                 "commands.stopsound.success.sourceless.sound",
-                [TextComponent::text(s.to_name())],
+                [TextComponent::text(s.identifier().to_string())],
             ),
             (Err(_), Err(_)) => {
                 TextComponent::translate("commands.stopsound.success.sourceless.any", [])
@@ -79,6 +79,51 @@ pub fn init_command_tree() -> CommandTree {
                 argument(ARG_SOURCE, SoundCategoryArgumentConsumer)
                     .execute(Executor)
                     .then(argument(ARG_SOUND, SoundArgumentConsumer).execute(Executor)),
-            ),
+            )
+            // Stops the sound in every category
+            .then(literal("*").then(argument(ARG_SOUND, SoundArgumentConsumer).execute(Executor))),
     )
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{packet::clientbound::PLAY_STOP_SOUND, sound::SoundCategory};
+    use pumpkin_protocol::{ClientPacket, client::play::CStopSound, codec::identifier::Identifier};
+
+    use crate::test_utils::{run_console_command, test_player, test_server};
+
+    #[tokio::test]
+    async fn stop_sound_is_sent_to_each_target() {
+        let server = test_server();
+        let (_alice, mut alice_packets) = test_player(&server, "alice").await;
+        let (_bob, mut bob_packets) = test_player(&server, "bob").await;
+
+        let output = run_console_command(&server, "stopsound @a record").await;
+        assert_eq!(output.len(), 1);
+        let output = run_console_command(&server, "stopsound alice * mypack:music/intro").await;
+        assert_eq!(output.len(), 1);
+
+        let mut records = Vec::new();
+        CStopSound::new(None, Some(SoundCategory::Records))
+            .write(&mut records)
+            .unwrap();
+        let mut intro = Vec::new();
+        CStopSound::new(
+            Some(Identifier {
+                namespace: "mypack".to_string(),
+                path: "music/intro".to_string(),
+            }),
+            None,
+        )
+        .write(&mut intro)
+        .unwrap();
+        for expected in [&records, &intro] {
+            let (packet, _) = alice_packets.try_recv().unwrap();
+            assert_eq!(i32::from(packet[0]), PLAY_STOP_SOUND);
+            assert_eq!(&packet[..], &expected[..]);
+        }
+        let (packet, _) = bob_packets.try_recv().unwrap();
+        assert_eq!(&packet[..], &records[..]);
+        assert!(bob_packets.try_recv().is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
 use pumpkin_util::text::TextComponent;
 
 use crate::{
@@ -11,7 +12,7 @@ use crate::{
         tree::CommandTree,
         tree::builder::argument,
     },
-    entity::mob,
+    entity::{lightning, mob},
 };
 const NAMES: [&str; 1] = ["summon"];
 
@@ -37,8 +38,13 @@ impl CommandExecutor for Executor {
         // TODO: Make this work in console
         if let Some(player) = sender.as_player() {
             let pos = pos.unwrap_or(player.living_entity.entity.pos.load());
-            let mob = mob::from_type(entity, pos, &player.world().await).await;
-            player.world().await.spawn_entity(mob).await;
+            let world = player.world().await;
+            if entity == EntityType::LIGHTNING_BOLT {
+                lightning::strike(&world, pos).await;
+            } else {
+                let mob = mob::from_type(entity, pos, &world).await;
+                world.spawn_entity(mob).await;
+            }
             sender
                 .send_message(TextComponent::translate(
                     "commands.summon.success",
@@ -0,0 +1,585 @@
+use async_trait::async_trait;
+use pumpkin_protocol::client::play::{CollisionRule, TeamVisibility};
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::command::args::bool::BoolArgConsumer;
+use crate::command::args::players::PlayersArgumentConsumer;
+use crate::command::args::simple::SimpleArgConsumer;
+use crate::command::args::textcomponent::TextComponentArgConsumer;
+use crate::command::args::{ConsumedArgs, FindArg};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{NonLeafNodeBuilder, argument, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::world::scoreboard::{TEAM_COLORS, Team, color_name};
+
+const NAMES: [&str; 1] = ["team"];
+
+const DESCRIPTION: &str = "Manages teams.";
+
+const ARG_TEAM: &str = "team";
+const ARG_DISPLAY_NAME: &str = "displayName";
+const ARG_MEMBERS: &str = "members";
+const ARG_VALUE: &str = "value";
+
+fn unknown_team(name: &str) -> TextComponent {
+    TextComponent::translate("team.notFound", [TextComponent::text(name.to_string())])
+        .color_named(NamedColor::Red)
+}
+
+/// The scoreboard entries of the member argument, or the sender if there is none.
+fn members(sender: &CommandSender, args: &ConsumedArgs) -> Result<Vec<String>, CommandError> {
+    match PlayersArgumentConsumer::find_arg(args, ARG_MEMBERS) {
+        Ok(players) => Ok(players
+            .iter()
+            .map(|player| player.gameprofile.name.clone())
+            .collect()),
+        Err(_) => sender
+            .as_player()
+            .map(|player| vec![player.gameprofile.name.clone()])
+            .ok_or(CommandError::InvalidRequirement),
+    }
+}
+
+/// `single` or `multiple` feedback, with the member's name or the amount of members.
+fn members_feedback(key: &str, members: &[String], with: Option<TextComponent>) -> TextComponent {
+    let (amount, members) = match members {
+        [member] => ("single", TextComponent::text(member.clone())),
+        members => ("multiple", TextComponent::text(members.len().to_string())),
+    };
+    TextComponent::translate(
+        format!("commands.team.{key}.success.{amount}"),
+        [members].into_iter().chain(with).collect::<Vec<_>>(),
+    )
+}
+
+struct AddTeamExecutor;
+
+#[async_trait]
+impl CommandExecutor for AddTeamExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_TEAM)?;
+        let display_name = TextComponentArgConsumer::find_arg(args, ARG_DISPLAY_NAME)
+            .unwrap_or_else(|_| TextComponent::text(name.to_string()));
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let added = world
+            .scoreboard
+            .lock()
+            .await
+            .add_team(&world, name, display_name.clone())
+            .await;
+        let message = if added {
+            TextComponent::translate("commands.team.add.success", [display_name])
+        } else {
+            TextComponent::translate("commands.team.add.duplicate", []).color_named(NamedColor::Red)
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct RemoveTeamExecutor;
+
+#[async_trait]
+impl CommandExecutor for RemoveTeamExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_TEAM)?;
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let mut scoreboard = world.scoreboard.lock().await;
+        let Some(display_name) = scoreboard.team(name).map(Team::formatted_name) else {
+            drop(scoreboard);
+            sender.send_message(unknown_team(name)).await;
+            return Ok(());
+        };
+        scoreboard.remove_team(&world, name).await;
+        drop(scoreboard);
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.team.remove.success",
+                [display_name],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct EmptyTeamExecutor;
+
+#[async_trait]
+impl CommandExecutor for EmptyTeamExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_TEAM)?;
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let mut scoreboard = world.scoreboard.lock().await;
+        let Some(display_name) = scoreboard.team(name).map(Team::formatted_name) else {
+            drop(scoreboard);
+            sender.send_message(unknown_team(name)).await;
+            return Ok(());
+        };
+        let removed = scoreboard.empty_team(&world, name).await.unwrap_or(0);
+        drop(scoreboard);
+
+        let message = if removed == 0 {
+            TextComponent::translate("commands.team.empty.unchanged", [])
+                .color_named(NamedColor::Red)
+        } else {
+            TextComponent::translate(
+                "commands.team.empty.success",
+                [TextComponent::text(removed.to_string()), display_name],
+            )
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct JoinTeamExecutor;
+
+#[async_trait]
+impl CommandExecutor for JoinTeamExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_TEAM)?;
+        let members = members(sender, args)?;
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let mut scoreboard = world.scoreboard.lock().await;
+        let Some(display_name) = scoreboard.team(name).map(Team::formatted_name) else {
+            drop(scoreboard);
+            sender.send_message(unknown_team(name)).await;
+            return Ok(());
+        };
+        scoreboard.join_team(&world, name, &members).await;
+        drop(scoreboard);
+
+        sender
+            .send_message(members_feedback("join", &members, Some(display_name)))
+            .await;
+        Ok(())
+    }
+}
+
+struct LeaveTeamExecutor;
+
+#[async_trait]
+impl CommandExecutor for LeaveTeamExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let members = members(sender, args)?;
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        world
+            .scoreboard
+            .lock()
+            .await
+            .leave_team(&world, &members)
+            .await;
+        sender
+            .send_message(members_feedback("leave", &members, None))
+            .await;
+        Ok(())
+    }
+}
+
+/// Lists the members of the team argument, or all teams when there is no argument.
+struct ListTeamsExecutor;
+
+#[async_trait]
+impl CommandExecutor for ListTeamsExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_TEAM).ok();
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let scoreboard = world.scoreboard.lock().await;
+        let message = match name {
+            Some(name) => match scoreboard.team(name) {
+                None => unknown_team(name),
+                Some(team) => {
+                    let members: Vec<_> = team.members().collect();
+                    if members.is_empty() {
+                        TextComponent::translate(
+                            "commands.team.list.members.empty",
+                            [team.formatted_name()],
+                        )
+                    } else {
+                        TextComponent::translate(
+                            "commands.team.list.members.success",
+                            [
+                                team.formatted_name(),
+                                TextComponent::text(members.len().to_string()),
+                                TextComponent::text(members.join(", ")),
+                            ],
+                        )
+                    }
+                }
+            },
+            None => {
+                let names = scoreboard.team_names();
+                if names.is_empty() {
+                    TextComponent::translate("commands.team.list.teams.empty", [])
+                } else {
+                    TextComponent::translate(
+                        "commands.team.list.teams.success",
+                        [
+                            TextComponent::text(names.len().to_string()),
+                            TextComponent::text(names.join(", ")),
+                        ],
+                    )
+                }
+            }
+        };
+        drop(scoreboard);
+
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy)]
+enum TeamOption {
+    DisplayName,
+    Color(Option<NamedColor>),
+    FriendlyFire,
+    SeeFriendlyInvisibles,
+    NametagVisibility(TeamVisibility),
+    DeathMessageVisibility(TeamVisibility),
+    CollisionRule(CollisionRule),
+    Prefix,
+    Suffix,
+}
+
+/// Changes an option of the team argument.
+struct ModifyTeamExecutor(TeamOption);
+
+impl ModifyTeamExecutor {
+    /// Applies the option to the team, returning the feedback and whether anything changed.
+    fn apply(
+        &self,
+        team: &mut Team,
+        args: &ConsumedArgs,
+    ) -> Result<(TextComponent, bool), CommandError> {
+        let name = team.formatted_name();
+        let unchanged = |key: &str| {
+            TextComponent::translate(format!("commands.team.option.{key}.unchanged"), [])
+                .color_named(NamedColor::Red)
+        };
+        let toggled = |key: &str, enabled: bool| {
+            let state = if enabled { "enabled" } else { "disabled" };
+            TextComponent::translate(
+                format!("commands.team.option.{key}.{state}"),
+                [name.clone()],
+            )
+        };
+        let already = |key: &str, enabled: bool| {
+            let state = if enabled {
+                "alreadyEnabled"
+            } else {
+                "alreadyDisabled"
+            };
+            TextComponent::translate(format!("commands.team.option.{key}.{state}"), [])
+                .color_named(NamedColor::Red)
+        };
+        let text = || TextComponentArgConsumer::find_arg(args, ARG_VALUE);
+
+        Ok(match self.0 {
+            TeamOption::DisplayName => {
+                let display_name = text()?;
+                if team.display_name == display_name {
+                    (unchanged("name"), false)
+                } else {
+                    team.display_name = display_name;
+                    (
+                        TextComponent::translate(
+                            "commands.team.option.name.success",
+                            [team.formatted_name()],
+                        ),
+                        true,
+                    )
+                }
+            }
+            TeamOption::Color(color) => {
+                if team.color == color {
+                    (unchanged("color"), false)
+                } else {
+                    team.color = color;
+                    let color_name = color.map_or("reset", color_name);
+                    (
+                        TextComponent::translate(
+                            "commands.team.option.color.success",
+                            [team.formatted_name(), TextComponent::text(color_name)],
+                        ),
+                        true,
+                    )
+                }
+            }
+            TeamOption::FriendlyFire => {
+                let enabled = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+                if team.friendly_fire == enabled {
+                    (already("friendlyfire", enabled), false)
+                } else {
+                    team.friendly_fire = enabled;
+                    (toggled("friendlyfire", enabled), true)
+                }
+            }
+            TeamOption::SeeFriendlyInvisibles => {
+                let enabled = BoolArgConsumer::find_arg(args, ARG_VALUE)?;
+                if team.see_friendly_invisibles == enabled {
+                    (already("seeFriendlyInvisibles", enabled), false)
+                } else {
+                    team.see_friendly_invisibles = enabled;
+                    (toggled("seeFriendlyInvisibles", enabled), true)
+                }
+            }
+            TeamOption::NametagVisibility(visibility) => {
+                if team.name_tag_visibility == visibility {
+                    (unchanged("nametagVisibility"), false)
+                } else {
+                    team.name_tag_visibility = visibility;
+                    (
+                        TextComponent::translate(
+                            "commands.team.option.nametagVisibility.success",
+                            [
+                                team.formatted_name(),
+                                TextComponent::translate(
+                                    format!("team.visibility.{}", visibility.name()),
+                                    [],
+                                ),
+                            ],
+                        ),
+                        true,
+                    )
+                }
+            }
+            TeamOption::DeathMessageVisibility(visibility) => {
+                if team.death_message_visibility == visibility {
+                    (unchanged("deathMessageVisibility"), false)
+                } else {
+                    team.death_message_visibility = visibility;
+                    (
+                        TextComponent::translate(
+                            "commands.team.option.deathMessageVisibility.success",
+                            [
+                                team.formatted_name(),
+                                TextComponent::translate(
+                                    format!("team.visibility.{}", visibility.name()),
+                                    [],
+                                ),
+                            ],
+                        ),
+                        true,
+                    )
+                }
+            }
+            TeamOption::CollisionRule(rule) => {
+                if team.collision_rule == rule {
+                    (unchanged("collisionRule"), false)
+                } else {
+                    team.collision_rule = rule;
+                    (
+                        TextComponent::translate(
+                            "commands.team.option.collisionRule.success",
+                            [
+                                team.formatted_name(),
+                                TextComponent::translate(
+                                    format!("team.collision.{}", rule.name()),
+                                    [],
+                                ),
+                            ],
+                        ),
+                        true,
+                    )
+                }
+            }
+            TeamOption::Prefix => {
+                team.prefix = text()?;
+                (
+                    TextComponent::translate(
+                        "commands.team.option.prefix.success",
+                        [team.prefix.clone()],
+                    ),
+                    true,
+                )
+            }
+            TeamOption::Suffix => {
+                team.suffix = text()?;
+                (
+                    TextComponent::translate(
+                        "commands.team.option.suffix.success",
+                        [team.suffix.clone()],
+                    ),
+                    true,
+                )
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl CommandExecutor for ModifyTeamExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let name = SimpleArgConsumer::find_arg(args, ARG_TEAM)?;
+        let world = sender
+            .world()
+            .await
+            .ok_or(CommandError::InvalidRequirement)?;
+
+        let mut scoreboard = world.scoreboard.lock().await;
+        let Some(team) = scoreboard.team_mut(name) else {
+            drop(scoreboard);
+            sender.send_message(unknown_team(name)).await;
+            return Ok(());
+        };
+        let (message, changed) = self.apply(team, args)?;
+        if changed {
+            scoreboard.send_team_update(&world, name).await;
+        }
+        drop(scoreboard);
+
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+fn modify_tree() -> NonLeafNodeBuilder {
+    let text_option = |option: &'static str, team_option| {
+        literal(option).then(
+            argument(ARG_VALUE, TextComponentArgConsumer).execute(ModifyTeamExecutor(team_option)),
+        )
+    };
+    let bool_option = |option: &'static str, team_option| {
+        literal(option)
+            .then(argument(ARG_VALUE, BoolArgConsumer).execute(ModifyTeamExecutor(team_option)))
+    };
+
+    let mut color = literal("color")
+        .then(literal("reset").execute(ModifyTeamExecutor(TeamOption::Color(None))));
+    for (color_name, named) in TEAM_COLORS {
+        color = color
+            .then(literal(color_name).execute(ModifyTeamExecutor(TeamOption::Color(Some(named)))));
+    }
+    let mut nametag_visibility = literal("nametagVisibility");
+    let mut death_message_visibility = literal("deathMessageVisibility");
+    for visibility in TeamVisibility::ALL {
+        nametag_visibility = nametag_visibility.then(literal(visibility.name()).execute(
+            ModifyTeamExecutor(TeamOption::NametagVisibility(visibility)),
+        ));
+        death_message_visibility =
+            death_message_visibility.then(literal(visibility.name()).execute(ModifyTeamExecutor(
+                TeamOption::DeathMessageVisibility(visibility),
+            )));
+    }
+    let mut collision_rule = literal("collisionRule");
+    for rule in CollisionRule::ALL {
+        collision_rule = collision_rule.then(
+            literal(rule.name()).execute(ModifyTeamExecutor(TeamOption::CollisionRule(rule))),
+        );
+    }
+
+    literal("modify").then(
+        argument(ARG_TEAM, SimpleArgConsumer)
+            .then(text_option("displayName", TeamOption::DisplayName))
+            .then(color)
+            .then(bool_option("friendlyFire", TeamOption::FriendlyFire))
+            .then(bool_option(
+                "seeFriendlyInvisibles",
+                TeamOption::SeeFriendlyInvisibles,
+            ))
+            .then(nametag_visibility)
+            .then(death_message_visibility)
+            .then(collision_rule)
+            .then(text_option("prefix", TeamOption::Prefix))
+            .then(text_option("suffix", TeamOption::Suffix)),
+    )
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(
+            literal("add").then(
+                argument(ARG_TEAM, SimpleArgConsumer)
+                    .execute(AddTeamExecutor)
+                    .then(
+                        argument(ARG_DISPLAY_NAME, TextComponentArgConsumer)
+                            .execute(AddTeamExecutor),
+                    ),
+            ),
+        )
+        .then(
+            literal("remove")
+                .then(argument(ARG_TEAM, SimpleArgConsumer).execute(RemoveTeamExecutor)),
+        )
+        .then(
+            literal("empty").then(argument(ARG_TEAM, SimpleArgConsumer).execute(EmptyTeamExecutor)),
+        )
+        .then(
+            literal("join").then(
+                argument(ARG_TEAM, SimpleArgConsumer)
+                    .execute(JoinTeamExecutor)
+                    .then(argument(ARG_MEMBERS, PlayersArgumentConsumer).execute(JoinTeamExecutor)),
+            ),
+        )
+        .then(
+            literal("leave")
+                .then(argument(ARG_MEMBERS, PlayersArgumentConsumer).execute(LeaveTeamExecutor)),
+        )
+        .then(
+            literal("list")
+                .execute(ListTeamsExecutor)
+                .then(argument(ARG_TEAM, SimpleArgConsumer).execute(ListTeamsExecutor)),
+        )
+        .then(modify_tree())
+}
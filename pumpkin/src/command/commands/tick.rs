@@ -0,0 +1,286 @@
+use async_trait::async_trait;
+use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::color::NamedColor;
+
+use crate::command::args::bounded_num::BoundedNumArgumentConsumer;
+use crate::command::args::time::TimeArgumentConsumer;
+use crate::command::args::{ConsumedArgs, FindArg, FindArgDefaultName};
+use crate::command::tree::CommandTree;
+use crate::command::tree::builder::{argument, argument_default_name, literal};
+use crate::command::{CommandError, CommandExecutor, CommandSender};
+use crate::server::Server;
+use crate::server::tick_rate::{MAX_TICK_RATE, MIN_TICK_RATE};
+
+const NAMES: [&str; 1] = ["tick"];
+
+const DESCRIPTION: &str = "Controls the tick rate of the game.";
+
+const ARG_TIME: &str = "time";
+
+fn rate_consumer() -> BoundedNumArgumentConsumer<f32> {
+    BoundedNumArgumentConsumer::new()
+        .min(MIN_TICK_RATE)
+        .max(MAX_TICK_RATE)
+        .name("rate")
+}
+
+struct QueryExecutor;
+
+#[async_trait]
+impl CommandExecutor for QueryExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let (tick_rate, frozen, sprinting) = {
+            let manager = server.tick_rate_manager.lock().unwrap();
+            (
+                manager.tick_rate(),
+                manager.is_frozen(),
+                manager.is_sprinting(),
+            )
+        };
+        let target_mspt = 1000.0 / f64::from(tick_rate);
+        let mspt = server.get_average_tick_time_nanos() as f64 / 1_000_000.0;
+
+        let status = if sprinting {
+            "commands.tick.status.sprinting"
+        } else if frozen {
+            "commands.tick.status.frozen"
+        } else if mspt > target_mspt {
+            "commands.tick.status.lagging"
+        } else {
+            "commands.tick.status.running"
+        };
+        sender
+            .send_message(TextComponent::translate(status, []))
+            .await;
+
+        let rate = TextComponent::text(format!("{tick_rate:.1}"));
+        let mspt = TextComponent::text(format!("{mspt:.1}"));
+        let message = if sprinting {
+            TextComponent::translate("commands.tick.query.rate.sprinting", [rate, mspt])
+        } else {
+            TextComponent::translate(
+                "commands.tick.query.rate.running",
+                [rate, mspt, TextComponent::text(format!("{target_mspt:.1}"))],
+            )
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct RateExecutor;
+
+#[async_trait]
+impl CommandExecutor for RateExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let rate = rate_consumer().find_arg_default_name(args)??;
+        server.tick_rate_manager.lock().unwrap().set_tick_rate(rate);
+        server.send_tick_state().await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.tick.rate.success",
+                [TextComponent::text(format!("{rate:.1}"))],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct FreezeExecutor(bool);
+
+#[async_trait]
+impl CommandExecutor for FreezeExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let frozen = self.0;
+        let sprint_report = {
+            let mut manager = server.tick_rate_manager.lock().unwrap();
+            let report = if frozen {
+                manager.stop_stepping();
+                manager.stop_sprinting()
+            } else {
+                None
+            };
+            manager.set_frozen(frozen);
+            report
+        };
+        if let Some(report) = sprint_report {
+            server.broadcast_sprint_report(&report).await;
+        }
+        server.send_tick_state().await;
+
+        let status = if frozen {
+            "commands.tick.status.frozen"
+        } else {
+            "commands.tick.status.running"
+        };
+        sender
+            .send_message(TextComponent::translate(status, []))
+            .await;
+        Ok(())
+    }
+}
+
+/// Steps a frozen game by the given ticks, one tick without an argument.
+struct StepExecutor;
+
+#[async_trait]
+impl CommandExecutor for StepExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let ticks = TimeArgumentConsumer::find_arg(args, ARG_TIME).unwrap_or(1);
+        let stepped = server
+            .tick_rate_manager
+            .lock()
+            .unwrap()
+            .step(ticks.max(1) as u32);
+        if !stepped {
+            sender
+                .send_message(
+                    TextComponent::translate("commands.tick.step.fail", [])
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        }
+        server.send_tick_state().await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.tick.step.success",
+                [TextComponent::text(ticks.to_string())],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct StopStepExecutor;
+
+#[async_trait]
+impl CommandExecutor for StopStepExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let stopped = server.tick_rate_manager.lock().unwrap().stop_stepping();
+        let message = if stopped {
+            server.send_tick_state().await;
+            TextComponent::translate("commands.tick.step.stop.success", [])
+        } else {
+            TextComponent::translate("commands.tick.step.stop.fail", [])
+                .color_named(NamedColor::Red)
+        };
+        sender.send_message(message).await;
+        Ok(())
+    }
+}
+
+struct SprintExecutor;
+
+#[async_trait]
+impl CommandExecutor for SprintExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let ticks = TimeArgumentConsumer::find_arg(args, ARG_TIME)?;
+        let replaced = server
+            .tick_rate_manager
+            .lock()
+            .unwrap()
+            .sprint(ticks.max(1) as u64);
+        server.send_tick_state().await;
+
+        if replaced {
+            sender
+                .send_message(TextComponent::translate(
+                    "commands.tick.sprint.stop.success",
+                    [],
+                ))
+                .await;
+        }
+        sender
+            .send_message(TextComponent::translate(
+                "commands.tick.status.sprinting",
+                [],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+struct StopSprintExecutor;
+
+#[async_trait]
+impl CommandExecutor for StopSprintExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        server: &Server,
+        _args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let report = server.tick_rate_manager.lock().unwrap().stop_sprinting();
+        let Some(report) = report else {
+            sender
+                .send_message(
+                    TextComponent::translate("commands.tick.sprint.stop.fail", [])
+                        .color_named(NamedColor::Red),
+                )
+                .await;
+            return Ok(());
+        };
+        server.broadcast_sprint_report(&report).await;
+        server.send_tick_state().await;
+
+        sender
+            .send_message(TextComponent::translate(
+                "commands.tick.sprint.stop.success",
+                [],
+            ))
+            .await;
+        Ok(())
+    }
+}
+
+pub fn init_command_tree() -> CommandTree {
+    CommandTree::new(NAMES, DESCRIPTION)
+        .then(literal("query").execute(QueryExecutor))
+        .then(literal("rate").then(argument_default_name(rate_consumer()).execute(RateExecutor)))
+        .then(literal("freeze").execute(FreezeExecutor(true)))
+        .then(literal("unfreeze").execute(FreezeExecutor(false)))
+        .then(
+            literal("step")
+                .execute(StepExecutor)
+                .then(literal("stop").execute(StopStepExecutor))
+                .then(argument(ARG_TIME, TimeArgumentConsumer).execute(StepExecutor)),
+        )
+        .then(
+            literal("sprint")
+                .then(literal("stop").execute(StopSprintExecutor))
+                .then(argument(ARG_TIME, TimeArgumentConsumer).execute(SprintExecutor)),
+        )
+}
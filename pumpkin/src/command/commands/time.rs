@@ -7,6 +7,7 @@ use crate::command::tree::builder::{argument, literal};
 use crate::command::{
     CommandError, CommandExecutor, CommandSender, ConsumedArgs, tree::CommandTree,
 };
+use crate::world::time::LevelTime;
 
 const NAMES: [&str; 1] = ["time"];
 const DESCRIPTION: &str = "Query the world time.";
@@ -37,6 +38,22 @@ enum Mode {
     Set(Option<PresetTime>),
 }
 
+impl Mode {
+    /// Changes the day time, the game time keeps counting as it is. Returns the time to report.
+    fn apply(self, level_time: &mut LevelTime, ticks: i32) -> i64 {
+        match self {
+            Self::Add => {
+                level_time.add_time(ticks.into());
+                level_time.query_daytime()
+            }
+            Self::Set(_) => {
+                level_time.set_time(ticks.into());
+                ticks.into()
+            }
+        }
+    }
+}
+
 #[derive(Clone, Copy)]
 enum QueryMode {
     DayTime,
@@ -123,29 +140,15 @@ impl CommandExecutor for ChangeExecutor {
             .expect("There should always be at least one world");
         let mut level_time = world.level_time.lock().await;
 
-        let msg = match mode {
-            Mode::Add => {
-                // add
-                level_time.add_time(time_count.into());
-                level_time.send_time(world).await;
-                let curr_time = level_time.query_daytime();
-                TextComponent::translate(
-                    "commands.time.set",
-                    [TextComponent::text(curr_time.to_string())],
-                )
-            }
-            Mode::Set(_) => {
-                // set
-                level_time.set_time(time_count.into());
-                level_time.send_time(world).await;
-                TextComponent::translate(
-                    "commands.time.set",
-                    [TextComponent::text(time_count.to_string())],
-                )
-            }
-        };
+        let curr_time = mode.apply(&mut level_time, time_count);
+        level_time.send_time(world).await;
 
-        sender.send_message(msg).await;
+        sender
+            .send_message(TextComponent::translate(
+                "commands.time.set",
+                [TextComponent::text(curr_time.to_string())],
+            ))
+            .await;
         Ok(())
     }
 }
@@ -177,3 +180,26 @@ pub fn init_command_tree() -> CommandTree {
                 ),
         )
 }
+
+#[cfg(test)]
+mod test {
+    use crate::world::time::LevelTime;
+
+    use super::{Mode, PresetTime};
+
+    #[test]
+    fn setting_the_time_keeps_the_game_time() {
+        let mut time = LevelTime::new();
+        time.world_age = 50_000;
+        time.time_of_day = 40_000;
+
+        let day = Mode::Set(Some(PresetTime::Day));
+        assert_eq!(day.apply(&mut time, PresetTime::Day.to_ticks()), 1000);
+        assert_eq!(time.query_daytime(), 1000);
+        assert_eq!(time.query_gametime(), 50_000);
+
+        assert_eq!(Mode::Add.apply(&mut time, 24_500), 1500);
+        assert_eq!(time.query_day(), 1);
+        assert_eq!(time.query_gametime(), 50_000);
+    }
+}
@@ -6,11 +6,12 @@ use crate::{
     command::{
         CommandError, CommandExecutor, CommandSender,
         args::{
-            Arg, ConsumedArgs, FindArg, players::PlayersArgumentConsumer,
+            Arg, ConsumedArgs, FindArg, FindArgDefaultName,
+            bounded_num::BoundedNumArgumentConsumer, players::PlayersArgumentConsumer,
             textcomponent::TextComponentArgConsumer,
         },
         tree::CommandTree,
-        tree::builder::{argument, literal},
+        tree::builder::{argument, argument_default_name, literal},
     },
     entity::player::TitleMode,
 };
@@ -22,6 +23,16 @@ const ARG_TARGETS: &str = "targets";
 
 const ARG_TITLE: &str = "title";
 
+const ARG_FADE_IN: &str = "fadeIn";
+
+const ARG_STAY: &str = "stay";
+
+const ARG_FADE_OUT: &str = "fadeOut";
+
+fn ticks_consumer(name: &'static str) -> BoundedNumArgumentConsumer<i32> {
+    BoundedNumArgumentConsumer::new().min(0).name(name)
+}
+
 /// bool: Whether to reset or not
 struct ClearOrResetExecutor(bool);
 
@@ -107,6 +118,49 @@ impl CommandExecutor for TitleExecutor {
     }
 }
 
+struct TimesExecutor;
+
+#[async_trait]
+impl CommandExecutor for TimesExecutor {
+    async fn execute<'a>(
+        &self,
+        sender: &mut CommandSender,
+        _server: &crate::server::Server,
+        args: &ConsumedArgs<'a>,
+    ) -> Result<(), CommandError> {
+        let Some(Arg::Players(targets)) = args.get(&ARG_TARGETS) else {
+            return Err(CommandError::InvalidConsumption(Some(ARG_TARGETS.into())));
+        };
+        let mut times = [0; 3];
+        for (time, name) in times.iter_mut().zip([ARG_FADE_IN, ARG_STAY, ARG_FADE_OUT]) {
+            let Ok(ticks) = ticks_consumer(name).find_arg_default_name(args)? else {
+                return Err(CommandError::InvalidConsumption(Some(name.into())));
+            };
+            *time = ticks;
+        }
+        let [fade_in, stay, fade_out] = times;
+
+        for target in targets {
+            target.set_title_times(fade_in, stay, fade_out).await;
+        }
+        sender
+            .send_message(if targets.len() == 1 {
+                TextComponent::translate(
+                    "commands.title.times.single",
+                    [TextComponent::text(targets[0].gameprofile.name.clone())],
+                )
+            } else {
+                TextComponent::translate(
+                    "commands.title.times.multiple",
+                    [TextComponent::text(targets.len().to_string())],
+                )
+            })
+            .await;
+
+        Ok(())
+    }
+}
+
 pub fn init_command_tree() -> CommandTree {
     CommandTree::new(NAMES, DESCRIPTION).then(
         argument(ARG_TARGETS, PlayersArgumentConsumer)
@@ -129,7 +183,51 @@ pub fn init_command_tree() -> CommandTree {
                     argument(ARG_TITLE, TextComponentArgConsumer)
                         .execute(TitleExecutor(TitleMode::ActionBar)),
                 ),
+            )
+            .then(
+                literal("times").then(argument_default_name(ticks_consumer(ARG_FADE_IN)).then(
+                    argument_default_name(ticks_consumer(ARG_STAY)).then(
+                        argument_default_name(ticks_consumer(ARG_FADE_OUT)).execute(TimesExecutor),
+                    ),
+                )),
             ),
-        // TODO: times
     )
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::packet::clientbound::{PLAY_SET_TITLE_TEXT, PLAY_SET_TITLES_ANIMATION};
+    use pumpkin_protocol::{ClientPacket, client::play::CTitleText};
+    use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+    use crate::test_utils::{run_console_command, test_player, test_server};
+
+    #[tokio::test]
+    async fn title_with_times_is_enqueued() {
+        let server = test_server();
+        let (_alice, mut outgoing) = test_player(&server, "alice").await;
+
+        let output = run_console_command(&server, "title alice times 10 70 20").await;
+        assert_eq!(output.len(), 1);
+        let output = run_console_command(
+            &server,
+            r#"title alice title {"text":"Welcome","color":"gold"}"#,
+        )
+        .await;
+        assert_eq!(output.len(), 1);
+
+        let (times, _) = outgoing.try_recv().unwrap();
+        assert_eq!(i32::from(times[0]), PLAY_SET_TITLES_ANIMATION);
+        assert_eq!(&times[1..], &[0, 0, 0, 10, 0, 0, 0, 70, 0, 0, 0, 20]);
+
+        let expected_text = TextComponent::text("Welcome").color_named(NamedColor::Gold);
+        let mut expected = Vec::new();
+        CTitleText::new(&expected_text)
+            .write(&mut expected)
+            .unwrap();
+        let (title, _) = outgoing.try_recv().unwrap();
+        assert_eq!(i32::from(title[0]), PLAY_SET_TITLE_TEXT);
+        assert_eq!(&title[..], &expected[..]);
+        assert!(outgoing.try_recv().is_err());
+    }
+}
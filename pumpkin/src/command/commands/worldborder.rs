@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
 use pumpkin_util::{
     math::vector2::Vector2,
@@ -18,6 +20,7 @@ use crate::{
         tree::builder::{argument_default_name, literal},
     },
     server::Server,
+    world::World,
 };
 
 const NAMES: [&str; 1] = ["worldborder"];
@@ -26,6 +29,20 @@ const DESCRIPTION: &str = "Worldborder command.";
 
 const NOTHING_CHANGED_EXCEPTION: &str = "commands.worldborder.set.failed.nochange";
 
+/// Borders belong to a world: players change the one they are in, the console the first world.
+async fn border_world(sender: &CommandSender, server: &Server) -> Arc<World> {
+    match sender.world().await {
+        Some(world) => world,
+        None => server
+            .worlds
+            .read()
+            .await
+            .first()
+            .cloned()
+            .expect("There should always be at least one world"),
+    }
+}
+
 fn distance_consumer() -> BoundedNumArgumentConsumer<f64> {
     BoundedNumArgumentConsumer::new().min(0.0).name("distance")
 }
@@ -58,14 +75,10 @@ impl CommandExecutor for GetExecutor {
         server: &Server,
         _args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = border_world(sender, server).await;
         let border = world.worldborder.lock().await;
 
-        let diameter = border.new_diameter.round() as i32;
+        let diameter = border.diameter().round() as i32;
         sender
             .send_message(TextComponent::translate(
                 "commands.worldborder.get",
@@ -86,11 +99,7 @@ impl CommandExecutor for SetExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = border_world(sender, server).await;
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -106,7 +115,7 @@ impl CommandExecutor for SetExecutor {
             return Ok(());
         };
 
-        if (distance - border.new_diameter).abs() < f64::EPSILON {
+        if (distance - border.diameter()).abs() < f64::EPSILON {
             sender
                 .send_message(
                     TextComponent::translate(NOTHING_CHANGED_EXCEPTION, [])
@@ -123,7 +132,7 @@ impl CommandExecutor for SetExecutor {
                 [TextComponent::text(dist)],
             ))
             .await;
-        border.set_diameter(world, distance, None).await;
+        border.set_diameter(&world, distance, None).await;
         Ok(())
     }
 }
@@ -138,11 +147,7 @@ impl CommandExecutor for SetTimeExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = border_world(sender, server).await;
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -170,7 +175,7 @@ impl CommandExecutor for SetTimeExecutor {
             return Ok(());
         };
 
-        match distance.total_cmp(&border.new_diameter) {
+        match distance.total_cmp(&border.diameter()) {
             std::cmp::Ordering::Equal => {
                 sender
                     .send_message(
@@ -207,7 +212,7 @@ impl CommandExecutor for SetTimeExecutor {
         }
 
         border
-            .set_diameter(world, distance, Some(i64::from(time) * 1000))
+            .set_diameter(&world, distance, Some(i64::from(time) * 1000))
             .await;
         Ok(())
     }
@@ -223,11 +228,7 @@ impl CommandExecutor for AddExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = border_world(sender, server).await;
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -253,7 +254,7 @@ impl CommandExecutor for AddExecutor {
             return Ok(());
         }
 
-        let distance = border.new_diameter + distance;
+        let distance = border.diameter() + distance;
 
         let dist = format!("{distance:.1}");
         sender
@@ -262,7 +263,7 @@ impl CommandExecutor for AddExecutor {
                 [TextComponent::text(dist)],
             ))
             .await;
-        border.set_diameter(world, distance, None).await;
+        border.set_diameter(&world, distance, None).await;
         Ok(())
     }
 }
@@ -277,11 +278,7 @@ impl CommandExecutor for AddTimeExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = border_world(sender, server).await;
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = distance_consumer().find_arg_default_name(args)? else {
@@ -309,9 +306,9 @@ impl CommandExecutor for AddTimeExecutor {
             return Ok(());
         };
 
-        let distance = distance + border.new_diameter;
+        let distance = distance + border.diameter();
 
-        match distance.total_cmp(&border.new_diameter) {
+        match distance.total_cmp(&border.diameter()) {
             std::cmp::Ordering::Equal => {
                 sender
                     .send_message(
@@ -348,7 +345,7 @@ impl CommandExecutor for AddTimeExecutor {
         }
 
         border
-            .set_diameter(world, distance, Some(i64::from(time) * 1000))
+            .set_diameter(&world, distance, Some(i64::from(time) * 1000))
             .await;
         Ok(())
     }
@@ -364,15 +361,20 @@ impl CommandExecutor for CenterExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = border_world(sender, server).await;
         let mut border = world.worldborder.lock().await;
 
         let Vector2 { x, z } = Position2DArgumentConsumer.find_arg_default_name(args)?;
 
+        if !border.set_center(&world, x, z).await {
+            sender
+                .send_message(
+                    TextComponent::translate("commands.worldborder.center.failed", [])
+                        .color(Color::Named(NamedColor::Red)),
+                )
+                .await;
+            return Ok(());
+        }
         sender
             .send_message(TextComponent::translate(
                 "commands.worldborder.center.success",
@@ -382,7 +384,6 @@ impl CommandExecutor for CenterExecutor {
                 ],
             ))
             .await;
-        border.set_center(world, x, z).await;
         Ok(())
     }
 }
@@ -397,11 +398,7 @@ impl CommandExecutor for DamageAmountExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = border_world(sender, server).await;
         let mut border = world.worldborder.lock().await;
 
         let Ok(damage_per_block) = damage_per_block_consumer().find_arg_default_name(args)? else {
@@ -449,11 +446,7 @@ impl CommandExecutor for DamageBufferExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = border_world(sender, server).await;
         let mut border = world.worldborder.lock().await;
 
         let Ok(buffer) = damage_buffer_consumer().find_arg_default_name(args)? else {
@@ -501,11 +494,7 @@ impl CommandExecutor for WarningDistanceExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = border_world(sender, server).await;
         let mut border = world.worldborder.lock().await;
 
         let Ok(distance) = warning_distance_consumer().find_arg_default_name(args)? else {
@@ -537,7 +526,7 @@ impl CommandExecutor for WarningDistanceExecutor {
                 [TextComponent::text(distance.to_string())],
             ))
             .await;
-        border.set_warning_distance(world, distance).await;
+        border.set_warning_distance(&world, distance).await;
         Ok(())
     }
 }
@@ -552,11 +541,7 @@ impl CommandExecutor for WarningTimeExecutor {
         server: &Server,
         args: &ConsumedArgs<'a>,
     ) -> Result<(), CommandError> {
-        // TODO: Maybe ask player for world, or get the current world
-        let worlds = server.worlds.read().await;
-        let world = worlds
-            .first()
-            .expect("There should always be at least one world");
+        let world = border_world(sender, server).await;
         let mut border = world.worldborder.lock().await;
 
         let Ok(time) = time_consumer().find_arg_default_name(args)? else {
@@ -588,7 +573,7 @@ impl CommandExecutor for WarningTimeExecutor {
                 [TextComponent::text(time.to_string())],
             ))
             .await;
-        border.set_warning_delay(world, time).await;
+        border.set_warning_delay(&world, time).await;
         Ok(())
     }
 }
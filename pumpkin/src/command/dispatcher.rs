@@ -2,17 +2,19 @@ use pumpkin_protocol::client::play::CommandSuggestion;
 use pumpkin_util::permission::PermissionLvl;
 use pumpkin_util::text::TextComponent;
 
-use super::args::ConsumedArgs;
+use super::args::{Arg, ConsumedArgs};
 
 use crate::command::CommandSender;
 use crate::command::dispatcher::CommandError::{
     GeneralCommandIssue, InvalidConsumption, InvalidRequirement, OtherPumpkin, PermissionDenied,
 };
+use crate::command::permission::{OpPermissionProvider, PermissionProvider, command_node};
 use crate::command::tree::{Command, CommandTree, NodeType, RawArgs};
 use crate::error::PumpkinError;
 use crate::server::Server;
 use pumpkin_util::text::color::{Color, NamedColor};
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 #[derive(Debug)]
 pub enum CommandError {
@@ -56,10 +58,23 @@ impl CommandError {
     }
 }
 
-#[derive(Default)]
 pub struct CommandDispatcher {
     pub(crate) commands: HashMap<String, Command>,
     pub(crate) permissions: HashMap<String, PermissionLvl>,
+    /// The permission node of every command name, aliases share the node of their command
+    pub(crate) permission_nodes: HashMap<String, String>,
+    pub(crate) permission_provider: Arc<dyn PermissionProvider>,
+}
+
+impl Default for CommandDispatcher {
+    fn default() -> Self {
+        Self {
+            commands: HashMap::new(),
+            permissions: HashMap::new(),
+            permission_nodes: HashMap::new(),
+            permission_provider: Arc::new(OpPermissionProvider),
+        }
+    }
 }
 
 /// Stores registered [`CommandTree`]s and dispatches commands to them.
@@ -261,13 +276,13 @@ impl CommandDispatcher {
             return Err(GeneralCommandIssue(format!("Command {key} does not exist")));
         }
 
-        let Some(permission) = self.permissions.get(key) else {
+        if !self.permissions.contains_key(key) {
             return Err(GeneralCommandIssue(
                 "Permission for Command not found".to_string(),
             ));
-        };
+        }
 
-        if !src.has_permission_lvl(*permission) {
+        if !self.has_permission(src, key).await {
             return Err(PermissionDenied);
         }
 
@@ -284,6 +299,31 @@ impl CommandDispatcher {
         )))
     }
 
+    /// The message arguments of `cmd` by name, in the order they appear in the command. Clients
+    /// sign these arguments when chat reports are enabled.
+    pub(crate) async fn message_arguments<'a>(
+        &'a self,
+        src: &mut CommandSender,
+        server: &'a Server,
+        cmd: &'a str,
+    ) -> Vec<(String, String)> {
+        let Ok((key, raw_args)) = Self::split_parts(cmd) else {
+            return Vec::new();
+        };
+        let Ok(tree) = self.get_tree(key) else {
+            return Vec::new();
+        };
+        for path in tree.iter_paths() {
+            if let Some(arguments) =
+                Self::message_arguments_on_path(src, server, &path, tree, &mut raw_args.clone())
+                    .await
+            {
+                return arguments;
+            }
+        }
+        Vec::new()
+    }
+
     pub(crate) fn get_tree<'a>(&'a self, key: &str) -> Result<&'a CommandTree, CommandError> {
         let command = self
             .commands
@@ -310,6 +350,24 @@ impl CommandDispatcher {
         self.permissions.get(key).copied()
     }
 
+    /// Whether the sender may run the command `key`, either by its permission node or by its op
+    /// level.
+    pub(crate) async fn has_permission(&self, sender: &CommandSender, key: &str) -> bool {
+        let (Some(node), Some(permission)) =
+            (self.permission_nodes.get(key), self.get_permission_lvl(key))
+        else {
+            return false;
+        };
+        self.permission_provider
+            .has_permission(sender, node, permission)
+            .await
+    }
+
+    /// Replaces how permission nodes are resolved, e.g. by a permissions plugin.
+    pub fn set_permission_provider(&mut self, provider: Arc<dyn PermissionProvider>) {
+        self.permission_provider = provider;
+    }
+
     async fn try_is_fitting_path<'a>(
         src: &mut CommandSender,
         server: &'a Server,
@@ -365,6 +423,39 @@ impl CommandDispatcher {
         Ok(false)
     }
 
+    /// Parses the arguments like [`Self::try_is_fitting_path`] without executing the command.
+    async fn message_arguments_on_path<'a>(
+        src: &mut CommandSender,
+        server: &'a Server,
+        path: &[usize],
+        tree: &'a CommandTree,
+        raw_args: &mut RawArgs<'a>,
+    ) -> Option<Vec<(String, String)>> {
+        let mut arguments = Vec::new();
+
+        for node in path.iter().map(|&i| &tree.nodes[i]) {
+            match &node.node_type {
+                NodeType::ExecuteLeaf { .. } => return raw_args.is_empty().then_some(arguments),
+                NodeType::Literal { string, .. } => {
+                    if raw_args.pop() != Some(string) {
+                        return None;
+                    }
+                }
+                NodeType::Argument { consumer, name, .. } => {
+                    if let Arg::Msg(message) = consumer.consume(src, server, raw_args).await? {
+                        arguments.push((name.clone(), message));
+                    }
+                }
+                NodeType::Require { predicate, .. } => {
+                    if !predicate(src) {
+                        return None;
+                    }
+                }
+            }
+        }
+        None
+    }
+
     async fn try_find_suggestions_on_path<'a>(
         src: &mut CommandSender,
         server: &'a Server,
@@ -416,15 +507,18 @@ impl CommandDispatcher {
         let mut names = tree.names.iter();
 
         let primary_name = names.next().expect("at least one name must be provided");
+        let node = command_node(primary_name);
 
         for name in names {
             self.commands
                 .insert(name.to_string(), Command::Alias(primary_name.to_string()));
             self.permissions.insert(name.to_string(), permission);
+            self.permission_nodes.insert(name.to_string(), node.clone());
         }
 
         self.permissions
             .insert(primary_name.to_string(), permission);
+        self.permission_nodes.insert(primary_name.to_string(), node);
         self.commands
             .insert(primary_name.to_string(), Command::Tree(tree));
     }
@@ -445,6 +539,7 @@ impl CommandDispatcher {
         for key in to_remove {
             self.commands.remove(&key);
             self.permissions.remove(&key);
+            self.permission_nodes.remove(&key);
         }
     }
 }
@@ -16,6 +16,7 @@ pub mod args;
 pub mod client_suggestions;
 pub mod commands;
 pub mod dispatcher;
+pub mod permission;
 pub mod tree;
 
 pub enum CommandSender {
@@ -0,0 +1,136 @@
+use async_trait::async_trait;
+use pumpkin_util::permission::PermissionLvl;
+use uuid::Uuid;
+
+use crate::data::permission_data::{PERMISSION_CONFIG, PermissionConfig};
+
+use super::CommandSender;
+
+/// The permission node of a command, e.g. `pumpkin.command.teleport`.
+#[must_use]
+pub fn command_node(name: &str) -> String {
+    format!("pumpkin.command.{name}")
+}
+
+/// Decides whether a command sender may use a permission node. Plugins can replace the default
+/// [`OpPermissionProvider`] to hook up their own permission system.
+#[async_trait]
+pub trait PermissionProvider: Send + Sync {
+    /// `default_lvl` is the op level which grants the node without any other permissions.
+    async fn has_permission(
+        &self,
+        sender: &CommandSender,
+        node: &str,
+        default_lvl: PermissionLvl,
+    ) -> bool;
+}
+
+/// Grants nodes to players with the op level of the node, and to the players they were given to
+/// in `permissions.json`.
+pub struct OpPermissionProvider;
+
+#[async_trait]
+impl PermissionProvider for OpPermissionProvider {
+    async fn has_permission(
+        &self,
+        sender: &CommandSender,
+        node: &str,
+        default_lvl: PermissionLvl,
+    ) -> bool {
+        match sender {
+            CommandSender::Console | CommandSender::Rcon(_) => true,
+            CommandSender::Player(player) => player_has_permission(
+                &*PERMISSION_CONFIG.read().await,
+                &player.gameprofile.id,
+                player.permission_lvl.load(),
+                node,
+                default_lvl,
+            ),
+        }
+    }
+}
+
+fn player_has_permission(
+    config: &PermissionConfig,
+    uuid: &Uuid,
+    lvl: PermissionLvl,
+    node: &str,
+    default_lvl: PermissionLvl,
+) -> bool {
+    lvl >= default_lvl || config.is_granted(uuid, node)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::permission::PermissionLvl;
+    use uuid::Uuid;
+
+    use super::{command_node, player_has_permission};
+    use crate::data::permission_data::PermissionConfig;
+
+    #[test]
+    fn granted_node_allows_command_without_op() {
+        let player = Uuid::new_v4();
+        let teleport = command_node("teleport");
+        let mut config = PermissionConfig::default();
+
+        let allowed = |config: &PermissionConfig, node: &str| {
+            player_has_permission(
+                config,
+                &player,
+                PermissionLvl::Zero,
+                node,
+                PermissionLvl::Two,
+            )
+        };
+        assert!(!allowed(&config, &teleport));
+
+        assert!(config.grant(player, "Steve".to_string(), teleport.clone()));
+        assert!(!config.grant(player, "Steve".to_string(), teleport.clone()));
+        assert!(allowed(&config, &teleport));
+        assert!(!allowed(&config, &command_node("kill")));
+        // Nodes only belong to the player they were given to
+        assert!(!player_has_permission(
+            &config,
+            &Uuid::new_v4(),
+            PermissionLvl::Zero,
+            &teleport,
+            PermissionLvl::Two
+        ));
+
+        assert!(config.revoke(&player, &teleport));
+        assert!(!allowed(&config, &teleport));
+        assert!(config.players.is_empty());
+    }
+
+    #[test]
+    fn wildcards_grant_every_node_below() {
+        let player = Uuid::new_v4();
+        let mut config = PermissionConfig::default();
+        config.grant(player, "Alex".to_string(), "pumpkin.command.*".to_string());
+        assert!(config.is_granted(&player, &command_node("kill")));
+        assert!(!config.is_granted(&player, "pumpkin.commandblock"));
+        assert!(!config.is_granted(&player, "other.command.kill"));
+    }
+
+    #[test]
+    fn op_level_grants_node() {
+        let config = PermissionConfig::default();
+        let player = Uuid::new_v4();
+        let node = command_node("kill");
+        assert!(player_has_permission(
+            &config,
+            &player,
+            PermissionLvl::Two,
+            &node,
+            PermissionLvl::Two
+        ));
+        assert!(!player_has_permission(
+            &config,
+            &player,
+            PermissionLvl::One,
+            &node,
+            PermissionLvl::Two
+        ));
+    }
+}
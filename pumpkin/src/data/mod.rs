@@ -1,17 +1,26 @@
 use std::{env, fs, path::Path};
 
+use pumpkin_inventory::{RecipeLoadError, reload_smithing_recipes};
 use serde::{Deserialize, Serialize};
 
 const DATA_FOLDER: &str = "data/";
+const RECIPE_FOLDER: &str = "recipes/";
 
 pub mod op_data;
 
 pub mod banlist_serializer;
 pub mod banned_ip_data;
 pub mod banned_player_data;
+pub mod permission_data;
 pub mod player_server_data;
 pub mod whitelist_data;
 
+/// Loads the smithing recipes from `data/recipes/` in addition to the built-in ones.
+pub fn load_recipes() -> Result<usize, RecipeLoadError> {
+    let exe_dir = env::current_dir().unwrap();
+    reload_smithing_recipes(&exe_dir.join(DATA_FOLDER).join(RECIPE_FOLDER))
+}
+
 pub trait LoadJSONConfiguration {
     #[must_use]
     fn load() -> Self
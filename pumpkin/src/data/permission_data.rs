@@ -0,0 +1,93 @@
+use std::{path::Path, sync::LazyLock};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{LoadJSONConfiguration, SaveJSONConfiguration};
+
+pub static PERMISSION_CONFIG: LazyLock<tokio::sync::RwLock<PermissionConfig>> =
+    LazyLock::new(|| tokio::sync::RwLock::new(PermissionConfig::load()));
+
+/// The permission nodes granted to a player, on top of what their op level allows.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct PermissionEntry {
+    pub uuid: Uuid,
+    pub name: String,
+    pub permissions: Vec<String>,
+}
+
+#[derive(Deserialize, Serialize, Default)]
+#[serde(transparent)]
+pub struct PermissionConfig {
+    pub players: Vec<PermissionEntry>,
+}
+
+impl PermissionConfig {
+    #[must_use]
+    pub fn get_entry(&self, uuid: &Uuid) -> Option<&PermissionEntry> {
+        self.players.iter().find(|entry| entry.uuid.eq(uuid))
+    }
+
+    /// Whether the player was granted `node`, either directly or by a wildcard like
+    /// `pumpkin.command.*`.
+    #[must_use]
+    pub fn is_granted(&self, uuid: &Uuid, node: &str) -> bool {
+        self.get_entry(uuid).is_some_and(|entry| {
+            entry
+                .permissions
+                .iter()
+                .any(|granted| node_matches(granted, node))
+        })
+    }
+
+    /// Grants `node` to the player, returns false if they already had it.
+    pub fn grant(&mut self, uuid: Uuid, name: String, node: String) -> bool {
+        match self.players.iter_mut().find(|entry| entry.uuid == uuid) {
+            Some(entry) if entry.permissions.contains(&node) => false,
+            Some(entry) => {
+                entry.permissions.push(node);
+                true
+            }
+            None => {
+                self.players.push(PermissionEntry {
+                    uuid,
+                    name,
+                    permissions: vec![node],
+                });
+                true
+            }
+        }
+    }
+
+    /// Takes `node` away from the player, returns false if they didn't have it.
+    pub fn revoke(&mut self, uuid: &Uuid, node: &str) -> bool {
+        let Some(entry) = self.players.iter_mut().find(|entry| entry.uuid == *uuid) else {
+            return false;
+        };
+        let count = entry.permissions.len();
+        entry.permissions.retain(|granted| granted != node);
+        let revoked = entry.permissions.len() != count;
+        self.players.retain(|entry| !entry.permissions.is_empty());
+        revoked
+    }
+}
+
+fn node_matches(granted: &str, node: &str) -> bool {
+    granted == "*"
+        || granted == node
+        || granted.strip_suffix(".*").is_some_and(|prefix| {
+            node.strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('.'))
+        })
+}
+
+impl LoadJSONConfiguration for PermissionConfig {
+    fn get_path() -> &'static Path {
+        Path::new("permissions.json")
+    }
+    fn validate(&self) {
+        // TODO: Validate the permission configuration
+    }
+}
+
+impl SaveJSONConfiguration for PermissionConfig {}
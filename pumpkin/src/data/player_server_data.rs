@@ -5,11 +5,15 @@ use crate::{
 use crossbeam::atomic::AtomicCell;
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_world::data::player_data::{PlayerDataError, PlayerDataStorage};
+use serde_json::Value;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::{
     path::PathBuf,
     time::{Duration, Instant},
 };
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use uuid::Uuid;
 /// Helper for managing player data in the server context.
 ///
 /// This struct provides server-wide access to the `PlayerDataStorage` and
@@ -18,6 +22,10 @@ pub struct ServerPlayerData {
     storage: Arc<PlayerDataStorage>,
     save_interval: Duration,
     last_save: AtomicCell<Instant>,
+    /// Whether the periodic save runs, turned off by `/save-off`
+    autosave: AtomicBool,
+    /// Held while saving, so a manual save and the periodic one don't run at the same time
+    saving: Arc<Mutex<()>>,
 }
 
 impl ServerPlayerData {
@@ -27,6 +35,19 @@ impl ServerPlayerData {
             storage: Arc::new(PlayerDataStorage::new(data_path)),
             save_interval,
             last_save: AtomicCell::new(Instant::now()),
+            autosave: AtomicBool::new(true),
+            saving: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Creates a `ServerPlayerData` which doesn't load or save anything, for in-memory worlds.
+    pub fn in_memory(save_interval: Duration) -> Self {
+        Self {
+            storage: Arc::new(PlayerDataStorage::disabled()),
+            save_interval,
+            last_save: AtomicCell::new(Instant::now()),
+            autosave: AtomicBool::new(true),
+            saving: Arc::new(Mutex::new(())),
         }
     }
 
@@ -57,15 +78,29 @@ impl ServerPlayerData {
     ///
     /// A Result indicating success or the error that occurred.
     pub async fn handle_player_leave(&self, player: &Player) -> Result<(), PlayerDataError> {
-        let mut nbt = NbtCompound::new();
-        player.write_nbt(&mut nbt).await;
-
         // Save to disk
-        self.storage.save_player_data(&player.gameprofile.id, nbt)?;
+        self.save_players(vec![Self::player_data(player).await])?;
 
         Ok(())
     }
 
+    /// Turns the periodic save on or off, returning whether it changed.
+    pub fn set_autosave(&self, enabled: bool) -> bool {
+        self.autosave.swap(enabled, Ordering::Relaxed) != enabled
+    }
+
+    /// Whether the periodic save should run at `now`.
+    fn autosave_due(&self, now: Instant) -> bool {
+        self.autosave.load(Ordering::Relaxed)
+            && now.duration_since(self.last_save.load()) >= self.save_interval
+    }
+
+    /// Keeps other saves from running until the guard is dropped, so `/save-all` doesn't write
+    /// the same files as the periodic save.
+    pub async fn lock_saves(&self) -> OwnedMutexGuard<()> {
+        self.saving.clone().lock_owned().await
+    }
+
     /// Performs periodic maintenance tasks.
     ///
     /// This function should be called regularly to save player data and clean
@@ -74,50 +109,71 @@ impl ServerPlayerData {
         let now = Instant::now();
 
         // Only save players periodically based on save_interval
-        let last_save = self.last_save.load();
-        let should_save = now.duration_since(last_save) >= self.save_interval;
-
-        if should_save && self.storage.is_save_enabled() {
-            self.last_save.store(now);
-            // Save all online players periodically across all worlds
-            for world in server.worlds.read().await.iter() {
-                for player in world.players.read().await.values() {
-                    let mut nbt = NbtCompound::new();
-                    player.write_nbt(&mut nbt).await;
-
-                    // Save to disk periodically to prevent data loss on server crash
-                    if let Err(e) = self.storage.save_player_data(&player.gameprofile.id, nbt) {
-                        log::error!(
-                            "Failed to save player data for {}: {e}",
-                            player.gameprofile.id,
-                        );
-                    }
-                }
+        if !self.autosave_due(now) || !self.storage.is_save_enabled() {
+            return Ok(());
+        }
+        // A manual save is already writing everything
+        let Ok(_saving) = self.saving.try_lock() else {
+            return Ok(());
+        };
+        self.last_save.store(now);
+
+        // Save all online players periodically across all worlds
+        let data = Self::collect_player_data(server).await;
+        for (uuid, nbt, advancements) in data {
+            // Save to disk periodically to prevent data loss on server crash
+            if let Err(e) = self.storage.save_player_data(&uuid, nbt) {
+                log::error!("Failed to save player data for {uuid}: {e}");
+            }
+            if let Err(e) = self.storage.save_advancements(&uuid, &advancements) {
+                log::error!("Failed to save advancements for {uuid}: {e}");
             }
-
-            log::debug!("Periodic player data save completed");
         }
 
+        log::debug!("Periodic player data save completed");
         Ok(())
     }
 
-    /// Saves all players' data immediately.
+    /// Saves all players' data immediately, returning how many players were saved.
     ///
-    /// This function immediately saves all online players' data to disk.
-    /// Useful for server shutdown or backup operations.
-    pub async fn save_all_players(&self, server: &Server) -> Result<(), PlayerDataError> {
-        let mut total_players = 0;
+    /// This function immediately saves all online players' data to disk, even if the periodic
+    /// save is turned off. Useful for server shutdown or backup operations.
+    pub async fn save_all_players(&self, server: &Server) -> Result<usize, PlayerDataError> {
+        let data = Self::collect_player_data(server).await;
+        let total_players = self.save_players(data)?;
+
+        log::debug!("Saved data for {total_players} online players");
+        Ok(total_players)
+    }
 
-        // Save players from all worlds
+    async fn collect_player_data(server: &Server) -> Vec<(Uuid, NbtCompound, Value)> {
+        let mut data = Vec::new();
         for world in server.worlds.read().await.iter() {
             for player in world.players.read().await.values() {
-                self.extract_data_and_save_player(player).await?;
-                total_players += 1;
+                data.push(Self::player_data(player).await);
             }
         }
+        data
+    }
 
-        log::debug!("Saved data for {total_players} online players");
-        Ok(())
+    /// The player's data and advancements, which are saved to separate files.
+    async fn player_data(player: &Player) -> (Uuid, NbtCompound, Value) {
+        let mut nbt = NbtCompound::new();
+        player.write_nbt(&mut nbt).await;
+        let advancements = player.advancements.lock().await.to_json();
+        (player.gameprofile.id, nbt, advancements)
+    }
+
+    fn save_players(
+        &self,
+        data: Vec<(Uuid, NbtCompound, Value)>,
+    ) -> Result<usize, PlayerDataError> {
+        let total_players = data.len();
+        for (uuid, nbt, advancements) in data {
+            self.storage.save_player_data(&uuid, nbt)?;
+            self.storage.save_advancements(&uuid, &advancements)?;
+        }
+        Ok(total_players)
     }
 
     /// Loads player data and applies it to a player.
@@ -137,6 +193,11 @@ impl ServerPlayerData {
         player: &mut Player,
     ) -> Result<(), PlayerDataError> {
         let uuid = &player.gameprofile.id;
+        match self.storage.load_advancements(uuid) {
+            Ok(Some(advancements)) => player.advancements.get_mut().read_json(&advancements),
+            Ok(None) => {}
+            Err(e) => log::error!("Error loading advancements for {uuid}: {e}"),
+        }
         match self.storage.load_player_data(uuid) {
             Ok((should_load, mut data)) => {
                 if !should_load {
@@ -179,10 +240,8 @@ impl ServerPlayerData {
             return Ok(());
         }
 
-        let uuid = &player.gameprofile.id;
-        let mut nbt = NbtCompound::new();
-        player.write_nbt(&mut nbt).await;
-        self.storage.save_player_data(uuid, nbt)
+        self.save_players(vec![Self::player_data(player).await])?;
+        Ok(())
     }
 }
 
@@ -300,6 +359,58 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn save_off_only_stops_periodic_saves() {
+        let temp_dir = tempdir().unwrap();
+        let player_data = ServerPlayerData::new(temp_dir.path(), Duration::ZERO);
+        let now = Instant::now();
+        assert!(player_data.autosave_due(now));
+
+        assert!(player_data.set_autosave(false));
+        assert!(!player_data.set_autosave(false));
+        assert!(!player_data.autosave_due(now));
+
+        // Saving on demand, like `/save-all`, still writes the players
+        let uuid = Uuid::new_v4();
+        let mut nbt = NbtCompound::new();
+        nbt.put_int("level", 7);
+        let advancements = serde_json::json!({});
+        assert_eq!(
+            player_data
+                .save_players(vec![(uuid, nbt, advancements)])
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            player_data.storage.get_player_data_path(&uuid).exists(),
+            player_data.storage.is_save_enabled()
+        );
+
+        assert!(player_data.set_autosave(true));
+        assert!(player_data.autosave_due(now));
+    }
+
+    #[tokio::test]
+    async fn advancements_are_saved_next_to_the_player_data() {
+        let temp_dir = tempdir().unwrap();
+        let mut storage = PlayerDataStorage::new(temp_dir.path().join("playerdata"));
+        storage.set_save_enabled(true);
+
+        let uuid = Uuid::new_v4();
+        let advancements = serde_json::json!({ "DataVersion": 4325 });
+        storage.save_advancements(&uuid, &advancements).unwrap();
+
+        let path = temp_dir
+            .path()
+            .join("advancements")
+            .join(format!("{uuid}.json"));
+        assert_eq!(storage.get_advancements_path(&uuid), path);
+        assert_eq!(
+            storage.load_advancements(&uuid).unwrap(),
+            Some(advancements)
+        );
+    }
+
     #[tokio::test]
     async fn test_player_data_file_structure() {
         let temp_dir = tempdir().unwrap();
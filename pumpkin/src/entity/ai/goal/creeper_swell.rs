@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+
+use crate::entity::mob::{
+    MobEntity,
+    creeper::{SWELL_START_DISTANCE, SWELL_STOP_DISTANCE},
+};
+
+use super::{Goal, GoalControls};
+
+/// Makes a creeper stop and swell once a player comes close, see
+/// [`crate::entity::mob::creeper::Creeper::aim`].
+pub struct CreeperSwellGoal;
+
+impl CreeperSwellGoal {
+    /// The squared distance to the closest player the creeper may blow up.
+    async fn target_distance_squared(mob: &MobEntity, range: f64) -> Option<f64> {
+        let entity = &mob.living_entity.entity;
+        let pos = entity.pos.load();
+        let player = entity
+            .world
+            .read()
+            .await
+            .get_closest_player(pos, range)
+            .await?;
+        if player.abilities.lock().await.invulnerable {
+            return None;
+        }
+        Some(
+            player
+                .living_entity
+                .entity
+                .pos
+                .load()
+                .squared_distance_to_vec(pos),
+        )
+    }
+
+    async fn aim(mob: &MobEntity, target_distance_squared: Option<f64>) {
+        let Some(creeper) = &mob.creeper else {
+            return;
+        };
+        let meta_data = {
+            let mut creeper = creeper.lock().await;
+            creeper
+                .aim(target_distance_squared)
+                .then(|| creeper.swell_meta_data())
+        };
+        if let Some(meta_data) = meta_data {
+            mob.living_entity.entity.send_meta_data(&[meta_data]).await;
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for CreeperSwellGoal {
+    async fn can_start(&self, mob: &MobEntity) -> bool {
+        let Some(creeper) = &mob.creeper else {
+            return false;
+        };
+        if creeper.lock().await.is_swelling() {
+            return true;
+        }
+        Self::target_distance_squared(mob, SWELL_START_DISTANCE)
+            .await
+            .is_some()
+    }
+
+    async fn should_continue(&self, mob: &MobEntity) -> bool {
+        self.can_start(mob).await
+    }
+
+    async fn start(&self, mob: &MobEntity) {
+        mob.navigation.lock().await.stop();
+    }
+
+    async fn stop(&self, mob: &MobEntity) {
+        Self::aim(mob, None).await;
+    }
+
+    async fn tick(&self, mob: &MobEntity) {
+        let target_distance_squared = Self::target_distance_squared(mob, SWELL_STOP_DISTANCE).await;
+        Self::aim(mob, target_distance_squared).await;
+    }
+
+    fn controls(&self) -> GoalControls {
+        GoalControls::MOVE
+    }
+}
@@ -0,0 +1,118 @@
+use async_trait::async_trait;
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use tokio::sync::Mutex;
+
+use crate::entity::mob::MobEntity;
+
+use super::{Goal, GoalControls};
+
+/// Makes a mob run away from nearby entities of the types it is scared of.
+pub struct FleeEntityGoal {
+    scared_of: &'static [EntityType],
+    /// How close the mob lets them come, it also runs this far
+    distance: f64,
+    /// Multiplies the walk speed of the mob
+    speed: f64,
+    destination: Mutex<Option<Vector3<f64>>>,
+}
+
+impl FleeEntityGoal {
+    #[must_use]
+    pub fn new(scared_of: &'static [EntityType], distance: f64, speed: f64) -> Self {
+        Self {
+            scared_of,
+            distance,
+            speed,
+            destination: Mutex::new(None),
+        }
+    }
+}
+
+/// Where a mob at `pos` runs to get `distance` blocks further away from `threat`.
+pub fn flee_destination(pos: Vector3<f64>, threat: Vector3<f64>, distance: f64) -> Vector3<f64> {
+    let away = Vector3::new(pos.x - threat.x, 0.0, pos.z - threat.z);
+    let away = if away.length_squared() > 1.0E-4 {
+        away.normalize()
+    } else {
+        Vector3::new(1.0, 0.0, 0.0)
+    };
+    pos + away * distance
+}
+
+#[async_trait]
+impl Goal for FleeEntityGoal {
+    async fn can_start(&self, mob: &MobEntity) -> bool {
+        let entity = &mob.living_entity.entity;
+        let pos = entity.pos.load();
+        let world = entity.world.read().await.clone();
+        let threat = world
+            .entities
+            .read()
+            .await
+            .values()
+            .map(|other| other.get_entity())
+            .filter(|other| self.scared_of.contains(&other.entity_type))
+            .map(|other| other.pos.load())
+            .filter(|other| other.squared_distance_to_vec(pos) < self.distance * self.distance)
+            .min_by(|a, b| {
+                a.squared_distance_to_vec(pos)
+                    .total_cmp(&b.squared_distance_to_vec(pos))
+            });
+        let Some(threat) = threat else {
+            return false;
+        };
+        *self.destination.lock().await = Some(flee_destination(pos, threat, self.distance));
+        true
+    }
+
+    async fn should_continue(&self, mob: &MobEntity) -> bool {
+        !mob.navigation.lock().await.is_idle()
+    }
+
+    async fn start(&self, mob: &MobEntity) {
+        let Some(destination) = *self.destination.lock().await else {
+            return;
+        };
+        let speed = mob.walk_speed().await * self.speed;
+        // If there is no way there, the mob looks for another one next tick
+        mob.navigation
+            .lock()
+            .await
+            .start_to(
+                &mob.living_entity,
+                BlockPos::floored(destination.x, destination.y, destination.z),
+                speed,
+            )
+            .await;
+    }
+
+    async fn stop(&self, mob: &MobEntity) {
+        *self.destination.lock().await = None;
+        mob.navigation.lock().await.stop();
+    }
+
+    async fn tick(&self, _mob: &MobEntity) {}
+
+    fn controls(&self) -> GoalControls {
+        GoalControls::MOVE
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::flee_destination;
+
+    #[test]
+    fn runs_away_from_the_threat() {
+        let creeper = Vector3::new(0.5, 64.0, 0.5);
+        // A cat to the west sends the creeper east
+        let destination = flee_destination(creeper, Vector3::new(-2.5, 64.0, 0.5), 6.0);
+        assert_eq!(destination, Vector3::new(6.5, 64.0, 0.5));
+        // The height of the threat doesn't matter
+        let destination = flee_destination(creeper, Vector3::new(0.5, 66.0, 3.5), 6.0);
+        assert_eq!(destination, Vector3::new(0.5, 64.0, -5.5));
+    }
+}
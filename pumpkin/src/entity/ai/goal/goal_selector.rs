@@ -0,0 +1,149 @@
+use std::sync::Arc;
+
+use crate::entity::mob::MobEntity;
+
+use super::Goal;
+
+struct PrioritizedGoal {
+    /// Lower priorities are more important
+    priority: u8,
+    goal: Arc<dyn Goal>,
+    running: bool,
+}
+
+/// Runs the goals of a mob, more important goals take over the controls of less important ones.
+#[derive(Default)]
+pub struct GoalSelector {
+    /// Sorted by priority
+    goals: Vec<PrioritizedGoal>,
+}
+
+impl GoalSelector {
+    /// Adds a goal, goals with a lower priority are more important.
+    pub fn add_goal(&mut self, priority: u8, goal: Arc<dyn Goal>) {
+        let index = self
+            .goals
+            .partition_point(|other| other.priority <= priority);
+        self.goals.insert(
+            index,
+            PrioritizedGoal {
+                priority,
+                goal,
+                running: false,
+            },
+        );
+    }
+
+    /// The running goals the goal at `index` would take controls from, `None` if one of them is
+    /// at least as important so the goal can't start.
+    fn conflicts(&self, index: usize) -> Option<Vec<usize>> {
+        let candidate = &self.goals[index];
+        let controls = candidate.goal.controls();
+        let mut conflicts = Vec::new();
+        for (other_index, other) in self.goals.iter().enumerate() {
+            if other_index == index || !other.running || !other.goal.controls().intersects(controls)
+            {
+                continue;
+            }
+            if other.priority <= candidate.priority {
+                return None;
+            }
+            conflicts.push(other_index);
+        }
+        Some(conflicts)
+    }
+
+    pub async fn tick(&mut self, mob: &MobEntity) {
+        for entry in &mut self.goals {
+            if entry.running && !entry.goal.should_continue(mob).await {
+                entry.running = false;
+                entry.goal.stop(mob).await;
+            }
+        }
+
+        for index in 0..self.goals.len() {
+            if self.goals[index].running {
+                continue;
+            }
+            let Some(conflicts) = self.conflicts(index) else {
+                continue;
+            };
+            let goal = self.goals[index].goal.clone();
+            if !goal.can_start(mob).await {
+                continue;
+            }
+            for conflict in conflicts {
+                self.goals[conflict].running = false;
+                self.goals[conflict].goal.stop(mob).await;
+            }
+            self.goals[index].running = true;
+            goal.start(mob).await;
+        }
+
+        for entry in &self.goals {
+            if entry.running {
+                entry.goal.tick(mob).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+
+    use crate::entity::{
+        ai::goal::{Goal, GoalControls},
+        mob::MobEntity,
+    };
+
+    use super::GoalSelector;
+
+    struct Controlling(GoalControls);
+
+    #[async_trait]
+    impl Goal for Controlling {
+        async fn can_start(&self, _mob: &MobEntity) -> bool {
+            true
+        }
+
+        async fn should_continue(&self, _mob: &MobEntity) -> bool {
+            true
+        }
+
+        async fn tick(&self, _mob: &MobEntity) {}
+
+        fn controls(&self) -> GoalControls {
+            self.0
+        }
+    }
+
+    #[test]
+    fn important_goals_take_over_controls() {
+        let mut selector = GoalSelector::default();
+        selector.add_goal(8, Arc::new(Controlling(GoalControls::LOOK)));
+        selector.add_goal(6, Arc::new(Controlling(GoalControls::MOVE)));
+        selector.add_goal(
+            8,
+            Arc::new(Controlling(GoalControls::MOVE | GoalControls::LOOK)),
+        );
+        selector.add_goal(2, Arc::new(Controlling(GoalControls::MOVE)));
+        let priorities: Vec<_> = selector.goals.iter().map(|goal| goal.priority).collect();
+        assert_eq!(priorities, [2, 6, 8, 8]);
+
+        // Strolling (6) and looking at a player (8) use different controls
+        selector.goals[1].running = true;
+        assert_eq!(selector.conflicts(2), Some(vec![]));
+        // Looking around (8) can't interrupt the more important stroll
+        assert_eq!(selector.conflicts(3), None);
+        // Attacking (2) stops the stroll
+        assert_eq!(selector.conflicts(0), Some(vec![1]));
+
+        // Goals of the same priority don't interrupt each other
+        selector.goals[2].running = true;
+        selector.goals[1].running = false;
+        assert_eq!(selector.conflicts(3), None);
+    }
+}
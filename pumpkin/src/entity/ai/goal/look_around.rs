@@ -0,0 +1,70 @@
+use std::{
+    f64::consts::TAU,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use async_trait::async_trait;
+use crossbeam::atomic::AtomicCell;
+use rand::Rng;
+
+use crate::entity::mob::MobEntity;
+
+use super::{Goal, GoalControls};
+
+/// The chance each tick that an idle mob starts looking around
+const LOOK_CHANCE: f32 = 0.02;
+/// Mobs look in a direction for at least this many ticks, and up to twice as long
+const LOOK_TICKS: u32 = 20;
+
+/// Lets an idle mob look into a random direction every now and then.
+pub struct LookAroundGoal {
+    /// The horizontal direction the mob looks in
+    direction: AtomicCell<(f64, f64)>,
+    look_ticks: AtomicU32,
+}
+
+impl LookAroundGoal {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            direction: AtomicCell::new((0.0, 0.0)),
+            look_ticks: AtomicU32::new(0),
+        }
+    }
+}
+
+impl Default for LookAroundGoal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Goal for LookAroundGoal {
+    async fn can_start(&self, _mob: &MobEntity) -> bool {
+        rand::thread_rng().r#gen::<f32>() < LOOK_CHANCE
+    }
+
+    async fn should_continue(&self, _mob: &MobEntity) -> bool {
+        self.look_ticks.load(Ordering::Relaxed) > 0
+    }
+
+    async fn start(&self, _mob: &MobEntity) {
+        let mut rng = rand::thread_rng();
+        let angle = rng.gen_range(0.0..TAU);
+        self.direction.store((angle.cos(), angle.sin()));
+        self.look_ticks
+            .store(LOOK_TICKS + rng.gen_range(0..LOOK_TICKS), Ordering::Relaxed);
+    }
+
+    async fn tick(&self, mob: &MobEntity) {
+        let entity = &mob.living_entity.entity;
+        let (x, z) = self.direction.load();
+        entity.look_at(entity.pos.load().add_raw(x, 0.0, z)).await;
+        self.look_ticks.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    fn controls(&self) -> GoalControls {
+        GoalControls::MOVE | GoalControls::LOOK
+    }
+}
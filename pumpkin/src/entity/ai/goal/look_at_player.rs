@@ -0,0 +1,118 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+
+use async_trait::async_trait;
+use rand::Rng;
+use tokio::sync::Mutex;
+
+use crate::entity::{mob::MobEntity, player::Player};
+
+use super::{Goal, GoalControls};
+
+/// The chance each tick that a mob starts looking at a nearby player
+const LOOK_CHANCE: f32 = 0.02;
+/// Mobs look at a player for at least this many ticks, and up to twice as long
+const LOOK_TICKS: u32 = 40;
+
+pub struct LookAtPlayerGoal {
+    // TODO: make this an entity
+    target: Mutex<Option<Arc<Player>>>,
+    range: f64,
+    look_ticks: AtomicU32,
+}
+
+impl LookAtPlayerGoal {
+    #[must_use]
+    pub fn new(range: f64) -> Self {
+        Self {
+            target: Mutex::new(None),
+            range,
+            look_ticks: AtomicU32::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl Goal for LookAtPlayerGoal {
+    async fn can_start(&self, mob: &MobEntity) -> bool {
+        if rand::thread_rng().r#gen::<f32>() >= LOOK_CHANCE {
+            return false;
+        }
+        // TODO: make this an entity
+        let mut target = self.target.lock().await;
+
+        *target = mob
+            .living_entity
+            .entity
+            .world
+            .read()
+            .await
+            .get_closest_player(mob.living_entity.entity.pos.load(), self.range)
+            .await;
+        target.is_some()
+    }
+
+    async fn should_continue(&self, mob: &MobEntity) -> bool {
+        if self.look_ticks.load(Ordering::Relaxed) == 0 {
+            return false;
+        }
+        if let Some(target) = self.target.lock().await.as_ref() {
+            let mob_pos = mob.living_entity.entity.pos.load();
+            let target_pos = target.living_entity.entity.pos.load();
+            return mob_pos.squared_distance_to_vec(target_pos) <= (self.range * self.range);
+        }
+        false
+    }
+
+    async fn start(&self, _mob: &MobEntity) {
+        let look_ticks = LOOK_TICKS + rand::thread_rng().gen_range(0..LOOK_TICKS);
+        self.look_ticks.store(look_ticks, Ordering::Relaxed);
+    }
+
+    async fn stop(&self, _mob: &MobEntity) {
+        *self.target.lock().await = None;
+    }
+
+    async fn tick(&self, mob: &MobEntity) {
+        if let Some(target) = self.target.lock().await.as_ref() {
+            let entity = &mob.living_entity.entity;
+            let target = &target.living_entity.entity;
+            // Eye to eye, while looking is measured from the feet
+            let eye_offset = target.standing_eye_height - entity.standing_eye_height;
+            let target_pos = target.pos.load().add_raw(0.0, f64::from(eye_offset), 0.0);
+            entity.look_at(target_pos).await;
+        }
+        self.look_ticks.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    fn controls(&self) -> GoalControls {
+        GoalControls::LOOK
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use crate::entity::rotation_towards;
+
+    #[test]
+    fn looks_towards_nearby_player() {
+        let mob = Vector3::new(0.5, 64.0, 0.5);
+
+        // Yaw 0 faces south (+z), -90 faces east (+x)
+        let (yaw, pitch) = rotation_towards(mob, Vector3::new(0.5, 64.0, 6.5));
+        assert!(yaw.abs() < 1e-3 && pitch.abs() < 1e-3);
+        let (yaw, _) = rotation_towards(mob, Vector3::new(6.5, 64.0, 0.5));
+        assert!((yaw + 90.0).abs() < 1e-3);
+        let (yaw, _) = rotation_towards(mob, Vector3::new(-5.5, 64.0, 0.5));
+        assert!((yaw - 90.0).abs() < 1e-3);
+
+        // A player above is looked up to with a negative pitch
+        let (yaw, pitch) = rotation_towards(mob, Vector3::new(0.5, 67.0, 3.5));
+        assert!(yaw.abs() < 1e-3);
+        assert!((pitch + 45.0).abs() < 1e-3);
+    }
+}
@@ -1,16 +1,42 @@
 use async_trait::async_trait;
+use bitflags::bitflags;
 
 use crate::entity::mob::MobEntity;
 
-pub mod look_at_entity;
+pub mod creeper_swell;
+pub mod flee_entity;
+pub mod goal_selector;
+pub mod look_around;
+pub mod look_at_player;
+pub mod random_stroll;
 pub mod target_goal;
 
+bitflags! {
+    /// What a [`Goal`] takes control of, only one running goal can control each at a time.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct GoalControls: u8 {
+        const MOVE   = 0b0001;
+        const LOOK   = 0b0010;
+        const JUMP   = 0b0100;
+        const TARGET = 0b1000;
+    }
+}
+
 #[async_trait]
 pub trait Goal: Send + Sync {
     /// How should the `Goal` initially start?
     async fn can_start(&self, mob: &MobEntity) -> bool;
     /// When it's started, how should it continue to run?
     async fn should_continue(&self, mob: &MobEntity) -> bool;
+    /// Called once when the `Goal` starts running.
+    async fn start(&self, _mob: &MobEntity) {}
+    /// Called once when the `Goal` stops running, either by itself or because a more important
+    /// `Goal` took over.
+    async fn stop(&self, _mob: &MobEntity) {}
     /// If the `Goal` is running, this gets called every tick.
     async fn tick(&self, mob: &MobEntity);
+    /// What the `Goal` controls while it is running.
+    fn controls(&self) -> GoalControls {
+        GoalControls::empty()
+    }
 }
@@ -0,0 +1,116 @@
+use async_trait::async_trait;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use rand::Rng;
+use tokio::sync::Mutex;
+
+use crate::entity::mob::MobEntity;
+
+use super::{Goal, GoalControls};
+
+/// How far away from where they stand mobs stroll to at most, horizontally
+const STROLL_RANGE: i32 = 10;
+/// On average, idle mobs start strolling once every this many ticks
+const STROLL_INTERVAL: u32 = 120;
+
+/// Lets an idle mob wander to a random spot nearby every now and then.
+pub struct RandomStrollGoal {
+    /// Multiplies the walk speed of the mob
+    speed: f64,
+    destination: Mutex<Option<BlockPos>>,
+}
+
+impl RandomStrollGoal {
+    #[must_use]
+    pub fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            destination: Mutex::new(None),
+        }
+    }
+}
+
+/// A random spot at the height of `pos` a strolling mob could walk to.
+pub fn stroll_destination(pos: BlockPos, rng: &mut impl Rng) -> BlockPos {
+    pos.offset(Vector3::new(
+        rng.gen_range(-STROLL_RANGE..=STROLL_RANGE),
+        0,
+        rng.gen_range(-STROLL_RANGE..=STROLL_RANGE),
+    ))
+}
+
+#[async_trait]
+impl Goal for RandomStrollGoal {
+    async fn can_start(&self, mob: &MobEntity) -> bool {
+        let entity = &mob.living_entity.entity;
+        let destination = {
+            let mut rng = rand::thread_rng();
+            if rng.gen_range(0..STROLL_INTERVAL) != 0 {
+                return false;
+            }
+            stroll_destination(entity.block_pos.load(), &mut rng)
+        };
+        // Only stroll to where the mob can stand
+        let world = entity.world.read().await.clone();
+        let free = world
+            .get_block_state(&destination)
+            .await
+            .is_ok_and(|state| state.is_air());
+        let supported = world
+            .get_block_state(&destination.down())
+            .await
+            .is_ok_and(|state| !state.is_air());
+        if !free || !supported {
+            return false;
+        }
+        *self.destination.lock().await = Some(destination);
+        true
+    }
+
+    async fn should_continue(&self, mob: &MobEntity) -> bool {
+        !mob.navigation.lock().await.is_idle()
+    }
+
+    async fn start(&self, mob: &MobEntity) {
+        let Some(destination) = *self.destination.lock().await else {
+            return;
+        };
+        let speed = mob.walk_speed().await * self.speed;
+        // If there is no way there, we stop strolling right away
+        mob.navigation
+            .lock()
+            .await
+            .start_to(&mob.living_entity, destination, speed)
+            .await;
+    }
+
+    async fn stop(&self, mob: &MobEntity) {
+        *self.destination.lock().await = None;
+        mob.navigation.lock().await.stop();
+    }
+
+    async fn tick(&self, _mob: &MobEntity) {}
+
+    fn controls(&self) -> GoalControls {
+        GoalControls::MOVE
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::position::BlockPos;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::{STROLL_RANGE, stroll_destination};
+
+    #[test]
+    fn stroll_destination_is_nearby() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let start = BlockPos::new(3, 64, -8);
+        for _ in 0..100 {
+            let destination = stroll_destination(start, &mut rng);
+            assert!((destination.0.x - start.0.x).abs() <= STROLL_RANGE);
+            assert!((destination.0.z - start.0.z).abs() <= STROLL_RANGE);
+            assert_eq!(destination.0.y, start.0.y);
+        }
+    }
+}
@@ -3,9 +3,9 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use tokio::sync::Mutex;
 
-use crate::entity::{ai::path::NavigatorGoal, mob::MobEntity, player::Player};
+use crate::entity::{mob::MobEntity, player::Player};
 
-use super::Goal;
+use super::{Goal, GoalControls};
 
 pub struct TargetGoal {
     // TODO: make this an entity
@@ -58,16 +58,19 @@ impl Goal for TargetGoal {
         }
         false
     }
+    async fn stop(&self, mob: &MobEntity) {
+        *self.target.lock().await = None;
+        mob.navigation.lock().await.stop();
+    }
+
     async fn tick(&self, mob: &MobEntity) {
         if let Some(target) = self.target.lock().await.as_ref() {
-            let mut navigator = mob.navigator.lock().await;
-            let target_player = target.living_entity.entity.pos.load();
-
-            navigator.set_progress(NavigatorGoal {
-                current_progress: mob.living_entity.entity.pos.load(),
-                destination: target_player,
-                speed: 0.1,
-            });
+            mob.walk_to(target.living_entity.entity.block_pos.load())
+                .await;
         }
     }
+
+    fn controls(&self) -> GoalControls {
+        GoalControls::MOVE | GoalControls::TARGET
+    }
 }
@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+use crate::entity::living::LivingEntity;
+
+use super::{
+    move_entity,
+    pathfinder::{MAX_EXPANDED_NODES, find_path},
+};
+
+/// Walks an entity along a path found by the pathfinder, one step each tick.
+#[derive(Default)]
+pub struct EntityNavigation {
+    /// The blocks left to walk through, the next one first
+    waypoints: VecDeque<BlockPos>,
+    /// How many blocks the entity walks per tick
+    speed: f64,
+}
+
+impl EntityNavigation {
+    /// Looks for a way from where the entity stands to `pos` and starts walking it.
+    ///
+    /// Returns `false` and stands still if there is no way.
+    pub async fn start_to(&mut self, entity: &LivingEntity, pos: BlockPos, speed: f64) -> bool {
+        let world = entity.entity.world.read().await.clone();
+        let start = entity.entity.block_pos.load();
+        let Some(path) = find_path(world.as_ref(), start, pos, MAX_EXPANDED_NODES).await else {
+            self.stop();
+            return false;
+        };
+        self.follow(path, speed);
+        true
+    }
+
+    /// Starts walking a path which begins at the block the entity stands on.
+    fn follow(&mut self, path: Vec<BlockPos>, speed: f64) {
+        // We are standing on the first one already
+        self.waypoints = path.into_iter().skip(1).collect();
+        self.speed = speed;
+    }
+
+    /// Where the path being walked ends.
+    #[must_use]
+    pub fn destination(&self) -> Option<BlockPos> {
+        self.waypoints.back().copied()
+    }
+
+    pub fn stop(&mut self) {
+        self.waypoints.clear();
+    }
+
+    /// Whether the entity has nowhere to go.
+    #[must_use]
+    pub fn is_idle(&self) -> bool {
+        self.waypoints.is_empty()
+    }
+
+    pub async fn tick(&mut self, entity: &LivingEntity) {
+        if let Some(new_pos) = self.step(entity.entity.pos.load()) {
+            move_entity(entity, new_pos).await;
+        }
+    }
+
+    /// Where an entity at `pos` is after walking towards the next waypoint for a tick.
+    fn step(&mut self, pos: Vector3<f64>) -> Option<Vector3<f64>> {
+        let next = self.waypoints.front()?;
+        // The middle of the block, so mobs don't scrape along the corners of the blocks around
+        let target = next.to_f64();
+        let delta = target.sub(&pos);
+        if delta.length() <= self.speed {
+            self.waypoints.pop_front();
+            Some(target)
+        } else {
+            Some(pos + delta.normalize() * self.speed)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::EntityNavigation;
+
+    #[test]
+    fn walks_through_the_middle_of_each_waypoint() {
+        let mut navigation = EntityNavigation::default();
+        let path = (0..=6).map(|x| BlockPos::new(x, 64, -3)).collect();
+        navigation.follow(path, 0.1);
+        assert_eq!(navigation.destination(), Some(BlockPos::new(6, 64, -3)));
+
+        let mut pos = Vector3::new(0.5, 64.0, -2.5);
+        let mut positions = vec![pos];
+        while let Some(next) = navigation.step(pos) {
+            pos = next;
+            positions.push(pos);
+            assert!(positions.len() < 1000, "the mob never arrives");
+        }
+        // The mob moved every tick, along the middle of the blocks, and ended up where it wanted
+        assert!((61..70).contains(&positions.len()));
+        assert!(positions.windows(2).all(|step| step[0] != step[1]));
+        assert!(positions.iter().all(|pos| pos.z == -2.5 && pos.y == 64.0));
+        assert!(pos.squared_distance_to_vec(Vector3::new(6.5, 64.0, -2.5)) < 1.0E-9);
+        assert!(navigation.is_idle());
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+};
+
+use async_trait::async_trait;
+use pumpkin_data::{Block, BlockState, tag::Tagable};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+use crate::{block, world::World};
+
+/// How many nodes the pathfinder looks at before giving up, so unreachable targets stay cheap
+pub const MAX_EXPANDED_NODES: usize = 1000;
+/// How far mobs are willing to fall down on their way
+pub const MAX_DROP: i32 = 3;
+
+/// Costs are doubled so falling can be half as expensive as walking
+const WALK_COST: u32 = 2;
+const STEP_UP_COST: u32 = 4;
+const DROP_COST_PER_BLOCK: u32 = 1;
+
+const HORIZONTAL_NEIGHBOURS: [Vector3<i32>; 4] = [
+    Vector3::new(1, 0, 0),
+    Vector3::new(-1, 0, 0),
+    Vector3::new(0, 0, 1),
+    Vector3::new(0, 0, -1),
+];
+
+/// What a block means to a walking mob.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BlockKind {
+    /// Nothing to collide with, e.g. air or grass
+    Open,
+    /// Something to stand on or bump into
+    Solid,
+    Water,
+    Lava,
+    /// A door mobs are able to walk through
+    Door,
+}
+
+impl BlockKind {
+    #[must_use]
+    pub fn of(block: &Block, state: &BlockState) -> Self {
+        if *block == Block::LAVA {
+            Self::Lava
+        } else if *block == Block::WATER || state.is_liquid() {
+            Self::Water
+        } else if block.is_tagged_with("minecraft:doors").unwrap_or(false) {
+            let open = block::get_block_property(block, state.id, "open");
+            if *block != Block::IRON_DOOR || open.as_deref() == Some("true") {
+                Self::Door
+            } else {
+                Self::Solid
+            }
+        } else if state.collision_shapes.is_empty() {
+            Self::Open
+        } else {
+            Self::Solid
+        }
+    }
+
+    /// Whether a mob fits into a block of this kind.
+    #[must_use]
+    pub fn is_passable(self) -> bool {
+        matches!(self, Self::Open | Self::Door)
+    }
+}
+
+/// Where the pathfinder gets its blocks from.
+#[async_trait]
+pub trait PathView: Sync {
+    async fn block_kind(&self, pos: &BlockPos) -> BlockKind;
+}
+
+#[async_trait]
+impl PathView for World {
+    async fn block_kind(&self, pos: &BlockPos) -> BlockKind {
+        // Mobs don't walk into chunks which are not loaded
+        self.get_block_and_block_state(pos)
+            .await
+            .map_or(BlockKind::Solid, |(block, state)| {
+                BlockKind::of(&block, &state)
+            })
+    }
+}
+
+/// Remembers the blocks we already looked at during a single search.
+struct CachedView<'a, V: PathView + ?Sized> {
+    view: &'a V,
+    cache: HashMap<BlockPos, BlockKind>,
+}
+
+impl<V: PathView + ?Sized> CachedView<'_, V> {
+    async fn kind(&mut self, pos: BlockPos) -> BlockKind {
+        if let Some(kind) = self.cache.get(&pos) {
+            return *kind;
+        }
+        let kind = self.view.block_kind(&pos).await;
+        self.cache.insert(pos, kind);
+        kind
+    }
+
+    /// Whether a mob can stand with its feet at `pos`.
+    async fn can_stand_at(&mut self, pos: BlockPos) -> bool {
+        self.kind(pos).await.is_passable()
+            && self.kind(pos.up()).await.is_passable()
+            && self.kind(pos.down()).await == BlockKind::Solid
+    }
+
+    /// Where a mob ends up when leaving `from` towards `offset`, and what that costs.
+    async fn neighbour(&mut self, from: BlockPos, offset: Vector3<i32>) -> Option<(BlockPos, u32)> {
+        let next = from.offset(offset);
+        if self.can_stand_at(next).await {
+            return Some((next, WALK_COST));
+        }
+        // Step up onto the block in front of us, if we can fit above it
+        let above = next.up();
+        if self.kind(from.up().up()).await.is_passable() && self.can_stand_at(above).await {
+            return Some((above, STEP_UP_COST));
+        }
+        // Walk off the edge and fall a few blocks
+        if !self.kind(next).await.is_passable() || !self.kind(above).await.is_passable() {
+            return None;
+        }
+        let mut below = next;
+        for drop in 1..=MAX_DROP {
+            below = below.down();
+            match self.kind(below).await {
+                kind if kind.is_passable() => {
+                    if self.can_stand_at(below).await {
+                        return Some((below, WALK_COST + drop as u32 * DROP_COST_PER_BLOCK));
+                    }
+                }
+                // Falling into water or lava, or onto something else we can't stand on
+                _ => return None,
+            }
+        }
+        None
+    }
+}
+
+/// Never overestimates the cost, so we always find the shortest path.
+fn estimate(from: BlockPos, to: BlockPos) -> u32 {
+    let delta = from.0.sub(&to.0);
+    (delta.x.unsigned_abs() + delta.z.unsigned_abs()) * WALK_COST + delta.y.unsigned_abs()
+}
+
+/// Finds the cheapest walkable path from `start` to `goal` using A*, looking at no more than
+/// `max_nodes` positions. The path includes both ends.
+///
+/// Returns `None` if there is no such path, or if finding it would take too long.
+pub async fn find_path<V: PathView + ?Sized>(
+    view: &V,
+    start: BlockPos,
+    goal: BlockPos,
+    max_nodes: usize,
+) -> Option<Vec<BlockPos>> {
+    let mut view = CachedView {
+        view,
+        cache: HashMap::new(),
+    };
+    if !view.can_stand_at(goal).await {
+        return None;
+    }
+
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<BlockPos, BlockPos> = HashMap::new();
+    let mut costs = HashMap::from([(start, 0)]);
+    open.push(Reverse((
+        estimate(start, goal),
+        0,
+        start.0.x,
+        start.0.y,
+        start.0.z,
+    )));
+
+    let mut expanded = 0;
+    while let Some(Reverse((_, cost, x, y, z))) = open.pop() {
+        let current = BlockPos::new(x, y, z);
+        if current == goal {
+            let mut path = vec![current];
+            let mut current = current;
+            while let Some(previous) = came_from.get(&current) {
+                path.push(*previous);
+                current = *previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        // We found a cheaper way here already
+        if costs.get(&current).is_some_and(|best| *best < cost) {
+            continue;
+        }
+        expanded += 1;
+        if expanded > max_nodes {
+            return None;
+        }
+
+        for offset in HORIZONTAL_NEIGHBOURS {
+            let Some((next, step_cost)) = view.neighbour(current, offset).await else {
+                continue;
+            };
+            let next_cost = cost + step_cost;
+            if costs.get(&next).is_some_and(|best| *best <= next_cost) {
+                continue;
+            }
+            costs.insert(next, next_cost);
+            came_from.insert(next, current);
+            open.push(Reverse((
+                next_cost + estimate(next, goal),
+                next_cost,
+                next.0.x,
+                next.0.y,
+                next.0.z,
+            )));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use async_trait::async_trait;
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::{BlockKind, MAX_EXPANDED_NODES, PathView, find_path};
+
+    /// A flat floor at y = 0 with some blocks on top of it.
+    struct Grid(HashMap<BlockPos, BlockKind>);
+
+    impl Grid {
+        fn with_walls(walls: impl IntoIterator<Item = (i32, i32)>) -> Self {
+            let mut blocks = HashMap::new();
+            for (x, z) in walls {
+                blocks.insert(BlockPos::new(x, 1, z), BlockKind::Solid);
+                blocks.insert(BlockPos::new(x, 2, z), BlockKind::Solid);
+            }
+            Self(blocks)
+        }
+    }
+
+    #[async_trait]
+    impl PathView for Grid {
+        async fn block_kind(&self, pos: &BlockPos) -> BlockKind {
+            if let Some(kind) = self.0.get(pos) {
+                *kind
+            } else if pos.0.y <= 0 {
+                BlockKind::Solid
+            } else {
+                BlockKind::Open
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn finds_way_around_walls() {
+        let start = BlockPos::new(0, 1, 0);
+        let goal = BlockPos::new(4, 1, 0);
+
+        // A wall between us and the goal with gaps at either end
+        let grid = Grid::with_walls((-3..=3).map(|z| (2, z)));
+        let path = find_path(&grid, start, goal, MAX_EXPANDED_NODES)
+            .await
+            .expect("there is a way around");
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&goal));
+        assert!(path.iter().all(|pos| pos.0.x != 2 || pos.0.z.abs() > 3));
+        // Every step goes to a neighbouring block
+        assert!(path.windows(2).all(|step| {
+            let delta = step[1].0.sub(&step[0].0);
+            delta.x.abs() + delta.z.abs() == 1
+        }));
+
+        // Now the goal is boxed in
+        let boxed = [
+            (3, -1),
+            (3, 0),
+            (3, 1),
+            (4, -1),
+            (4, 1),
+            (5, -1),
+            (5, 0),
+            (5, 1),
+        ];
+        let grid = Grid::with_walls(boxed);
+        assert_eq!(
+            find_path(&grid, start, goal, MAX_EXPANDED_NODES).await,
+            None
+        );
+    }
+}
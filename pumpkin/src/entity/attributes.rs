@@ -0,0 +1,460 @@
+use std::collections::HashMap;
+
+use pumpkin_data::entity::{Attribute, EntityType};
+use pumpkin_data::item::Operation;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::client::play::{AttributeModifierProperty, AttributeProperty};
+
+/// A modifier changing the value of an attribute, e.g. from an item or a command.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeModifier {
+    /// The namespaced id, a modifier with the same id can only be added once per attribute
+    pub id: String,
+    pub amount: f64,
+    pub operation: Operation,
+}
+
+impl AttributeModifier {
+    pub fn new(id: &str, amount: f64, operation: Operation) -> Self {
+        let id = if id.contains(':') {
+            id.to_string()
+        } else {
+            format!("minecraft:{id}")
+        };
+        Self {
+            id,
+            amount,
+            operation,
+        }
+    }
+}
+
+pub const fn operation_id(operation: Operation) -> u8 {
+    match operation {
+        Operation::AddValue => 0,
+        Operation::AddMultipliedBase => 1,
+        Operation::AddMultipliedTotal => 2,
+    }
+}
+
+pub const fn operation_name(operation: Operation) -> &'static str {
+    match operation {
+        Operation::AddValue => "add_value",
+        Operation::AddMultipliedBase => "add_multiplied_base",
+        Operation::AddMultipliedTotal => "add_multiplied_total",
+    }
+}
+
+pub fn operation_from_name(name: &str) -> Option<Operation> {
+    match name {
+        "add_value" => Some(Operation::AddValue),
+        "add_multiplied_base" => Some(Operation::AddMultipliedBase),
+        "add_multiplied_total" => Some(Operation::AddMultipliedTotal),
+        _ => None,
+    }
+}
+
+/// The range vanilla clamps the value of an attribute to.
+fn value_range(attribute: Attribute) -> (f64, f64) {
+    match attribute {
+        Attribute::MaxHealth => (1.0, 1024.0),
+        Attribute::Armor => (0.0, 30.0),
+        Attribute::ArmorToughness => (0.0, 20.0),
+        Attribute::AttackDamage => (0.0, 2048.0),
+        Attribute::KnockbackResistance | Attribute::ExplosionKnockbackResistance => (0.0, 1.0),
+        Attribute::FallDamageMultiplier => (0.0, 100.0),
+        Attribute::SafeFallDistance | Attribute::Luck => (-1024.0, 1024.0),
+        Attribute::Gravity => (-1.0, 1.0),
+        Attribute::Scale => (0.0625, 16.0),
+        _ => (0.0, 1024.0),
+    }
+}
+
+/// The base value of an attribute for a freshly spawned entity of the given type.
+pub fn default_base_value(entity_type: &EntityType, attribute: Attribute) -> f64 {
+    let is_player = entity_type.id == EntityType::PLAYER.id;
+    let is_zombie = entity_type.id == EntityType::ZOMBIE.id;
+    match attribute {
+        Attribute::MaxHealth => entity_type
+            .max_health
+            .map_or(attribute.default_value(), f64::from),
+        Attribute::AttackDamage if is_player => 1.0,
+        Attribute::MovementSpeed if is_player => 0.1,
+        Attribute::FollowRange if is_zombie => 35.0,
+        Attribute::FollowRange if !is_player => 16.0,
+        Attribute::AttackDamage if is_zombie => 3.0,
+        Attribute::Armor if is_zombie => 2.0,
+        Attribute::MovementSpeed => match *entity_type {
+            EntityType::ZOMBIE
+            | EntityType::HUSK
+            | EntityType::DROWNED
+            | EntityType::ZOMBIE_VILLAGER
+            | EntityType::ZOMBIFIED_PIGLIN
+            | EntityType::BLAZE
+            | EntityType::SHEEP => 0.23,
+            EntityType::SKELETON
+            | EntityType::STRAY
+            | EntityType::BOGGED
+            | EntityType::WITHER_SKELETON
+            | EntityType::CREEPER
+            | EntityType::WITCH
+            | EntityType::ENDERMITE
+            | EntityType::SILVERFISH
+            | EntityType::ZOGLIN
+            | EntityType::IRON_GOLEM
+            | EntityType::POLAR_BEAR
+            | EntityType::TURTLE
+            | EntityType::PIG
+            | EntityType::CHICKEN => 0.25,
+            EntityType::SPIDER
+            | EntityType::CAVE_SPIDER
+            | EntityType::ENDERMAN
+            | EntityType::RAVAGER
+            | EntityType::ELDER_GUARDIAN
+            | EntityType::WARDEN
+            | EntityType::WOLF
+            | EntityType::CAT
+            | EntityType::OCELOT
+            | EntityType::FOX
+            | EntityType::BEE
+            | EntityType::RABBIT
+            | EntityType::HOGLIN => 0.3,
+            EntityType::VINDICATOR
+            | EntityType::PILLAGER
+            | EntityType::PIGLIN
+            | EntityType::PIGLIN_BRUTE => 0.35,
+            EntityType::EVOKER | EntityType::GUARDIAN | EntityType::VILLAGER => 0.5,
+            EntityType::COW
+            | EntityType::MOOSHROOM
+            | EntityType::GOAT
+            | EntityType::SNOW_GOLEM
+            | EntityType::MAGMA_CUBE
+            | EntityType::PARROT => 0.2,
+            EntityType::LLAMA
+            | EntityType::TRADER_LLAMA
+            | EntityType::DONKEY
+            | EntityType::MULE
+            | EntityType::STRIDER => 0.175,
+            EntityType::PANDA => 0.15,
+            EntityType::ARMADILLO => 0.14,
+            EntityType::SNIFFER | EntityType::ALLAY => 0.1,
+            EntityType::CAMEL => 0.09,
+            EntityType::FROG | EntityType::AXOLOTL | EntityType::TADPOLE => 1.0,
+            EntityType::DOLPHIN => 1.2,
+            _ => attribute.default_value(),
+        },
+        _ => attribute.default_value(),
+    }
+}
+
+/// The base value and modifiers of one attribute of an entity.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeInstance {
+    base: f64,
+    modifiers: Vec<AttributeModifier>,
+}
+
+impl AttributeInstance {
+    pub fn new(base: f64) -> Self {
+        Self {
+            base,
+            modifiers: Vec::new(),
+        }
+    }
+
+    pub fn base(&self) -> f64 {
+        self.base
+    }
+
+    pub fn modifiers(&self) -> &[AttributeModifier] {
+        &self.modifiers
+    }
+
+    /// Applies the modifiers the same way vanilla does: first the added values, then the
+    /// multiples of that sum, then every total multiplier one after the other.
+    pub fn value(&self, attribute: Attribute) -> f64 {
+        let with_operation = |operation| {
+            self.modifiers
+                .iter()
+                .filter(move |modifier| modifier.operation == operation)
+                .map(|modifier| modifier.amount)
+        };
+        let added = self.base + with_operation(Operation::AddValue).sum::<f64>();
+        let mut value = added + added * with_operation(Operation::AddMultipliedBase).sum::<f64>();
+        for amount in with_operation(Operation::AddMultipliedTotal) {
+            value *= 1.0 + amount;
+        }
+        let (min, max) = value_range(attribute);
+        value.clamp(min, max)
+    }
+}
+
+/// The attributes of a living entity, like its max health or movement speed.
+pub struct AttributeMap {
+    entity_type: EntityType,
+    /// Only the attributes which were changed, the others have their default base value
+    instances: HashMap<Attribute, AttributeInstance>,
+}
+
+impl AttributeMap {
+    pub fn new(entity_type: EntityType) -> Self {
+        Self {
+            entity_type,
+            instances: HashMap::new(),
+        }
+    }
+
+    fn instance_mut(&mut self, attribute: Attribute) -> &mut AttributeInstance {
+        let entity_type = &self.entity_type;
+        self.instances
+            .entry(attribute)
+            .or_insert_with(|| AttributeInstance::new(default_base_value(entity_type, attribute)))
+    }
+
+    pub fn get(&self, attribute: Attribute) -> AttributeInstance {
+        self.instances.get(&attribute).cloned().unwrap_or_else(|| {
+            AttributeInstance::new(default_base_value(&self.entity_type, attribute))
+        })
+    }
+
+    pub fn base_value(&self, attribute: Attribute) -> f64 {
+        self.instances.get(&attribute).map_or_else(
+            || default_base_value(&self.entity_type, attribute),
+            AttributeInstance::base,
+        )
+    }
+
+    /// The value with all modifiers applied, this is what mechanics should use.
+    pub fn value(&self, attribute: Attribute) -> f64 {
+        self.get(attribute).value(attribute)
+    }
+
+    /// Keeps the health between 0 and the max health.
+    pub fn cap_health(&self, health: f32) -> f32 {
+        health.clamp(0.0, self.value(Attribute::MaxHealth) as f32)
+    }
+
+    /// The damage left after the armor absorbed its share, which shrinks the harder the hit is
+    /// and the less armor toughness there is, like vanilla.
+    pub fn damage_after_armor(&self, amount: f32) -> f32 {
+        let armor = self.value(Attribute::Armor) as f32;
+        let toughness = self.value(Attribute::ArmorToughness) as f32;
+        let effective_armor = (armor - amount / (2.0 + toughness / 4.0)).clamp(armor * 0.2, 20.0);
+        amount * (1.0 - effective_armor / 25.0)
+    }
+
+    pub fn set_base_value(&mut self, attribute: Attribute, base: f64) {
+        self.instance_mut(attribute).base = base;
+    }
+
+    /// Returns `false` if the attribute already has a modifier with the same id.
+    pub fn add_modifier(&mut self, attribute: Attribute, modifier: AttributeModifier) -> bool {
+        let instance = self.instance_mut(attribute);
+        if instance
+            .modifiers
+            .iter()
+            .any(|existing| existing.id == modifier.id)
+        {
+            return false;
+        }
+        instance.modifiers.push(modifier);
+        true
+    }
+
+    /// Returns `false` if the attribute has no modifier with this id.
+    pub fn remove_modifier(&mut self, attribute: Attribute, id: &str) -> bool {
+        let Some(instance) = self.instances.get_mut(&attribute) else {
+            return false;
+        };
+        let len = instance.modifiers.len();
+        instance.modifiers.retain(|modifier| modifier.id != id);
+        instance.modifiers.len() != len
+    }
+
+    pub fn modifier(&self, attribute: Attribute, id: &str) -> Option<&AttributeModifier> {
+        self.instances
+            .get(&attribute)?
+            .modifiers
+            .iter()
+            .find(|modifier| modifier.id == id)
+    }
+
+    pub fn to_property(&self, attribute: Attribute) -> AttributeProperty {
+        let instance = self.get(attribute);
+        AttributeProperty {
+            attribute: i32::from(attribute.id()).into(),
+            base: instance.base,
+            modifiers: instance
+                .modifiers
+                .iter()
+                .map(|modifier| AttributeModifierProperty {
+                    id: modifier.id.clone(),
+                    amount: modifier.amount,
+                    operation: operation_id(modifier.operation),
+                })
+                .collect(),
+        }
+    }
+
+    /// The attributes which were changed, the client knows the defaults of the others.
+    pub fn changed_properties(&self) -> Vec<AttributeProperty> {
+        self.instances
+            .keys()
+            .map(|attribute| self.to_property(*attribute))
+            .collect()
+    }
+
+    pub fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let attributes = self
+            .instances
+            .iter()
+            .map(|(attribute, instance)| {
+                let mut compound = NbtCompound::new();
+                compound.put_string("id", format!("minecraft:{}", attribute.to_name()));
+                compound.put_double("base", instance.base);
+                if !instance.modifiers.is_empty() {
+                    let modifiers = instance
+                        .modifiers
+                        .iter()
+                        .map(|modifier| {
+                            let mut compound = NbtCompound::new();
+                            compound.put_string("id", modifier.id.clone());
+                            compound.put_double("amount", modifier.amount);
+                            compound.put_string(
+                                "operation",
+                                operation_name(modifier.operation).to_string(),
+                            );
+                            NbtTag::Compound(compound)
+                        })
+                        .collect();
+                    compound.put_list("modifiers", modifiers);
+                }
+                NbtTag::Compound(compound)
+            })
+            .collect();
+        nbt.put_list("attributes", attributes);
+    }
+
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        let Some(attributes) = nbt.get_list("attributes") else {
+            return;
+        };
+        for compound in attributes.iter().filter_map(NbtTag::extract_compound) {
+            let Some(attribute) = compound
+                .get_string("id")
+                .and_then(|id| Attribute::from_name(id))
+            else {
+                continue;
+            };
+            let mut instance = AttributeInstance::new(
+                compound
+                    .get_double("base")
+                    .unwrap_or_else(|| default_base_value(&self.entity_type, attribute)),
+            );
+            if let Some(modifiers) = compound.get_list("modifiers") {
+                instance.modifiers = modifiers
+                    .iter()
+                    .filter_map(NbtTag::extract_compound)
+                    .filter_map(|modifier| {
+                        Some(AttributeModifier {
+                            id: modifier.get_string("id")?.clone(),
+                            amount: modifier.get_double("amount")?,
+                            operation: operation_from_name(modifier.get_string("operation")?)?,
+                        })
+                    })
+                    .collect();
+            }
+            self.instances.insert(attribute, instance);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::entity::{Attribute, EntityType};
+    use pumpkin_data::item::Operation;
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_protocol::{ClientPacket, client::play::CUpdateAttributes};
+
+    use super::{AttributeMap, AttributeModifier};
+
+    #[test]
+    fn max_health_caps_health_and_is_synced() {
+        let mut attributes = AttributeMap::new(EntityType::PLAYER);
+        assert_eq!(attributes.cap_health(25.0), 20.0);
+        assert!(attributes.changed_properties().is_empty());
+
+        attributes.set_base_value(Attribute::MaxHealth, 10.0);
+        assert_eq!(attributes.cap_health(20.0), 10.0);
+        assert_eq!(attributes.cap_health(-1.0), 0.0);
+
+        let packet = CUpdateAttributes::new(7.into(), attributes.changed_properties());
+        let mut data = Vec::new();
+        packet.write_packet_data(&mut data).unwrap();
+        // entity id, one property, max health, its base value and no modifiers
+        let mut expected = vec![7, 1, Attribute::MaxHealth.id()];
+        expected.extend_from_slice(&10.0f64.to_be_bytes());
+        expected.push(0);
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn modifiers_are_applied_in_order() {
+        let mut attributes = AttributeMap::new(EntityType::PLAYER);
+        assert_eq!(attributes.value(Attribute::MovementSpeed), 0.1);
+        assert_eq!(attributes.value(Attribute::MaxHealth), 20.0);
+
+        attributes.set_base_value(Attribute::MaxHealth, 10.0);
+        assert!(attributes.add_modifier(
+            Attribute::MaxHealth,
+            AttributeModifier::new("bonus", 2.0, Operation::AddValue),
+        ));
+        assert!(!attributes.add_modifier(
+            Attribute::MaxHealth,
+            AttributeModifier::new("minecraft:bonus", 5.0, Operation::AddValue),
+        ));
+        attributes.add_modifier(
+            Attribute::MaxHealth,
+            AttributeModifier::new("half", 0.5, Operation::AddMultipliedBase),
+        );
+        attributes.add_modifier(
+            Attribute::MaxHealth,
+            AttributeModifier::new("double", 1.0, Operation::AddMultipliedTotal),
+        );
+        // (10 + 2) * 1.5 * 2
+        assert_eq!(attributes.value(Attribute::MaxHealth), 36.0);
+
+        let mut nbt = NbtCompound::new();
+        attributes.write_nbt(&mut nbt);
+        let mut read = AttributeMap::new(EntityType::PLAYER);
+        read.read_nbt(&nbt);
+        assert_eq!(
+            read.get(Attribute::MaxHealth),
+            attributes.get(Attribute::MaxHealth)
+        );
+
+        assert!(attributes.remove_modifier(Attribute::MaxHealth, "minecraft:double"));
+        assert_eq!(attributes.value(Attribute::MaxHealth), 18.0);
+    }
+
+    #[test]
+    fn armor_absorbs_less_of_harder_hits() {
+        let mut attributes = AttributeMap::new(EntityType::PLAYER);
+        assert_eq!(attributes.damage_after_armor(10.0), 10.0);
+
+        // A full set of diamond armor
+        attributes.set_base_value(Attribute::Armor, 20.0);
+        attributes.set_base_value(Attribute::ArmorToughness, 8.0);
+        // (20 - 10 / 4) / 25 is absorbed
+        assert!((attributes.damage_after_armor(10.0) - 3.0).abs() < 1e-5);
+        // but only (20 - 40 / 4) / 25 of harder hits
+        assert!((attributes.damage_after_armor(40.0) - 24.0).abs() < 1e-5);
+
+        // Zombies are born with a bit of armor
+        let zombie = AttributeMap::new(EntityType::ZOMBIE);
+        assert!((zombie.damage_after_armor(5.0) - 4.92).abs() < 1e-5);
+        assert_eq!(zombie.value(Attribute::MovementSpeed), 0.23);
+        let skeleton = AttributeMap::new(EntityType::SKELETON);
+        assert_eq!(skeleton.value(Attribute::MovementSpeed), 0.25);
+    }
+}
@@ -0,0 +1,215 @@
+use pumpkin_config::advanced_config;
+use pumpkin_data::damage::DamageType;
+use pumpkin_util::GameMode;
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use rand::Rng;
+
+use crate::world::World;
+
+use super::{EntityBase, living::LivingEntity};
+
+/// How strongly two overlapping entities push each other apart per tick, like vanilla.
+const PUSH_STRENGTH: f64 = 0.05;
+/// The most an entity gets pushed by others in one tick, so a crowd can't fling it away.
+const MAX_PUSH_PER_TICK: f64 = 0.2;
+
+/// The velocity pushing an entity at `pos` away from an overlapping entity at `other`. `None` if
+/// they are at (almost) the same spot, so there is no direction to push in.
+pub fn push_away(pos: Vector3<f64>, other: Vector3<f64>) -> Option<Vector3<f64>> {
+    let dx = other.x - pos.x;
+    let dz = other.z - pos.z;
+    let distance = dx.abs().max(dz.abs());
+    if distance < 0.01 {
+        return None;
+    }
+    let distance = distance.sqrt();
+    let scale = (1.0 / distance).min(1.0) / distance * PUSH_STRENGTH;
+    Some(Vector3::new(-dx * scale, 0.0, -dz * scale))
+}
+
+fn cap_push(push: Vector3<f64>) -> Vector3<f64> {
+    let length = push.horizontal_length();
+    if length > MAX_PUSH_PER_TICK {
+        let scale = MAX_PUSH_PER_TICK / length;
+        push.multiply(scale, 1.0, scale)
+    } else {
+        push
+    }
+}
+
+/// Where an entity whose feet are stuck in a block gets moved to: the closest of the horizontally
+/// adjacent blocks and the block above which is free. `None` if the entity is trapped.
+pub fn push_out_of_block(
+    pos: Vector3<f64>,
+    is_free: impl Fn(&BlockPos) -> bool,
+) -> Option<Vector3<f64>> {
+    let block = BlockPos::floored(pos.x, pos.y, pos.z);
+    let inside = pos.sub(&block.0.to_f64());
+    [
+        (Vector3::new(-1, 0, 0), inside.x),
+        (Vector3::new(1, 0, 0), 1.0 - inside.x),
+        (Vector3::new(0, 0, -1), inside.z),
+        (Vector3::new(0, 0, 1), 1.0 - inside.z),
+        (Vector3::new(0, 1, 0), 1.0 - inside.y),
+    ]
+    .into_iter()
+    .filter(|(offset, _)| is_free(&block.offset(*offset)))
+    .min_by(|(_, a), (_, b)| a.total_cmp(b))
+    .map(|(offset, _)| {
+        let free = block.offset(offset).0;
+        if offset.x != 0 {
+            Vector3::new(f64::from(free.x) + 0.5, pos.y, pos.z)
+        } else if offset.z != 0 {
+            Vector3::new(pos.x, pos.y, f64::from(free.z) + 0.5)
+        } else {
+            Vector3::new(pos.x, f64::from(free.y), pos.z)
+        }
+    })
+}
+
+/// Whether an entity overlapping `crammed` other entities takes cramming damage.
+pub fn is_crammed(crammed: usize, max_entity_cramming: u32) -> bool {
+    max_entity_cramming > 0 && crammed >= max_entity_cramming as usize
+}
+
+async fn is_free(world: &World, pos: &BlockPos) -> bool {
+    world
+        .get_block_state(pos)
+        .await
+        .is_ok_and(|state| !(state.is_solid() && state.is_full_cube()))
+}
+
+/// Pushes a mob out of the block it is stuck in and away from the entities it overlaps, hurting it
+/// when it is trapped or too many entities are crammed together.
+pub async fn tick_collisions(living: &LivingEntity) {
+    if living.health.load() <= 0.0 {
+        return;
+    }
+    let config = &advanced_config().entity_collision;
+    let entity = &living.entity;
+    let world = entity.world.read().await.clone();
+    let pos = entity.pos.load();
+
+    let block_pos = entity.block_pos.load();
+    if config.push_out_of_blocks && !is_free(&world, &block_pos).await {
+        let mut free = Vec::new();
+        for offset in [(-1, 0, 0), (1, 0, 0), (0, 0, -1), (0, 0, 1), (0, 1, 0)] {
+            let neighbor = block_pos.offset(Vector3::new(offset.0, offset.1, offset.2));
+            if is_free(&world, &neighbor).await {
+                free.push(neighbor);
+            }
+        }
+        if let Some(target) = push_out_of_block(pos, |pos| free.contains(pos)) {
+            entity
+                .teleport(target, entity.yaw.load(), entity.pitch.load())
+                .await;
+        } else {
+            living.damage(1.0, DamageType::IN_WALL).await;
+        }
+        return;
+    }
+
+    if !config.push_entities && config.max_entity_cramming == 0 {
+        return;
+    }
+    let bounding_box = entity.bounding_box.load();
+    let mut overlapping = Vec::new();
+    for other in world.entities.read().await.values() {
+        let other_entity = other.get_entity();
+        if other_entity.entity_id != entity.entity_id
+            && other
+                .get_living_entity()
+                .is_some_and(|other| other.health.load() > 0.0)
+            && other_entity.bounding_box.load().intersects(&bounding_box)
+        {
+            overlapping.push((
+                other_entity.pos.load(),
+                other_entity.entity_uuid.to_string(),
+            ));
+        }
+    }
+    for player in world.players.read().await.values() {
+        let player_entity = &player.living_entity.entity;
+        // Spectators pass through everything
+        if player.gamemode.load() != GameMode::Spectator
+            && player_entity.bounding_box.load().intersects(&bounding_box)
+        {
+            overlapping.push((player_entity.pos.load(), player.gameprofile.name.clone()));
+        }
+    }
+
+    if config.push_entities {
+        // Team collision rules decide who may push whom
+        let name = entity.entity_uuid.to_string();
+        let scoreboard = world.scoreboard.lock().await;
+        let push = overlapping
+            .iter()
+            .filter(|(_, other_name)| scoreboard.can_push(&name, other_name))
+            .filter_map(|(other, _)| push_away(pos, *other))
+            .fold(Vector3::new(0.0, 0.0, 0.0), |total, push| total + push);
+        drop(scoreboard);
+        if push.horizontal_length_squared() > 0.0 {
+            entity
+                .set_velocity(entity.velocity.load() + cap_push(push))
+                .await;
+        }
+    }
+
+    if is_crammed(overlapping.len(), config.max_entity_cramming) {
+        // Like vanilla, crammed entities only get hurt every now and then
+        let unlucky = rand::thread_rng().gen_ratio(1, 4);
+        if unlucky {
+            living.damage(6.0, DamageType::CRAMMING).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+    use super::{cap_push, is_crammed, push_away, push_out_of_block};
+
+    #[test]
+    fn overlapping_mobs_are_pushed_apart() {
+        let first = Vector3::new(0.0, 64.0, 0.0);
+        let second = Vector3::new(0.3, 64.0, 0.0);
+        let first_push = push_away(first, second).unwrap();
+        let second_push = push_away(second, first).unwrap();
+        assert!(first_push.x < 0.0);
+        assert!(second_push.x > 0.0);
+        assert_eq!(first_push.x, -second_push.x);
+        assert_eq!(first_push.y, 0.0);
+        assert_eq!(first_push.z, 0.0);
+
+        // Nowhere to push to
+        assert!(push_away(first, first).is_none());
+
+        let crowd = cap_push(Vector3::new(1.0, 0.0, 0.0));
+        assert_eq!(crowd.x, 0.2);
+    }
+
+    #[test]
+    fn stuck_mob_is_pushed_into_free_neighbor() {
+        let pos = Vector3::new(0.8, 64.0, 0.4);
+        let free = [BlockPos::new(-1, 64, 0), BlockPos::new(1, 64, 0)];
+        // The east side is closer
+        assert_eq!(
+            push_out_of_block(pos, |pos| free.contains(pos)),
+            Some(Vector3::new(1.5, 64.0, 0.4))
+        );
+        assert_eq!(
+            push_out_of_block(pos, |pos| *pos == BlockPos::new(0, 65, 0)),
+            Some(Vector3::new(0.8, 65.0, 0.4))
+        );
+        // Trapped
+        assert_eq!(push_out_of_block(pos, |_| false), None);
+    }
+
+    #[test]
+    fn cramming_starts_at_the_limit() {
+        assert!(!is_crammed(23, 24));
+        assert!(is_crammed(24, 24));
+        assert!(!is_crammed(100, 0));
+    }
+}
@@ -1,8 +1,8 @@
 use pumpkin_data::{
+    entity::EntityType,
     particle::Particle,
     sound::{Sound, SoundCategory},
 };
-use pumpkin_protocol::{client::play::CEntityVelocity, codec::var_int::VarInt};
 use pumpkin_util::math::vector3::Vector3;
 use pumpkin_world::item::ItemStack;
 
@@ -55,25 +55,25 @@ impl AttackType {
     }
 }
 
-pub async fn handle_knockback(attacker: &Entity, world: &World, victim: &Entity, strength: f64) {
+pub async fn handle_knockback(attacker: &Entity, victim: &Entity, strength: f64) {
     let yaw = attacker.yaw.load();
 
     let saved_velo = victim.velocity.load();
-    victim.knockback(
-        strength * 0.5,
-        f64::from((yaw.to_radians()).sin()),
-        f64::from(-(yaw.to_radians()).cos()),
-    );
-
-    let entity_id = VarInt(victim.entity_id);
-    let victim_velocity = victim.velocity.load();
+    victim
+        .knockback(
+            strength * 0.5,
+            f64::from((yaw.to_radians()).sin()),
+            f64::from(-(yaw.to_radians()).cos()),
+        )
+        .await;
 
-    let packet = CEntityVelocity::new(entity_id, victim_velocity);
     let velocity = attacker.velocity.load();
     attacker.velocity.store(velocity.multiply(0.6, 1.0, 0.6));
 
-    victim.velocity.store(saved_velo);
-    world.broadcast_packet_all(&packet).await;
+    // Players move themselves, their client takes it from here
+    if victim.entity_type == EntityType::PLAYER {
+        victim.velocity.store(saved_velo);
+    }
 }
 
 pub async fn spawn_sweep_particle(attacker_entity: &Entity, world: &World, pos: &Vector3<f64>) {
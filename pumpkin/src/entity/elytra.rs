@@ -0,0 +1,116 @@
+use pumpkin_data::item::Item;
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::item::ItemStack;
+
+/// The gravity pulling a gliding player down every tick.
+const GRAVITY: f64 = 0.08;
+/// Elytras lose a point of durability every second of flight.
+pub const DURABILITY_INTERVAL: u32 = 20;
+/// How many ticks a firework rocket of flight duration 1 keeps boosting a gliding player.
+pub const FIREWORK_BOOST_TICKS: u32 = 20;
+
+/// The unit vector a player with the given `yaw` and `pitch` in degrees looks along.
+pub fn look_direction(yaw: f32, pitch: f32) -> Vector3<f64> {
+    let pitch = f64::from(pitch).to_radians();
+    let yaw = -f64::from(yaw).to_radians();
+    Vector3::new(
+        yaw.sin() * pitch.cos(),
+        -pitch.sin(),
+        yaw.cos() * pitch.cos(),
+    )
+}
+
+/// The velocity of a gliding player after one tick, trading height for speed along the look
+/// direction with vanilla's lift and drag.
+pub fn glide(velocity: Vector3<f64>, yaw: f32, pitch: f32) -> Vector3<f64> {
+    let look = look_direction(yaw, pitch);
+    let pitch = f64::from(pitch).to_radians();
+    let look_horizontal = look.horizontal_length();
+    let speed = velocity.horizontal_length();
+    let lift = pitch.cos().powi(2);
+
+    let mut velocity = velocity.add_raw(0.0, GRAVITY * (lift * 0.75 - 1.0), 0.0);
+    if look_horizontal > 0.0 {
+        if velocity.y < 0.0 {
+            // Falling is turned into forward speed
+            let gain = velocity.y * -0.1 * lift;
+            velocity = velocity.add_raw(
+                look.x * gain / look_horizontal,
+                gain,
+                look.z * gain / look_horizontal,
+            );
+        }
+        if pitch < 0.0 {
+            // Pulling up is paid for with forward speed
+            let climb = speed * -pitch.sin() * 0.04;
+            velocity = velocity.add_raw(
+                -look.x * climb / look_horizontal,
+                climb * 3.2,
+                -look.z * climb / look_horizontal,
+            );
+        }
+        velocity = velocity.add_raw(
+            (look.x / look_horizontal * speed - velocity.x) * 0.1,
+            0.0,
+            (look.z / look_horizontal * speed - velocity.z) * 0.1,
+        );
+    }
+    velocity.multiply(0.99, 0.98, 0.99)
+}
+
+/// The velocity of a gliding player after a tick of being pushed by a firework rocket.
+pub fn firework_boost(velocity: Vector3<f64>, yaw: f32, pitch: f32) -> Vector3<f64> {
+    let look = look_direction(yaw, pitch);
+    velocity.add_raw(
+        look.x * 0.1 + (look.x * 1.5 - velocity.x) * 0.5,
+        look.y * 0.1 + (look.y * 1.5 - velocity.y) * 0.5,
+        look.z * 0.1 + (look.z * 1.5 - velocity.z) * 0.5,
+    )
+}
+
+/// The kinetic damage of flying into a wall, `None` if the player didn't lose enough speed to get
+/// hurt.
+pub fn wall_impact_damage(speed_before: f64, speed_after: f64) -> Option<f32> {
+    let damage = (speed_before - speed_after) * 10.0 - 3.0;
+    (damage > 0.0).then_some(damage as f32)
+}
+
+/// Whether `stack` is an elytra which can still be flown with, elytras stop working at their
+/// last point of durability instead of breaking.
+pub fn is_usable(stack: &ItemStack) -> bool {
+    let max_damage = i32::from(Item::ELYTRA.components.max_damage.unwrap_or_default());
+    stack.item.id == Item::ELYTRA.id && stack.patch.damage.unwrap_or(0) < max_damage - 1
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{firework_boost, glide, wall_impact_damage};
+
+    #[test]
+    fn diving_gains_speed_and_fireworks_boost() {
+        // Diving south, a gliding player speeds up
+        let mut velocity = Vector3::new(0.0, -0.1, 0.2);
+        for _ in 0..20 {
+            let next = glide(velocity, 0.0, 30.0);
+            assert!(next.horizontal_length() > velocity.horizontal_length());
+            velocity = next;
+        }
+        assert!(velocity.z > 0.4);
+        assert!(velocity.x.abs() < 1e-9);
+
+        // A rocket pushes the player towards 1.5 blocks per tick where they look
+        let boosted = firework_boost(Vector3::new(0.0, 0.0, 0.0), 0.0, 0.0);
+        assert!((boosted.z - 0.85).abs() < 1e-9);
+        assert!(boosted.x.abs() < 1e-9 && boosted.y.abs() < 1e-9);
+        let mut velocity = boosted;
+        for _ in 0..20 {
+            velocity = firework_boost(velocity, 0.0, 0.0);
+        }
+        assert!((velocity.z - 1.7).abs() < 1e-3);
+
+        assert_eq!(wall_impact_damage(0.5, 0.0), Some(2.0));
+        assert_eq!(wall_impact_damage(0.2, 0.0), None);
+    }
+}
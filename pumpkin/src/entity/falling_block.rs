@@ -0,0 +1,308 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering::Relaxed},
+};
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    Block, BlockState, damage::DamageType, entity::EntityType, item::Item, tag::Tagable,
+    world::WorldEvent,
+};
+use pumpkin_util::math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3};
+use pumpkin_world::{BlockStateId, block::BlockDirection, item::ItemStack};
+
+use crate::{
+    block::drop_stack,
+    server::Server,
+    world::{BlockFlags, World},
+};
+
+use super::{Entity, EntityBase, living::LivingEntity};
+
+/// How much faster a falling block gets every tick
+const GRAVITY: f64 = 0.04;
+/// How much of its velocity a falling block keeps every tick
+const DRAG: f64 = 0.98;
+/// After how many ticks a falling block which didn't land gives up and drops as an item
+const MAX_FALL_TIME: u32 = 600;
+/// The damage an anvil deals for every block it fell, after the first one
+const ANVIL_DAMAGE_PER_BLOCK: f32 = 2.0;
+const ANVIL_MAX_DAMAGE: f32 = 40.0;
+
+pub struct FallingBlockEntity {
+    entity: Entity,
+    block_state: BlockStateId,
+    fall_time: AtomicU32,
+    /// Where the block started falling, to know how far it fell
+    start_y: f64,
+}
+
+impl FallingBlockEntity {
+    pub fn new(entity: Entity, block_state: BlockStateId) -> Self {
+        let start_y = entity.pos.load().y;
+        Self {
+            entity,
+            block_state,
+            fall_time: AtomicU32::new(0),
+            start_y,
+        }
+    }
+
+    /// Turns the block at `pos` into a falling block.
+    pub async fn spawn_from_block(world: &Arc<World>, pos: &BlockPos, block_state: BlockStateId) {
+        let position = Vector3::new(
+            f64::from(pos.0.x) + 0.5,
+            f64::from(pos.0.y),
+            f64::from(pos.0.z) + 0.5,
+        );
+        let entity = world.create_entity(position, EntityType::FALLING_BLOCK);
+        let falling_block = Arc::new(Self::new(entity, block_state));
+        world.set_block_state(pos, 0, BlockFlags::NOTIFY_ALL).await;
+        world.spawn_entity(falling_block).await;
+    }
+
+    fn block(&self) -> Block {
+        Block::from_state_id(self.block_state).unwrap_or(Block::SAND)
+    }
+
+    async fn land(&self, world: &Arc<World>, pos: BlockPos) {
+        self.entity.remove().await;
+        let block = self.block();
+
+        if block.is_tagged_with("minecraft:anvil").unwrap_or(false) {
+            let fall_distance = self.start_y - f64::from(pos.0.y);
+            if let Some(damage) = anvil_damage(fall_distance) {
+                damage_entities_in(world, &pos, damage).await;
+            }
+            world.sync_world_event(WorldEvent::AnvilLands, pos, 0).await;
+        }
+
+        let Ok((replaced, replaced_state)) = world.get_block_and_block_state(&pos).await else {
+            return;
+        };
+        if !can_fall_through(&replaced, &replaced_state) {
+            self.drop_item(world, &pos).await;
+            return;
+        }
+        if !replaced_state.is_air() && !replaced_state.is_liquid() {
+            // Grass, flowers and the like in the way get destroyed
+            world.break_block(&pos, None, BlockFlags::NOTIFY_ALL).await;
+        }
+
+        let state = match hardened_concrete(&block) {
+            Some(concrete) if touches_water(world, &pos).await => concrete.default_state_id,
+            _ => self.block_state,
+        };
+        world
+            .set_block_state(&pos, state, BlockFlags::NOTIFY_ALL)
+            .await;
+    }
+
+    async fn drop_item(&self, world: &Arc<World>, pos: &BlockPos) {
+        if let Some(item) = Item::from_id(self.block().item_id) {
+            drop_stack(world, pos, ItemStack::new(1, item)).await;
+        }
+    }
+}
+
+#[async_trait]
+impl EntityBase for FallingBlockEntity {
+    async fn tick(&self, server: &Server) {
+        let entity = &self.entity;
+        entity.tick(server).await;
+        let world = entity.world.read().await.clone();
+        let pos = entity.pos.load();
+        let block_pos = entity.block_pos.load();
+
+        if self.fall_time.fetch_add(1, Relaxed) >= MAX_FALL_TIME {
+            entity.remove().await;
+            self.drop_item(&world, &block_pos).await;
+            return;
+        }
+
+        // Concrete powder stops as soon as it falls into water
+        if hardened_concrete(&self.block()).is_some()
+            && world
+                .get_block(&block_pos)
+                .await
+                .is_ok_and(|block| block == Block::WATER)
+        {
+            self.land(&world, block_pos).await;
+            return;
+        }
+
+        let velocity = entity.velocity.load().y - GRAVITY;
+        let target_y = pos.y + velocity;
+        let mut stopping = Vec::new();
+        for y in (target_y.floor() as i32..pos.y.floor() as i32).rev() {
+            let below = BlockPos::new(block_pos.0.x, y, block_pos.0.z);
+            if let Ok((block, state)) = world.get_block_and_block_state(&below).await {
+                if stops_fall(&block, &state) {
+                    stopping.push(y);
+                }
+            }
+        }
+
+        if let Some(landing_y) = landing_height(pos.y, velocity, |y| stopping.contains(&y)) {
+            self.land(
+                &world,
+                BlockPos::new(block_pos.0.x, landing_y, block_pos.0.z),
+            )
+            .await;
+            return;
+        }
+        entity.set_pos(Vector3::new(pos.x, target_y, pos.z));
+        entity
+            .velocity
+            .store(Vector3::new(0.0, velocity * DRAG, 0.0));
+    }
+
+    async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
+        false
+    }
+
+    fn spawn_data(&self) -> i32 {
+        i32::from(self.block_state)
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+}
+
+/// Whether a falling block can fall into the space of this block, like air, water or grass.
+pub fn can_fall_through(block: &Block, state: &BlockState) -> bool {
+    state.is_air()
+        || state.is_liquid()
+        || state.replaceable()
+        || block.is_tagged_with("minecraft:fire").unwrap_or(false)
+}
+
+/// Whether a falling block lands on top of this block. Blocks without collision like torches
+/// are fallen into, but the falling block can't be placed there.
+fn stops_fall(block: &Block, state: &BlockState) -> bool {
+    !can_fall_through(block, state) && !state.collision_shapes.is_empty()
+}
+
+/// The y of the block a falling block at `y` lands in when it moves by `velocity`, or `None` if
+/// it keeps falling. `stops_fall` tells whether the block at the given y is landed on.
+pub fn landing_height(y: f64, velocity: f64, stops_fall: impl Fn(i32) -> bool) -> Option<i32> {
+    let target_y = y + velocity;
+    // A block is landed on once the falling block drops below its top
+    (target_y.floor() as i32..y.floor() as i32)
+        .rev()
+        .find(|&below| stops_fall(below))
+        .map(|below| below + 1)
+}
+
+/// The damage an anvil deals to the entities it lands on after falling `fall_distance` blocks.
+pub fn anvil_damage(fall_distance: f64) -> Option<f32> {
+    let blocks = (fall_distance - 1.0).ceil() as f32;
+    (blocks > 0.0).then(|| (blocks * ANVIL_DAMAGE_PER_BLOCK).min(ANVIL_MAX_DAMAGE))
+}
+
+/// The concrete a concrete powder block turns into in water.
+fn hardened_concrete(block: &Block) -> Option<Block> {
+    Block::from_registry_key(block.name.strip_suffix("_powder")?)
+}
+
+/// Whether there is water in the block or next to it, except below.
+async fn touches_water(world: &World, pos: &BlockPos) -> bool {
+    let mut neighbors = vec![*pos, pos.up()];
+    neighbors.extend(
+        BlockDirection::horizontal()
+            .into_iter()
+            .map(|direction| pos.offset(direction.to_offset())),
+    );
+    for neighbor in neighbors {
+        if world
+            .get_block(&neighbor)
+            .await
+            .is_ok_and(|block| block == Block::WATER)
+        {
+            return true;
+        }
+    }
+    false
+}
+
+async fn damage_entities_in(world: &World, pos: &BlockPos, damage: f32) {
+    let area = BoundingBox::new(pos.to_f64(), pos.up().to_f64().add_raw(1.0, 0.0, 1.0));
+    let entities: Vec<_> = world.entities.read().await.values().cloned().collect();
+    for entity in entities {
+        if entity.get_entity().bounding_box.load().intersects(&area) {
+            entity.damage(damage, DamageType::FALLING_ANVIL).await;
+        }
+    }
+    let players: Vec<_> = world.players.read().await.values().cloned().collect();
+    for player in players {
+        if player
+            .living_entity
+            .entity
+            .bounding_box
+            .load()
+            .intersects(&area)
+        {
+            player.damage(damage, DamageType::FALLING_ANVIL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, block_properties::get_state_by_state_id};
+
+    use super::{DRAG, GRAVITY, anvil_damage, can_fall_through, landing_height};
+
+    #[test]
+    fn sand_without_support_lands_one_block_down() {
+        // Sand at y 65, the block below it removed and solid ground at y 63
+        let mut y = 65.0;
+        let mut velocity = 0.0;
+        let mut landed = None;
+        for _ in 0..20 {
+            velocity -= GRAVITY;
+            landed = landing_height(y, velocity, |below| below <= 63);
+            if landed.is_some() {
+                break;
+            }
+            y += velocity;
+            velocity *= DRAG;
+        }
+        assert_eq!(landed, Some(64));
+    }
+
+    #[test]
+    fn fast_blocks_dont_skip_the_ground() {
+        assert_eq!(landing_height(70.2, -3.0, |below| below == 68), Some(69));
+        assert_eq!(landing_height(70.2, -3.0, |below| below == 66), None);
+        assert_eq!(landing_height(65.5, -0.2, |below| below == 64), None);
+    }
+
+    #[test]
+    fn falls_through_air_water_and_grass() {
+        let falls_through = |block: Block| {
+            can_fall_through(
+                &block,
+                &get_state_by_state_id(block.default_state_id).unwrap(),
+            )
+        };
+        assert!(falls_through(Block::AIR));
+        assert!(falls_through(Block::WATER));
+        assert!(falls_through(Block::SHORT_GRASS));
+        assert!(falls_through(Block::FIRE));
+        assert!(!falls_through(Block::STONE));
+        assert!(!falls_through(Block::TORCH));
+    }
+
+    #[test]
+    fn anvil_damage_grows_with_fall_distance() {
+        assert_eq!(anvil_damage(1.0), None);
+        assert_eq!(anvil_damage(3.0), Some(4.0));
+        assert_eq!(anvil_damage(100.0), Some(40.0));
+    }
+}
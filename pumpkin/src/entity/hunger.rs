@@ -32,21 +32,30 @@ impl HungerManager {
         let level = self.level.load();
         let exhaustion = self.exhaustion.load();
         let health = player.living_entity.health.load();
-        let difficulty = player.world().await.level.level_info.difficulty.clone();
+        let difficulty = player
+            .world()
+            .await
+            .level
+            .level_info
+            .read()
+            .await
+            .difficulty
+            .clone();
         // Decrease hunger level on exhaustion
         if level != 0 && exhaustion > 4.0 {
             self.exhaustion.store(exhaustion - 4.0);
             if saturation > 0.0 {
                 self.saturation.store((saturation - 1.0).max(0.0));
+                player.sync_health_food().await;
             } else if difficulty != Difficulty::Peaceful {
                 self.level.store(level - 1);
-                player.send_health().await;
+                player.sync_health_food().await;
             }
         }
 
         // Heal when hunger is full
         let natural_regn = true; // TODO: Get the actual value when this will be implemented.
-        if natural_regn && saturation > 0.0 && player.can_food_heal() && level >= 20 {
+        if natural_regn && saturation > 0.0 && player.can_food_heal().await && level >= 20 {
             self.tick_timer.fetch_add(1);
             if self.tick_timer.load() >= 10 {
                 let saturation = saturation.min(6.0);
@@ -54,7 +63,7 @@ impl HungerManager {
                 self.add_exhaustion(saturation);
                 self.tick_timer.store(0);
             }
-        } else if natural_regn && level >= 18 && player.can_food_heal() {
+        } else if natural_regn && level >= 18 && player.can_food_heal().await {
             self.tick_timer.fetch_add(1);
             if self.tick_timer.load() >= 80 {
                 player.heal(1.0).await;
@@ -28,6 +28,18 @@ pub struct ItemEntity {
 
 impl ItemEntity {
     pub async fn new(entity: Entity, item_id: u16, count: u32) -> Self {
+        Self::from_stack(
+            entity,
+            ItemStack::new(
+                count as u8,
+                Item::from_id(item_id).expect("We passed a bad item id into ItemEntity"),
+            ),
+        )
+        .await
+    }
+
+    /// Creates an item entity holding the given stack, keeping its components.
+    pub async fn from_stack(entity: Entity, item_stack: ItemStack) -> Self {
         entity
             .set_velocity(Vector3::new(
                 rand::random::<f64>() * 0.2 - 0.1,
@@ -38,10 +50,7 @@ impl ItemEntity {
         entity.yaw.store(rand::random::<f32>() * 360.0);
         Self {
             entity,
-            item_stack: Mutex::new(ItemStack::new(
-                count as u8,
-                Item::from_id(item_id).expect("We passed a bad item id into ItemEntity"),
-            )),
+            item_stack: Mutex::new(item_stack),
             item_age: AtomicU32::new(0),
             pickup_delay: Mutex::new(10), // Vanilla pickup delay is 10 ticks
         }
@@ -49,8 +58,7 @@ impl ItemEntity {
 
     pub async fn new_with_velocity(
         entity: Entity,
-        item_id: u16,
-        count: u32,
+        item_stack: ItemStack,
         velocity: Vector3<f64>,
         pickup_delay: u8,
     ) -> Self {
@@ -58,10 +66,7 @@ impl ItemEntity {
         entity.yaw.store(rand::random::<f32>() * 360.0);
         Self {
             entity,
-            item_stack: Mutex::new(ItemStack::new(
-                count as u8,
-                Item::from_id(item_id).expect("We passed a bad item id into ItemEntity"),
-            )),
+            item_stack: Mutex::new(item_stack),
             item_age: AtomicU32::new(0),
             pickup_delay: Mutex::new(pickup_delay), // Vanilla pickup delay is 10 ticks
         }
@@ -105,84 +110,48 @@ impl EntityBase for ItemEntity {
         };
 
         if can_pickup {
-            let mut inv = player.inventory.lock().await;
-            let mut total_pick_up = 0;
-            let mut slot_updates = Vec::new();
-            let remove_entity = {
-                let item_stack = self.item_stack.lock().await.clone();
-                let mut stack_size = item_stack.item_count;
-                let max_stack = item_stack.item.components.max_stack_size;
-                while stack_size > 0 {
-                    if let Some(slot) = inv.get_pickup_item_slot(item_stack.item.id) {
-                        // Fill the inventory while there are items in the stack and space in the inventory
-                        let maybe_stack = inv.get_slot(slot).unwrap();
-
-                        if let Some(existing_stack) = maybe_stack {
-                            // We have the item in this stack already
-
-                            // This is bounded to `u8::MAX`
-                            let amount_to_fill = u32::from(max_stack - existing_stack.item_count);
-                            // This is also bounded to `u8::MAX` since `amount_to_fill` is max `u8::MAX`
-                            let amount_to_add = amount_to_fill.min(u32::from(stack_size));
-                            // Therefore this is safe
-
-                            // Update referenced stack so next call to `get_pickup_item_slot` is
-                            // correct
-                            existing_stack.item_count += amount_to_add as u8;
-                            total_pick_up += amount_to_add;
-
-                            debug_assert!(amount_to_add > 0);
-                            stack_size = stack_size.saturating_sub(amount_to_add as u8);
-
-                            slot_updates.push((slot, existing_stack.clone()));
-                        } else {
-                            // A new stack
-
-                            // This is bounded to `u8::MAX`
-                            let amount_to_fill = u32::from(max_stack);
-                            // This is also bounded to `u8::MAX` since `amount_to_fill` is max `u8::MAX`
-                            let amount_to_add = amount_to_fill.min(u32::from(stack_size));
-                            total_pick_up += amount_to_add;
-
-                            debug_assert!(amount_to_add > 0);
-                            stack_size = stack_size.saturating_sub(amount_to_add as u8);
-
-                            slot_updates.push((slot, self.item_stack.lock().await.clone()));
-                        }
-                    } else {
-                        // We can't pick anything else up
-                        break;
-                    }
-                }
-
-                stack_size == 0
-            };
-
-            if total_pick_up > 0 {
-                player
-                    .client
-                    .enqueue_packet(&CTakeItemEntity::new(
-                        self.entity.entity_id.into(),
-                        player.entity_id().into(),
-                        total_pick_up.try_into().unwrap(),
-                    ))
-                    .await;
-            }
+            let mut item_stack = self.item_stack.lock().await;
+            let item_count = item_stack.item_count;
+            let leftover = player.give_item(item_stack.clone()).await;
+            let total_pick_up = item_count - leftover.as_ref().map_or(0, |stack| stack.item_count);
 
-            // TODO: Can we batch slot updates?
-            for (slot, stack) in slot_updates {
-                player.update_single_slot(&mut inv, slot, stack).await;
+            if total_pick_up == 0 {
+                return;
             }
-
-            if remove_entity {
-                self.entity.remove().await;
-            } else {
+            player
+                .client
+                .enqueue_packet(&CTakeItemEntity::new(
+                    self.entity.entity_id.into(),
+                    player.entity_id().into(),
+                    i32::from(total_pick_up).into(),
+                ))
+                .await;
+
+            if let Some(leftover) = leftover {
+                *item_stack = leftover;
+                drop(item_stack);
                 // Update entity
                 self.send_meta_packet().await;
+            } else {
+                drop(item_stack);
+                self.entity.remove().await;
             }
         }
     }
 
+    async fn send_tracking_data(&self, player: &Player) {
+        self.entity
+            .send_meta_data_to(
+                player,
+                &[Metadata::new(
+                    8,
+                    MetaDataType::ItemStack,
+                    &ItemStackSerializer::from(self.item_stack.lock().await.clone()),
+                )],
+            )
+            .await;
+    }
+
     fn get_entity(&self) -> &Entity {
         &self.entity
     }
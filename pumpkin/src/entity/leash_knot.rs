@@ -0,0 +1,91 @@
+use async_trait::async_trait;
+use pumpkin_data::{
+    damage::DamageType,
+    sound::{Sound, SoundCategory},
+    tag::Tagable,
+};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+
+use crate::server::Server;
+
+use super::{Entity, EntityBase, living::LivingEntity, player::Player};
+
+/// The knot leashes are tied to when a player ties their leashed mobs to a fence.
+/// It only lasts as long as leashes are tied to it and the fence stands.
+pub struct LeashKnotEntity {
+    entity: Entity,
+    fence: BlockPos,
+}
+
+impl LeashKnotEntity {
+    pub fn new(entity: Entity, fence: BlockPos) -> Self {
+        Self { entity, fence }
+    }
+
+    /// Where the knot sits on a fence.
+    pub fn position(fence: &BlockPos) -> Vector3<f64> {
+        Vector3::new(
+            f64::from(fence.0.x) + 0.5,
+            f64::from(fence.0.y) + 0.375,
+            f64::from(fence.0.z) + 0.5,
+        )
+    }
+
+    /// Removes the knot, the leashes tied to it break and drop their leads.
+    async fn untie(&self) {
+        let world = self.entity.world.read().await.clone();
+        world
+            .play_sound(
+                Sound::EntityLeashKnotBreak,
+                SoundCategory::Blocks,
+                &self.entity.pos.load(),
+            )
+            .await;
+        world.remove_entity(&self.entity).await;
+    }
+}
+
+#[async_trait]
+impl EntityBase for LeashKnotEntity {
+    async fn tick(&self, _server: &Server) {
+        let world = self.entity.world.read().await.clone();
+        let unused = world
+            .leashes
+            .lock()
+            .await
+            .leashed_to(self.entity.entity_id)
+            .is_empty();
+        let on_fence = world
+            .get_block(&self.fence)
+            .await
+            .is_ok_and(|block| block.is_tagged_with("minecraft:fences") == Some(true));
+        if unused || !on_fence {
+            world.remove_entity(&self.entity).await;
+        }
+    }
+
+    async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
+        self.untie().await;
+        true
+    }
+
+    async fn interact(&self, player: &Player) -> bool {
+        let world = self.entity.world.read().await.clone();
+        if !world
+            .tie_leashes_to_fence(player.entity_id(), self.fence)
+            .await
+        {
+            // Nothing to tie to the knot, so the player unties it instead
+            self.untie().await;
+        }
+        true
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+}
@@ -0,0 +1,175 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU8, Ordering::Relaxed},
+};
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    damage::DamageType,
+    entity::EntityType,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_util::math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3};
+
+use crate::{
+    block::blocks::fire::FireBlockBase,
+    server::Server,
+    world::{BlockFlags, World},
+};
+
+use super::{Entity, EntityBase, living::LivingEntity, mob};
+
+/// How many ticks the bolt stays visible before it is removed.
+const LIFETIME: u8 = 2;
+/// The damage dealt to entities close to the strike.
+const DAMAGE: f32 = 5.0;
+
+pub struct LightningEntity {
+    entity: Entity,
+    ticks_left: AtomicU8,
+}
+
+impl LightningEntity {
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            ticks_left: AtomicU8::new(LIFETIME),
+        }
+    }
+}
+
+#[async_trait]
+impl EntityBase for LightningEntity {
+    async fn tick(&self, _server: &Server) {
+        if self.ticks_left.fetch_sub(1, Relaxed) <= 1 {
+            self.entity.remove().await;
+        }
+    }
+
+    async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
+        false
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+}
+
+/// The mob a mob struck by lightning turns into, like vanilla.
+pub fn struck_conversion(entity_type: EntityType) -> Option<EntityType> {
+    match entity_type {
+        EntityType::PIG => Some(EntityType::ZOMBIFIED_PIGLIN),
+        EntityType::VILLAGER => Some(EntityType::WITCH),
+        _ => None,
+    }
+}
+
+/// The area in which entities get hit by a bolt striking `pos`.
+pub fn strike_area(pos: Vector3<f64>) -> BoundingBox {
+    BoundingBox::new(pos, pos)
+        .expand(3.0, 3.0, 3.0)
+        .offset(BoundingBox::new(
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.0, 6.0, 0.0),
+        ))
+}
+
+/// Strikes lightning at `pos`: spawns the bolt, plays the thunder, sets the ground on fire, hurts
+/// the entities nearby and converts the mobs which react to lightning.
+pub async fn strike(world: &Arc<World>, pos: Vector3<f64>) {
+    let bolt = Arc::new(LightningEntity::new(
+        world.create_entity(pos, EntityType::LIGHTNING_BOLT),
+    ));
+    world.spawn_entity(bolt.clone()).await;
+    world
+        .play_sound(
+            Sound::EntityLightningBoltThunder,
+            SoundCategory::Weather,
+            &pos,
+        )
+        .await;
+    world
+        .play_sound(
+            Sound::EntityLightningBoltImpact,
+            SoundCategory::Weather,
+            &pos,
+        )
+        .await;
+
+    let block_pos = BlockPos::floored(pos.x, pos.y, pos.z);
+    if FireBlockBase::can_place_at(world, &block_pos).await {
+        let fire = FireBlockBase::get_fire_type(world, &block_pos).await;
+        world
+            .set_block_state(&block_pos, fire.default_state_id, BlockFlags::NOTIFY_ALL)
+            .await;
+    }
+
+    let area = strike_area(pos);
+    let entities: Vec<_> = world.entities.read().await.values().cloned().collect();
+    for entity in entities {
+        let base = entity.get_entity();
+        if base.entity_id == bolt.entity.entity_id || !base.bounding_box.load().intersects(&area) {
+            continue;
+        }
+        if let Some(converted) = struck_conversion(base.entity_type) {
+            let new_mob = mob::from_type(converted, base.pos.load(), world).await;
+            new_mob
+                .get_entity()
+                .set_rotation(base.yaw.load(), base.pitch.load());
+            base.remove().await;
+            world.spawn_entity(new_mob).await;
+            continue;
+        }
+        if let Some(mob) = entity.get_mob_entity() {
+            mob.charge().await;
+        }
+        entity.damage(DAMAGE, DamageType::LIGHTNING_BOLT).await;
+    }
+    let players: Vec<_> = world.players.read().await.values().cloned().collect();
+    for player in players {
+        if player
+            .living_entity
+            .entity
+            .bounding_box
+            .load()
+            .intersects(&area)
+        {
+            player.damage(DAMAGE, DamageType::LIGHTNING_BOLT).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_util::math::{boundingbox::BoundingBox, vector3::Vector3};
+
+    use super::{strike_area, struck_conversion};
+
+    #[test]
+    fn struck_pig_turns_into_zombified_piglin() {
+        assert_eq!(
+            struck_conversion(EntityType::PIG).map(|entity| entity.id),
+            Some(EntityType::ZOMBIFIED_PIGLIN.id)
+        );
+        assert_eq!(
+            struck_conversion(EntityType::VILLAGER).map(|entity| entity.id),
+            Some(EntityType::WITCH.id)
+        );
+        assert!(struck_conversion(EntityType::COW).is_none());
+    }
+
+    #[test]
+    fn strike_hits_nearby_entities_only() {
+        let strike = Vector3::new(0.5, 64.0, 0.5);
+        let area = strike_area(strike);
+        let nearby = BoundingBox::new(Vector3::new(2.0, 64.0, 2.0), Vector3::new(2.9, 65.8, 2.9));
+        let far = BoundingBox::new(Vector3::new(5.0, 64.0, 5.0), Vector3::new(5.9, 65.8, 5.9));
+        assert!(nearby.intersects(&area));
+        assert!(!far.intersects(&area));
+    }
+}
@@ -2,16 +2,19 @@ use std::sync::atomic::{AtomicU8, Ordering::Relaxed};
 use std::{collections::HashMap, sync::atomic::AtomicI32};
 
 use super::EntityBase;
+use super::attributes::{AttributeMap, AttributeModifier};
+use super::player::Player;
 use super::{Entity, EntityId, NBTStorage, effect::Effect};
 use crate::server::Server;
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
 use pumpkin_config::advanced_config;
 use pumpkin_data::Block;
-use pumpkin_data::entity::{EffectType, EntityStatus};
+use pumpkin_data::entity::{Attribute, EffectType, EntityStatus};
+use pumpkin_data::tag::{RegistryKey, get_tag_values};
 use pumpkin_data::{damage::DamageType, sound::Sound};
 use pumpkin_nbt::tag::NbtTag;
-use pumpkin_protocol::client::play::{CHurtAnimation, CTakeItemEntity};
+use pumpkin_protocol::client::play::{CHurtAnimation, CTakeItemEntity, CUpdateAttributes};
 use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::{
     client::play::{CDamageEvent, CSetEquipment, EquipmentSlot, MetaDataType, Metadata},
@@ -21,6 +24,9 @@ use pumpkin_util::math::vector3::Vector3;
 use pumpkin_world::item::ItemStack;
 use tokio::sync::Mutex;
 
+/// How hard entities are pushed away from whatever damaged them
+const DAMAGE_KNOCKBACK: f64 = 0.4;
+
 /// Represents a living entity within the game world.
 ///
 /// This struct encapsulates the core properties and behaviors of living entities, including players, mobs, and other creatures.
@@ -39,50 +45,150 @@ pub struct LivingEntity {
     /// The distance the entity has been falling.
     pub fall_distance: AtomicCell<f32>,
     pub active_effects: Mutex<HashMap<EffectType, Effect>>,
+    /// Max health, movement speed, attack damage, etc.
+    pub attributes: Mutex<AttributeMap>,
 }
 impl LivingEntity {
     pub fn new(entity: Entity) -> Self {
         let pos = entity.pos.load();
+        let attributes = AttributeMap::new(entity.entity_type);
+        let max_health = attributes.value(Attribute::MaxHealth) as f32;
         Self {
             entity,
             last_pos: AtomicCell::new(pos),
             time_until_regen: AtomicI32::new(0),
             last_damage_taken: AtomicCell::new(0.0),
-            health: AtomicCell::new(20.0),
+            health: AtomicCell::new(max_health),
             fall_distance: AtomicCell::new(0.0),
             death_time: AtomicU8::new(0),
             active_effects: Mutex::new(HashMap::new()),
+            attributes: Mutex::new(attributes),
         }
     }
 
+    /// The value of the attribute with all modifiers applied.
+    pub async fn get_attribute_value(&self, attribute: Attribute) -> f64 {
+        self.attributes.lock().await.value(attribute)
+    }
+
+    pub async fn max_health(&self) -> f32 {
+        self.get_attribute_value(Attribute::MaxHealth).await as f32
+    }
+
+    pub async fn set_attribute_base(&self, attribute: Attribute, base: f64) {
+        self.attributes.lock().await.set_base_value(attribute, base);
+        self.on_attribute_changed(attribute).await;
+    }
+
+    /// Returns `false` if the attribute already has a modifier with the same id.
+    pub async fn add_attribute_modifier(
+        &self,
+        attribute: Attribute,
+        modifier: AttributeModifier,
+    ) -> bool {
+        let added = self
+            .attributes
+            .lock()
+            .await
+            .add_modifier(attribute, modifier);
+        if added {
+            self.on_attribute_changed(attribute).await;
+        }
+        added
+    }
+
+    /// Returns `false` if the attribute has no modifier with this id.
+    pub async fn remove_attribute_modifier(&self, attribute: Attribute, id: &str) -> bool {
+        let removed = self.attributes.lock().await.remove_modifier(attribute, id);
+        if removed {
+            self.on_attribute_changed(attribute).await;
+        }
+        removed
+    }
+
+    async fn on_attribute_changed(&self, attribute: Attribute) {
+        let property = self.attributes.lock().await.to_property(attribute);
+        self.entity
+            .world
+            .read()
+            .await
+            .broadcast_to_trackers(
+                self.entity_id(),
+                &CUpdateAttributes::new(self.entity_id().into(), vec![property]),
+            )
+            .await;
+        if attribute == Attribute::MaxHealth {
+            let max_health = self.max_health().await;
+            if self.health.load() > max_health {
+                self.set_health(max_health).await;
+            }
+        }
+    }
+
+    /// Sends the attributes which differ from their defaults, e.g. when the player starts
+    /// tracking the entity.
+    pub async fn send_attributes_to(&self, player: &Player) {
+        let properties = self.attributes.lock().await.changed_properties();
+        if properties.is_empty() {
+            return;
+        }
+        player
+            .client
+            .enqueue_packet(&CUpdateAttributes::new(self.entity_id().into(), properties))
+            .await;
+    }
+
     pub async fn send_equipment_changes(&self, equipment: &[(EquipmentSlot, ItemStack)]) {
-        let equipment: Vec<(EquipmentSlot, ItemStackSerializer)> = equipment
-            .iter()
-            .map(|(slot, stack)| (*slot, ItemStackSerializer::from(stack.clone())))
-            .collect();
         self.entity
             .world
             .read()
             .await
-            .broadcast_packet_except(
+            .broadcast_to_trackers_except(
+                self.entity_id(),
                 &[self.entity.entity_uuid],
-                &CSetEquipment::new(self.entity_id().into(), equipment),
+                &self.equipment_packet(equipment),
             )
             .await;
     }
 
+    /// Sends the given equipment only to the given player, e.g. because they just started
+    /// tracking us.
+    pub async fn send_equipment_to(
+        &self,
+        player: &Player,
+        equipment: &[(EquipmentSlot, ItemStack)],
+    ) {
+        if equipment.is_empty() {
+            return;
+        }
+        player
+            .client
+            .enqueue_packet(&self.equipment_packet(equipment))
+            .await;
+    }
+
+    fn equipment_packet(&self, equipment: &[(EquipmentSlot, ItemStack)]) -> CSetEquipment {
+        let equipment: Vec<(EquipmentSlot, ItemStackSerializer)> = equipment
+            .iter()
+            .map(|(slot, stack)| (*slot, ItemStackSerializer::from(stack.clone())))
+            .collect();
+        CSetEquipment::new(self.entity_id().into(), equipment)
+    }
+
     /// Picks up and Item entity or XP Orb
     pub async fn pickup(&self, item: &Entity, stack_amount: u32) {
-        // TODO: Only nearby
         self.entity
             .world
             .read()
             .await
-            .broadcast_packet_all(&CTakeItemEntity::new(
-                item.entity_id.into(),
-                self.entity.entity_id.into(),
-                stack_amount.try_into().unwrap(),
-            ))
+            .broadcast_to_trackers(
+                item.entity_id,
+                &CTakeItemEntity::new(
+                    item.entity_id.into(),
+                    self.entity.entity_id.into(),
+                    stack_amount.try_into().unwrap(),
+                ),
+            )
             .await;
     }
 
@@ -97,7 +203,9 @@ impl LivingEntity {
             .await;
     }
 
+    /// Sets the health, capped by the max health attribute.
     pub async fn set_health(&self, health: f32) {
+        let health = self.attributes.lock().await.cap_health(health);
         self.health.store(health);
         // tell everyone entities health changed
         self.entity
@@ -105,11 +213,22 @@ impl LivingEntity {
             .await;
     }
 
+    /// Sends the health only to the given player, e.g. because they just started tracking us.
+    pub async fn send_health_to(&self, player: &Player) {
+        self.entity
+            .send_meta_data_to(
+                player,
+                &[Metadata::new(9, MetaDataType::Float, self.health.load())],
+            )
+            .await;
+    }
+
     pub const fn entity_id(&self) -> EntityId {
         self.entity.entity_id
     }
 
-    pub async fn damage_with_context(
+    /// Deals the damage without any invulnerability frames, the hurt animation or knockback.
+    pub async fn apply_damage(
         &self,
         amount: f32,
         damage_type: DamageType,
@@ -121,21 +240,29 @@ impl LivingEntity {
         if self.entity.is_invulnerable_to(&damage_type) {
             return false;
         }
+        let amount = if bypasses_armor(damage_type) {
+            amount
+        } else {
+            self.attributes.lock().await.damage_after_armor(amount)
+        };
 
         self.entity
             .world
             .read()
             .await
-            .broadcast_packet_all(&CDamageEvent::new(
-                self.entity.entity_id.into(),
-                damage_type.id.into(),
-                source.map(|e| e.entity_id.into()),
-                cause.map(|e| e.entity_id.into()),
-                position,
-            ))
+            .broadcast_to_trackers(
+                self.entity.entity_id,
+                &CDamageEvent::new(
+                    self.entity.entity_id.into(),
+                    damage_type.id.into(),
+                    source.map(|e| e.entity_id.into()),
+                    cause.map(|e| e.entity_id.into()),
+                    position,
+                ),
+            )
             .await;
 
-        let new_health = (self.health.load() - amount).max(0.0);
+        let new_health = health_after_damage(self.health.load(), amount);
 
         if new_health == 0.0 {
             self.kill().await;
@@ -213,9 +340,17 @@ impl LivingEntity {
                 return;
             }
 
-            let safe_fall_distance = 3.0;
-            let mut damage = fall_distance - safe_fall_distance;
-            damage = (damage).ceil();
+            let (safe_fall_distance, damage_multiplier) = {
+                let attributes = self.attributes.lock().await;
+                (
+                    attributes.value(Attribute::SafeFallDistance) as f32,
+                    attributes.value(Attribute::FallDamageMultiplier) as f32,
+                )
+            };
+            let damage = ((fall_distance - safe_fall_distance) * damage_multiplier).ceil();
+            if damage <= 0.0 {
+                return;
+            }
 
             // TODO: Play block fall sound
             let check_damage = self.damage(damage, DamageType::FALL).await; // Fall
@@ -288,6 +423,20 @@ impl LivingEntity {
     }
 }
 
+/// Whether armor doesn't protect against the damage type, like falling or drowning.
+fn bypasses_armor(damage_type: DamageType) -> bool {
+    get_tag_values(RegistryKey::DamageType, "minecraft:bypasses_armor")
+        .unwrap_or(&[])
+        .iter()
+        .filter_map(|name| DamageType::from_name(name))
+        .any(|bypassing| bypassing.id == damage_type.id)
+}
+
+/// The health left after taking `amount` damage, dead entities have none.
+pub(super) fn health_after_damage(health: f32, amount: f32) -> f32 {
+    (health - amount).max(0.0)
+}
+
 #[async_trait]
 impl EntityBase for LivingEntity {
     async fn tick(&self, server: &Server) {
@@ -314,6 +463,18 @@ impl EntityBase for LivingEntity {
         }
     }
     async fn damage(&self, amount: f32, damage_type: DamageType) -> bool {
+        self.damage_with_context(amount, damage_type, None, None, None)
+            .await
+    }
+
+    async fn damage_with_context(
+        &self,
+        amount: f32,
+        damage_type: DamageType,
+        position: Option<Vector3<f64>>,
+        source: Option<&Entity>,
+        cause: Option<&Entity>,
+    ) -> bool {
         let world = self.entity.world.read().await;
         if !self.check_damage(amount) {
             return false;
@@ -321,7 +482,7 @@ impl EntityBase for LivingEntity {
         let config = &advanced_config().pvp;
 
         if !self
-            .damage_with_context(amount, damage_type, None, None, None)
+            .apply_damage(amount, damage_type, position, source, cause)
             .await
         {
             return false;
@@ -330,11 +491,26 @@ impl EntityBase for LivingEntity {
         if config.hurt_animation {
             let entity_id = VarInt(self.entity.entity_id);
             world
-                .broadcast_packet_all(&CHurtAnimation::new(entity_id, self.entity.yaw.load()))
+                .broadcast_to_trackers(
+                    self.entity.entity_id,
+                    &CHurtAnimation::new(entity_id, self.entity.yaw.load()),
+                )
+                .await;
+        }
+        drop(world);
+        // Push the entity away from whatever hurt it
+        if let Some(from) = source.map(|source| source.pos.load()).or(position) {
+            let pos = self.entity.pos.load();
+            self.entity
+                .knockback(DAMAGE_KNOCKBACK, from.x - pos.x, from.z - pos.z)
                 .await;
         }
         true
     }
+    fn nbt_storage(&self) -> &dyn NBTStorage {
+        self
+    }
+
     fn get_entity(&self) -> &Entity {
         &self.entity
     }
@@ -349,11 +525,13 @@ impl NBTStorage for LivingEntity {
     async fn write_nbt(&self, nbt: &mut pumpkin_nbt::compound::NbtCompound) {
         self.entity.write_nbt(nbt).await;
         nbt.put("Health", NbtTag::Float(self.health.load()));
+        self.attributes.lock().await.write_nbt(nbt);
         // todo more...
     }
 
     async fn read_nbt(&mut self, nbt: &mut pumpkin_nbt::compound::NbtCompound) {
         self.entity.read_nbt(nbt).await;
+        self.attributes.get_mut().read_nbt(nbt);
         self.health.store(nbt.get_float("Health").unwrap_or(0.0));
         // todo more...
     }
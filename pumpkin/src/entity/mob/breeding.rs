@@ -0,0 +1,188 @@
+use std::sync::atomic::{AtomicI32, Ordering::Relaxed};
+
+use pumpkin_data::entity::EntityType;
+use pumpkin_data::item::Item;
+use pumpkin_data::tag::Tagable;
+use pumpkin_nbt::compound::NbtCompound;
+
+/// The age of a newborn baby, it grows up once its age reaches 0.
+pub const BABY_AGE: i32 = -24000;
+/// How long parents have to wait before they can breed again.
+pub const BREED_COOLDOWN: i32 = 6000;
+/// How long an animal stays in love mode after it was fed.
+pub const LOVE_TICKS: i32 = 600;
+
+/// What happened when an animal was fed its breeding item.
+#[derive(Debug, PartialEq, Eq)]
+pub enum FeedResult {
+    /// An adult entered love mode
+    InLove,
+    /// A baby grew up a bit
+    Grew,
+    /// The animal is in love already or still has to cool down from breeding
+    Ignored,
+}
+
+/// The age and love mode of an animal which can breed.
+pub struct Breeding {
+    /// Negative while it is a baby, counting up until it grows up.
+    /// Positive while it has to cool down from breeding, counting down to 0.
+    age: AtomicI32,
+    /// The ticks left in love mode
+    love_ticks: AtomicI32,
+    /// The item tag of the items it can be bred with
+    food_tag: &'static str,
+}
+
+impl Breeding {
+    /// The breeding of an adult of the given type, if it can be bred.
+    pub fn for_type(entity_type: EntityType) -> Option<Self> {
+        let food_tag = match entity_type {
+            EntityType::COW | EntityType::MOOSHROOM => "#minecraft:cow_food",
+            EntityType::SHEEP => "#minecraft:sheep_food",
+            EntityType::PIG => "#minecraft:pig_food",
+            EntityType::CHICKEN => "#minecraft:chicken_food",
+            EntityType::RABBIT => "#minecraft:rabbit_food",
+            EntityType::GOAT => "#minecraft:goat_food",
+            EntityType::HOGLIN => "#minecraft:hoglin_food",
+            EntityType::ARMADILLO => "#minecraft:armadillo_food",
+            EntityType::STRIDER => "#minecraft:strider_food",
+            _ => return None,
+        };
+        Some(Self {
+            age: AtomicI32::new(0),
+            love_ticks: AtomicI32::new(0),
+            food_tag,
+        })
+    }
+
+    pub fn age(&self) -> i32 {
+        self.age.load(Relaxed)
+    }
+
+    pub fn set_age(&self, age: i32) {
+        self.age.store(age, Relaxed);
+    }
+
+    pub fn is_baby(&self) -> bool {
+        self.age() < 0
+    }
+
+    pub fn is_in_love(&self) -> bool {
+        self.love_ticks.load(Relaxed) > 0
+    }
+
+    pub fn is_breeding_item(&self, item: &Item) -> bool {
+        item.is_tagged_with(self.food_tag).unwrap_or(false)
+    }
+
+    /// Feeds the animal its breeding item, adults fall in love and babies grow up faster.
+    pub fn feed(&self) -> FeedResult {
+        let age = self.age();
+        if age < 0 {
+            // Each feeding takes a tenth off the remaining time
+            self.set_age(age + -age / 10);
+            FeedResult::Grew
+        } else if age == 0 && !self.is_in_love() {
+            self.love_ticks.store(LOVE_TICKS, Relaxed);
+            FeedResult::InLove
+        } else {
+            FeedResult::Ignored
+        }
+    }
+
+    /// Ages the animal by a tick and returns whether it just grew up.
+    pub fn tick(&self) -> bool {
+        if self.love_ticks.load(Relaxed) > 0 {
+            self.love_ticks.fetch_sub(1, Relaxed);
+        }
+        match self.age() {
+            0 => false,
+            age if age < 0 => {
+                self.set_age(age + 1);
+                age + 1 == 0
+            }
+            age => {
+                self.set_age(age - 1);
+                false
+            }
+        }
+    }
+
+    /// Whether both animals are adults in love, ready to make a baby.
+    pub fn can_breed_with(&self, partner: &Self) -> bool {
+        self.food_tag == partner.food_tag
+            && self.is_in_love()
+            && partner.is_in_love()
+            && self.age() == 0
+            && partner.age() == 0
+    }
+
+    /// Ends the love mode of both parents, lets them cool down and returns the baby's breeding.
+    pub fn breed_with(&self, partner: &Self) -> Self {
+        for parent in [self, partner] {
+            parent.love_ticks.store(0, Relaxed);
+            parent.set_age(BREED_COOLDOWN);
+        }
+        Self {
+            age: AtomicI32::new(BABY_AGE),
+            love_ticks: AtomicI32::new(0),
+            food_tag: self.food_tag,
+        }
+    }
+
+    pub fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put_int("Age", self.age());
+        nbt.put_int("InLove", self.love_ticks.load(Relaxed));
+    }
+
+    pub fn read_nbt(&self, nbt: &NbtCompound) {
+        self.set_age(nbt.get_int("Age").unwrap_or(0));
+        self.love_ticks
+            .store(nbt.get_int("InLove").unwrap_or(0), Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_data::item::Item;
+
+    use super::{BABY_AGE, BREED_COOLDOWN, Breeding, FeedResult};
+
+    #[test]
+    fn feeding_two_adults_makes_a_baby() {
+        let cow = Breeding::for_type(EntityType::COW).unwrap();
+        let other = Breeding::for_type(EntityType::COW).unwrap();
+        assert!(cow.is_breeding_item(&Item::WHEAT));
+        assert!(!cow.is_breeding_item(&Item::CARROT));
+        assert!(!cow.can_breed_with(&other));
+
+        assert_eq!(cow.feed(), FeedResult::InLove);
+        assert_eq!(other.feed(), FeedResult::InLove);
+        assert!(cow.can_breed_with(&other));
+
+        let baby = cow.breed_with(&other);
+        assert!(baby.is_baby());
+        assert_eq!(baby.age(), BABY_AGE);
+        assert_eq!(cow.age(), BREED_COOLDOWN);
+        assert_eq!(other.age(), BREED_COOLDOWN);
+        assert!(!cow.is_in_love());
+        // The cooldown keeps them from falling in love again
+        assert_eq!(cow.feed(), FeedResult::Ignored);
+    }
+
+    #[test]
+    fn babies_grow_up() {
+        let cow = Breeding::for_type(EntityType::COW).unwrap();
+        let baby = cow.breed_with(&Breeding::for_type(EntityType::COW).unwrap());
+
+        assert_eq!(baby.feed(), FeedResult::Grew);
+        assert_eq!(baby.age(), BABY_AGE - BABY_AGE / 10);
+
+        baby.set_age(-2);
+        assert!(!baby.tick());
+        assert!(baby.tick());
+        assert!(!baby.is_baby());
+    }
+}
@@ -0,0 +1,197 @@
+use pumpkin_data::{entity::EntityType, item::Item};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::{
+    client::play::{MetaDataType, Metadata},
+    codec::var_int::VarInt,
+};
+use pumpkin_world::{
+    entity::entity_data_flags::{DATA_IS_POWERED, DATA_SWELL_DIR},
+    item::ItemStack,
+};
+use rand::Rng;
+
+/// How many ticks a creeper swells before it explodes. Vanilla: 30
+pub const FUSE_TICKS: i32 = 30;
+/// The power of a creeper's explosion, charged creepers explode twice as strong. Vanilla: 3
+const EXPLOSION_POWER: f32 = 3.0;
+/// How close a player has to get for a creeper to start swelling. Vanilla: 3
+pub const SWELL_START_DISTANCE: f64 = 3.0;
+/// How far a player has to get away for a swelling creeper to calm down again. Vanilla: 7
+pub const SWELL_STOP_DISTANCE: f64 = 7.0;
+/// How far creepers keep away from cats and ocelots
+pub const SCARED_DISTANCE: f64 = 6.0;
+/// The mobs creepers are scared of
+pub const SCARED_OF: [EntityType; 2] = [EntityType::CAT, EntityType::OCELOT];
+
+/// What happened to a creeper's fuse in a tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FuseTick {
+    Idle,
+    /// The creeper started swelling and hisses
+    Primed,
+    Swelling,
+    Exploded,
+}
+
+/// The fuse and charge of creepers.
+pub struct Creeper {
+    /// How many ticks the creeper has been swelling for
+    swell: i32,
+    /// 1 while swelling, -1 while calming down
+    swell_dir: i32,
+    /// Set by lightning, charged creepers explode stronger and make their victims drop heads
+    pub charged: bool,
+    /// Ignited by flint and steel, the creeper swells no matter where players are
+    pub ignited: bool,
+}
+
+impl Creeper {
+    pub fn for_type(entity_type: EntityType) -> Option<Self> {
+        (entity_type == EntityType::CREEPER).then_some(Self {
+            swell: 0,
+            swell_dir: -1,
+            charged: false,
+            ignited: false,
+        })
+    }
+
+    pub const fn is_swelling(&self) -> bool {
+        self.swell_dir > 0
+    }
+
+    /// Whether a creeper with a target this far away swells, returns whether that changed.
+    pub fn aim(&mut self, target_distance_squared: Option<f64>) -> bool {
+        let swell_dir = match target_distance_squared {
+            Some(distance_squared) if self.is_swelling() => {
+                if distance_squared > SWELL_STOP_DISTANCE * SWELL_STOP_DISTANCE {
+                    -1
+                } else {
+                    1
+                }
+            }
+            Some(distance_squared)
+                if distance_squared < SWELL_START_DISTANCE * SWELL_START_DISTANCE =>
+            {
+                1
+            }
+            _ => -1,
+        };
+        let changed = swell_dir != self.swell_dir;
+        self.swell_dir = swell_dir;
+        changed
+    }
+
+    /// Burns the fuse down while swelling, or lets it recover after backing off.
+    pub fn tick(&mut self) -> FuseTick {
+        if self.ignited {
+            self.swell_dir = 1;
+        }
+        let primed = self.swell_dir > 0 && self.swell == 0;
+        self.swell = (self.swell + self.swell_dir).max(0);
+        if self.swell >= FUSE_TICKS {
+            self.swell = FUSE_TICKS;
+            return FuseTick::Exploded;
+        }
+        if primed {
+            FuseTick::Primed
+        } else if self.swell > 0 {
+            FuseTick::Swelling
+        } else {
+            FuseTick::Idle
+        }
+    }
+
+    pub fn explosion_power(&self) -> f32 {
+        if self.charged {
+            EXPLOSION_POWER * 2.0
+        } else {
+            EXPLOSION_POWER
+        }
+    }
+
+    /// The head a mob killed by a charged creeper drops.
+    pub fn head_of(entity_type: EntityType) -> Option<Item> {
+        Some(match entity_type {
+            EntityType::ZOMBIE => Item::ZOMBIE_HEAD,
+            EntityType::SKELETON => Item::SKELETON_SKULL,
+            EntityType::WITHER_SKELETON => Item::WITHER_SKELETON_SKULL,
+            EntityType::CREEPER => Item::CREEPER_HEAD,
+            EntityType::PIGLIN => Item::PIGLIN_HEAD,
+            _ => return None,
+        })
+    }
+
+    /// The gunpowder dropped on death, sometimes there is none.
+    pub fn loot(rng: &mut impl Rng) -> Option<ItemStack> {
+        let count = rng.gen_range(0..=2);
+        (count > 0).then(|| ItemStack::new(count, Item::GUNPOWDER))
+    }
+
+    pub fn swell_meta_data(&self) -> Metadata<VarInt> {
+        Metadata::new(
+            DATA_SWELL_DIR,
+            MetaDataType::Integer,
+            VarInt(self.swell_dir),
+        )
+    }
+
+    pub fn charged_meta_data(&self) -> Metadata<bool> {
+        Metadata::new(DATA_IS_POWERED, MetaDataType::Boolean, self.charged)
+    }
+
+    pub fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put_bool("powered", self.charged);
+        nbt.put_short("Fuse", FUSE_TICKS as i16);
+        nbt.put_byte("ExplosionRadius", EXPLOSION_POWER as i8);
+        nbt.put_bool("ignited", self.ignited);
+    }
+
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        self.charged = nbt.get_bool("powered").unwrap_or(false);
+        self.ignited = nbt.get_bool("ignited").unwrap_or(false);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::entity::EntityType;
+
+    use super::{Creeper, FUSE_TICKS, FuseTick};
+
+    #[test]
+    fn creeper_explodes_after_fuse_unless_player_backs_off() {
+        let mut creeper = Creeper::for_type(EntityType::CREEPER).unwrap();
+        // Too far away to care
+        assert!(!creeper.aim(Some(16.0)));
+        assert_eq!(creeper.tick(), FuseTick::Idle);
+
+        // Within three blocks, the creeper hisses and swells
+        assert!(creeper.aim(Some(4.0)));
+        assert_eq!(creeper.tick(), FuseTick::Primed);
+        for _ in 0..10 {
+            assert_eq!(creeper.tick(), FuseTick::Swelling);
+        }
+
+        // Backing off a little isn't enough, running away calms it down again
+        assert!(!creeper.aim(Some(36.0)));
+        assert!(creeper.aim(Some(64.0)));
+        let ticks_to_calm = (0..)
+            .take_while(|_| creeper.tick() != FuseTick::Idle)
+            .count();
+        assert_eq!(ticks_to_calm, 10);
+
+        // Staying close, it explodes after the fuse burned down
+        creeper.aim(Some(1.0));
+        let ticks = (1..=FUSE_TICKS)
+            .find(|_| creeper.tick() == FuseTick::Exploded)
+            .unwrap();
+        assert_eq!(ticks, FUSE_TICKS);
+        assert_eq!(creeper.explosion_power(), 3.0);
+        creeper.charged = true;
+        assert_eq!(creeper.explosion_power(), 6.0);
+
+        assert!(Creeper::for_type(EntityType::ZOMBIE).is_none());
+        assert!(Creeper::head_of(EntityType::ZOMBIE).is_some());
+        assert!(Creeper::head_of(EntityType::COW).is_none());
+    }
+}
@@ -1,45 +1,227 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use pumpkin_data::entity::EntityType;
+use breeding::{Breeding, FeedResult};
+use creeper::{Creeper, FuseTick, SCARED_DISTANCE, SCARED_OF};
+use pumpkin_data::Block;
+use pumpkin_data::damage::DamageType;
+use pumpkin_data::entity::{Attribute, EntityStatus, EntityType};
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::client::play::{CLinkEntities, CUpdateEntityPos, MetaDataType, Metadata};
+use pumpkin_util::GameMode;
+use pumpkin_util::math::boundingbox::EntityDimensions;
+use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::block::BlockDirection;
+use pumpkin_world::item::ItemStack;
+use rand::Rng;
+use squid::{DRY_OUT_DAMAGE, INK_CLOUD_RADIUS, Squid};
 use tokio::sync::Mutex;
+use villager::{Villager, WORK_HOURS};
 use zombie::Zombie;
 
-use crate::{server::Server, world::World};
+use crate::{
+    block::drop_stack,
+    server::Server,
+    world::{
+        World,
+        leashing::{LEASH_BREAK_DISTANCE, LEASH_PULL_DISTANCE},
+    },
+};
 
 use super::{
-    Entity, EntityBase,
-    ai::{goal::Goal, path::Navigator},
+    Entity, EntityBase, NBTStorage,
+    ai::{
+        goal::{
+            Goal, creeper_swell::CreeperSwellGoal, flee_entity::FleeEntityGoal,
+            goal_selector::GoalSelector, look_around::LookAroundGoal,
+            look_at_player::LookAtPlayerGoal, random_stroll::RandomStrollGoal,
+            target_goal::TargetGoal,
+        },
+        path::navigation::EntityNavigation,
+    },
+    collision,
+    effect::Effect,
+    experience_orb::ExperienceOrbEntity,
     living::LivingEntity,
+    player::Player,
 };
 
+pub mod breeding;
+pub mod creeper;
+pub mod squid;
+pub mod villager;
 pub mod zombie;
 
+/// How far animals in love look for a partner.
+const PARTNER_SEARCH_DISTANCE: f64 = 8.0;
+/// How close partners have to get to breed.
+const BREEDING_DISTANCE: f64 = 3.0;
+/// About how many blocks a mob walks per tick for each point of its movement speed.
+const WALK_SPEED_PER_MOVEMENT_SPEED: f64 = 0.43;
+/// How many ticks pass between villagers checking on their workstation
+const JOB_SITE_CHECK_INTERVAL: i64 = 100;
+/// How far villagers look for a workstation, horizontally and vertically
+const JOB_SITE_SEARCH_RADIUS: (i32, i32) = (8, 4);
+/// How close a villager has to be to its workstation to work at it
+const WORKING_DISTANCE: f64 = 2.0;
+/// How far squids notice the player who hurt them
+const SQUID_THREAT_DISTANCE: f64 = 16.0;
+
 pub struct MobEntity {
     pub living_entity: LivingEntity,
-    pub goals: Mutex<Vec<(Arc<dyn Goal>, bool)>>,
-    pub navigator: Mutex<Navigator>,
+    pub goals: Mutex<GoalSelector>,
+    /// Walks the mob along paths around obstacles
+    pub navigation: Mutex<EntityNavigation>,
+    /// The age and love mode of mobs which can be bred
+    pub breeding: Option<Breeding>,
+    /// The profession and trades of villagers
+    pub villager: Option<Mutex<Villager>>,
+    /// The swimming and ink of squids and glow squids
+    pub squid: Option<Mutex<Squid>>,
+    /// The fuse and charge of creepers
+    pub creeper: Option<Mutex<Creeper>>,
 }
 
 #[async_trait]
 impl EntityBase for MobEntity {
     async fn tick(&self, server: &Server) {
         self.living_entity.tick(server).await;
-        let mut goals = self.goals.lock().await;
-        for (goal, running) in goals.iter_mut() {
-            if *running {
-                if goal.should_continue(self).await {
-                    goal.tick(self).await;
-                } else {
-                    *running = false;
+        collision::tick_collisions(&self.living_entity).await;
+        self.goals.lock().await.tick(self).await;
+        self.tick_breeding().await;
+        self.tick_villager().await;
+        self.tick_squid().await;
+        self.tick_creeper(server).await;
+        self.tick_leash().await;
+        self.navigation.lock().await.tick(&self.living_entity).await;
+    }
+
+    async fn damage(&self, amount: f32, damage_type: DamageType) -> bool {
+        self.damage_with_context(amount, damage_type, None, None, None)
+            .await
+    }
+
+    async fn damage_with_context(
+        &self,
+        amount: f32,
+        damage_type: DamageType,
+        position: Option<Vector3<f64>>,
+        source: Option<&Entity>,
+        cause: Option<&Entity>,
+    ) -> bool {
+        let was_alive = self.living_entity.health.load() > 0.0;
+        if !self
+            .living_entity
+            .damage_with_context(amount, damage_type, position, source, cause)
+            .await
+        {
+            return false;
+        }
+        // Squids ink when something hurts them, not when they dry out
+        if let Some(squid) = &self.squid {
+            if damage_type.id != DamageType::DROWN.id {
+                self.release_ink(squid).await;
+            }
+        }
+        if was_alive && self.living_entity.health.load() <= 0.0 {
+            self.drop_loot().await;
+        }
+        true
+    }
+
+    async fn interact(&self, player: &Player) -> bool {
+        if self.interact_leash(player).await {
+            return true;
+        }
+        let Some(breeding) = &self.breeding else {
+            return false;
+        };
+        {
+            let mut inventory = player.inventory().lock().await;
+            let Some(held) = inventory.held_item() else {
+                return false;
+            };
+            if !breeding.is_breeding_item(&held.item) {
+                return false;
+            }
+            match breeding.feed() {
+                FeedResult::Ignored => return false,
+                FeedResult::InLove => {
+                    self.living_entity
+                        .entity
+                        .world
+                        .read()
+                        .await
+                        .send_entity_status(
+                            &self.living_entity.entity,
+                            EntityStatus::AddBreedingParticles,
+                        )
+                        .await;
                 }
-            } else {
-                *running = goal.can_start(self).await;
+                FeedResult::Grew => (),
+            }
+            if player.gamemode.load() != GameMode::Creative {
+                inventory.decrease_current_stack(1);
             }
         }
-        let mut navigator = self.navigator.lock().await;
-        navigator.tick(&self.living_entity).await;
+        player.send_inventory().await;
+        true
+    }
+
+    async fn send_tracking_data(&self, player: &Player) {
+        self.living_entity.send_attributes_to(player).await;
+        let entity = &self.living_entity.entity;
+        let holder = entity
+            .world
+            .read()
+            .await
+            .leashes
+            .lock()
+            .await
+            .holder_of(entity.entity_id);
+        if let Some(holder) = holder {
+            player
+                .client
+                .enqueue_packet(&CLinkEntities::new(entity.entity_id, holder))
+                .await;
+        }
+        if self.breeding.as_ref().is_some_and(Breeding::is_baby) {
+            self.living_entity
+                .entity
+                .send_meta_data_to(player, &[Self::baby_meta_data(true)])
+                .await;
+        }
+        if let Some(villager) = &self.villager {
+            let meta_data = villager.lock().await.meta_data();
+            self.living_entity
+                .entity
+                .send_meta_data_to(player, &[meta_data])
+                .await;
+        }
+        if let Some(squid) = &self.squid {
+            if let Some(meta_data) = squid.lock().await.meta_data() {
+                self.living_entity
+                    .entity
+                    .send_meta_data_to(player, &[meta_data])
+                    .await;
+            }
+        }
+        if let Some(creeper) = &self.creeper {
+            let (swell, charged) = {
+                let creeper = creeper.lock().await;
+                (creeper.swell_meta_data(), creeper.charged_meta_data())
+            };
+            let entity = &self.living_entity.entity;
+            entity.send_meta_data_to(player, &[swell]).await;
+            entity.send_meta_data_to(player, &[charged]).await;
+        }
+    }
+
+    fn nbt_storage(&self) -> &dyn NBTStorage {
+        self
     }
 
     fn get_entity(&self) -> &Entity {
@@ -49,6 +231,48 @@ impl EntityBase for MobEntity {
     fn get_living_entity(&self) -> Option<&LivingEntity> {
         Some(&self.living_entity)
     }
+
+    fn get_mob_entity(&self) -> Option<&MobEntity> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl NBTStorage for MobEntity {
+    async fn write_nbt(&self, nbt: &mut NbtCompound) {
+        self.living_entity.write_nbt(nbt).await;
+        if let Some(breeding) = &self.breeding {
+            breeding.write_nbt(nbt);
+        }
+        if let Some(villager) = &self.villager {
+            villager.lock().await.write_nbt(nbt);
+        }
+        if let Some(squid) = &self.squid {
+            squid.lock().await.write_nbt(nbt);
+        }
+        if let Some(creeper) = &self.creeper {
+            creeper.lock().await.write_nbt(nbt);
+        }
+    }
+
+    async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
+        self.living_entity.read_nbt(nbt).await;
+        if let Some(breeding) = &self.breeding {
+            breeding.read_nbt(nbt);
+            if breeding.is_baby() {
+                self.set_baby_size(true);
+            }
+        }
+        if let Some(villager) = &mut self.villager {
+            villager.get_mut().read_nbt(nbt);
+        }
+        if let Some(squid) = &mut self.squid {
+            squid.get_mut().read_nbt(nbt);
+        }
+        if let Some(creeper) = &mut self.creeper {
+            creeper.get_mut().read_nbt(nbt);
+        }
+    }
 }
 
 pub async fn from_type(
@@ -57,21 +281,508 @@ pub async fn from_type(
     world: &Arc<World>,
 ) -> Arc<dyn EntityBase> {
     let entity = world.create_entity(position, entity_type);
+    Arc::new(from_entity(entity).await)
+}
+
+/// Makes `entity` a mob with the goals of its type, before its data is read and it is spawned.
+pub async fn from_entity(entity: Entity) -> MobEntity {
+    let entity_type = entity.entity_type;
     let mob = MobEntity {
         living_entity: LivingEntity::new(entity),
-        goals: Mutex::new(vec![]),
-        navigator: Mutex::new(Navigator::default()),
+        goals: Mutex::new(GoalSelector::default()),
+        navigation: Mutex::new(EntityNavigation::default()),
+        breeding: Breeding::for_type(entity_type),
+        villager: Villager::for_type(entity_type).map(Mutex::new),
+        squid: Squid::for_type(entity_type).map(Mutex::new),
+        creeper: Creeper::for_type(entity_type).map(Mutex::new),
     };
     match entity_type {
         EntityType::ZOMBIE => Zombie::make(&mob).await,
+        EntityType::CREEPER => {
+            mob.goal(2, CreeperSwellGoal).await;
+            mob.goal(3, FleeEntityGoal::new(&SCARED_OF, SCARED_DISTANCE, 1.2))
+                .await;
+            mob.goal(4, TargetGoal::new(16.0)).await;
+            mob.goal(5, RandomStrollGoal::new(0.8)).await;
+            mob.goal(6, LookAtPlayerGoal::new(8.0)).await;
+            mob.goal(6, LookAroundGoal::new()).await;
+        }
+        // Animals and villagers idle around
+        _ if mob.breeding.is_some() || mob.villager.is_some() => {
+            mob.goal(6, RandomStrollGoal::new(1.0)).await;
+            mob.goal(7, LookAtPlayerGoal::new(6.0)).await;
+            mob.goal(8, LookAroundGoal::new()).await;
+        }
         // TODO
         _ => (),
     }
-    Arc::new(mob)
+    mob
 }
 
 impl MobEntity {
-    pub async fn goal<T: Goal + 'static>(&self, goal: T) {
-        self.goals.lock().await.push((Arc::new(goal), false));
+    /// Adds a goal to the mob, goals with a lower priority are more important.
+    pub async fn goal<T: Goal + 'static>(&self, priority: u8, goal: T) {
+        self.goals.lock().await.add_goal(priority, Arc::new(goal));
+    }
+
+    /// How far the mob walks per tick, based on its movement speed attribute.
+    pub async fn walk_speed(&self) -> f64 {
+        self.living_entity
+            .get_attribute_value(Attribute::MovementSpeed)
+            .await
+            * WALK_SPEED_PER_MOVEMENT_SPEED
+    }
+
+    /// Walks to `pos` unless the mob is on its way there already, since finding a path is
+    /// expensive. Returns `false` if there is no way there.
+    pub async fn walk_to(&self, pos: BlockPos) -> bool {
+        let mut navigation = self.navigation.lock().await;
+        if navigation.destination() == Some(pos) {
+            return true;
+        }
+        let speed = self.walk_speed().await;
+        navigation.start_to(&self.living_entity, pos, speed).await
+    }
+
+    fn baby_meta_data(baby: bool) -> Metadata<bool> {
+        Metadata::new(16, MetaDataType::Boolean, baby)
+    }
+
+    /// Babies are half as big as adults.
+    fn set_baby_size(&self, baby: bool) {
+        let entity = &self.living_entity.entity;
+        let scale = if baby { 0.5 } else { 1.0 };
+        entity.set_bounding_box_size(EntityDimensions {
+            width: entity.entity_type.dimension[0] * scale,
+            height: entity.entity_type.dimension[1] * scale,
+        });
+    }
+
+    pub async fn set_baby(&self, baby: bool) {
+        self.set_baby_size(baby);
+        self.living_entity
+            .entity
+            .send_meta_data(&[Self::baby_meta_data(baby)])
+            .await;
+    }
+
+    async fn tick_breeding(&self) {
+        let Some(breeding) = &self.breeding else {
+            return;
+        };
+        if breeding.tick() {
+            self.set_baby(false).await;
+        }
+        if breeding.is_in_love() {
+            self.approach_partner(breeding).await;
+        }
+    }
+
+    async fn tick_villager(&self) {
+        let Some(villager) = &self.villager else {
+            return;
+        };
+        let entity = &self.living_entity.entity;
+        let world = entity.world.read().await.clone();
+        let (time_of_day, game_time) = {
+            let level_time = world.level_time.lock().await;
+            (level_time.time_of_day, level_time.world_age)
+        };
+
+        // Searching for a workstation is expensive, so villagers spread their checks over time
+        if (game_time + i64::from(entity.entity_id)) % JOB_SITE_CHECK_INTERVAL == 0 {
+            self.check_job_site(&world, villager).await;
+        }
+
+        let mut villager = villager.lock().await;
+        let Some(job_site) = villager.job_site else {
+            return;
+        };
+        if !WORK_HOURS.contains(&time_of_day.rem_euclid(24000)) {
+            return;
+        }
+        let pos = entity.pos.load();
+        let workstation = job_site.to_f64();
+        if pos.squared_distance_to_vec(workstation) > WORKING_DISTANCE * WORKING_DISTANCE {
+            if villager.needs_restock() {
+                drop(villager);
+                // The workstation itself is solid, so the villager walks up to one of its sides
+                for direction in BlockDirection::horizontal() {
+                    if self.walk_to(job_site.offset(direction.to_offset())).await {
+                        break;
+                    }
+                }
+            }
+            return;
+        }
+        villager.try_restock(time_of_day, game_time);
+    }
+
+    /// Squids drift through the water and dry out on land.
+    async fn tick_squid(&self) {
+        let Some(squid) = &self.squid else {
+            return;
+        };
+        let entity = &self.living_entity.entity;
+        let in_water = self.living_entity.is_in_water().await;
+        let (dry_out, meta_data, swim) = {
+            let mut squid = squid.lock().await;
+            let dry_out = squid.tick_air(in_water);
+            let meta_data = if squid.tick_dark() {
+                squid.meta_data()
+            } else {
+                None
+            };
+            (dry_out, meta_data, squid.swim)
+        };
+        if let Some(meta_data) = meta_data {
+            entity.send_meta_data(&[meta_data]).await;
+        }
+        if !in_water {
+            if dry_out {
+                self.damage(DRY_OUT_DAMAGE, DamageType::DROWN).await;
+            }
+            return;
+        }
+
+        let world = entity.world.read().await.clone();
+        let last_pos = entity.pos.load();
+        let blocked = !Self::is_water_at(&world, last_pos + swim).await;
+        let swim = squid
+            .lock()
+            .await
+            .next_swim(blocked, &mut rand::thread_rng());
+        let pos = last_pos + swim;
+        // Squids never swim out of the water on their own
+        if !Self::is_water_at(&world, pos).await {
+            return;
+        }
+        self.living_entity.set_pos(pos);
+        world
+            .broadcast_to_trackers(
+                entity.entity_id,
+                &CUpdateEntityPos::new(
+                    entity.entity_id.into(),
+                    Vector3::new(
+                        pos.x.mul_add(4096.0, -(last_pos.x * 4096.0)) as i16,
+                        pos.y.mul_add(4096.0, -(last_pos.y * 4096.0)) as i16,
+                        pos.z.mul_add(4096.0, -(last_pos.z * 4096.0)) as i16,
+                    ),
+                    false,
+                ),
+            )
+            .await;
+    }
+
+    async fn is_water_at(world: &World, pos: Vector3<f64>) -> bool {
+        world
+            .get_block(&BlockPos::floored(pos.x, pos.y, pos.z))
+            .await
+            .is_ok_and(|block| block == Block::WATER)
+    }
+
+    /// Squirts an ink cloud which blinds nearby players and jets away from the closest player.
+    async fn release_ink(&self, squid: &Mutex<Squid>) {
+        let entity = &self.living_entity.entity;
+        let world = entity.world.read().await.clone();
+        let pos = entity.pos.load();
+        let players: Vec<_> = world.players.read().await.values().cloned().collect();
+
+        let threat = players
+            .iter()
+            .map(|player| player.living_entity.entity.pos.load())
+            .filter(|player_pos| {
+                player_pos.squared_distance_to_vec(pos)
+                    <= SQUID_THREAT_DISTANCE * SQUID_THREAT_DISTANCE
+            })
+            .min_by(|a, b| {
+                a.squared_distance_to_vec(pos)
+                    .total_cmp(&b.squared_distance_to_vec(pos))
+            });
+        let (cloud, meta_data) = {
+            let mut squid = squid.lock().await;
+            let cloud = squid.hurt();
+            squid.flee(pos, threat, &mut rand::thread_rng());
+            (cloud, squid.meta_data())
+        };
+        if let Some(meta_data) = meta_data {
+            entity.send_meta_data(&[meta_data]).await;
+        }
+
+        let center = pos.add_raw(0.0, f64::from(entity.entity_type.dimension[1]) / 2.0, 0.0);
+        world
+            .spawn_particle(center, Vector3::new(0.5, 0.5, 0.5), 0.1, 30, cloud.particle)
+            .await;
+        world
+            .play_sound(cloud.sound, SoundCategory::Neutral, &pos)
+            .await;
+
+        for player in players {
+            let player_pos = player.living_entity.entity.pos.load();
+            if player_pos.squared_distance_to_vec(center) > INK_CLOUD_RADIUS * INK_CLOUD_RADIUS {
+                continue;
+            }
+            player
+                .add_effect(Effect {
+                    r#type: cloud.effect,
+                    duration: cloud.effect_duration,
+                    amplifier: 0,
+                    ambient: false,
+                    show_particles: false,
+                    show_icon: true,
+                    blend: false,
+                })
+                .await;
+        }
+    }
+
+    /// Puts the mob on the lead the player holds, or takes it off again if the player already
+    /// holds its leash.
+    async fn interact_leash(&self, player: &Player) -> bool {
+        let entity = &self.living_entity.entity;
+        let world = entity.world.read().await.clone();
+        let holder = world.leashes.lock().await.holder_of(entity.entity_id);
+        if holder == Some(player.entity_id()) {
+            world.unleash_entity(entity).await;
+            return true;
+        }
+        // Only animals can be put on a lead for now
+        if holder.is_some() || self.breeding.is_none() {
+            return false;
+        }
+        {
+            let mut inventory = player.inventory().lock().await;
+            if !inventory
+                .held_item()
+                .is_some_and(|held| held.item.id == Item::LEAD.id)
+            {
+                return false;
+            }
+            if player.gamemode.load() != GameMode::Creative {
+                inventory.decrease_current_stack(1);
+            }
+        }
+        player.send_inventory().await;
+        world
+            .leash_entity(entity.entity_id, player.entity_id())
+            .await;
+        true
+    }
+
+    /// Makes a leashed mob follow whoever holds the leash, the leash breaks if it gets too far.
+    async fn tick_leash(&self) {
+        let entity = &self.living_entity.entity;
+        let world = entity.world.read().await.clone();
+        let Some(holder) = world.leashes.lock().await.holder_of(entity.entity_id) else {
+            return;
+        };
+        let holder_pos = match world.get_player_by_id(holder).await {
+            Some(player) => Some(player.living_entity.entity.pos.load()),
+            None => world
+                .get_entity_by_id(holder)
+                .await
+                .map(|holder| holder.get_entity().pos.load()),
+        };
+        let pos = entity.pos.load();
+        let Some(holder_pos) = holder_pos.filter(|holder_pos| {
+            holder_pos.squared_distance_to_vec(pos) <= LEASH_BREAK_DISTANCE * LEASH_BREAK_DISTANCE
+        }) else {
+            world.unleash_entity(entity).await;
+            return;
+        };
+        if holder_pos.squared_distance_to_vec(pos) > LEASH_PULL_DISTANCE * LEASH_PULL_DISTANCE {
+            self.walk_to(BlockPos::floored(holder_pos.x, holder_pos.y, holder_pos.z))
+                .await;
+        }
+    }
+
+    /// Counts down a creeper's fuse, it hisses when it starts swelling and explodes once the fuse
+    /// burned down.
+    async fn tick_creeper(&self, server: &Server) {
+        let Some(creeper) = &self.creeper else {
+            return;
+        };
+        if self.living_entity.health.load() <= 0.0 {
+            return;
+        }
+        let (fuse, power, charged) = {
+            let mut creeper = creeper.lock().await;
+            (creeper.tick(), creeper.explosion_power(), creeper.charged)
+        };
+        let entity = &self.living_entity.entity;
+        let world = entity.world.read().await.clone();
+        let pos = entity.pos.load();
+        match fuse {
+            FuseTick::Primed => {
+                world
+                    .play_sound(Sound::EntityCreeperPrimed, SoundCategory::Hostile, &pos)
+                    .await;
+            }
+            FuseTick::Exploded => {
+                // Gone before the blast, so the creeper neither hurts itself nor drops loot
+                entity.remove().await;
+                let killed = world.explode(server, pos, power).await;
+                if !charged {
+                    return;
+                }
+                // A charged creeper only makes one of its victims drop their head
+                let head = killed.iter().find_map(|victim| {
+                    let victim = victim.get_entity();
+                    Creeper::head_of(victim.entity_type).map(|head| (victim.block_pos.load(), head))
+                });
+                if let Some((victim_pos, head)) = head {
+                    drop_stack(&world, &victim_pos, ItemStack::new(1, head)).await;
+                }
+            }
+            FuseTick::Idle | FuseTick::Swelling => (),
+        }
+    }
+
+    /// Lightning charges creepers.
+    pub async fn charge(&self) {
+        let Some(creeper) = &self.creeper else {
+            return;
+        };
+        let meta_data = {
+            let mut creeper = creeper.lock().await;
+            creeper.charged = true;
+            creeper.charged_meta_data()
+        };
+        self.living_entity.entity.send_meta_data(&[meta_data]).await;
+    }
+
+    /// Drops the mob's loot where it died.
+    async fn drop_loot(&self) {
+        let loot = if let Some(squid) = &self.squid {
+            Some(squid.lock().await.loot(&mut rand::thread_rng()))
+        } else if self.creeper.is_some() {
+            Creeper::loot(&mut rand::thread_rng())
+        } else {
+            None
+        };
+        let Some(loot) = loot else {
+            return;
+        };
+        let entity = &self.living_entity.entity;
+        let world = entity.world.read().await.clone();
+        drop_stack(&world, &entity.block_pos.load(), loot).await;
+    }
+
+    /// Drops the villager's workstation once it is gone, and lets villagers without one claim a
+    /// free workstation nearby.
+    async fn check_job_site(&self, world: &Arc<World>, villager: &Mutex<Villager>) {
+        let entity = &self.living_entity.entity;
+        let job_site = villager.lock().await.job_site;
+        if let Some(job_site) = job_site {
+            let block = world.get_block(&job_site).await;
+            let mut villager = villager.lock().await;
+            if !block.is_ok_and(|block| villager.is_workstation(&block)) {
+                villager.lose_job_site();
+                let meta_data = villager.meta_data();
+                drop(villager);
+                entity.send_meta_data(&[meta_data]).await;
+            }
+            return;
+        }
+        if !villager.lock().await.looks_for_job_site() {
+            return;
+        }
+
+        let mut claimed = Vec::new();
+        for other in world.entities.read().await.values() {
+            if other.get_entity().entity_id == entity.entity_id {
+                continue;
+            }
+            if let Some(other) = other.get_mob_entity().and_then(|mob| mob.villager.as_ref()) {
+                claimed.extend(other.lock().await.job_site);
+            }
+        }
+
+        let center = entity.block_pos.load();
+        let (horizontal, vertical) = JOB_SITE_SEARCH_RADIUS;
+        for x in -horizontal..=horizontal {
+            for y in -vertical..=vertical {
+                for z in -horizontal..=horizontal {
+                    let pos = center.offset(Vector3::new(x, y, z));
+                    if claimed.contains(&pos) {
+                        continue;
+                    }
+                    let Ok(block) = world.get_block(&pos).await else {
+                        continue;
+                    };
+                    let mut villager = villager.lock().await;
+                    if villager.claim_workstation(&block, pos, &mut rand::thread_rng()) {
+                        let meta_data = villager.meta_data();
+                        drop(villager);
+                        entity.send_meta_data(&[meta_data]).await;
+                        world
+                            .send_entity_status(entity, EntityStatus::AddVillagerHappyParticles)
+                            .await;
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Walks towards the closest partner in love and makes a baby with it once they are close.
+    async fn approach_partner(&self, breeding: &Breeding) {
+        let entity = &self.living_entity.entity;
+        let world = entity.world.read().await.clone();
+        let pos = entity.pos.load();
+
+        let mut closest: Option<(Arc<dyn EntityBase>, f64)> = None;
+        for other in world.entities.read().await.values() {
+            let other_entity = other.get_entity();
+            if other_entity.entity_id == entity.entity_id
+                || other_entity.entity_type != entity.entity_type
+            {
+                continue;
+            }
+            let Some(other_breeding) = other.get_mob_entity().and_then(|mob| mob.breeding.as_ref())
+            else {
+                continue;
+            };
+            if !breeding.can_breed_with(other_breeding) {
+                continue;
+            }
+            let distance_squared = other_entity.pos.load().squared_distance_to_vec(pos);
+            if distance_squared <= PARTNER_SEARCH_DISTANCE * PARTNER_SEARCH_DISTANCE
+                && closest
+                    .as_ref()
+                    .is_none_or(|(_, closest)| distance_squared < *closest)
+            {
+                closest = Some((other.clone(), distance_squared));
+            }
+        }
+        let Some((partner, distance_squared)) = closest else {
+            return;
+        };
+        let Some(partner_breeding) = partner
+            .get_mob_entity()
+            .and_then(|mob| mob.breeding.as_ref())
+        else {
+            return;
+        };
+
+        if distance_squared > BREEDING_DISTANCE * BREEDING_DISTANCE {
+            self.walk_to(partner.get_entity().block_pos.load()).await;
+            return;
+        }
+        let baby_breeding = breeding.breed_with(partner_breeding);
+
+        let baby = from_type(entity.entity_type, pos, &world).await;
+        if let Some(baby_mob) = baby.get_mob_entity() {
+            if let Some(breeding) = &baby_mob.breeding {
+                breeding.set_age(baby_breeding.age());
+            }
+            baby_mob.set_baby_size(true);
+        }
+        world.spawn_entity(baby.clone()).await;
+        if let Some(baby_mob) = baby.get_mob_entity() {
+            baby_mob.set_baby(true).await;
+        }
+        let experience = rand::thread_rng().gen_range(1..=7);
+        ExperienceOrbEntity::spawn(&world, pos, experience).await;
     }
 }
@@ -0,0 +1,262 @@
+use std::f64::consts::TAU;
+
+use pumpkin_data::{
+    entity::{EffectType, EntityType},
+    item::Item,
+    particle::Particle,
+    sound::Sound,
+};
+use pumpkin_nbt::compound::NbtCompound;
+use pumpkin_protocol::{
+    client::play::{MetaDataType, Metadata},
+    codec::var_int::VarInt,
+};
+use pumpkin_util::math::vector3::Vector3;
+use pumpkin_world::{entity::entity_data_flags::DATA_DARK_TICKS_REMAINING, item::ItemStack};
+use rand::Rng;
+
+/// How many ticks a squid can stay out of water before it starts drowning. Vanilla: 300
+pub const MAX_AIR: i32 = 300;
+/// The damage a squid takes every second once it ran out of air. Vanilla: 2
+pub const DRY_OUT_DAMAGE: f32 = 2.0;
+/// How long a glow squid stops glowing after it was hurt. Vanilla: 100
+const DARK_TICKS_WHEN_HURT: i32 = 100;
+/// How long a squid swims in one direction before it may pick a new one
+const SWIM_TICKS: i32 = 50;
+/// How far a squid drifts per tick
+const SWIM_SPEED: f64 = 0.1;
+/// How long and fast a squid jets away from what hurt it
+const FLEE_TICKS: i32 = 20;
+const FLEE_SPEED: f64 = 0.4;
+/// How far the ink cloud spreads, players inside it lose their sight for a moment
+pub const INK_CLOUD_RADIUS: f64 = 3.0;
+const INK_EFFECT_DURATION: i32 = 40;
+/// Glow squids only spawn this far below sea level, squids only above it. Vanilla: 33
+const GLOW_SQUID_DEPTH: i32 = 33;
+/// Squids spawn at most this far below sea level. Vanilla: 13
+const SQUID_DEPTH: i32 = 13;
+
+/// The cloud a squid releases when it gets hurt.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InkCloud {
+    pub particle: Particle,
+    pub sound: Sound,
+    /// The effect players caught in the cloud get
+    pub effect: EffectType,
+    pub effect_duration: i32,
+}
+
+/// The swimming and ink of squids and glow squids.
+pub struct Squid {
+    glowing: bool,
+    /// The movement per tick while drifting or fleeing
+    pub swim: Vector3<f64>,
+    swim_ticks: i32,
+    flee_ticks: i32,
+    /// Ticks left until the squid runs out of air, negative once it's drowning
+    pub air: i32,
+    /// How long a glow squid stays dark
+    pub dark_ticks: i32,
+}
+
+impl Squid {
+    pub fn for_type(entity_type: EntityType) -> Option<Self> {
+        let glowing = match entity_type {
+            EntityType::SQUID => false,
+            EntityType::GLOW_SQUID => true,
+            _ => return None,
+        };
+        Some(Self {
+            glowing,
+            swim: Vector3::new(0.0, 0.0, 0.0),
+            swim_ticks: 0,
+            flee_ticks: 0,
+            air: MAX_AIR,
+            dark_ticks: 0,
+        })
+    }
+
+    /// Whether a squid of this type may spawn at this height, it also needs to be in water.
+    pub fn spawn_height_allowed(entity_type: EntityType, y: i32, sea_level: i32) -> bool {
+        match entity_type {
+            EntityType::SQUID => (sea_level - SQUID_DEPTH..=sea_level).contains(&y),
+            EntityType::GLOW_SQUID => y <= sea_level - GLOW_SQUID_DEPTH,
+            _ => false,
+        }
+    }
+
+    pub const fn is_fleeing(&self) -> bool {
+        self.flee_ticks > 0
+    }
+
+    /// Updates the air supply, returns whether the squid takes damage for being out of water.
+    /// It loses a point of air every tick on land and gets hurt whenever 20 more are gone.
+    pub const fn tick_air(&mut self, in_water: bool) -> bool {
+        if in_water {
+            self.air = MAX_AIR;
+            return false;
+        }
+        self.air -= 1;
+        if self.air <= -20 {
+            self.air = 0;
+            return true;
+        }
+        false
+    }
+
+    /// Counts down the glow squid's darkness, returns whether it starts glowing again.
+    pub const fn tick_dark(&mut self) -> bool {
+        if self.dark_ticks <= 0 {
+            return false;
+        }
+        self.dark_ticks -= 1;
+        self.dark_ticks == 0
+    }
+
+    /// The movement for this tick. A new random direction is picked every so often, or when
+    /// `blocked` by something which isn't water.
+    pub fn next_swim(&mut self, blocked: bool, rng: &mut impl Rng) -> Vector3<f64> {
+        if self.flee_ticks > 0 {
+            self.flee_ticks -= 1;
+            if !blocked {
+                return self.swim;
+            }
+        }
+        self.swim_ticks -= 1;
+        if blocked || self.swim_ticks <= 0 {
+            let angle = rng.gen_range(0.0..TAU);
+            self.swim = Vector3::new(
+                angle.cos() * SWIM_SPEED,
+                rng.gen_range(-0.5..0.5) * SWIM_SPEED,
+                angle.sin() * SWIM_SPEED,
+            );
+            self.swim_ticks = rng.gen_range(SWIM_TICKS / 2..=SWIM_TICKS);
+        }
+        self.swim
+    }
+
+    /// Jets away from the position of a threat, or in a random direction without one.
+    pub fn flee(&mut self, pos: Vector3<f64>, threat: Option<Vector3<f64>>, rng: &mut impl Rng) {
+        let away = threat
+            .map(|threat| pos.sub(&threat))
+            .filter(|away| away.length_squared() > 1.0E-4)
+            .unwrap_or_else(|| {
+                Vector3::new(
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-0.5..0.5),
+                    rng.gen_range(-1.0..1.0),
+                )
+            });
+        self.swim = away.normalize() * FLEE_SPEED;
+        self.flee_ticks = FLEE_TICKS;
+    }
+
+    /// Releases ink when the squid gets hurt, glow squids also stop glowing for a while.
+    pub const fn hurt(&mut self) -> InkCloud {
+        if self.glowing {
+            self.dark_ticks = DARK_TICKS_WHEN_HURT;
+            InkCloud {
+                particle: Particle::GlowSquidInk,
+                sound: Sound::EntityGlowSquidSquirt,
+                effect: EffectType::Darkness,
+                effect_duration: INK_EFFECT_DURATION,
+            }
+        } else {
+            InkCloud {
+                particle: Particle::SquidInk,
+                sound: Sound::EntitySquidSquirt,
+                effect: EffectType::Blindness,
+                effect_duration: INK_EFFECT_DURATION,
+            }
+        }
+    }
+
+    /// The ink sacs dropped on death.
+    pub fn loot(&self, rng: &mut impl Rng) -> ItemStack {
+        let item = if self.glowing {
+            Item::GLOW_INK_SAC
+        } else {
+            Item::INK_SAC
+        };
+        ItemStack::new(rng.gen_range(1..=3), item)
+    }
+
+    /// Only glow squids have metadata.
+    pub fn meta_data(&self) -> Option<Metadata<VarInt>> {
+        self.glowing.then(|| {
+            Metadata::new(
+                DATA_DARK_TICKS_REMAINING,
+                MetaDataType::Integer,
+                VarInt(self.dark_ticks),
+            )
+        })
+    }
+
+    pub fn write_nbt(&self, nbt: &mut NbtCompound) {
+        nbt.put_short("Air", self.air.clamp(0, MAX_AIR) as i16);
+        if self.glowing {
+            nbt.put_int("DarkTicksRemaining", self.dark_ticks);
+        }
+    }
+
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(air) = nbt.get_short("Air") {
+            self.air = i32::from(air);
+        }
+        if self.glowing {
+            self.dark_ticks = nbt.get_int("DarkTicksRemaining").unwrap_or(0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{entity::EntityType, item::Item, particle::Particle};
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{MAX_AIR, Squid};
+
+    #[test]
+    fn squids_dry_out_and_ink_when_hurt() {
+        let mut squid = Squid::for_type(EntityType::SQUID).unwrap();
+        let damaged_ticks: Vec<_> = (1..=MAX_AIR + 60)
+            .filter(|_| squid.tick_air(false))
+            .collect();
+        // Out of air after 300 ticks, then hurt once a second
+        assert_eq!(damaged_ticks, [MAX_AIR + 20, MAX_AIR + 40, MAX_AIR + 60]);
+        assert!(!squid.tick_air(true));
+        assert_eq!(squid.air, MAX_AIR);
+
+        let cloud = squid.hurt();
+        assert_eq!(cloud.particle, Particle::SquidInk);
+        assert!(squid.meta_data().is_none());
+
+        let mut glow_squid = Squid::for_type(EntityType::GLOW_SQUID).unwrap();
+        assert_eq!(glow_squid.hurt().particle, Particle::GlowSquidInk);
+        assert!(glow_squid.dark_ticks > 0);
+        assert!(glow_squid.meta_data().is_some());
+        assert_eq!(
+            glow_squid.loot(&mut rand::thread_rng()).item.id,
+            Item::GLOW_INK_SAC.id
+        );
+    }
+
+    #[test]
+    fn fleeing_squids_jet_away_from_the_threat() {
+        let mut rng = rand::thread_rng();
+        let mut squid = Squid::for_type(EntityType::SQUID).unwrap();
+        squid.flee(
+            Vector3::new(0.0, 50.0, 0.0),
+            Some(Vector3::new(-2.0, 50.0, 0.0)),
+            &mut rng,
+        );
+        assert!(squid.is_fleeing());
+        let swim = squid.next_swim(false, &mut rng);
+        assert!(swim.x > 0.0);
+
+        assert!(Squid::spawn_height_allowed(EntityType::SQUID, 60, 63));
+        assert!(!Squid::spawn_height_allowed(EntityType::SQUID, 20, 63));
+        assert!(Squid::spawn_height_allowed(EntityType::GLOW_SQUID, 20, 63));
+        assert!(!Squid::spawn_height_allowed(EntityType::GLOW_SQUID, 60, 63));
+    }
+}
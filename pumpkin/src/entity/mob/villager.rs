@@ -0,0 +1,768 @@
+use std::ops::Range;
+
+use pumpkin_data::{Block, entity::EntityType, item::Item};
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
+use pumpkin_protocol::{
+    client::play::{MetaDataType, Metadata},
+    codec::var_int::VarInt,
+};
+use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_world::{entity::entity_data_flags::DATA_VILLAGER_DATA_VILLAGER, item::ItemStack};
+use rand::{Rng, seq::SliceRandom};
+use serde::Serialize;
+
+pub const MAX_LEVEL: i32 = 5;
+/// How many offers a villager unlocks with each level
+const OFFERS_PER_LEVEL: usize = 2;
+/// How often a villager can restock a day. Vanilla: 2
+const MAX_RESTOCKS_PER_DAY: i32 = 2;
+/// How many ticks a villager waits after restocking before it restocks again. Vanilla: 2400
+const RESTOCK_COOLDOWN: i64 = 2400;
+/// The day time during which villagers work at their workstation
+pub const WORK_HOURS: Range<i64> = 2000..9000;
+/// The plains villager type, villagers don't get their type from the biome yet
+const PLAINS_TYPE: i32 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profession {
+    None,
+    Armorer,
+    Butcher,
+    Cartographer,
+    Cleric,
+    Farmer,
+    Fisherman,
+    Fletcher,
+    Leatherworker,
+    Librarian,
+    Mason,
+    Nitwit,
+    Shepherd,
+    Toolsmith,
+    Weaponsmith,
+}
+
+impl Profession {
+    /// In the order of the profession registry
+    const ALL: [Self; 15] = [
+        Self::None,
+        Self::Armorer,
+        Self::Butcher,
+        Self::Cartographer,
+        Self::Cleric,
+        Self::Farmer,
+        Self::Fisherman,
+        Self::Fletcher,
+        Self::Leatherworker,
+        Self::Librarian,
+        Self::Mason,
+        Self::Nitwit,
+        Self::Shepherd,
+        Self::Toolsmith,
+        Self::Weaponsmith,
+    ];
+
+    pub fn resource_name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Armorer => "armorer",
+            Self::Butcher => "butcher",
+            Self::Cartographer => "cartographer",
+            Self::Cleric => "cleric",
+            Self::Farmer => "farmer",
+            Self::Fisherman => "fisherman",
+            Self::Fletcher => "fletcher",
+            Self::Leatherworker => "leatherworker",
+            Self::Librarian => "librarian",
+            Self::Mason => "mason",
+            Self::Nitwit => "nitwit",
+            Self::Shepherd => "shepherd",
+            Self::Toolsmith => "toolsmith",
+            Self::Weaponsmith => "weaponsmith",
+        }
+    }
+
+    pub fn from_resource_name(name: &str) -> Option<Self> {
+        let name = name.strip_prefix("minecraft:").unwrap_or(name);
+        Self::ALL
+            .into_iter()
+            .find(|profession| profession.resource_name() == name)
+    }
+
+    /// The profession a villager takes on when it claims this workstation.
+    pub fn from_workstation(block: &Block) -> Option<Self> {
+        WORKSTATIONS
+            .iter()
+            .find(|(workstation, _)| workstation.id == block.id)
+            .map(|(_, profession)| *profession)
+    }
+
+    fn network_id(self) -> i32 {
+        Self::ALL
+            .iter()
+            .position(|profession| *profession == self)
+            .unwrap_or(0) as i32
+    }
+}
+
+const WORKSTATIONS: [(Block, Profession); 16] = [
+    (Block::BLAST_FURNACE, Profession::Armorer),
+    (Block::SMOKER, Profession::Butcher),
+    (Block::CARTOGRAPHY_TABLE, Profession::Cartographer),
+    (Block::BREWING_STAND, Profession::Cleric),
+    (Block::COMPOSTER, Profession::Farmer),
+    (Block::BARREL, Profession::Fisherman),
+    (Block::FLETCHING_TABLE, Profession::Fletcher),
+    (Block::CAULDRON, Profession::Leatherworker),
+    (Block::WATER_CAULDRON, Profession::Leatherworker),
+    (Block::LAVA_CAULDRON, Profession::Leatherworker),
+    (Block::POWDER_SNOW_CAULDRON, Profession::Leatherworker),
+    (Block::LECTERN, Profession::Librarian),
+    (Block::STONECUTTER, Profession::Mason),
+    (Block::LOOM, Profession::Shepherd),
+    (Block::SMITHING_TABLE, Profession::Toolsmith),
+    (Block::GRINDSTONE, Profession::Weaponsmith),
+];
+
+/// A trade a villager can unlock, before it is rolled into a [`TradeOffer`].
+struct Trade {
+    buy: Item,
+    buy_count: u8,
+    sell: Item,
+    sell_count: u8,
+    max_uses: i32,
+    xp: i32,
+    price_multiplier: f32,
+}
+
+/// The villager buys items for an emerald.
+const fn buys(item: Item, count: u8, max_uses: i32, xp: i32) -> Trade {
+    Trade {
+        buy: item,
+        buy_count: count,
+        sell: Item::EMERALD,
+        sell_count: 1,
+        max_uses,
+        xp,
+        price_multiplier: 0.05,
+    }
+}
+
+/// The villager sells items for emeralds.
+const fn sells(emeralds: u8, item: Item, count: u8, max_uses: i32, xp: i32) -> Trade {
+    Trade {
+        buy: Item::EMERALD,
+        buy_count: emeralds,
+        sell: item,
+        sell_count: count,
+        max_uses,
+        xp,
+        price_multiplier: 0.05,
+    }
+}
+
+/// The villager sells tools or armor, which get more expensive with demand.
+const fn sells_gear(emeralds: u8, item: Item, max_uses: i32, xp: i32) -> Trade {
+    Trade {
+        price_multiplier: 0.2,
+        ..sells(emeralds, item, 1, max_uses, xp)
+    }
+}
+
+/// The trades of every profession by level. Higher levels are still missing.
+const TRADES: &[(Profession, i32, &[Trade])] = &[
+    (
+        Profession::Armorer,
+        1,
+        &[
+            buys(Item::COAL, 15, 16, 2),
+            sells_gear(7, Item::IRON_LEGGINGS, 12, 1),
+            sells_gear(4, Item::IRON_BOOTS, 12, 1),
+            sells_gear(5, Item::IRON_HELMET, 12, 1),
+            sells_gear(9, Item::IRON_CHESTPLATE, 12, 1),
+        ],
+    ),
+    (
+        Profession::Armorer,
+        2,
+        &[
+            buys(Item::IRON_INGOT, 4, 12, 10),
+            sells(36, Item::BELL, 1, 12, 5),
+            sells_gear(1, Item::CHAINMAIL_BOOTS, 12, 5),
+        ],
+    ),
+    (
+        Profession::Butcher,
+        1,
+        &[
+            buys(Item::CHICKEN, 14, 16, 2),
+            buys(Item::PORKCHOP, 7, 16, 2),
+            buys(Item::RABBIT, 4, 16, 2),
+            sells(1, Item::RABBIT_STEW, 1, 12, 1),
+        ],
+    ),
+    (
+        Profession::Butcher,
+        2,
+        &[
+            buys(Item::COAL, 15, 16, 2),
+            sells(1, Item::COOKED_PORKCHOP, 5, 16, 5),
+            sells(1, Item::COOKED_CHICKEN, 8, 16, 5),
+        ],
+    ),
+    (
+        Profession::Cartographer,
+        1,
+        &[buys(Item::PAPER, 24, 16, 2), sells(7, Item::MAP, 1, 12, 1)],
+    ),
+    (
+        Profession::Cartographer,
+        2,
+        &[buys(Item::GLASS_PANE, 11, 16, 10)],
+    ),
+    (
+        Profession::Cleric,
+        1,
+        &[
+            buys(Item::ROTTEN_FLESH, 32, 16, 2),
+            sells(1, Item::REDSTONE, 2, 12, 1),
+        ],
+    ),
+    (
+        Profession::Cleric,
+        2,
+        &[
+            buys(Item::GOLD_INGOT, 3, 12, 10),
+            sells(1, Item::LAPIS_LAZULI, 1, 12, 5),
+        ],
+    ),
+    (
+        Profession::Farmer,
+        1,
+        &[
+            buys(Item::WHEAT, 20, 16, 2),
+            buys(Item::POTATO, 26, 16, 2),
+            buys(Item::CARROT, 22, 16, 2),
+            buys(Item::BEETROOT, 15, 16, 2),
+            sells(1, Item::BREAD, 6, 16, 1),
+        ],
+    ),
+    (
+        Profession::Farmer,
+        2,
+        &[
+            buys(Item::PUMPKIN, 6, 12, 10),
+            sells(1, Item::PUMPKIN_PIE, 4, 12, 5),
+            sells(1, Item::APPLE, 4, 16, 5),
+        ],
+    ),
+    (
+        Profession::Fisherman,
+        1,
+        &[
+            buys(Item::STRING, 20, 16, 2),
+            buys(Item::COAL, 10, 16, 2),
+            sells(3, Item::COD_BUCKET, 1, 16, 1),
+        ],
+    ),
+    (
+        Profession::Fisherman,
+        2,
+        &[
+            buys(Item::COD, 15, 16, 10),
+            sells(2, Item::CAMPFIRE, 1, 12, 5),
+        ],
+    ),
+    (
+        Profession::Fletcher,
+        1,
+        &[
+            buys(Item::STICK, 32, 16, 2),
+            sells(1, Item::ARROW, 16, 12, 1),
+        ],
+    ),
+    (
+        Profession::Fletcher,
+        2,
+        &[
+            buys(Item::FLINT, 26, 12, 10),
+            sells_gear(2, Item::BOW, 12, 5),
+        ],
+    ),
+    (
+        Profession::Leatherworker,
+        1,
+        &[
+            buys(Item::LEATHER, 6, 16, 2),
+            sells_gear(3, Item::LEATHER_LEGGINGS, 12, 1),
+            sells_gear(7, Item::LEATHER_CHESTPLATE, 12, 1),
+        ],
+    ),
+    (
+        Profession::Leatherworker,
+        2,
+        &[
+            buys(Item::FLINT, 26, 12, 10),
+            sells_gear(5, Item::LEATHER_HELMET, 12, 5),
+            sells_gear(4, Item::LEATHER_BOOTS, 12, 5),
+        ],
+    ),
+    (
+        Profession::Librarian,
+        1,
+        &[
+            buys(Item::PAPER, 24, 16, 2),
+            sells(9, Item::BOOKSHELF, 1, 12, 1),
+        ],
+    ),
+    (
+        Profession::Librarian,
+        2,
+        &[
+            buys(Item::BOOK, 4, 12, 10),
+            sells(1, Item::LANTERN, 1, 12, 5),
+        ],
+    ),
+    (
+        Profession::Mason,
+        1,
+        &[
+            buys(Item::CLAY_BALL, 10, 16, 2),
+            sells(1, Item::BRICK, 10, 16, 1),
+        ],
+    ),
+    (
+        Profession::Mason,
+        2,
+        &[
+            buys(Item::STONE, 20, 16, 10),
+            sells(1, Item::CHISELED_STONE_BRICKS, 4, 16, 5),
+        ],
+    ),
+    (
+        Profession::Shepherd,
+        1,
+        &[
+            buys(Item::WHITE_WOOL, 18, 16, 2),
+            sells_gear(2, Item::SHEARS, 12, 1),
+        ],
+    ),
+    (
+        Profession::Shepherd,
+        2,
+        &[
+            buys(Item::WHITE_DYE, 12, 16, 10),
+            sells(1, Item::WHITE_WOOL, 1, 16, 5),
+        ],
+    ),
+    (
+        Profession::Toolsmith,
+        1,
+        &[
+            buys(Item::COAL, 15, 16, 2),
+            sells_gear(1, Item::STONE_AXE, 12, 1),
+            sells_gear(1, Item::STONE_SHOVEL, 12, 1),
+            sells_gear(1, Item::STONE_PICKAXE, 12, 1),
+            sells_gear(1, Item::STONE_HOE, 12, 1),
+        ],
+    ),
+    (
+        Profession::Toolsmith,
+        2,
+        &[
+            buys(Item::IRON_INGOT, 4, 12, 10),
+            sells(36, Item::BELL, 1, 12, 5),
+        ],
+    ),
+    (
+        Profession::Weaponsmith,
+        1,
+        &[
+            buys(Item::COAL, 15, 16, 2),
+            sells_gear(3, Item::IRON_AXE, 12, 1),
+        ],
+    ),
+    (
+        Profession::Weaponsmith,
+        2,
+        &[
+            buys(Item::IRON_INGOT, 4, 12, 10),
+            sells(36, Item::BELL, 1, 12, 5),
+        ],
+    ),
+];
+
+fn trades(profession: Profession, level: i32) -> &'static [Trade] {
+    TRADES
+        .iter()
+        .find(|(trade_profession, trade_level, _)| {
+            *trade_profession == profession && *trade_level == level
+        })
+        .map_or(&[], |(_, _, trades)| trades)
+}
+
+#[derive(Clone, Debug)]
+pub struct TradeOffer {
+    pub buy: ItemStack,
+    pub sell: ItemStack,
+    pub uses: i32,
+    pub max_uses: i32,
+    /// The experience the villager gets from the trade
+    pub xp: i32,
+    pub price_multiplier: f32,
+    /// Goes up when the trade sold out before a restock, making it more expensive
+    pub demand: i32,
+    pub special_price: i32,
+}
+
+impl TradeOffer {
+    fn from_trade(trade: &Trade) -> Self {
+        Self {
+            buy: ItemStack::new(trade.buy_count, trade.buy.clone()),
+            sell: ItemStack::new(trade.sell_count, trade.sell.clone()),
+            uses: 0,
+            max_uses: trade.max_uses,
+            xp: trade.xp,
+            price_multiplier: trade.price_multiplier,
+            demand: 0,
+            special_price: 0,
+        }
+    }
+
+    pub fn is_out_of_stock(&self) -> bool {
+        self.uses >= self.max_uses
+    }
+
+    fn restock(&mut self) {
+        // Trades which were used more than they were left alone get more expensive
+        self.demand += self.uses - (self.max_uses - self.uses);
+        self.uses = 0;
+    }
+
+    fn write_nbt(&self) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        let mut buy = NbtCompound::new();
+        self.buy.write_item_stack(&mut buy);
+        nbt.put_component("buy", buy);
+        let mut sell = NbtCompound::new();
+        self.sell.write_item_stack(&mut sell);
+        nbt.put_component("sell", sell);
+        nbt.put_int("uses", self.uses);
+        nbt.put_int("maxUses", self.max_uses);
+        nbt.put_int("xp", self.xp);
+        nbt.put_float("priceMultiplier", self.price_multiplier);
+        nbt.put_int("demand", self.demand);
+        nbt.put_int("specialPrice", self.special_price);
+        nbt
+    }
+
+    fn read_nbt(nbt: &NbtCompound) -> Option<Self> {
+        Some(Self {
+            buy: ItemStack::read_item_stack(nbt.get_compound("buy")?)?,
+            sell: ItemStack::read_item_stack(nbt.get_compound("sell")?)?,
+            uses: nbt.get_int("uses").unwrap_or(0),
+            max_uses: nbt.get_int("maxUses").unwrap_or(4),
+            xp: nbt.get_int("xp").unwrap_or(1),
+            price_multiplier: nbt.get_float("priceMultiplier").unwrap_or(0.0),
+            demand: nbt.get_int("demand").unwrap_or(0),
+            special_price: nbt.get_int("specialPrice").unwrap_or(0),
+        })
+    }
+}
+
+/// The villager data entity metadata
+#[derive(Serialize)]
+struct VillagerData {
+    villager_type: VarInt,
+    profession: VarInt,
+    level: VarInt,
+}
+
+/// The profession, level and offers of a villager.
+pub struct Villager {
+    pub profession: Profession,
+    pub level: i32,
+    pub xp: i32,
+    pub offers: Vec<TradeOffer>,
+    /// The workstation the villager claimed for its profession
+    pub job_site: Option<BlockPos>,
+    /// The game time of the last restock
+    last_restock: i64,
+    restocks_today: i32,
+    /// The day `restocks_today` counts for
+    restock_day: i64,
+}
+
+impl Villager {
+    pub fn for_type(entity_type: EntityType) -> Option<Self> {
+        (entity_type == EntityType::VILLAGER).then(|| Self {
+            profession: Profession::None,
+            level: 1,
+            xp: 0,
+            offers: Vec::new(),
+            job_site: None,
+            last_restock: 0,
+            restocks_today: 0,
+            restock_day: 0,
+        })
+    }
+
+    /// Whether the villager looks for a workstation. Jobless villagers take any workstation,
+    /// the others only one of their profession.
+    pub fn looks_for_job_site(&self) -> bool {
+        self.job_site.is_none() && self.profession != Profession::Nitwit
+    }
+
+    /// Claims the workstation at `pos`, taking on its profession and unlocking the first offers
+    /// if the villager had none. Returns whether the villager claimed it.
+    pub fn claim_workstation(&mut self, block: &Block, pos: BlockPos, rng: &mut impl Rng) -> bool {
+        let Some(profession) = Profession::from_workstation(block) else {
+            return false;
+        };
+        if !self.looks_for_job_site()
+            || (self.profession != Profession::None && self.profession != profession)
+        {
+            return false;
+        }
+        self.profession = profession;
+        self.job_site = Some(pos);
+        if self.offers.is_empty() {
+            self.unlock_offers(rng);
+        }
+        true
+    }
+
+    /// Whether the block at the job site is still the workstation of the villager's profession.
+    pub fn is_workstation(&self, block: &Block) -> bool {
+        Profession::from_workstation(block) == Some(self.profession)
+    }
+
+    /// The workstation of the villager is gone. Villagers who never traded lose their profession
+    /// with it, experienced ones keep it until they find a new workstation of the same kind.
+    pub fn lose_job_site(&mut self) {
+        self.job_site = None;
+        if self.level == 1 && self.xp == 0 {
+            self.profession = Profession::None;
+            self.offers.clear();
+        }
+    }
+
+    /// Adds up to [`OFFERS_PER_LEVEL`] random trades of the villager's level.
+    fn unlock_offers(&mut self, rng: &mut impl Rng) {
+        let new_offers = trades(self.profession, self.level)
+            .choose_multiple(rng, OFFERS_PER_LEVEL)
+            .map(TradeOffer::from_trade);
+        self.offers.extend(new_offers);
+    }
+
+    pub fn needs_restock(&self) -> bool {
+        self.offers.iter().any(|offer| offer.uses > 0)
+    }
+
+    /// Restocks all offers when the villager is at its workstation during work hours, at most
+    /// [`MAX_RESTOCKS_PER_DAY`] times a day. Returns whether it restocked.
+    pub fn try_restock(&mut self, time_of_day: i64, game_time: i64) -> bool {
+        let day = time_of_day.div_euclid(24000);
+        if day != self.restock_day {
+            self.restock_day = day;
+            self.restocks_today = 0;
+        }
+        if !WORK_HOURS.contains(&time_of_day.rem_euclid(24000))
+            || !self.needs_restock()
+            || self.restocks_today >= MAX_RESTOCKS_PER_DAY
+            || game_time < self.last_restock + RESTOCK_COOLDOWN
+        {
+            return false;
+        }
+        for offer in &mut self.offers {
+            offer.restock();
+        }
+        self.last_restock = game_time;
+        self.restocks_today += 1;
+        true
+    }
+
+    pub fn meta_data(&self) -> Metadata<impl Serialize + use<>> {
+        Metadata::new(
+            DATA_VILLAGER_DATA_VILLAGER,
+            MetaDataType::VillagerData,
+            VillagerData {
+                villager_type: VarInt(PLAINS_TYPE),
+                profession: VarInt(self.profession.network_id()),
+                level: VarInt(self.level),
+            },
+        )
+    }
+
+    pub fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let mut data = NbtCompound::new();
+        data.put_string("type", "minecraft:plains".to_string());
+        data.put_string(
+            "profession",
+            format!("minecraft:{}", self.profession.resource_name()),
+        );
+        data.put_int("level", self.level);
+        nbt.put_component("VillagerData", data);
+        nbt.put_int("Xp", self.xp);
+
+        let recipes = self
+            .offers
+            .iter()
+            .map(|offer| NbtTag::Compound(offer.write_nbt()))
+            .collect();
+        let mut offers = NbtCompound::new();
+        offers.put_list("Recipes", recipes);
+        nbt.put_component("Offers", offers);
+
+        nbt.put_long("LastRestock", self.last_restock);
+        nbt.put_int("RestocksToday", self.restocks_today);
+
+        if let Some(job_site) = self.job_site {
+            let mut value = NbtCompound::new();
+            value.put(
+                "pos",
+                NbtTag::IntArray(Box::new([job_site.0.x, job_site.0.y, job_site.0.z])),
+            );
+            let mut memory = NbtCompound::new();
+            memory.put_component("value", value);
+            let mut memories = NbtCompound::new();
+            memories.put_component("minecraft:job_site", memory);
+            let mut brain = NbtCompound::new();
+            brain.put_component("memories", memories);
+            nbt.put_component("Brain", brain);
+        }
+    }
+
+    pub fn read_nbt(&mut self, nbt: &NbtCompound) {
+        if let Some(data) = nbt.get_compound("VillagerData") {
+            self.profession = data
+                .get_string("profession")
+                .and_then(|name| Profession::from_resource_name(name))
+                .unwrap_or(Profession::None);
+            self.level = data.get_int("level").unwrap_or(1).clamp(1, MAX_LEVEL);
+        }
+        self.xp = nbt.get_int("Xp").unwrap_or(0);
+        self.offers = nbt
+            .get_compound("Offers")
+            .and_then(|offers| offers.get_list("Recipes"))
+            .into_iter()
+            .flatten()
+            .filter_map(NbtTag::extract_compound)
+            .filter_map(TradeOffer::read_nbt)
+            .collect();
+        self.last_restock = nbt.get_long("LastRestock").unwrap_or(0);
+        self.restocks_today = nbt.get_int("RestocksToday").unwrap_or(0);
+        self.job_site = nbt
+            .get_compound("Brain")
+            .and_then(|brain| brain.get_compound("memories"))
+            .and_then(|memories| memories.get_compound("minecraft:job_site"))
+            .and_then(|memory| memory.get_compound("value"))
+            .and_then(|value| value.get_int_array("pos"))
+            .and_then(|pos| match pos {
+                [x, y, z] => Some(BlockPos(Vector3::new(*x, *y, *z))),
+                _ => None,
+            });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::{Block, entity::EntityType};
+    use pumpkin_nbt::compound::NbtCompound;
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::{Profession, RESTOCK_COOLDOWN, Villager, WORK_HOURS, trades};
+
+    #[test]
+    fn claiming_a_lectern_makes_a_librarian_who_restocks_at_work() {
+        let mut rng = rand::thread_rng();
+        let mut villager = Villager::for_type(EntityType::VILLAGER).unwrap();
+        let lectern = BlockPos::new(3, 64, 7);
+        assert!(!villager.claim_workstation(&Block::STONE, lectern, &mut rng));
+        assert!(villager.claim_workstation(&Block::LECTERN, lectern, &mut rng));
+        assert_eq!(villager.profession, Profession::Librarian);
+        assert_eq!(villager.job_site, Some(lectern));
+
+        // Both offers come from the first tier of librarian trades
+        let tier_one = trades(Profession::Librarian, 1);
+        assert_eq!(villager.offers.len(), 2);
+        for offer in &villager.offers {
+            assert!(
+                tier_one
+                    .iter()
+                    .any(|trade| trade.sell.id == offer.sell.item.id
+                        && trade.buy.id == offer.buy.item.id)
+            );
+        }
+
+        // Trades are used up until the villager restocks
+        villager.offers[0].uses = 5;
+        assert!(villager.needs_restock());
+
+        // Villagers only restock while working
+        let day = 24000 * 3;
+        assert!(!villager.try_restock(day + WORK_HOURS.end, RESTOCK_COOLDOWN));
+        assert!(villager.try_restock(day + WORK_HOURS.start, RESTOCK_COOLDOWN));
+        assert_eq!(villager.offers[0].uses, 0);
+        assert!(!villager.needs_restock());
+
+        // A used up villager restocks twice a day, with a break in between
+        villager.offers[0].uses = villager.offers[0].max_uses;
+        assert!(villager.offers[0].is_out_of_stock());
+        let noon = day + 6000;
+        assert!(!villager.try_restock(noon, RESTOCK_COOLDOWN + 100));
+        assert!(villager.try_restock(noon, RESTOCK_COOLDOWN * 2));
+        assert!(villager.offers[0].demand > 0);
+        villager.offers[0].uses = 1;
+        assert!(!villager.try_restock(noon, RESTOCK_COOLDOWN * 4));
+        // The next day it can restock again
+        assert!(villager.try_restock(noon + 24000, RESTOCK_COOLDOWN * 10));
+    }
+
+    #[test]
+    fn unexperienced_villagers_lose_their_profession_with_the_workstation() {
+        let mut rng = rand::thread_rng();
+        let mut villager = Villager::for_type(EntityType::VILLAGER).unwrap();
+        villager.claim_workstation(&Block::COMPOSTER, BlockPos::new(0, 64, 0), &mut rng);
+        assert!(villager.is_workstation(&Block::COMPOSTER));
+        villager.lose_job_site();
+        assert_eq!(villager.profession, Profession::None);
+        assert!(villager.offers.is_empty());
+
+        villager.claim_workstation(&Block::COMPOSTER, BlockPos::new(0, 64, 0), &mut rng);
+        villager.xp = 10;
+        villager.lose_job_site();
+        assert_eq!(villager.profession, Profession::Farmer);
+        assert_eq!(villager.job_site, None);
+        assert!(!villager.claim_workstation(&Block::LECTERN, BlockPos::new(1, 64, 0), &mut rng));
+        // But another composter will do
+        let offers = villager.offers.len();
+        assert!(villager.claim_workstation(&Block::COMPOSTER, BlockPos::new(1, 64, 0), &mut rng));
+        assert_eq!(villager.offers.len(), offers);
+    }
+
+    #[test]
+    fn villagers_are_saved_with_their_offers() {
+        let mut rng = rand::thread_rng();
+        let mut villager = Villager::for_type(EntityType::VILLAGER).unwrap();
+        villager.claim_workstation(&Block::LECTERN, BlockPos::new(3, 64, 7), &mut rng);
+        villager.xp = 10;
+        villager.offers[1].uses = 3;
+
+        let mut nbt = NbtCompound::new();
+        villager.write_nbt(&mut nbt);
+        let mut loaded = Villager::for_type(EntityType::VILLAGER).unwrap();
+        loaded.read_nbt(&nbt);
+
+        assert_eq!(loaded.profession, Profession::Librarian);
+        assert_eq!(loaded.level, villager.level);
+        assert_eq!(loaded.xp, villager.xp);
+        assert_eq!(loaded.job_site, Some(BlockPos::new(3, 64, 7)));
+        assert_eq!(loaded.offers.len(), villager.offers.len());
+        for (loaded, saved) in loaded.offers.iter().zip(&villager.offers) {
+            assert_eq!(loaded.sell.item.id, saved.sell.item.id);
+            assert_eq!(loaded.buy.item_count, saved.buy.item_count);
+            assert_eq!(loaded.uses, saved.uses);
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::entity::ai::goal::{look_at_entity::LookAtEntityGoal, target_goal::TargetGoal};
+use crate::entity::ai::goal::{
+    look_around::LookAroundGoal, look_at_player::LookAtPlayerGoal, random_stroll::RandomStrollGoal,
+    target_goal::TargetGoal,
+};
 
 use super::MobEntity;
 
@@ -6,7 +9,9 @@ pub struct Zombie;
 
 impl Zombie {
     pub async fn make(mob: &MobEntity) {
-        mob.goal(LookAtEntityGoal::new(8.0)).await;
-        mob.goal(TargetGoal::new(16.0)).await;
+        mob.goal(2, TargetGoal::new(16.0)).await;
+        mob.goal(7, RandomStrollGoal::new(1.0)).await;
+        mob.goal(8, LookAtPlayerGoal::new(8.0)).await;
+        mob.goal(8, LookAroundGoal::new()).await;
     }
 }
@@ -4,7 +4,9 @@ use bytes::BufMut;
 use core::f32;
 use crossbeam::atomic::AtomicCell;
 use living::LivingEntity;
+use mob::MobEntity;
 use player::Player;
+use portal::{Cooldown, PortalTravel};
 use pumpkin_data::{
     block_properties::{Facing, HorizontalFacing},
     damage::DamageType,
@@ -14,8 +16,8 @@ use pumpkin_data::{
 use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_protocol::{
     client::play::{
-        CEntityPositionSync, CEntityVelocity, CHeadRot, CSetEntityMetadata, CSpawnEntity,
-        CUpdateEntityRot, MetaDataType, Metadata,
+        Animation, CEntityAnimation, CEntityPositionSync, CEntityVelocity, CHeadRot,
+        CSetEntityMetadata, CSpawnEntity, CUpdateEntityRot, MetaDataType, Metadata,
     },
     codec::var_int::VarInt,
     ser::serializer::Serializer,
@@ -32,7 +34,7 @@ use serde::Serialize;
 use std::sync::{
     Arc,
     atomic::{
-        AtomicBool, AtomicI32,
+        AtomicBool, AtomicI32, AtomicU8,
         Ordering::{Relaxed, SeqCst},
     },
 };
@@ -41,15 +43,23 @@ use tokio::sync::RwLock;
 use crate::world::World;
 
 pub mod ai;
+pub mod attributes;
+pub mod collision;
 pub mod effect;
+pub mod elytra;
 pub mod experience_orb;
+pub mod falling_block;
 pub mod hunger;
 pub mod item;
+pub mod leash_knot;
+pub mod lightning;
 pub mod living;
 pub mod mob;
 pub mod player;
+pub mod portal;
 pub mod projectile;
 pub mod tnt;
+pub mod tracking;
 
 mod combat;
 
@@ -75,10 +85,47 @@ pub trait EntityBase: Send + Sync {
         }
     }
 
+    /// Like [`Self::damage`], but dealt directly by `source` (e.g. an arrow) on behalf of `cause`
+    /// (e.g. whoever shot it), or coming from `position` if there is no entity. These get the
+    /// blame in death messages and decide where the knockback pushes to.
+    async fn damage_with_context(
+        &self,
+        amount: f32,
+        damage_type: DamageType,
+        _position: Option<Vector3<f64>>,
+        _source: Option<&Entity>,
+        _cause: Option<&Entity>,
+    ) -> bool {
+        self.damage(amount, damage_type).await
+    }
+
     /// Called when a player collides with a entity
     async fn on_player_collision(&self, _player: Arc<Player>) {}
+
+    /// Called after the entity was spawned for a player, to send the metadata the client needs to
+    /// display it correctly
+    async fn send_tracking_data(&self, _player: &Player) {}
+
+    /// The data sent along with the spawn packet, like the block state of a falling block
+    fn spawn_data(&self) -> i32 {
+        0
+    }
+
+    /// Called when a player right clicks the entity, returns whether the entity reacted to it
+    async fn interact(&self, _player: &Player) -> bool {
+        false
+    }
+
+    /// The data the entity is saved with, e.g. for `/data`
+    fn nbt_storage(&self) -> &dyn NBTStorage {
+        self.get_entity()
+    }
+
     fn get_entity(&self) -> &Entity;
     fn get_living_entity(&self) -> Option<&LivingEntity>;
+    fn get_mob_entity(&self) -> Option<&MobEntity> {
+        None
+    }
 }
 
 static CURRENT_ID: AtomicI32 = AtomicI32::new(0);
@@ -105,6 +152,8 @@ pub struct Entity {
     pub sprinting: AtomicBool,
     /// Indicates whether the entity is flying due to a fall
     pub fall_flying: AtomicBool,
+    /// The shared entity flags (see `Flag`), sent as metadata index 0
+    pub flags: AtomicU8,
     /// The entity's current velocity vector, aka knockback
     pub velocity: AtomicCell<Vector3<f64>>,
     /// Indicates whether the entity is on the ground (may not always be accurate).
@@ -127,6 +176,10 @@ pub struct Entity {
     pub invulnerable: AtomicBool,
     /// List of damage types this entity is immune to
     pub damage_immunities: Vec<DamageType>,
+    /// Keeps the entity from mounting a vehicle right after it dismounted
+    pub ride_cooldown: Cooldown,
+    /// The time spent in a nether portal and the immunity after changing dimensions
+    pub portal: PortalTravel,
 }
 
 impl Entity {
@@ -158,6 +211,7 @@ impl Entity {
             world: Arc::new(RwLock::new(world)),
             sprinting: AtomicBool::new(false),
             fall_flying: AtomicBool::new(false),
+            flags: AtomicU8::new(0),
             yaw: AtomicCell::new(0.0),
             head_yaw: AtomicCell::new(0.0),
             pitch: AtomicCell::new(0.0),
@@ -173,18 +227,59 @@ impl Entity {
             bounding_box_size: AtomicCell::new(bounding_box_size),
             invulnerable: AtomicBool::new(invulnerable),
             damage_immunities: Vec::new(),
+            ride_cooldown: Cooldown::default(),
+            portal: PortalTravel::default(),
         }
     }
 
+    /// Applies the data every entity is saved with, see [`NBTStorage::read_nbt`]. Missing or
+    /// malformed values are left as they are.
+    pub fn load_nbt(&self, nbt: &NbtCompound) {
+        let doubles = |key: &str| {
+            nbt.get_list(key).map(|list| {
+                list.iter()
+                    .map(|tag| tag.extract_double().unwrap_or(0.0))
+                    .collect::<Vec<_>>()
+            })
+        };
+        if let Some([x, y, z]) = doubles("Pos").as_deref() {
+            self.set_pos(Vector3::new(*x, *y, *z));
+        }
+        if let Some([x, y, z]) = doubles("Motion").as_deref() {
+            self.velocity.store(Vector3::new(*x, *y, *z));
+        }
+        if let Some([yaw, pitch]) = nbt.get_list("Rotation") {
+            let yaw = yaw.extract_float().unwrap_or(0.0);
+            let pitch = pitch.extract_float().unwrap_or(0.0);
+            self.set_rotation(yaw, pitch);
+            self.head_yaw.store(yaw);
+        }
+        if let Some(on_ground) = nbt.get_bool("OnGround") {
+            self.on_ground.store(on_ground, Relaxed);
+        }
+        // todo more...
+    }
+
     pub async fn set_velocity(&self, velocity: Vector3<f64>) {
         self.velocity.store(velocity);
         self.world
             .read()
             .await
-            .broadcast_packet_all(&CEntityVelocity::new(self.entity_id.into(), velocity))
+            .broadcast_to_trackers(
+                self.entity_id,
+                &CEntityVelocity::new(self.entity_id.into(), velocity),
+            )
             .await;
     }
 
+    /// Resizes the entity's bounding box, keeping it at the same position.
+    pub fn set_bounding_box_size(&self, size: EntityDimensions) {
+        let pos = self.pos.load();
+        self.bounding_box_size.store(size);
+        self.bounding_box
+            .store(BoundingBox::new_from_pos(pos.x, pos.y, pos.z, &size));
+    }
+
     /// Updates the entity's position, block position, and chunk position.
     ///
     /// This function calculates the new position, block position, and chunk position based on the provided coordinates. If any of these values change, the corresponding fields are updated.
@@ -241,11 +336,7 @@ impl Entity {
 
     /// Changes this entity's pitch and yaw to look at target
     pub async fn look_at(&self, target: Vector3<f64>) {
-        let position = self.pos.load();
-        let delta = target.sub(&position);
-        let root = delta.x.hypot(delta.z);
-        let pitch = wrap_degrees(-delta.y.atan2(root) as f32 * 180.0 / f32::consts::PI);
-        let yaw = wrap_degrees((delta.z.atan2(delta.x) as f32 * 180.0 / f32::consts::PI) - 90.0);
+        let (yaw, pitch) = rotation_towards(self.pos.load(), target);
         self.pitch.store(pitch);
         self.yaw.store(yaw);
 
@@ -256,17 +347,23 @@ impl Entity {
         self.world
             .read()
             .await
-            .broadcast_packet_all(&CUpdateEntityRot::new(
-                self.entity_id.into(),
-                yaw as u8,
-                pitch as u8,
-                self.on_ground.load(Relaxed),
-            ))
+            .broadcast_to_trackers(
+                self.entity_id,
+                &CUpdateEntityRot::new(
+                    self.entity_id.into(),
+                    yaw as u8,
+                    pitch as u8,
+                    self.on_ground.load(Relaxed),
+                ),
+            )
             .await;
         self.world
             .read()
             .await
-            .broadcast_packet_all(&CHeadRot::new(self.entity_id.into(), yaw as u8))
+            .broadcast_to_trackers(
+                self.entity_id,
+                &CHeadRot::new(self.entity_id.into(), yaw as u8),
+            )
             .await;
     }
 
@@ -274,15 +371,18 @@ impl Entity {
         self.world
             .read()
             .await
-            .broadcast_packet_all(&CEntityPositionSync::new(
-                self.entity_id.into(),
-                position,
-                Vector3::new(0.0, 0.0, 0.0),
-                yaw,
-                pitch,
-                // TODO
-                self.on_ground.load(SeqCst),
-            ))
+            .broadcast_to_trackers(
+                self.entity_id,
+                &CEntityPositionSync::new(
+                    self.entity_id.into(),
+                    position,
+                    Vector3::new(0.0, 0.0, 0.0),
+                    yaw,
+                    pitch,
+                    // TODO
+                    self.on_ground.load(SeqCst),
+                ),
+            )
             .await;
         self.set_pos(position);
         self.set_rotation(yaw, pitch);
@@ -300,7 +400,7 @@ impl Entity {
         self.world.read().await.remove_entity(self).await;
     }
 
-    pub fn create_spawn_packet(&self) -> CSpawnEntity {
+    pub fn create_spawn_packet(&self, data: i32) -> CSpawnEntity {
         let entity_loc = self.pos.load();
         let entity_vel = self.velocity.load();
         CSpawnEntity::new(
@@ -311,7 +411,7 @@ impl Entity {
             self.pitch.load(),
             self.yaw.load(),
             self.head_yaw.load(), // todo: head_yaw and yaw are swapped, find out why
-            0.into(),
+            data.into(),
             entity_vel,
         )
     }
@@ -325,8 +425,9 @@ impl Entity {
 
     /// Applies knockback to the entity, following vanilla Minecraft's mechanics.
     ///
-    /// This function calculates the entity's new velocity based on the specified knockback strength and direction.
-    pub fn knockback(&self, strength: f64, x: f64, z: f64) {
+    /// This function calculates the entity's new velocity based on the specified knockback strength and direction,
+    /// and sends it to the clients so they see the entity fly.
+    pub async fn knockback(&self, strength: f64, x: f64, z: f64) {
         // This has some vanilla magic
         let mut x = x;
         let mut z = z;
@@ -335,22 +436,45 @@ impl Entity {
             z = (rand::random::<f64>() - rand::random::<f64>()) * 0.01;
         }
 
+        let velocity = Self::knockback_velocity(
+            self.velocity.load(),
+            self.on_ground.load(Relaxed),
+            strength,
+            x,
+            z,
+        );
+        self.set_velocity(velocity).await;
+    }
+
+    /// The velocity of an entity moving at `velocity` after being knocked back.
+    fn knockback_velocity(
+        velocity: Vector3<f64>,
+        on_ground: bool,
+        strength: f64,
+        x: f64,
+        z: f64,
+    ) -> Vector3<f64> {
         let var8 = Vector3::new(x, 0.0, z).normalize() * strength;
-        let velocity = self.velocity.load();
-        self.velocity.store(Vector3::new(
+        Vector3::new(
             velocity.x / 2.0 - var8.x,
-            if self.on_ground.load(Relaxed) {
+            if on_ground {
                 (velocity.y / 2.0 + strength).min(0.4)
             } else {
                 velocity.y
             },
             velocity.z / 2.0 - var8.z,
-        ));
+        )
+    }
+
+    /// Stores the new value, returning whether it differs from the previous one
+    fn update_state(state: &AtomicBool, value: bool) -> bool {
+        state.swap(value, Relaxed) != value
     }
 
     pub async fn set_sneaking(&self, sneaking: bool) {
-        assert!(self.sneaking.load(Relaxed) != sneaking);
-        self.sneaking.store(sneaking, Relaxed);
+        if !Self::update_state(&self.sneaking, sneaking) {
+            return;
+        }
         self.set_flag(Flag::Sneaking, sneaking).await;
         if sneaking {
             self.set_pose(EntityPose::Crouching).await;
@@ -398,8 +522,9 @@ impl Entity {
     }
 
     pub async fn set_sprinting(&self, sprinting: bool) {
-        assert!(self.sprinting.load(Relaxed) != sprinting);
-        self.sprinting.store(sprinting, Relaxed);
+        if !Self::update_state(&self.sprinting, sprinting) {
+            return;
+        }
         self.set_flag(Flag::Sprinting, sprinting).await;
     }
 
@@ -408,21 +533,56 @@ impl Entity {
     }
 
     pub async fn set_fall_flying(&self, fall_flying: bool) {
-        assert!(self.fall_flying.load(Relaxed) != fall_flying);
-        self.fall_flying.store(fall_flying, Relaxed);
+        if !Self::update_state(&self.fall_flying, fall_flying) {
+            return;
+        }
         self.set_flag(Flag::FallFlying, fall_flying).await;
     }
 
     async fn set_flag(&self, flag: Flag, value: bool) {
-        let index = flag as u8;
-        let mut b = 0i8;
+        let bit = 1 << flag as u8;
+        let old = if value {
+            self.flags.fetch_or(bit, Relaxed)
+        } else {
+            self.flags.fetch_and(!bit, Relaxed)
+        };
+        let b = Self::with_flag(old, flag, value);
+        self.send_meta_data(&[Metadata::new(0, MetaDataType::Byte, b as i8)])
+            .await;
+    }
+
+    pub fn has_flag(&self, flag: Flag) -> bool {
+        self.flags.load(Relaxed) & (1 << flag as u8) != 0
+    }
+
+    pub fn is_glowing(&self) -> bool {
+        self.has_flag(Flag::Glowing)
+    }
+
+    /// Makes the entity glow for every player in the world.
+    ///
+    /// The outline is rendered in the color of the entity's team, or white if it has none.
+    pub async fn set_glowing(&self, glowing: bool) {
+        self.set_flag(Flag::Glowing, glowing).await;
+    }
+
+    pub async fn set_invisible(&self, invisible: bool) {
+        self.set_flag(Flag::Invisible, invisible).await;
+    }
+
+    /// Makes the entity glow only for the given player, leaving the shared flags untouched.
+    pub async fn set_glowing_for(&self, player: &Player, glowing: bool) {
+        let flags = Self::with_flag(self.flags.load(Relaxed), Flag::Glowing, glowing);
+        self.send_meta_data_to(player, &[Metadata::new(0, MetaDataType::Byte, flags as i8)])
+            .await;
+    }
+
+    fn with_flag(flags: u8, flag: Flag, value: bool) -> u8 {
         if value {
-            b |= 1 << index;
+            flags | (1 << flag as u8)
         } else {
-            b &= !(1 << index);
+            flags & !(1 << flag as u8)
         }
-        self.send_meta_data(&[Metadata::new(0, MetaDataType::Byte, b)])
-            .await;
     }
 
     /// Plays sound at this entity's position with the entity's sound category
@@ -434,7 +594,26 @@ impl Entity {
             .await;
     }
 
-    pub async fn send_meta_data<T>(&self, meta: &[Metadata<T>])
+    /// Plays an animation of this entity for the players around it. Swings aren't sent back to
+    /// a swinging player, their client already played it.
+    pub async fn play_animation(&self, animation: Animation) {
+        let except: &[uuid::Uuid] = if animation.is_swing() {
+            &[self.entity_uuid]
+        } else {
+            &[]
+        };
+        self.world
+            .read()
+            .await
+            .broadcast_to_trackers_except(
+                self.entity_id,
+                except,
+                &CEntityAnimation::new(self.entity_id.into(), animation as u8),
+            )
+            .await;
+    }
+
+    fn serialize_meta_data<T>(meta: &[Metadata<T>]) -> Vec<u8>
     where
         T: Serialize,
     {
@@ -446,13 +625,58 @@ impl Entity {
             buf.extend(serializer_buf);
         }
         buf.put_u8(255);
+        buf
+    }
+
+    pub async fn send_meta_data<T>(&self, meta: &[Metadata<T>])
+    where
+        T: Serialize,
+    {
+        let buf = Self::serialize_meta_data(meta);
         self.world
             .read()
             .await
-            .broadcast_packet_all(&CSetEntityMetadata::new(self.entity_id.into(), buf.into()))
+            .broadcast_to_trackers(
+                self.entity_id,
+                &CSetEntityMetadata::new(self.entity_id.into(), buf.into()),
+            )
             .await;
     }
 
+    /// Sends metadata only to the given player, e.g. because they just started tracking us.
+    pub async fn send_meta_data_to<T>(&self, player: &Player, meta: &[Metadata<T>])
+    where
+        T: Serialize,
+    {
+        let buf = Self::serialize_meta_data(meta);
+        player
+            .client
+            .enqueue_packet(&CSetEntityMetadata::new(self.entity_id.into(), buf.into()))
+            .await;
+    }
+
+    /// Sends the flags and the pose only to the given player if they differ from the defaults the
+    /// client assumes, e.g. because they just started tracking us.
+    pub async fn send_base_meta_data_to(&self, player: &Player) {
+        let flags = self.flags.load(Relaxed);
+        if flags != 0 {
+            self.send_meta_data_to(player, &[Metadata::new(0, MetaDataType::Byte, flags as i8)])
+                .await;
+        }
+        let pose = self.pose.load();
+        if pose != EntityPose::Standing {
+            self.send_meta_data_to(
+                player,
+                &[Metadata::new(
+                    6,
+                    MetaDataType::EntityPose,
+                    VarInt(pose as i32),
+                )],
+            )
+            .await;
+        }
+    }
+
     pub async fn set_pose(&self, pose: EntityPose) {
         self.pose.store(pose);
         let pose = pose as i32;
@@ -491,7 +715,7 @@ impl EntityBase for Entity {
     }
 
     async fn tick(&self, _: &Server) {
-        //Todo! Tick
+        self.ride_cooldown.tick();
     }
 
     fn get_entity(&self) -> &Entity {
@@ -530,27 +754,19 @@ impl NBTStorage for Entity {
     }
 
     async fn read_nbt(&mut self, nbt: &mut pumpkin_nbt::compound::NbtCompound) {
-        let position = nbt.get_list("Pos").unwrap();
-        let x = position[0].extract_double().unwrap_or(0.0);
-        let y = position[1].extract_double().unwrap_or(0.0);
-        let z = position[2].extract_double().unwrap_or(0.0);
-        self.set_pos(Vector3::new(x, y, z));
-        let velocity = nbt.get_list("Motion").unwrap();
-        let x = velocity[0].extract_double().unwrap_or(0.0);
-        let y = velocity[1].extract_double().unwrap_or(0.0);
-        let z = velocity[2].extract_double().unwrap_or(0.0);
-        self.velocity.store(Vector3::new(x, y, z));
-        let rotation = nbt.get_list("Rotation").unwrap();
-        let yaw = rotation[0].extract_float().unwrap_or(0.0);
-        let pitch = rotation[1].extract_float().unwrap_or(0.0);
-        self.set_rotation(yaw, pitch);
-        self.head_yaw.store(yaw);
-        self.on_ground
-            .store(nbt.get_bool("OnGround").unwrap_or(false), Relaxed);
-        // todo more...
+        self.load_nbt(nbt);
     }
 }
 
+/// The yaw and pitch in degrees of something at `position` looking at `target`.
+pub fn rotation_towards(position: Vector3<f64>, target: Vector3<f64>) -> (f32, f32) {
+    let delta = target.sub(&position);
+    let root = delta.x.hypot(delta.z);
+    let pitch = wrap_degrees(-delta.y.atan2(root) as f32 * 180.0 / f32::consts::PI);
+    let yaw = wrap_degrees((delta.z.atan2(delta.x) as f32 * 180.0 / f32::consts::PI) - 90.0);
+    (yaw, pitch)
+}
+
 #[async_trait]
 pub trait NBTStorage: Send + Sync {
     async fn write_nbt(&self, nbt: &mut NbtCompound);
@@ -582,3 +798,73 @@ pub enum Flag {
     /// Indicates if the entity is flying due to a fall.
     FallFlying = 7,
 }
+
+#[cfg(test)]
+mod test {
+    use super::{Entity, Flag};
+    use crate::test_utils::{test_player, test_server};
+    use pumpkin_data::packet::clientbound::{PLAY_SET_ENTITY_DATA, PLAY_SET_ENTITY_MOTION};
+    use pumpkin_protocol::{client::play::MetaDataType, codec::var_int::VarInt};
+    use pumpkin_util::math::vector3::Vector3;
+    use std::sync::atomic::Ordering::Relaxed;
+
+    #[tokio::test]
+    async fn glowing_is_sent_to_trackers() {
+        let server = test_server();
+        let (alice, _alice_packets) = test_player(&server, "alice").await;
+        let (bob, mut bob_packets) = test_player(&server, "bob").await;
+        let entity = &alice.living_entity.entity;
+        bob.tracked_entities.lock().await.track(entity.entity_id);
+        entity.flags.store(1 << Flag::Sneaking as u8, Relaxed);
+
+        entity.set_glowing(true).await;
+        assert!(entity.is_glowing());
+        entity.set_glowing(false).await;
+        assert!(!entity.is_glowing());
+
+        let sneaking = 1 << Flag::Sneaking as u8;
+        for flags in [sneaking | 1 << Flag::Glowing as u8, sneaking] {
+            let (packet, _) = bob_packets.try_recv().unwrap();
+            assert_eq!(i32::from(packet[0]), PLAY_SET_ENTITY_DATA);
+            let mut expected = Vec::new();
+            VarInt(entity.entity_id).encode(&mut expected).unwrap();
+            // Index, type and value of the flags, then the end of the metadata
+            expected.extend_from_slice(&[0, MetaDataType::Byte as u8, flags, 255]);
+            assert_eq!(&packet[1..], &expected[..]);
+        }
+        assert!(bob_packets.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn knockback_is_sent_to_clients() {
+        let server = test_server();
+        let (alice, mut alice_packets) = test_player(&server, "alice").await;
+        let (bob, mut bob_packets) = test_player(&server, "bob").await;
+        let (_carol, mut carol_packets) = test_player(&server, "carol").await;
+        let victim = &alice.living_entity.entity;
+        bob.tracked_entities.lock().await.track(victim.entity_id);
+
+        // Hit from the south while standing still
+        victim.on_ground.store(true, Relaxed);
+        victim.knockback(0.5, 0.0, -1.0).await;
+        assert_eq!(victim.velocity.load(), Vector3::new(0.0, 0.4, 0.5));
+
+        let mut expected = Vec::new();
+        VarInt(victim.entity_id).encode(&mut expected).unwrap();
+        for component in [0_i16, 3200, 4000] {
+            expected.extend_from_slice(&component.to_be_bytes());
+        }
+        // The victim is told too, as players move themselves
+        for packets in [&mut alice_packets, &mut bob_packets] {
+            let (packet, _) = packets.try_recv().unwrap();
+            assert_eq!(i32::from(packet[0]), PLAY_SET_ENTITY_MOTION);
+            assert_eq!(&packet[1..], &expected[..]);
+        }
+        assert!(carol_packets.try_recv().is_err());
+
+        // In the air, the vertical velocity is kept
+        let falling =
+            Entity::knockback_velocity(Vector3::new(0.0, -0.3, 0.2), false, 0.5, 1.0, 0.0);
+        assert_eq!(falling, Vector3::new(-0.5, -0.3, 0.1));
+    }
+}
@@ -1,16 +1,16 @@
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
     f64::{self, consts::TAU},
     num::NonZeroU8,
     ops::AddAssign,
     sync::{
         Arc,
         atomic::{
-            AtomicBool, AtomicI32, AtomicI64, AtomicU8, AtomicU32,
+            AtomicBool, AtomicI32, AtomicI64, AtomicU32,
             Ordering::{self, Relaxed},
         },
     },
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 
 use super::living::LivingEntity;
@@ -18,14 +18,17 @@ use super::{
     Entity, EntityBase, EntityId, NBTStorage,
     combat::{self, AttackType, player_attack_sound},
     effect::Effect,
+    elytra,
     hunger::HungerManager,
     item::ItemEntity,
+    portal::{Cooldown, PLAYER_PORTAL_WAIT_TIME, nether_portal_destination},
+    tracking::EntityTracker,
 };
 use crate::{
-    block,
+    block::{self, blocks::respawn_anchor::RespawnAnchorBlock},
     command::{client_suggestions, dispatcher::CommandDispatcher},
     data::op_data::OPERATOR_CONFIG,
-    net::{Client, PlayerConfig},
+    net::{Client, PlayerConfig, chat_signing::LastSeenValidator},
     plugin::player::{
         player_change_world::PlayerChangeWorldEvent,
         player_gamemode_change::PlayerGamemodeChangeEvent, player_teleport::PlayerTeleportEvent,
@@ -33,58 +36,68 @@ use crate::{
     server::Server,
     world::World,
 };
-use crate::{error::PumpkinError, net::GameProfile};
+use crate::{
+    error::PumpkinError,
+    net::{GameProfile, teleport::TeleportTracker},
+};
 use async_trait::async_trait;
 use crossbeam::atomic::AtomicCell;
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
 use pumpkin_data::{
-    BlockState,
+    Block, BlockState,
+    block_properties::Integer0To4,
     damage::DamageType,
-    entity::{EffectType, EntityPose, EntityStatus, EntityType},
-    item::Operation,
+    entity::{Attribute, EffectType, EntityPose, EntityStatus, EntityType},
+    item::{Item, Operation},
     particle::Particle,
     sound::{Sound, SoundCategory},
+    tag::Tagable,
 };
 use pumpkin_inventory::player::{
-    PlayerInventory, SLOT_BOOT, SLOT_CRAFT_INPUT_END, SLOT_CRAFT_INPUT_START, SLOT_HELM,
-    SLOT_HOTBAR_END, SLOT_INV_START, SLOT_OFFHAND,
+    PlayerInventory, SLOT_BOOT, SLOT_CHEST, SLOT_CRAFT_INPUT_END, SLOT_CRAFT_INPUT_START,
+    SLOT_HELM, SLOT_HOTBAR_END, SLOT_INV_START, SLOT_OFFHAND,
 };
+use pumpkin_inventory::{Container, EnderChestContainer};
 use pumpkin_macros::send_cancellable;
 use pumpkin_nbt::compound::NbtCompound;
 use pumpkin_nbt::tag::NbtTag;
 use pumpkin_protocol::client::play::{
-    CEntityPositionSync, CSetHeldItem, PlayerInfoFlags, PreviousMessage,
+    Animation, CEntityPositionSync, CSetHeldItem, PlayerInfoFlags, PreviousMessage,
 };
+use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::{
-    IdOr, RawPacket, ServerPacket,
+    ConnectionState, IdOr, RawPacket, ServerPacket,
     client::play::{
         CAcknowledgeBlockChange, CActionBar, CChunkBatchEnd, CChunkBatchStart, CChunkData,
-        CCombatDeath, CDisguisedChatMessage, CGameEvent, CKeepAlive, CParticle, CPlayDisconnect,
-        CPlayerAbilities, CPlayerInfoUpdate, CPlayerPosition, CRespawn, CSetExperience, CSetHealth,
-        CStopSound, CSubtitle, CSystemChatMessage, CTitleText, CUnloadChunk, CUpdateMobEffect,
-        GameEvent, MetaDataType, PlayerAction,
+        CCombatDeath, CDisguisedChatMessage, CGameEvent, CKeepAlive, CParticle, CPing,
+        CPingResponse, CPlayDisconnect, CPlayerAbilities, CPlayerChatMessage, CPlayerInfoUpdate,
+        CPlayerPosition, CRespawn, CSetExperience, CSetHealth, CStopSound, CSubtitle,
+        CSystemChatMessage, CTitleAnimation, CTitleText, CUnloadChunk, CUpdateAdvancements,
+        CUpdateMobEffect, EquipmentSlot, FilterType, GameEvent, MetaDataType, PlayerAction,
     },
     codec::identifier::Identifier,
     ser::packet::Packet,
     server::play::{
-        SChatCommand, SChatMessage, SChunkBatch, SClientCommand, SClientInformationPlay,
-        SClientTickEnd, SCommandSuggestion, SConfirmTeleport, SInteract, SPickItemFromBlock,
-        SPlayerAbilities, SPlayerAction, SPlayerCommand, SPlayerInput, SPlayerPosition,
-        SPlayerPositionRotation, SPlayerRotation, SPlayerSession, SSetCreativeSlot, SSetHeldItem,
-        SSetPlayerGround, SSwingArm, SUpdateSign, SUseItem, SUseItemOn,
+        SChatAck, SChatCommand, SChatCommandSigned, SChatMessage, SChunkBatch, SClientCommand,
+        SClientInformationPlay, SClientTickEnd, SCommandSuggestion, SConfigurationAcknowledged,
+        SConfirmTeleport, SInteract, SPickItemFromBlock, SPlayerAbilities, SPlayerAction,
+        SPlayerCommand, SPlayerInput, SPlayerPosition, SPlayerPositionRotation, SPlayerRotation,
+        SPlayerSession, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm, SUpdateSign,
+        SUseItem, SUseItemOn,
     },
 };
 use pumpkin_protocol::{
     client::play::CSoundEffect,
     server::play::{
         SCloseContainer, SCookieResponse as SPCookieResponse, SPlayPingRequest, SPlayerLoaded,
+        SPong,
     },
 };
-use pumpkin_protocol::{client::play::CUpdateTime, codec::var_int::VarInt};
 use pumpkin_protocol::{
     client::play::Metadata,
     server::play::{SClickContainer, SKeepAlive},
 };
+use pumpkin_registry::DimensionType;
 use pumpkin_util::{
     GameMode,
     math::{
@@ -92,18 +105,23 @@ use pumpkin_util::{
         vector3::Vector3,
     },
     permission::PermissionLvl,
-    text::TextComponent,
+    text::{TextComponent, color::NamedColor, hover::HoverEvent},
 };
 use pumpkin_world::entity::entity_data_flags::{
     DATA_PLAYER_MAIN_HAND, DATA_PLAYER_MODE_CUSTOMISATION,
 };
-use pumpkin_world::{cylindrical_chunk_iterator::Cylindrical, item::ItemStack, level::SyncChunk};
+use pumpkin_world::{
+    BlockStateId,
+    advancement::{Advancement, AdvancementFrame, AdvancementTrigger, PlayerAdvancements},
+    cylindrical_chunk_iterator::Cylindrical,
+    item::ItemStack,
+    level::SyncChunk,
+};
 use tokio::sync::RwLock;
 use tokio::{sync::Mutex, task::JoinHandle};
 use uuid::Uuid;
 
 const MAX_CACHED_SIGNATURES: u8 = 128; // Vanilla: 128
-const MAX_PREVIOUS_MESSAGES: u8 = 20; // Vanilla: 20
 
 enum BatchState {
     Initial,
@@ -189,6 +207,8 @@ pub struct Player {
     pub client: Client,
     /// The player's inventory.
     pub inventory: Mutex<PlayerInventory>,
+    /// The player's ender chest contents, shared by every ender chest they open.
+    pub ender_chest: Arc<Mutex<Box<dyn Container>>>,
     /// The player's configuration settings. Changes when the player changes their settings.
     pub config: RwLock<PlayerConfig>,
     /// The player's current gamemode (e.g., Survival, Creative, Adventure).
@@ -212,13 +232,23 @@ pub struct Player {
     /// Indicates if the player is currently mining a block.
     pub mining: AtomicBool,
     pub start_mining_time: AtomicI32,
+    /// The tick the player started using their held item, like charging a trident.
+    pub item_use_start: AtomicCell<Option<i32>>,
     pub tick_counter: AtomicI32,
     pub packet_sequence: AtomicI32,
     pub mining_pos: Mutex<BlockPos>,
+    /// The player who last sent this player a private message, used to reply.
+    pub last_whisperer: Mutex<Option<uuid::Uuid>>,
+    /// The signed message arguments of the command the player is running, by argument name
+    pub signed_arguments: Mutex<HashMap<String, SignedMessage>>,
+    /// The entities this player's client has been sent, see [`World::update_entity_tracking`]
+    pub tracked_entities: Mutex<EntityTracker>,
+    /// The equipment other players last saw, see [`Player::sync_equipment`].
+    last_equipment: Mutex<[ItemStack; EQUIPMENT_SLOTS.len()]>,
     /// A counter for teleport IDs used to track pending teleports.
     pub teleport_id_count: AtomicI32,
-    /// The pending teleport information, including the teleport ID and target location.
-    pub awaiting_teleport: Mutex<Option<(VarInt, Vector3<f64>)>>,
+    /// The teleport the client has yet to confirm, movement is ignored until it does.
+    pub awaiting_teleport: Mutex<TeleportTracker>,
     /// The coordinates of the chunk section the player is currently watching.
     pub watched_section: AtomicCell<Cylindrical>,
     /// Whether we are waiting for a response after sending a keep alive packet.
@@ -227,13 +257,27 @@ pub struct Player {
     pub keep_alive_id: AtomicI64,
     /// The last time we sent a keep alive packet.
     pub last_keep_alive_time: AtomicCell<Instant>,
+    /// When the player last moved or did something, used to kick idle players
+    pub last_action_time: AtomicCell<Instant>,
+    /// The id and send time of the last ping sent with [`Player::ping`], until the client answers.
+    pending_ping: Mutex<Option<(i32, Instant)>>,
+    /// The round trip time of the last answered ping.
+    pub ping_latency: AtomicCell<Option<Duration>>,
     /// The amount of ticks since the player's last attack.
     pub last_attacked_ticks: AtomicU32,
+    /// How many ticks the player has been gliding with an elytra.
+    pub fall_flying_ticks: AtomicU32,
+    /// The remaining ticks a firework rocket keeps pushing the gliding player.
+    pub firework_boost: Cooldown,
     /// The player's last known experience level.
     pub last_sent_xp: AtomicI32,
-    pub last_sent_health: AtomicI32,
-    pub last_sent_food: AtomicU8,
-    pub last_food_saturation: AtomicBool,
+    /// The health, food level and saturation last sent in a [`CSetHealth`], `None` if the client
+    /// has to be sent them again.
+    pub last_sent_health_food: AtomicCell<Option<(f32, u8, f32)>>,
+    /// Where the player respawns instead of the world spawn.
+    pub respawn_point: Mutex<Option<RespawnPoint>>,
+    /// The player's progress on the server's advancements.
+    pub advancements: Mutex<PlayerAdvancements>,
     /// The player's permission level.
     pub permission_lvl: AtomicCell<PermissionLvl>,
     /// Whether the client has reported that it has loaded.
@@ -282,7 +326,7 @@ impl Player {
             config: RwLock::new(config),
             gameprofile,
             client,
-            awaiting_teleport: Mutex::new(None),
+            awaiting_teleport: Mutex::new(TeleportTracker::default()),
             // TODO: Load this from previous instance
             hunger_manager: HungerManager::default(),
             current_block_destroy_stage: AtomicI32::new(-1),
@@ -290,11 +334,16 @@ impl Player {
             tick_counter: AtomicI32::new(0),
             packet_sequence: AtomicI32::new(-1),
             start_mining_time: AtomicI32::new(0),
+            item_use_start: AtomicCell::new(None),
             carried_item: Mutex::new(None),
             experience_pick_up_delay: Mutex::new(0),
             teleport_id_count: AtomicI32::new(0),
             mining: AtomicBool::new(false),
             mining_pos: Mutex::new(BlockPos(Vector3::new(0, 0, 0))),
+            last_whisperer: Mutex::new(None),
+            signed_arguments: Mutex::new(HashMap::new()),
+            tracked_entities: Mutex::new(EntityTracker::default()),
+            last_equipment: Mutex::new(std::array::from_fn(|_| ItemStack::EMPTY)),
             abilities: Mutex::new(Abilities::default()),
             gamemode: AtomicCell::new(gamemode),
             previous_gamemode: AtomicCell::new(None),
@@ -308,7 +357,12 @@ impl Player {
             wait_for_keep_alive: AtomicBool::new(false),
             keep_alive_id: AtomicI64::new(0),
             last_keep_alive_time: AtomicCell::new(std::time::Instant::now()),
+            last_action_time: AtomicCell::new(std::time::Instant::now()),
+            pending_ping: Mutex::new(None),
+            ping_latency: AtomicCell::new(None),
             last_attacked_ticks: AtomicU32::new(0),
+            fall_flying_ticks: AtomicU32::new(0),
+            firework_boost: Cooldown::default(),
             client_loaded: AtomicBool::new(false),
             client_loaded_timeout: AtomicU32::new(60),
             // Minecraft has no way to change the default permission level of new players.
@@ -318,15 +372,17 @@ impl Player {
                 |op| AtomicCell::new(op.level),
             ),
             inventory: Mutex::new(PlayerInventory::new()),
+            ender_chest: Arc::new(Mutex::new(Box::new(EnderChestContainer::new()))),
             experience_level: AtomicI32::new(0),
             experience_progress: AtomicCell::new(0.0),
             experience_points: AtomicI32::new(0),
             // Default to sending 16 chunks per tick.
             chunk_manager: Mutex::new(ChunkManager::new(16)),
             last_sent_xp: AtomicI32::new(-1),
-            last_sent_health: AtomicI32::new(-1),
-            last_sent_food: AtomicU8::new(0),
-            last_food_saturation: AtomicBool::new(true),
+            last_sent_health_food: AtomicCell::new(None),
+            respawn_point: Mutex::new(None),
+            // The server swaps in its advancements before the player's data is loaded
+            advancements: Mutex::new(PlayerAdvancements::new(Arc::default())),
             has_played_before: AtomicBool::new(false),
             chat_session: Arc::new(Mutex::new(ChatSession::default())), // Placeholder value until the player actually sets their session id
             signature_cache: Mutex::new(MessageCache::default()),
@@ -351,10 +407,13 @@ impl Player {
     }
 
     /// Removes the [`Player`] out of the current [`World`].
+    /// Returns `false` if the player was already removed.
     #[allow(unused_variables)]
-    pub async fn remove(self: &Arc<Self>) {
+    pub async fn remove(self: &Arc<Self>) -> bool {
         let world = self.world().await;
-        world.remove_player(self, true).await;
+        if !world.remove_player(self, true).await {
+            return false;
+        }
 
         let cylindrical = self.watched_section.load();
 
@@ -389,6 +448,7 @@ impl Player {
         level.clean_up_log().await;
 
         //self.world().level.list_cached();
+        true
     }
 
     pub async fn attack(&self, victim: Arc<dyn EntityBase>) {
@@ -397,11 +457,26 @@ impl Player {
         let attacker_entity = &self.living_entity.entity;
         let config = &advanced_config().pvp;
 
+        let victim_name = world.scoreboard_name(victim_entity).await;
+        if !world
+            .scoreboard
+            .lock()
+            .await
+            .can_hurt(&self.gameprofile.name, &victim_name)
+        {
+            return;
+        }
+
         let inventory = self.inventory().lock().await;
         let item_slot = inventory.held_item();
 
-        let base_damage = 1.0;
-        let base_attack_speed = 4.0;
+        let (base_damage, base_attack_speed) = {
+            let attributes = self.living_entity.attributes.lock().await;
+            (
+                attributes.value(Attribute::AttackDamage),
+                attributes.value(Attribute::AttackSpeed),
+            )
+        };
 
         let mut damage_multiplier = 1.0;
         let mut add_damage = 0.0;
@@ -460,6 +535,16 @@ impl Player {
             return;
         }
 
+        if victim
+            .get_living_entity()
+            .is_some_and(|living| living.health.load() <= 0.0)
+        {
+            self.trigger_advancements(AdvancementTrigger::PlayerKilledEntity {
+                entity_type: victim_entity.entity_type.resource_name,
+            })
+            .await;
+        }
+
         if victim.get_living_entity().is_some() {
             let mut knockback_strength = 1.0;
             player_attack_sound(&pos, &world, attack_type).await;
@@ -468,16 +553,15 @@ impl Player {
                 AttackType::Sweeping => {
                     combat::spawn_sweep_particle(attacker_entity, &world, &pos).await;
                 }
+                AttackType::Critical => {
+                    victim_entity
+                        .play_animation(Animation::CriticalEffect)
+                        .await;
+                }
                 _ => {}
             }
             if config.knockback {
-                combat::handle_knockback(
-                    attacker_entity,
-                    &world,
-                    victim_entity,
-                    knockback_strength,
-                )
-                .await;
+                combat::handle_knockback(attacker_entity, victim_entity, knockback_strength).await;
             }
         }
 
@@ -486,12 +570,33 @@ impl Player {
 
     pub async fn show_title(&self, text: &TextComponent, mode: &TitleMode) {
         match mode {
-            TitleMode::Title => self.client.enqueue_packet(&CTitleText::new(text)).await,
-            TitleMode::SubTitle => self.client.enqueue_packet(&CSubtitle::new(text)).await,
-            TitleMode::ActionBar => self.client.enqueue_packet(&CActionBar::new(text)).await,
+            TitleMode::Title => self.send_title(text).await,
+            TitleMode::SubTitle => self.send_subtitle(text).await,
+            TitleMode::ActionBar => self.send_action_bar(text).await,
         }
     }
 
+    /// Shows a title in the middle of the screen, along with the last subtitle sent.
+    pub async fn send_title(&self, text: &TextComponent) {
+        self.client.enqueue_packet(&CTitleText::new(text)).await;
+    }
+
+    /// Sets the subtitle, which only shows up together with the next title.
+    pub async fn send_subtitle(&self, text: &TextComponent) {
+        self.client.enqueue_packet(&CSubtitle::new(text)).await;
+    }
+
+    pub async fn send_action_bar(&self, text: &TextComponent) {
+        self.client.enqueue_packet(&CActionBar::new(text)).await;
+    }
+
+    /// Sets the fade in, stay and fade out times of titles, in ticks.
+    pub async fn set_title_times(&self, fade_in: i32, stay: i32, fade_out: i32) {
+        self.client
+            .enqueue_packet(&CTitleAnimation::new(fade_in, stay, fade_out))
+            .await;
+    }
+
     pub async fn spawn_particle(
         &self,
         position: Vector3<f64>,
@@ -573,13 +678,16 @@ impl Player {
         };
 
         if let Some(chunk_of_chunks) = chunk_of_chunks {
+            let world = self.world().await;
             let chunk_count = chunk_of_chunks.len();
             self.client.send_packet_now(&CChunkBatchStart).await;
             for chunk in chunk_of_chunks {
                 let chunk = chunk.read().await;
                 // TODO: Can we check if we still need to send the chunk? Like if it's a fast moving
                 // player or something.
-                self.client.send_packet_now(&CChunkData(&chunk)).await;
+                self.client
+                    .send_packet_now(&CChunkData::new(&chunk, world.anti_xray.as_ref()))
+                    .await;
             }
             self.client
                 .send_packet_now(&CChunkBatchEnd::new(chunk_count as u16))
@@ -587,6 +695,7 @@ impl Player {
         }
 
         self.tick_counter.fetch_add(1, Relaxed);
+        self.tick_teleport_confirm().await;
 
         if self.mining.load(Relaxed) {
             let pos = self.mining_pos.lock().await;
@@ -615,16 +724,29 @@ impl Player {
         self.last_attacked_ticks.fetch_add(1, Relaxed);
 
         self.living_entity.tick(server).await;
+        self.tick_fall_flying().await;
+        self.tick_world_border().await;
+        self.tick_lodestone_compasses().await;
+        self.tick_advancements(server).await;
         self.hunger_manager.tick(self).await;
+        self.sync_equipment().await;
 
         // experience handling
         self.tick_experience().await;
-        self.tick_health().await;
+        self.sync_health_food().await;
 
         // Timeout/keep alive handling
         self.tick_client_load_timeout();
 
         let now = Instant::now();
+        if self.is_idle_kickable(now) {
+            self.kick(TextComponent::translate(
+                "multiplayer.disconnect.idling",
+                [],
+            ))
+            .await;
+            return;
+        }
         if now.duration_since(self.last_keep_alive_time.load()) >= Duration::from_secs(15) {
             // We never got a response from the last keep alive we sent.
             if self.wait_for_keep_alive.load(Relaxed) {
@@ -640,6 +762,241 @@ impl Player {
         }
     }
 
+    /// Hurts the player while they are outside of the world border and its buffer. The border is
+    /// looked up every tick, so moving it takes effect right away.
+    async fn tick_world_border(&self) {
+        if self.living_entity.health.load() <= 0.0 {
+            return;
+        }
+        let pos = self.living_entity.entity.pos.load();
+        let damage = self
+            .world()
+            .await
+            .worldborder
+            .lock()
+            .await
+            .damage_at(pos.x, pos.z);
+        if let Some(damage) = damage {
+            self.damage(damage, DamageType::OUTSIDE_BORDER).await;
+        }
+    }
+
+    /// Checks the location criteria once a second, tells the client what changed about the
+    /// player's advancements and announces those which got done to everyone on the server.
+    async fn tick_advancements(&self, server: &Server) {
+        if self.tick_counter.load(Relaxed) % 20 == 0 {
+            let pos = self.living_entity.entity.block_pos.load();
+            if let Some(biome) = self.world().await.get_biome(&pos).await {
+                self.trigger_advancements(AdvancementTrigger::Location {
+                    biome: biome.registry_id,
+                })
+                .await;
+            }
+        }
+
+        let announcements = {
+            let mut advancements = self.advancements.lock().await;
+            let Some(update) = advancements.take_update() else {
+                return;
+            };
+            self.client
+                .enqueue_packet(&CUpdateAdvancements::new(
+                    update.reset,
+                    &update.added,
+                    &[],
+                    &update.progress,
+                    // No toasts for what was done before joining
+                    !update.reset,
+                ))
+                .await;
+            update
+                .done
+                .into_iter()
+                .filter_map(|advancement| self.advancement_announcement(advancement))
+                .collect::<Vec<_>>()
+        };
+        if announcements.is_empty()
+            || !self
+                .world()
+                .await
+                .level
+                .level_info
+                .read()
+                .await
+                .game_rules
+                .announce_advancements
+        {
+            return;
+        }
+        for player in server.get_all_players().await {
+            for announcement in &announcements {
+                player.send_system_message(announcement).await;
+            }
+        }
+    }
+
+    /// Obtains the advancement criteria `trigger` matches. The client is sent the progress, and
+    /// the advancements this completed are announced, on the next tick.
+    pub async fn trigger_advancements(&self, trigger: AdvancementTrigger<'_>) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |time| time.as_millis() as i64);
+        self.advancements.lock().await.trigger(&trigger, now);
+    }
+
+    /// The chat message telling everyone the player made `advancement`, if it's announced.
+    fn advancement_announcement(&self, advancement: &Advancement) -> Option<TextComponent> {
+        let display = advancement.display.as_ref()?;
+        if !display.announce_to_chat {
+            return None;
+        }
+        let color = match display.frame {
+            AdvancementFrame::Challenge => NamedColor::DarkPurple,
+            AdvancementFrame::Task | AdvancementFrame::Goal => NamedColor::Green,
+        };
+        let hover = display
+            .title
+            .clone()
+            .color_named(color)
+            .add_text("\n")
+            .add_child(display.description.clone());
+        let title = TextComponent::text("[")
+            .add_child(display.title.clone())
+            .add_text("]")
+            .color_named(color)
+            .hover_event(HoverEvent::show_text(hover));
+        Some(TextComponent::translate(
+            display.frame.chat_translation_key(),
+            [TextComponent::text(self.gameprofile.name.clone()), title],
+        ))
+    }
+
+    /// Lets the tracked compasses in the inventory spin once the lodestone they point to in this
+    /// world is gone. Lodestones in chunks which aren't loaded are assumed to still be there.
+    async fn tick_lodestone_compasses(&self) {
+        let world = self.world().await;
+        let dimension = world.dimension_type.name().to_string();
+        let mut inventory = self.inventory.lock().await;
+        let targets = inventory
+            .slots()
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, stack)| {
+                let (target_dimension, pos) = stack
+                    .filter(|stack| stack.is_compass())?
+                    .lodestone_target()?;
+                (target_dimension == dimension).then_some((slot, pos))
+            })
+            .collect::<Vec<_>>();
+        for (slot, pos) in targets {
+            let Some(block) = world.get_loaded_block(&pos).await else {
+                continue;
+            };
+            if block == Block::LODESTONE {
+                continue;
+            }
+            let compass = {
+                let slots = inventory.slots_mut();
+                let Some(compass) = slots[slot].as_mut() else {
+                    continue;
+                };
+                if !compass.forget_lodestone(&dimension, pos) {
+                    continue;
+                }
+                compass.clone()
+            };
+            self.update_single_slot(&mut inventory, slot, compass).await;
+        }
+    }
+
+    /// Glides the player while they are fall flying, wearing down their elytra and applying the
+    /// push of a firework rocket. Landing, swimming or losing the elytra ends the flight.
+    async fn tick_fall_flying(&self) {
+        let entity = &self.living_entity.entity;
+        if !entity.fall_flying.load(Relaxed) {
+            return;
+        }
+        let wears_elytra = self
+            .inventory
+            .lock()
+            .await
+            .armor_slots()
+            .get(1)
+            .copied()
+            .flatten()
+            .is_some_and(elytra::is_usable);
+        if !wears_elytra
+            || entity.on_ground.load(Relaxed)
+            || self.living_entity.is_in_water().await
+            || self.abilities.lock().await.flying
+        {
+            self.stop_fall_flying().await;
+            return;
+        }
+
+        let (yaw, pitch) = (entity.yaw.load(), entity.pitch.load());
+        entity
+            .velocity
+            .store(elytra::glide(entity.velocity.load(), yaw, pitch));
+        if self.firework_boost.is_active() {
+            self.firework_boost.tick();
+            // The client moves the player itself, it has to be told about the boost
+            entity
+                .set_velocity(elytra::firework_boost(entity.velocity.load(), yaw, pitch))
+                .await;
+        }
+
+        let ticks = self.fall_flying_ticks.fetch_add(1, Relaxed) + 1;
+        if ticks % elytra::DURABILITY_INTERVAL == 0 && self.gamemode.load() != GameMode::Creative {
+            let mut inventory = self.inventory.lock().await;
+            if let Ok(Some(elytra)) = inventory.get_slot(SLOT_CHEST) {
+                elytra.patch.damage = Some(elytra.patch.damage.unwrap_or(0) + 1);
+            }
+            drop(inventory);
+            self.send_inventory().await;
+        }
+    }
+
+    pub async fn stop_fall_flying(&self) {
+        self.fall_flying_ticks.store(0, Relaxed);
+        self.firework_boost.set(0);
+        self.living_entity.entity.set_fall_flying(false).await;
+    }
+
+    /// Called when a fall flying player moved by `delta`, hurting them if they flew into a wall.
+    pub async fn fall_flying_moved(&self, delta: Vector3<f64>, horizontal_collision: bool) {
+        let entity = &self.living_entity.entity;
+        if !entity.fall_flying.load(Relaxed) {
+            return;
+        }
+        let speed_before = entity.velocity.load().horizontal_length();
+        entity.velocity.store(delta);
+        if !horizontal_collision {
+            return;
+        }
+        if let Some(damage) = elytra::wall_impact_damage(speed_before, delta.horizontal_length()) {
+            self.damage(damage, DamageType::FLY_INTO_WALL).await;
+        }
+    }
+
+    /// Resets the idle timer, called whenever the player does something meaningful.
+    pub fn update_last_action_time(&self) {
+        self.last_action_time.store(Instant::now());
+    }
+
+    fn is_idle_kickable(&self, now: Instant) -> bool {
+        if BASIC_CONFIG.idle_timeout_exempts_ops
+            && self.permission_lvl.load() >= BASIC_CONFIG.op_permission_level
+        {
+            return false;
+        }
+        is_idle(
+            self.last_action_time.load(),
+            now,
+            BASIC_CONFIG.player_idle_timeout,
+        )
+    }
+
     async fn continue_mining(
         &self,
         location: BlockPos,
@@ -659,6 +1016,51 @@ impl Player {
         }
     }
 
+    /// Stops breaking the current block, returning its position if the player was mining, so
+    /// the destroy stage can be cleared for other players.
+    pub async fn stop_mining(&self) -> Option<BlockPos> {
+        take_mining_pos(
+            &self.mining,
+            &self.current_block_destroy_stage,
+            &self.mining_pos,
+        )
+        .await
+    }
+
+    /// Broadcasts equipment which changed since the last sync to other players, playing the equip
+    /// sound for newly worn armor.
+    pub async fn sync_equipment(&self) {
+        let current = {
+            let inventory = self.inventory.lock().await;
+            current_equipment(&inventory)
+        };
+        let changes = {
+            let mut last_equipment = self.last_equipment.lock().await;
+            let changes = equipment_changes(&last_equipment, &current);
+            *last_equipment = current;
+            changes
+        };
+        if changes.is_empty() {
+            return;
+        }
+
+        self.living_entity.send_equipment_changes(&changes).await;
+        if self.gamemode.load() == GameMode::Spectator {
+            return;
+        }
+        let sound = changes
+            .iter()
+            .filter(|(slot, _)| is_armor_slot(*slot))
+            .find_map(|(_, stack)| equip_sound(&stack.item));
+        if let Some(sound) = sound {
+            let position = self.living_entity.entity.pos.load();
+            self.world()
+                .await
+                .play_sound(sound, SoundCategory::Players, &position)
+                .await;
+        }
+    }
+
     pub async fn jump(&self) {
         if self.living_entity.entity.sprinting.load(Relaxed) {
             self.add_exhaustion(0.2).await;
@@ -736,6 +1138,16 @@ impl Player {
         )
     }
 
+    /// Whether an entity with this bounding box is close enough to be picked up or otherwise touched
+    /// by the player.
+    pub fn touches_entity(&self, entity_box: &BoundingBox) -> bool {
+        touches_entity(
+            self.gamemode.load(),
+            &self.living_entity.entity.bounding_box.load(),
+            entity_box,
+        )
+    }
+
     /// Updates the current abilities the player has.
     pub async fn send_abilities_update(&self) {
         let mut b = 0i8;
@@ -762,6 +1174,12 @@ impl Player {
             .await;
     }
 
+    /// Sends the server's MOTD and icon, which the client shows in its pause menu.
+    pub async fn send_server_data(&self, server: &Server) {
+        let server_data = server.get_status().lock().await.server_data();
+        self.client.enqueue_packet(&server_data).await;
+    }
+
     /// Updates the client of the player's current permission level.
     pub async fn send_permission_lvl_update(&self) {
         let status = match self.permission_lvl.load() {
@@ -790,25 +1208,22 @@ impl Player {
 
     /// Sends the world time to only this player.
     pub async fn send_time(&self, world: &World) {
-        let l_world = world.level_time.lock().await;
-        self.client
-            .enqueue_packet(&CUpdateTime::new(
-                l_world.world_age,
-                l_world.time_of_day,
-                true,
-            ))
-            .await;
+        let packet = world.level_time.lock().await.update_packet();
+        self.client.enqueue_packet(&packet).await;
     }
 
-    /// Sends a world's mobs to only this player.
-    // TODO: This should be optimized for larger servers based on the player's current chunk.
-    pub async fn send_mobs(&self, world: &World) {
-        let entities = world.entities.read().await.clone();
-        for entity in entities.values() {
-            self.client
-                .enqueue_packet(&entity.get_entity().create_spawn_packet())
-                .await;
-        }
+    /// Sends a ping the client answers with a pong carrying the same id.
+    ///
+    /// Unlike keep alives, pings are not required; they are used to measure the latency, which is
+    /// stored in `ping_latency` once the client answers.
+    pub async fn ping(&self, id: i32) {
+        *self.pending_ping.lock().await = Some((id, Instant::now()));
+        self.client.enqueue_packet(&CPing::new(id)).await;
+    }
+
+    /// Returns the round trip time if the pong answers the pending ping.
+    pub(crate) async fn take_ping_latency(&self, id: i32) -> Option<Duration> {
+        pong_latency(&mut *self.pending_ping.lock().await, id, Instant::now())
     }
 
     async fn unload_watched_chunks(&self, world: &World) {
@@ -828,6 +1243,51 @@ impl Player {
         ));
     }
 
+    /// Moves the player from `current_world` into `new_world` without telling the client, which
+    /// has to be sent a respawn.
+    pub async fn move_to_world(
+        self: &Arc<Self>,
+        current_world: &Arc<World>,
+        new_world: &Arc<World>,
+    ) {
+        current_world.remove_player(self, false).await;
+        *self.living_entity.entity.world.write().await = new_world.clone();
+        new_world
+            .players
+            .write()
+            .await
+            .insert(self.gameprofile.id, self.clone());
+        self.unload_watched_chunks(current_world).await;
+    }
+
+    /// The world the player respawns in: the one of their respawn point, e.g. the nether for a
+    /// respawn anchor, if it is loaded and the respawn point is still valid, otherwise the
+    /// current one.
+    pub async fn respawn_world(&self, server: &Server) -> Arc<World> {
+        let current = self.world().await;
+        let dimension = self
+            .respawn_point
+            .lock()
+            .await
+            .as_ref()
+            .map(|point| point.dimension.clone());
+        let Some(dimension) = dimension.filter(|dimension| *dimension != current.dimension_type)
+        else {
+            return current;
+        };
+        let world = server
+            .worlds
+            .read()
+            .await
+            .iter()
+            .find(|world| world.dimension_type == dimension)
+            .cloned();
+        match world {
+            Some(world) if self.respawn_point_in(&world).await.is_some() => world,
+            _ => current,
+        }
+    }
+
     /// Teleports the player to a different world or dimension with an optional position, yaw, and pitch.
     pub async fn teleport_world(
         self: &Arc<Self>,
@@ -837,25 +1297,22 @@ impl Player {
         pitch: Option<f32>,
     ) {
         let current_world = self.living_entity.entity.world.read().await.clone();
-        let info = &new_world.level.level_info;
+        let (spawn, spawn_angle) = new_world.spawn_point().await;
         let position = if let Some(pos) = position {
             pos
         } else {
             Vector3::new(
-                f64::from(info.spawn_x),
+                f64::from(spawn.0.x),
                 f64::from(
                     new_world
-                        .get_top_block(Vector2::new(
-                            f64::from(info.spawn_x) as i32,
-                            f64::from(info.spawn_z) as i32,
-                        ))
+                        .get_top_block(Vector2::new(spawn.0.x, spawn.0.z))
                         .await
                         + 1,
                 ),
-                f64::from(info.spawn_z),
+                f64::from(spawn.0.z),
             )
         };
-        let yaw = yaw.unwrap_or(info.spawn_angle);
+        let yaw = yaw.unwrap_or(spawn_angle);
         let pitch = pitch.unwrap_or(10.0);
 
         send_cancellable! {{
@@ -876,11 +1333,7 @@ impl Player {
                 let new_world = event.new_world;
 
                 self.set_client_loaded(false);
-                let uuid = self.gameprofile.id;
-                current_world.remove_player(self, false).await;
-                *self.living_entity.entity.world.write().await = new_world.clone();
-                new_world.players.write().await.insert(uuid, self.clone());
-                self.unload_watched_chunks(&current_world).await;
+                self.move_to_world(&current_world, &new_world).await;
                 let last_pos = self.living_entity.last_pos.load();
                 let death_dimension = self.world().await.dimension_type.name();
                 let death_location = BlockPos(Vector3::new(
@@ -890,7 +1343,7 @@ impl Player {
                 ));
                 self.client
                     .send_packet_now(&CRespawn::new(
-                        (new_world.dimension_type as u8).into(),
+                        new_world.dimension_type_id.into(),
                         new_world.dimension_type.name(),
                         0, // seed
                         self.gamemode.load() as u8,
@@ -908,19 +1361,68 @@ impl Player {
                 self.clone().request_teleport(position, yaw, pitch).await;
                 self.living_entity.last_pos.store(position);
 
-                new_world.send_world_info(self, position, yaw, pitch).await;
+                new_world.send_world_info(self).await;
+                self.trigger_advancements(AdvancementTrigger::ChangedDimension {
+                    from: &current_world.dimension_type.name().to_string(),
+                    to: &new_world.dimension_type.name().to_string(),
+                })
+                .await;
             }
         }}
     }
 
+    /// Counts the time the player stands in a nether portal and sends them to the other dimension
+    /// once they waited long enough.
+    pub async fn tick_portal(self: &Arc<Self>, server: &Server) {
+        let entity = &self.living_entity.entity;
+        let world = self.world().await;
+        let in_portal = world
+            .get_block(&entity.block_pos.load())
+            .await
+            .is_ok_and(|block| block == Block::NETHER_PORTAL);
+        let wait_time = if self.abilities.lock().await.invulnerable {
+            1
+        } else {
+            PLAYER_PORTAL_WAIT_TIME
+        };
+        if !entity.portal.tick(in_portal, wait_time) {
+            return;
+        }
+        let Some((dimension, pos)) =
+            nether_portal_destination(&world.dimension_type, entity.pos.load())
+        else {
+            return;
+        };
+        let Some(destination) = server
+            .worlds
+            .read()
+            .await
+            .iter()
+            .find(|world| world.dimension_type == dimension)
+            .cloned()
+        else {
+            log::debug!(
+                "{} used a portal to {dimension:?}, which is not loaded",
+                self.gameprofile.name
+            );
+            return;
+        };
+        let arrival = destination
+            .portal_arrival(BlockPos::floored(pos.x, pos.y, pos.z))
+            .await;
+        self.teleport_world(
+            destination,
+            Some(arrival),
+            Some(entity.yaw.load()),
+            Some(entity.pitch.load()),
+        )
+        .await;
+    }
+
     /// `yaw` and `pitch` are in degrees.
     /// Rarly used, for example when waking up the player from a bed or their first time spawn. Otherwise, the `teleport` method should be used.
     /// The player should respond with the `SConfirmTeleport` packet.
     pub async fn request_teleport(self: &Arc<Self>, position: Vector3<f64>, yaw: f32, pitch: f32) {
-        // This is the ultra special magic code used to create the teleport id
-        // This returns the old value
-        // This operation wraps around on overflow.
-
         send_cancellable! {{
             PlayerTeleportEvent {
                 player: self.clone(),
@@ -931,28 +1433,51 @@ impl Player {
 
             'after: {
                 let position = event.to;
-                let i = self
-                    .teleport_id_count
-                    .fetch_add(1, Relaxed);
-                let teleport_id = i + 1;
                 self.living_entity.set_pos(position);
                 let entity = &self.living_entity.entity;
                 entity.set_rotation(yaw, pitch);
-                *self.awaiting_teleport.lock().await = Some((teleport_id.into(), position));
-                self.client
-                    .send_packet_now(&CPlayerPosition::new(
-                        teleport_id.into(),
-                        position,
-                        Vector3::new(0.0, 0.0, 0.0),
-                        yaw,
-                        pitch,
-                        // TODO
-                        &[],
-                    )).await;
+                self.send_teleport(position, yaw, pitch).await;
             }
         }}
     }
 
+    /// Sends the client a teleport with a new id, its movement is ignored until it confirms it.
+    pub async fn send_teleport(&self, position: Vector3<f64>, yaw: f32, pitch: f32) {
+        // This returns the old value
+        // This operation wraps around on overflow.
+        let teleport_id = self.teleport_id_count.fetch_add(1, Relaxed).wrapping_add(1);
+        self.awaiting_teleport.lock().await.start(
+            teleport_id,
+            position,
+            self.tick_counter.load(Relaxed),
+        );
+        self.client
+            .send_packet_now(&CPlayerPosition::new(
+                teleport_id.into(),
+                position,
+                Vector3::new(0.0, 0.0, 0.0),
+                yaw,
+                pitch,
+                // TODO
+                &[],
+            ))
+            .await;
+    }
+
+    /// Sends a teleport again if the client did not confirm it in time, like vanilla does.
+    async fn tick_teleport_confirm(&self) {
+        let timed_out = self
+            .awaiting_teleport
+            .lock()
+            .await
+            .timed_out(self.tick_counter.load(Relaxed));
+        if let Some(pending) = timed_out {
+            let entity = &self.living_entity.entity;
+            self.send_teleport(pending.position, entity.yaw.load(), entity.pitch.load())
+                .await;
+        }
+    }
+
     /// Teleports the player to a different position with an optional yaw and pitch.
     /// This method is identical to `entity.teleport()` but emits a `PlayerTeleportEvent` instead of a `EntityTeleportEvent`.
     pub async fn teleport(self: &Arc<Self>, position: Vector3<f64>, yaw: f32, pitch: f32) {
@@ -971,7 +1496,7 @@ impl Player {
                     .world
                     .read()
                     .await
-                    .broadcast_packet_except(&[self.gameprofile.id], &CEntityPositionSync::new(
+                    .broadcast_to_trackers_except(self.living_entity.entity.entity_id, &[self.gameprofile.id], &CEntityPositionSync::new(
                         self.living_entity.entity.entity_id.into(),
                         position,
                         Vector3::new(0.0, 0.0, 0.0),
@@ -1028,9 +1553,9 @@ impl Player {
         self.client.close();
     }
 
-    pub fn can_food_heal(&self) -> bool {
+    pub async fn can_food_heal(&self) -> bool {
         let health = self.living_entity.health.load();
-        let max_health = 20.0; // TODO
+        let max_health = self.living_entity.max_health().await;
         health > 0.0 && health < max_health
     }
 
@@ -1044,39 +1569,50 @@ impl Player {
 
     pub async fn heal(&self, additional_health: f32) {
         self.living_entity.heal(additional_health).await;
-        self.send_health().await;
+        self.sync_health_food().await;
     }
 
-    pub async fn send_health(&self) {
-        self.client
-            .enqueue_packet(&CSetHealth::new(
-                self.living_entity.health.load(),
-                self.hunger_manager.level.load().into(),
-                self.hunger_manager.saturation.load(),
-            ))
-            .await;
-    }
-
-    pub async fn tick_health(&self) {
-        let health = self.living_entity.health.load() as i32;
-        let food = self.hunger_manager.level.load();
-        let saturation = self.hunger_manager.saturation.load();
-
-        let last_health = self.last_sent_health.load(Relaxed);
-        let last_food = self.last_sent_food.load(Relaxed);
-        let last_saturation = self.last_food_saturation.load(Relaxed);
-
-        if health != last_health || food != last_food || (saturation == 0.0) != last_saturation {
-            self.last_sent_health.store(health, Relaxed);
-            self.last_sent_food.store(food, Relaxed);
-            self.last_food_saturation.store(saturation == 0.0, Relaxed);
-            self.send_health().await;
+    /// Sends the health, food level and saturation to the client, unless it already knows them.
+    /// Called after every change to them and once per tick, for changes made elsewhere.
+    pub async fn sync_health_food(&self) {
+        if let Some(packet) = health_food_update(
+            &self.last_sent_health_food,
+            self.living_entity.health.load(),
+            self.hunger_manager.level.load(),
+            self.hunger_manager.saturation.load(),
+        ) {
+            self.client.enqueue_packet(&packet).await;
         }
     }
 
     pub async fn set_health(&self, health: f32) {
         self.living_entity.set_health(health).await;
-        self.send_health().await;
+        self.sync_health_food().await;
+    }
+
+    /// The respawn point of the player if it is in `world`. A respawn point whose bed or respawn
+    /// anchor is gone is reset.
+    pub async fn respawn_point_in(&self, world: &World) -> Option<RespawnPoint> {
+        let mut respawn_point = self.respawn_point.lock().await;
+        let point = respawn_point
+            .as_ref()
+            .filter(|point| point.dimension == world.dimension_type)?
+            .clone();
+        if point.forced
+            || world
+                .get_block_state_id(&point.position)
+                .await
+                .is_ok_and(|state_id| is_respawn_block(state_id, world.respawn_anchor_works))
+        {
+            return Some(point);
+        }
+        *respawn_point = None;
+        self.send_system_message(&TextComponent::translate(
+            "block.minecraft.spawn.not_valid",
+            [],
+        ))
+        .await;
+        None
     }
 
     pub fn tick_client_load_timeout(&self) {
@@ -1089,15 +1625,17 @@ impl Player {
 
     pub async fn kill(&self) {
         self.living_entity.kill().await;
-        self.handle_killed().await;
+        self.handle_killed(DamageType::GENERIC_KILL, None).await;
     }
 
-    async fn handle_killed(&self) {
+    /// Shows the death screen, the client answers with a perform respawn [`SClientCommand`] once
+    /// the player clicks respawn (or right away when `immediate_respawn` is enabled).
+    async fn handle_killed(&self, damage_type: DamageType, killer: Option<TextComponent>) {
         self.set_client_loaded(false);
         self.client
             .send_packet_now(&CCombatDeath::new(
                 self.entity_id().into(),
-                &TextComponent::text("noob"),
+                &death_message(&damage_type, &self.gameprofile.name, killer),
             ))
             .await;
     }
@@ -1119,6 +1657,7 @@ impl Player {
 
             'after: {
                 let gamemode = event.new_gamemode;
+                let was_spectator = self.gamemode.load() == GameMode::Spectator;
                 self.gamemode.store(gamemode);
                 // TODO: Fix this when mojang fixes it
                 // This is intentional to keep the pure vanilla mojang experience
@@ -1134,6 +1673,10 @@ impl Player {
                     matches!(gamemode, GameMode::Creative | GameMode::Spectator),
                     Relaxed,
                 );
+                let is_spectator = gamemode == GameMode::Spectator;
+                if was_spectator != is_spectator {
+                    self.living_entity.entity.set_invisible(is_spectator).await;
+                }
                 self.living_entity
                     .entity
                     .world
@@ -1159,22 +1702,24 @@ impl Player {
 
     /// Send the player's skin layers and used hand to all players.
     pub async fn send_client_information(&self) {
+        let meta_data = self.client_information_meta_data().await;
+        self.living_entity.entity.send_meta_data(&meta_data).await;
+    }
+
+    async fn client_information_meta_data(&self) -> [Metadata<u8>; 2] {
         let config = self.config.read().await;
-        self.living_entity
-            .entity
-            .send_meta_data(&[
-                Metadata::new(
-                    DATA_PLAYER_MODE_CUSTOMISATION,
-                    MetaDataType::Byte,
-                    config.skin_parts,
-                ),
-                Metadata::new(
-                    DATA_PLAYER_MAIN_HAND,
-                    MetaDataType::Byte,
-                    config.main_hand as u8,
-                ),
-            ])
-            .await;
+        [
+            Metadata::new(
+                DATA_PLAYER_MODE_CUSTOMISATION,
+                MetaDataType::Byte,
+                config.skin_parts,
+            ),
+            Metadata::new(
+                DATA_PLAYER_MAIN_HAND,
+                MetaDataType::Byte,
+                config.main_hand as u8,
+            ),
+        ]
     }
 
     pub async fn can_harvest(&self, block: &BlockState, block_name: &str) -> bool {
@@ -1257,7 +1802,51 @@ impl Player {
             .await;
     }
 
+    /// Sends a chat message signed by `sender`, so the player can verify and report it.
+    pub async fn send_signed_message(
+        &self,
+        sender: &Player,
+        message: &SignedMessage,
+        unsigned_content: Option<&TextComponent>,
+        chat_type: u8,
+        sender_name: &TextComponent,
+        target_name: Option<&TextComponent>,
+    ) {
+        let messages_received = self.chat_session.lock().await.messages_received;
+        let packet = CPlayerChatMessage::new(
+            VarInt(messages_received),
+            sender.gameprofile.id,
+            VarInt(message.index),
+            Some(message.signature.clone()),
+            message.message.clone(),
+            message.timestamp,
+            message.salt,
+            message.last_seen.indexed_for(self).await,
+            unsigned_content.cloned(),
+            FilterType::PassThrough,
+            (chat_type + 1).into(),
+            sender_name.clone(),
+            target_name.cloned(),
+        );
+        self.client.enqueue_packet(&packet).await;
+
+        let mut signature_cache = self.signature_cache.lock().await;
+        signature_cache.add_seen_signature(&message.signature);
+        if self.gameprofile.id != sender.gameprofile.id {
+            // Sender may update recipient on signatures recipient hasn't seen
+            signature_cache.cache_signatures(message.last_seen.as_ref());
+        }
+        drop(signature_cache);
+        self.chat_session.lock().await.messages_received += 1;
+    }
+
     pub async fn drop_item(&self, item_id: u16, count: u32) {
+        let item = Item::from_id(item_id).expect("We passed a bad item id into drop_item");
+        self.drop_stack(ItemStack::new(count as u8, item)).await;
+    }
+
+    /// Throws the stack in front of the player, keeping its components.
+    pub async fn drop_stack(&self, item_stack: ItemStack) {
         let entity = self.world().await.create_entity(
             self.living_entity.entity.pos.load()
                 + Vector3::new(0.0, f64::from(EntityType::PLAYER.eye_height) - 0.3, 0.0),
@@ -1281,7 +1870,7 @@ impl Player {
 
         // TODO: Merge stacks together
         let item_entity =
-            Arc::new(ItemEntity::new_with_velocity(entity, item_id, count, velocity, 40).await);
+            Arc::new(ItemEntity::new_with_velocity(entity, item_stack, velocity, 40).await);
         self.world().await.spawn_entity(item_entity.clone()).await;
         item_entity.send_meta_packet().await;
     }
@@ -1290,7 +1879,7 @@ impl Player {
         let mut inv = self.inventory.lock().await;
         if let Some(item_stack) = inv.held_item_mut() {
             let drop_amount = if drop_stack { item_stack.item_count } else { 1 };
-            self.drop_item(item_stack.item.id, u32::from(drop_amount))
+            self.drop_stack(item_stack.copy_with_count(drop_amount))
                 .await;
             inv.decrease_current_stack(drop_amount);
         }
@@ -1482,6 +2071,7 @@ impl NBTStorage for Player {
     async fn write_nbt(&self, nbt: &mut NbtCompound) {
         self.living_entity.write_nbt(nbt).await;
         self.inventory.lock().await.write_nbt(nbt).await;
+        write_ender_items(self.ender_chest.lock().await.as_ref(), nbt);
 
         self.abilities.lock().await.write_nbt(nbt).await;
 
@@ -1495,6 +2085,9 @@ impl NBTStorage for Player {
         }
 
         nbt.put_bool("HasPlayedBefore", self.has_played_before.load(Relaxed));
+        if let Some(respawn_point) = self.respawn_point.lock().await.as_ref() {
+            respawn_point.write_nbt(nbt);
+        }
 
         // Store food level, saturation, exhaustion, and tick timer
         self.hunger_manager.write_nbt(nbt).await;
@@ -1503,12 +2096,16 @@ impl NBTStorage for Player {
     async fn read_nbt(&mut self, nbt: &mut NbtCompound) {
         self.living_entity.read_nbt(nbt).await;
         self.inventory.lock().await.read_nbt(nbt).await;
+        read_ender_items(self.ender_chest.lock().await.as_mut(), nbt);
         self.abilities.lock().await.read_nbt(nbt).await;
 
-        self.gamemode.store(
-            GameMode::try_from(nbt.get_byte("playerGameType").unwrap_or(0))
-                .unwrap_or(GameMode::Survival),
-        );
+        let gamemode = GameMode::try_from(nbt.get_byte("playerGameType").unwrap_or(0))
+            .unwrap_or(GameMode::Survival);
+        self.gamemode.store(gamemode);
+        if gamemode == GameMode::Spectator {
+            // Spectators always fly, even if the saved abilities say otherwise
+            self.abilities.lock().await.set_for_gamemode(gamemode);
+        }
 
         self.previous_gamemode.store(
             nbt.get_byte("previousPlayerGameType")
@@ -1517,6 +2114,7 @@ impl NBTStorage for Player {
 
         self.has_played_before
             .store(nbt.get_bool("HasPlayedBefore").unwrap_or(false), Relaxed);
+        *self.respawn_point.lock().await = RespawnPoint::read_nbt(nbt);
 
         // Load food level, saturation, exhaustion, and tick timer
         self.hunger_manager.read_nbt(nbt).await;
@@ -1531,6 +2129,40 @@ impl NBTStorage for Player {
     }
 }
 
+fn write_ender_items(ender_chest: &dyn Container, nbt: &mut NbtCompound) {
+    let items: Vec<NbtTag> = ender_chest
+        .all_slots_ref()
+        .iter()
+        .enumerate()
+        .filter_map(|(slot, stack)| {
+            let stack = (*stack)?;
+            let mut item_compound = NbtCompound::new();
+            item_compound.put_byte("Slot", slot as i8);
+            stack.write_item_stack(&mut item_compound);
+            Some(NbtTag::Compound(item_compound))
+        })
+        .collect();
+    nbt.put("EnderItems", NbtTag::List(items.into_boxed_slice()));
+}
+
+fn read_ender_items(ender_chest: &mut dyn Container, nbt: &NbtCompound) {
+    let mut slots = ender_chest.all_slots();
+    for tag in nbt.get_list("EnderItems").into_iter().flatten() {
+        let Some(item_compound) = tag.extract_compound() else {
+            continue;
+        };
+        let Some(slot_byte) = item_compound.get_byte("Slot") else {
+            continue;
+        };
+        if let (Some(slot), Some(item_stack)) = (
+            slots.get_mut(slot_byte as usize),
+            ItemStack::read_item_stack(item_compound),
+        ) {
+            **slot = Some(item_stack);
+        }
+    }
+}
+
 #[async_trait]
 impl NBTStorage for PlayerInventory {
     async fn write_nbt(&self, nbt: &mut NbtCompound) {
@@ -1595,24 +2227,65 @@ impl NBTStorage for PlayerInventory {
 #[async_trait]
 impl EntityBase for Player {
     async fn damage(&self, amount: f32, damage_type: DamageType) -> bool {
-        self.world()
+        self.damage_with_context(amount, damage_type, None, None, None)
             .await
+    }
+
+    async fn damage_with_context(
+        &self,
+        amount: f32,
+        damage_type: DamageType,
+        position: Option<Vector3<f64>>,
+        source: Option<&Entity>,
+        cause: Option<&Entity>,
+    ) -> bool {
+        let world = self.world().await;
+        world
             .play_sound(
                 Sound::EntityPlayerHurt,
                 SoundCategory::Players,
                 &self.living_entity.entity.pos.load(),
             )
             .await;
-        let result = self.living_entity.damage(amount, damage_type).await;
+        let result = self
+            .living_entity
+            .damage_with_context(amount, damage_type, position, source, cause)
+            .await;
         if result {
+            self.sync_health_food().await;
             let health = self.living_entity.health.load();
             if health <= 0.0 {
-                self.handle_killed().await;
+                // Whoever is behind the damage gets the blame, not the arrow they shot
+                let killer = match cause.or(source) {
+                    Some(killer) => Some(world.display_name(killer).await),
+                    None => None,
+                };
+                self.handle_killed(damage_type, killer).await;
             }
         }
         result
     }
 
+    async fn send_tracking_data(&self, player: &Player) {
+        let meta_data = self.client_information_meta_data().await;
+        self.living_entity
+            .entity
+            .send_meta_data_to(player, &meta_data)
+            .await;
+        // The equipment everyone else sees, anything newer goes out with the next sync
+        let equipment = equipment_changes(
+            &std::array::from_fn(|_| ItemStack::EMPTY),
+            &*self.last_equipment.lock().await,
+        );
+        self.living_entity
+            .send_equipment_to(player, &equipment)
+            .await;
+    }
+
+    fn nbt_storage(&self) -> &dyn NBTStorage {
+        self
+    }
+
     fn get_entity(&self) -> &Entity {
         &self.living_entity.entity
     }
@@ -1635,7 +2308,11 @@ impl Player {
 
     pub async fn process_packets(self: &Arc<Self>, server: &Arc<Server>) {
         while let Some(packet) = self.client.get_packet().await {
-            let packet_result = self.handle_play_packet(server, &packet).await;
+            let packet_result = if self.client.connection_state.load() == ConnectionState::Config {
+                self.handle_reconfiguration_packet(server, &packet).await
+            } else {
+                self.handle_play_packet(server, &packet).await
+            };
             match packet_result {
                 Ok(()) => {}
                 Err(e) => {
@@ -1655,6 +2332,46 @@ impl Player {
         }
     }
 
+    /// Sends the player back through configuration to resend the registries and tags, and then
+    /// back into its world. The player keeps its position, inventory and everything else.
+    pub async fn reconfigure(&self) {
+        self.set_client_loaded(false);
+        self.unload_watched_chunks(&*self.world().await).await;
+        self.client.start_reconfiguration().await;
+    }
+
+    async fn handle_configuration_acknowledged(&self, server: &Server) {
+        if self.client.enter_reconfiguration() {
+            self.client.send_configuration(server).await;
+        } else {
+            self.kick(TextComponent::text(
+                "Unexpected configuration acknowledgement",
+            ))
+            .await;
+        }
+    }
+
+    /// Handles a packet sent while the player is being reconfigured.
+    async fn handle_reconfiguration_packet(
+        self: &Arc<Self>,
+        server: &Arc<Server>,
+        packet: &RawPacket,
+    ) -> Result<(), Box<dyn PumpkinError>> {
+        self.client
+            .handle_config_packet(server, packet)
+            .await
+            .map_err(|err| Box::new(err) as Box<dyn PumpkinError>)?;
+        if self.client.connection_state.load() == ConnectionState::Play {
+            // The client forgot its world, so it is sent again like when joining
+            self.tracked_entities.lock().await.clear();
+            self.world()
+                .await
+                .spawn_player(&BASIC_CONFIG, self.clone(), server)
+                .await;
+        }
+        Ok(())
+    }
+
     #[allow(clippy::too_many_lines)]
     pub async fn handle_play_packet(
         self: &Arc<Self>,
@@ -1662,24 +2379,37 @@ impl Player {
         packet: &RawPacket,
     ) -> Result<(), Box<dyn PumpkinError>> {
         let payload = &packet.payload[..];
+        if resets_idle_timer(packet.id) {
+            self.update_last_action_time();
+        }
         match packet.id {
             SConfirmTeleport::PACKET_ID => {
                 self.handle_confirm_teleport(SConfirmTeleport::read(payload)?)
                     .await;
             }
+            SConfigurationAcknowledged::PACKET_ID => {
+                self.handle_configuration_acknowledged(server).await;
+            }
             SChatCommand::PACKET_ID => {
                 self.handle_chat_command(server, &(SChatCommand::read(payload)?))
                     .await;
             }
+            SChatCommandSigned::PACKET_ID => {
+                self.handle_signed_chat_command(server, SChatCommandSigned::read(payload)?)
+                    .await;
+            }
             SChatMessage::PACKET_ID => {
                 self.handle_chat_message(SChatMessage::read(payload)?).await;
             }
+            SChatAck::PACKET_ID => {
+                self.handle_chat_ack(SChatAck::read(payload)?).await;
+            }
             SClientInformationPlay::PACKET_ID => {
                 self.handle_client_information(SClientInformationPlay::read(payload)?)
                     .await;
             }
             SClientCommand::PACKET_ID => {
-                self.handle_client_status(SClientCommand::read(payload)?)
+                self.handle_client_status(server, SClientCommand::read(payload)?)
                     .await;
             }
             SPlayerInput::PACKET_ID => {
@@ -1729,6 +2459,9 @@ impl Player {
                 self.handle_play_ping_request(SPlayPingRequest::read(payload)?)
                     .await;
             }
+            SPong::PACKET_ID => {
+                self.handle_pong(SPong::read(payload)?).await;
+            }
             SClickContainer::PACKET_ID => {
                 self.handle_click_container(server, SClickContainer::read(payload)?)
                     .await?;
@@ -1790,6 +2523,50 @@ pub enum TitleMode {
     ActionBar,
 }
 
+/// Where a player respawns instead of the world spawn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RespawnPoint {
+    pub dimension: DimensionType,
+    pub position: BlockPos,
+    pub angle: f32,
+    /// Set by `/spawnpoint`, the player respawns there even without a bed or respawn anchor.
+    pub forced: bool,
+}
+
+impl RespawnPoint {
+    fn write_nbt(&self, nbt: &mut NbtCompound) {
+        let mut respawn = NbtCompound::new();
+        respawn.put_string("dimension", self.dimension.name().to_string());
+        let pos = self.position.0;
+        respawn.put("pos", NbtTag::IntArray([pos.x, pos.y, pos.z].into()));
+        respawn.put_float("angle", self.angle);
+        respawn.put_bool("forced", self.forced);
+        nbt.put_component("respawn", respawn);
+    }
+
+    fn read_nbt(nbt: &NbtCompound) -> Option<Self> {
+        let respawn = nbt.get_compound("respawn")?;
+        let &[x, y, z] = respawn.get_int_array("pos")? else {
+            return None;
+        };
+        let dimension = respawn
+            .get_string("dimension")
+            .and_then(|id| id.split_once(':'))
+            .map_or(DimensionType::Overworld, |(namespace, path)| {
+                DimensionType::from_id(Identifier {
+                    namespace: namespace.to_string(),
+                    path: path.to_string(),
+                })
+            });
+        Some(Self {
+            dimension,
+            position: BlockPos::new(x, y, z),
+            angle: respawn.get_float("angle").unwrap_or(0.0),
+            forced: respawn.get_bool("forced").unwrap_or(false),
+        })
+    }
+}
+
 /// Represents a player's abilities and special powers.
 ///
 /// This struct contains information about the player's current abilities, such as flight, invulnerability, and creative mode.
@@ -1852,6 +2629,19 @@ impl Default for Abilities {
 }
 
 impl Abilities {
+    /// Applies a flying toggle sent by the client. Returns `false` if the request was refused and
+    /// the client has to be sent the abilities again.
+    pub fn request_flying(&mut self, flying: bool, gamemode: GameMode) -> bool {
+        // Spectators can't stop flying, they would fall through the world
+        let allowed = if gamemode == GameMode::Spectator {
+            true
+        } else {
+            flying && self.allow_flying
+        };
+        self.flying = allowed;
+        allowed == flying
+    }
+
     pub fn set_for_gamemode(&mut self, gamemode: GameMode) {
         match gamemode {
             GameMode::Creative => {
@@ -1910,6 +2700,13 @@ pub enum ChatMode {
     Hidden,
 }
 
+impl ChatMode {
+    /// Whether messages of other players, including private messages, are shown.
+    pub const fn accepts_chat_messages(&self) -> bool {
+        matches!(self, Self::Enabled)
+    }
+}
+
 pub struct InvalidChatMode;
 
 impl TryFrom<i32> for ChatMode {
@@ -1960,11 +2757,22 @@ impl ChatSession {
             signature_cache: Vec::new(),
         }
     }
+
+    /// Whether the player has set up a chat session, and so can sign their messages.
+    pub fn is_initialized(&self) -> bool {
+        !self.public_key.is_empty()
+    }
 }
 
 #[derive(Clone, Default)]
 pub struct LastSeen(Vec<Box<[u8]>>);
 
+impl From<Vec<Box<[u8]>>> for LastSeen {
+    fn from(signatures: Vec<Box<[u8]>>) -> Self {
+        Self(signatures)
+    }
+}
+
 impl From<LastSeen> for Vec<Box<[u8]>> {
     fn from(seen: LastSeen) -> Self {
         seen.0
@@ -1980,7 +2788,7 @@ impl AsRef<[Box<[u8]>]> for LastSeen {
 impl LastSeen {
     /// The sender's `last_seen` signatures are sent as ID's if the recipient has them in their cache.
     /// Otherwise, the full signature is sent. (ID:0 indicates full signature is being sent)
-    pub async fn indexed_for(&self, recipient: &Arc<Player>) -> Box<[PreviousMessage]> {
+    pub async fn indexed_for(&self, recipient: &Player) -> Box<[PreviousMessage]> {
         let mut indexed = Vec::new();
         for signature in &self.0 {
             if let Some(index) = recipient
@@ -2008,20 +2816,32 @@ impl LastSeen {
     }
 }
 
+/// A chat message or message argument of a command, together with its sender's signature.
+#[derive(Clone)]
+pub struct SignedMessage {
+    pub message: String,
+    /// The index of the message in the sender's chain
+    pub index: i32,
+    pub signature: Box<[u8]>,
+    pub salt: i64,
+    pub timestamp: i64,
+    pub last_seen: LastSeen,
+}
+
 pub struct MessageCache {
     /// max 128 cached message signatures. Most recent FIRST.
     /// Server should (when possible) reference indexes in this (recipient's) cache instead of sending full signatures in last seen.
     /// Must be 1:1 with client's signature cache.
     full_cache: VecDeque<Box<[u8]>>,
-    /// max 20 last seen messages by the sender. Most Recent LAST
-    pub last_seen: LastSeen,
+    /// The signed messages the player received and has to acknowledge
+    pub last_seen: LastSeenValidator,
 }
 
 impl Default for MessageCache {
     fn default() -> Self {
         Self {
             full_cache: VecDeque::with_capacity(MAX_CACHED_SIGNATURES as usize),
-            last_seen: LastSeen::default(),
+            last_seen: LastSeenValidator::default(),
         }
     }
 }
@@ -2042,10 +2862,7 @@ impl MessageCache {
 
     /// Adds a seen signature to `last_seen` and `full_cache`.
     pub fn add_seen_signature(&mut self, signature: &[u8]) {
-        if self.last_seen.0.len() >= MAX_PREVIOUS_MESSAGES as usize {
-            self.last_seen.0.remove(0);
-        }
-        self.last_seen.0.push(signature.into());
+        self.last_seen.add_pending(signature);
         // This probably doesn't need to be a loop, but better safe than sorry
         while self.full_cache.len() >= MAX_CACHED_SIGNATURES as usize {
             self.full_cache.pop_back();
@@ -2053,3 +2870,461 @@ impl MessageCache {
         self.full_cache.push_front(signature.into()); // Since recipient saw this message it will be most recent in cache
     }
 }
+
+async fn take_mining_pos(
+    mining: &AtomicBool,
+    destroy_stage: &AtomicI32,
+    mining_pos: &Mutex<BlockPos>,
+) -> Option<BlockPos> {
+    if !mining.swap(false, Relaxed) {
+        return None;
+    }
+    destroy_stage.store(-1, Relaxed);
+    Some(*mining_pos.lock().await)
+}
+
+/// Packets which mean the player is at the keyboard. Position packets are left out, the client
+/// sends them every second even when standing still, so they only count if the player moved.
+fn resets_idle_timer(packet_id: i32) -> bool {
+    matches!(
+        packet_id,
+        SChatCommand::PACKET_ID
+            | SChatCommandSigned::PACKET_ID
+            | SChatMessage::PACKET_ID
+            | SInteract::PACKET_ID
+            | SPlayerRotation::PACKET_ID
+            | SPickItemFromBlock::PACKET_ID
+            | SPlayerAction::PACKET_ID
+            | SPlayerCommand::PACKET_ID
+            | SClickContainer::PACKET_ID
+            | SSetHeldItem::PACKET_ID
+            | SSetCreativeSlot::PACKET_ID
+            | SSwingArm::PACKET_ID
+            | SUpdateSign::PACKET_ID
+            | SUseItemOn::PACKET_ID
+            | SUseItem::PACKET_ID
+    )
+}
+
+/// Whether a player who last acted at `last_action` has been idle for longer than the timeout in
+/// minutes, a timeout of `0` never expires.
+fn is_idle(last_action: Instant, now: Instant, timeout_minutes: u32) -> bool {
+    timeout_minutes > 0
+        && now.saturating_duration_since(last_action)
+            > Duration::from_secs(u64::from(timeout_minutes) * 60)
+}
+
+/// Play state pings are simply echoed back.
+pub(crate) fn ping_response(request: &SPlayPingRequest) -> CPingResponse {
+    CPingResponse::new(request.payload)
+}
+
+/// Clears the pending ping if the pong answers it, returning the round trip time.
+fn pong_latency(pending: &mut Option<(i32, Instant)>, id: i32, now: Instant) -> Option<Duration> {
+    match *pending {
+        Some((pending_id, sent)) if pending_id == id => {
+            *pending = None;
+            Some(now.duration_since(sent))
+        }
+        _ => None,
+    }
+}
+
+/// The vanilla death message for the damage type which killed the player, naming the `killer`
+/// if there was one.
+fn death_message(
+    damage_type: &DamageType,
+    name: &str,
+    killer: Option<TextComponent>,
+) -> TextComponent {
+    let key = format!("death.attack.{}", damage_type.message_id);
+    let name = TextComponent::text(name.to_string());
+    match killer {
+        Some(killer) => TextComponent::translate(key, [name, killer]),
+        None => TextComponent::translate(key, [name]),
+    }
+}
+
+/// Spectators pass through entities, so they never touch any.
+fn touches_entity(gamemode: GameMode, player_box: &BoundingBox, entity_box: &BoundingBox) -> bool {
+    gamemode != GameMode::Spectator
+        && player_box
+            // This is vanilla, but TODO: change this when is in a vehicle
+            .expand(1.0, 0.5, 1.0)
+            .intersects(entity_box)
+}
+
+/// Whether players can keep their respawn point at the block.
+/// Whether a player can respawn at the block: a bed, or a charged respawn anchor where they work.
+fn is_respawn_block(state_id: BlockStateId, anchor_works: bool) -> bool {
+    let Some(block) = Block::from_state_id(state_id) else {
+        return false;
+    };
+    if block == Block::RESPAWN_ANCHOR {
+        return anchor_works && RespawnAnchorBlock::charges(state_id) != Integer0To4::L0;
+    }
+    block.is_tagged_with("minecraft:beds") == Some(true)
+}
+
+/// The [`CSetHealth`] to send if the values differ from the ones last sent.
+fn health_food_update(
+    last_sent: &AtomicCell<Option<(f32, u8, f32)>>,
+    health: f32,
+    food: u8,
+    saturation: f32,
+) -> Option<CSetHealth> {
+    let current = (health, food, saturation);
+    (last_sent.swap(Some(current)) != Some(current))
+        .then(|| CSetHealth::new(health, food.into(), saturation))
+}
+
+/// The slots other players can see, in the order of [`current_equipment`].
+const EQUIPMENT_SLOTS: [EquipmentSlot; 6] = [
+    EquipmentSlot::MainHand,
+    EquipmentSlot::OffHand,
+    EquipmentSlot::Feet,
+    EquipmentSlot::Legs,
+    EquipmentSlot::Chest,
+    EquipmentSlot::Head,
+];
+
+fn current_equipment(inventory: &PlayerInventory) -> [ItemStack; EQUIPMENT_SLOTS.len()] {
+    let stack = |stack: Option<&ItemStack>| stack.cloned().unwrap_or(ItemStack::EMPTY);
+    let armor = inventory.armor_slots();
+    [
+        stack(inventory.held_item()),
+        stack(inventory.offhand_slot()),
+        stack(armor[3]),
+        stack(armor[2]),
+        stack(armor[1]),
+        stack(armor[0]),
+    ]
+}
+
+fn equipment_changes(
+    previous: &[ItemStack; EQUIPMENT_SLOTS.len()],
+    current: &[ItemStack; EQUIPMENT_SLOTS.len()],
+) -> Vec<(EquipmentSlot, ItemStack)> {
+    EQUIPMENT_SLOTS
+        .iter()
+        .zip(previous.iter().zip(current))
+        .filter(|(_, (previous, current))| {
+            previous.item_count != current.item_count
+                || !previous.are_items_and_components_equal(current)
+        })
+        .map(|(slot, (_, current))| (*slot, current.clone()))
+        .collect()
+}
+
+const fn is_armor_slot(slot: EquipmentSlot) -> bool {
+    matches!(
+        slot,
+        EquipmentSlot::Feet | EquipmentSlot::Legs | EquipmentSlot::Chest | EquipmentSlot::Head
+    )
+}
+
+/// The sound played when the given item is put into an armor slot.
+fn equip_sound(item: &Item) -> Option<Sound> {
+    if item.id == Item::AIR.id {
+        return None;
+    }
+    let key = item.registry_key;
+    let sound = if key == "elytra" {
+        Sound::ItemArmorEquipElytra
+    } else if key == "turtle_helmet" {
+        Sound::ItemArmorEquipTurtle
+    } else if key.starts_with("leather_") {
+        Sound::ItemArmorEquipLeather
+    } else if key.starts_with("chainmail_") {
+        Sound::ItemArmorEquipChain
+    } else if key.starts_with("iron_") {
+        Sound::ItemArmorEquipIron
+    } else if key.starts_with("golden_") {
+        Sound::ItemArmorEquipGold
+    } else if key.starts_with("diamond_") {
+        Sound::ItemArmorEquipDiamond
+    } else if key.starts_with("netherite_") {
+        Sound::ItemArmorEquipNetherite
+    } else {
+        Sound::ItemArmorEquipGeneric
+    };
+    Some(sound)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::atomic::Ordering::Relaxed,
+        time::{Duration, Instant},
+    };
+
+    use bytes::Bytes;
+    use pumpkin_util::GameMode;
+    use pumpkin_util::math::{boundingbox::BoundingBox, position::BlockPos};
+    use pumpkin_util::text::TextComponent;
+
+    use pumpkin_data::{
+        Block,
+        damage::DamageType,
+        item::Item,
+        packet::clientbound::{PLAY_PLAYER_COMBAT_KILL, PLAY_SET_HEALTH},
+        sound::Sound,
+    };
+    use pumpkin_protocol::{
+        ClientPacket, CompressionPolicy, ServerPacket,
+        client::play::{CCombatDeath, CSetBlockDestroyStage, CSetHealth, EquipmentSlot},
+        ser::packet::Packet,
+        server::play::{SKeepAlive, SPlayPingRequest, SSwingArm},
+    };
+    use pumpkin_world::item::ItemStack;
+    use tokio::sync::mpsc::Receiver;
+
+    use crate::entity::EntityBase;
+    use crate::entity::living::health_after_damage;
+    use crate::test_utils::{connected_test_player, read_sent_packet, test_player, test_server};
+    use crate::world::BlockFlags;
+
+    use super::{
+        Abilities, Player, death_message, equip_sound, equipment_changes, is_idle, ping_response,
+        pong_latency, resets_idle_timer, touches_entity,
+    };
+
+    #[tokio::test]
+    async fn disconnect_while_mining_clears_breaking_position() {
+        let server = test_server();
+        let (alice, _alice_packets) = test_player(&server, "alice").await;
+        let (_bob, mut bob_packets) = test_player(&server, "bob").await;
+        let pos = BlockPos::new(3, 64, -7);
+        *alice.mining_pos.lock().await = pos;
+        alice.current_block_destroy_stage.store(4, Relaxed);
+        alice.mining.store(true, Relaxed);
+
+        let world = alice.world().await;
+        assert!(world.remove_player(&alice, false).await);
+        assert!(!alice.mining.load(Relaxed));
+        assert_eq!(alice.current_block_destroy_stage.load(Relaxed), -1);
+
+        let mut expected = Vec::new();
+        CSetBlockDestroyStage::new(alice.entity_id().into(), pos, -1)
+            .write(&mut expected)
+            .unwrap();
+        let mut reset = false;
+        while let Ok((packet, _)) = bob_packets.try_recv() {
+            reset |= packet == expected;
+        }
+        assert!(reset);
+
+        // Nothing to clear once the player stopped mining
+        assert_eq!(alice.stop_mining().await, None);
+    }
+
+    #[test]
+    fn death_screen_shows_vanilla_death_message() {
+        assert_eq!(
+            death_message(&DamageType::GENERIC_KILL, "Steve", None),
+            TextComponent::translate("death.attack.genericKill", [TextComponent::text("Steve")])
+        );
+        assert_eq!(
+            death_message(&DamageType::FALL, "Alex", None),
+            TextComponent::translate("death.attack.fall", [TextComponent::text("Alex")])
+        );
+    }
+
+    #[tokio::test]
+    async fn lethal_damage_blames_the_attacker() {
+        // 6 points of damage to a player with full health
+        assert_eq!(health_after_damage(20.0, 6.0), 14.0);
+
+        // The same hit kills a player with only 4 health left
+        let server = test_server();
+        let (steve, _packets, mut remote) = connected_test_player(&server, "Steve").await;
+        let (alex, _) = test_player(&server, "Alex").await;
+        steve.living_entity.health.store(4.0);
+        let attacker = &alex.living_entity.entity;
+        assert!(
+            EntityBase::damage_with_context(
+                &*steve,
+                6.0,
+                DamageType::PLAYER_ATTACK,
+                None,
+                Some(attacker),
+                Some(attacker),
+            )
+            .await
+        );
+        assert_eq!(steve.living_entity.health.load(), 0.0);
+
+        let message = TextComponent::translate(
+            "death.attack.player",
+            [TextComponent::text("Steve"), TextComponent::text("Alex")],
+        );
+        assert_eq!(
+            message.clone().to_locale_string("en_us"),
+            "Steve was slain by Alex"
+        );
+        let mut expected = Vec::new();
+        CCombatDeath::new(steve.entity_id().into(), &message)
+            .write(&mut expected)
+            .unwrap();
+        assert_eq!(
+            read_sent_packet(&mut remote, PLAY_PLAYER_COMBAT_KILL).await,
+            expected
+        );
+    }
+
+    #[test]
+    fn equipping_helmet_is_broadcast_with_sound() {
+        let previous: [ItemStack; 6] = std::array::from_fn(|_| ItemStack::EMPTY);
+        let mut current = previous.clone();
+        current[5] = ItemStack::new(1, Item::IRON_HELMET);
+
+        let changes = equipment_changes(&previous, &current);
+        assert_eq!(changes.len(), 1);
+        let (slot, stack) = &changes[0];
+        assert!(matches!(slot, EquipmentSlot::Head));
+        assert_eq!(stack.item.id, Item::IRON_HELMET.id);
+        assert_eq!(
+            equip_sound(&stack.item).map(|sound| sound as u16),
+            Some(Sound::ItemArmorEquipIron as u16)
+        );
+
+        // Nothing changed, nothing to broadcast
+        assert!(equipment_changes(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn play_ping_request_is_answered_with_same_id() {
+        let payload = 0x0123_4567_89AB_CDEF_i64.to_be_bytes();
+        let request = SPlayPingRequest::read(&payload[..]).unwrap();
+
+        let mut response = Vec::new();
+        ping_response(&request)
+            .write_packet_data(&mut response)
+            .unwrap();
+        assert_eq!(response, payload);
+    }
+
+    #[test]
+    fn spectators_fly_and_pass_through_entities() {
+        let mut abilities = Abilities::default();
+        abilities.set_for_gamemode(GameMode::Spectator);
+        assert!(abilities.flying && abilities.allow_flying);
+        // The client can't turn flying off and is corrected instead
+        assert!(!abilities.request_flying(false, GameMode::Spectator));
+        assert!(abilities.flying);
+
+        let player_box = BoundingBox::new_array([-0.3, 64.0, -0.3], [0.3, 65.8, 0.3]);
+        let item_box = BoundingBox::new_array([0.5, 64.0, 0.0], [0.75, 64.25, 0.25]);
+        assert!(!touches_entity(GameMode::Spectator, &player_box, &item_box));
+        assert!(touches_entity(GameMode::Survival, &player_box, &item_box));
+
+        abilities.set_for_gamemode(GameMode::Survival);
+        assert!(!abilities.flying && !abilities.allow_flying);
+        assert!(!abilities.request_flying(true, GameMode::Survival));
+    }
+
+    #[test]
+    fn idle_players_are_kicked_after_timeout() {
+        let last_action = Instant::now();
+        let later = last_action + Duration::from_secs(5 * 60 + 1);
+        assert!(is_idle(last_action, later, 5));
+        assert!(!is_idle(last_action, later, 6));
+        // 0 disables the kick
+        assert!(!is_idle(last_action, later, 0));
+
+        assert!(resets_idle_timer(SSwingArm::PACKET_ID));
+        assert!(!resets_idle_timer(SKeepAlive::PACKET_ID));
+    }
+
+    #[tokio::test]
+    async fn damage_and_food_changes_send_health_and_food() {
+        let server = test_server();
+        let (steve, mut packets) = test_player(&server, "Steve").await;
+        let set_health_packets = |packets: &mut Receiver<(Bytes, CompressionPolicy)>| {
+            let mut sent = Vec::new();
+            while let Ok((packet, _)) = packets.try_recv() {
+                if i32::from(packet[0]) == PLAY_SET_HEALTH {
+                    sent.push(packet.to_vec());
+                }
+            }
+            sent
+        };
+        let expected = |health: f32, food: u8, saturation: f32| {
+            let mut expected = Vec::new();
+            CSetHealth::new(health, food.into(), saturation)
+                .write(&mut expected)
+                .unwrap();
+            vec![expected]
+        };
+
+        steve.sync_health_food().await;
+        assert_eq!(set_health_packets(&mut packets), expected(20.0, 20, 5.0));
+        // Nothing changed, nothing to send
+        steve.sync_health_food().await;
+        assert!(set_health_packets(&mut packets).is_empty());
+
+        assert!(EntityBase::damage(&*steve, 6.0, DamageType::GENERIC).await);
+        assert_eq!(set_health_packets(&mut packets), expected(14.0, 20, 5.0));
+
+        // Exhaustion without saturation left costs food
+        steve.hunger_manager.saturation.store(0.0);
+        steve.hunger_manager.exhaustion.store(4.5);
+        steve.hunger_manager.tick(&steve).await;
+        assert_eq!(set_health_packets(&mut packets), expected(14.0, 19, 0.0));
+    }
+
+    #[test]
+    fn only_pong_for_pending_ping_measures_latency() {
+        let sent = Instant::now();
+        let mut pending = Some((7, sent));
+
+        assert_eq!(pong_latency(&mut pending, 6, sent), None);
+        assert!(pending.is_some());
+
+        let latency = pong_latency(&mut pending, 7, sent + Duration::from_millis(42));
+        assert_eq!(latency, Some(Duration::from_millis(42)));
+        assert!(pending.is_none());
+    }
+
+    async fn first_compass(player: &Player) -> ItemStack {
+        player
+            .inventory()
+            .lock()
+            .await
+            .slots()
+            .iter()
+            .find_map(|stack| stack.filter(|stack| stack.is_compass()).cloned())
+            .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn compasses_spin_once_their_lodestone_is_gone() {
+        let server = test_server();
+        let (player, _packets) = test_player(&server, "alice").await;
+        let world = player.world().await;
+        let pos = BlockPos::new(0, 200, 0);
+        world
+            .set_block_state(
+                &pos,
+                Block::LODESTONE.default_state_id,
+                BlockFlags::NOTIFY_ALL,
+            )
+            .await;
+        let dimension = world.dimension_type.name().to_string();
+        let mut compass = ItemStack::new(1, Item::COMPASS);
+        compass.bind_to_lodestone(&dimension, pos);
+        assert!(player.give_item(compass).await.is_none());
+
+        player.tick_lodestone_compasses().await;
+        let compass = first_compass(&player).await;
+        assert_eq!(compass.lodestone_target(), Some((dimension.as_str(), pos)));
+
+        world
+            .set_block_state(&pos, Block::AIR.default_state_id, BlockFlags::NOTIFY_ALL)
+            .await;
+        player.tick_lodestone_compasses().await;
+        let compass = first_compass(&player).await;
+        assert_eq!(compass.lodestone_target(), None);
+        assert!(compass.patch.lodestone_tracker.is_some());
+    }
+}
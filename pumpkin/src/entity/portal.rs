@@ -0,0 +1,155 @@
+use std::sync::atomic::{AtomicU32, Ordering::Relaxed};
+
+use pumpkin_registry::DimensionType;
+use pumpkin_util::math::vector3::Vector3;
+
+/// How many ticks players in survival have to stand in a nether portal before they travel.
+pub const PLAYER_PORTAL_WAIT_TIME: u32 = 80;
+/// How many ticks an entity can't use a portal after it changed dimensions. It only runs out
+/// once the entity left the portal.
+pub const PORTAL_COOLDOWN: u32 = 300;
+/// How many ticks an entity can't mount a vehicle again after it dismounted.
+pub const RIDE_COOLDOWN: u32 = 60;
+
+/// A counter which goes down by one every tick until it runs out.
+#[derive(Default)]
+pub struct Cooldown(AtomicU32);
+
+impl Cooldown {
+    pub fn set(&self, ticks: u32) {
+        self.0.store(ticks, Relaxed);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.0.load(Relaxed) > 0
+    }
+
+    pub fn tick(&self) {
+        let _ = self
+            .0
+            .fetch_update(Relaxed, Relaxed, |ticks| ticks.checked_sub(1));
+    }
+}
+
+/// Tracks how long an entity has been standing in a portal, and keeps it from travelling straight
+/// back after it went through one.
+#[derive(Default)]
+pub struct PortalTravel {
+    time_in_portal: AtomicU32,
+    /// The dimension change immunity
+    pub cooldown: Cooldown,
+}
+
+impl PortalTravel {
+    /// Called every tick, returns whether the entity should now travel through the portal it is
+    /// standing in.
+    pub fn tick(&self, in_portal: bool, wait_time: u32) -> bool {
+        if !in_portal {
+            self.time_in_portal.store(0, Relaxed);
+            self.cooldown.tick();
+            return false;
+        }
+        if self.cooldown.is_active() {
+            // Standing in the destination portal keeps the entity from travelling back
+            self.cooldown.set(PORTAL_COOLDOWN);
+            return false;
+        }
+        if self.time_in_portal.fetch_add(1, Relaxed) + 1 < wait_time {
+            return false;
+        }
+        self.time_in_portal.store(0, Relaxed);
+        self.cooldown.set(PORTAL_COOLDOWN);
+        true
+    }
+}
+
+/// The dimension a nether portal in `from` leads to, and where `pos` ends up there. Horizontal
+/// distances in the nether are 8 times shorter. The entity arrives in the portal closest to
+/// there, see `World::portal_arrival`.
+pub fn nether_portal_destination(
+    from: &DimensionType,
+    pos: Vector3<f64>,
+) -> Option<(DimensionType, Vector3<f64>)> {
+    match from {
+        DimensionType::Overworld | DimensionType::OverworldCaves => Some((
+            DimensionType::TheNether,
+            Vector3::new(pos.x / 8.0, pos.y, pos.z / 8.0),
+        )),
+        DimensionType::TheNether => Some((
+            DimensionType::Overworld,
+            Vector3::new(pos.x * 8.0, pos.y, pos.z * 8.0),
+        )),
+        DimensionType::TheEnd | DimensionType::Custom(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_registry::DimensionType;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{
+        Cooldown, PLAYER_PORTAL_WAIT_TIME, PORTAL_COOLDOWN, PortalTravel, nether_portal_destination,
+    };
+
+    #[test]
+    fn entity_travels_once_wait_time_elapsed() {
+        let portal = PortalTravel::default();
+        for _ in 1..PLAYER_PORTAL_WAIT_TIME {
+            assert!(!portal.tick(true, PLAYER_PORTAL_WAIT_TIME));
+        }
+        assert!(portal.tick(true, PLAYER_PORTAL_WAIT_TIME));
+
+        // Arriving in the other portal doesn't send the entity straight back
+        for _ in 0..PORTAL_COOLDOWN * 2 {
+            assert!(!portal.tick(true, PLAYER_PORTAL_WAIT_TIME));
+        }
+
+        // Leaving the portal lets the cooldown run out
+        for _ in 0..PORTAL_COOLDOWN {
+            assert!(!portal.tick(false, PLAYER_PORTAL_WAIT_TIME));
+        }
+        assert!(!portal.cooldown.is_active());
+        assert!(portal.tick(true, 1));
+    }
+
+    #[test]
+    fn stepping_out_resets_the_wait() {
+        let portal = PortalTravel::default();
+        for _ in 0..10 {
+            assert!(!portal.tick(true, 20));
+        }
+        assert!(!portal.tick(false, 20));
+        for _ in 1..20 {
+            assert!(!portal.tick(true, 20));
+        }
+        assert!(portal.tick(true, 20));
+    }
+
+    #[test]
+    fn cooldown_counts_down_to_zero() {
+        let cooldown = Cooldown::default();
+        cooldown.set(2);
+        cooldown.tick();
+        assert!(cooldown.is_active());
+        cooldown.tick();
+        assert!(!cooldown.is_active());
+        cooldown.tick();
+        assert!(!cooldown.is_active());
+    }
+
+    #[test]
+    fn nether_distances_are_scaled() {
+        let (dimension, pos) =
+            nether_portal_destination(&DimensionType::Overworld, Vector3::new(800.0, 70.0, -80.0))
+                .unwrap();
+        assert_eq!(dimension, DimensionType::TheNether);
+        assert_eq!(pos, Vector3::new(100.0, 70.0, -10.0));
+
+        let (dimension, pos) = nether_portal_destination(&DimensionType::TheNether, pos).unwrap();
+        assert_eq!(dimension, DimensionType::Overworld);
+        assert_eq!(pos, Vector3::new(800.0, 70.0, -80.0));
+
+        assert!(nether_portal_destination(&DimensionType::TheEnd, pos).is_none());
+    }
+}
@@ -6,6 +6,8 @@ use pumpkin_util::math::vector3::Vector3;
 
 use super::{Entity, EntityBase, living::LivingEntity};
 
+pub mod trident;
+
 pub struct ThrownItemEntity {
     entity: Entity,
 }
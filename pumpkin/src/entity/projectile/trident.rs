@@ -0,0 +1,459 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicU32, Ordering::Relaxed},
+};
+
+use async_trait::async_trait;
+use pumpkin_data::{
+    block_properties::Axis,
+    damage::DamageType,
+    sound::{Sound, SoundCategory},
+};
+use pumpkin_protocol::client::play::{
+    CEntityPositionSync, CTakeItemEntity, MetaDataType, Metadata,
+};
+use pumpkin_util::{
+    GameMode,
+    math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3},
+};
+use pumpkin_world::item::ItemStack;
+
+use crate::{
+    block::{blocks::redstone::target_block::TargetBlock, drop_stack},
+    entity::{Entity, EntityBase, EntityId, lightning, living::LivingEntity, player::Player},
+    server::Server,
+    world::World,
+};
+
+/// How many ticks a trident has to be charged before it is thrown. Vanilla: 10
+pub const MIN_CHARGE_TICKS: i32 = 10;
+/// How fast a thrown trident leaves the hand
+const THROW_SPEED: f64 = 2.5;
+/// How much faster a flying trident falls every tick
+const GRAVITY: f64 = 0.05;
+/// How much of its velocity a flying trident keeps every tick
+const DRAG: f64 = 0.99;
+/// The damage a trident deals to the entity it hits. Vanilla: 8
+const DAMAGE: f32 = 8.0;
+/// How far a trident moves between collision checks, so fast tridents don't fly through walls
+const STEP_LENGTH: f64 = 0.25;
+/// How close a flying trident has to get to an entity to hit it
+const HIT_MARGIN: f64 = 0.25;
+/// Tridents stuck in the ground despawn after this many ticks. Vanilla: 1200
+const DESPAWN_TICKS: u32 = 1200;
+const NO_CLIP_FLAG: u8 = 0x02;
+const ARROW_FLAGS_INDEX: u8 = 8;
+const LOYALTY_INDEX: u8 = 10;
+const FOIL_INDEX: u8 = 11;
+
+/// A thrown trident. It sticks in the block or bounces off the entity it hits, and comes back to
+/// its owner after that if it is enchanted with Loyalty.
+pub struct TridentEntity {
+    entity: Entity,
+    owner: EntityId,
+    stack: ItemStack,
+    /// Whether picking the trident up gives it back, tridents thrown in creative don't
+    pickup: bool,
+    in_ground: AtomicBool,
+    dealt_damage: AtomicBool,
+    returning: AtomicBool,
+    ground_ticks: AtomicU32,
+}
+
+impl TridentEntity {
+    pub fn new(entity: Entity, owner: &Entity, stack: ItemStack, pickup: bool) -> Self {
+        let mut owner_pos = owner.pos.load();
+        owner_pos.y = (owner_pos.y + f64::from(owner.standing_eye_height)) - 0.1;
+        entity.pos.store(owner_pos);
+        Self {
+            entity,
+            owner: owner.entity_id,
+            stack,
+            pickup,
+            in_ground: AtomicBool::new(false),
+            dealt_damage: AtomicBool::new(false),
+            returning: AtomicBool::new(false),
+            ground_ticks: AtomicU32::new(0),
+        }
+    }
+
+    /// Throws the trident in the direction the owner looks.
+    pub fn shoot(&self, yaw: f32, pitch: f32) {
+        let velocity = throw_velocity(yaw, pitch);
+        self.entity.velocity.store(velocity);
+        self.entity.set_rotation(yaw, pitch);
+    }
+
+    fn loyalty(&self) -> u8 {
+        self.stack
+            .patch
+            .enchantment_level("loyalty")
+            .map_or(0, |level| level.clamp(0, i32::from(u8::MAX)) as u8)
+    }
+
+    fn channeling(&self) -> bool {
+        self.stack
+            .patch
+            .enchantment_level("channeling")
+            .is_some_and(|level| level > 0)
+    }
+
+    async fn sync_position(&self, world: &World) {
+        let entity = &self.entity;
+        world
+            .broadcast_to_trackers(
+                entity.entity_id,
+                &CEntityPositionSync::new(
+                    entity.entity_id.into(),
+                    entity.pos.load(),
+                    entity.velocity.load(),
+                    entity.yaw.load(),
+                    entity.pitch.load(),
+                    false,
+                ),
+            )
+            .await;
+    }
+
+    /// Flies back to the owner, or drops if the owner isn't around anymore.
+    async fn tick_return(&self, world: &Arc<World>, loyalty: u8) {
+        let entity = &self.entity;
+        let owner = world
+            .get_player_by_id(self.owner)
+            .await
+            .filter(|owner| owner.living_entity.health.load() > 0.0);
+        let Some(owner) = owner else {
+            if self.pickup {
+                drop_stack(world, &entity.block_pos.load(), self.stack.clone()).await;
+            }
+            entity.remove().await;
+            return;
+        };
+
+        if !self.returning.swap(true, Relaxed) {
+            self.in_ground.store(false, Relaxed);
+            entity
+                .send_meta_data(&[Metadata::new(
+                    ARROW_FLAGS_INDEX,
+                    MetaDataType::Byte,
+                    NO_CLIP_FLAG,
+                )])
+                .await;
+            world
+                .play_sound(
+                    Sound::ItemTridentReturn,
+                    SoundCategory::Neutral,
+                    &entity.pos.load(),
+                )
+                .await;
+        }
+        let owner_entity = &owner.living_entity.entity;
+        let owner_eye =
+            owner_entity
+                .pos
+                .load()
+                .add_raw(0.0, f64::from(owner_entity.standing_eye_height), 0.0);
+        let (pos, velocity) = return_step(
+            entity.pos.load(),
+            entity.velocity.load(),
+            owner_eye,
+            loyalty,
+        );
+        entity.set_pos(pos);
+        entity.velocity.store(velocity);
+        self.sync_position(world).await;
+    }
+
+    async fn tick_flight(&self, world: &Arc<World>) {
+        let entity = &self.entity;
+        let pos = entity.pos.load();
+        let velocity = entity.velocity.load();
+        let target = pos.add(&velocity);
+        let steps = (velocity.length() / STEP_LENGTH).ceil().max(1.0);
+
+        let mut last = pos;
+        for step in 1..=steps as u32 {
+            let point = pos.lerp(&target, f64::from(step) / steps);
+            if !self.dealt_damage.load(Relaxed) {
+                if let Some(hit) = self.entity_at(world, point).await {
+                    self.hit_entity(world, &hit).await;
+                    return;
+                }
+            }
+            let block_pos = BlockPos::floored(point.x, point.y, point.z);
+            let solid = world
+                .get_block_state(&block_pos)
+                .await
+                .is_ok_and(|state| !state.collision_shapes.is_empty());
+            if solid {
+                TargetBlock::hit(
+                    world,
+                    &block_pos,
+                    point,
+                    hit_axis(last, block_pos, velocity),
+                    entity.entity_type,
+                )
+                .await;
+                entity.set_pos(last);
+                entity.velocity.store(Vector3::new(0.0, 0.0, 0.0));
+                self.in_ground.store(true, Relaxed);
+                world
+                    .play_sound(Sound::ItemTridentHitGround, SoundCategory::Neutral, &last)
+                    .await;
+                self.sync_position(world).await;
+                return;
+            }
+            last = point;
+        }
+
+        entity.set_pos(target);
+        entity.velocity.store(
+            velocity
+                .multiply(DRAG, DRAG, DRAG)
+                .add_raw(0.0, -GRAVITY, 0.0),
+        );
+    }
+
+    /// The first living entity except the owner the trident hits at `point`.
+    async fn entity_at(&self, world: &World, point: Vector3<f64>) -> Option<Arc<dyn EntityBase>> {
+        let area = BoundingBox::new(point, point).expand(HIT_MARGIN, HIT_MARGIN, HIT_MARGIN);
+        let mut candidates: Vec<Arc<dyn EntityBase>> =
+            world.entities.read().await.values().cloned().collect();
+        candidates.extend(
+            world
+                .players
+                .read()
+                .await
+                .values()
+                .map(|player| player.clone() as Arc<dyn EntityBase>),
+        );
+        candidates.into_iter().find(|candidate| {
+            let base = candidate.get_entity();
+            base.entity_id != self.owner
+                && base.entity_id != self.entity.entity_id
+                && candidate.get_living_entity().is_some()
+                && base.bounding_box.load().intersects(&area)
+        })
+    }
+
+    async fn hit_entity(&self, world: &Arc<World>, target: &Arc<dyn EntityBase>) {
+        let entity = &self.entity;
+        target.damage(DAMAGE, DamageType::TRIDENT).await;
+        self.dealt_damage.store(true, Relaxed);
+        // The trident bounces off the entity it hit
+        entity
+            .velocity
+            .store(entity.velocity.load().multiply(-0.01, -0.1, -0.01));
+        world
+            .play_sound(
+                Sound::ItemTridentHit,
+                SoundCategory::Neutral,
+                &entity.pos.load(),
+            )
+            .await;
+
+        let target_pos = target.get_entity().pos.load();
+        let block_pos = BlockPos::floored(target_pos.x, target_pos.y, target_pos.z);
+        if self.channeling()
+            && world.weather.lock().await.thundering
+            && world.is_raining_at(&block_pos).await
+        {
+            lightning::strike(world, target_pos).await;
+            world
+                .play_sound(
+                    Sound::ItemTridentThunder,
+                    SoundCategory::Weather,
+                    &target_pos,
+                )
+                .await;
+        }
+        self.sync_position(world).await;
+    }
+}
+
+#[async_trait]
+impl EntityBase for TridentEntity {
+    async fn tick(&self, server: &Server) {
+        let entity = &self.entity;
+        entity.tick(server).await;
+        let world = entity.world.read().await.clone();
+
+        let loyalty = self.loyalty();
+        if loyalty > 0 && (self.dealt_damage.load(Relaxed) || self.in_ground.load(Relaxed)) {
+            self.tick_return(&world, loyalty).await;
+            return;
+        }
+        if self.in_ground.load(Relaxed) {
+            if self.ground_ticks.fetch_add(1, Relaxed) >= DESPAWN_TICKS {
+                entity.remove().await;
+            }
+            return;
+        }
+        self.tick_flight(&world).await;
+    }
+
+    async fn on_player_collision(&self, player: Arc<Player>) {
+        let returning = self.returning.load(Relaxed);
+        if !returning && !self.in_ground.load(Relaxed) {
+            return;
+        }
+        // Returning tridents only fly back into their owner's hand
+        if returning && player.entity_id() != self.owner {
+            return;
+        }
+        if self.pickup {
+            let mut inventory = player.inventory().lock().await;
+            let Some(slot) = inventory.get_empty_slot() else {
+                return;
+            };
+            player
+                .update_single_slot(&mut inventory, slot, self.stack.clone())
+                .await;
+        } else if player.gamemode.load() != GameMode::Creative {
+            return;
+        }
+        player
+            .client
+            .enqueue_packet(&CTakeItemEntity::new(
+                self.entity.entity_id.into(),
+                player.entity_id().into(),
+                1.into(),
+            ))
+            .await;
+        self.entity.remove().await;
+    }
+
+    async fn damage(&self, _amount: f32, _damage_type: DamageType) -> bool {
+        false
+    }
+
+    async fn send_tracking_data(&self, player: &Player) {
+        self.entity
+            .send_meta_data_to(
+                player,
+                &[Metadata::new(
+                    LOYALTY_INDEX,
+                    MetaDataType::Byte,
+                    self.loyalty(),
+                )],
+            )
+            .await;
+        self.entity
+            .send_meta_data_to(
+                player,
+                &[Metadata::new(
+                    FOIL_INDEX,
+                    MetaDataType::Boolean,
+                    !self.stack.patch.enchantments.is_empty(),
+                )],
+            )
+            .await;
+    }
+
+    fn spawn_data(&self) -> i32 {
+        // Clients expect the id of the owner plus one
+        self.owner + 1
+    }
+
+    fn get_entity(&self) -> &Entity {
+        &self.entity
+    }
+
+    fn get_living_entity(&self) -> Option<&LivingEntity> {
+        None
+    }
+}
+
+/// The velocity of a trident thrown by someone looking in the direction of `yaw` and `pitch`.
+pub fn throw_velocity(yaw: f32, pitch: f32) -> Vector3<f64> {
+    let yaw = f64::from(yaw).to_radians();
+    let pitch = f64::from(pitch).to_radians();
+    Vector3::new(
+        -yaw.sin() * pitch.cos(),
+        -pitch.sin(),
+        yaw.cos() * pitch.cos(),
+    )
+    .multiply(THROW_SPEED, THROW_SPEED, THROW_SPEED)
+}
+
+/// The axis across the face of `block` a projectile flying at `velocity` entered it through,
+/// coming from `last`.
+pub fn hit_axis(last: Vector3<f64>, block: BlockPos, velocity: Vector3<f64>) -> Axis {
+    let from = BlockPos::floored(last.x, last.y, last.z);
+    let crossed = [
+        (Axis::X, from.0.x != block.0.x, velocity.x.abs()),
+        (Axis::Y, from.0.y != block.0.y, velocity.y.abs()),
+        (Axis::Z, from.0.z != block.0.z, velocity.z.abs()),
+    ];
+    // When it crossed more than one, it hit the face it was flying at the most
+    crossed
+        .into_iter()
+        .filter(|(_, crossed, _)| *crossed)
+        .max_by(|a, b| a.2.total_cmp(&b.2))
+        .or_else(|| crossed.into_iter().max_by(|a, b| a.2.total_cmp(&b.2)))
+        .map_or(Axis::Y, |(axis, _, _)| axis)
+}
+
+/// Moves a trident with Loyalty one tick closer to its owner's eyes, returning its new position
+/// and velocity. Higher levels of Loyalty pull it back faster.
+pub fn return_step(
+    pos: Vector3<f64>,
+    velocity: Vector3<f64>,
+    owner_eye: Vector3<f64>,
+    loyalty: u8,
+) -> (Vector3<f64>, Vector3<f64>) {
+    let loyalty = f64::from(loyalty);
+    let to_owner = owner_eye.sub(&pos);
+    let pull = 0.05 * loyalty;
+    let velocity = velocity
+        .multiply(0.95, 0.95, 0.95)
+        .add(&to_owner.normalize().multiply(pull, pull, pull));
+    let pos = pos.add_raw(0.0, to_owner.y * 0.015 * loyalty, 0.0);
+    (pos.add(&velocity), velocity)
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{THROW_SPEED, return_step, throw_velocity};
+
+    #[test]
+    fn thrown_trident_flies_where_the_owner_looks() {
+        // Looking straight south
+        let velocity = throw_velocity(0.0, 0.0);
+        assert!(velocity.x.abs() < 1e-9 && velocity.y.abs() < 1e-9);
+        assert!((velocity.z - THROW_SPEED).abs() < 1e-9);
+        // Looking east and up
+        let velocity = throw_velocity(-90.0, -45.0);
+        assert!(velocity.x > 0.0 && velocity.y > 0.0);
+        assert!(velocity.z.abs() < 1e-9);
+        assert!((velocity.length() - THROW_SPEED).abs() < 1e-9);
+    }
+
+    #[test]
+    fn loyalty_trident_returns_to_its_owner() {
+        let owner = Vector3::new(0.0, 65.62, 0.0);
+        // Stuck in a wall 12 blocks away
+        let mut pos = Vector3::new(12.0, 64.5, 3.0);
+        let mut velocity = Vector3::new(0.0, 0.0, 0.0);
+        let start = pos.squared_distance_to_vec(owner);
+        let mut closest = start;
+        for _ in 0..40 {
+            (pos, velocity) = return_step(pos, velocity, owner, 1);
+            closest = closest.min(pos.squared_distance_to_vec(owner));
+        }
+        assert!(closest < 1.0);
+
+        // Higher loyalty gets there sooner
+        let after = |loyalty| {
+            let (mut pos, mut velocity) =
+                (Vector3::new(12.0, 64.5, 3.0), Vector3::new(0.0, 0.0, 0.0));
+            for _ in 0..5 {
+                (pos, velocity) = return_step(pos, velocity, owner, loyalty);
+            }
+            pos.squared_distance_to_vec(owner)
+        };
+        assert!(after(3) < after(1));
+    }
+}
@@ -11,7 +11,7 @@ use std::{
     sync::atomic::{AtomicU32, Ordering::Relaxed},
 };
 
-use super::{Entity, EntityBase, living::LivingEntity};
+use super::{Entity, EntityBase, living::LivingEntity, player::Player};
 
 pub struct TNTEntity {
     entity: Entity,
@@ -35,20 +35,22 @@ impl TNTEntity {
             .set_velocity(Vector3::new(-pos.sin() * 0.02, 0.2, -pos.cos() * 0.02))
             .await;
         // We can merge multiple `Metadata`s into one meta packet.
-        self.entity
-            .send_meta_data(&[
-                Metadata::new(
-                    8,
-                    MetaDataType::Integer,
-                    VarInt(self.fuse.load(Relaxed) as i32),
-                ),
-                Metadata::new(
-                    9,
-                    MetaDataType::BlockState,
-                    VarInt(i32::from(Block::TNT.default_state_id)),
-                ),
-            ])
-            .await;
+        self.entity.send_meta_data(&self.meta_data()).await;
+    }
+
+    fn meta_data(&self) -> [Metadata<VarInt>; 2] {
+        [
+            Metadata::new(
+                8,
+                MetaDataType::Integer,
+                VarInt(self.fuse.load(Relaxed) as i32),
+            ),
+            Metadata::new(
+                9,
+                MetaDataType::BlockState,
+                VarInt(i32::from(Block::TNT.default_state_id)),
+            ),
+        ]
     }
 }
 
@@ -70,6 +72,12 @@ impl EntityBase for TNTEntity {
         false
     }
 
+    async fn send_tracking_data(&self, player: &Player) {
+        self.entity
+            .send_meta_data_to(player, &self.meta_data())
+            .await;
+    }
+
     fn get_entity(&self) -> &Entity {
         &self.entity
     }
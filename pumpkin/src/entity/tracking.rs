@@ -0,0 +1,238 @@
+use std::collections::{HashMap, HashSet};
+
+use pumpkin_config::EntityTrackingConfig;
+use pumpkin_data::entity::EntityType;
+use pumpkin_util::math::vector3::Vector3;
+
+use super::EntityId;
+
+const MISC_ENTITIES: [EntityType; 5] = [
+    EntityType::ITEM,
+    EntityType::EXPERIENCE_ORB,
+    EntityType::ITEM_FRAME,
+    EntityType::GLOW_ITEM_FRAME,
+    EntityType::PAINTING,
+];
+
+/// The horizontal distance in blocks up to which players get sent an entity.
+pub fn tracking_range(
+    config: &EntityTrackingConfig,
+    entity_type: &EntityType,
+    view_distance: u8,
+) -> f64 {
+    let range = if entity_type.id == EntityType::PLAYER.id {
+        config.players
+    } else if MISC_ENTITIES.iter().any(|misc| misc.id == entity_type.id) {
+        config.misc
+    } else if entity_type.max_health.is_some() {
+        config.mobs
+    } else {
+        config.other
+    };
+    // Entities in chunks the player can't see are pointless to send
+    f64::from(range.min(u32::from(view_distance) * 16))
+}
+
+/// The furthest any entity gets sent to a player with the given view distance.
+pub fn max_tracking_range(config: &EntityTrackingConfig, view_distance: u8) -> f64 {
+    let range = config
+        .players
+        .max(config.mobs)
+        .max(config.misc)
+        .max(config.other);
+    f64::from(range.min(u32::from(view_distance) * 16))
+}
+
+pub fn is_in_tracking_range(
+    config: &EntityTrackingConfig,
+    entity_type: &EntityType,
+    viewer: Vector3<f64>,
+    pos: Vector3<f64>,
+    view_distance: u8,
+) -> bool {
+    let range = tracking_range(config, entity_type, view_distance);
+    let (dx, dz) = (pos.x - viewer.x, pos.z - viewer.z);
+    dx * dx + dz * dz <= range * range
+}
+
+/// The entities of a world by chunk, so each player only has to look at the entities in the chunks
+/// around them instead of at every entity of the world.
+#[derive(Default)]
+pub struct EntityGrid<'a> {
+    chunks: HashMap<(i32, i32), Vec<(EntityId, &'a EntityType, Vector3<f64>)>>,
+}
+
+impl<'a> EntityGrid<'a> {
+    pub fn insert(&mut self, entity_id: EntityId, entity_type: &'a EntityType, pos: Vector3<f64>) {
+        self.chunks
+            .entry(chunk_of(pos.x, pos.z))
+            .or_default()
+            .push((entity_id, entity_type, pos));
+    }
+
+    /// The entities in the chunks overlapping the square of `range` blocks around `center`, which
+    /// includes every entity within `range` blocks.
+    pub fn nearby(
+        &self,
+        center: Vector3<f64>,
+        range: f64,
+    ) -> impl Iterator<Item = (EntityId, &'a EntityType, Vector3<f64>)> + '_ {
+        let (min_x, min_z) = chunk_of(center.x - range, center.z - range);
+        let (max_x, max_z) = chunk_of(center.x + range, center.z + range);
+        (min_x..=max_x)
+            .flat_map(move |x| (min_z..=max_z).filter_map(move |z| self.chunks.get(&(x, z))))
+            .flatten()
+            .copied()
+    }
+}
+
+fn chunk_of(x: f64, z: f64) -> (i32, i32) {
+    ((x.floor() as i32) >> 4, (z.floor() as i32) >> 4)
+}
+
+/// The entities which got in or out of a player's tracking range.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct TrackingUpdate {
+    pub spawned: Vec<EntityId>,
+    pub removed: Vec<EntityId>,
+}
+
+/// The set of entities a single client currently knows about.
+#[derive(Default)]
+pub struct EntityTracker {
+    tracked: HashSet<EntityId>,
+}
+
+impl EntityTracker {
+    pub fn is_tracking(&self, entity_id: EntityId) -> bool {
+        self.tracked.contains(&entity_id)
+    }
+
+    /// Returns `true` if the entity was not tracked before.
+    pub fn track(&mut self, entity_id: EntityId) -> bool {
+        self.tracked.insert(entity_id)
+    }
+
+    /// Returns `true` if the entity was tracked before.
+    pub fn untrack(&mut self, entity_id: EntityId) -> bool {
+        self.tracked.remove(&entity_id)
+    }
+
+    /// Forgets all entities, e.g. because the client dropped them when respawning.
+    pub fn clear(&mut self) {
+        self.tracked.clear();
+    }
+
+    /// Tracks the entities which are now in range and untracks the ones which are not, including
+    /// entities that are gone entirely.
+    pub fn update<'a>(
+        &mut self,
+        config: &EntityTrackingConfig,
+        viewer: Vector3<f64>,
+        view_distance: u8,
+        entities: impl IntoIterator<Item = (EntityId, &'a EntityType, Vector3<f64>)>,
+    ) -> TrackingUpdate {
+        let mut update = TrackingUpdate::default();
+        let mut in_range = HashSet::new();
+        for (entity_id, entity_type, pos) in entities {
+            if !is_in_tracking_range(config, entity_type, viewer, pos, view_distance) {
+                continue;
+            }
+            in_range.insert(entity_id);
+            if self.tracked.insert(entity_id) {
+                update.spawned.push(entity_id);
+            }
+        }
+        self.tracked.retain(|entity_id| {
+            let keep = in_range.contains(entity_id);
+            if !keep {
+                update.removed.push(*entity_id);
+            }
+            keep
+        });
+        update
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_config::EntityTrackingConfig;
+    use pumpkin_data::entity::EntityType;
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{EntityGrid, EntityTracker, tracking_range};
+
+    #[test]
+    fn ranges_depend_on_entity_type_and_view_distance() {
+        let config = EntityTrackingConfig::default();
+        assert_eq!(tracking_range(&config, &EntityType::PLAYER, 10), 48.0);
+        assert_eq!(tracking_range(&config, &EntityType::ZOMBIE, 10), 48.0);
+        assert_eq!(tracking_range(&config, &EntityType::ITEM, 10), 32.0);
+        assert_eq!(tracking_range(&config, &EntityType::SNOWBALL, 10), 64.0);
+        assert_eq!(tracking_range(&config, &EntityType::SNOWBALL, 2), 32.0);
+    }
+
+    #[test]
+    fn spawns_entities_in_range_and_removes_them_when_they_leave() {
+        let config = EntityTrackingConfig::default();
+        let mut tracker = EntityTracker::default();
+        let viewer = Vector3::new(0.0, 64.0, 0.0);
+
+        let update = tracker.update(
+            &config,
+            viewer,
+            10,
+            [
+                (1, &EntityType::ZOMBIE, Vector3::new(40.0, 64.0, 0.0)),
+                (2, &EntityType::ITEM, Vector3::new(40.0, 64.0, 0.0)),
+                (3, &EntityType::PLAYER, Vector3::new(0.0, 64.0, 100.0)),
+            ],
+        );
+        assert_eq!(update.spawned, vec![1]);
+        assert!(update.removed.is_empty());
+
+        // Nothing changes while everything stays where it is
+        let update = tracker.update(
+            &config,
+            viewer,
+            10,
+            [
+                (1, &EntityType::ZOMBIE, Vector3::new(40.0, 64.0, 0.0)),
+                (2, &EntityType::ITEM, Vector3::new(40.0, 64.0, 0.0)),
+            ],
+        );
+        assert!(update.spawned.is_empty() && update.removed.is_empty());
+
+        // The zombie walks away while the player comes closer
+        let update = tracker.update(
+            &config,
+            viewer,
+            10,
+            [
+                (1, &EntityType::ZOMBIE, Vector3::new(60.0, 64.0, 0.0)),
+                (3, &EntityType::PLAYER, Vector3::new(0.0, 64.0, 20.0)),
+            ],
+        );
+        assert_eq!(update.spawned, vec![3]);
+        assert_eq!(update.removed, vec![1]);
+        assert!(tracker.is_tracking(3));
+        assert!(!tracker.is_tracking(1));
+    }
+
+    #[test]
+    fn grid_only_yields_entities_of_nearby_chunks() {
+        let mut grid = EntityGrid::default();
+        grid.insert(1, &EntityType::ZOMBIE, Vector3::new(-20.0, 64.0, 5.0));
+        grid.insert(2, &EntityType::ZOMBIE, Vector3::new(40.0, 64.0, 40.0));
+        grid.insert(3, &EntityType::ITEM, Vector3::new(500.0, 64.0, 0.0));
+        grid.insert(4, &EntityType::ITEM, Vector3::new(0.0, 64.0, -500.0));
+
+        let mut nearby: Vec<_> = grid
+            .nearby(Vector3::new(0.0, 64.0, 0.0), 48.0)
+            .map(|(entity_id, _, _)| entity_id)
+            .collect();
+        nearby.sort_unstable();
+        assert_eq!(nearby, vec![1, 2]);
+        assert_eq!(grid.nearby(Vector3::new(490.0, 64.0, 0.0), 16.0).count(), 1);
+    }
+}
@@ -80,6 +80,7 @@ impl PumpkinError for PlayerDataError {
         match self {
             Self::Io(err) => Some(format!("Failed to load player data: {err}")),
             Self::Nbt(err) => Some(format!("Failed to parse player data: {err}")),
+            Self::Json(err) => Some(format!("Failed to parse player data: {err}")),
         }
     }
 }
@@ -0,0 +1,65 @@
+use crate::entity::player::Player;
+use crate::item::pumpkin_item::{ItemMetadata, PumpkinItem};
+use async_trait::async_trait;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_data::tag::Tagable;
+use pumpkin_inventory::player::PlayerInventory;
+use pumpkin_protocol::client::play::CSetContainerSlot;
+use pumpkin_protocol::codec::item_stack_seralizer::ItemStackSerializer;
+
+pub struct BundleItem;
+
+impl ItemMetadata for BundleItem {
+    fn ids() -> Box<[u16]> {
+        Item::get_tag_values("#minecraft:bundles")
+            .expect("This is a valid vanilla tag")
+            .iter()
+            .map(|key| {
+                Item::from_registry_key(key)
+                    .expect("We just got this key from the registry")
+                    .id
+            })
+            .collect::<Vec<_>>()
+            .into_boxed_slice()
+    }
+}
+
+#[async_trait]
+impl PumpkinItem for BundleItem {
+    async fn normal_use(&self, _item: &Item, player: &Player) {
+        // Using a bundle empties it onto the ground
+        let contents = {
+            let mut inventory = player.inventory().lock().await;
+            let Some(bundle) = inventory.held_item_mut().as_mut() else {
+                return;
+            };
+            let contents = bundle.bundle_take_all();
+            if contents.is_empty() {
+                return;
+            }
+            let slot_data = ItemStackSerializer::from(bundle.clone());
+            let dest_packet = CSetContainerSlot::new(
+                PlayerInventory::CONTAINER_ID,
+                inventory.state_id as i32,
+                inventory.get_selected_slot() as i16,
+                &slot_data,
+            );
+            player.client.enqueue_packet(&dest_packet).await;
+            contents
+        };
+
+        player
+            .world()
+            .await
+            .play_sound(
+                Sound::ItemBundleDropContents,
+                SoundCategory::Players,
+                &player.position(),
+            )
+            .await;
+        for stack in contents {
+            player.drop_stack(stack).await;
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::entity::player::Player;
+use crate::item::pumpkin_item::{ItemMetadata, PumpkinItem};
+use crate::server::Server;
+use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::GameMode;
+use pumpkin_util::math::position::BlockPos;
+use pumpkin_world::block::BlockDirection;
+
+pub struct CompassItem;
+
+impl ItemMetadata for CompassItem {
+    fn ids() -> Box<[u16]> {
+        [Item::COMPASS.id].into()
+    }
+}
+
+#[async_trait]
+impl PumpkinItem for CompassItem {
+    async fn use_on_block(
+        &self,
+        _item: &Item,
+        player: &Player,
+        location: BlockPos,
+        _face: BlockDirection,
+        block: &Block,
+        _server: &Server,
+    ) {
+        if *block != Block::LODESTONE {
+            return;
+        }
+        let world = player.world().await;
+        let dimension = world.dimension_type.name().to_string();
+        let creative = player.gamemode.load() == GameMode::Creative;
+
+        let bound = {
+            let mut inventory = player.inventory().lock().await;
+            let slot = inventory.get_selected_slot();
+            let Some(compass) = inventory.held_item_mut().as_mut() else {
+                return;
+            };
+            // A single compass is bound itself, otherwise one is taken off the stack
+            let (held, bound) = if !creative && compass.item_count == 1 {
+                compass.bind_to_lodestone(&dimension, location);
+                (compass.clone(), None)
+            } else {
+                let mut bound = compass.copy_with_count(1);
+                bound.bind_to_lodestone(&dimension, location);
+                if !creative {
+                    compass.decrement(1);
+                }
+                (compass.clone(), Some(bound))
+            };
+            player.update_single_slot(&mut inventory, slot, held).await;
+            bound
+        };
+
+        world
+            .play_block_sound(
+                Sound::ItemLodestoneCompassLock,
+                SoundCategory::Players,
+                location,
+            )
+            .await;
+        if let Some(bound) = bound {
+            if let Some(leftover) = player.give_item(bound).await {
+                player.drop_stack(leftover).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use async_trait::async_trait;
+use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_util::GameMode;
+
+use crate::entity::elytra::FIREWORK_BOOST_TICKS;
+use crate::entity::player::Player;
+use crate::item::pumpkin_item::{ItemMetadata, PumpkinItem};
+
+pub struct FireworkRocketItem;
+
+impl ItemMetadata for FireworkRocketItem {
+    fn ids() -> Box<[u16]> {
+        [Item::FIREWORK_ROCKET.id].into()
+    }
+}
+
+#[async_trait]
+impl PumpkinItem for FireworkRocketItem {
+    async fn normal_use(&self, _item: &Item, player: &Player) {
+        // TODO: Spawn the rocket entity, for now it only boosts gliding players
+        if !player
+            .living_entity
+            .entity
+            .fall_flying
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            return;
+        }
+        if player.gamemode.load() != GameMode::Creative {
+            player.inventory().lock().await.decrease_current_stack(1);
+            player.send_inventory().await;
+        }
+        player.firework_boost.set(FIREWORK_BOOST_TICKS);
+        player
+            .world()
+            .await
+            .play_sound(
+                Sound::EntityFireworkRocketLaunch,
+                SoundCategory::Ambient,
+                &player.position(),
+            )
+            .await;
+    }
+}
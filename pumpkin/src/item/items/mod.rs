@@ -1,6 +1,9 @@
 mod axe;
 mod bucket;
+mod bundle;
+mod compass;
 mod egg;
+mod firework_rocket;
 mod flint_and_steel;
 mod hoe;
 mod honeycomb;
@@ -11,7 +14,10 @@ mod trident;
 
 use axe::AxeItem;
 use bucket::{EmptyBucketItem, FilledBucketItem};
+use bundle::BundleItem;
+use compass::CompassItem;
 use egg::EggItem;
+use firework_rocket::FireworkRocketItem;
 use flint_and_steel::FlintAndSteelItem;
 use hoe::HoeItem;
 use honeycomb::HoneyCombItem;
@@ -37,6 +43,9 @@ pub fn default_registry() -> Arc<ItemRegistry> {
     manager.register(ShovelItem);
     manager.register(AxeItem);
     manager.register(HoneyCombItem);
+    manager.register(BundleItem);
+    manager.register(FireworkRocketItem);
+    manager.register(CompassItem);
 
     Arc::new(manager)
 }
@@ -1,9 +1,21 @@
+use std::sync::Arc;
+
 use async_trait::async_trait;
+use pumpkin_data::Block;
+use pumpkin_data::entity::EntityType;
 use pumpkin_data::item::Item;
+use pumpkin_data::sound::{Sound, SoundCategory};
+use pumpkin_inventory::player::PlayerInventory;
+use pumpkin_protocol::client::play::CSetContainerSlot;
+use pumpkin_protocol::codec::item_stack_seralizer::ItemStackSerializer;
 use pumpkin_util::GameMode;
+use pumpkin_world::item::ItemStack;
 
 use crate::{
-    entity::player::Player,
+    entity::{
+        player::Player,
+        projectile::trident::{MIN_CHARGE_TICKS, TridentEntity},
+    },
     item::pumpkin_item::{ItemMetadata, PumpkinItem},
 };
 
@@ -15,8 +27,67 @@ impl ItemMetadata for TridentItem {
     }
 }
 
+fn riptide_sound(level: i32) -> Sound {
+    match level {
+        ..=1 => Sound::ItemTridentRiptide1,
+        2 => Sound::ItemTridentRiptide2,
+        _ => Sound::ItemTridentRiptide3,
+    }
+}
+
 #[async_trait]
 impl PumpkinItem for TridentItem {
+    async fn release_use(&self, _item: &Item, player: &Player, use_ticks: i32) {
+        if use_ticks < MIN_CHARGE_TICKS {
+            return;
+        }
+        let Some(stack) = player.inventory().lock().await.held_item().cloned() else {
+            return;
+        };
+        let world = player.world().await;
+        let position = player.position();
+
+        let riptide = stack.patch.enchantment_level("riptide").unwrap_or(0);
+        if riptide > 0 {
+            // Riptide tridents aren't thrown, they launch their holder in water or rain. The
+            // client moves the player itself, so only the others need to hear it
+            let block_pos = player.living_entity.entity.block_pos.load();
+            let in_water = world
+                .get_block(&block_pos)
+                .await
+                .is_ok_and(|block| block == Block::WATER);
+            if in_water || world.is_raining_at(&block_pos).await {
+                world
+                    .play_sound(riptide_sound(riptide), SoundCategory::Players, &position)
+                    .await;
+            }
+            return;
+        }
+
+        let creative = player.gamemode.load() == GameMode::Creative;
+        if !creative {
+            let mut inventory = player.inventory().lock().await;
+            inventory.decrease_current_stack(1);
+            let slot_data = ItemStackSerializer::from(ItemStack::EMPTY);
+            let dest_packet = CSetContainerSlot::new(
+                PlayerInventory::CONTAINER_ID,
+                inventory.state_id as i32,
+                inventory.get_selected_slot() as i16,
+                &slot_data,
+            );
+            player.client.enqueue_packet(&dest_packet).await;
+        }
+
+        let owner = &player.living_entity.entity;
+        let entity = world.create_entity(position, EntityType::TRIDENT);
+        let trident = TridentEntity::new(entity, owner, stack, !creative);
+        trident.shoot(owner.yaw.load(), owner.pitch.load());
+        world.spawn_entity(Arc::new(trident)).await;
+        world
+            .play_sound(Sound::ItemTridentThrow, SoundCategory::Players, &position)
+            .await;
+    }
+
     fn can_mine(&self, player: &Player) -> bool {
         player.gamemode.load() != GameMode::Creative
     }
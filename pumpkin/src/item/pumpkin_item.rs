@@ -14,6 +14,10 @@ pub trait ItemMetadata {
 pub trait PumpkinItem: Send + Sync {
     async fn normal_use(&self, _block: &Item, _player: &Player) {}
 
+    /// Called when the player stops using the item after holding it down for `use_ticks` ticks,
+    /// like throwing a charged trident.
+    async fn release_use(&self, _item: &Item, _player: &Player, _use_ticks: i32) {}
+
     async fn use_on_block(
         &self,
         _item: &Item,
@@ -26,6 +26,13 @@ impl ItemRegistry {
         }
     }
 
+    pub async fn release_use(&self, item: &Item, player: &Player, use_ticks: i32) {
+        let pumpkin_item = self.get_pumpkin_item(item.id);
+        if let Some(pumpkin_item) = pumpkin_item {
+            pumpkin_item.release_use(item, player, use_ticks).await;
+        }
+    }
+
     pub async fn use_on_block(
         &self,
         item: &Item,
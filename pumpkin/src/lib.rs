@@ -1,7 +1,8 @@
 // Not warn event sending macros
 #![allow(unused_labels)]
 
-use crate::net::{Client, lan_broadcast, query, rcon::RCONServer};
+use crate::command::commands::pregen;
+use crate::net::{Client, lan_broadcast, metrics, query, rcon::RCONServer};
 use crate::server::{Server, ticker::Ticker};
 use log::{Level, LevelFilter, Log};
 use net::authentication::fetch_mojang_public_keys;
@@ -34,6 +35,9 @@ pub mod plugin;
 pub mod server;
 pub mod world;
 
+#[cfg(test)]
+mod test_utils;
+
 const GIT_VERSION: &str = env!("GIT_VERSION");
 
 #[cfg(feature = "dhat-heap")]
@@ -186,10 +190,35 @@ impl PumpkinServer {
     pub async fn new() -> Self {
         let server = Arc::new(Server::new());
 
+        if let Err(err) = data::load_recipes() {
+            log::error!("Failed to load recipes, using the built-in ones: {err}");
+        }
+
         for world in &*server.worlds.read().await {
             world.level.read_spawn_chunks(&Server::spawn_chunks()).await;
         }
 
+        let pregen_radius = advanced_config().chunk.pregen_radius;
+        if pregen_radius > 0 {
+            let world = server.worlds.read().await.first().cloned();
+            if let Some(world) = world {
+                let (spawn, _) = world.spawn_point().await;
+                let center = spawn.chunk_and_chunk_relative_position().0;
+                match i32::try_from(pregen_radius)
+                    .ok()
+                    .and_then(|radius| pregen::chunks_around(center, radius))
+                {
+                    Some(chunks) => {
+                        pregen::start(&server, world, chunks, None);
+                    }
+                    None => log::warn!(
+                        "Not pregenerating, chunk.pregen_radius {pregen_radius} is larger than {}",
+                        pregen::MAX_RADIUS
+                    ),
+                }
+            }
+        }
+
         // Setup the TCP server socket.
         let listener = tokio::net::TcpListener::bind(BASIC_CONFIG.server_address)
             .await
@@ -201,7 +230,7 @@ impl PumpkinServer {
 
         let rcon = advanced_config().networking.rcon.clone();
 
-        let mut ticker = Ticker::new(BASIC_CONFIG.tps);
+        let mut ticker = Ticker::new();
 
         if advanced_config().commands.use_console {
             if let Some((wrapper, _)) = &*LOGGER_IMPL {
@@ -235,6 +264,11 @@ impl PumpkinServer {
             server.spawn_task(lan_broadcast::start_lan_broadcast(addr));
         }
 
+        if advanced_config().networking.metrics.enabled {
+            log::info!("Metrics endpoint is enabled. Starting...");
+            server.spawn_task(metrics::start_metrics_server(server.clone()));
+        }
+
         if BASIC_CONFIG.allow_chat_reports {
             let mojang_public_keys = fetch_mojang_public_keys(server.auth_client.as_ref().unwrap())
                 .await
@@ -308,7 +342,6 @@ impl PumpkinServer {
             let server = self.server.clone();
 
             tasks.spawn(async move {
-                // TODO: We need to add a time-out here for un-cooperative clients
                 client.process_packets(&server).await;
 
                 if client
@@ -327,19 +360,7 @@ impl PumpkinServer {
                         //TODO: Move these somewhere less likely to be forgotten
                         log::debug!("Cleaning up player for id {}", id);
 
-                        // Save player data on disconnect
-                        if let Err(e) = server
-                            .player_data_storage
-                            .handle_player_leave(&player)
-                            .await
-                        {
-                            log::error!("Failed to save player data on disconnect: {}", e);
-                        }
-
-                        // Remove the player from its world
-                        player.remove().await;
-                        // Tick down the online count
-                        server.remove_player(&player).await;
+                        server.disconnect_player(&player).await;
                     }
                 } else {
                     // Also handle case of client connects but does not become a player (like a server
@@ -65,6 +65,9 @@ pub mod plugin;
 pub mod server;
 pub mod world;
 
+#[cfg(test)]
+mod test_utils;
+
 #[cfg(feature = "dhat-heap")]
 #[global_allocator]
 static ALLOC: dhat::Alloc = dhat::Alloc;
@@ -0,0 +1,287 @@
+use pumpkin_util::math::polynomial_rolling_hash;
+use rsa::{
+    RsaPublicKey,
+    pkcs1v15::{Signature, VerifyingKey},
+    pkcs8::DecodePublicKey,
+    signature::Verifier,
+};
+use sha2::Sha256;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// How many messages a client can acknowledge in a chat message. Vanilla: 20
+pub const LAST_SEEN_WINDOW: usize = 20;
+/// How many signed messages a player may have received without acknowledging them. Vanilla: 4096
+pub const MAX_PENDING_MESSAGES: usize = 4096;
+
+/// Builds the data a client signs when sending a chat message, in the same layout as vanilla.
+pub fn signed_message_body(
+    sender: Uuid,
+    session_id: Uuid,
+    index: i32,
+    salt: i64,
+    timestamp_millis: i64,
+    message: &str,
+    last_seen: &[Box<[u8]>],
+) -> Vec<u8> {
+    let mut body = Vec::new();
+    // Signature version
+    body.extend_from_slice(&1i32.to_be_bytes());
+    body.extend_from_slice(sender.as_bytes());
+    body.extend_from_slice(session_id.as_bytes());
+    body.extend_from_slice(&index.to_be_bytes());
+    body.extend_from_slice(&salt.to_be_bytes());
+    // Signed in seconds while the packet is in milliseconds
+    body.extend_from_slice(&(timestamp_millis / 1000).to_be_bytes());
+    body.extend_from_slice(&(message.len() as i32).to_be_bytes());
+    body.extend_from_slice(message.as_bytes());
+    body.extend_from_slice(&(last_seen.len() as i32).to_be_bytes());
+    for signature in last_seen {
+        body.extend_from_slice(signature);
+    }
+    body
+}
+
+/// Checks a chat message signature (RSA-SHA256) against the DER encoded public key of the
+/// sender's chat session.
+pub fn verify_message_signature(public_key: &[u8], body: &[u8], signature: &[u8]) -> bool {
+    let Ok(public_key) = RsaPublicKey::from_public_key_der(public_key) else {
+        return false;
+    };
+    let Ok(signature) = Signature::try_from(signature) else {
+        return false;
+    };
+    VerifyingKey::<Sha256>::new(public_key)
+        .verify(body, &signature)
+        .is_ok()
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum LastSeenError {
+    #[error("advanced the last seen window by {0} messages, but at most {1} were expected")]
+    InvalidOffset(i32, usize),
+    #[error("acknowledged an unknown or previously ignored message at index {0}")]
+    UnknownAcknowledged(usize),
+    #[error("ignored a previously acknowledged message at index {0}")]
+    IgnoredAcknowledged(usize),
+    #[error("sent a last seen checksum which doesn't match, the client desynced")]
+    ChecksumMismatch,
+}
+
+struct TrackedMessage {
+    signature: Box<[u8]>,
+    /// Whether the client has not acknowledged nor ignored the message yet
+    pending: bool,
+}
+
+/// Tracks the signed messages sent to a player, so the messages the player claims to have seen
+/// can be checked and turned back into signatures. Works like vanilla's last seen validator.
+pub struct LastSeenValidator {
+    /// The window the client acknowledges is always the first [`LAST_SEEN_WINDOW`] entries.
+    /// Empty slots are messages which were ignored or never existed.
+    tracked: Vec<Option<TrackedMessage>>,
+    last_pending: Option<Box<[u8]>>,
+}
+
+impl Default for LastSeenValidator {
+    fn default() -> Self {
+        Self {
+            tracked: std::iter::repeat_with(|| None)
+                .take(LAST_SEEN_WINDOW)
+                .collect(),
+            last_pending: None,
+        }
+    }
+}
+
+impl LastSeenValidator {
+    /// Tracks a signed message sent to the player.
+    pub fn add_pending(&mut self, signature: &[u8]) {
+        // The client tracks a message it receives twice in a row only once
+        if self.last_pending.as_deref() == Some(signature) {
+            return;
+        }
+        self.tracked.push(Some(TrackedMessage {
+            signature: signature.into(),
+            pending: true,
+        }));
+        self.last_pending = Some(signature.into());
+    }
+
+    /// How many messages are tracked, including the acknowledgment window.
+    pub fn tracked_count(&self) -> usize {
+        self.tracked.len()
+    }
+
+    /// Drops the oldest `offset` messages, because the client moved its window past them.
+    pub fn apply_offset(&mut self, offset: i32) -> Result<(), LastSeenError> {
+        let max = self.tracked.len() - LAST_SEEN_WINDOW;
+        match usize::try_from(offset) {
+            Ok(offset) if offset <= max => {
+                self.tracked.drain(..offset);
+                Ok(())
+            }
+            _ => Err(LastSeenError::InvalidOffset(offset, max)),
+        }
+    }
+
+    /// Applies the acknowledgment of a chat message and returns the signatures of the messages
+    /// the client has seen, oldest first. `acknowledged` is the 20 bit set sent by the client.
+    pub fn apply_update(
+        &mut self,
+        offset: i32,
+        acknowledged: &[u8],
+        checksum: u8,
+    ) -> Result<Vec<Box<[u8]>>, LastSeenError> {
+        self.apply_offset(offset)?;
+
+        let mut last_seen = Vec::new();
+        for index in 0..LAST_SEEN_WINDOW {
+            let is_acknowledged = acknowledged
+                .get(index / 8)
+                .is_some_and(|byte| byte >> (index % 8) & 1 == 1);
+            let entry = &mut self.tracked[index];
+            if is_acknowledged {
+                let Some(message) = entry else {
+                    return Err(LastSeenError::UnknownAcknowledged(index));
+                };
+                message.pending = false;
+                last_seen.push(message.signature.clone());
+            } else {
+                if entry.as_ref().is_some_and(|message| !message.pending) {
+                    return Err(LastSeenError::IgnoredAcknowledged(index));
+                }
+                *entry = None;
+            }
+        }
+
+        // The client can skip this check by sending 0
+        if checksum != 0 && checksum != polynomial_rolling_hash(&last_seen) {
+            return Err(LastSeenError::ChecksumMismatch);
+        }
+        Ok(last_seen)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::polynomial_rolling_hash;
+    use rsa::{
+        RsaPrivateKey, RsaPublicKey,
+        pkcs1v15::SigningKey,
+        pkcs8::EncodePublicKey,
+        signature::{SignatureEncoding, Signer},
+    };
+    use sha2::Sha256;
+    use uuid::Uuid;
+
+    use super::{
+        LAST_SEEN_WINDOW, LastSeenError, LastSeenValidator, signed_message_body,
+        verify_message_signature,
+    };
+
+    #[test]
+    fn only_correctly_signed_messages_are_accepted() {
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 1024).unwrap();
+        let public_key = RsaPublicKey::from(&private_key)
+            .to_public_key_der()
+            .unwrap();
+        let signing_key = SigningKey::<Sha256>::new(private_key);
+
+        let sender = Uuid::new_v4();
+        let session = Uuid::new_v4();
+        let last_seen: Vec<Box<[u8]>> = vec![Box::new([1; 256])];
+        let body = signed_message_body(sender, session, 3, 42, 1_700_000_000_000, "hi", &last_seen);
+        let signature = signing_key.sign(&body).to_vec();
+
+        assert!(verify_message_signature(
+            public_key.as_bytes(),
+            &body,
+            &signature
+        ));
+
+        // A different message, a different index or a forged signature are all rejected
+        let tampered =
+            signed_message_body(sender, session, 3, 42, 1_700_000_000_000, "ho", &last_seen);
+        assert!(!verify_message_signature(
+            public_key.as_bytes(),
+            &tampered,
+            &signature
+        ));
+        let replayed =
+            signed_message_body(sender, session, 4, 42, 1_700_000_000_000, "hi", &last_seen);
+        assert!(!verify_message_signature(
+            public_key.as_bytes(),
+            &replayed,
+            &signature
+        ));
+        let mut forged = signature.clone();
+        forged[0] ^= 1;
+        assert!(!verify_message_signature(
+            public_key.as_bytes(),
+            &body,
+            &forged
+        ));
+        assert!(!verify_message_signature(&[], &body, &signature));
+    }
+
+    /// The bit set a client sends acknowledging the given window indices.
+    fn acknowledged(indices: &[usize]) -> [u8; 3] {
+        let mut bits = [0; 3];
+        for index in indices {
+            bits[index / 8] |= 1 << (index % 8);
+        }
+        bits
+    }
+
+    #[test]
+    fn last_seen_window_follows_the_client() {
+        let mut validator = LastSeenValidator::default();
+        let first: Box<[u8]> = Box::new([1; 256]);
+        let second: Box<[u8]> = Box::new([2; 256]);
+        validator.add_pending(&first);
+        validator.add_pending(&second);
+        assert_eq!(validator.tracked_count(), LAST_SEEN_WINDOW + 2);
+
+        // The client moves its window by both messages and saw both of them
+        let expected = vec![first.clone(), second.clone()];
+        let checksum = polynomial_rolling_hash(&expected);
+        let bits = acknowledged(&[LAST_SEEN_WINDOW - 2, LAST_SEEN_WINDOW - 1]);
+        assert_eq!(validator.apply_update(2, &bits, checksum), Ok(expected));
+
+        // Nothing new arrived, so the window can't move again
+        assert_eq!(
+            validator.apply_update(1, &bits, 0),
+            Err(LastSeenError::InvalidOffset(1, 0))
+        );
+        assert_eq!(
+            validator.apply_offset(-1),
+            Err(LastSeenError::InvalidOffset(-1, 0))
+        );
+        // Messages which were acknowledged can't be ignored afterwards
+        assert_eq!(
+            validator.apply_update(0, &acknowledged(&[LAST_SEEN_WINDOW - 1]), 0),
+            Err(LastSeenError::IgnoredAcknowledged(LAST_SEEN_WINDOW - 2))
+        );
+    }
+
+    #[test]
+    fn unknown_messages_and_bad_checksums_are_rejected() {
+        let mut validator = LastSeenValidator::default();
+        assert_eq!(
+            validator.apply_update(0, &acknowledged(&[0]), 0),
+            Err(LastSeenError::UnknownAcknowledged(0))
+        );
+
+        let mut validator = LastSeenValidator::default();
+        validator.add_pending(&[1; 256]);
+        let bits = acknowledged(&[LAST_SEEN_WINDOW - 1]);
+        let wrong = polynomial_rolling_hash(&[Box::new([1; 256])])
+            .wrapping_add(1)
+            .max(1);
+        assert_eq!(
+            validator.apply_update(1, &bits, wrong),
+            Err(LastSeenError::ChecksumMismatch)
+        );
+    }
+}
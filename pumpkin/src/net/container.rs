@@ -1,3 +1,4 @@
+use crate::block::blocks::shulker_box::ShulkerBoxBlock;
 use crate::entity::player::Player;
 use crate::server::Server;
 use pumpkin_data::item::Item;
@@ -9,7 +10,9 @@ use pumpkin_inventory::container_click::{
 use pumpkin_inventory::drag_handler::DragHandler;
 use pumpkin_inventory::player::{SLOT_BOOT, SLOT_CHEST, SLOT_HELM, SLOT_HOTBAR_START, SLOT_LEG};
 use pumpkin_inventory::window_property::{WindowProperty, WindowPropertyTrait};
-use pumpkin_inventory::{InventoryError, OptionallyCombinedContainer, container_click};
+use pumpkin_inventory::{
+    InventoryError, OpenContainer, OptionallyCombinedContainer, container_click, leave_container,
+};
 use pumpkin_protocol::client::play::{
     CCloseContainer, COpenScreen, CSetContainerContent, CSetContainerProperty, CSetContainerSlot,
 };
@@ -18,6 +21,7 @@ use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::server::play::SClickContainer;
 use pumpkin_util::text::TextComponent;
 use pumpkin_util::{GameMode, MutableSplitSlice};
+use pumpkin_world::advancement::AdvancementTrigger;
 use pumpkin_world::item::ItemStack;
 use std::sync::Arc;
 
@@ -95,6 +99,41 @@ impl Player {
             .await;
     }
 
+    /// Leaves the container the player has open, running the block's close handler. The
+    /// container is dropped once nobody uses it. The item on the cursor goes back into the
+    /// inventory, or is dropped if it doesn't fit.
+    pub async fn close_open_container(&self, server: &Server) {
+        if let Some(id) = self.open_container.load() {
+            let mut open_containers = server.open_containers.write().await;
+            if let Some(container) = open_containers.get_mut(&id) {
+                // If the container contains both a location and a type, run the `on_close` `block_manager` handler
+                if let Some(pos) = container.get_location() {
+                    if let Some(block) = container.get_block() {
+                        server
+                            .block_registry
+                            .close(&block, self, pos, server, container)
+                            .await;
+                    }
+                }
+
+                let mut inventory = self.inventory().lock().await;
+                inventory.state_id = inventory.state_id.saturating_sub(2);
+            }
+            leave_container(&mut open_containers, id, self.entity_id()).await;
+            drop(open_containers);
+            self.open_container.store(None);
+        }
+
+        let carried_item = self.carried_item.lock().await.take();
+        if let Some(carried_item) = carried_item {
+            let leftover = self.inventory().lock().await.insert_stack(carried_item);
+            if let Some(leftover) = leftover {
+                self.drop_stack(leftover).await;
+            }
+            self.send_inventory().await;
+        }
+    }
+
     pub async fn set_container_property<T: WindowPropertyTrait>(
         &mut self,
         window_property: WindowProperty<T>,
@@ -139,6 +178,7 @@ impl Player {
         }
 
         let click = Click::new(packet.mode, packet.button, packet.slot)?;
+        let items_before = self.carried_items().await;
         let (crafted_item, crafted_item_slot) = {
             let mut inventory = self.inventory().lock().await;
             let combined =
@@ -184,6 +224,12 @@ impl Player {
             }
         }
 
+        // Shulker boxes keep their contents in the block entity, so it has to follow every change
+        if let Some(container) = opened_container.as_deref() {
+            self.save_shulker_box_contents(server, container.as_ref())
+                .await;
+        }
+
         if let Some(mut opened_container) = opened_container {
             if update_whole_container {
                 drop(opened_container);
@@ -200,6 +246,11 @@ impl Player {
                 }
             }
         }
+
+        // Like taking crafted items, or items out of a chest
+        if self.carried_items().await != items_before {
+            self.trigger_inventory_changed().await;
+        }
         Ok(())
     }
 
@@ -343,12 +394,12 @@ impl Player {
         }
     }
 
-    /// TODO: Allow equiping/de equiping armor and allow taking items from crafting grid
+    /// TODO: Allow equiping/de equiping armor
     async fn shift_mouse_click(
         &self,
         opened_container: Option<&mut Box<dyn Container>>,
         slot: container_click::Slot,
-        _taking_crafted: bool,
+        taking_crafted: bool,
     ) -> Result<(), InventoryError> {
         let mut inventory = self.inventory().lock().await;
         let has_container = opened_container.is_some();
@@ -357,8 +408,33 @@ impl Player {
             .map_or(0, |c| c.all_slots_ref().len());
         let mut container = OptionallyCombinedContainer::new(&mut inventory, opened_container);
 
+        if taking_crafted {
+            let crafted = match slot {
+                container_click::Slot::Normal(slot) => container
+                    .all_slots()
+                    .get_mut(slot)
+                    .and_then(|item_stack| item_stack.take()),
+                container_click::Slot::OutsideInventory => None,
+            };
+            drop(container);
+            drop(inventory);
+            // The ingredients are used up either way, so whatever doesn't fit is dropped
+            if let Some(crafted) = crafted {
+                if let Some(leftover) = self.give_item(crafted).await {
+                    self.drop_stack(leftover).await;
+                }
+            }
+            return Ok(());
+        }
+
         match slot {
             container_click::Slot::Normal(slot) => {
+                let accepted_by_container = container
+                    .all_slots_ref()
+                    .get(slot)
+                    .copied()
+                    .flatten()
+                    .is_none_or(|item| container.accepts_item(0, item));
                 let mut all_slots = container.all_slots();
                 let (item_stack, mut split_slice) =
                     MutableSplitSlice::extract_ith(&mut all_slots, slot);
@@ -397,6 +473,11 @@ impl Player {
                         target_inv.clone().collect()
                     };
 
+                // Containers like shulker boxes refuse some items
+                if has_container && target_inv == &inv1_range && !accepted_by_container {
+                    return Ok(());
+                }
+
                 //Handle armor slots
                 if !has_container {
                     let temp_item_stack = ItemStack::new(1, clicked_item_stack.item.clone());
@@ -585,6 +666,24 @@ impl Player {
         }
     }
 
+    async fn save_shulker_box_contents(&self, server: &Server, container: &dyn Container) {
+        if container.window_type() != &WindowType::ShulkerBox {
+            return;
+        }
+        let Some(id) = self.open_container.load() else {
+            return;
+        };
+        let location = server
+            .open_containers
+            .read()
+            .await
+            .get(&id)
+            .and_then(OpenContainer::get_location);
+        if let Some(location) = location {
+            ShulkerBoxBlock::save_contents(&*self.world().await, location, container).await;
+        }
+    }
+
     async fn get_current_players_in_container(&self, server: &Server) -> Vec<Arc<Self>> {
         let player_ids: Vec<i32> = {
             let open_containers = server.open_containers.read().await;
@@ -658,6 +757,23 @@ impl Player {
         Ok(())
     }
 
+    /// Crafts the output of the open container again, e.g. after the recipes were reloaded,
+    /// and sends it to the player if it changed.
+    pub async fn refresh_crafting_output(&self, server: &Server) {
+        let container = self.get_open_container(server).await;
+        let mut container = match container.as_ref() {
+            Some(container) => Some(container.lock().await),
+            None => None,
+        };
+        let mut inventory = self.inventory().lock().await;
+        let mut combined =
+            OptionallyCombinedContainer::new(&mut inventory, container.as_deref_mut());
+        if combined.craft() {
+            drop(inventory);
+            self.set_container_content(container.as_deref_mut()).await;
+        }
+    }
+
     pub async fn get_open_container(
         &self,
         server: &Server,
@@ -668,57 +784,63 @@ impl Player {
         }
     }
 
-    // TODO: Use this method when actually picking up items instead of just the command
-    async fn pickup_items(&self, item: Item, amount: u32) {
-        let mut amount_left = amount;
-        let max_stack = item.components.max_stack_size;
+    /// Puts the stack into the inventory, filling up stacks of the same item before using empty
+    /// slots. Returns what didn't fit, for the caller to drop.
+    ///
+    /// This method automatically syncs the changed slots with the client.
+    pub async fn give_item(&self, stack: ItemStack) -> Option<ItemStack> {
         let mut inventory = self.inventory().lock().await;
-
-        while let Some(slot) = inventory.get_pickup_item_slot(item.id) {
-            let item_stack = inventory
-                .get_slot(slot)
-                .expect("We just called a method that said this was a valid slot");
-
-            if let Some(item_stack) = item_stack {
-                let amount_to_add = max_stack - item_stack.item_count;
-                if let Some(new_amount_left) = amount_left.checked_sub(u32::from(amount_to_add)) {
-                    item_stack.item_count = max_stack;
-                    amount_left = new_amount_left;
-                } else {
-                    // This is safe because amount left is less than amount_to_add which is a u8
-                    item_stack.item_count = max_stack - (amount_to_add - amount_left as u8);
-                    // Return here because if we have less than the max amount left then the whole
-                    // stack will be moved
-                    return;
-                }
-            } else if let Some(new_amount_left) = amount_left.checked_sub(u32::from(max_stack)) {
-                *item_stack = Some(ItemStack {
-                    item: item.clone(),
-                    item_count: max_stack,
-                });
-                amount_left = new_amount_left;
-            } else {
-                *item_stack = Some(ItemStack {
-                    item: item.clone(),
-                    // This is safe because amount left is less than max_stack which is a u8
-                    item_count: amount_left as u8,
-                });
-                // Return here because if we have less than the max amount left then the whole
-                // stack will be moved
-                return;
+        let (leftover, changed_slots) = inventory.insert_stack_tracked(stack);
+        if changed_slots.is_empty() {
+            return leftover;
+        }
+        for slot in changed_slots {
+            if let Ok(Some(stack)) = inventory.get_slot(slot).cloned() {
+                self.update_single_slot(&mut inventory, slot, stack).await;
             }
         }
+        drop(inventory);
+        self.trigger_inventory_changed().await;
+        leftover
+    }
 
-        log::warn!(
-            "{amount} items were discarded because dropping them to the ground is not implemented"
-        );
+    /// The items the player carries, as the `inventory_changed` criteria see them. The crafting
+    /// grid doesn't count.
+    async fn carried_items(&self) -> Vec<&'static str> {
+        let inventory = self.inventory().lock().await;
+        inventory
+            .item_slots()
+            .iter()
+            .chain(inventory.armor_slots().iter())
+            .chain(&[inventory.offhand_slot()])
+            .flatten()
+            .map(|stack| stack.item.registry_key)
+            .collect()
+    }
+
+    /// Lets the `inventory_changed` advancement criteria look at the player's items.
+    async fn trigger_inventory_changed(&self) {
+        let items = self.carried_items().await;
+        self.trigger_advancements(AdvancementTrigger::InventoryChanged { items: &items })
+            .await;
     }
 
     /// Add items to inventory if there's space, else drop them to the ground.
     ///
     /// This method automatically syncs changes with the client.
     pub async fn give_items(&self, item: Item, amount: u32) {
-        self.pickup_items(item, amount).await;
-        self.set_container_content(None).await;
+        let max_stack = u32::from(item.components.max_stack_size);
+        let mut amount_left = amount;
+        while amount_left > 0 {
+            let count = amount_left.min(max_stack);
+            amount_left -= count;
+            // This is safe because count is at most max_stack which is a u8
+            if let Some(leftover) = self
+                .give_item(ItemStack::new(count as u8, item.clone()))
+                .await
+            {
+                self.drop_stack(leftover).await;
+            }
+        }
     }
 }
@@ -0,0 +1,296 @@
+use std::{
+    fmt::Write,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use pumpkin_config::advanced_config;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{SHOULD_STOP, STOP_INTERRUPT, server::Server};
+
+/// Counts packets and bytes going through all client connections.
+pub struct NetworkStats {
+    pub packets_received: AtomicU64,
+    pub packets_sent: AtomicU64,
+    pub bytes_received: AtomicU64,
+    pub bytes_sent: AtomicU64,
+}
+
+pub static NETWORK_STATS: NetworkStats = NetworkStats {
+    packets_received: AtomicU64::new(0),
+    packets_sent: AtomicU64::new(0),
+    bytes_received: AtomicU64::new(0),
+    bytes_sent: AtomicU64::new(0),
+};
+
+impl NetworkStats {
+    pub fn record_received(&self, bytes: usize) {
+        self.packets_received.fetch_add(1, Ordering::Relaxed);
+        self.bytes_received
+            .fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+
+    pub fn record_sent(&self, bytes: usize) {
+        self.packets_sent.fetch_add(1, Ordering::Relaxed);
+        self.bytes_sent.fetch_add(bytes as u64, Ordering::Relaxed);
+    }
+}
+
+pub struct WorldMetrics {
+    pub name: String,
+    pub loaded_chunks: usize,
+    /// Estimated heap size of the block entity NBT in the loaded chunks
+    pub loaded_nbt_bytes: usize,
+    pub entities: usize,
+    pub players: usize,
+}
+
+/// A point-in-time copy of everything we expose, so rendering never holds server locks.
+#[derive(Default)]
+pub struct MetricsSnapshot {
+    pub players_online: usize,
+    pub worlds: Vec<WorldMetrics>,
+    pub mspt: f64,
+    pub tps: f64,
+    pub resident_memory_bytes: Option<u64>,
+    pub packets_received: u64,
+    pub packets_sent: u64,
+    pub bytes_received: u64,
+    pub bytes_sent: u64,
+}
+
+impl MetricsSnapshot {
+    pub async fn capture(server: &Server) -> Self {
+        let mut worlds = Vec::new();
+        for world in server.worlds.read().await.iter() {
+            worlds.push(WorldMetrics {
+                name: world.dimension_type.name().to_string(),
+                loaded_chunks: world.level.loaded_chunk_count(),
+                loaded_nbt_bytes: world.level.loaded_nbt_heap_size(),
+                entities: world.entities.read().await.len(),
+                players: world.players.read().await.len(),
+            });
+        }
+
+        let mspt = server.get_average_tick_time_nanos() as f64 / 1_000_000.0;
+        Self {
+            players_online: worlds.iter().map(|world| world.players).sum(),
+            worlds,
+            mspt,
+            tps: server.get_tps(),
+            resident_memory_bytes: resident_memory_bytes(),
+            packets_received: NETWORK_STATS.packets_received.load(Ordering::Relaxed),
+            packets_sent: NETWORK_STATS.packets_sent.load(Ordering::Relaxed),
+            bytes_received: NETWORK_STATS.bytes_received.load(Ordering::Relaxed),
+            bytes_sent: NETWORK_STATS.bytes_sent.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Renders the snapshot in the Prometheus text exposition format.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, samples: &[(String, f64)]| {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} {kind}");
+            for (labels, value) in samples {
+                let _ = writeln!(out, "{name}{labels} {value}");
+            }
+        };
+
+        let per_world = |value: fn(&WorldMetrics) -> usize| {
+            self.worlds
+                .iter()
+                .map(|world| (format!("{{world=\"{}\"}}", world.name), value(world) as f64))
+                .collect::<Vec<_>>()
+        };
+
+        metric(
+            "pumpkin_players_online",
+            "gauge",
+            "Number of players currently online.",
+            &[(String::new(), self.players_online as f64)],
+        );
+        metric(
+            "pumpkin_loaded_chunks",
+            "gauge",
+            "Number of chunks loaded per world.",
+            &per_world(|world| world.loaded_chunks),
+        );
+        metric(
+            "pumpkin_loaded_nbt_bytes",
+            "gauge",
+            "Estimated heap memory of the block entity NBT in loaded chunks per world.",
+            &per_world(|world| world.loaded_nbt_bytes),
+        );
+        metric(
+            "pumpkin_entities",
+            "gauge",
+            "Number of non-player entities per world.",
+            &per_world(|world| world.entities),
+        );
+        metric(
+            "pumpkin_mspt",
+            "gauge",
+            "Average milliseconds per tick over the last 100 ticks.",
+            &[(String::new(), self.mspt)],
+        );
+        metric(
+            "pumpkin_tps",
+            "gauge",
+            "Ticks per second over the last 100 ticks.",
+            &[(String::new(), self.tps)],
+        );
+        if let Some(memory) = self.resident_memory_bytes {
+            metric(
+                "pumpkin_resident_memory_bytes",
+                "gauge",
+                "Resident memory size of the server process.",
+                &[(String::new(), memory as f64)],
+            );
+        }
+        metric(
+            "pumpkin_packets_total",
+            "counter",
+            "Number of packets processed.",
+            &[
+                (
+                    "{direction=\"received\"}".to_string(),
+                    self.packets_received as f64,
+                ),
+                ("{direction=\"sent\"}".to_string(), self.packets_sent as f64),
+            ],
+        );
+        metric(
+            "pumpkin_network_bytes_total",
+            "counter",
+            "Number of packet bytes processed, before compression and encryption.",
+            &[
+                (
+                    "{direction=\"received\"}".to_string(),
+                    self.bytes_received as f64,
+                ),
+                ("{direction=\"sent\"}".to_string(), self.bytes_sent as f64),
+            ],
+        );
+
+        out
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn resident_memory_bytes() -> Option<u64> {
+    const PAGE_SIZE: u64 = 4096;
+    let statm = std::fs::read_to_string("/proc/self/statm").ok()?;
+    let resident_pages: u64 = statm.split_whitespace().nth(1)?.parse().ok()?;
+    Some(resident_pages * PAGE_SIZE)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn resident_memory_bytes() -> Option<u64> {
+    None
+}
+
+pub async fn start_metrics_server(server: Arc<Server>) {
+    let address = advanced_config().networking.metrics.address;
+    let listener = match TcpListener::bind(address).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            log::error!("Failed to bind metrics endpoint to {address}: {err}");
+            return;
+        }
+    };
+    log::info!("Metrics endpoint running on http://{address}/metrics");
+
+    while !SHOULD_STOP.load(Ordering::Relaxed) {
+        let stream = tokio::select! {
+            stream = listener.accept() => stream,
+            () = STOP_INTERRUPT.notified() => break,
+        };
+        let Ok((stream, _)) = stream else {
+            continue;
+        };
+
+        let server = server.clone();
+        tokio::spawn(async move {
+            handle_connection(stream, || MetricsSnapshot::capture(&server)).await;
+        });
+    }
+}
+
+async fn handle_connection<F, Fut>(mut stream: TcpStream, snapshot: F)
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = MetricsSnapshot>,
+{
+    // We only care about the request line, so a small buffer is enough
+    let mut buf = [0; 1024];
+    let Ok(read) = stream.read(&mut buf).await else {
+        return;
+    };
+    let request = String::from_utf8_lossy(&buf[..read]);
+    let path = request.split_whitespace().nth(1).unwrap_or_default();
+
+    let response = if path == "/metrics" {
+        let body = snapshot().await.render();
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        )
+    } else {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    };
+
+    if let Err(err) = stream.write_all(response.as_bytes()).await {
+        log::debug!("Failed to write metrics response: {err}");
+    }
+    let _ = stream.shutdown().await;
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MetricsSnapshot, WorldMetrics, handle_connection};
+    use crate::test_utils::loopback_streams;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn metrics_endpoint_exposes_player_count() {
+        let (stream, mut client) = loopback_streams().await;
+
+        let server = tokio::spawn(async move {
+            handle_connection(stream, || async {
+                MetricsSnapshot {
+                    players_online: 3,
+                    worlds: vec![WorldMetrics {
+                        name: "minecraft:overworld".to_string(),
+                        loaded_chunks: 42,
+                        loaded_nbt_bytes: 1024,
+                        entities: 7,
+                        players: 3,
+                    }],
+                    ..Default::default()
+                }
+            })
+            .await;
+        });
+
+        client
+            .write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+        server.await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("# TYPE pumpkin_players_online gauge"));
+        assert!(response.contains("\npumpkin_players_online 3\n"));
+        assert!(response.contains("pumpkin_loaded_chunks{world=\"minecraft:overworld\"} 42"));
+        assert!(response.contains("pumpkin_loaded_nbt_bytes{world=\"minecraft:overworld\"} 1024"));
+    }
+}
@@ -5,6 +5,7 @@ use std::{
         Arc,
         atomic::{AtomicBool, AtomicI32, Ordering},
     },
+    time::{Duration, Instant},
 };
 
 use crate::{
@@ -18,9 +19,11 @@ use crate::{
 
 use bytes::Bytes;
 use crossbeam::atomic::AtomicCell;
-use pumpkin_config::networking::compression::CompressionInfo;
+use metrics::NETWORK_STATS;
+use packet_log::PacketLog;
+use pumpkin_config::{advanced_config, networking::compression::CompressionInfo};
 use pumpkin_protocol::{
-    ClientPacket, ConnectionState, Property, RawPacket, ServerPacket,
+    ClientPacket, CompressionPolicy, ConnectionState, Property, RawPacket, ServerPacket,
     client::{config::CConfigDisconnect, login::CLoginDisconnect, play::CPlayDisconnect},
     packet_decoder::{NetworkDecoder, PacketDecodeError},
     packet_encoder::NetworkEncoder,
@@ -38,7 +41,7 @@ use pumpkin_protocol::{
         status::{SStatusPingRequest, SStatusRequest},
     },
 };
-use pumpkin_util::{ProfileAction, text::TextComponent};
+use pumpkin_util::{ProfileAction, text::TextComponent, translation::DEFAULT_LOCALE};
 use serde::Deserialize;
 use sha1::Digest;
 use sha2::Sha256;
@@ -60,12 +63,16 @@ use thiserror::Error;
 use tokio_util::task::TaskTracker;
 use uuid::Uuid;
 pub mod authentication;
+pub mod chat_signing;
 mod container;
 pub mod lan_broadcast;
+pub mod metrics;
 mod packet;
+pub mod packet_log;
 mod proxy;
 pub mod query;
 pub mod rcon;
+pub mod teleport;
 
 #[derive(Deserialize, Clone, Debug)]
 pub struct GameProfile {
@@ -110,7 +117,7 @@ pub struct PlayerConfig {
 impl Default for PlayerConfig {
     fn default() -> Self {
         Self {
-            locale: "en_us".to_string(),
+            locale: DEFAULT_LOCALE.to_string(),
             view_distance: NonZeroU8::new(10).unwrap(),
             chat_mode: ChatMode::Enabled,
             chat_colors: true,
@@ -151,20 +158,43 @@ pub struct Client {
     pub address: Mutex<SocketAddr>,
     /// Indicates if the client is added to the server listing.
     pub added_to_server_listing: AtomicBool,
+    /// Logs raw packets of this client when enabled, for protocol debugging.
+    pub packet_log: PacketLog,
     /// The packet encoder for outgoing packets.
     network_writer: Arc<Mutex<NetworkEncoder<BufWriter<OwnedWriteHalf>>>>,
     /// The packet decoder for incoming packets.
     network_reader: Mutex<NetworkDecoder<BufReader<OwnedReadHalf>>>,
+    /// When the client last sent a packet that was handled, used to close idle connections
+    /// before they start playing.
+    pub last_progress: AtomicCell<Instant>,
+    /// Indicates whether the login succeeded, so the client may acknowledge it.
+    pub sent_login_success: AtomicBool,
+    /// Indicates whether all registry data and the finish packet were sent during configuration.
+    pub sent_finish_config: AtomicBool,
     /// Indicates whether the client should be converted into a player.
     pub make_player: AtomicBool,
+    /// Whether a playing client is being sent back through configuration.
+    pub reconfiguring: AtomicBool,
     /// A collection of tasks associated with this client. The tasks await completion when removing the client.
     tasks: TaskTracker,
     /// An notifier that is triggered when this client is closed.
     close_interrupt: Arc<Notify>,
     /// A queue of serialized packets to send to the network
-    outgoing_packet_queue_send: Sender<Bytes>,
+    outgoing_packet_queue_send: Sender<(Bytes, CompressionPolicy)>,
     /// A queue of serialized packets to send to the network
-    outgoing_packet_queue_recv: Option<Receiver<Bytes>>,
+    outgoing_packet_queue_recv: Option<Receiver<(Bytes, CompressionPolicy)>>,
+}
+
+/// Checks an outgoing packet when Pumpkin is built with the `protocol_strict` feature, panicking
+/// in debug builds and logging in release builds when it is malformed.
+#[cfg(feature = "protocol_strict")]
+pub fn check_outgoing_packet<P: ClientPacket>(packet: &P, data: &[u8]) {
+    if let Err(err) = pumpkin_protocol::strict::check_packet(packet, data) {
+        if cfg!(debug_assertions) {
+            panic!("Malformed packet {}: {err}", P::PACKET_ID);
+        }
+        log::error!("Malformed packet {}: {err}", P::PACKET_ID);
+    }
 }
 
 impl Client {
@@ -184,19 +214,39 @@ impl Client {
             network_writer: Arc::new(Mutex::new(NetworkEncoder::new(BufWriter::new(write)))),
             network_reader: Mutex::new(NetworkDecoder::new(BufReader::new(read))),
             closed: Arc::new(AtomicBool::new(false)),
+            last_progress: AtomicCell::new(Instant::now()),
+            sent_login_success: AtomicBool::new(false),
+            sent_finish_config: AtomicBool::new(false),
             make_player: AtomicBool::new(false),
+            reconfiguring: AtomicBool::new(false),
             close_interrupt: Arc::new(Notify::new()),
             tasks: TaskTracker::new(),
             outgoing_packet_queue_send: send,
             outgoing_packet_queue_recv: Some(recv),
             added_to_server_listing: AtomicBool::new(false),
+            packet_log: PacketLog::default(),
         }
     }
 
     pub fn init(&mut self) {
+        self.network_reader.get_mut().set_max_packet_size(
+            advanced_config()
+                .networking
+                .connection
+                .max_packet_size
+                .into(),
+        );
         self.start_outgoing_packet_task();
     }
 
+    /// Takes the queue of packets waiting to be sent instead of sending them, to look at them in tests.
+    #[cfg(test)]
+    pub(crate) fn take_outgoing_packets(&mut self) -> Receiver<(Bytes, CompressionPolicy)> {
+        self.outgoing_packet_queue_recv
+            .take()
+            .expect("The outgoing packet task wasn't started")
+    }
+
     fn start_outgoing_packet_task(&mut self) {
         let mut packet_receiver = self
             .outgoing_packet_queue_recv
@@ -217,11 +267,17 @@ impl Client {
                     }
                 };
 
-                let Some(packet_data) = recv_result else {
+                let Some((packet_data, compression)) = recv_result else {
                     break;
                 };
 
-                if let Err(err) = writer.lock().await.write_packet(packet_data).await {
+                NETWORK_STATS.record_sent(packet_data.len());
+                if let Err(err) = writer
+                    .lock()
+                    .await
+                    .write_packet_with_policy(packet_data, compression)
+                    .await
+                {
                     // It is expected that the packet will fail if we are closed
                     if !closed.load(std::sync::atomic::Ordering::Relaxed) {
                         log::warn!("Failed to send packet to client {id}: {err}",);
@@ -317,10 +373,9 @@ impl Client {
             .await
             .set_compression(compression.threshold as usize);
 
-        self.network_writer
-            .lock()
-            .await
-            .set_compression((compression.threshold as usize, compression.level));
+        let mut network_writer = self.network_writer.lock().await;
+        network_writer.set_compression((compression.threshold as usize, compression.level));
+        network_writer.set_skip_incompressible(compression.skip_incompressible);
     }
 
     /// Gets the next packet from the network or `None` if the connection has closed
@@ -333,12 +388,23 @@ impl Client {
             },
             packet_result = network_reader.get_raw_packet() => {
                 match packet_result {
-                    Ok(packet) => Some(packet),
+                    Ok(packet) => {
+                        NETWORK_STATS.record_received(packet.payload.len());
+                        self.packet_log.log_serverbound(
+                            self.connection_state.load(),
+                            packet.id,
+                            &packet.payload,
+                        );
+                        Some(packet)
+                    }
                     Err(err) => {
                         if !matches!(err, PacketDecodeError::ConnectionClosed) {
                             log::warn!("Failed to decode packet from client {}: {}", self.id, err);
                             let text = format!("Error while reading incoming packet {err}");
                             self.kick(TextComponent::text(text)).await;
+                            // Clients can't be kicked before logging in, but the rest of the
+                            // stream can't be trusted either
+                            self.close();
                         }
                         None
                     }
@@ -362,11 +428,27 @@ impl Client {
             log::error!("Failed to serialize packet {}: {}", P::PACKET_ID, err);
             return;
         }
-        self.enqueue_packet_data(packet_buf.into()).await;
+        #[cfg(feature = "protocol_strict")]
+        check_outgoing_packet(packet, &packet_buf);
+        self.enqueue_packet_data(packet_buf.into(), P::COMPRESSION)
+            .await;
     }
 
-    pub async fn enqueue_packet_data(&self, packet_data: Bytes) {
-        if let Err(err) = self.outgoing_packet_queue_send.send(packet_data).await {
+    pub async fn enqueue_packet_data(&self, packet_data: Bytes, compression: CompressionPolicy) {
+        if self
+            .reconfiguring
+            .load(std::sync::atomic::Ordering::Relaxed)
+        {
+            // Play packets can't be read during configuration
+            return;
+        }
+        self.packet_log
+            .log_clientbound(self.connection_state.load(), &packet_data);
+        if let Err(err) = self
+            .outgoing_packet_queue_send
+            .send((packet_data, compression))
+            .await
+        {
             // This is expected to fail if we are closed
             if !self.closed.load(std::sync::atomic::Ordering::Relaxed) {
                 log::error!(
@@ -399,12 +481,17 @@ impl Client {
             log::error!("Failed to serialize packet {}: {}", P::PACKET_ID, err);
             return;
         }
+        #[cfg(feature = "protocol_strict")]
+        check_outgoing_packet(packet, &packet_buf);
 
+        NETWORK_STATS.record_sent(packet_buf.len());
+        self.packet_log
+            .log_clientbound(self.connection_state.load(), &packet_buf);
         if let Err(err) = self
             .network_writer
             .lock()
             .await
-            .write_packet(packet_buf.into())
+            .write_packet_with_policy(packet_buf.into(), P::COMPRESSION)
             .await
         {
             // It is expected that the packet will fail if we are closed
@@ -431,8 +518,15 @@ impl Client {
     ///
     /// * `server`: A reference to the `Server` instance.
     pub async fn process_packets(&self, server: &Server) {
+        let timeout = Duration::from_millis(
+            advanced_config()
+                .networking
+                .connection
+                .pre_play_timeout
+                .into(),
+        );
         while !self.make_player.load(std::sync::atomic::Ordering::Relaxed) {
-            let packet = self.get_packet().await;
+            let packet = self.get_packet_before_timeout(timeout).await;
             let Some(packet) = packet else { break };
 
             if let Err(error) = self.handle_packet(server, &packet).await {
@@ -443,10 +537,37 @@ impl Client {
                     error
                 );
                 self.kick(TextComponent::text(text)).await;
+            } else {
+                self.last_progress.store(Instant::now());
             }
         }
     }
 
+    /// Gets the next packet like [`Client::get_packet`], but closes the connection if it doesn't
+    /// arrive within `timeout` of the last handled packet. This keeps clients which open a
+    /// connection but never finish logging in from holding on to it forever.
+    pub async fn get_packet_before_timeout(&self, timeout: Duration) -> Option<RawPacket> {
+        let deadline = self.last_progress.load() + timeout;
+        if let Ok(packet) = tokio::time::timeout_at(deadline.into(), self.get_packet()).await {
+            return packet;
+        }
+
+        log::debug!(
+            "Client {} sent nothing useful for {:?} in {:?} state",
+            self.id,
+            timeout,
+            self.connection_state.load()
+        );
+        match self.connection_state.load() {
+            ConnectionState::Login | ConnectionState::Config => {
+                self.kick(TextComponent::translate("disconnect.timeout", []))
+                    .await;
+            }
+            _ => self.close(),
+        }
+        None
+    }
+
     /// Handles an incoming packet, routing it to the appropriate handler based on the current connection state.
     ///
     /// This function takes a `RawPacket` and routes it to the corresponding handler based on the current connection state.
@@ -459,6 +580,9 @@ impl Client {
     ///
     /// For the `Play` state, an error is logged as it indicates an invalid state for packet processing.
     ///
+    /// Packets which aren't valid in the current state disconnect the client, see
+    /// [`Client::validate_packet`].
+    ///
     /// # Arguments
     ///
     /// * `server`: A reference to the `Server` instance.
@@ -476,6 +600,9 @@ impl Client {
         server: &Server,
         packet: &RawPacket,
     ) -> Result<(), ReadingError> {
+        if !self.validate_packet(packet).await {
+            return Ok(());
+        }
         match self.connection_state.load() {
             pumpkin_protocol::ConnectionState::HandShake => {
                 self.handle_handshake_packet(packet).await
@@ -498,11 +625,40 @@ impl Client {
         }
     }
 
+    /// Disconnects the client if it sent a packet which isn't valid in its connection state, or
+    /// skipped a step of logging in, e.g. acknowledging a login which didn't succeed yet.
+    ///
+    /// Returns whether the packet may be handled.
+    pub async fn validate_packet(&self, packet: &RawPacket) -> bool {
+        let state = self.connection_state.load();
+        let reason = if !is_valid_in_state(state, packet.id) {
+            format!("Unexpected packet id {} in the {state:?} state", packet.id)
+        } else if !matches!(state, ConnectionState::Login | ConnectionState::Transfer) {
+            return true;
+        } else if packet.id == SLoginStart::PACKET_ID && self.gameprofile.lock().await.is_some() {
+            "Started logging in twice".to_string()
+        } else if packet.id == SLoginAcknowledged::PACKET_ID
+            && !self.sent_login_success.load(Ordering::Relaxed)
+        {
+            "Acknowledged the login before it succeeded".to_string()
+        } else {
+            return true;
+        };
+
+        log::warn!("Disconnecting client {}: {reason}", self.id);
+        match state {
+            // There is no disconnect packet before logging in
+            ConnectionState::HandShake | ConnectionState::Status => self.close(),
+            _ => self.kick(TextComponent::text(reason)).await,
+        }
+        false
+    }
+
     async fn handle_handshake_packet(&self, packet: &RawPacket) -> Result<(), ReadingError> {
         log::debug!("Handling handshake group");
         let payload = &packet.payload[..];
         match packet.id {
-            0 => {
+            SHandShake::PACKET_ID => {
                 self.handle_handshake(SHandShake::read(payload)?).await;
             }
             _ => {
@@ -577,7 +733,7 @@ impl Client {
         Ok(())
     }
 
-    async fn handle_config_packet(
+    pub(crate) async fn handle_config_packet(
         &self,
         server: &Server,
         packet: &RawPacket,
@@ -597,15 +753,23 @@ impl Client {
                 self.handle_config_acknowledged(server).await;
             }
             SKnownPacks::PACKET_ID => {
-                self.handle_known_packs(server, SKnownPacks::read(payload)?)
-                    .await;
+                if let Some(packet) = self
+                    .read_config_packet::<SKnownPacks>(ConfigStage::KnownPacks, payload)
+                    .await
+                {
+                    self.handle_known_packs(server, packet).await;
+                }
             }
             SConfigCookieResponse::PACKET_ID => {
                 self.handle_config_cookie_response(&SConfigCookieResponse::read(payload)?);
             }
             SConfigResourcePack::PACKET_ID => {
-                self.handle_resource_pack_response(SConfigResourcePack::read(payload)?)
-                    .await;
+                if let Some(packet) = self
+                    .read_config_packet::<SConfigResourcePack>(ConfigStage::ResourcePack, payload)
+                    .await
+                {
+                    self.handle_resource_pack_response(packet).await;
+                }
             }
             _ => {
                 log::error!(
@@ -634,7 +798,12 @@ impl Client {
                 .await;
             }
             ConnectionState::Config => {
-                self.send_packet_now(&CConfigDisconnect::new(&reason.get_text()))
+                // This reason is a plain string, so we have to translate it ourselves
+                let locale = self.config.lock().await.as_ref().map_or_else(
+                    || DEFAULT_LOCALE.to_string(),
+                    |config| config.locale.clone(),
+                );
+                self.send_packet_now(&CConfigDisconnect::new(&reason.to_locale_string(&locale)))
                     .await;
             }
             // This way players get kicked when players using client functions (e.g. poll, send_packet)
@@ -735,3 +904,121 @@ pub enum EncryptionError {
     #[error("shared secret has the wrong length")]
     SharedWrongLength,
 }
+
+/// Whether clients may send the packet with this id in the given connection state.
+/// Play packets are handled by the player, so they are all accepted here.
+fn is_valid_in_state(state: ConnectionState, id: i32) -> bool {
+    let valid: &[i32] = match state {
+        ConnectionState::HandShake => &[SHandShake::PACKET_ID],
+        ConnectionState::Status => &[SStatusRequest::PACKET_ID, SStatusPingRequest::PACKET_ID],
+        ConnectionState::Login | ConnectionState::Transfer => &[
+            SLoginStart::PACKET_ID,
+            SEncryptionResponse::PACKET_ID,
+            SLoginPluginResponse::PACKET_ID,
+            SLoginAcknowledged::PACKET_ID,
+            SLoginCookieResponse::PACKET_ID,
+        ],
+        ConnectionState::Config => &[
+            SClientInformationConfig::PACKET_ID,
+            SPluginMessage::PACKET_ID,
+            SAcknowledgeFinishConfig::PACKET_ID,
+            SKnownPacks::PACKET_ID,
+            SConfigCookieResponse::PACKET_ID,
+            SConfigResourcePack::PACKET_ID,
+        ],
+        ConnectionState::Play => return true,
+    };
+    valid.contains(&id)
+}
+
+/// The steps of the configuration state a client has to pass before it can play.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigStage {
+    ResourcePack,
+    KnownPacks,
+    RegistryData,
+    Finish,
+}
+
+impl std::fmt::Display for ConfigStage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::ResourcePack => "resource pack",
+            Self::KnownPacks => "known packs",
+            Self::RegistryData => "registry data",
+            Self::Finish => "finish",
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{sync::atomic::Ordering, time::Duration};
+
+    use bytes::Bytes;
+    use pumpkin_protocol::{
+        ConnectionState, RawPacket,
+        client::login::CLoginDisconnect,
+        ser::{NetworkReadExt, packet::Packet},
+        server::{config::SKnownPacks, login::SLoginAcknowledged},
+    };
+    use tokio::io::AsyncReadExt;
+
+    use crate::test_utils::loopback_client;
+
+    #[tokio::test]
+    async fn silent_connection_is_reaped() {
+        let (client, mut remote) = loopback_client().await;
+
+        // The client opens the socket but never sends anything
+        let packet = client
+            .get_packet_before_timeout(Duration::from_millis(50))
+            .await;
+        assert!(packet.is_none());
+        assert!(client.closed.load(Ordering::Relaxed));
+
+        // Dropping the client closes the socket, which the remote sees as EOF
+        drop(client);
+        let mut buf = [0; 1];
+        assert_eq!(remote.read(&mut buf).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn config_packet_during_login_kicks() {
+        let (client, mut remote) = loopback_client().await;
+        client.connection_state.store(ConnectionState::Login);
+
+        let known_packs = RawPacket {
+            id: SKnownPacks::PACKET_ID,
+            payload: Bytes::new(),
+        };
+        assert!(!client.validate_packet(&known_packs).await);
+        assert!(client.closed.load(Ordering::Relaxed));
+
+        // The client is told why instead of the packet being ignored
+        let mut buf = [0; 2];
+        remote.read_exact(&mut buf).await.unwrap();
+        let mut read = &buf[..];
+        let _length = read.get_var_int().unwrap();
+        assert_eq!(read.get_var_int().unwrap().0, CLoginDisconnect::PACKET_ID);
+    }
+
+    #[tokio::test]
+    async fn login_is_acknowledged_only_after_success() {
+        let acknowledged = RawPacket {
+            id: SLoginAcknowledged::PACKET_ID,
+            payload: Bytes::new(),
+        };
+
+        let (client, _remote) = loopback_client().await;
+        client.connection_state.store(ConnectionState::Login);
+        client.sent_login_success.store(true, Ordering::Relaxed);
+        assert!(client.validate_packet(&acknowledged).await);
+        assert!(!client.closed.load(Ordering::Relaxed));
+
+        let (client, _remote) = loopback_client().await;
+        client.connection_state.store(ConnectionState::Login);
+        assert!(!client.validate_packet(&acknowledged).await);
+        assert!(client.closed.load(Ordering::Relaxed));
+    }
+}
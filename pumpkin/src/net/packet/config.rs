@@ -2,22 +2,59 @@ use std::num::NonZeroU8;
 
 use crate::{
     entity::player::{ChatMode, Hand},
-    net::{Client, PlayerConfig},
+    net::{Client, ConfigStage, PlayerConfig},
     server::Server,
 };
 use core::str;
 use pumpkin_config::advanced_config;
 use pumpkin_protocol::{
-    ConnectionState,
-    client::config::{CFinishConfig, CRegistryData},
+    ConnectionState, ServerPacket,
+    client::{
+        config::{CFinishConfig, CRegistryData},
+        play::CStartConfiguration,
+    },
     server::config::{
         ResourcePackResponseResult, SClientInformationConfig, SConfigCookieResponse,
         SConfigResourcePack, SKnownPacks, SPluginMessage,
     },
 };
 use pumpkin_util::text::TextComponent;
+use std::{fmt::Display, sync::atomic::Ordering};
+
+/// The reason shown to a client which failed a configuration stage.
+fn config_failure_reason(stage: ConfigStage, error: &impl Display) -> TextComponent {
+    TextComponent::text(format!(
+        "Configuration failed at the {stage} stage: {error}"
+    ))
+}
 
 impl Client {
+    /// Kicks the client because it failed a configuration stage, instead of leaving it in a state
+    /// it can't get out of.
+    pub async fn fail_config(&self, stage: ConfigStage, error: &impl Display) {
+        log::warn!(
+            "Client {} failed the {stage} stage of configuration: {error}",
+            self.id
+        );
+        self.kick(config_failure_reason(stage, error)).await;
+    }
+
+    /// Reads a configuration packet, kicking the client with a descriptive reason if it is
+    /// malformed.
+    pub async fn read_config_packet<P: ServerPacket>(
+        &self,
+        stage: ConfigStage,
+        payload: &[u8],
+    ) -> Option<P> {
+        match P::read(payload) {
+            Ok(packet) => Some(packet),
+            Err(error) => {
+                self.fail_config(stage, &error).await;
+                None
+            }
+        }
+    }
+
     pub async fn handle_client_information_config(
         &self,
         client_information: SClientInformationConfig,
@@ -144,31 +181,160 @@ impl Client {
         );
     }
 
-    pub async fn handle_known_packs(&self, server: &Server, _config_acknowledged: SKnownPacks) {
-        log::debug!("Handling known packs");
+    pub async fn handle_known_packs(&self, server: &Server, known_packs: SKnownPacks) {
+        log::debug!(
+            "Handling known packs, client knows {} packs",
+            known_packs.known_packs.len()
+        );
         for registry in &server.cached_registry {
             self.send_packet_now(&CRegistryData::new(
                 &registry.registry_id,
                 &registry.registry_entries,
             ))
             .await;
+            // Sending closes the connection when it fails, finishing would only confuse the client
+            if self.closed.load(Ordering::Relaxed) {
+                log::warn!(
+                    "Client {} failed the {} stage of configuration: could not send registry {}",
+                    self.id,
+                    ConfigStage::RegistryData,
+                    registry.registry_id
+                );
+                return;
+            }
         }
 
         // We are done with configuring
         log::debug!("Finished config");
         self.send_packet_now(&CFinishConfig).await;
+        self.sent_finish_config.store(true, Ordering::Relaxed);
     }
 
     pub async fn handle_config_acknowledged(&self, server: &Server) {
         log::debug!("Handling config acknowledgement");
-        self.connection_state.store(ConnectionState::Play);
+        let Some(reconfigured) = self.finish_config().await else {
+            return;
+        };
+        if reconfigured {
+            // The player already joined, it just has to be sent back into its world
+            return;
+        }
 
         if let Some(reason) = self.can_not_join(server).await {
             self.kick(reason).await;
             return;
         }
 
-        self.make_player
-            .store(true, std::sync::atomic::Ordering::Relaxed);
+        self.make_player.store(true, Ordering::Relaxed);
+    }
+
+    /// Enters the play state once the client acknowledged the end of configuration.
+    ///
+    /// Returns whether a playing client was reconfigured, or `None` if it acknowledged too early.
+    async fn finish_config(&self) -> Option<bool> {
+        if !self.sent_finish_config.load(Ordering::Relaxed) {
+            self.fail_config(
+                ConfigStage::Finish,
+                &"acknowledged before all registry data was sent",
+            )
+            .await;
+            return None;
+        }
+        self.connection_state.store(ConnectionState::Play);
+        Some(self.reconfiguring.swap(false, Ordering::Relaxed))
+    }
+
+    /// Sends a playing client back to the configuration state, e.g. to resend the registries.
+    ///
+    /// The connection only changes its state once the client acknowledged it, see
+    /// [`Client::enter_reconfiguration`]. Until configuration is finished again, play packets
+    /// are dropped since the client couldn't read them.
+    pub async fn start_reconfiguration(&self) {
+        self.sent_finish_config.store(false, Ordering::Relaxed);
+        // Queued, so that everything queued before still arrives in the play state
+        self.enqueue_packet(&CStartConfiguration).await;
+        self.reconfiguring.store(true, Ordering::Relaxed);
+    }
+
+    /// Handles the client acknowledging `CStartConfiguration`, returns whether it was expected.
+    pub fn enter_reconfiguration(&self) -> bool {
+        if !self.reconfiguring.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.connection_state.store(ConnectionState::Config);
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::Ordering;
+
+    use pumpkin_protocol::{
+        ConnectionState,
+        client::{config::CConfigDisconnect, play::CStartConfiguration},
+        codec::var_int::VarInt,
+        ser::{NetworkReadExt, NetworkWriteExt, packet::Packet},
+        server::config::SKnownPacks,
+    };
+    use tokio::io::AsyncReadExt;
+
+    use crate::{net::ConfigStage, test_utils::loopback_client};
+
+    #[tokio::test]
+    async fn malformed_known_packs_kick_cleanly() {
+        let (client, mut remote) = loopback_client().await;
+        client.connection_state.store(ConnectionState::Config);
+
+        // Claims two packs but only contains one
+        let mut payload = Vec::new();
+        payload.write_var_int(&VarInt(2)).unwrap();
+        for string in ["minecraft", "core", "1.21"] {
+            payload.write_string(string).unwrap();
+        }
+
+        let packet = client
+            .read_config_packet::<SKnownPacks>(ConfigStage::KnownPacks, &payload)
+            .await;
+        assert!(packet.is_none());
+        assert!(client.closed.load(Ordering::Relaxed));
+        assert!(!client.sent_finish_config.load(Ordering::Relaxed));
+
+        // The client got told why instead of the connection just dropping
+        let mut buf = [0; 4];
+        remote.read_exact(&mut buf).await.unwrap();
+        let mut read = &buf[..];
+        let _length = read.get_var_int().unwrap();
+        assert_eq!(read.get_var_int().unwrap().0, CConfigDisconnect::PACKET_ID);
+    }
+
+    #[tokio::test]
+    async fn reconfiguration_returns_to_play() {
+        let (mut client, _remote) = loopback_client().await;
+        let mut outgoing = client.take_outgoing_packets();
+        client.connection_state.store(ConnectionState::Play);
+        client.sent_finish_config.store(true, Ordering::Relaxed);
+
+        // An acknowledgement nobody asked for is rejected
+        assert!(!client.enter_reconfiguration());
+
+        client.start_reconfiguration().await;
+        let (start, _) = outgoing.recv().await.unwrap();
+        assert_eq!(start[0] as i32, CStartConfiguration::PACKET_ID);
+        // The client is still playing until it acknowledges
+        assert_eq!(client.connection_state.load(), ConnectionState::Play);
+        assert!(!client.sent_finish_config.load(Ordering::Relaxed));
+
+        assert!(client.enter_reconfiguration());
+        assert_eq!(client.connection_state.load(), ConnectionState::Config);
+        // Play packets are held back while configuring
+        client.enqueue_packet(&CStartConfiguration).await;
+        assert!(outgoing.try_recv().is_err());
+
+        client.sent_finish_config.store(true, Ordering::Relaxed);
+        assert_eq!(client.finish_config().await, Some(true));
+        assert_eq!(client.connection_state.load(), ConnectionState::Play);
+        client.enqueue_packet(&CStartConfiguration).await;
+        assert!(outgoing.try_recv().is_ok());
     }
 }
@@ -1,4 +1,4 @@
-use std::sync::LazyLock;
+use std::sync::{LazyLock, atomic::Ordering};
 
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
 use pumpkin_protocol::{
@@ -204,24 +204,7 @@ impl Client {
             }
         }
 
-        // Don't allow duplicate UUIDs
-        if let Some(online_player) = &server.get_player_by_uuid(profile.id).await {
-            log::debug!(
-                "Player (IP '{}', username '{}') tried to log in with the same UUID ('{}') as an online player (IP '{}', username '{}')",
-                &self.address.lock().await,
-                &profile.name,
-                &profile.id,
-                &online_player.client.address.lock().await,
-                &online_player.gameprofile.name
-            );
-            self.kick(TextComponent::translate(
-                "multiplayer.disconnect.duplicate_login",
-                [],
-            ))
-            .await;
-            return;
-        }
-
+        // A duplicate UUID replaces the online player when it is added to the server
         // Don't allow a duplicate username
         if let Some(online_player) = &server.get_player_by_name(&profile.name).await {
             log::debug!(
@@ -256,6 +239,7 @@ impl Client {
     async fn finish_login(&self, profile: &GameProfile) {
         let packet = CLoginSuccess::new(&profile.id, &profile.name, &profile.properties);
         self.send_packet_now(&packet).await;
+        self.sent_login_success.store(true, Ordering::Relaxed);
     }
 
     async fn authenticate(
@@ -338,6 +322,12 @@ impl Client {
     pub async fn handle_login_acknowledged(&self, server: &Server) {
         log::debug!("Handling login acknowledgement");
         self.connection_state.store(ConnectionState::Config);
+        self.send_configuration(server).await;
+        log::debug!("login acknowledged");
+    }
+
+    /// Starts configuring a client which just entered the configuration state.
+    pub async fn send_configuration(&self, server: &Server) {
         self.send_packet_now(&server.get_branding()).await;
 
         if advanced_config().server_links.enabled {
@@ -373,7 +363,6 @@ impl Client {
             // This will be invoked by our resource pack handler in the case of the above branch.
             self.send_known_packs().await;
         }
-        log::debug!("login acknowledged");
     }
 
     /// Send the known data packs to the client.
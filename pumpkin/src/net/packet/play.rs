@@ -3,6 +3,7 @@ use rsa::pkcs1v15::{Signature as RsaPkcs1v15Signature, VerifyingKey};
 use rsa::signature::Verifier;
 use sha1::Sha1;
 
+use std::collections::HashMap;
 use std::num::NonZeroU8;
 use std::sync::Arc;
 use std::sync::atomic::Ordering;
@@ -10,9 +11,12 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::block::registry::BlockActionResult;
 use crate::block::{self, BlockIsReplacing};
-use crate::entity::mob;
-use crate::entity::player::ChatSession;
+use crate::entity::player::{ChatSession, LastSeen, SignedMessage};
+use crate::entity::{elytra, mob};
 use crate::net::PlayerConfig;
+use crate::net::chat_signing::{
+    MAX_PENDING_MESSAGES, signed_message_body, verify_message_signature,
+};
 use crate::plugin::player::player_chat::PlayerChatEvent;
 use crate::plugin::player::player_command_send::PlayerCommandSendEvent;
 use crate::plugin::player::player_move::PlayerMoveEvent;
@@ -20,13 +24,13 @@ use crate::server::seasonal_events;
 use crate::world::{BlockFlags, World};
 use crate::{
     command::CommandSender,
-    entity::player::{ChatMode, Hand, Player},
+    entity::player::{ChatMode, Hand, Player, ping_response},
     error::PumpkinError,
     server::Server,
     world::chunker,
 };
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
-use pumpkin_data::entity::{EntityType, entity_from_egg};
+use pumpkin_data::entity::{EntityPose, EntityType, entity_from_egg};
 use pumpkin_data::item::Item;
 use pumpkin_data::sound::Sound;
 use pumpkin_data::sound::SoundCategory;
@@ -40,28 +44,29 @@ use pumpkin_inventory::player::{
 };
 use pumpkin_macros::send_cancellable;
 use pumpkin_protocol::client::play::{
-    CBlockUpdate, CEntityPositionSync, COpenSignEditor, CPlayerInfoUpdate, CPlayerPosition,
-    CSetContainerSlot, CSetHeldItem, CSystemChatMessage, EquipmentSlot, InitChat, PlayerAction,
+    CBlockUpdate, CEntityPositionSync, COpenSignEditor, CPlayerInfoUpdate, CSetContainerSlot,
+    CSetHeldItem, CSetSimulationDistance, CSystemChatMessage, InitChat, PlayerAction,
 };
 use pumpkin_protocol::codec::item_stack_seralizer::ItemStackSerializer;
 use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::server::play::{
-    FLAG_ON_GROUND, SChunkBatch, SCookieResponse as SPCookieResponse, SPlayerSession, SUpdateSign,
+    FLAG_IN_WALL, FLAG_ON_GROUND, SChunkBatch, SCookieResponse as SPCookieResponse, SPlayerSession,
+    SUpdateSign,
 };
 use pumpkin_protocol::{
     client::play::{
-        Animation, CCommandSuggestions, CEntityAnimation, CHeadRot, CPingResponse,
-        CUpdateEntityPos, CUpdateEntityPosRot, CUpdateEntityRot,
+        Animation, CCommandSuggestions, CHeadRot, CUpdateEntityPos, CUpdateEntityPosRot,
+        CUpdateEntityRot,
     },
     server::play::{
-        Action, ActionType, SChatCommand, SChatMessage, SClientCommand, SClientInformationPlay,
-        SCloseContainer, SCommandSuggestion, SConfirmTeleport, SInteract, SKeepAlive,
-        SPickItemFromBlock, SPlayPingRequest, SPlayerAbilities, SPlayerAction, SPlayerCommand,
-        SPlayerPosition, SPlayerPositionRotation, SPlayerRotation, SSetCreativeSlot, SSetHeldItem,
-        SSetPlayerGround, SSwingArm, SUseItem, SUseItemOn, Status,
+        Action, ActionType, SChatAck, SChatCommand, SChatCommandSigned, SChatMessage,
+        SClientCommand, SClientInformationPlay, SCloseContainer, SCommandSuggestion,
+        SConfirmTeleport, SInteract, SKeepAlive, SPickItemFromBlock, SPlayPingRequest,
+        SPlayerAbilities, SPlayerAction, SPlayerCommand, SPlayerPosition, SPlayerPositionRotation,
+        SPlayerRotation, SPong, SSetCreativeSlot, SSetHeldItem, SSetPlayerGround, SSwingArm,
+        SUseItem, SUseItemOn, Status,
     },
 };
-use pumpkin_util::math::polynomial_rolling_hash;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::text::color::NamedColor;
 use pumpkin_util::{
@@ -74,6 +79,7 @@ use pumpkin_world::block::entities::sign::SignBlockEntity;
 use pumpkin_world::item::ItemStack;
 
 use thiserror::Error;
+use uuid::Uuid;
 
 /// In secure chat mode, Player will be kicked if they send a chat message with a timestamp that is older than this (in ms)
 /// Vanilla: 2 minutes
@@ -187,23 +193,22 @@ impl PumpkinError for ChatError {
 /// NEVER TRUST THE CLIENT. HANDLE EVERY ERROR; UNWRAP/EXPECT ARE FORBIDDEN.
 impl Player {
     pub async fn handle_confirm_teleport(&self, confirm_teleport: SConfirmTeleport) {
-        let mut awaiting_teleport = self.awaiting_teleport.lock().await;
-        if let Some((id, position)) = awaiting_teleport.as_ref() {
-            if id == &confirm_teleport.teleport_id {
-                // We should set the position now to what we requested in the teleport packet.
-                // This may fix issues when the client sends the position while being teleported.
-                self.living_entity.set_pos(*position);
-
-                *awaiting_teleport = None;
-            } else {
-                self.kick(TextComponent::text("Wrong teleport id")).await;
-            }
-        } else {
-            self.kick(TextComponent::text(
-                "Send Teleport confirm, but we did not teleport",
-            ))
-            .await;
+        let teleport_id = confirm_teleport.teleport_id.0;
+        let confirmed = self.awaiting_teleport.lock().await.confirm(teleport_id);
+        if let Some(position) = confirmed {
+            // We should set the position now to what we requested in the teleport packet.
+            // This may fix issues when the client sends the position while being teleported.
+            self.living_entity.set_pos(position);
+        } else if teleport_id > self.teleport_id_count.load(Ordering::Relaxed) {
+            self.kick(TextComponent::text("Wrong teleport id")).await;
         }
+        // Otherwise it confirms a teleport we already sent again, the newer one is still pending
+    }
+
+    /// Whether the client's movement should be applied, movement sent before it confirmed a
+    /// teleport is from its old position.
+    async fn accepts_movement(&self) -> bool {
+        self.has_client_loaded() && self.awaiting_teleport.lock().await.accepts_movement()
     }
 
     fn clamp_horizontal(pos: f64) -> f64 {
@@ -237,7 +242,8 @@ impl Player {
         }
         // Sync position with all other players.
         world
-            .broadcast_packet_except(
+            .broadcast_to_trackers_except(
+                entity_id,
                 &[self.gameprofile.id],
                 &CEntityPositionSync::new(
                     entity_id.into(),
@@ -253,7 +259,7 @@ impl Player {
     }
 
     pub async fn handle_position(self: &Arc<Self>, packet: SPlayerPosition) {
-        if !self.has_client_loaded() {
+        if !self.accepts_movement().await {
             return;
         }
         // y = feet Y
@@ -285,6 +291,9 @@ impl Player {
                 let entity = &self.living_entity.entity;
                 let last_pos = entity.pos.load();
                 self.living_entity.set_pos(pos);
+                if pos != last_pos {
+                    self.update_last_action_time();
+                }
 
                 let height_difference = pos.y - last_pos.y;
                 if entity.on_ground.load(Ordering::Relaxed) && packet.collision & FLAG_ON_GROUND == 0 && height_difference > 0.0 {
@@ -292,13 +301,15 @@ impl Player {
                 }
 
                 entity.on_ground.store(packet.collision & FLAG_ON_GROUND != 0, Ordering::Relaxed);
+                self.fall_flying_moved(pos.sub(&last_pos), packet.collision & FLAG_IN_WALL != 0).await;
                 let world = &self.world().await;
 
                 // TODO: Warn when player moves to quickly
                 if !self.sync_position(world, pos, last_pos, entity.yaw.load(), entity.pitch.load(), packet.collision & FLAG_ON_GROUND != 0).await {
                     // Send the new position to all other players.
                     world
-                        .broadcast_packet_except(
+                        .broadcast_to_trackers_except(
+                            self.entity_id(),
                             &[self.gameprofile.id],
                             &CUpdateEntityPos::new(
                                 self.entity_id().into(),
@@ -332,20 +343,13 @@ impl Player {
             }
 
             'cancelled: {
-                self.client.enqueue_packet(&CPlayerPosition::new(
-                    self.teleport_id_count.load(std::sync::atomic::Ordering::Relaxed).into(),
-                    self.living_entity.entity.pos.load(),
-                    Vector3::new(0.0, 0.0, 0.0),
-                    self.living_entity.entity.yaw.load(),
-                    self.living_entity.entity.pitch.load(),
-                    &[],
-                )).await;
+                self.force_tp(self.living_entity.entity.pos.load()).await;
             }
         }}
     }
 
     pub async fn handle_position_rotation(self: &Arc<Self>, packet: SPlayerPositionRotation) {
-        if !self.has_client_loaded() {
+        if !self.accepts_movement().await {
             return;
         }
         // y = feet Y
@@ -382,6 +386,9 @@ impl Player {
                 let entity = &self.living_entity.entity;
                 let last_pos = entity.pos.load();
                 self.living_entity.set_pos(pos);
+                if pos != last_pos {
+                    self.update_last_action_time();
+                }
 
                 let height_difference = pos.y - last_pos.y;
                 if entity.on_ground.load(std::sync::atomic::Ordering::Relaxed)
@@ -393,6 +400,8 @@ impl Player {
                 entity
                     .on_ground
                     .store((packet.collision & FLAG_ON_GROUND) != 0, std::sync::atomic::Ordering::Relaxed);
+                self.fall_flying_moved(pos.sub(&last_pos), (packet.collision & FLAG_IN_WALL) != 0)
+                    .await;
 
                 entity.set_rotation(wrap_degrees(packet.yaw) % 360.0, wrap_degrees(packet.pitch));
 
@@ -410,7 +419,8 @@ impl Player {
                 {
                     // Send the new position to all other players.
                     world
-                        .broadcast_packet_except(
+                        .broadcast_to_trackers_except(
+                            entity_id,
                             &[self.gameprofile.id],
                             &CUpdateEntityPosRot::new(
                                 entity_id.into(),
@@ -428,7 +438,8 @@ impl Player {
                 }
 
                 world
-                    .broadcast_packet_except(
+                    .broadcast_to_trackers_except(
+                        entity_id,
                         &[self.gameprofile.id],
                         &CHeadRot::new(entity_id.into(), yaw as u8),
                     )
@@ -452,31 +463,19 @@ impl Player {
             }
 
             'cancelled: {
-                self.force_tp(position).await;
+                self.force_tp(self.living_entity.entity.pos.load()).await;
             }
         }}
     }
 
     pub async fn force_tp(&self, position: Vector3<f64>) {
-        let teleport_id = self
-            .teleport_id_count
-            .fetch_add(1, std::sync::atomic::Ordering::Relaxed)
-            + 1;
-        *self.awaiting_teleport.lock().await = Some((teleport_id.into(), position));
-        self.client
-            .enqueue_packet(&CPlayerPosition::new(
-                teleport_id.into(),
-                self.living_entity.entity.pos.load(),
-                Vector3::new(0.0, 0.0, 0.0),
-                self.living_entity.entity.yaw.load(),
-                self.living_entity.entity.pitch.load(),
-                &[],
-            ))
+        let entity = &self.living_entity.entity;
+        self.send_teleport(position, entity.yaw.load(), entity.pitch.load())
             .await;
     }
 
     pub async fn handle_rotation(&self, rotation: SPlayerRotation) {
-        if !self.has_client_loaded() {
+        if !self.accepts_movement().await {
             return;
         }
         if !rotation.yaw.is_finite() || !rotation.pitch.is_finite() {
@@ -505,11 +504,11 @@ impl Player {
         let packet =
             CUpdateEntityRot::new(entity_id.into(), yaw as u8, pitch as u8, rotation.ground);
         world
-            .broadcast_packet_except(&[self.gameprofile.id], &packet)
+            .broadcast_to_trackers_except(entity_id, &[self.gameprofile.id], &packet)
             .await;
         let packet = CHeadRot::new(entity_id.into(), yaw as u8);
         world
-            .broadcast_packet_except(&[self.gameprofile.id], &packet)
+            .broadcast_to_trackers_except(entity_id, &[self.gameprofile.id], &packet)
             .await;
     }
 
@@ -517,13 +516,41 @@ impl Player {
         self: &Arc<Self>,
         server: &Arc<Server>,
         command: &SChatCommand,
+    ) {
+        self.run_chat_command(server, command.command.clone(), HashMap::new())
+            .await;
+    }
+
+    pub async fn handle_signed_chat_command(
+        self: &Arc<Self>,
+        server: &Arc<Server>,
+        command: SChatCommandSigned,
+    ) {
+        let signed_arguments = match self.validate_chat_command(server, &command).await {
+            Ok(signed_arguments) => signed_arguments,
+            Err(err) => {
+                self.handle_chat_error(&err).await;
+                return;
+            }
+        };
+        self.run_chat_command(server, command.command, signed_arguments)
+            .await;
+    }
+
+    /// Runs a command the player typed. `signed_arguments` are the verified message arguments,
+    /// so commands like `/msg` can forward them with their signatures.
+    async fn run_chat_command(
+        self: &Arc<Self>,
+        server: &Arc<Server>,
+        command: String,
+        signed_arguments: HashMap<String, SignedMessage>,
     ) {
         let player_clone = self.clone();
         let server_clone = server.clone();
         send_cancellable! {{
             PlayerCommandSendEvent {
                 player: self.clone(),
-                command: command.command.clone(),
+                command,
                 cancelled: false
             };
 
@@ -533,14 +560,16 @@ impl Player {
                 // Some commands can take a long time to execute. If they do, they block packet processing for the player.
                 // That's why we will spawn a task instead.
                 server.spawn_task(async move {
+                    *player_clone.signed_arguments.lock().await = signed_arguments;
                     let dispatcher = server_clone.command_dispatcher.read().await;
                     dispatcher
                         .handle_command(
-                            &mut CommandSender::Player(player_clone),
+                            &mut CommandSender::Player(player_clone.clone()),
                             &server_clone,
                             &command_clone,
                         )
                         .await;
+                    player_clone.signed_arguments.lock().await.clear();
                 });
 
                 if advanced_config().commands.log_console {
@@ -654,10 +683,8 @@ impl Player {
 
         // Update held item
         inventory.set_selected(dest_slot);
-        let empty = &ItemStack::new(0, Item::AIR);
-        let stack = inventory.held_item().unwrap_or(empty);
-        let equipment = &[(EquipmentSlot::MainHand, stack.clone())];
-        self.living_entity.send_equipment_changes(equipment).await;
+        drop(inventory);
+        self.sync_equipment().await;
         self.client
             .enqueue_packet(&CSetHeldItem::new(dest_slot as i8))
             .await;
@@ -678,42 +705,39 @@ impl Player {
         if let Ok(action) = Action::try_from(command.action.0) {
             let entity = &self.living_entity.entity;
             match action {
-                pumpkin_protocol::server::play::Action::StartSneaking => {
-                    if !entity.sneaking.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sneaking(true).await;
-                    }
-                }
-                pumpkin_protocol::server::play::Action::StopSneaking => {
-                    if entity.sneaking.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sneaking(false).await;
+                Action::StartSneaking => entity.set_sneaking(true).await,
+                Action::StopSneaking => entity.set_sneaking(false).await,
+                Action::StartSprinting => entity.set_sprinting(true).await,
+                Action::StopSprinting => entity.set_sprinting(false).await,
+                Action::LeaveBed => {
+                    if entity.pose.load() == EntityPose::Sleeping {
+                        entity.set_pose(EntityPose::Standing).await;
+                        entity.play_animation(Animation::LeaveBed).await;
                     }
                 }
-                pumpkin_protocol::server::play::Action::StartSprinting => {
-                    if !entity.sprinting.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sprinting(true).await;
-                    }
-                }
-                pumpkin_protocol::server::play::Action::StopSprinting => {
-                    if entity.sprinting.load(std::sync::atomic::Ordering::Relaxed) {
-                        entity.set_sprinting(false).await;
-                    }
+                Action::StartHorseJump | Action::StopHorseJump | Action::OpenVehicleInventory => {
+                    // TODO: Handle these once there are horses and other vehicles with an inventory,
+                    // players can only ride entities without either for now
+                    log::debug!(
+                        "Player {} sent a vehicle command without a vehicle",
+                        self.gameprofile.name
+                    );
                 }
-                pumpkin_protocol::server::play::Action::LeaveBed
-                | pumpkin_protocol::server::play::Action::StartHorseJump
-                | pumpkin_protocol::server::play::Action::StopHorseJump
-                | pumpkin_protocol::server::play::Action::OpenVehicleInventory => {
-                    log::debug!("todo");
+                Action::StartFlyingElytra => {
+                    let has_elytra = self
+                        .inventory()
+                        .lock()
+                        .await
+                        .armor_slots()
+                        .get(1)
+                        .copied()
+                        .flatten()
+                        .is_some_and(elytra::is_usable);
+                    let can_fly = has_elytra
+                        && entity.check_fall_flying()
+                        && !self.abilities.lock().await.flying;
+                    entity.set_fall_flying(can_fly).await;
                 }
-                pumpkin_protocol::server::play::Action::StartFlyingElytra => {
-                    let fall_flying = entity.check_fall_flying();
-                    if entity
-                        .fall_flying
-                        .load(std::sync::atomic::Ordering::Relaxed)
-                        != fall_flying
-                    {
-                        entity.set_fall_flying(fall_flying).await;
-                    }
-                } // TODO
             }
         } else {
             self.kick(TextComponent::text("Invalid player command"))
@@ -722,52 +746,23 @@ impl Player {
     }
 
     pub async fn handle_swing_arm(&self, swing_arm: SSwingArm) {
-        let animation = match swing_arm.hand.0 {
-            0 => Animation::SwingMainArm,
-            1 => Animation::SwingOffhand,
-            _ => {
-                self.kick(TextComponent::text("Invalid hand")).await;
-                return;
-            }
-        };
-        // Invert hand if player is left handed
-        let animation = match self.config.read().await.main_hand {
-            Hand::Left => match animation {
-                Animation::SwingMainArm => Animation::SwingOffhand,
-                Animation::SwingOffhand => Animation::SwingMainArm,
-                _ => unreachable!(),
-            },
-            Hand::Right => animation,
+        let Some(animation) = Animation::from_swing_hand(swing_arm.hand.0) else {
+            self.kick(TextComponent::text("Invalid hand")).await;
+            return;
         };
-
-        let id = self.entity_id();
-        let world = self.world().await;
-        world
-            .broadcast_packet_except(
-                &[self.gameprofile.id],
-                &CEntityAnimation::new(id.into(), animation as u8),
-            )
-            .await;
+        self.living_entity.entity.play_animation(animation).await;
     }
 
     pub async fn handle_chat_message(self: &Arc<Self>, chat_message: SChatMessage) {
         let gameprofile = &self.gameprofile;
 
-        if let Err(err) = self.validate_chat_message(&chat_message).await {
-            log::log!(
-                err.severity(),
-                "{} (uuid {}) {}",
-                gameprofile.name,
-                gameprofile.id,
-                err
-            );
-            if err.is_kick() {
-                if let Some(reason) = err.client_kick_reason() {
-                    self.kick(TextComponent::text(reason)).await;
-                }
+        let signed = match self.validate_chat_message(&chat_message).await {
+            Ok(signed) => signed,
+            Err(err) => {
+                self.handle_chat_error(&err).await;
+                return;
             }
-            return;
-        }
+        };
 
         send_cancellable! {{
             PlayerChatEvent::new(self.clone(), chat_message.message.clone(), vec![]);
@@ -790,8 +785,10 @@ impl Player {
 
                 let entity = &self.living_entity.entity;
                 let world = &entity.world.read().await;
-                if BASIC_CONFIG.allow_chat_reports {
-                    world.broadcast_secure_player_chat(self, &chat_message, decorated_message).await;
+                if let Some(signed) = &signed {
+                    world
+                        .broadcast_secure_player_chat(self, signed, decorated_message)
+                        .await;
                 } else {
                     let no_reports_packet = &CSystemChatMessage::new(
                         decorated_message,
@@ -803,11 +800,28 @@ impl Player {
         }}
     }
 
-    /// Runs all vanilla checks for a valid chat message
+    async fn handle_chat_error(&self, err: &ChatError) {
+        let gameprofile = &self.gameprofile;
+        log::log!(
+            err.severity(),
+            "{} (uuid {}) {}",
+            gameprofile.name,
+            gameprofile.id,
+            err
+        );
+        if err.is_kick() {
+            if let Some(reason) = err.client_kick_reason() {
+                self.kick(TextComponent::text(reason)).await;
+            }
+        }
+    }
+
+    /// Runs all vanilla checks for a valid chat message. Returns the message with its signature
+    /// when it is signed.
     pub async fn validate_chat_message(
         &self,
         chat_message: &SChatMessage,
-    ) -> Result<(), ChatError> {
+    ) -> Result<Option<SignedMessage>, ChatError> {
         // Check for oversized messages
         if chat_message.message.len() > 256 {
             return Err(ChatError::OversizedMessage);
@@ -821,43 +835,135 @@ impl Player {
             return Err(ChatError::IllegalCharacters);
         }
         // These checks are only run in secure chat mode
-        if BASIC_CONFIG.allow_chat_reports {
-            // Check for unsigned chat
-            if let Some(signature) = &chat_message.signature {
-                if signature.len() != 256 {
-                    return Err(ChatError::UnsignedChat); // Signature is the wrong length
-                }
-            } else {
-                return Err(ChatError::UnsignedChat); // There is no signature
-            }
+        if !BASIC_CONFIG.allow_chat_reports {
+            return Ok(None);
+        }
 
-            let now = SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_millis() as i64;
+        // Every chat message acknowledges the signed messages the player received, even unsigned ones
+        let last_seen = self
+            .apply_last_seen_update(
+                chat_message.message_count.0,
+                &chat_message.acknowledged,
+                chat_message.checksum,
+            )
+            .await?;
 
-            // Verify message timestamp
-            if chat_message.timestamp > now || chat_message.timestamp < (now - CHAT_MESSAGE_MAX_AGE)
-            {
-                return Err(ChatError::OutOfOrderChat);
+        let mut session = self.chat_session.lock().await;
+        if !session.is_initialized() {
+            if BASIC_CONFIG.enforce_secure_chat {
+                return Err(ChatError::UnsignedChat); // There is no session to sign with
             }
+            return Ok(None);
+        }
+
+        // Check for unsigned chat
+        let Some(signature) = &chat_message.signature else {
+            return Err(ChatError::UnsignedChat); // There is no signature
+        };
+        verify_signed_message(
+            self.gameprofile.id,
+            &mut session,
+            &chat_message.message,
+            signature,
+            chat_message.salt,
+            chat_message.timestamp,
+            last_seen,
+        )
+        .map(Some)
+    }
+
+    /// Runs the vanilla checks for a signed command. Returns its verified message arguments by
+    /// name.
+    pub async fn validate_chat_command(
+        self: &Arc<Self>,
+        server: &Server,
+        command: &SChatCommandSigned,
+    ) -> Result<HashMap<String, SignedMessage>, ChatError> {
+        if !BASIC_CONFIG.allow_chat_reports {
+            return Ok(HashMap::new());
+        }
+
+        let last_seen = self
+            .apply_last_seen_update(
+                command.message_count.0,
+                &command.acknowledged,
+                command.checksum,
+            )
+            .await?;
 
-            // Verify session expiry
-            if self.chat_session.lock().await.expires_at < now {
-                return Err(ChatError::ExpiredPublicKey);
+        let arguments = server
+            .command_dispatcher
+            .read()
+            .await
+            .message_arguments(
+                &mut CommandSender::Player(self.clone()),
+                server,
+                &command.command,
+            )
+            .await;
+        if arguments.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut session = self.chat_session.lock().await;
+        if !session.is_initialized() {
+            if BASIC_CONFIG.enforce_secure_chat {
+                return Err(ChatError::UnsignedChat); // There is no session to sign with
             }
+            return Ok(HashMap::new());
+        }
+        verify_argument_signatures(
+            self.gameprofile.id,
+            &mut session,
+            command,
+            arguments,
+            &last_seen,
+        )
+    }
 
-            // Validate previous signature checksum (new in 1.21.5)
-            // The client can bypass this check by sending 0
-            if chat_message.checksum != 0 {
-                let checksum =
-                    polynomial_rolling_hash(self.signature_cache.lock().await.last_seen.as_ref());
-                if checksum != chat_message.checksum {
-                    return Err(ChatError::ChatValidationFailed);
-                }
+    /// Applies the acknowledgment every chat message and signed command carries. Returns the
+    /// signatures of the messages the player has seen.
+    async fn apply_last_seen_update(
+        &self,
+        offset: i32,
+        acknowledged: &[u8],
+        checksum: u8,
+    ) -> Result<LastSeen, ChatError> {
+        let mut cache = self.signature_cache.lock().await;
+        let last_seen = cache
+            .last_seen
+            .apply_update(offset, acknowledged, checksum)
+            .map_err(|err| {
+                log::debug!("{} last seen update failed: {err}", self.gameprofile.name);
+                ChatError::ChatValidationFailed
+            })?;
+        if cache.last_seen.tracked_count() > MAX_PENDING_MESSAGES {
+            return Err(ChatError::TooManyPendingChats);
+        }
+        Ok(last_seen.into())
+    }
+
+    pub async fn handle_chat_ack(&self, chat_ack: SChatAck) {
+        if !BASIC_CONFIG.allow_chat_reports {
+            return;
+        }
+        let result = self
+            .signature_cache
+            .lock()
+            .await
+            .last_seen
+            .apply_offset(chat_ack.offset.0);
+        if let Err(err) = result {
+            log::warn!(
+                "{} (uuid {}) {}",
+                self.gameprofile.name,
+                self.gameprofile.id,
+                err
+            );
+            if let Some(reason) = ChatError::ChatValidationFailed.client_kick_reason() {
+                self.kick(TextComponent::text(reason)).await;
             }
         }
-        Ok(())
     }
 
     pub async fn handle_chat_session_update(
@@ -972,6 +1078,7 @@ impl Player {
                 return;
             }
 
+            let old_simulation_distance = chunker::get_simulation_distance(self).await;
             let (update_settings, update_watched) = {
                 let mut config = self.config.write().await;
                 let update_settings = config.main_hand != main_hand
@@ -1017,6 +1124,14 @@ impl Player {
 
             if update_watched {
                 chunker::update_position(self).await;
+                let simulation_distance = chunker::get_simulation_distance(self).await;
+                if simulation_distance != old_simulation_distance {
+                    self.client
+                        .enqueue_packet(&CSetSimulationDistance::new(
+                            simulation_distance.get().into(),
+                        ))
+                        .await;
+                }
             }
 
             if update_settings {
@@ -1033,14 +1148,21 @@ impl Player {
         }
     }
 
-    pub async fn handle_client_status(self: &Arc<Self>, client_status: SClientCommand) {
+    pub async fn handle_client_status(
+        self: &Arc<Self>,
+        server: &Server,
+        client_status: SClientCommand,
+    ) {
         match client_status.action_id.0 {
             0 => {
                 // Perform respawn
                 if self.living_entity.health.load() > 0.0 {
                     return;
                 }
-                self.world().await.respawn_player(self, false).await;
+                self.respawn_world(server)
+                    .await
+                    .respawn_player(self, false)
+                    .await;
 
                 // Restore abilities based on gamemode after respawn
                 let mut abilities = self.abilities.lock().await;
@@ -1131,8 +1253,18 @@ impl Player {
                     return;
                 }
             }
-            ActionType::Interact | ActionType::InteractAt => {
-                log::debug!("todo");
+            ActionType::Interact => {
+                // Only the main hand can feed animals for now
+                if interact.hand.is_some_and(|hand| hand.0 != 0) {
+                    return;
+                }
+                let world = entity.world.read().await.clone();
+                if let Some(target) = world.get_entity_by_id(interact.entity_id.0).await {
+                    target.interact(self).await;
+                }
+            }
+            ActionType::InteractAt => {
+                // Also sent as a plain interaction, which is handled there
             }
         }
     }
@@ -1310,7 +1442,20 @@ impl Player {
                 Status::DropItemStack => {
                     self.drop_held_item(true).await;
                 }
-                Status::ShootArrowOrFinishEating | Status::SwapItem => {
+                Status::ShootArrowOrFinishEating => {
+                    let Some(start) = self.item_use_start.take() else {
+                        return;
+                    };
+                    let held = self.inventory().lock().await.held_item().cloned();
+                    if let Some(held) = held {
+                        let use_ticks = self.tick_counter.load(Ordering::Relaxed) - start;
+                        server
+                            .item_registry
+                            .release_use(&held.item, self, use_ticks)
+                            .await;
+                    }
+                }
+                Status::SwapItem => {
                     log::debug!("todo");
                 }
             },
@@ -1350,17 +1495,26 @@ impl Player {
         let mut abilities = self.abilities.lock().await;
 
         // Set the flying ability
-        let flying = player_abilities.flags & 0x02 != 0 && abilities.allow_flying;
-        if flying {
+        let accepted =
+            abilities.request_flying(player_abilities.flags & 0x02 != 0, self.gamemode.load());
+        if abilities.flying {
             self.living_entity.fall_distance.store(0.0);
         }
-        abilities.flying = flying;
+        drop(abilities);
+        if !accepted {
+            self.send_abilities_update().await;
+        }
     }
 
     pub async fn handle_play_ping_request(&self, request: SPlayPingRequest) {
-        self.client
-            .enqueue_packet(&CPingResponse::new(request.payload))
-            .await;
+        self.client.enqueue_packet(&ping_response(&request)).await;
+    }
+
+    pub async fn handle_pong(&self, pong: SPong) {
+        // Answers to older pings are ignored
+        if let Some(latency) = self.take_ping_latency(pong.id).await {
+            self.ping_latency.store(Some(latency));
+        }
     }
 
     pub async fn handle_use_item_on(
@@ -1494,6 +1648,8 @@ impl Player {
         };
 
         if held.is_some() {
+            self.item_use_start
+                .store(Some(self.tick_counter.load(Ordering::Relaxed)));
             server.item_registry.on_use(&held.unwrap().item, self).await;
         }
         self.update_sequence(use_item.sequence.0);
@@ -1505,12 +1661,8 @@ impl Player {
             self.kick(TextComponent::text("Invalid held slot")).await;
             return;
         }
-        let mut inv = self.inventory().lock().await;
-        inv.set_selected(slot as usize);
-        let empty = &ItemStack::new(0, Item::AIR);
-        let stack = inv.held_item().unwrap_or(empty);
-        let equipment = &[(EquipmentSlot::MainHand, stack.clone())];
-        self.living_entity.send_equipment_changes(equipment).await;
+        self.inventory().lock().await.set_selected(slot as usize);
+        self.sync_equipment().await;
     }
 
     pub async fn handle_set_creative_slot(
@@ -1557,31 +1709,7 @@ impl Player {
         //     return;
         // };
         // window_id 0 represents both 9x1 Generic AND inventory here
-        let open_container = self.open_container.load();
-        if let Some(id) = open_container {
-            let mut open_containers = server.open_containers.write().await;
-            if let Some(container) = open_containers.get_mut(&id) {
-                // If the container contains both a location and a type, run the `on_close` `block_manager` handler
-                if let Some(pos) = container.get_location() {
-                    if let Some(block) = container.get_block() {
-                        server
-                            .block_registry
-                            .close(&block, self, pos, server, container) //block, self, location, server)
-                            .await;
-                    }
-                }
-                // Remove the player from the container
-                container.remove_player(self.entity_id());
-
-                let mut inventory = self.inventory().lock().await;
-                if inventory.state_id >= 2 {
-                    inventory.state_id -= 2;
-                } else {
-                    inventory.state_id = 0;
-                }
-            }
-            self.open_container.store(None);
-        }
+        self.close_open_container(server).await;
     }
 
     pub async fn handle_command_suggestion(
@@ -1650,8 +1778,26 @@ impl Player {
         // TODO: send/configure additional commands/data based on the type of entity (horse, slime, etc)
     }
 
-    const WORLD_LOWEST_Y: i8 = -64;
-    const WORLD_MAX_Y: u16 = 384;
+    /// Checks that a block can be placed at `y`, telling the player if it's above the build limit.
+    async fn check_build_height(&self, world: &World, y: i32) -> Result<(), BlockPlacingError> {
+        let build_height = world.build_height;
+        if y < build_height.min_y {
+            return Err(BlockPlacingError::BlockOutOfWorld);
+        }
+        if y > build_height.max_y() {
+            self.send_system_message_raw(
+                &TextComponent::translate(
+                    "build.tooHigh",
+                    vec![TextComponent::text(build_height.max_y().to_string())],
+                )
+                .color_named(NamedColor::Red),
+                true,
+            )
+            .await;
+            return Err(BlockPlacingError::BlockOutOfWorld);
+        }
+        Ok(())
+    }
 
     #[allow(clippy::too_many_lines)]
     async fn run_is_block_place(
@@ -1665,25 +1811,6 @@ impl Player {
         let entity = &self.living_entity.entity;
         let world = &entity.world.read().await;
 
-        // Check if the block is under the world
-        if location.0.y + face.to_offset().y < i32::from(Self::WORLD_LOWEST_Y) {
-            return Err(BlockPlacingError::BlockOutOfWorld.into());
-        }
-
-        // Check the world's max build height
-        if location.0.y + face.to_offset().y >= i32::from(Self::WORLD_MAX_Y) {
-            self.send_system_message_raw(
-                &TextComponent::translate(
-                    "build.tooHigh",
-                    vec![TextComponent::text((Self::WORLD_MAX_Y - 1).to_string())],
-                )
-                .color_named(NamedColor::Red),
-                true,
-            )
-            .await;
-            return Err(BlockPlacingError::BlockOutOfWorld.into());
-        }
-
         match self.gamemode.load() {
             GameMode::Spectator | GameMode::Adventure => {
                 return Err(BlockPlacingError::InvalidGamemode.into());
@@ -1692,6 +1819,8 @@ impl Player {
         }
 
         let clicked_block_pos = BlockPos(location.0);
+        self.check_build_height(world, clicked_block_pos.0.y)
+            .await?;
         let (clicked_block, clicked_block_state) =
             world.get_block_and_block_state(&clicked_block_pos).await?;
 
@@ -1725,6 +1854,7 @@ impl Player {
                 (clicked_block_pos, face, replacing)
             } else {
                 let block_pos = BlockPos(location.0 + face.to_offset());
+                self.check_build_height(world, block_pos.0.y).await?;
                 let (previous_block, previous_block_state) =
                     world.get_block_and_block_state(&block_pos).await?;
 
@@ -1825,3 +1955,230 @@ impl Player {
             .await;
     }
 }
+
+/// Checks a message the sender signed in their chat session, consuming the next index of their
+/// chain.
+fn verify_signed_message(
+    sender: Uuid,
+    session: &mut ChatSession,
+    message: &str,
+    signature: &[u8],
+    salt: i64,
+    timestamp: i64,
+    last_seen: LastSeen,
+) -> Result<SignedMessage, ChatError> {
+    if signature.len() != 256 {
+        return Err(ChatError::UnsignedChat); // Signature is the wrong length
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as i64;
+
+    // Verify message timestamp
+    if timestamp > now || timestamp < (now - CHAT_MESSAGE_MAX_AGE) {
+        return Err(ChatError::OutOfOrderChat);
+    }
+
+    // Verify session expiry
+    if session.expires_at < now {
+        return Err(ChatError::ExpiredPublicKey);
+    }
+
+    // Verify the signature against the session key, which was checked with Mojang's keys
+    let index = session.messages_sent;
+    let body = signed_message_body(
+        sender,
+        session.session_id,
+        index,
+        salt,
+        timestamp,
+        message,
+        last_seen.as_ref(),
+    );
+    if !verify_message_signature(&session.public_key, &body, signature) {
+        return Err(ChatError::UnsignedChat);
+    }
+    session.messages_sent += 1;
+
+    Ok(SignedMessage {
+        message: message.to_string(),
+        index,
+        signature: signature.into(),
+        salt,
+        timestamp,
+        last_seen,
+    })
+}
+
+/// Checks the signature of every message argument of a command. Like vanilla, the arguments are
+/// signed in the order they appear in the command and each takes the next index of the chain.
+fn verify_argument_signatures(
+    sender: Uuid,
+    session: &mut ChatSession,
+    command: &SChatCommandSigned,
+    arguments: Vec<(String, String)>,
+    last_seen: &LastSeen,
+) -> Result<HashMap<String, SignedMessage>, ChatError> {
+    let mut signed_arguments = HashMap::new();
+    for (name, message) in arguments {
+        let Some(argument) = command
+            .argument_signatures
+            .iter()
+            .find(|argument| argument.name == name)
+        else {
+            return Err(ChatError::UnsignedChat); // The client didn't sign the argument
+        };
+        let signed = verify_signed_message(
+            sender,
+            session,
+            &message,
+            &argument.signature,
+            command.salt,
+            command.timestamp,
+            last_seen.clone(),
+        )?;
+        signed_arguments.insert(name, signed);
+    }
+    Ok(signed_arguments)
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        sync::atomic::Ordering::Relaxed,
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    use pumpkin_data::packet::clientbound::PLAY_SET_ENTITY_DATA;
+    use pumpkin_protocol::{
+        codec::var_int::VarInt,
+        server::play::{Action, ArgumentSignature, SChatCommandSigned, SPlayerCommand},
+    };
+    use rsa::{
+        RsaPrivateKey, RsaPublicKey,
+        pkcs1v15::SigningKey,
+        pkcs8::EncodePublicKey,
+        signature::{SignatureEncoding, Signer},
+    };
+    use sha2::Sha256;
+    use uuid::Uuid;
+
+    use super::{ChatError, verify_argument_signatures};
+    use crate::entity::player::{ChatSession, LastSeen};
+    use crate::net::chat_signing::signed_message_body;
+    use crate::test_utils::{test_player, test_server};
+
+    #[test]
+    fn signed_command_arguments_are_verified_along_the_chain() {
+        // Clients sign with 2048 bit keys, so signatures are 256 bytes long
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 2048).unwrap();
+        let public_key = RsaPublicKey::from(&private_key)
+            .to_public_key_der()
+            .unwrap();
+        let signing_key = SigningKey::<Sha256>::new(private_key);
+
+        let sender = Uuid::new_v4();
+        let session_id = Uuid::new_v4();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+        let mut session = ChatSession::new(
+            session_id,
+            now + 60_000,
+            public_key.as_bytes().into(),
+            Box::new([]),
+        );
+        session.messages_sent = 2;
+        let last_seen = LastSeen::from(vec![Box::new([1_u8; 256]) as Box<[u8]>]);
+
+        let body = signed_message_body(
+            sender,
+            session_id,
+            2,
+            42,
+            now,
+            "hello there",
+            last_seen.as_ref(),
+        );
+        let command = SChatCommandSigned {
+            command: "msg Steve hello there".to_string(),
+            timestamp: now,
+            salt: 42,
+            argument_signatures: Box::new([ArgumentSignature {
+                name: "message".to_string(),
+                signature: signing_key.sign(&body).to_vec().into(),
+            }]),
+            message_count: VarInt(0),
+            acknowledged: Box::new([0; 3]),
+            checksum: 0,
+        };
+        // What the dispatcher parses the message arguments of the command to
+        let arguments = || vec![("message".to_string(), "hello there".to_string())];
+
+        let signed =
+            verify_argument_signatures(sender, &mut session, &command, arguments(), &last_seen)
+                .unwrap();
+        let message = &signed["message"];
+        assert_eq!(message.message, "hello there");
+        assert_eq!(message.index, 2);
+        assert_eq!(message.signature, command.argument_signatures[0].signature);
+        assert_eq!(session.messages_sent, 3);
+
+        // The same command can't be replayed, the chain moved on
+        assert!(matches!(
+            verify_argument_signatures(sender, &mut session, &command, arguments(), &last_seen),
+            Err(ChatError::UnsignedChat)
+        ));
+
+        session.messages_sent = 2;
+        // A message argument the client didn't sign is rejected
+        let unsigned = vec![("reason".to_string(), "hello there".to_string())];
+        assert!(matches!(
+            verify_argument_signatures(sender, &mut session, &command, unsigned, &last_seen),
+            Err(ChatError::UnsignedChat)
+        ));
+        // And so is a message which differs from what was signed
+        let tampered = vec![("message".to_string(), "hello here".to_string())];
+        assert!(matches!(
+            verify_argument_signatures(sender, &mut session, &command, tampered, &last_seen),
+            Err(ChatError::UnsignedChat)
+        ));
+        // Commands without message arguments have nothing to verify
+        assert!(
+            verify_argument_signatures(sender, &mut session, &command, Vec::new(), &last_seen)
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(session.messages_sent, 2);
+    }
+
+    #[tokio::test]
+    async fn repeated_start_sneaking_is_ignored() {
+        let server = test_server();
+        let (alice, _alice_packets) = test_player(&server, "alice").await;
+        let (bob, mut bob_packets) = test_player(&server, "bob").await;
+        bob.tracked_entities.lock().await.track(alice.entity_id());
+        alice.set_client_loaded(true);
+
+        let start_sneaking = || SPlayerCommand {
+            entity_id: alice.entity_id().into(),
+            action: VarInt(Action::StartSneaking as i32),
+            jump_boost: VarInt(0),
+        };
+        alice.handle_player_command(start_sneaking()).await;
+        assert!(alice.living_entity.entity.sneaking.load(Relaxed));
+        let mut updates = 0;
+        while let Ok((packet, _)) = bob_packets.try_recv() {
+            assert_eq!(i32::from(packet[0]), PLAY_SET_ENTITY_DATA);
+            updates += 1;
+        }
+        assert!(updates > 0);
+
+        // The client repeats the command, but nothing changed
+        alice.handle_player_command(start_sneaking()).await;
+        assert!(bob_packets.try_recv().is_err());
+    }
+}
@@ -1,13 +1,33 @@
-use pumpkin_protocol::{client::status::CPingResponse, server::status::SStatusPingRequest};
+use std::net::SocketAddr;
 
-use crate::{net::Client, server::Server};
+use pumpkin_protocol::{
+    StatusResponse,
+    client::status::{CPingResponse, CStatusResponse},
+    server::status::SStatusPingRequest,
+};
+
+use crate::{
+    PLUGIN_MANAGER,
+    net::Client,
+    plugin::{PluginManager, server::server_list_ping::ServerListPingEvent},
+    server::Server,
+};
 
 impl Client {
     pub async fn handle_status_request(&self, server: &Server) {
         log::debug!("Handling status request");
-        let status = server.get_status();
-        self.send_packet_now(&status.lock().await.get_status())
-            .await;
+        let address = *self.address.lock().await;
+        let rewritten = rewritten_status(&*PLUGIN_MANAGER.lock().await, server, address).await;
+        match rewritten {
+            Some(status) => {
+                self.send_packet_now(&CStatusResponse::new(&status)).await;
+            }
+            None => {
+                let status = server.get_status();
+                self.send_packet_now(&status.lock().await.get_status())
+                    .await;
+            }
+        }
     }
 
     pub async fn handle_ping_request(&self, ping_request: SStatusPingRequest) {
@@ -17,3 +37,72 @@ impl Client {
         self.close();
     }
 }
+
+/// The status JSON after the ping handlers of `plugins` had their say, or `None` if there are
+/// none, as nothing can change the status then and the cached JSON can be sent as is.
+async fn rewritten_status(
+    plugins: &PluginManager,
+    server: &Server,
+    address: SocketAddr,
+) -> Option<String> {
+    if !plugins.has_handlers::<ServerListPingEvent>().await {
+        return None;
+    }
+    let status = server.get_status().lock().await.status_response().clone();
+    let event = plugins
+        .fire(ServerListPingEvent::new(status, address))
+        .await;
+    Some(status_json(&event.status))
+}
+
+fn status_json(status: &StatusResponse) -> String {
+    serde_json::to_string(status).expect("Failed to parse status response into JSON")
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::{Ipv4Addr, SocketAddr};
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+
+    use super::{rewritten_status, status_json};
+    use crate::plugin::{
+        EventHandler, EventPriority, PluginManager, server::server_list_ping::ServerListPingEvent,
+    };
+    use crate::server::Server;
+    use crate::test_utils::test_server;
+
+    struct Maintenance;
+
+    #[async_trait]
+    impl EventHandler<ServerListPingEvent> for Maintenance {
+        async fn handle_blocking(&self, _server: &Arc<Server>, event: &mut ServerListPingEvent) {
+            event.status.description = "Event starts soon!".to_string();
+            event.status.players = None;
+        }
+    }
+
+    #[tokio::test]
+    async fn ping_handlers_can_rewrite_the_status() {
+        let server = test_server();
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, 52000));
+        let mut plugins = PluginManager::new();
+        plugins.set_server(server.clone());
+        assert!(rewritten_status(&plugins, &server, address).await.is_none());
+
+        plugins
+            .register::<ServerListPingEvent, _>(Arc::new(Maintenance), EventPriority::Normal, true)
+            .await;
+        let status = rewritten_status(&plugins, &server, address).await.unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&status).unwrap();
+        assert_eq!(json["description"], "Event starts soon!");
+        assert!(json["players"].is_null());
+        let cached: serde_json::Value = serde_json::from_str(&status_json(
+            server.get_status().lock().await.status_response(),
+        ))
+        .unwrap();
+        assert_eq!(json["version"], cached["version"]);
+    }
+}
@@ -0,0 +1,221 @@
+use std::{
+    fmt::Write as _,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use pumpkin_data::packet::{clientbound, serverbound};
+use pumpkin_protocol::{ConnectionState, ser::NetworkReadExt};
+use tokio::{
+    sync::mpsc::{self, UnboundedReceiver, UnboundedSender},
+    task::JoinHandle,
+};
+
+const LOG_DIRECTORY: &str = "packet_logs";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PacketDirection {
+    Serverbound,
+    Clientbound,
+}
+
+impl PacketDirection {
+    const fn arrow(self) -> &'static str {
+        match self {
+            Self::Serverbound => "C->S",
+            Self::Clientbound => "S->C",
+        }
+    }
+}
+
+/// Writes the raw packets of a single client to a log, for protocol debugging.
+///
+/// Disabled by default, in which case logging a packet is a single atomic load. Entries are
+/// written by a blocking task, so logging a packet never waits for the disk.
+#[derive(Default)]
+pub struct PacketLog {
+    enabled: AtomicBool,
+    sender: Mutex<Option<UnboundedSender<String>>>,
+    writer_task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl PacketLog {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Starts logging packets into the given writer, replacing any previous one.
+    pub fn enable(&self, writer: Box<dyn Write + Send>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let task = tokio::task::spawn_blocking(move || write_entries(writer, receiver));
+        // The previous writer finishes its entries on its own once its sender is gone
+        *self.sender.lock().unwrap() = Some(sender);
+        *self.writer_task.lock().unwrap() = Some(task);
+        self.enabled.store(true, Ordering::Relaxed);
+    }
+
+    /// Starts logging packets into a new file in the packet log directory, returning its path.
+    pub fn enable_file(&self, name: &str) -> io::Result<PathBuf> {
+        fs::create_dir_all(LOG_DIRECTORY)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let path = PathBuf::from(LOG_DIRECTORY).join(format!("{name}-{timestamp}.log"));
+        let file = File::create(&path)?;
+        self.enable(Box::new(BufWriter::new(file)));
+        Ok(path)
+    }
+
+    /// Stops logging packets, waiting until the packets logged so far are written.
+    pub async fn disable(&self) {
+        self.enabled.store(false, Ordering::Relaxed);
+        self.sender.lock().unwrap().take();
+        let task = self.writer_task.lock().unwrap().take();
+        if let Some(task) = task {
+            let _ = task.await;
+        }
+    }
+
+    pub fn log_serverbound(&self, state: ConnectionState, id: i32, payload: &[u8]) {
+        if self.is_enabled() {
+            self.write_entry(PacketDirection::Serverbound, state, id, payload);
+        }
+    }
+
+    /// Logs an outgoing packet, which is still prefixed with its id.
+    pub fn log_clientbound(&self, state: ConnectionState, packet_data: &[u8]) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut payload = packet_data;
+        let Ok(id) = payload.get_var_int() else {
+            return;
+        };
+        self.write_entry(PacketDirection::Clientbound, state, id.0, payload);
+    }
+
+    fn write_entry(
+        &self,
+        direction: PacketDirection,
+        state: ConnectionState,
+        id: i32,
+        payload: &[u8],
+    ) {
+        let entry = format_entry(direction, state, id, payload);
+        if let Some(sender) = self.sender.lock().unwrap().as_ref() {
+            let _ = sender.send(entry);
+        }
+    }
+}
+
+/// Writes entries until the log is disabled. Everything queued up is written before flushing,
+/// so a busy connection doesn't flush for every packet.
+fn write_entries(mut writer: Box<dyn Write + Send>, mut receiver: UnboundedReceiver<String>) {
+    while let Some(entry) = receiver.blocking_recv() {
+        let mut result = writeln!(writer, "{entry}");
+        while let Ok(entry) = receiver.try_recv() {
+            result = result.and_then(|()| writeln!(writer, "{entry}"));
+        }
+        if let Err(err) = result.and_then(|()| writer.flush()) {
+            log::warn!("Failed to write packet log: {err}");
+        }
+    }
+}
+
+/// Packets carrying encryption keys or signing keys, whose contents must never end up in a log.
+fn is_sensitive(direction: PacketDirection, state: ConnectionState, id: i32) -> bool {
+    match (direction, state) {
+        (PacketDirection::Clientbound, ConnectionState::Login) => id == clientbound::LOGIN_HELLO,
+        (PacketDirection::Serverbound, ConnectionState::Login) => id == serverbound::LOGIN_KEY,
+        (PacketDirection::Serverbound, ConnectionState::Play) => {
+            id == serverbound::PLAY_CHAT_SESSION_UPDATE
+        }
+        _ => false,
+    }
+}
+
+fn format_entry(
+    direction: PacketDirection,
+    state: ConnectionState,
+    id: i32,
+    payload: &[u8],
+) -> String {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let mut entry = format!(
+        "{timestamp} {} {state:?} id=0x{id:02X} len={} ",
+        direction.arrow(),
+        payload.len()
+    );
+    if is_sensitive(direction, state, id) {
+        entry.push_str("<redacted>");
+    } else {
+        for byte in payload {
+            let _ = write!(entry, "{byte:02x}");
+        }
+    }
+    entry
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        io::{self, Write},
+        sync::{Arc, Mutex},
+    };
+
+    use pumpkin_data::packet::{clientbound, serverbound};
+    use pumpkin_protocol::ConnectionState;
+
+    use super::PacketLog;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn logs_both_directions_when_enabled() {
+        let log = PacketLog::default();
+        let buffer = SharedBuffer::default();
+
+        // Nothing is recorded while disabled
+        log.log_serverbound(ConnectionState::Play, 0x1A, &[1, 2]);
+        log.enable(Box::new(buffer.clone()));
+
+        log.log_serverbound(ConnectionState::Play, 0x1A, &[0xAB, 0xCD]);
+        log.log_clientbound(ConnectionState::Play, &[0x27, 0x01]);
+        log.log_clientbound(
+            ConnectionState::Login,
+            &[clientbound::LOGIN_HELLO as u8, 0x42],
+        );
+        log.log_serverbound(ConnectionState::Login, serverbound::LOGIN_KEY, &[0x13]);
+        log.disable().await;
+        log.log_clientbound(ConnectionState::Play, &[0x27, 0x02]);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].ends_with("C->S Play id=0x1A len=2 abcd"));
+        assert!(lines[1].ends_with("S->C Play id=0x27 len=1 01"));
+        assert!(lines[2].ends_with("<redacted>"));
+        assert!(lines[3].ends_with("<redacted>"));
+    }
+}
@@ -0,0 +1,106 @@
+use pumpkin_util::math::vector3::Vector3;
+
+/// How many ticks the client has to confirm a teleport before it is sent again. Vanilla: 20
+pub const TELEPORT_CONFIRM_TIMEOUT: i32 = 20;
+
+/// A teleport sent to the client which it has not confirmed yet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PendingTeleport {
+    pub id: i32,
+    pub position: Vector3<f64>,
+    /// The player tick the teleport was sent in
+    pub sent_tick: i32,
+}
+
+/// Tracks the last server initiated teleport until the client confirms it.
+/// Until then, the client's movement is from before the teleport and must be discarded,
+/// otherwise the player would be pulled back to where they were.
+#[derive(Default)]
+pub struct TeleportTracker {
+    pending: Option<PendingTeleport>,
+}
+
+impl TeleportTracker {
+    /// Remembers a teleport sent to the client, replacing any earlier unconfirmed one.
+    pub fn start(&mut self, id: i32, position: Vector3<f64>, tick: i32) {
+        self.pending = Some(PendingTeleport {
+            id,
+            position,
+            sent_tick: tick,
+        });
+    }
+
+    pub const fn pending(&self) -> Option<&PendingTeleport> {
+        self.pending.as_ref()
+    }
+
+    /// Whether movement packets from the client should be applied.
+    pub const fn accepts_movement(&self) -> bool {
+        self.pending.is_none()
+    }
+
+    /// Handles a teleport confirmation and returns the position the player was teleported to
+    /// if it matches the pending teleport. Confirmations of older teleports, which were replaced
+    /// before the client answered, return `None`.
+    pub fn confirm(&mut self, id: i32) -> Option<Vector3<f64>> {
+        match self.pending {
+            Some(pending) if pending.id == id => {
+                self.pending = None;
+                Some(pending.position)
+            }
+            _ => None,
+        }
+    }
+
+    /// The pending teleport if the client didn't confirm it in time, so it has to be sent again.
+    pub fn timed_out(&self, tick: i32) -> Option<PendingTeleport> {
+        self.pending
+            .filter(|pending| tick.wrapping_sub(pending.sent_tick) > TELEPORT_CONFIRM_TIMEOUT)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{TELEPORT_CONFIRM_TIMEOUT, TeleportTracker};
+
+    #[test]
+    fn movement_is_ignored_until_the_teleport_is_confirmed() {
+        let mut tracker = TeleportTracker::default();
+        assert!(tracker.accepts_movement());
+
+        let target = Vector3::new(100.0, 64.0, -20.0);
+        tracker.start(1, target, 0);
+        assert!(!tracker.accepts_movement());
+
+        // A confirmation for another teleport doesn't unlock movement
+        assert_eq!(tracker.confirm(2), None);
+        assert!(!tracker.accepts_movement());
+
+        assert_eq!(tracker.confirm(1), Some(target));
+        assert!(tracker.accepts_movement());
+        // Confirming twice does nothing
+        assert_eq!(tracker.confirm(1), None);
+    }
+
+    #[test]
+    fn unconfirmed_teleports_time_out() {
+        let mut tracker = TeleportTracker::default();
+        let target = Vector3::new(0.0, 80.0, 0.0);
+        tracker.start(5, target, 100);
+        assert_eq!(tracker.timed_out(100 + TELEPORT_CONFIRM_TIMEOUT), None);
+        assert_eq!(
+            tracker
+                .timed_out(101 + TELEPORT_CONFIRM_TIMEOUT)
+                .map(|pending| pending.id),
+            Some(5)
+        );
+
+        // Sending it again replaces the pending teleport, the old id is stale now
+        tracker.start(6, target, 121);
+        assert_eq!(tracker.confirm(5), None);
+        assert!(!tracker.accepts_movement());
+        assert_eq!(tracker.confirm(6), Some(target));
+    }
+}
@@ -1,6 +1,7 @@
 use std::{fs, path::Path, sync::Arc};
 
 use crate::command::client_suggestions;
+use crate::command::permission::PermissionProvider;
 use pumpkin_util::PermissionLvl;
 use tokio::sync::RwLock;
 
@@ -94,6 +95,25 @@ impl Context {
         }
     }
 
+    /// Replaces how permission nodes of commands (e.g. `pumpkin.command.teleport`) are resolved.
+    ///
+    /// # Arguments
+    /// - `provider`: The provider deciding which command senders have a permission node.
+    pub async fn set_permission_provider(&self, provider: Arc<dyn PermissionProvider>) {
+        self.server
+            .command_dispatcher
+            .write()
+            .await
+            .set_permission_provider(provider);
+
+        for world in self.server.worlds.read().await.iter() {
+            for player in world.players.read().await.values() {
+                let command_dispatcher = self.server.command_dispatcher.read().await;
+                client_suggestions::send_c_commands_packet(player, &command_dispatcher).await;
+            }
+        }
+    }
+
     /// Asynchronously unregisters a command from the server.
     ///
     /// # Arguments
@@ -1,2 +1,3 @@
 pub mod server_broadcast;
 pub mod server_command;
+pub mod server_list_ping;
@@ -0,0 +1,30 @@
+use std::net::SocketAddr;
+
+use pumpkin_macros::Event;
+use pumpkin_protocol::StatusResponse;
+
+/// An event that occurs when a client pings the server from its server list.
+///
+/// Handlers can rewrite the status (MOTD, version, players, favicon) before it is sent.
+#[derive(Event, Clone)]
+pub struct ServerListPingEvent {
+    /// The status which will be sent to the client.
+    pub status: StatusResponse,
+    /// The address of the client pinging the server.
+    pub address: SocketAddr,
+}
+
+impl ServerListPingEvent {
+    /// Creates a new instance of `ServerListPingEvent`.
+    ///
+    /// # Arguments
+    /// - `status`: The status which will be sent to the client.
+    /// - `address`: The address of the client pinging the server.
+    ///
+    /// # Returns
+    /// A new instance of `ServerListPingEvent`.
+    #[must_use]
+    pub fn new(status: StatusResponse, address: SocketAddr) -> Self {
+        Self { status, address }
+    }
+}
@@ -353,6 +353,15 @@ impl PluginManager {
             .push(Box::new(typed_handler));
     }
 
+    /// Whether any handler is registered for events of type `E`
+    pub async fn has_handlers<E: Event + Send + Sync + 'static>(&self) -> bool {
+        self.handlers
+            .read()
+            .await
+            .get(&E::get_name_static())
+            .is_some_and(|handlers| !handlers.is_empty())
+    }
+
     /// Fire an event to all registered handlers
     pub async fn fire<E: Event + Send + Sync + 'static>(&self, mut event: E) -> E {
         if let Some(server) = &self.server {
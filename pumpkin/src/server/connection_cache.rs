@@ -6,9 +6,10 @@ use pumpkin_config::{BASIC_CONFIG, BasicConfiguration};
 use pumpkin_data::packet::CURRENT_MC_PROTOCOL;
 use pumpkin_protocol::{
     Players, StatusResponse, Version,
-    client::{config::CPluginMessage, status::CStatusResponse},
+    client::{config::CPluginMessage, play::CServerData, status::CStatusResponse},
     codec::var_int::VarInt,
 };
+use pumpkin_util::text::TextComponent;
 use std::sync::atomic::Ordering;
 use std::{
     fs::File,
@@ -17,6 +18,7 @@ use std::{
 };
 
 const DEFAULT_ICON: &[u8] = include_bytes!("../../../assets/default_icon.png");
+const ICON_PREFIX: &str = "data:image/png;base64,";
 
 fn load_icon_from_file<P: AsRef<Path>>(path: P) -> Result<String, Box<dyn error::Error>> {
     let mut icon_file = File::open(path)?;
@@ -34,7 +36,7 @@ fn load_icon_from_bytes(png_data: &[u8]) -> Result<String, Box<dyn error::Error>
     assert!(info.height == 64, "Icon height must be 64");
 
     // Reader consumes the image. Once we verify dimensions, we want to encode the entire raw image
-    let mut result = "data:image/png;base64,".to_owned();
+    let mut result = ICON_PREFIX.to_owned();
     general_purpose::STANDARD.encode_string(png_data, &mut result);
     Ok(result)
 }
@@ -89,6 +91,27 @@ impl CachedStatus {
         CStatusResponse::new(&self.status_response_json)
     }
 
+    /// The status before plugins had a chance to change it.
+    pub fn status_response(&self) -> &StatusResponse {
+        &self.status_response
+    }
+
+    /// The MOTD and icon for players who are already playing, the MOTD is legacy formatted text.
+    pub fn server_data(&self) -> CServerData {
+        let motd = TextComponent::text(self.status_response.description.clone());
+        let icon = self.status_response.favicon.as_ref().and_then(|favicon| {
+            let base64 = favicon.strip_prefix(ICON_PREFIX)?;
+            general_purpose::STANDARD.decode(base64).ok()
+        });
+        CServerData::new(motd, icon)
+    }
+
+    pub fn set_motd(&mut self, motd: String) {
+        self.status_response.description = motd;
+        self.status_response_json = serde_json::to_string(&self.status_response)
+            .expect("Failed to parse status response into JSON");
+    }
+
     // TODO: Player samples
     pub fn add_player(&mut self, player: &Player) {
         let status_response = &mut self.status_response;
@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use uuid::Uuid;
+
+/// Serializes logins of the same UUID, so two clients connecting at nearly the same time
+/// can't both be added as a player.
+#[derive(Default)]
+pub struct LoginLocks {
+    locks: std::sync::Mutex<HashMap<Uuid, Weak<Mutex<()>>>>,
+}
+
+impl LoginLocks {
+    /// Waits until no other login of `uuid` is in progress. The login lock is held until the
+    /// returned guard is dropped.
+    pub async fn acquire(&self, uuid: Uuid) -> OwnedMutexGuard<()> {
+        let lock = {
+            let mut locks = self.locks.lock().unwrap();
+            // Forget the locks of logins which have finished
+            locks.retain(|_, lock| lock.strong_count() > 0);
+            if let Some(lock) = locks.get(&uuid).and_then(Weak::upgrade) {
+                lock
+            } else {
+                let lock = Arc::new(Mutex::new(()));
+                locks.insert(uuid, Arc::downgrade(&lock));
+                lock
+            }
+        };
+        lock.lock_owned().await
+    }
+
+    #[cfg(test)]
+    fn pending(&self) -> usize {
+        let locks = self.locks.lock().unwrap();
+        locks
+            .values()
+            .filter(|lock| lock.strong_count() > 0)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tokio::time::timeout;
+    use uuid::Uuid;
+
+    use super::LoginLocks;
+
+    #[tokio::test]
+    async fn same_uuid_logins_are_serialized() {
+        let locks = LoginLocks::default();
+        let uuid = Uuid::new_v4();
+
+        let first = locks.acquire(uuid).await;
+        assert!(
+            timeout(Duration::from_millis(20), locks.acquire(uuid))
+                .await
+                .is_err()
+        );
+        // Other players can still log in
+        let other = timeout(Duration::from_millis(20), locks.acquire(Uuid::new_v4()))
+            .await
+            .unwrap();
+        drop(other);
+
+        drop(first);
+        let second = timeout(Duration::from_millis(20), locks.acquire(uuid))
+            .await
+            .unwrap();
+        assert_eq!(locks.pending(), 1);
+        drop(second);
+        assert_eq!(locks.pending(), 0);
+    }
+}
@@ -2,7 +2,7 @@ use crate::block::registry::BlockRegistry;
 use crate::command::commands::default_dispatcher;
 use crate::command::commands::defaultgamemode::DefaultGamemode;
 use crate::data::player_server_data::ServerPlayerData;
-use crate::entity::EntityId;
+use crate::entity::{EntityBase, EntityId};
 use crate::item::registry::ItemRegistry;
 use crate::net::EncryptionError;
 use crate::plugin::player::player_login::PlayerLoginEvent;
@@ -13,39 +13,57 @@ use crate::{
 };
 use bytes::Bytes;
 use connection_cache::{CachedBranding, CachedStatus};
+use futures::future::join_all;
 use key_store::KeyStore;
+use login_lock::LoginLocks;
 use pumpkin_config::{BASIC_CONFIG, advanced_config};
 use pumpkin_data::Block;
 use pumpkin_inventory::drag_handler::DragHandler;
 use pumpkin_inventory::{Container, OpenContainer};
 use pumpkin_macros::send_cancellable;
 use pumpkin_protocol::client::login::CEncryptionRequest;
+use pumpkin_protocol::client::play::{CTickingState, CTickingStep};
+use pumpkin_protocol::codec::var_int::VarInt;
 use pumpkin_protocol::{ClientPacket, client::config::CPluginMessage};
-use pumpkin_registry::{DimensionType, Registry};
+use pumpkin_registry::{DimensionType, DimensionTypes, Registry};
+use pumpkin_util::PermissionLvl;
 use pumpkin_util::math::position::BlockPos;
 use pumpkin_util::math::vector2::Vector2;
 use pumpkin_util::text::TextComponent;
-use pumpkin_world::dimension::Dimension;
+use pumpkin_world::advancement::{Advancements, PlayerAdvancements};
+use pumpkin_world::chunk::world_height_sections;
+use pumpkin_world::get_configured_world_gen;
+use pumpkin_world::level::Level;
+use pumpkin_world::world_info::LevelData;
 use rand::prelude::SliceRandom;
 use rsa::RsaPublicKey;
+use scheduler::Scheduler;
 use std::collections::HashMap;
 use std::net::IpAddr;
-use std::sync::atomic::{AtomicBool, AtomicU32};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize};
 use std::{
     sync::{Arc, atomic::Ordering},
-    time::Duration,
+    time::{Duration, Instant},
 };
+use tick_rate::{SprintReport, TickRateManager};
 use tokio::sync::{Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tokio_util::task::TaskTracker;
 
 mod connection_cache;
 mod key_store;
+mod login_lock;
+pub mod scheduler;
 pub mod seasonal_events;
+pub mod tick_rate;
 pub mod ticker;
 
 pub const CURRENT_MC_VERSION: &str = "1.21.5";
 
+/// How many of the last ticks are used for MSPT/TPS calculations
+const TICK_TIMES_LEN: usize = 100;
+
 /// Represents a Minecraft server instance.
 pub struct Server {
     /// Handles cryptographic keys for secure communication.
@@ -83,15 +101,149 @@ pub struct Server {
     pub defaultgamemode: Mutex<DefaultGamemode>,
     /// Manages player data storage
     pub player_data_storage: ServerPlayerData,
+    /// The bundled advancements and those of the world's data packs
+    pub advancements: Arc<Advancements>,
     // Whether the server whitelist is on or off
     pub white_list: AtomicBool,
+    /// Runs delayed and repeating tasks on the server tick
+    pub scheduler: Scheduler,
+    /// The tick rate and whether the game is frozen, see `/tick`
+    pub tick_rate_manager: std::sync::Mutex<TickRateManager>,
+    /// The duration of the last ticks in nanoseconds
+    tick_times_nanos: std::sync::Mutex<[u64; TICK_TIMES_LEN]>,
+    tick_count: AtomicUsize,
+    /// Prevents the same UUID from logging in twice at the same time
+    login_locks: LoginLocks,
     tasks: TaskTracker,
 }
 
+/// Loads the world of a custom dimension, which is saved in `dimensions/<namespace>/<name>` like in
+/// vanilla. Returns `None` if the dimension's bounds can't be used.
+fn load_custom_world(
+    world_path: &Path,
+    dimension_type: DimensionType,
+    dimension_types: &DimensionTypes,
+    block_registry: &Arc<BlockRegistry>,
+) -> Option<World> {
+    let id = dimension_type.name();
+    let dimension = dimension_types.get(&dimension_type)?;
+    let min_y = dimension.min_y();
+    let Ok(height) = u32::try_from(dimension.height()) else {
+        log::error!("Dimension type {id} has a negative height");
+        return None;
+    };
+    if let Err(error) = world_height_sections(min_y, height) {
+        log::error!("Dimension type {id} can't be loaded: {error}");
+        return None;
+    }
+    let folder = world_path
+        .join("dimensions")
+        .join(&id.namespace)
+        .join(&id.path);
+    log::info!("Loading custom dimension {id}");
+    let level = if BASIC_CONFIG.in_memory_world {
+        Level::in_memory_with_height(LevelData::default(), min_y, height)
+    } else {
+        Level::from_root_folder_with_height(folder, min_y, height)
+    };
+    Some(World::load(
+        level,
+        dimension_type,
+        dimension_types,
+        block_registry.clone(),
+    ))
+}
+
 impl Server {
     #[allow(clippy::new_without_default)]
     #[must_use]
     pub fn new() -> Self {
+        let world_path = BASIC_CONFIG.get_world_path();
+
+        let block_registry = super::block::default_registry();
+
+        let mut dimension_types = DimensionTypes::default();
+        let custom_dimensions = dimension_types
+            .load_folder(&world_path.join("dimension_type"))
+            .unwrap_or_else(|error| {
+                log::error!("Failed to load custom dimension types: {error}");
+                Vec::new()
+            });
+
+        let mut advancements = Advancements::vanilla();
+        if let Err(error) = advancements.load_datapacks(&world_path.join("datapacks")) {
+            log::error!("Failed to load data pack advancements: {error}");
+        }
+
+        let world_gen = |seed| {
+            get_configured_world_gen(
+                BASIC_CONFIG.level_type,
+                &BASIC_CONFIG.generator_settings,
+                seed,
+            )
+        };
+        let level = if BASIC_CONFIG.in_memory_world {
+            log::warn!("The world is kept in memory only, nothing will be saved");
+            Level::in_memory_with_generator(LevelData::default(), world_gen)
+        } else {
+            Level::from_root_folder_with_generator(world_path.clone(), world_gen)
+        };
+        let world = World::load(
+            level,
+            DimensionType::Overworld,
+            &dimension_types,
+            block_registry.clone(),
+        );
+        let mut worlds = vec![Arc::new(world)];
+        let mut dimensions = vec![
+            DimensionType::Overworld,
+            DimensionType::OverworldCaves,
+            DimensionType::TheNether,
+            DimensionType::TheEnd,
+        ];
+        for dimension_type in custom_dimensions {
+            if let Some(world) = load_custom_world(
+                &world_path,
+                dimension_type.clone(),
+                &dimension_types,
+                &block_registry,
+            ) {
+                worlds.push(Arc::new(world));
+                dimensions.push(dimension_type);
+            }
+        }
+
+        let world_name = world_path.to_str().unwrap();
+        let player_data_storage = if BASIC_CONFIG.in_memory_world {
+            ServerPlayerData::in_memory(Duration::from_secs(
+                advanced_config().player_data.save_player_cron_interval,
+            ))
+        } else {
+            ServerPlayerData::new(
+                format!("{world_name}/playerdata"),
+                Duration::from_secs(advanced_config().player_data.save_player_cron_interval),
+            )
+        };
+
+        Self::with_worlds(
+            worlds,
+            dimensions,
+            &dimension_types,
+            block_registry,
+            advancements,
+            player_data_storage,
+        )
+    }
+
+    /// A server of already loaded worlds, `worlds[0]` is where players join.
+    pub(crate) fn with_worlds(
+        worlds: Vec<Arc<World>>,
+        dimensions: Vec<DimensionType>,
+        dimension_types: &DimensionTypes,
+        block_registry: Arc<BlockRegistry>,
+        advancements: Advancements,
+        player_data_storage: ServerPlayerData,
+    ) -> Self {
         let auth_client = BASIC_CONFIG.online_mode.then(|| {
             reqwest::Client::builder()
                 .connect_timeout(Duration::from_millis(u64::from(
@@ -106,30 +258,14 @@ impl Server {
 
         // First register the default commands. After that, plugins can put in their own.
         let command_dispatcher = RwLock::new(default_dispatcher());
-        let world_path = BASIC_CONFIG.get_world_path();
-
-        let block_registry = super::block::default_registry();
-
-        let world = World::load(
-            Dimension::Overworld.into_level(world_path.clone()),
-            DimensionType::Overworld,
-            block_registry.clone(),
-        );
-
-        let world_name = world_path.to_str().unwrap();
 
         Self {
-            cached_registry: Registry::get_synced(),
+            cached_registry: Registry::get_synced(dimension_types),
             open_containers: RwLock::new(HashMap::new()),
             drag_handler: DragHandler::new(),
             container_id: 0.into(),
-            worlds: RwLock::new(vec![Arc::new(world)]),
-            dimensions: vec![
-                DimensionType::Overworld,
-                DimensionType::OverworldCaves,
-                DimensionType::TheNether,
-                DimensionType::TheEnd,
-            ],
+            worlds: RwLock::new(worlds),
+            dimensions,
             command_dispatcher,
             block_registry,
             item_registry: super::item::items::default_registry(),
@@ -141,11 +277,14 @@ impl Server {
             defaultgamemode: Mutex::new(DefaultGamemode {
                 gamemode: BASIC_CONFIG.default_gamemode,
             }),
-            player_data_storage: ServerPlayerData::new(
-                format!("{world_name}/playerdata"),
-                Duration::from_secs(advanced_config().player_data.save_player_cron_interval),
-            ),
+            player_data_storage,
+            advancements: Arc::new(advancements),
             white_list: AtomicBool::new(BASIC_CONFIG.white_list),
+            scheduler: Scheduler::default(),
+            tick_rate_manager: std::sync::Mutex::new(TickRateManager::new(BASIC_CONFIG.tps)),
+            tick_times_nanos: std::sync::Mutex::new([0; TICK_TIMES_LEN]),
+            tick_count: AtomicUsize::new(0),
+            login_locks: LoginLocks::default(),
             tasks: TaskTracker::new(),
             mojang_public_keys: Mutex::new(Vec::new()),
         }
@@ -200,12 +339,39 @@ impl Server {
     ///
     /// You still have to spawn the `Player` in a `World` to let them join and make them visible.
     pub async fn add_player(&self, client: Client) -> Option<(Arc<Player>, Arc<World>)> {
+        let uuid = client
+            .gameprofile
+            .lock()
+            .await
+            .as_ref()
+            .map(|profile| profile.id);
+        let _login_guard = match uuid {
+            Some(uuid) => Some(self.login_locks.acquire(uuid).await),
+            None => None,
+        };
+
+        // The same account is still online (or a ghost session was left behind), the new login wins
+        if let Some(existing) = match uuid {
+            Some(uuid) => self.get_player_by_uuid(uuid).await,
+            None => None,
+        } {
+            existing
+                .kick(TextComponent::translate(
+                    "multiplayer.disconnect.duplicate_login",
+                    [],
+                ))
+                .await;
+            // Save and remove them now, so the new player loads the latest data
+            self.disconnect_player(&existing).await;
+        }
+
         let gamemode = self.defaultgamemode.lock().await.gamemode;
         // Basically the default world
         // TODO: select default from config
         let world = &self.worlds.read().await[0];
 
         let mut player = Player::new(client, world.clone(), gamemode).await;
+        *player.advancements.get_mut() = PlayerAdvancements::new(self.advancements.clone());
 
         // Load player data
         if let Err(e) = self
@@ -246,6 +412,24 @@ impl Server {
         }}
     }
 
+    /// Saves the data of a player, removes them from their world and ticks down the online count.
+    ///
+    /// Does nothing if the player was already removed, e.g. because they were replaced by
+    /// a duplicate login.
+    pub async fn disconnect_player(&self, player: &Arc<Player>) {
+        if !player.remove().await {
+            return;
+        }
+        player.close_open_container(self).await;
+
+        // Save player data on disconnect
+        if let Err(e) = self.player_data_storage.handle_player_leave(player).await {
+            log::error!("Failed to save player data on disconnect: {}", e);
+        }
+
+        self.remove_player(player).await;
+    }
+
     pub async fn remove_player(&self, player: &Player) {
         // TODO: Config if we want decrease online
         self.listing.lock().await.remove_player(player);
@@ -264,6 +448,49 @@ impl Server {
         log::info!("Completed worlds");
     }
 
+    /// Saves all worlds and online players. With `flush` this waits until the chunks are written
+    /// to disk, otherwise they are written in the background, and no other save runs until
+    /// they are.
+    pub async fn save_all(&self, flush: bool) {
+        let saving = self.player_data_storage.lock_saves().await;
+        let mut chunks = 0;
+        let mut writes = Vec::new();
+        for world in self.worlds.read().await.iter() {
+            let (count, write) = world.save().await;
+            chunks += count;
+            writes.push(write);
+        }
+        let players = self
+            .player_data_storage
+            .save_all_players(self)
+            .await
+            .unwrap_or_else(|err| {
+                log::error!("Failed to save players: {err}");
+                0
+            });
+        log::debug!("Saving {chunks} chunks and {players} players");
+
+        let write = async move {
+            join_all(writes).await;
+            drop(saving);
+        };
+        if flush {
+            write.await;
+        } else {
+            self.spawn_task(write);
+        }
+    }
+
+    /// Turns saving on or off, like `/save-on` and `/save-off`, returning whether it changed.
+    /// While it is off, neither the periodic save nor unloading chunks writes anything.
+    pub async fn set_autosave(&self, enabled: bool) -> bool {
+        let changed = self.player_data_storage.set_autosave(enabled);
+        for world in self.worlds.read().await.iter() {
+            world.level.set_unload_saving(enabled).await;
+        }
+        changed
+    }
+
     pub async fn try_get_container(
         &self,
         player_id: EntityId,
@@ -337,12 +564,17 @@ impl Server {
             log::error!("Failed to serialize packet {}: {}", P::PACKET_ID, err);
             return;
         }
+        #[cfg(feature = "protocol_strict")]
+        crate::net::check_outgoing_packet(packet, &packet_buf);
         let packet_data: Bytes = packet_buf.into();
 
         for world in self.worlds.read().await.iter() {
             let current_players = world.players.read().await;
             for player in current_players.values() {
-                player.client.enqueue_packet_data(packet_data.clone()).await;
+                player
+                    .client
+                    .enqueue_packet_data(packet_data.clone(), P::COMPRESSION)
+                    .await;
             }
         }
     }
@@ -443,6 +675,19 @@ impl Server {
         None
     }
 
+    /// Searches for an entity of any kind, including players, by its UUID across all worlds.
+    pub async fn get_entity_by_uuid(&self, id: uuid::Uuid) -> Option<Arc<dyn EntityBase>> {
+        if let Some(player) = self.get_player_by_uuid(id).await {
+            return Some(player);
+        }
+        for world in self.worlds.read().await.iter() {
+            if let Some(entity) = world.entities.read().await.get(&id) {
+                return Some(entity.clone());
+            }
+        }
+        None
+    }
+
     /// Counts the total number of players across all worlds.
     ///
     /// This function iterates through each world and sums up the number of players currently connected to that world.
@@ -483,6 +728,14 @@ impl Server {
         &self.listing
     }
 
+    /// Changes the MOTD of the server list and the pause menus of players who are playing.
+    pub async fn set_motd(&self, motd: String) {
+        self.listing.lock().await.set_motd(motd);
+        for player in self.get_all_players().await {
+            player.send_server_data(self).await;
+        }
+    }
+
     pub fn encryption_request<'a>(
         &'a self,
         verification_token: &'a [u8; 4],
@@ -500,13 +753,146 @@ impl Server {
         self.key_store.get_digest(secret)
     }
 
+    /// Returns the average tick duration over the last 100 ticks in nanoseconds.
+    pub fn get_average_tick_time_nanos(&self) -> u64 {
+        let ticks = self.tick_count.load(Ordering::Relaxed).min(TICK_TIMES_LEN);
+        if ticks == 0 {
+            return 0;
+        }
+        let tick_times = *self.tick_times_nanos.lock().unwrap();
+        tick_times.iter().sum::<u64>() / ticks as u64
+    }
+
+    /// Returns the ticks per second the server managed over the last 100 ticks, capped at the target tick rate.
+    pub fn get_tps(&self) -> f64 {
+        let mspt = self.get_average_tick_time_nanos() as f64 / 1_000_000.0;
+        let target = f64::from(self.tick_rate_manager.lock().unwrap().tick_rate());
+        if mspt == 0.0 {
+            return target;
+        }
+        (1000.0 / mspt).min(target)
+    }
+
+    /// Tells clients how fast the game ticks and whether it is frozen or being stepped.
+    pub async fn send_tick_state(&self) {
+        for player in self.get_all_players().await {
+            self.send_tick_state_to(&player.client).await;
+        }
+    }
+
+    pub async fn send_tick_state_to(&self, client: &Client) {
+        let (tick_rate, frozen, steps) = {
+            let manager = self.tick_rate_manager.lock().unwrap();
+            (
+                manager.tick_rate(),
+                manager.is_frozen(),
+                manager.frozen_steps(),
+            )
+        };
+        client
+            .enqueue_packet(&CTickingState::new(tick_rate, frozen))
+            .await;
+        client
+            .enqueue_packet(&CTickingStep::new(VarInt(steps as i32)))
+            .await;
+    }
+
     async fn tick(&self) {
+        let start = Instant::now();
+        self.tick_rate_manager.lock().unwrap().start_tick();
+
         for world in self.worlds.read().await.iter() {
             world.tick(self).await;
         }
 
+        self.scheduler.tick(self).await;
+
         if let Err(e) = self.player_data_storage.tick(self).await {
             log::error!("Error ticking player data: {e}");
         }
+
+        let tick = self.tick_count.fetch_add(1, Ordering::Relaxed);
+        self.tick_times_nanos.lock().unwrap()[tick % TICK_TIMES_LEN] =
+            start.elapsed().as_nanos() as u64;
+
+        let sprint_report = self.tick_rate_manager.lock().unwrap().end_tick();
+        if let Some(report) = sprint_report {
+            self.broadcast_sprint_report(&report).await;
+            self.send_tick_state().await;
+        }
+    }
+
+    /// Tells operators how fast a finished `/tick sprint` ran.
+    pub async fn broadcast_sprint_report(&self, report: &SprintReport) {
+        let message = TextComponent::translate(
+            "commands.tick.sprint.report",
+            [
+                TextComponent::text(format!("{:.0}", report.ticks_per_second)),
+                TextComponent::text(format!("{:.2}", report.millis_per_tick)),
+            ],
+        );
+        log::info!("{}", message.clone().to_pretty_console());
+        for player in self.get_all_players().await {
+            if player.permission_lvl.load() >= PermissionLvl::Two {
+                player.send_system_message(&message).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, atomic::Ordering};
+
+    use pumpkin_data::packet::clientbound::{PLAY_DISCONNECT, PLAY_SERVER_DATA};
+    use pumpkin_protocol::{ClientPacket, client::play::CPlayDisconnect};
+    use pumpkin_util::text::TextComponent;
+
+    use crate::test_utils::{
+        connected_test_player, loopback_client, read_sent_packet, test_player, test_server,
+    };
+
+    #[tokio::test]
+    async fn duplicate_login_replaces_the_online_player() {
+        let server = test_server();
+        let (online, _packets, mut remote) = connected_test_player(&server, "alice").await;
+
+        let (client, _second_remote) = loopback_client().await;
+        *client.gameprofile.lock().await = Some(online.gameprofile.clone());
+        let (player, _world) = server.add_player(client).await.unwrap();
+
+        let reason = TextComponent::translate("multiplayer.disconnect.duplicate_login", []);
+        let mut expected = Vec::new();
+        CPlayDisconnect::new(&reason).write(&mut expected).unwrap();
+        assert_eq!(
+            read_sent_packet(&mut remote, PLAY_DISCONNECT).await,
+            expected
+        );
+        assert!(online.client.closed.load(Ordering::Relaxed));
+
+        let current = server
+            .get_player_by_uuid(online.gameprofile.id)
+            .await
+            .unwrap();
+        assert!(Arc::ptr_eq(&current, &player));
+        assert_eq!(server.get_all_players().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn new_motd_is_sent_to_playing_players() {
+        let server = test_server();
+        let (_alice, mut packets) = test_player(&server, "alice").await;
+
+        server.set_motd("Back after the update!".to_string()).await;
+        let status = server.get_status().lock().await;
+        assert_eq!(
+            status.status_response().description,
+            "Back after the update!"
+        );
+        let mut expected = Vec::new();
+        status.server_data().write(&mut expected).unwrap();
+        let (packet, _) = packets.try_recv().unwrap();
+        assert_eq!(i32::from(packet[0]), PLAY_SERVER_DATA);
+        assert_eq!(&packet[..], &expected[..]);
     }
 }
@@ -0,0 +1,165 @@
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+use async_trait::async_trait;
+
+use super::Server;
+
+/// A unit of work which is run by the [`Scheduler`] on the server tick.
+#[async_trait]
+pub trait ScheduledTask: Send + Sync {
+    async fn run(&self, server: &Server);
+}
+
+/// A handle to a scheduled task, which can be used to cancel it.
+#[derive(Clone)]
+pub struct TaskHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl TaskHandle {
+    /// Cancels the task. A repeating task won't run again, a delayed task won't run at all.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+struct Entry {
+    next_run: u64,
+    period: Option<u64>,
+    task: Arc<dyn ScheduledTask>,
+    handle: TaskHandle,
+}
+
+/// Runs delayed and repeating tasks on the main tick loop.
+///
+/// Unlike spawning a tokio task, scheduled tasks run in sync with the server tick, so they can
+/// safely interact with worlds and players.
+#[derive(Default)]
+pub struct Scheduler {
+    current_tick: AtomicU64,
+    entries: Mutex<Vec<Entry>>,
+}
+
+impl Scheduler {
+    /// Runs the task once after the given amount of ticks. A delay of 0 runs it on the next tick.
+    pub fn run_later(&self, delay_ticks: u64, task: impl ScheduledTask + 'static) -> TaskHandle {
+        self.schedule(delay_ticks, None, Arc::new(task))
+    }
+
+    /// Runs the task every `period_ticks` ticks, starting `period_ticks` ticks from now.
+    pub fn run_repeating(
+        &self,
+        period_ticks: u64,
+        task: impl ScheduledTask + 'static,
+    ) -> TaskHandle {
+        let period_ticks = period_ticks.max(1);
+        self.schedule(period_ticks, Some(period_ticks), Arc::new(task))
+    }
+
+    fn schedule(
+        &self,
+        delay_ticks: u64,
+        period: Option<u64>,
+        task: Arc<dyn ScheduledTask>,
+    ) -> TaskHandle {
+        let handle = TaskHandle {
+            cancelled: Arc::new(AtomicBool::new(false)),
+        };
+        let next_run = self.current_tick.load(Ordering::Relaxed) + delay_ticks.max(1);
+        self.entries.lock().unwrap().push(Entry {
+            next_run,
+            period,
+            task,
+            handle: handle.clone(),
+        });
+        handle
+    }
+
+    /// Returns the amount of pending tasks.
+    pub fn pending_tasks(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    /// Advances the scheduler by one tick, returning the tasks that are due.
+    /// Repeating tasks are rescheduled, cancelled and finished tasks are dropped.
+    fn advance(&self) -> Vec<Arc<dyn ScheduledTask>> {
+        let tick = self.current_tick.fetch_add(1, Ordering::Relaxed) + 1;
+        let mut due = Vec::new();
+        self.entries.lock().unwrap().retain_mut(|entry| {
+            if entry.handle.is_cancelled() {
+                return false;
+            }
+            if entry.next_run > tick {
+                return true;
+            }
+            due.push(entry.task.clone());
+            match entry.period {
+                Some(period) => {
+                    entry.next_run = tick + period;
+                    true
+                }
+                None => false,
+            }
+        });
+        due
+    }
+
+    pub(crate) async fn tick(&self, server: &Server) {
+        // The lock is released before running, so tasks are free to schedule new tasks
+        for task in self.advance() {
+            task.run(server).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use async_trait::async_trait;
+
+    use super::{ScheduledTask, Scheduler};
+    use crate::server::Server;
+
+    struct Noop;
+
+    #[async_trait]
+    impl ScheduledTask for Noop {
+        async fn run(&self, _server: &Server) {}
+    }
+
+    #[test]
+    fn run_later_fires_once_after_delay() {
+        let scheduler = Scheduler::default();
+        scheduler.run_later(5, Noop);
+
+        for _ in 1..5 {
+            assert!(scheduler.advance().is_empty());
+        }
+        assert_eq!(scheduler.advance().len(), 1);
+        assert!(scheduler.advance().is_empty());
+        assert_eq!(scheduler.pending_tasks(), 0);
+    }
+
+    #[test]
+    fn run_repeating_fires_every_period() {
+        let scheduler = Scheduler::default();
+        let handle = scheduler.run_repeating(3, Noop);
+
+        let fired: Vec<u64> = (1..=9)
+            .filter(|_| !scheduler.advance().is_empty())
+            .collect();
+        assert_eq!(fired, vec![3, 6, 9]);
+
+        handle.cancel();
+        for _ in 0..3 {
+            assert!(scheduler.advance().is_empty());
+        }
+        assert_eq!(scheduler.pending_tasks(), 0);
+    }
+}
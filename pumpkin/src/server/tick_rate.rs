@@ -0,0 +1,214 @@
+use std::time::{Duration, Instant};
+
+/// The slowest tick rate `/tick rate` accepts.
+pub const MIN_TICK_RATE: f32 = 1.0;
+/// The fastest tick rate `/tick rate` accepts.
+pub const MAX_TICK_RATE: f32 = 10_000.0;
+
+/// How fast the game ticks, and whether it is frozen, stepped or sprinting, like `/tick` in
+/// vanilla. Players and the network keep going while the game is frozen.
+pub struct TickRateManager {
+    tick_rate: f32,
+    frozen: bool,
+    /// Ticks left to run while frozen, see [`Self::step`]
+    frozen_steps: u32,
+    /// Whether worlds and entities tick this tick
+    runs_normally: bool,
+    sprint: Option<Sprint>,
+}
+
+struct Sprint {
+    ticks_left: u64,
+    ticks_run: u64,
+    start: Instant,
+    /// The game is unfrozen while sprinting and frozen again afterwards
+    was_frozen: bool,
+}
+
+/// How fast a finished sprint ran.
+pub struct SprintReport {
+    pub ticks_per_second: f64,
+    pub millis_per_tick: f64,
+}
+
+impl TickRateManager {
+    #[must_use]
+    pub fn new(tick_rate: f32) -> Self {
+        Self {
+            tick_rate,
+            frozen: false,
+            frozen_steps: 0,
+            runs_normally: true,
+            sprint: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn tick_rate(&self) -> f32 {
+        self.tick_rate
+    }
+
+    pub fn set_tick_rate(&mut self, tick_rate: f32) {
+        self.tick_rate = tick_rate.clamp(MIN_TICK_RATE, MAX_TICK_RATE);
+    }
+
+    /// How long a tick should take. Sprints don't wait between ticks.
+    #[must_use]
+    pub fn tick_interval(&self) -> Duration {
+        if self.sprint.is_some() {
+            Duration::ZERO
+        } else {
+            Duration::from_nanos((1_000_000_000.0 / f64::from(self.tick_rate)) as u64)
+        }
+    }
+
+    #[must_use]
+    pub const fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    pub fn set_frozen(&mut self, frozen: bool) {
+        self.frozen = frozen;
+        if !frozen {
+            self.frozen_steps = 0;
+        }
+    }
+
+    /// Ticks left to step, `0` unless the game is frozen and being stepped.
+    #[must_use]
+    pub const fn frozen_steps(&self) -> u32 {
+        self.frozen_steps
+    }
+
+    /// Runs `ticks` more ticks of a frozen game. `false` if the game isn't frozen.
+    pub fn step(&mut self, ticks: u32) -> bool {
+        if !self.frozen {
+            return false;
+        }
+        self.frozen_steps = ticks;
+        true
+    }
+
+    /// `false` if the game wasn't being stepped.
+    pub fn stop_stepping(&mut self) -> bool {
+        if self.frozen_steps == 0 {
+            return false;
+        }
+        self.frozen_steps = 0;
+        true
+    }
+
+    #[must_use]
+    pub const fn is_sprinting(&self) -> bool {
+        self.sprint.is_some()
+    }
+
+    /// Runs the next `ticks` ticks as fast as possible, even if the game is frozen. Returns
+    /// whether a running sprint was replaced.
+    pub fn sprint(&mut self, ticks: u64) -> bool {
+        let replaced = self.sprint.take();
+        let was_frozen = replaced
+            .as_ref()
+            .map_or(self.frozen, |sprint| sprint.was_frozen);
+        self.frozen = false;
+        self.sprint = Some(Sprint {
+            ticks_left: ticks,
+            ticks_run: 0,
+            start: Instant::now(),
+            was_frozen,
+        });
+        replaced.is_some()
+    }
+
+    /// Ends the running sprint, `None` if there is none.
+    pub fn stop_sprinting(&mut self) -> Option<SprintReport> {
+        let sprint = self.sprint.take()?;
+        self.frozen = sprint.was_frozen;
+        let millis = sprint.start.elapsed().as_secs_f64() * 1000.0;
+        let ticks = sprint.ticks_run.max(1) as f64;
+        let millis_per_tick = millis / ticks;
+        Some(SprintReport {
+            ticks_per_second: 1000.0 / millis_per_tick.max(f64::MIN_POSITIVE),
+            millis_per_tick,
+        })
+    }
+
+    /// Starts a tick, returning whether worlds and entities tick in it.
+    pub fn start_tick(&mut self) -> bool {
+        self.runs_normally = !self.frozen || self.frozen_steps > 0;
+        self.frozen_steps = self.frozen_steps.saturating_sub(1);
+        self.runs_normally
+    }
+
+    /// Whether worlds and entities tick in the current tick.
+    #[must_use]
+    pub const fn runs_normally(&self) -> bool {
+        self.runs_normally
+    }
+
+    /// Ends a tick, returning the report of a sprint which just finished.
+    pub fn end_tick(&mut self) -> Option<SprintReport> {
+        let sprint = self.sprint.as_mut()?;
+        sprint.ticks_run += 1;
+        sprint.ticks_left = sprint.ticks_left.saturating_sub(1);
+        if sprint.ticks_left > 0 {
+            return None;
+        }
+        self.stop_sprinting()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::world::time::LevelTime;
+
+    use super::TickRateManager;
+
+    fn run_ticks(manager: &mut TickRateManager, time: &mut LevelTime, ticks: usize) {
+        for _ in 0..ticks {
+            if manager.start_tick() {
+                time.tick_time();
+            }
+            manager.end_tick();
+        }
+    }
+
+    #[test]
+    fn frozen_game_only_advances_when_stepped() {
+        let mut manager = TickRateManager::new(20.0);
+        let mut time = LevelTime::new();
+        run_ticks(&mut manager, &mut time, 10);
+        assert_eq!(time.world_age, 10);
+
+        manager.set_frozen(true);
+        run_ticks(&mut manager, &mut time, 10);
+        assert_eq!(time.world_age, 10);
+
+        assert!(manager.step(5));
+        run_ticks(&mut manager, &mut time, 10);
+        assert_eq!(time.world_age, 15);
+        assert_eq!(manager.frozen_steps(), 0);
+
+        manager.set_frozen(false);
+        assert!(!manager.step(5));
+        run_ticks(&mut manager, &mut time, 10);
+        assert_eq!(time.world_age, 25);
+    }
+
+    #[test]
+    fn sprint_runs_without_waiting_and_refreezes() {
+        let mut manager = TickRateManager::new(20.0);
+        assert_eq!(manager.tick_interval(), Duration::from_millis(50));
+        manager.set_frozen(true);
+
+        assert!(!manager.sprint(3));
+        assert_eq!(manager.tick_interval(), Duration::ZERO);
+        let mut time = LevelTime::new();
+        run_ticks(&mut manager, &mut time, 3);
+        assert_eq!(time.world_age, 3);
+        assert!(!manager.is_sprinting());
+        assert!(manager.is_frozen());
+    }
+}
@@ -1,4 +1,4 @@
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 use tokio::time::sleep;
 
@@ -7,15 +7,19 @@ use crate::SHOULD_STOP;
 use super::Server;
 
 pub struct Ticker {
-    tick_interval: Duration,
     last_tick: Instant,
 }
 
+impl Default for Ticker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Ticker {
     #[must_use]
-    pub fn new(tps: f32) -> Self {
+    pub fn new() -> Self {
         Self {
-            tick_interval: Duration::from_millis((1000.0 / tps) as u64),
             last_tick: Instant::now(),
         }
     }
@@ -25,13 +29,15 @@ impl Ticker {
         while !SHOULD_STOP.load(std::sync::atomic::Ordering::Relaxed) {
             let now = Instant::now();
             let elapsed = now - self.last_tick;
+            // `/tick` can change the rate at any time
+            let tick_interval = server.tick_rate_manager.lock().unwrap().tick_interval();
 
-            if elapsed >= self.tick_interval {
+            if elapsed >= tick_interval {
                 server.tick().await;
                 self.last_tick = now;
             } else {
                 // Wait for the remaining time until the next tick.
-                let sleep_time = self.tick_interval - elapsed;
+                let sleep_time = tick_interval - elapsed;
                 sleep(sleep_time).await;
             }
         }
@@ -0,0 +1,126 @@
+//! Helpers for tests which need a running server, e.g. to run commands through the dispatcher.
+
+use std::{sync::Arc, time::Duration};
+
+use bytes::Bytes;
+use pumpkin_protocol::{CompressionPolicy, codec::var_int::VarInt};
+use pumpkin_registry::{DimensionType, DimensionTypes};
+use pumpkin_util::GameMode;
+use pumpkin_world::{advancement::Advancements, level::Level, world_info::LevelData};
+use tokio::{
+    io::AsyncReadExt,
+    net::{TcpListener, TcpStream},
+    sync::{Mutex, mpsc::Receiver},
+};
+
+use crate::{
+    command::CommandSender,
+    data::player_server_data::ServerPlayerData,
+    entity::player::Player,
+    net::{Client, GameProfile},
+    server::Server,
+    world::World,
+};
+
+/// A server with just an in-memory overworld, so tests neither touch the world on disk nor lock
+/// each other out of it.
+pub fn test_server() -> Arc<Server> {
+    let dimension_types = DimensionTypes::default();
+    let block_registry = crate::block::default_registry();
+    let world = World::load(
+        Level::in_memory(LevelData::default()),
+        DimensionType::Overworld,
+        &dimension_types,
+        block_registry.clone(),
+    );
+    Arc::new(Server::with_worlds(
+        vec![Arc::new(world)],
+        vec![DimensionType::Overworld],
+        &dimension_types,
+        block_registry,
+        Advancements::vanilla(),
+        ServerPlayerData::in_memory(Duration::from_secs(60)),
+    ))
+}
+
+/// Both ends of a loopback connection, the accepted one first.
+pub async fn loopback_streams() -> (TcpStream, TcpStream) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let remote = TcpStream::connect(listener.local_addr().unwrap())
+        .await
+        .unwrap();
+    let (stream, _) = listener.accept().await.unwrap();
+    (stream, remote)
+}
+
+/// A client connected over loopback, with the other end of its connection.
+pub async fn loopback_client() -> (Client, TcpStream) {
+    let (stream, remote) = loopback_streams().await;
+    let address = stream.peer_addr().unwrap();
+    (Client::new(stream, address, 0), remote)
+}
+
+/// A player called `name` in the first world of the server, with the packets queued for its
+/// client. Nobody is told about the player joining.
+pub async fn test_player(
+    server: &Server,
+    name: &str,
+) -> (Arc<Player>, Receiver<(Bytes, CompressionPolicy)>) {
+    let (player, outgoing, _remote) = connected_test_player(server, name).await;
+    (player, outgoing)
+}
+
+/// Like [`test_player`], with the other end of the player's connection, which receives the
+/// packets sent right away instead of being queued.
+pub async fn connected_test_player(
+    server: &Server,
+    name: &str,
+) -> (Arc<Player>, Receiver<(Bytes, CompressionPolicy)>, TcpStream) {
+    let (mut client, remote) = loopback_client().await;
+    let outgoing = client.take_outgoing_packets();
+    *client.gameprofile.lock().await = Some(GameProfile {
+        id: uuid::Uuid::new_v4(),
+        name: name.to_string(),
+        properties: Vec::new(),
+        profile_actions: None,
+    });
+
+    let world = server.worlds.read().await[0].clone();
+    let player = Arc::new(Player::new(client, world.clone(), GameMode::Survival).await);
+    world
+        .players
+        .write()
+        .await
+        .insert(player.gameprofile.id, player.clone());
+    (player, outgoing, remote)
+}
+
+/// Reads the packets sent right away to `remote` until one with the id `packet_id` arrives, and
+/// returns it without its length.
+pub async fn read_sent_packet(remote: &mut TcpStream, packet_id: i32) -> Vec<u8> {
+    loop {
+        let length = VarInt::decode_async(remote).await.unwrap();
+        let mut packet = vec![0; length.0 as usize];
+        remote.read_exact(&mut packet).await.unwrap();
+        if i32::from(packet[0]) == packet_id {
+            return packet;
+        }
+    }
+}
+
+/// Runs `command` through the dispatcher of `server` as `sender`.
+pub async fn run_command(server: &Server, sender: &mut CommandSender, command: &str) {
+    server
+        .command_dispatcher
+        .read()
+        .await
+        .handle_command(sender, server, command)
+        .await;
+}
+
+/// Runs `command` as the console, like over RCON, and returns what the console was told.
+pub async fn run_console_command(server: &Server, command: &str) -> Vec<String> {
+    let output = Arc::new(Mutex::new(Vec::new()));
+    run_command(server, &mut CommandSender::Rcon(output.clone()), command).await;
+    std::mem::take(&mut *output.lock().await)
+}
@@ -1,7 +1,10 @@
+use std::time::Instant;
+
 use pumpkin_protocol::client::play::{
     CInitializeWorldBorder, CSetBorderCenter, CSetBorderLerpSize, CSetBorderSize,
     CSetBorderWarningDelay, CSetBorderWarningDistance,
 };
+use pumpkin_world::world_info::LevelData;
 
 use crate::net::Client;
 
@@ -18,6 +21,8 @@ pub struct Worldborder {
     pub warning_time: i32,
     pub damage_per_block: f32,
     pub buffer: f32,
+    /// When the border started moving from `old_diameter` to `new_diameter`.
+    lerp_start: Instant,
 }
 
 impl Worldborder {
@@ -41,17 +46,79 @@ impl Worldborder {
             warning_time,
             damage_per_block: 0.0,
             buffer: 0.0,
+            lerp_start: Instant::now(),
+        }
+    }
+
+    /// The border saved in `level.dat`.
+    #[must_use]
+    pub fn from_level_data(info: &LevelData) -> Self {
+        let mut border = Self::new(
+            info.border_center_x,
+            info.border_center_z,
+            info.border_size,
+            0,
+            info.border_warning_blocks as i32,
+            info.border_warning_time as i32,
+        );
+        if info.border_size_lerp_time > 0 {
+            border.new_diameter = info.border_size_lerp_target;
+            border.speed = info.border_size_lerp_time;
         }
+        border.damage_per_block = info.border_damage_per_block as f32;
+        border.buffer = info.border_safe_zone as f32;
+        border
     }
 
+    /// Stores the border in `level.dat`. A border which is still moving is saved where it is
+    /// now, and keeps moving once it is loaded again.
+    pub fn write_level_data(&self, info: &mut LevelData) {
+        let (diameter, remaining) = self.lerp_state(self.lerp_elapsed());
+        info.border_center_x = self.center_x;
+        info.border_center_z = self.center_z;
+        info.border_size = diameter;
+        info.border_size_lerp_target = self.new_diameter;
+        info.border_size_lerp_time = remaining;
+        info.border_warning_blocks = f64::from(self.warning_blocks);
+        info.border_warning_time = f64::from(self.warning_time);
+        info.border_damage_per_block = f64::from(self.damage_per_block);
+        info.border_safe_zone = f64::from(self.buffer);
+    }
+
+    /// The diameter and the milliseconds left until the border reaches `new_diameter`,
+    /// `elapsed` milliseconds after it started moving.
+    fn lerp_state(&self, elapsed: i64) -> (f64, i64) {
+        let remaining = self.speed - elapsed;
+        if remaining <= 0 {
+            return (self.new_diameter, 0);
+        }
+        let progress = elapsed as f64 / self.speed as f64;
+        let diameter = self.old_diameter + (self.new_diameter - self.old_diameter) * progress;
+        (diameter, remaining)
+    }
+
+    fn lerp_elapsed(&self) -> i64 {
+        self.lerp_start.elapsed().as_millis() as i64
+    }
+
+    /// The current diameter, somewhere between `old_diameter` and `new_diameter` while the
+    /// border is moving.
+    #[must_use]
+    pub fn diameter(&self) -> f64 {
+        self.lerp_state(self.lerp_elapsed()).0
+    }
+
+    /// Sends the whole border to a client, e.g. when it joins or switches to this world. A moving
+    /// border is sent where it is now, so the client doesn't replay the whole movement.
     pub async fn init_client(&self, client: &Client) {
+        let (diameter, remaining) = self.lerp_state(self.lerp_elapsed());
         client
             .enqueue_packet(&CInitializeWorldBorder::new(
                 self.center_x,
                 self.center_z,
-                self.old_diameter,
+                diameter,
                 self.new_diameter,
-                self.speed.into(),
+                remaining.into(),
                 self.portal_teleport_boundary.into(),
                 self.warning_blocks.into(),
                 self.warning_time.into(),
@@ -59,18 +126,55 @@ impl Worldborder {
             .await;
     }
 
-    pub async fn set_center(&mut self, world: &World, x: f64, z: f64) {
+    /// How far `x`, `z` is inside of the border, negative when it is outside.
+    #[must_use]
+    pub fn distance_inside(&self, x: f64, z: f64) -> f64 {
+        let radius = self.diameter() / 2.0;
+        radius - (x - self.center_x).abs().max((z - self.center_z).abs())
+    }
+
+    /// The damage a player at `x`, `z` takes, if they are outside of the border and its buffer.
+    #[must_use]
+    pub fn damage_at(&self, x: f64, z: f64) -> Option<f32> {
+        let beyond_buffer = -(self.distance_inside(x, z) + f64::from(self.buffer));
+        if beyond_buffer <= 0.0 || self.damage_per_block <= 0.0 {
+            return None;
+        }
+        Some(
+            (beyond_buffer * f64::from(self.damage_per_block))
+                .floor()
+                .max(1.0) as f32,
+        )
+    }
+
+    /// Moves the center of the border, returning the packet to tell clients about it, or `None`
+    /// if the border is centered there already.
+    fn move_center(&mut self, x: f64, z: f64) -> Option<CSetBorderCenter> {
+        if self.center_x == x && self.center_z == z {
+            return None;
+        }
         self.center_x = x;
         self.center_z = z;
+        Some(CSetBorderCenter::new(x, z))
+    }
 
-        world
-            .broadcast_packet_all(&CSetBorderCenter::new(self.center_x, self.center_z))
-            .await;
+    /// Centers the border at `x`, `z`. Returns `false` if it is centered there already.
+    pub async fn set_center(&mut self, world: &World, x: f64, z: f64) -> bool {
+        let Some(packet) = self.move_center(x, z) else {
+            return false;
+        };
+        world.broadcast_packet_all(&packet).await;
+        true
     }
 
     pub async fn set_diameter(&mut self, world: &World, diameter: f64, speed: Option<i64>) {
-        self.old_diameter = self.new_diameter;
+        self.old_diameter = match speed {
+            Some(_) => self.diameter(),
+            None => diameter,
+        };
         self.new_diameter = diameter;
+        self.speed = speed.unwrap_or(0);
+        self.lerp_start = Instant::now();
 
         match speed {
             Some(speed) => {
@@ -91,7 +195,7 @@ impl Worldborder {
     }
 
     pub async fn add_diameter(&mut self, world: &World, offset: f64, speed: Option<i64>) {
-        self.set_diameter(world, self.new_diameter + offset, speed)
+        self.set_diameter(world, self.diameter() + offset, speed)
             .await;
     }
 
@@ -111,3 +215,104 @@ impl Worldborder {
             .await;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_data::packet::clientbound::PLAY_INITIALIZE_BORDER;
+    use pumpkin_protocol::{ClientPacket, client::play::CInitializeWorldBorder};
+    use pumpkin_world::world_info::LevelData;
+
+    use super::Worldborder;
+    use crate::test_utils::loopback_client;
+
+    #[test]
+    fn moving_the_center_moves_the_damage_zone() {
+        let mut border = Worldborder::new(0.0, 0.0, 100.0, 0, 5, 15);
+        border.damage_per_block = 0.2;
+        border.buffer = 5.0;
+        assert_eq!(border.damage_at(40.0, 0.0), None);
+        assert_eq!(border.damage_at(-70.0, 10.0), Some(3.0));
+
+        let packet = border.move_center(-60.0, 0.0).expect("the center moved");
+        let mut buf = Vec::new();
+        packet.write_packet_data(&mut buf).unwrap();
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&(-60.0_f64).to_be_bytes());
+        expected.extend_from_slice(&0.0_f64.to_be_bytes());
+        assert_eq!(buf, expected);
+        assert!(border.move_center(-60.0, 0.0).is_none());
+
+        // What was inside is outside now, and the other way around
+        assert_eq!(border.distance_inside(40.0, 0.0), -50.0);
+        assert_eq!(border.damage_at(40.0, 0.0), Some(9.0));
+        assert_eq!(border.damage_at(-70.0, 10.0), None);
+        // Just outside, but still within the buffer
+        assert_eq!(border.damage_at(-112.0, 0.0), None);
+        // Less than a point of damage still hurts
+        assert_eq!(border.damage_at(-116.0, 0.0), Some(1.0));
+    }
+
+    #[test]
+    fn moving_border_is_sent_where_it_is_now() {
+        let mut border = Worldborder::new(0.0, 0.0, 100.0, 0, 5, 15);
+        border.new_diameter = 50.0;
+        border.speed = 10_000;
+
+        assert_eq!(border.lerp_state(0), (100.0, 10_000));
+        assert_eq!(border.lerp_state(5_000), (75.0, 5_000));
+        assert_eq!(border.lerp_state(20_000), (50.0, 0));
+    }
+
+    #[tokio::test]
+    async fn each_world_initializes_clients_with_its_own_border() {
+        let overworld = LevelData {
+            border_center_x: 10.0,
+            border_size: 200.0,
+            ..Default::default()
+        };
+        let nether = LevelData {
+            border_center_x: -40.0,
+            border_size: 300.0,
+            border_size_lerp_target: 100.0,
+            border_size_lerp_time: 5_000,
+            ..Default::default()
+        };
+        let overworld = Worldborder::from_level_data(&overworld);
+        let nether = Worldborder::from_level_data(&nether);
+        assert_eq!(overworld.lerp_state(0), (200.0, 0));
+        assert_eq!(nether.lerp_state(0), (300.0, 5_000));
+
+        let (mut client, _remote) = loopback_client().await;
+        let mut outgoing = client.take_outgoing_packets();
+        overworld.init_client(&client).await;
+        nether.init_client(&client).await;
+
+        let mut expected = Vec::new();
+        CInitializeWorldBorder::new(
+            10.0,
+            0.0,
+            200.0,
+            200.0,
+            0_i64.into(),
+            overworld.portal_teleport_boundary.into(),
+            overworld.warning_blocks.into(),
+            overworld.warning_time.into(),
+        )
+        .write(&mut expected)
+        .unwrap();
+        let (packet, _) = outgoing.try_recv().unwrap();
+        assert_eq!(i32::from(packet[0]), PLAY_INITIALIZE_BORDER);
+        assert_eq!(&packet[..], &expected[..]);
+        // The nether border is sent on its way to its own target
+        let (packet, _) = outgoing.try_recv().unwrap();
+        assert_eq!(i32::from(packet[0]), PLAY_INITIALIZE_BORDER);
+        assert_eq!(&packet[1..9], &(-40.0_f64).to_be_bytes());
+        assert_eq!(&packet[25..33], &100.0_f64.to_be_bytes());
+
+        // The moving border keeps moving after it is saved and loaded again
+        let mut saved = LevelData::default();
+        nether.write_level_data(&mut saved);
+        assert_eq!(saved.border_size_lerp_target, 100.0);
+        assert!(saved.border_size_lerp_time > 0);
+    }
+}
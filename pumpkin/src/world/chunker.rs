@@ -2,6 +2,7 @@ use std::{num::NonZeroU8, sync::Arc};
 
 use pumpkin_config::BASIC_CONFIG;
 use pumpkin_protocol::client::play::{CCenterChunk, CUnloadChunk};
+use pumpkin_util::math::vector2::Vector2;
 use pumpkin_world::cylindrical_chunk_iterator::Cylindrical;
 
 use crate::entity::player::Player;
@@ -15,6 +16,28 @@ pub async fn get_view_distance(player: &Player) -> NonZeroU8 {
         .clamp(NonZeroU8::new(2).unwrap(), BASIC_CONFIG.view_distance)
 }
 
+/// Entities and blocks are only simulated as far as the player can see, capped by the server.
+pub fn effective_simulation_distance(server: NonZeroU8, view_distance: NonZeroU8) -> NonZeroU8 {
+    server.min(view_distance)
+}
+
+pub async fn get_simulation_distance(player: &Player) -> NonZeroU8 {
+    effective_simulation_distance(
+        BASIC_CONFIG.simulation_distance,
+        get_view_distance(player).await,
+    )
+}
+
+/// Whether the chunk is close enough to the center to be simulated.
+pub fn is_within_simulation_distance(
+    center: Vector2<i32>,
+    simulation_distance: NonZeroU8,
+    chunk: Vector2<i32>,
+) -> bool {
+    let distance = i32::from(simulation_distance.get());
+    (chunk.x - center.x).abs() <= distance && (chunk.z - center.z).abs() <= distance
+}
+
 pub async fn player_join(player: &Arc<Player>) {
     let chunk_pos = player.living_entity.entity.chunk_pos.load();
 
@@ -102,3 +125,33 @@ pub async fn update_position(player: &Arc<Player>) {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::num::NonZeroU8;
+
+    use pumpkin_util::math::vector2::Vector2;
+
+    use super::{effective_simulation_distance, is_within_simulation_distance};
+
+    #[test]
+    fn simulation_distance_is_capped_by_view_distance() {
+        let server = NonZeroU8::new(10).unwrap();
+        let short = NonZeroU8::new(4).unwrap();
+        let far = NonZeroU8::new(16).unwrap();
+        assert_eq!(effective_simulation_distance(server, short), short);
+        assert_eq!(effective_simulation_distance(server, far), server);
+
+        let center = Vector2::new(3, -2);
+        assert!(is_within_simulation_distance(
+            center,
+            short,
+            Vector2::new(7, -6)
+        ));
+        assert!(!is_within_simulation_distance(
+            center,
+            short,
+            Vector2::new(8, -2)
+        ));
+    }
+}
@@ -1,9 +1,14 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use pumpkin_data::damage::DamageType;
 use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
 
-use crate::{block::drop_loot, server::Server};
+use crate::{
+    block::drop_loot,
+    entity::{EntityBase, player::Player},
+    server::Server,
+};
 
 use super::{BlockFlags, World};
 
@@ -68,9 +73,51 @@ impl Explosion {
         set.into_iter().collect()
     }
 
-    pub async fn explode(&self, server: &Server, world: &Arc<World>) {
+    /// Hurts the entities caught in the blast, returns the ones it killed.
+    async fn damage_entities(&self, world: &World) -> Vec<Arc<dyn EntityBase>> {
+        let mut killed = Vec::new();
+        let entities: Vec<_> = world.entities.read().await.values().cloned().collect();
+        for entity in entities {
+            let base = entity.get_entity();
+            let pos = base.pos.load();
+            let distance = pos.squared_distance_to_vec(self.pos).sqrt();
+            let Some(damage) = entity_damage(self.power, distance) else {
+                continue;
+            };
+            let Some(living) = entity.get_living_entity() else {
+                continue;
+            };
+            if living.health.load() <= 0.0 {
+                continue;
+            }
+            if let Some(push) = entity_knockback(self.power, self.pos, pos) {
+                base.set_velocity(base.velocity.load() + push).await;
+            }
+            if entity.damage(damage, DamageType::EXPLOSION).await && living.health.load() <= 0.0 {
+                killed.push(entity);
+            }
+        }
+        let players: Vec<Arc<Player>> = world.players.read().await.values().cloned().collect();
+        for player in players {
+            let pos = player.position();
+            let distance = pos.squared_distance_to_vec(self.pos).sqrt();
+            if let Some(damage) = entity_damage(self.power, distance) {
+                let base = &player.living_entity.entity;
+                if let Some(push) = entity_knockback(self.power, self.pos, pos) {
+                    base.set_velocity(base.velocity.load() + push).await;
+                }
+                player.damage(damage, DamageType::EXPLOSION).await;
+            }
+        }
+        killed
+    }
+
+    /// Destroys the blocks and hurts the entities around the explosion, returns the entities it
+    /// killed.
+    pub async fn explode(&self, server: &Server, world: &Arc<World>) -> Vec<Arc<dyn EntityBase>> {
+        let killed = self.damage_entities(world).await;
         let blocks = self.get_blocks_to_destroy(world).await;
-        // TODO: Entity damage, fire
+        // TODO: Blocks shielding entities, fire
         for pos in blocks {
             let block_state = world.get_block_state(&pos).await.unwrap();
 
@@ -81,14 +128,80 @@ impl Explosion {
             let block = world.get_block(&pos).await.unwrap();
             let pumpkin_block = server.block_registry.get_pumpkin_block(&block);
 
+            let block_entity = world.get_block_entity(&pos).await;
             world.set_block_state(&pos, 0, BlockFlags::NOTIFY_ALL).await;
 
             if pumpkin_block.is_none_or(|s| s.should_drop_items_on_explosion()) {
-                drop_loot(world, &block, &pos, false, block_state.id).await;
+                drop_loot(
+                    world,
+                    &block,
+                    &pos,
+                    false,
+                    block_state.id,
+                    block_entity,
+                    None,
+                )
+                .await;
             }
             if let Some(pumpkin_block) = pumpkin_block {
                 pumpkin_block.explode(&block, world, pos).await;
             }
         }
+        killed
+    }
+}
+
+/// The damage an entity `distance` blocks away from an explosion of `power` takes when nothing
+/// shields it, `None` if it is out of reach.
+pub fn entity_damage(power: f32, distance: f64) -> Option<f32> {
+    let reach = f64::from(power) * 2.0;
+    if distance >= reach {
+        return None;
+    }
+    let impact = 1.0 - distance / reach;
+    Some(((impact * impact + impact) / 2.0 * 7.0 * reach + 1.0) as f32)
+}
+
+/// How much an explosion of `power` at `center` pushes an entity at `pos` away, `None` if it is
+/// out of reach or right in the middle of it.
+pub fn entity_knockback(
+    power: f32,
+    center: Vector3<f64>,
+    pos: Vector3<f64>,
+) -> Option<Vector3<f64>> {
+    let reach = f64::from(power) * 2.0;
+    let offset = pos.sub(&center);
+    let distance = offset.length();
+    if distance >= reach || distance == 0.0 {
+        return None;
+    }
+    Some(offset.normalize() * (1.0 - distance / reach))
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_util::math::vector3::Vector3;
+
+    use super::{entity_damage, entity_knockback};
+
+    #[test]
+    fn closer_entities_take_more_damage() {
+        // A creeper blast right next to an entity
+        assert_eq!(entity_damage(3.0, 0.0), Some(43.0));
+        let near = entity_damage(3.0, 1.0).unwrap();
+        let far = entity_damage(3.0, 5.0).unwrap();
+        assert!(near > far && far > 1.0);
+        assert_eq!(entity_damage(3.0, 6.0), None);
+        // Charged creepers reach twice as far
+        assert!(entity_damage(6.0, 6.0).is_some());
+
+        // Entities fly away from the blast
+        let center = Vector3::new(0.0, 64.0, 0.0);
+        let push = entity_knockback(3.0, center, Vector3::new(3.0, 64.0, 0.0)).unwrap();
+        assert_eq!(push, Vector3::new(0.5, 0.0, 0.0));
+        assert_eq!(
+            entity_knockback(3.0, center, Vector3::new(0.0, 64.0, 6.0)),
+            None
+        );
     }
 }
@@ -0,0 +1,146 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use pumpkin_config::{FirstJoinConfig, KitItem};
+use pumpkin_data::item::Item;
+use pumpkin_util::text::TextComponent;
+use pumpkin_world::item::ItemStack;
+
+use crate::{command::CommandSender, entity::player::Player, server::Server};
+
+/// Marks the player as having played before, returns whether this is their first join.
+pub fn take_first_join(has_played_before: &AtomicBool) -> bool {
+    !has_played_before.swap(true, Ordering::Relaxed)
+}
+
+/// The stacks of the starter kit, split up into full stacks. Unknown items are skipped.
+pub fn kit_stacks(kit: &[KitItem]) -> Vec<ItemStack> {
+    let mut stacks = Vec::new();
+    for kit_item in kit {
+        let Some(item) = Item::from_registry_key(kit_item.item.trim_start_matches("minecraft:"))
+        else {
+            log::warn!(
+                "The first join kit contains {}, which is no item",
+                kit_item.item
+            );
+            continue;
+        };
+        let max_stack = u32::from(item.components.max_stack_size);
+        let mut count = kit_item.count;
+        while count > 0 {
+            let stack_count = count.min(max_stack);
+            stacks.push(ItemStack::new(stack_count as u8, item.clone()));
+            count -= stack_count;
+        }
+    }
+    stacks
+}
+
+fn fill_placeholders(text: &str, player: &Player) -> String {
+    text.replace("{player}", &player.gameprofile.name)
+}
+
+/// Welcomes a player joining for the first time: hands out the starter kit, runs the commands
+/// and greets them.
+pub async fn welcome(player: &Arc<Player>, server: &Server, config: &FirstJoinConfig) {
+    let leftover: Vec<_> = {
+        let mut inventory = player.inventory().lock().await;
+        kit_stacks(&config.kit)
+            .into_iter()
+            .filter_map(|stack| inventory.insert_stack(stack))
+            .collect()
+    };
+    for stack in leftover {
+        player.drop_stack(stack).await;
+    }
+    player.send_inventory().await;
+
+    if !config.commands.is_empty() {
+        let dispatcher = server.command_dispatcher.read().await;
+        for command in &config.commands {
+            dispatcher
+                .handle_command(
+                    &mut CommandSender::Player(player.clone()),
+                    server,
+                    &fill_placeholders(command, player),
+                )
+                .await;
+        }
+    }
+
+    if !config.welcome_message.is_empty() {
+        player
+            .send_system_message(&TextComponent::text(fill_placeholders(
+                &config.welcome_message,
+                player,
+            )))
+            .await;
+    }
+    if !config.welcome_title.is_empty() {
+        if !config.welcome_subtitle.is_empty() {
+            player
+                .send_subtitle(&TextComponent::text(fill_placeholders(
+                    &config.welcome_subtitle,
+                    player,
+                )))
+                .await;
+        }
+        player
+            .send_title(&TextComponent::text(fill_placeholders(
+                &config.welcome_title,
+                player,
+            )))
+            .await;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::AtomicBool;
+
+    use pumpkin_config::KitItem;
+    use pumpkin_data::item::Item;
+    use pumpkin_inventory::player::PlayerInventory;
+
+    use super::{kit_stacks, take_first_join};
+
+    fn kit_item(item: &str, count: u32) -> KitItem {
+        KitItem {
+            item: item.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn new_players_get_the_kit_once() {
+        let kit = [
+            kit_item("minecraft:stone_sword", 1),
+            kit_item("bread", 80),
+            kit_item("minecraft:no_such_item", 3),
+        ];
+        let has_played_before = AtomicBool::new(false);
+
+        assert!(take_first_join(&has_played_before));
+        let mut inventory = PlayerInventory::new();
+        for stack in kit_stacks(&kit) {
+            assert!(inventory.insert_stack(stack).is_none());
+        }
+        let count = |item: &Item| -> u32 {
+            inventory
+                .slots()
+                .iter()
+                .flatten()
+                .filter(|stack| stack.item.id == item.id)
+                .map(|stack| u32::from(stack.item_count))
+                .sum()
+        };
+        assert_eq!(count(&Item::STONE_SWORD), 1);
+        assert_eq!(count(&Item::BREAD), 80);
+        assert!(inventory.get_slot_with_item(Item::BREAD.id).is_some());
+
+        // Rejoining doesn't hand out the kit again
+        assert!(!take_first_join(&has_played_before));
+    }
+}
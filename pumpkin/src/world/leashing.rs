@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+
+use pumpkin_protocol::client::play::CLinkEntities;
+use pumpkin_util::math::position::BlockPos;
+
+use crate::entity::EntityId;
+
+/// The holder id telling clients that a leash was removed
+const NO_HOLDER: EntityId = 0;
+/// How close leashed mobs have to be to a fence to get tied to it. Vanilla: 7
+pub const FENCE_TIE_DISTANCE: f64 = 7.0;
+/// Leashed mobs further away than this from their holder get pulled along. Vanilla: 6
+pub const LEASH_PULL_DISTANCE: f64 = 6.0;
+/// The leash breaks once the mob is this far away from its holder. Vanilla: 10
+pub const LEASH_BREAK_DISTANCE: f64 = 10.0;
+
+/// Which entities are held on a leash, and the lead knots tied to fences.
+#[derive(Default)]
+pub struct Leashes {
+    /// The holder of each leashed entity, a player or a lead knot
+    holders: HashMap<EntityId, EntityId>,
+    /// The lead knot on each fence
+    knots: HashMap<BlockPos, EntityId>,
+}
+
+impl Leashes {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn holder_of(&self, leashed: EntityId) -> Option<EntityId> {
+        self.holders.get(&leashed).copied()
+    }
+
+    /// Every entity held by `holder`, ordered by id.
+    pub fn leashed_to(&self, holder: EntityId) -> Vec<EntityId> {
+        let mut leashed: Vec<_> = self
+            .holders
+            .iter()
+            .filter(|(_, id)| **id == holder)
+            .map(|(&leashed, _)| leashed)
+            .collect();
+        leashed.sort_unstable();
+        leashed
+    }
+
+    /// Puts `leashed` on the leash of `holder`, replacing any other holder, and returns the packet
+    /// which tells clients about it.
+    pub fn attach(&mut self, leashed: EntityId, holder: EntityId) -> CLinkEntities {
+        self.holders.insert(leashed, holder);
+        CLinkEntities::new(leashed, holder)
+    }
+
+    /// Takes `leashed` off its leash, returning its old holder and the packet which tells clients
+    /// about it.
+    pub fn detach(&mut self, leashed: EntityId) -> Option<(EntityId, CLinkEntities)> {
+        let holder = self.holders.remove(&leashed)?;
+        Some((holder, CLinkEntities::new(leashed, NO_HOLDER)))
+    }
+
+    pub fn knot_at(&self, fence: &BlockPos) -> Option<EntityId> {
+        self.knots.get(fence).copied()
+    }
+
+    pub fn fence_of(&self, knot: EntityId) -> Option<BlockPos> {
+        self.knots
+            .iter()
+            .find(|(_, id)| **id == knot)
+            .map(|(&fence, _)| fence)
+    }
+
+    /// Ties the given entities held by `holder` to the knot on `fence`. `spawn_knot` is only
+    /// called if the fence has no knot yet and returns the id of the new knot entity.
+    /// Returns the knot and the packets which tell clients about the new holder, or `None` if
+    /// `holder` doesn't hold any of them.
+    pub fn tie_to_fence(
+        &mut self,
+        holder: EntityId,
+        leashed: &[EntityId],
+        fence: BlockPos,
+        spawn_knot: impl FnOnce() -> EntityId,
+    ) -> Option<(EntityId, Vec<CLinkEntities>)> {
+        let leashed: Vec<_> = leashed
+            .iter()
+            .copied()
+            .filter(|&id| self.holder_of(id) == Some(holder))
+            .collect();
+        if leashed.is_empty() {
+            return None;
+        }
+        let knot = *self.knots.entry(fence).or_insert_with(spawn_knot);
+        let packets = leashed
+            .into_iter()
+            .map(|id| self.attach(id, knot))
+            .collect();
+        Some((knot, packets))
+    }
+
+    /// Forgets an entity which left the world, both as a leashed entity and as a holder.
+    /// Returns every entity whose leash broke with the packets which tell clients about it.
+    pub fn remove_entity(&mut self, entity: EntityId) -> Vec<(EntityId, CLinkEntities)> {
+        self.knots.retain(|_, knot| *knot != entity);
+        let mut released = Vec::new();
+        if let Some((_, packet)) = self.detach(entity) {
+            released.push((entity, packet));
+        }
+        for leashed in self.leashed_to(entity) {
+            if let Some((_, packet)) = self.detach(leashed) {
+                released.push((leashed, packet));
+            }
+        }
+        released
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::ClientPacket;
+    use pumpkin_util::math::position::BlockPos;
+
+    use super::Leashes;
+
+    fn packet_data(packet: &impl ClientPacket) -> Vec<u8> {
+        let mut data = Vec::new();
+        packet.write_packet_data(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn tying_to_a_fence_spawns_a_knot_and_relinks_the_leash() {
+        let mut leashes = Leashes::new();
+        let fence = BlockPos::new(4, 64, -2);
+        leashes.attach(5, 1);
+        // Nothing held by the player which isn't on a leash
+        assert!(leashes.tie_to_fence(1, &[6], fence, || 9).is_none());
+        assert_eq!(leashes.knot_at(&fence), None);
+
+        let mut spawned = 0;
+        let (knot, packets) = leashes
+            .tie_to_fence(1, &[5], fence, || {
+                spawned += 1;
+                9
+            })
+            .unwrap();
+        assert_eq!((knot, spawned), (9, 1));
+        assert_eq!(leashes.knot_at(&fence), Some(9));
+        assert_eq!(leashes.fence_of(9), Some(fence));
+        assert_eq!(leashes.holder_of(5), Some(9));
+        assert!(leashes.leashed_to(1).is_empty());
+        // The leashed entity, then its new holder
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packet_data(&packets[0]), [0, 0, 0, 5, 0, 0, 0, 9]);
+
+        // A second mob reuses the knot already on the fence
+        leashes.attach(7, 1);
+        let (knot, _) = leashes
+            .tie_to_fence(1, &[7], fence, || unreachable!())
+            .unwrap();
+        assert_eq!(knot, 9);
+        assert_eq!(leashes.leashed_to(9), [5, 7]);
+    }
+
+    #[test]
+    fn removed_knot_releases_its_leashes() {
+        let mut leashes = Leashes::new();
+        let fence = BlockPos::new(0, 70, 0);
+        leashes.attach(5, 1);
+        leashes.attach(7, 1);
+        leashes.tie_to_fence(1, &[5, 7], fence, || 9);
+
+        let released = leashes.remove_entity(9);
+        assert_eq!(
+            released.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            [5, 7]
+        );
+        assert_eq!(packet_data(&released[0].1), [0, 0, 0, 5, 0, 0, 0, 0]);
+        assert_eq!(leashes.knot_at(&fence), None);
+        assert_eq!(leashes.holder_of(5), None);
+        assert!(leashes.detach(7).is_none());
+    }
+}
@@ -6,14 +6,24 @@ use std::{
 
 pub mod chunker;
 pub mod explosion;
+pub mod first_join;
+pub mod natural_spawner;
 pub mod portal;
 pub mod time;
 
 use crate::{
     PLUGIN_MANAGER,
-    block::{self, registry::BlockRegistry},
+    block::{
+        self, blocks::respawn_anchor::RespawnAnchorBlock, drop_stack, registry::BlockRegistry,
+    },
     command::client_suggestions,
-    entity::{Entity, EntityBase, EntityId, player::Player},
+    entity::{
+        Entity, EntityBase, EntityId,
+        leash_knot::LeashKnotEntity,
+        mob,
+        player::{Player, RespawnPoint, SignedMessage},
+        tracking::{EntityGrid, is_in_tracking_range, max_tracking_range},
+    },
     error::PumpkinError,
     plugin::{
         block::block_break::BlockBreakEvent,
@@ -24,61 +34,65 @@ use crate::{
 };
 use bitflags::bitflags;
 use border::Worldborder;
-use bytes::{BufMut, Bytes};
+use bytes::Bytes;
 use explosion::Explosion;
-use pumpkin_config::BasicConfiguration;
-use pumpkin_data::block_properties::{BlockProperties, Integer0To15, WaterLikeProperties};
+use leashing::{FENCE_TIE_DISTANCE, Leashes};
+use pumpkin_config::{BasicConfiguration, advanced_config};
 use pumpkin_data::entity::EffectType;
 use pumpkin_data::{
     Block,
     block_properties::{
-        get_block_and_state_by_state_id, get_block_by_state_id, get_state_by_state_id,
+        get_block_and_state_by_state_id, get_block_by_state_id, get_block_collision_shapes,
+        get_state_by_state_id,
     },
     entity::{EntityStatus, EntityType},
     fluid::Fluid,
+    item::Item,
     particle::Particle,
     sound::{Sound, SoundCategory},
     world::{RAW, WorldEvent},
 };
 use pumpkin_macros::send_cancellable;
 use pumpkin_nbt::to_bytes_unnamed;
-use pumpkin_protocol::client::play::{
-    CRemoveMobEffect, CSetEntityMetadata, MetaDataType, Metadata,
-};
+use pumpkin_protocol::client::play::CRemoveMobEffect;
 use pumpkin_protocol::codec::identifier::Identifier;
-use pumpkin_protocol::ser::serializer::Serializer;
 use pumpkin_protocol::{
     ClientPacket, IdOr, SoundEvent,
     client::play::{
-        CBlockEntityData, CEntityStatus, CGameEvent, CLogin, CMultiBlockUpdate, CPlayerChatMessage,
-        CPlayerInfoUpdate, CRemoveEntities, CRemovePlayerInfo, CSoundEffect, CSpawnEntity,
-        FilterType, GameEvent, InitChat, PlayerAction, PlayerInfoFlags,
+        CBlockEntityData, CEntityStatus, CGameEvent, CLogin, CMultiBlockUpdate, CPlayerInfoUpdate,
+        CRemoveEntities, CRemovePlayerInfo, CSoundEffect, GameEvent, InitChat, PlayerAction,
+        PlayerInfoFlags,
     },
-    server::play::SChatMessage,
 };
 use pumpkin_protocol::{
     client::play::{
         CBlockUpdate, CDisguisedChatMessage, CExplosion, CRespawn, CSetBlockDestroyStage,
-        CWorldEvent,
+        CSetDefaultSpawnPosition, CWorldEvent,
     },
     codec::var_int::VarInt,
 };
-use pumpkin_registry::DimensionType;
-use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+use pumpkin_registry::{DimensionType, DimensionTypes};
+use pumpkin_util::math::{boundingbox::BoundingBox, position::BlockPos, vector3::Vector3};
 use pumpkin_util::math::{position::chunk_section_from_pos, vector2::Vector2};
 use pumpkin_util::text::{TextComponent, color::NamedColor};
-use pumpkin_world::entity::entity_data_flags::{
-    DATA_PLAYER_MAIN_HAND, DATA_PLAYER_MODE_CUSTOMISATION,
-};
 use pumpkin_world::{
-    BlockStateId, GENERATION_SETTINGS, GeneratorSetting, biome, block::entities::BlockEntity,
+    BlockStateId, GENERATION_SETTINGS, GeneratorSetting, biome,
+    block::entities::{BlockEntity, block_entity_from_nbt},
+    item::ItemStack,
     level::SyncChunk,
+    structure::{StructurePlaceSettings, StructureTemplate},
+};
+use pumpkin_world::{
+    block::BlockDirection,
+    chunk::{
+        ChunkData,
+        anti_xray::{AntiXray, is_see_through},
+    },
 };
-use pumpkin_world::{block::BlockDirection, chunk::ChunkData};
-use pumpkin_world::{chunk::TickPriority, level::Level};
+use pumpkin_world::{chunk::TickPriority, level::Level, world_info::LevelData};
 use rand::{Rng, thread_rng};
-use scoreboard::Scoreboard;
-use serde::Serialize;
+use riding::{RideError, Riding};
+use scoreboard::{SCOREBOARD_FILE, Scoreboard};
 use thiserror::Error;
 use time::LevelTime;
 use tokio::sync::{RwLock, mpsc};
@@ -90,6 +104,8 @@ use tokio::{
 pub mod border;
 pub mod bossbar;
 pub mod custom_bossbar;
+pub mod leashing;
+pub mod riding;
 pub mod scoreboard;
 pub mod weather;
 
@@ -136,6 +152,57 @@ impl PumpkinError for GetBlockError {
     }
 }
 
+/// The vertical range blocks can be in, defined by the dimension type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildHeight {
+    /// The lowest y blocks can be at
+    pub min_y: i32,
+    /// How many layers of blocks there are from `min_y` up
+    pub height: i32,
+    /// How many of those layers portals may take entities to
+    pub logical_height: i32,
+}
+
+impl BuildHeight {
+    pub fn from_dimension(
+        dimension_types: &DimensionTypes,
+        dimension_type: &DimensionType,
+    ) -> Self {
+        dimension_types
+            .get(dimension_type)
+            .map_or_else(Self::default, |dimension| Self {
+                min_y: dimension.min_y(),
+                height: dimension.height(),
+                logical_height: dimension.logical_height(),
+            })
+    }
+
+    /// The highest y blocks can be at
+    pub const fn max_y(self) -> i32 {
+        self.min_y + self.height - 1
+    }
+
+    /// The highest y portals may take entities to
+    pub const fn logical_max_y(self) -> i32 {
+        self.min_y + self.logical_height - 1
+    }
+
+    pub const fn contains(self, y: i32) -> bool {
+        y >= self.min_y && y <= self.max_y()
+    }
+}
+
+impl Default for BuildHeight {
+    /// The overworld's build height
+    fn default() -> Self {
+        Self {
+            min_y: -64,
+            height: 384,
+            logical_height: 384,
+        }
+    }
+}
+
 /// Represents a Minecraft world, containing entities, players, and the underlying level data.
 ///
 /// Each dimension (Overworld, Nether, End) typically has its own `World`.
@@ -155,12 +222,22 @@ pub struct World {
     pub entities: Arc<RwLock<HashMap<uuid::Uuid, Arc<dyn EntityBase>>>>,
     /// The world's scoreboard, used for tracking scores, objectives, and display information.
     pub scoreboard: Mutex<Scoreboard>,
+    /// Which entities are riding which vehicles.
+    pub riding: Mutex<Riding>,
+    /// Which entities are held on a leash, and the lead knots on fences.
+    pub leashes: Mutex<Leashes>,
     /// The world's worldborder, defining the playable area and controlling its expansion or contraction.
     pub worldborder: Mutex<Worldborder>,
     /// The world's time, including counting ticks for weather, time cycles, and statistics.
     pub level_time: Mutex<LevelTime>,
     /// The type of dimension the world is in.
     pub dimension_type: DimensionType,
+    /// The id of the dimension type in the synced `dimension_type` registry.
+    pub dimension_type_id: i32,
+    /// Where blocks can be placed, from the dimension type.
+    pub build_height: BuildHeight,
+    /// Whether respawn anchors work here, from the dimension type. They explode otherwise.
+    pub respawn_anchor_works: bool,
     pub sea_level: i32,
     /// The world's weather, including rain and thunder levels.
     pub weather: Mutex<Weather>,
@@ -168,51 +245,116 @@ pub struct World {
     pub block_registry: Arc<BlockRegistry>,
     /// A map of unsent block changes, keyed by block position.
     unsent_block_changes: Mutex<HashMap<BlockPos, u16>>,
+    /// Hides the ores in the chunks sent to players, if enabled in the config.
+    pub anti_xray: Option<AntiXray>,
     // TODO: entities
 }
 
 impl World {
     #[must_use]
     pub fn load(
-        level: Level,
+        mut level: Level,
         dimension_type: DimensionType,
+        dimension_types: &DimensionTypes,
         block_registry: Arc<BlockRegistry>,
     ) -> Self {
+        let dimension_type_id = dimension_types
+            .registry_id(&dimension_type)
+            .expect("The dimension type of a world must be registered");
+        let build_height = BuildHeight::from_dimension(dimension_types, &dimension_type);
+        let respawn_anchor_works = dimension_types
+            .get(&dimension_type)
+            .is_some_and(|dimension| dimension.respawn_anchor_works());
         // TODO
         let generation_settings = GENERATION_SETTINGS
             .get(&GeneratorSetting::Overworld)
             .unwrap();
+        let worldborder = Worldborder::from_level_data(level.level_info.get_mut());
+        let level_time = LevelTime::from_level_data(level.level_info.get_mut());
+        let scoreboard = level
+            .data_file(SCOREBOARD_FILE)
+            .map_or_else(Scoreboard::new, |path| Scoreboard::load(&path));
         Self {
             level: Arc::new(level),
             players: Arc::new(RwLock::new(HashMap::new())),
             entities: Arc::new(RwLock::new(HashMap::new())),
-            scoreboard: Mutex::new(Scoreboard::new()),
-            worldborder: Mutex::new(Worldborder::new(0.0, 0.0, 29_999_984.0, 0, 0, 0)),
-            level_time: Mutex::new(LevelTime::new()),
+            scoreboard: Mutex::new(scoreboard),
+            riding: Mutex::new(Riding::new()),
+            leashes: Mutex::new(Leashes::new()),
+            worldborder: Mutex::new(worldborder),
+            level_time: Mutex::new(level_time),
             dimension_type,
+            dimension_type_id,
+            build_height,
+            respawn_anchor_works,
             weather: Mutex::new(Weather::new()),
             block_registry,
             sea_level: generation_settings.sea_level,
             unsent_block_changes: Mutex::new(HashMap::new()),
+            anti_xray: AntiXray::new(&advanced_config().anti_xray),
         }
     }
 
     pub async fn shutdown(&self) {
+        self.save_level_data().await;
         self.level.shutdown().await;
     }
 
-    pub async fn send_entity_status(&self, entity: &Entity, status: EntityStatus) {
-        // TODO: only nearby
-        self.broadcast_packet_all(&CEntityStatus::new(entity.entity_id, status as i8))
+    /// Saves the world without unloading it, see [`Level::save`]. Returns how many chunks are
+    /// saved, along with the writing to disk which is left.
+    pub async fn save(&self) -> (usize, impl Future<Output = ()> + Send + 'static) {
+        self.save_level_data().await;
+        self.level.save().await
+    }
+
+    /// Puts the world border and time into `level.dat` and saves the scoreboard.
+    async fn save_level_data(&self) {
+        {
+            let mut level_info = self.level.level_info.write().await;
+            self.worldborder
+                .lock()
+                .await
+                .write_level_data(&mut level_info);
+            self.level_time
+                .lock()
+                .await
+                .write_level_data(&mut level_info);
+        }
+        if let Some(path) = self.level.data_file(SCOREBOARD_FILE) {
+            self.scoreboard.lock().await.save(&path);
+        }
+    }
+
+    /// The world spawn and the yaw players face there.
+    pub async fn spawn_point(&self) -> (BlockPos, f32) {
+        self.level.level_info.read().await.spawn_point()
+    }
+
+    /// Moves the world spawn, which is saved to `level.dat`, and points the compasses of all
+    /// players to it.
+    pub async fn set_spawn_point(&self, pos: BlockPos, angle: f32) {
+        self.level
+            .level_info
+            .write()
+            .await
+            .set_spawn_point(pos, angle);
+        self.broadcast_packet_all(&CSetDefaultSpawnPosition::new(pos, angle))
             .await;
     }
 
+    pub async fn send_entity_status(&self, entity: &Entity, status: EntityStatus) {
+        self.broadcast_to_trackers(
+            entity.entity_id,
+            &CEntityStatus::new(entity.entity_id, status as i8),
+        )
+        .await;
+    }
+
     pub async fn send_remove_mob_effect(&self, entity: &Entity, effect_type: EffectType) {
-        // TODO: only nearby
-        self.broadcast_packet_all(&CRemoveMobEffect::new(
-            entity.entity_id.into(),
-            VarInt(effect_type as i32),
-        ))
+        self.broadcast_to_trackers(
+            entity.entity_id,
+            &CRemoveMobEffect::new(entity.entity_id.into(), VarInt(effect_type as i32)),
+        )
         .await;
     }
 
@@ -246,50 +388,22 @@ impl World {
 
     pub async fn broadcast_secure_player_chat(
         &self,
-        sender: &Arc<Player>,
-        chat_message: &SChatMessage,
+        sender: &Player,
+        message: &SignedMessage,
         decorated_message: &TextComponent,
     ) {
-        let messages_sent: i32 = sender.chat_session.lock().await.messages_sent;
-        let sender_last_seen = {
-            let cache = sender.signature_cache.lock().await;
-            cache.last_seen.clone()
-        };
-
         for recipient in self.players.read().await.values() {
-            let messages_received: i32 = recipient.chat_session.lock().await.messages_received;
-            let packet = &CPlayerChatMessage::new(
-                VarInt(messages_received),
-                sender.gameprofile.id,
-                VarInt(messages_sent),
-                chat_message.signature.clone(),
-                chat_message.message.clone(),
-                chat_message.timestamp,
-                chat_message.salt,
-                sender_last_seen.indexed_for(recipient).await,
-                Some(decorated_message.clone()),
-                FilterType::PassThrough,
-                (RAW + 1).into(), // Custom registry chat_type with no sender name
-                TextComponent::text(""), // Not needed since we're injecting the name in the message for custom formatting
-                None,
-            );
-            recipient.client.enqueue_packet(packet).await;
-
             recipient
-                .signature_cache
-                .lock()
-                .await
-                .add_seen_signature(&chat_message.signature.clone().unwrap()); // Unwrap is safe because we check for None in validate_chat_message
-
-            let recipient_signature_cache = &mut recipient.signature_cache.lock().await;
-            if recipient.gameprofile.id != sender.gameprofile.id {
-                // Sender may update recipient on signatures recipient hasn't seen
-                recipient_signature_cache.cache_signatures(sender_last_seen.as_ref());
-            }
-            recipient.chat_session.lock().await.messages_received += 1;
+                .send_signed_message(
+                    sender,
+                    message,
+                    Some(decorated_message),
+                    RAW,                      // Custom registry chat_type with no sender name
+                    &TextComponent::text(""), // Not needed since we're injecting the name in the message for custom formatting
+                    None,
+                )
+                .await;
         }
-
-        sender.chat_session.lock().await.messages_sent += 1;
     }
 
     /// Broadcasts a packet to all connected players within the world, excluding the specified players.
@@ -301,19 +415,75 @@ impl World {
     where
         P: ClientPacket,
     {
-        let mut packet_buf = Vec::new();
-        if let Err(err) = packet.write(&mut packet_buf) {
-            log::error!("Failed to serialize packet {}: {}", P::PACKET_ID, err);
+        let Some(packet_data) = Self::serialize_packet(packet) else {
             return;
-        }
-        let packet_data: Bytes = packet_buf.into();
+        };
 
         let current_players = self.players.read().await;
         for (_, player) in current_players.iter().filter(|c| !except.contains(c.0)) {
-            player.client.enqueue_packet_data(packet_data.clone()).await;
+            player
+                .client
+                .enqueue_packet_data(packet_data.clone(), P::COMPRESSION)
+                .await;
+        }
+    }
+
+    /// Sends a packet about an entity to the players tracking it, and to the entity itself if it
+    /// is a player. Players that don't track the entity don't know it, so there is no point in
+    /// telling them.
+    pub async fn broadcast_to_trackers<P>(&self, entity_id: EntityId, packet: &P)
+    where
+        P: ClientPacket,
+    {
+        self.broadcast_to_trackers_except(entity_id, &[], packet)
+            .await;
+    }
+
+    /// Like [`Self::broadcast_to_trackers`], excluding the specified players.
+    pub async fn broadcast_to_trackers_except<P>(
+        &self,
+        entity_id: EntityId,
+        except: &[uuid::Uuid],
+        packet: &P,
+    ) where
+        P: ClientPacket,
+    {
+        let Some(packet_data) = Self::serialize_packet(packet) else {
+            return;
+        };
+
+        let current_players = self.players.read().await;
+        for (uuid, player) in current_players.iter() {
+            if except.contains(uuid) {
+                continue;
+            }
+            if player.entity_id() != entity_id
+                && !player.tracked_entities.lock().await.is_tracking(entity_id)
+            {
+                continue;
+            }
+            player
+                .client
+                .enqueue_packet_data(packet_data.clone(), P::COMPRESSION)
+                .await;
         }
     }
 
+    /// Serializes a packet once, so it can be sent to many players.
+    fn serialize_packet<P>(packet: &P) -> Option<Bytes>
+    where
+        P: ClientPacket,
+    {
+        let mut packet_buf = Vec::new();
+        if let Err(err) = packet.write(&mut packet_buf) {
+            log::error!("Failed to serialize packet {}: {}", P::PACKET_ID, err);
+            return None;
+        }
+        #[cfg(feature = "protocol_strict")]
+        crate::net::check_outgoing_packet(packet, &packet_buf);
+        Some(packet_buf.into())
+    }
+
     pub async fn spawn_particle(
         &self,
         position: Vector3<f64>,
@@ -364,48 +534,168 @@ impl World {
 
     pub async fn tick(self: &Arc<Self>, server: &Server) {
         self.flush_block_updates().await;
+        // While `/tick freeze` is on, only players keep going
+        let runs_normally = server.tick_rate_manager.lock().unwrap().runs_normally();
 
         // world ticks
-        {
-            let mut level_time = self.level_time.lock().await;
-            level_time.tick_time();
-            if level_time.world_age % 20 == 0 {
-                level_time.send_time(self).await;
+        if runs_normally {
+            {
+                let mut level_time = self.level_time.lock().await;
+                level_time.tick_time();
+                if level_time.world_age % 20 == 0 {
+                    level_time.send_time(self).await;
+                }
             }
-        }
 
-        {
-            let mut weather = self.weather.lock().await;
-            weather.tick_weather(self).await;
-        };
+            {
+                let mut weather = self.weather.lock().await;
+                weather.tick_weather(self).await;
+            };
 
-        self.tick_scheduled_block_ticks().await;
+            self.tick_scheduled_block_ticks().await;
+            self.tick_natural_spawning().await;
+        }
 
         // player ticks
-        for player in self.players.read().await.values() {
+        // Collected first, so players can change worlds while ticking
+        let players_to_tick: Vec<_> = self.players.read().await.values().cloned().collect();
+        for player in players_to_tick {
             player.tick(server).await;
+            player.tick_portal(server).await;
+            self.tick_block_contact(player.as_ref()).await;
         }
 
-        let entities_to_tick: Vec<_> = self.entities.read().await.values().cloned().collect();
+        let entities_to_tick: Vec<_> = if runs_normally {
+            self.entities.read().await.values().cloned().collect()
+        } else {
+            Vec::new()
+        };
+        let mut simulated_areas = Vec::new();
+        for player in self.players.read().await.values() {
+            simulated_areas.push((
+                player.living_entity.entity.chunk_pos.load(),
+                chunker::get_simulation_distance(player).await,
+            ));
+        }
 
         // Entity ticks
         for entity in entities_to_tick {
+            let chunk_pos = entity.get_entity().chunk_pos.load();
+            if !simulated_areas.iter().any(|(center, distance)| {
+                chunker::is_within_simulation_distance(*center, *distance, chunk_pos)
+            }) {
+                continue;
+            }
             entity.tick(server).await;
+            self.tick_block_contact(entity.as_ref()).await;
             for player in self.players.read().await.values() {
-                if player
-                    .living_entity
-                    .entity
-                    .bounding_box
-                    .load()
-                    // This is vanilla, but TODO: change this when is in a vehicle
-                    .expand(1.0, 0.5, 1.0)
-                    .intersects(&entity.get_entity().bounding_box.load())
-                {
+                if player.touches_entity(&entity.get_entity().bounding_box.load()) {
                     entity.on_player_collision(player.clone()).await;
                     break;
                 }
             }
         }
+        self.tick_passengers().await;
+
+        self.update_entity_tracking().await;
+    }
+
+    /// Lets the blocks an entity touches act on it, like magma blocks burning it.
+    async fn tick_block_contact(self: &Arc<Self>, entity: &dyn EntityBase) {
+        let base = entity.get_entity();
+        let pos = base.pos.load();
+        let inside = BlockPos::floored(pos.x, pos.y, pos.z);
+        if let Ok(block) = self.get_block(&inside).await {
+            self.block_registry
+                .on_entity_inside(&block, self, &inside, entity)
+                .await;
+        }
+
+        // The block the feet rest on, looked up a bit below them like vanilla
+        let below = BlockPos::floored(pos.x, pos.y - 0.2, pos.z);
+        let Ok(state) = self.get_block_state(&below).await else {
+            return;
+        };
+        // Only players tell us whether they are on the ground, for other entities it depends
+        // on whether they rest on top of the block without moving up
+        if base.entity_type != EntityType::PLAYER {
+            base.on_ground.store(
+                base.velocity.load().y <= 0.0
+                    && stands_on(&base.bounding_box.load(), below, state.id),
+                Ordering::Relaxed,
+            );
+        }
+        if !base.on_ground.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Some(block) = get_block_by_state_id(state.id) {
+            self.block_registry
+                .on_entity_step(&block, self, &below, entity)
+                .await;
+        }
+    }
+
+    /// Spawns entities for the players that got in their tracking range and despawns them for the
+    /// players that left it.
+    pub async fn update_entity_tracking(&self) {
+        let config = &advanced_config().entity_tracking;
+        let entities: Vec<_> = self.entities.read().await.values().cloned().collect();
+        let players: Vec<_> = self.players.read().await.values().cloned().collect();
+        let candidates: HashMap<EntityId, &dyn EntityBase> = entities
+            .iter()
+            .map(|entity| entity.as_ref())
+            .chain(
+                players
+                    .iter()
+                    .map(|player| player.as_ref() as &dyn EntityBase),
+            )
+            .map(|entity| (entity.get_entity().entity_id, entity))
+            .collect();
+
+        let mut grid = EntityGrid::default();
+        for (entity_id, entity) in &candidates {
+            let entity = entity.get_entity();
+            grid.insert(*entity_id, &entity.entity_type, entity.pos.load());
+        }
+
+        for viewer in &players {
+            let viewer_id = viewer.entity_id();
+            let view_distance = chunker::get_view_distance(viewer).await.get();
+            let pos = viewer.living_entity.entity.pos.load();
+            let update = viewer.tracked_entities.lock().await.update(
+                config,
+                pos,
+                view_distance,
+                grid.nearby(pos, max_tracking_range(config, view_distance))
+                    .filter(|(entity_id, _, _)| *entity_id != viewer_id),
+            );
+
+            if !update.removed.is_empty() {
+                let removed: Vec<VarInt> = update.removed.into_iter().map(VarInt).collect();
+                viewer
+                    .client
+                    .enqueue_packet(&CRemoveEntities::new(&removed))
+                    .await;
+            }
+            for entity_id in update.spawned {
+                Self::send_entity_to(viewer, candidates[&entity_id]).await;
+            }
+        }
+    }
+
+    /// Spawns an entity for a player that just started tracking it, along with everything that
+    /// changed about it since it was created.
+    async fn send_entity_to(viewer: &Player, entity: &dyn EntityBase) {
+        let base_entity = entity.get_entity();
+        viewer
+            .client
+            .enqueue_packet(&base_entity.create_spawn_packet(entity.spawn_data()))
+            .await;
+        base_entity.send_base_meta_data_to(viewer).await;
+        if let Some(living_entity) = entity.get_living_entity() {
+            living_entity.send_health_to(viewer).await;
+        }
+        entity.send_tracking_data(viewer).await;
     }
 
     pub async fn flush_block_updates(&self) {
@@ -471,17 +761,25 @@ impl World {
 
     /// Gets the y position of the first non air block from the top down
     pub async fn get_top_block(&self, position: Vector2<i32>) -> i32 {
-        for y in (-64..=319).rev() {
+        let build_height = self.build_height;
+        for y in (build_height.min_y..=build_height.max_y()).rev() {
             let pos = BlockPos(Vector3::new(position.x, y, position.z));
-            let block = self.get_block_state(&pos).await;
-            if let Ok(block) = block {
-                if block.is_air() {
-                    continue;
-                }
+            match self.get_block_state(&pos).await {
+                // Layers the chunk doesn't have count as air
+                Ok(block) if block.is_air() => {}
+                Err(GetBlockError::BlockOutOfWorldBounds) => {}
+                _ => return y,
             }
-            return y;
         }
-        319
+        build_height.max_y()
+    }
+
+    /// Whether rain falls on `pos`, so it is raining and nothing above blocks the sky.
+    pub async fn is_raining_at(&self, pos: &BlockPos) -> bool {
+        if !self.weather.lock().await.raining {
+            return false;
+        }
+        self.get_top_block(Vector2::new(pos.0.x, pos.0.z)).await < pos.0.y
     }
 
     #[expect(clippy::too_many_lines)]
@@ -512,11 +810,12 @@ impl World {
                 &dimensions,
                 base_config.max_players.try_into().unwrap(),
                 base_config.view_distance.get().into(), //  TODO: view distance
-                base_config.simulation_distance.get().into(), // TODO: sim view dinstance
+                chunker::get_simulation_distance(&player).await.get().into(),
                 false,
-                true,
+                // The client skips the death screen and asks to respawn right away
+                !base_config.immediate_respawn,
                 false,
-                (self.dimension_type as u8).into(),
+                self.dimension_type_id.into(),
                 self.dimension_type.name(),
                 biome::hash_seed(self.level.seed.0), // seed
                 gamemode as u8,
@@ -542,27 +841,32 @@ impl World {
         };
 
         // Teleport
-        let (position, yaw, pitch) = if player.has_played_before.load(Ordering::Relaxed) {
+        let first_join = first_join::take_first_join(&player.has_played_before);
+        let first_join_spawn = advanced_config().first_join.spawn.filter(|_| first_join);
+        let (position, yaw, pitch) = if !first_join {
             let position = player.position();
             let yaw = player.living_entity.entity.yaw.load(); //info.spawn_angle;
             let pitch = player.living_entity.entity.pitch.load();
 
             (position, yaw, pitch)
+        } else if let Some(spawn) = first_join_spawn {
+            (
+                Vector3::new(spawn.x, spawn.y, spawn.z),
+                spawn.yaw,
+                spawn.pitch,
+            )
         } else {
-            let info = &self.level.level_info;
+            let (spawn, yaw) = self.spawn_point().await;
             let position = Vector3::new(
-                f64::from(info.spawn_x),
-                f64::from(info.spawn_y) + 1.0,
-                f64::from(info.spawn_z),
+                f64::from(spawn.0.x),
+                f64::from(spawn.0.y) + 1.0,
+                f64::from(spawn.0.z),
             );
-            let yaw = info.spawn_angle;
             let pitch = 0.0;
 
             (position, yaw, pitch)
         };
 
-        let velocity = player.living_entity.entity.velocity.load();
-
         log::debug!("Sending player teleport to {}", player.gameprofile.name);
         player.request_teleport(position, yaw, pitch).await;
 
@@ -643,77 +947,8 @@ impl World {
                 .await;
         };
 
-        let gameprofile = &player.gameprofile;
-
-        log::debug!("Broadcasting player spawn for {}", player.gameprofile.name);
-        // Spawn the player for every client.
-        self.broadcast_packet_except(
-            &[player.gameprofile.id],
-            &CSpawnEntity::new(
-                entity_id.into(),
-                gameprofile.id,
-                i32::from(EntityType::PLAYER.id).into(),
-                position,
-                pitch,
-                yaw,
-                yaw,
-                0.into(),
-                velocity,
-            ),
-        )
-        .await;
-
-        // Spawn players for our client.
-        let id = player.gameprofile.id;
-        for (_, existing_player) in self.players.read().await.iter().filter(|c| c.0 != &id) {
-            let entity = &existing_player.living_entity.entity;
-            let pos = entity.pos.load();
-            let gameprofile = &existing_player.gameprofile;
-            log::debug!("Sending player entities to {}", player.gameprofile.name);
-
-            player
-                .client
-                .enqueue_packet(&CSpawnEntity::new(
-                    existing_player.entity_id().into(),
-                    gameprofile.id,
-                    i32::from(EntityType::PLAYER.id).into(),
-                    pos,
-                    entity.yaw.load(),
-                    entity.pitch.load(),
-                    entity.head_yaw.load(),
-                    0.into(),
-                    entity.velocity.load(),
-                ))
-                .await;
-            let config = existing_player.config.read().await;
-            let mut buf = Vec::new();
-            for meta in [
-                Metadata::new(
-                    DATA_PLAYER_MODE_CUSTOMISATION,
-                    MetaDataType::Byte,
-                    config.skin_parts,
-                ),
-                Metadata::new(
-                    DATA_PLAYER_MAIN_HAND,
-                    MetaDataType::Byte,
-                    config.main_hand as u8,
-                ),
-            ] {
-                let mut serializer_buf = Vec::new();
-
-                let mut serializer = Serializer::new(&mut serializer_buf);
-                meta.serialize(&mut serializer).unwrap();
-                buf.extend(serializer_buf);
-            }
-            buf.put_u8(255);
-            player
-                .client
-                .enqueue_packet(&CSetEntityMetadata::new(
-                    existing_player.get_entity().entity_id.into(),
-                    buf.into(),
-                ))
-                .await;
-        }
+        // The player and the entities around them get spawned for each other once they are in
+        // tracking range, see `update_entity_tracking`
         player.send_client_information().await;
 
         // Start waiting for level chunks. Sets the "Loading Terrain" screen
@@ -728,9 +963,16 @@ impl World {
             .await
             .init_client(&player.client)
             .await;
+        self.scoreboard
+            .lock()
+            .await
+            .init_client(&player.client)
+            .await;
 
         // Sends initial time
         player.send_time(self).await;
+        server.send_tick_state_to(&player.client).await;
+        player.send_server_data(server).await;
 
         // Send initial weather state
         let weather = self.weather.lock().await;
@@ -766,60 +1008,59 @@ impl World {
         //     }
         // }
 
-        player.has_played_before.store(true, Ordering::Relaxed);
-        player.send_mobs(self).await;
         player.send_inventory().await;
+        player.living_entity.send_attributes_to(&player).await;
+        if first_join {
+            first_join::welcome(&player, server, &advanced_config().first_join).await;
+        }
     }
 
-    pub async fn send_world_info(
-        &self,
-        player: &Arc<Player>,
-        position: Vector3<f64>,
-        yaw: f32,
-        pitch: f32,
-    ) {
+    pub async fn send_world_info(&self, player: &Arc<Player>) {
         self.worldborder
             .lock()
             .await
             .init_client(&player.client)
             .await;
 
-        // TODO: World spawn (compass stuff)
+        let (spawn, angle) = self.spawn_point().await;
+        player
+            .client
+            .enqueue_packet(&CSetDefaultSpawnPosition::new(spawn, angle))
+            .await;
 
         player
             .client
             .enqueue_packet(&CGameEvent::new(GameEvent::StartWaitingChunks, 0.0))
             .await;
 
-        let entity = &player.living_entity.entity;
-
-        self.broadcast_packet_except(
-            &[player.gameprofile.id],
-            // TODO: add velo
-            &CSpawnEntity::new(
-                entity.entity_id.into(),
-                player.gameprofile.id,
-                i32::from(EntityType::PLAYER.id).into(),
-                position,
-                pitch,
-                yaw,
-                yaw,
-                0.into(),
-                Vector3::new(0.0, 0.0, 0.0),
-            ),
-        )
-        .await;
+        // The client forgot all entities, and everyone else should see the player anew
+        player.tracked_entities.lock().await.clear();
+        let entity_id = player.entity_id();
+        for other in self.players.read().await.values() {
+            other.tracked_entities.lock().await.untrack(entity_id);
+        }
         player.send_client_information().await;
 
         chunker::player_join(player).await;
         // Update commands
 
-        player.set_health(20.0).await;
+        player.living_entity.send_attributes_to(player).await;
+        // The respawned client starts over with default values
+        player.last_sent_health_food.store(None);
+        player
+            .set_health(player.living_entity.max_health().await)
+            .await;
     }
 
-    pub async fn explode(self: &Arc<Self>, server: &Server, position: Vector3<f64>, power: f32) {
+    /// Lets an explosion of `power` go off at `position`, returns the entities it killed.
+    pub async fn explode(
+        self: &Arc<Self>,
+        server: &Server,
+        position: Vector3<f64>,
+        power: f32,
+    ) -> Vec<Arc<dyn EntityBase>> {
         let explosion = Explosion::new(power, position);
-        explosion.explode(server, self).await;
+        let killed = explosion.explode(server, self).await;
         let particle = if power < 2.0 {
             Particle::Explosion
         } else {
@@ -840,11 +1081,15 @@ impl World {
                 ))
                 .await;
         }
+        killed
     }
 
-    pub async fn respawn_player(&self, player: &Arc<Player>, alive: bool) {
+    /// Respawns the player in this world, moving them here if they died in another one, like
+    /// when respawning at a respawn anchor in the nether.
+    pub async fn respawn_player(self: &Arc<Self>, player: &Arc<Player>, alive: bool) {
         let last_pos = player.living_entity.last_pos.load();
-        let death_dimension = player.world().await.dimension_type.name();
+        let death_world = player.world().await;
+        let death_dimension = death_world.dimension_type.name();
         let death_location = BlockPos(Vector3::new(
             last_pos.x.round() as i32,
             last_pos.y.round() as i32,
@@ -853,12 +1098,14 @@ impl World {
 
         let data_kept = u8::from(alive);
 
-        // TODO: switch world in player entity to new world
+        if !Arc::ptr_eq(&death_world, self) {
+            player.move_to_world(&death_world, self).await;
+        }
 
         player
             .client
             .enqueue_packet(&CRespawn::new(
-                (self.dimension_type as u8).into(),
+                self.dimension_type_id.into(),
                 self.dimension_type.name(),
                 biome::hash_seed(self.level.seed.0), // seed
                 player.gamemode.load() as u8,
@@ -878,19 +1125,38 @@ impl World {
         player.send_permission_lvl_update().await;
 
         // Teleport
-        let info = &self.level.level_info;
-        let mut position = Vector3::new(
-            f64::from(info.spawn_x),
-            f64::from(info.spawn_y),
-            f64::from(info.spawn_z),
-        );
-        let yaw = info.spawn_angle;
+        let respawn_point = player.respawn_point_in(self).await;
+        let (spawn, yaw) =
+            respawn_location(respawn_point.as_ref(), &*self.level.level_info.read().await);
         let pitch = 0.0;
-
-        let top = self
-            .get_top_block(Vector2::new(position.x as i32, position.z as i32))
-            .await;
-        position.y = f64::from(top + 1);
+        if let Some(point) = respawn_point
+            .as_ref()
+            .filter(|point| !point.forced && !alive)
+        {
+            if self
+                .get_block(&point.position)
+                .await
+                .is_ok_and(|block| block == Block::RESPAWN_ANCHOR)
+            {
+                RespawnAnchorBlock::deplete(self, &point.position).await;
+            }
+        }
+        let position = match respawn_point {
+            // Players respawn on top of their bed or respawn anchor
+            Some(point) => Vector3::new(
+                f64::from(spawn.0.x) + 0.5,
+                f64::from(spawn.0.y) + if point.forced { 0.0 } else { 1.0 },
+                f64::from(spawn.0.z) + 0.5,
+            ),
+            None => {
+                let top = self.get_top_block(Vector2::new(spawn.0.x, spawn.0.z)).await;
+                Vector3::new(
+                    f64::from(spawn.0.x),
+                    f64::from(top + 1),
+                    f64::from(spawn.0.z),
+                )
+            }
+        };
 
         log::debug!("Sending player teleport to {}", player.gameprofile.name);
         player.clone().request_teleport(position, yaw, pitch).await;
@@ -899,7 +1165,7 @@ impl World {
 
         // TODO: difficulty, exp bar, status effect
 
-        self.send_world_info(player, position, yaw, pitch).await;
+        self.send_world_info(player).await;
     }
 
     // NOTE: This function doesn't actually await on anything, it just spawns two tokio tasks
@@ -954,8 +1220,9 @@ impl World {
                 #[cfg(debug_assertions)]
                 if position == (0, 0).into() {
                     use pumpkin_protocol::client::play::CChunkData;
+                    let world = player.world().await;
                     let binding = chunk.read().await;
-                    let packet = CChunkData(&binding);
+                    let packet = CChunkData::new(&binding, world.anti_xray.as_ref());
                     let mut test = Vec::new();
                     packet.write_packet_data(&mut test).unwrap();
                     let len = test.len();
@@ -1037,6 +1304,26 @@ impl World {
         None
     }
 
+    /// The name the scoreboard knows an entity by: a player's name, the UUID of any other entity.
+    pub async fn scoreboard_name(&self, entity: &Entity) -> String {
+        match self.get_player_by_id(entity.entity_id).await {
+            Some(player) => player.gameprofile.name.clone(),
+            None => entity.entity_uuid.to_string(),
+        }
+    }
+
+    /// The name an entity is shown with, e.g. in death messages: a player's name, the translated
+    /// type of any other entity.
+    pub async fn display_name(&self, entity: &Entity) -> TextComponent {
+        match self.get_player_by_id(entity.entity_id).await {
+            Some(player) => TextComponent::text(player.gameprofile.name.clone()),
+            None => TextComponent::translate(
+                format!("entity.minecraft.{}", entity.entity_type.resource_name),
+                [],
+            ),
+        }
+    }
+
     /// Gets an entity by an entity id
     pub async fn get_entity_by_id(&self, id: EntityId) -> Option<Arc<dyn EntityBase>> {
         for entity in self.entities.read().await.values() {
@@ -1047,6 +1334,14 @@ impl World {
         None
     }
 
+    /// Gets any entity by its id, players included.
+    pub async fn get_any_entity_by_id(&self, id: EntityId) -> Option<Arc<dyn EntityBase>> {
+        if let Some(player) = self.get_player_by_id(id).await {
+            return Some(player);
+        }
+        self.get_entity_by_id(id).await
+    }
+
     /// Gets a `Player` by a username
     pub async fn get_player_by_name(&self, name: &str) -> Option<Arc<Player>> {
         let lowercase = name.to_lowercase();
@@ -1206,17 +1501,30 @@ impl World {
     ///
     /// - This function assumes `broadcast_packet_expect` and `remove_entity` are defined elsewhere.
     /// - The disconnect message sending is currently optional. Consider making it a configurable option.
-    pub async fn remove_player(&self, player: &Arc<Player>, fire_event: bool) {
-        self.players
-            .write()
-            .await
-            .remove(&player.gameprofile.id)
-            .unwrap();
+    /// - Returns `false` without doing anything if this player isn't in the world (anymore), e.g. when
+    ///   a duplicate login already replaced them.
+    pub async fn remove_player(self: &Arc<Self>, player: &Arc<Player>, fire_event: bool) -> bool {
+        {
+            let mut players = self.players.write().await;
+            if !players
+                .get(&player.gameprofile.id)
+                .is_some_and(|current| Arc::ptr_eq(current, player))
+            {
+                return false;
+            }
+            players.remove(&player.gameprofile.id);
+        }
         let uuid = player.gameprofile.id;
         self.broadcast_packet_except(&[player.gameprofile.id], &CRemovePlayerInfo::new(&[uuid]))
             .await;
-        self.broadcast_packet_all(&CRemoveEntities::new(&[player.entity_id().into()]))
-            .await;
+        self.despawn_for_trackers(player.entity_id()).await;
+        self.forget_rider(player.entity_id()).await;
+        self.forget_leashes(&player.living_entity.entity).await;
+        // Otherwise the destroy stage of the block they were breaking lingers for everyone else
+        if let Some(mining_pos) = player.stop_mining().await {
+            self.set_block_breaking(&player.living_entity.entity, mining_pos, -1)
+                .await;
+        }
 
         if fire_event {
             let msg_comp = TextComponent::translate(
@@ -1240,6 +1548,7 @@ impl World {
                 log::info!("{}", event.leave_message.clone().to_pretty_console());
             }
         }
+        true
     }
 
     pub fn create_entity(
@@ -1252,18 +1561,189 @@ impl World {
     }
 
     /// Adds an entity to the world.
+    ///
+    /// It is spawned right away for the players in its tracking range, so that packets sent
+    /// directly afterwards (e.g. metadata) reach them.
     pub async fn spawn_entity(&self, entity: Arc<dyn EntityBase>) {
+        self.entities
+            .write()
+            .await
+            .insert(entity.get_entity().entity_uuid, entity.clone());
         let base_entity = entity.get_entity();
-        self.broadcast_packet_all(&base_entity.create_spawn_packet())
-            .await;
-        let mut current_living_entities = self.entities.write().await;
-        current_living_entities.insert(base_entity.entity_uuid, entity);
+        let config = &advanced_config().entity_tracking;
+        let pos = base_entity.pos.load();
+        for player in self.players.read().await.values() {
+            let view_distance = chunker::get_view_distance(player).await.get();
+            if is_in_tracking_range(
+                config,
+                &base_entity.entity_type,
+                player.living_entity.entity.pos.load(),
+                pos,
+                view_distance,
+            ) && player
+                .tracked_entities
+                .lock()
+                .await
+                .track(base_entity.entity_id)
+            {
+                Self::send_entity_to(player, entity.as_ref()).await;
+            }
+        }
     }
 
-    pub async fn remove_entity(&self, entity: &Entity) {
+    pub async fn remove_entity(self: &Arc<Self>, entity: &Entity) {
         self.entities.write().await.remove(&entity.entity_uuid);
-        self.broadcast_packet_all(&CRemoveEntities::new(&[entity.entity_id.into()]))
-            .await;
+        self.despawn_for_trackers(entity.entity_id).await;
+        self.forget_rider(entity.entity_id).await;
+        self.forget_leashes(entity).await;
+    }
+
+    /// Lets `passenger` ride `vehicle` and tells every player about it.
+    pub async fn mount_entity(
+        &self,
+        passenger: EntityId,
+        vehicle: EntityId,
+    ) -> Result<(), RideError> {
+        let packet = self.riding.lock().await.mount(passenger, vehicle)?;
+        self.broadcast_packet_all(&packet).await;
+        Ok(())
+    }
+
+    /// Stops `passenger` from riding and returns the vehicle it was riding.
+    pub async fn dismount_entity(&self, passenger: EntityId) -> Option<EntityId> {
+        let (vehicle, packet) = self.riding.lock().await.dismount(passenger)?;
+        self.broadcast_packet_all(&packet).await;
+        Some(vehicle)
+    }
+
+    /// Keeps every passenger on top of its vehicle after the vehicles moved.
+    async fn tick_passengers(&self) {
+        let riders = self.riding.lock().await.riders();
+        for (passenger, vehicle) in riders {
+            let (Some(passenger), Some(vehicle)) = (
+                self.get_any_entity_by_id(passenger).await,
+                self.get_any_entity_by_id(vehicle).await,
+            ) else {
+                continue;
+            };
+            let vehicle = vehicle.get_entity();
+            let pos = vehicle.pos.load();
+            passenger.get_entity().set_pos(Vector3::new(
+                pos.x,
+                pos.y + f64::from(vehicle.height()),
+                pos.z,
+            ));
+        }
+    }
+
+    async fn forget_rider(&self, entity_id: EntityId) {
+        let packets = self.riding.lock().await.remove_entity(entity_id);
+        for packet in packets {
+            self.broadcast_packet_all(&packet).await;
+        }
+    }
+
+    /// Puts `leashed` on the leash of `holder` and tells every player about it.
+    pub async fn leash_entity(&self, leashed: EntityId, holder: EntityId) {
+        let packet = self.leashes.lock().await.attach(leashed, holder);
+        self.broadcast_packet_all(&packet).await;
+    }
+
+    /// Takes `leashed` off its leash, dropping the lead where it stands, and returns its old
+    /// holder.
+    pub async fn unleash_entity(self: &Arc<Self>, leashed: &Entity) -> Option<EntityId> {
+        let (holder, packet) = self.leashes.lock().await.detach(leashed.entity_id)?;
+        self.broadcast_packet_all(&packet).await;
+        drop_stack(
+            self,
+            &leashed.block_pos.load(),
+            ItemStack::new(1, Item::LEAD),
+        )
+        .await;
+        Some(holder)
+    }
+
+    /// Ties the mobs `holder` leads within reach of `fence` to the lead knot on it, spawning the
+    /// knot first if the fence has none. Returns whether any mob was tied.
+    pub async fn tie_leashes_to_fence(self: &Arc<Self>, holder: EntityId, fence: BlockPos) -> bool {
+        let knot_pos = LeashKnotEntity::position(&fence);
+        let leashed = self.leashes.lock().await.leashed_to(holder);
+        let mut in_reach = Vec::new();
+        for id in leashed {
+            if let Some(entity) = self.get_entity_by_id(id).await {
+                if entity
+                    .get_entity()
+                    .pos
+                    .load()
+                    .squared_distance_to_vec(knot_pos)
+                    <= FENCE_TIE_DISTANCE * FENCE_TIE_DISTANCE
+                {
+                    in_reach.push(id);
+                }
+            }
+        }
+
+        let mut new_knot = None;
+        let Some((_, packets)) =
+            self.leashes
+                .lock()
+                .await
+                .tie_to_fence(holder, &in_reach, fence, || {
+                    let knot = LeashKnotEntity::new(
+                        self.create_entity(knot_pos, EntityType::LEASH_KNOT),
+                        fence,
+                    );
+                    let id = knot.get_entity().entity_id;
+                    new_knot = Some(Arc::new(knot));
+                    id
+                })
+        else {
+            return false;
+        };
+        if let Some(knot) = new_knot {
+            self.spawn_entity(knot).await;
+        }
+        for packet in packets {
+            self.broadcast_packet_all(&packet).await;
+        }
+        self.play_sound(
+            Sound::EntityLeashKnotPlace,
+            SoundCategory::Blocks,
+            &knot_pos,
+        )
+        .await;
+        true
+    }
+
+    /// Breaks every leash held by or attached to an entity which left the world, dropping the
+    /// leads.
+    async fn forget_leashes(self: &Arc<Self>, entity: &Entity) {
+        let released = self.leashes.lock().await.remove_entity(entity.entity_id);
+        for (leashed, packet) in released {
+            self.broadcast_packet_all(&packet).await;
+            let pos = if leashed == entity.entity_id {
+                Some(entity.block_pos.load())
+            } else {
+                self.get_entity_by_id(leashed)
+                    .await
+                    .map(|leashed| leashed.get_entity().block_pos.load())
+            };
+            if let Some(pos) = pos {
+                drop_stack(self, &pos, ItemStack::new(1, Item::LEAD)).await;
+            }
+        }
+    }
+
+    /// Removes the entity from every client that is tracking it.
+    async fn despawn_for_trackers(&self, entity_id: EntityId) {
+        for player in self.players.read().await.values() {
+            if player.tracked_entities.lock().await.untrack(entity_id) {
+                player
+                    .client
+                    .enqueue_packet(&CRemoveEntities::new(&[entity_id.into()]))
+                    .await;
+            }
+        }
     }
 
     pub async fn set_block_breaking(&self, from: &Entity, location: BlockPos, progress: i32) {
@@ -1274,29 +1754,49 @@ impl World {
         .await;
     }
 
-    /// Sets a block and returns the old block id
-    #[expect(clippy::too_many_lines)]
+    /// Sets a block and returns the old block id. Positions outside of the world's build height
+    /// are left alone, they are always air.
     pub async fn set_block_state(
         self: &Arc<Self>,
         position: &BlockPos,
         block_state_id: BlockStateId,
         flags: BlockFlags,
     ) -> BlockStateId {
+        self.try_set_block_state(position, block_state_id, flags)
+            .await
+            .unwrap_or(Block::AIR.default_state_id)
+    }
+
+    /// Sets a block and returns the old block id, or `BlockOutOfWorldBounds` if the position is
+    /// outside of the world's build height.
+    #[expect(clippy::too_many_lines)]
+    pub async fn try_set_block_state(
+        self: &Arc<Self>,
+        position: &BlockPos,
+        block_state_id: BlockStateId,
+        flags: BlockFlags,
+    ) -> Result<BlockStateId, GetBlockError> {
+        if !self.build_height.contains(position.0.y) {
+            return Err(GetBlockError::BlockOutOfWorldBounds);
+        }
         let chunk = self.get_chunk(position).await;
         let (_, relative) = position.chunk_and_chunk_relative_position();
         let mut chunk = chunk.write().await;
-        let replaced_block_state_id = chunk
-            .section
-            .get_block_absolute_y(relative.x as usize, relative.y, relative.z as usize)
-            .unwrap();
+        let Some(replaced_block_state_id) = chunk.section.get_block_absolute_y(
+            relative.x as usize,
+            relative.y,
+            relative.z as usize,
+        ) else {
+            return Err(GetBlockError::BlockOutOfWorldBounds);
+        };
 
         if replaced_block_state_id == block_state_id {
-            return block_state_id;
+            return Ok(block_state_id);
         }
 
         chunk.dirty = true;
 
-        chunk.section.set_block_absolute_y(
+        chunk.set_block_absolute_y(
             relative.x as usize,
             relative.y,
             relative.z as usize,
@@ -1307,6 +1807,8 @@ impl World {
             .await
             .insert(*position, block_state_id);
         drop(chunk);
+        self.reveal_exposed_neighbours(position, block_state_id)
+            .await;
 
         let old_block = Block::from_state_id(replaced_block_state_id).unwrap();
         let new_block = Block::from_state_id(block_state_id).unwrap();
@@ -1398,7 +1900,30 @@ impl World {
             }
         }
 
-        replaced_block_state_id
+        Ok(replaced_block_state_id)
+    }
+
+    /// Sends the real blocks next to a block which players can now see through, as anti-xray may
+    /// have sent them as other blocks.
+    async fn reveal_exposed_neighbours(&self, position: &BlockPos, block_state_id: BlockStateId) {
+        let Some(anti_xray) = &self.anti_xray else {
+            return;
+        };
+        if !is_see_through(block_state_id) {
+            return;
+        }
+        for direction in BlockDirection::all() {
+            let neighbour = position.offset(direction.to_offset());
+            let Ok(neighbour_state_id) = self.get_block_state_id(&neighbour).await else {
+                continue;
+            };
+            if anti_xray.is_obfuscated(neighbour_state_id, neighbour.0.y) {
+                self.unsent_block_changes
+                    .lock()
+                    .await
+                    .insert(neighbour, neighbour_state_id);
+            }
+        }
     }
 
     pub async fn schedule_block_tick(
@@ -1475,25 +2000,18 @@ impl World {
             .await;
 
         if !event.cancelled {
-            let new_state_id = if broken_block
-                .properties(broken_block_state.id)
-                .and_then(|properties| {
-                    properties
-                        .to_props()
-                        .into_iter()
-                        .find(|p| p.0 == "waterlogged")
-                        .map(|(_, value)| value == true.to_string())
-                })
-                .unwrap_or(false)
-            {
-                // Broken block was waterlogged
-                let mut water_props = WaterLikeProperties::default(&Block::WATER);
-                water_props.level = Integer0To15::L15;
-                water_props.to_state_id(&Block::WATER)
-            } else {
-                0
-            };
+            let new_state_id =
+                if block::get_block_property(&broken_block, broken_block_state.id, "waterlogged")
+                    .is_some_and(|waterlogged| waterlogged == "true")
+                {
+                    // Broken block was waterlogged
+                    let water = Block::WATER.default_state_id;
+                    block::with_block_property(&Block::WATER, water, "level", "15").unwrap_or(water)
+                } else {
+                    0
+                };
 
+            let block_entity = self.get_block_entity(position).await;
             let broken_state_id = self.set_block_state(position, new_state_id, flags).await;
 
             let particles_packet = CWorldEvent::new(
@@ -1504,7 +2022,20 @@ impl World {
             );
 
             if !flags.contains(BlockFlags::SKIP_DROPS) {
-                block::drop_loot(self, &broken_block, position, true, broken_state_id).await;
+                let tool = match &cause {
+                    Some(player) => player.inventory().lock().await.held_item().cloned(),
+                    None => None,
+                };
+                block::drop_loot(
+                    self,
+                    &broken_block,
+                    position,
+                    true,
+                    broken_state_id,
+                    block_entity,
+                    tool.as_ref(),
+                )
+                .await;
             }
 
             match cause {
@@ -1531,6 +2062,20 @@ impl World {
         }
     }
 
+    /// The block at `position`, or `None` if its chunk isn't loaded or it is outside of the
+    /// world. Unlike [`Self::get_block`] this never loads or generates the chunk.
+    pub async fn get_loaded_block(&self, position: &BlockPos) -> Option<Block> {
+        let (chunk_coordinate, relative) = position.chunk_and_chunk_relative_position();
+        let chunk = self.level.try_get_chunk(chunk_coordinate)?.clone();
+        let chunk = chunk.read().await;
+        let id = chunk.section.get_block_absolute_y(
+            relative.x as usize,
+            relative.y,
+            relative.z as usize,
+        )?;
+        get_block_by_state_id(id)
+    }
+
     pub async fn get_block_state_id(
         &self,
         position: &BlockPos,
@@ -1589,6 +2134,30 @@ impl World {
         get_block_and_state_by_state_id(id).ok_or(GetBlockError::InvalidBlockId)
     }
 
+    /// Gets the value of a property of the block at `position`, e.g. `facing` of stairs.
+    /// Returns `None` if the block doesn't have that property.
+    pub async fn get_block_property(&self, position: &BlockPos, name: &str) -> Option<String> {
+        let id = self.get_block_state_id(position).await.ok()?;
+        let block = get_block_by_state_id(id)?;
+        block::get_block_property(&block, id, name)
+    }
+
+    /// Sets a single property of the block at `position` and keeps all other properties.
+    /// Returns the new state id, or `None` if the block doesn't have that property or value.
+    pub async fn set_block_property(
+        self: &Arc<Self>,
+        position: &BlockPos,
+        name: &str,
+        value: &str,
+        flags: BlockFlags,
+    ) -> Option<BlockStateId> {
+        let id = self.get_block_state_id(position).await.ok()?;
+        let block = get_block_by_state_id(id)?;
+        let new_id = block::with_block_property(&block, id, name, value)?;
+        self.set_block_state(position, new_id, flags).await;
+        Some(new_id)
+    }
+
     /// Updates neighboring blocks of a block
     pub async fn update_neighbors(
         self: &Arc<Self>,
@@ -1721,15 +2290,55 @@ impl World {
 
         chunk.block_entities.insert(block_pos, block_entity);
         chunk.dirty = true;
+        self.level.block_entities_changed(&chunk);
     }
 
     pub async fn remove_block_entity(&self, block_pos: &BlockPos) {
         let chunk = self.get_chunk(block_pos).await;
         let mut chunk: tokio::sync::RwLockWriteGuard<ChunkData> = chunk.write().await;
-        chunk.block_entities.remove(block_pos);
+        if chunk.block_entities.remove(block_pos).is_some() {
+            self.level.block_entities_changed(&chunk);
+        }
         chunk.dirty = true;
     }
 
+    /// Places a structure template with its origin at `origin` and spawns its entities, returning
+    /// the amount of placed blocks. Structure voids keep the blocks that are already there.
+    pub async fn place_structure(
+        self: &Arc<Self>,
+        template: &StructureTemplate,
+        origin: BlockPos,
+        settings: &StructurePlaceSettings,
+    ) -> usize {
+        let mut placed_blocks = 0;
+        for block in template.placed_blocks(settings) {
+            let pos = origin.offset(block.pos);
+            self.set_block_state(&pos, block.state_id, BlockFlags::FORCE_STATE)
+                .await;
+            if let Some(mut nbt) = block.nbt.filter(|nbt| nbt.get_string("id").is_some()) {
+                nbt.put_int("x", pos.0.x);
+                nbt.put_int("y", pos.0.y);
+                nbt.put_int("z", pos.0.z);
+                if let Some(block_entity) = block_entity_from_nbt(&nbt) {
+                    self.add_block_entity(block_entity).await;
+                }
+            }
+            placed_blocks += 1;
+        }
+        for entity in template.placed_entities(settings) {
+            let Some(entity_type) = entity
+                .id()
+                .and_then(|id| EntityType::from_name(id.strip_prefix("minecraft:").unwrap_or(id)))
+            else {
+                continue;
+            };
+            let mob = mob::from_type(entity_type, origin.0.to_f64() + entity.pos, self).await;
+            mob.get_entity().set_rotation(entity.yaw, entity.pitch);
+            self.spawn_entity(mob).await;
+        }
+        placed_blocks
+    }
+
     pub async fn raytrace(
         self: &Arc<Self>,
         start_pos: Vector3<f64>,
@@ -1832,3 +2441,148 @@ impl World {
         (None, None)
     }
 }
+
+/// How far above the top of a block an entity may be while still standing on it
+const GROUND_DISTANCE: f64 = 1.0e-3;
+
+/// Whether an entity with the bounding box `entity_box` stands on top of one of the collision
+/// shapes of the block `state_id` at `pos`.
+fn stands_on(entity_box: &BoundingBox, pos: BlockPos, state_id: BlockStateId) -> bool {
+    get_block_collision_shapes(state_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|shape| shape.at_pos(pos))
+        .any(|shape| {
+            (entity_box.min.y - shape.max.y).abs() <= GROUND_DISTANCE
+                && shape.min.x < entity_box.max.x
+                && shape.max.x > entity_box.min.x
+                && shape.min.z < entity_box.max.z
+                && shape.max.z > entity_box.min.z
+        })
+}
+
+/// Where a player respawns: at their respawn point if they have a valid one in this world,
+/// otherwise at the world spawn.
+fn respawn_location(
+    respawn_point: Option<&RespawnPoint>,
+    level_info: &LevelData,
+) -> (BlockPos, f32) {
+    respawn_point.map_or_else(
+        || level_info.spawn_point(),
+        |point| (point.position, point.angle),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::Ordering;
+
+    use pumpkin_data::{Block, entity::EntityType};
+    use pumpkin_registry::{DimensionType, DimensionTypes};
+    use pumpkin_util::math::boundingbox::{BoundingBox, EntityDimensions};
+    use pumpkin_util::math::{position::BlockPos, vector3::Vector3};
+    use pumpkin_world::{
+        level::LevelFolder,
+        world_info::{LevelData, WorldInfoReader, WorldInfoWriter, anvil::AnvilLevelInfo},
+    };
+
+    use super::{BlockFlags, BuildHeight, respawn_location, stands_on};
+    use crate::entity::mob;
+    use crate::entity::player::RespawnPoint;
+    use crate::test_utils::test_server;
+
+    #[test]
+    fn world_spawn_is_saved_and_used_without_respawn_point() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let level_folder = LevelFolder {
+            root_folder: temp_dir.path().to_path_buf(),
+            region_folder: temp_dir.path().join("region"),
+        };
+
+        let mut info = LevelData::default();
+        info.set_spawn_point(BlockPos::new(120, 70, -45), 90.0);
+        AnvilLevelInfo
+            .write_world_info(info, &level_folder)
+            .unwrap();
+        let info = AnvilLevelInfo.read_world_info(&level_folder).unwrap();
+
+        assert_eq!(
+            respawn_location(None, &info),
+            (BlockPos::new(120, 70, -45), 90.0)
+        );
+
+        let bed = RespawnPoint {
+            dimension: DimensionType::Overworld,
+            position: BlockPos::new(3, 64, 3),
+            angle: 0.0,
+            forced: false,
+        };
+        assert_eq!(
+            respawn_location(Some(&bed), &info),
+            (BlockPos::new(3, 64, 3), 0.0)
+        );
+    }
+
+    #[test]
+    fn blocks_can_be_placed_up_to_the_dimensions_build_limit() {
+        let dimension_types = DimensionTypes::default();
+
+        let overworld = BuildHeight::from_dimension(&dimension_types, &DimensionType::Overworld);
+        assert_eq!(overworld.max_y(), 319);
+        assert!(overworld.contains(319));
+        assert!(!overworld.contains(320));
+        assert!(overworld.contains(-64));
+        assert!(!overworld.contains(-65));
+
+        let nether = BuildHeight::from_dimension(&dimension_types, &DimensionType::TheNether);
+        assert!(nether.contains(0));
+        assert!(nether.contains(255));
+        assert!(!nether.contains(256));
+        assert!(!nether.contains(-1));
+    }
+
+    #[test]
+    fn entities_stand_on_top_of_collision_shapes() {
+        let pos = BlockPos::new(0, 63, 0);
+        let stone = Block::STONE.default_state_id;
+        let size = EntityDimensions {
+            width: 0.9,
+            height: 0.9,
+        };
+        let standing = BoundingBox::new_from_pos(0.5, 64.0, 0.5, &size);
+        assert!(stands_on(&standing, pos, stone));
+        let hovering = BoundingBox::new_from_pos(0.5, 64.5, 0.5, &size);
+        assert!(!stands_on(&hovering, pos, stone));
+        // Nothing to stand on in the air
+        assert!(!stands_on(&standing, pos, Block::AIR.default_state_id));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn entities_standing_on_magma_get_burned() {
+        let server = test_server();
+        let world = server.worlds.read().await[0].clone();
+        let magma = BlockPos::new(0, 200, 0);
+        world
+            .set_block_state(
+                &magma,
+                Block::MAGMA_BLOCK.default_state_id,
+                BlockFlags::NOTIFY_ALL,
+            )
+            .await;
+        let pig = mob::from_type(EntityType::PIG, Vector3::new(0.5, 201.0, 0.5), &world).await;
+        world.spawn_entity(pig.clone()).await;
+        let living = pig.get_living_entity().unwrap();
+        let health = living.health.load();
+
+        world.tick_block_contact(pig.as_ref()).await;
+        assert!(pig.get_entity().on_ground.load(Ordering::Relaxed));
+        assert_eq!(living.health.load(), health - 1.0);
+
+        // Jumping off it keeps the pig safe
+        living.time_until_regen.store(0, Ordering::Relaxed);
+        pig.get_entity().set_pos(Vector3::new(0.5, 201.5, 0.5));
+        world.tick_block_contact(pig.as_ref()).await;
+        assert!(!pig.get_entity().on_ground.load(Ordering::Relaxed));
+        assert_eq!(living.health.load(), health - 1.0);
+    }
+}
@@ -0,0 +1,670 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, LazyLock},
+};
+
+use async_trait::async_trait;
+use pumpkin_config::{SpawnLimitsConfig, advanced_config};
+use pumpkin_data::{
+    Block,
+    block_properties::get_state_by_state_id,
+    chunk::{Biome, SpawnEntry, SpawnGroup},
+    entity::EntityType,
+};
+use pumpkin_util::{
+    Difficulty,
+    math::{position::BlockPos, vector2::Vector2, vector3::Vector3},
+};
+use pumpkin_world::BlockStateId;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use tokio::sync::Mutex;
+
+use crate::entity::mob::{self, squid::Squid};
+
+use super::{World, chunker};
+
+/// The groups which spawn naturally, misc mobs like villagers never do
+const NATURAL_GROUPS: [SpawnGroup; 7] = [
+    SpawnGroup::Monster,
+    SpawnGroup::Creature,
+    SpawnGroup::Ambient,
+    SpawnGroup::Axolotls,
+    SpawnGroup::UndergroundWaterCreature,
+    SpawnGroup::WaterCreature,
+    SpawnGroup::WaterAmbient,
+];
+/// The configured mob caps are per this many chunks around players, 17x17 like vanilla
+const CHUNKS_PER_CAP: usize = 289;
+/// Mobs don't spawn closer than this to players
+const MIN_PLAYER_DISTANCE: f64 = 24.0;
+/// How many packs spawn around one spot at most
+const PACKS_PER_SPOT: u32 = 3;
+/// How many places we try for each mob of a pack
+const TRIES_PER_MOB: u32 = 2;
+/// How far the mobs of a pack wander from each other, horizontally
+const PACK_SPREAD: i32 = 5;
+/// How far we look for blocks giving off light, we don't keep light levels around
+const BLOCK_LIGHT_RADIUS: i32 = 3;
+
+/// What a mob finds at a spot it could spawn at.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct SpawnSpot {
+    /// Whether a mob fits there, nothing to collide with at its feet and head
+    pub free: bool,
+    pub in_water: bool,
+    /// Whether the block below can be stood on
+    pub solid_floor: bool,
+    /// Whether the block below is grass, where animals spawn
+    pub grassy_floor: bool,
+    pub sky_light: u8,
+    pub block_light: u8,
+    /// Whether a player is too close for mobs to spawn
+    pub near_player: bool,
+}
+
+/// Where the spawner gets its spots from.
+#[async_trait]
+pub trait SpawnView: Sync {
+    async fn spot(&self, pos: &BlockPos) -> SpawnSpot;
+
+    /// The sea level, which water mobs spawn relative to.
+    fn sea_level(&self) -> i32;
+}
+
+/// Whether a mob of `group` may spawn at `spot`.
+pub fn can_spawn(group: SpawnGroup, spot: &SpawnSpot, rng: &mut impl Rng) -> bool {
+    if !spot.free || spot.near_player {
+        return false;
+    }
+    let light = spot.sky_light.max(spot.block_light);
+    match group {
+        // Monsters want it dark, torches keep them away entirely
+        SpawnGroup::Monster => {
+            !spot.in_water
+                && spot.solid_floor
+                && spot.block_light == 0
+                && spot.sky_light <= rng.gen_range(0..8)
+        }
+        SpawnGroup::Creature => !spot.in_water && spot.grassy_floor && light > 8,
+        SpawnGroup::Ambient => !spot.in_water && light <= rng.gen_range(0..4),
+        SpawnGroup::Axolotls | SpawnGroup::UndergroundWaterCreature => {
+            spot.in_water && spot.sky_light == 0
+        }
+        SpawnGroup::WaterCreature | SpawnGroup::WaterAmbient => spot.in_water,
+        SpawnGroup::Misc => false,
+    }
+}
+
+/// Whether a mob of `entity_type` may spawn at the height of `pos`, on top of what its group
+/// needs. Most mobs don't care.
+pub fn height_allowed(entity_type: EntityType, pos: &BlockPos, sea_level: i32) -> bool {
+    match entity_type {
+        EntityType::SQUID | EntityType::GLOW_SQUID => {
+            Squid::spawn_height_allowed(entity_type, pos.0.y, sea_level)
+        }
+        _ => true,
+    }
+}
+
+/// Picks one of `entries` with a chance proportional to its weight.
+pub fn pick_entry<'a>(entries: &'a [SpawnEntry], rng: &mut impl Rng) -> Option<&'a SpawnEntry> {
+    let total: u32 = entries.iter().map(|entry| entry.weight).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = rng.gen_range(0..total);
+    entries.iter().find(|entry| {
+        if roll < entry.weight {
+            return true;
+        }
+        roll -= entry.weight;
+        false
+    })
+}
+
+/// Finds places for packs of mobs of `group` around `origin`, picking them from `entries`.
+/// Returns no more than `room` of them.
+pub async fn find_packs<V: SpawnView + ?Sized>(
+    view: &V,
+    group: SpawnGroup,
+    origin: BlockPos,
+    entries: &[SpawnEntry],
+    room: usize,
+    rng: &mut (impl Rng + Send),
+) -> Vec<(EntityType, BlockPos)> {
+    let mut spawns = Vec::new();
+    for _ in 0..PACKS_PER_SPOT {
+        let Some(entry) = pick_entry(entries, rng) else {
+            break;
+        };
+        let size = rng.gen_range(entry.min_count..=entry.max_count.max(entry.min_count));
+        let mut pos = origin;
+        let mut spawned = 0;
+        for _ in 0..size * TRIES_PER_MOB {
+            if spawned == size || spawns.len() == room {
+                break;
+            }
+            pos = pos.offset(Vector3::new(
+                rng.gen_range(0..=PACK_SPREAD) - rng.gen_range(0..=PACK_SPREAD),
+                0,
+                rng.gen_range(0..=PACK_SPREAD) - rng.gen_range(0..=PACK_SPREAD),
+            ));
+            if spawns.iter().any(|(_, taken)| *taken == pos) {
+                continue;
+            }
+            let spot = view.spot(&pos).await;
+            if can_spawn(group, &spot, rng)
+                && height_allowed(entry.entity_type, &pos, view.sea_level())
+            {
+                spawns.push((entry.entity_type, pos));
+                spawned += 1;
+            }
+        }
+    }
+    spawns
+}
+
+/// Which mob cap mobs of `entity_type` count towards.
+#[must_use]
+pub fn spawn_group(entity_type: EntityType) -> SpawnGroup {
+    match entity_type {
+        EntityType::ZOMBIE
+        | EntityType::ZOMBIE_VILLAGER
+        | EntityType::HUSK
+        | EntityType::DROWNED
+        | EntityType::SKELETON
+        | EntityType::STRAY
+        | EntityType::BOGGED
+        | EntityType::WITHER_SKELETON
+        | EntityType::CREEPER
+        | EntityType::SPIDER
+        | EntityType::CAVE_SPIDER
+        | EntityType::ENDERMAN
+        | EntityType::WITCH
+        | EntityType::SLIME
+        | EntityType::MAGMA_CUBE
+        | EntityType::BLAZE
+        | EntityType::GHAST
+        | EntityType::ZOMBIFIED_PIGLIN
+        | EntityType::PIGLIN
+        | EntityType::PIGLIN_BRUTE
+        | EntityType::HOGLIN
+        | EntityType::ZOGLIN
+        | EntityType::SILVERFISH
+        | EntityType::ENDERMITE
+        | EntityType::GUARDIAN
+        | EntityType::ELDER_GUARDIAN
+        | EntityType::PHANTOM
+        | EntityType::PILLAGER
+        | EntityType::VINDICATOR
+        | EntityType::EVOKER
+        | EntityType::ILLUSIONER
+        | EntityType::RAVAGER
+        | EntityType::VEX
+        | EntityType::SHULKER
+        | EntityType::WARDEN
+        | EntityType::BREEZE
+        | EntityType::CREAKING
+        | EntityType::GIANT
+        | EntityType::WITHER
+        | EntityType::ENDER_DRAGON => SpawnGroup::Monster,
+        EntityType::PIG
+        | EntityType::COW
+        | EntityType::MOOSHROOM
+        | EntityType::SHEEP
+        | EntityType::CHICKEN
+        | EntityType::RABBIT
+        | EntityType::HORSE
+        | EntityType::DONKEY
+        | EntityType::MULE
+        | EntityType::SKELETON_HORSE
+        | EntityType::ZOMBIE_HORSE
+        | EntityType::LLAMA
+        | EntityType::TRADER_LLAMA
+        | EntityType::WOLF
+        | EntityType::CAT
+        | EntityType::OCELOT
+        | EntityType::PARROT
+        | EntityType::FOX
+        | EntityType::PANDA
+        | EntityType::POLAR_BEAR
+        | EntityType::BEE
+        | EntityType::GOAT
+        | EntityType::FROG
+        | EntityType::CAMEL
+        | EntityType::SNIFFER
+        | EntityType::ARMADILLO
+        | EntityType::TURTLE
+        | EntityType::STRIDER
+        | EntityType::ALLAY
+        | EntityType::WANDERING_TRADER => SpawnGroup::Creature,
+        EntityType::BAT => SpawnGroup::Ambient,
+        EntityType::AXOLOTL => SpawnGroup::Axolotls,
+        EntityType::GLOW_SQUID => SpawnGroup::UndergroundWaterCreature,
+        EntityType::SQUID | EntityType::DOLPHIN => SpawnGroup::WaterCreature,
+        EntityType::COD
+        | EntityType::SALMON
+        | EntityType::TROPICAL_FISH
+        | EntityType::PUFFERFISH
+        | EntityType::TADPOLE => SpawnGroup::WaterAmbient,
+        _ => SpawnGroup::Misc,
+    }
+}
+
+/// The configured cap of `group`, for the chunks around a single player.
+fn cap(config: &SpawnLimitsConfig, group: SpawnGroup) -> usize {
+    let cap = match group {
+        SpawnGroup::Monster => config.monsters,
+        SpawnGroup::Creature => config.creatures,
+        SpawnGroup::Ambient => config.ambient,
+        SpawnGroup::Axolotls => config.axolotls,
+        SpawnGroup::UndergroundWaterCreature => config.underground_water_creatures,
+        SpawnGroup::WaterCreature => config.water_creatures,
+        SpawnGroup::WaterAmbient => config.water_ambient,
+        SpawnGroup::Misc => 0,
+    };
+    cap as usize
+}
+
+/// How bright the sky is at `time_of_day`: full at noon, 4 at midnight.
+#[must_use]
+pub fn sky_brightness(time_of_day: i64) -> u8 {
+    let time = time_of_day.rem_euclid(24000);
+    // Dusk and dawn take a thousand ticks each
+    let darkness = match time {
+        0..12000 => 0,
+        12000..13000 => (time - 12000) * 11 / 1000,
+        13000..23000 => 11,
+        _ => (24000 - time) * 11 / 1000,
+    };
+    (15 - darkness) as u8
+}
+
+/// The block states giving off light, indexed by their id
+static LUMINANCE: LazyLock<Box<[u8]>> = LazyLock::new(|| {
+    (0..=u16::MAX)
+        .map_while(get_state_by_state_id)
+        .map(|state| state.luminance)
+        .collect()
+});
+
+fn luminance(state_id: BlockStateId) -> u8 {
+    LUMINANCE.get(state_id as usize).copied().unwrap_or(0)
+}
+
+/// The spots of a world around its players.
+struct SpawnArea<'a> {
+    world: &'a World,
+    players: &'a [Vector3<f64>],
+    sky_brightness: u8,
+    /// The blocks giving off light in the chunks looked at during this tick, with their light
+    light_sources: Mutex<HashMap<Vector2<i32>, Arc<[(BlockPos, u8)]>>>,
+}
+
+impl SpawnArea<'_> {
+    /// Whether nothing blocks the sky above `pos`, going by the motion-blocking heightmap.
+    async fn sees_sky(&self, pos: &BlockPos) -> bool {
+        pos.0.y >= self.world.get_motion_blocking_top(pos).await
+    }
+
+    /// The blocks giving off light in the chunk at `chunk_pos`, found once per tick.
+    async fn light_sources(&self, chunk_pos: Vector2<i32>) -> Arc<[(BlockPos, u8)]> {
+        if let Some(sources) = self.light_sources.lock().await.get(&chunk_pos) {
+            return sources.clone();
+        }
+        let origin = BlockPos::new(chunk_pos.x * 16, 0, chunk_pos.z * 16);
+        let chunk = self.world.get_chunk(&origin).await;
+        let sources: Arc<[_]> = {
+            let chunk = chunk.read().await;
+            chunk
+                .section
+                .find_blocks_where(|state| luminance(state) > 0)
+                .into_iter()
+                .filter_map(|(x, y, z)| {
+                    let state = chunk.section.get_block_absolute_y(x, y, z)?;
+                    Some((
+                        origin.offset(Vector3::new(x as i32, y, z as i32)),
+                        luminance(state),
+                    ))
+                })
+                .collect()
+        };
+        self.light_sources
+            .lock()
+            .await
+            .insert(chunk_pos, sources.clone());
+        sources
+    }
+
+    /// Roughly the light the blocks within [`BLOCK_LIGHT_RADIUS`] of `pos` give off.
+    async fn block_light(&self, pos: &BlockPos) -> u8 {
+        let mut light = 0;
+        let min = pos.offset(Vector3::new(-BLOCK_LIGHT_RADIUS, 0, -BLOCK_LIGHT_RADIUS));
+        let max = pos.offset(Vector3::new(BLOCK_LIGHT_RADIUS, 0, BLOCK_LIGHT_RADIUS));
+        for chunk_x in min.0.x >> 4..=max.0.x >> 4 {
+            for chunk_z in min.0.z >> 4..=max.0.z >> 4 {
+                let sources = self.light_sources(Vector2::new(chunk_x, chunk_z)).await;
+                for (source, luminance) in sources.iter() {
+                    let offset = source.0.sub(&pos.0);
+                    if offset.x.abs().max(offset.y.abs()).max(offset.z.abs()) > BLOCK_LIGHT_RADIUS {
+                        continue;
+                    }
+                    let distance = (offset.x.abs() + offset.y.abs() + offset.z.abs()) as u8;
+                    light = light.max(luminance.saturating_sub(distance));
+                }
+            }
+        }
+        light
+    }
+}
+
+#[async_trait]
+impl SpawnView for SpawnArea<'_> {
+    async fn spot(&self, pos: &BlockPos) -> SpawnSpot {
+        let center = pos.to_f64();
+        let near_player = self.players.iter().any(|player| {
+            player.squared_distance_to_vec(center) < MIN_PLAYER_DISTANCE * MIN_PLAYER_DISTANCE
+        });
+        let Ok((feet, feet_state)) = self.world.get_block_and_block_state(pos).await else {
+            return SpawnSpot::default();
+        };
+        let Ok(head_state) = self.world.get_block_state(&pos.up()).await else {
+            return SpawnSpot::default();
+        };
+        let Ok((floor, floor_state)) = self.world.get_block_and_block_state(&pos.down()).await
+        else {
+            return SpawnSpot::default();
+        };
+        let free = feet != Block::LAVA
+            && feet_state.collision_shapes.is_empty()
+            && head_state.collision_shapes.is_empty();
+        if !free || near_player {
+            // No need to look at the light
+            return SpawnSpot {
+                near_player,
+                ..Default::default()
+            };
+        }
+        SpawnSpot {
+            free,
+            in_water: feet == Block::WATER,
+            solid_floor: floor_state.is_solid(),
+            grassy_floor: floor == Block::GRASS_BLOCK,
+            sky_light: if self.sees_sky(pos).await {
+                self.sky_brightness
+            } else {
+                0
+            },
+            block_light: self.block_light(pos).await,
+            near_player,
+        }
+    }
+
+    fn sea_level(&self) -> i32 {
+        self.world.sea_level
+    }
+}
+
+impl World {
+    /// The y above the highest block of the column at `position` which blocks motion or is a
+    /// liquid, going by the motion-blocking heightmap.
+    pub async fn get_motion_blocking_top(&self, position: &BlockPos) -> i32 {
+        let chunk = self.get_chunk(position).await;
+        let (_, relative) = position.chunk_and_chunk_relative_position();
+        let chunk = chunk.read().await;
+        chunk.section.min_y()
+            + chunk.motion_blocking_height(relative.x as usize, relative.z as usize) as i32
+    }
+
+    /// The biome at `position`.
+    pub async fn get_biome(&self, position: &BlockPos) -> Option<&'static Biome> {
+        let chunk = self.get_chunk(position).await;
+        let (_, relative) = position.chunk_and_chunk_relative_position();
+        let chunk = chunk.read().await;
+        let id = chunk.section.get_biome_absolute_y(
+            relative.x as usize,
+            relative.y,
+            relative.z as usize,
+        )?;
+        Biome::from_id(id)
+    }
+
+    /// Spawns mobs around the players, as long as there are fewer than the mob caps allow.
+    pub(super) async fn tick_natural_spawning(self: &Arc<Self>) {
+        let config = &advanced_config().spawn_limits;
+        if !config.enabled {
+            return;
+        }
+        let players: Vec<_> = self.players.read().await.values().cloned().collect();
+        if players.is_empty() {
+            return;
+        }
+        let world_age = self.level_time.lock().await.world_age;
+        let time_of_day = self.level_time.lock().await.time_of_day;
+        let peaceful = self.level.level_info.read().await.difficulty == Difficulty::Peaceful;
+
+        // The caps grow with the chunks around players, but chunks around several players
+        // only count once
+        let mut areas = Vec::new();
+        let mut chunks = HashSet::new();
+        for player in &players {
+            let center = player.living_entity.entity.chunk_pos.load();
+            let distance = chunker::get_simulation_distance(player).await;
+            let radius = i32::from(distance.get());
+            for x in -radius..=radius {
+                for z in -radius..=radius {
+                    chunks.insert(Vector2::new(center.x + x, center.z + z));
+                }
+            }
+            areas.push((center, distance));
+        }
+
+        let mut counts = [0_usize; NATURAL_GROUPS.len()];
+        for entity in self.entities.read().await.values() {
+            let base = entity.get_entity();
+            let chunk = base.chunk_pos.load();
+            if !areas.iter().any(|(center, distance)| {
+                chunker::is_within_simulation_distance(*center, *distance, chunk)
+            }) {
+                continue;
+            }
+            let group = spawn_group(base.entity_type);
+            if let Some(index) = NATURAL_GROUPS.iter().position(|other| *other == group) {
+                counts[index] += 1;
+            }
+        }
+
+        let positions: Vec<_> = players.iter().map(|player| player.position()).collect();
+        let area = SpawnArea {
+            world: self,
+            players: &positions,
+            sky_brightness: sky_brightness(time_of_day),
+            light_sources: Mutex::new(HashMap::new()),
+        };
+        let mut rng = StdRng::from_entropy();
+        for (center, distance) in &areas {
+            // One spot per player and tick, in a random chunk around them
+            let radius = i32::from(distance.get());
+            let x = (center.x + rng.gen_range(-radius..=radius)) * 16 + rng.gen_range(0..16);
+            let z = (center.z + rng.gen_range(-radius..=radius)) * 16 + rng.gen_range(0..16);
+            let top = self.get_motion_blocking_top(&BlockPos::new(x, 0, z)).await;
+            let y = rng.gen_range(self.build_height.min_y..=top);
+            let origin = BlockPos::new(x, y, z);
+            let Some(biome) = self.get_biome(&origin).await else {
+                continue;
+            };
+
+            for (index, group) in NATURAL_GROUPS.into_iter().enumerate() {
+                if group == SpawnGroup::Monster && peaceful {
+                    continue;
+                }
+                let interval = i64::from(config.ticks_per_creature_spawn.max(1));
+                if group == SpawnGroup::Creature && world_age % interval != 0 {
+                    continue;
+                }
+                let cap = cap(config, group) * chunks.len() / CHUNKS_PER_CAP;
+                if counts[index] >= cap {
+                    continue;
+                }
+                let entries = biome.spawn_entries(group);
+                let room = cap - counts[index];
+                let spawns = find_packs(&area, group, origin, entries, room, &mut rng).await;
+                counts[index] += spawns.len();
+                for (entity_type, pos) in spawns {
+                    // In the middle of the block, not at its corner
+                    let mob = mob::from_type(entity_type, pos.to_f64(), self).await;
+                    self.spawn_entity(mob).await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use async_trait::async_trait;
+    use pumpkin_data::{
+        Block,
+        chunk::{SpawnEntry, SpawnGroup},
+        entity::EntityType,
+    };
+    use pumpkin_util::math::position::BlockPos;
+    use pumpkin_world::BlockStateId;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::{SpawnSpot, SpawnView, find_packs, luminance, sky_brightness};
+
+    const HOSTILE: [SpawnEntry; 1] = [SpawnEntry {
+        entity_type: EntityType::ZOMBIE,
+        min_count: 4,
+        max_count: 4,
+        weight: 100,
+    }];
+
+    /// A flat cave floor, lit as bright as `light`.
+    struct Cave {
+        light: u8,
+    }
+
+    #[async_trait]
+    impl SpawnView for Cave {
+        async fn spot(&self, _pos: &BlockPos) -> SpawnSpot {
+            SpawnSpot {
+                free: true,
+                solid_floor: true,
+                block_light: self.light,
+                ..Default::default()
+            }
+        }
+
+        fn sea_level(&self) -> i32 {
+            63
+        }
+    }
+
+    /// Water all the way down, with the sea level at 63.
+    struct Ocean;
+
+    #[async_trait]
+    impl SpawnView for Ocean {
+        async fn spot(&self, _pos: &BlockPos) -> SpawnSpot {
+            SpawnSpot {
+                free: true,
+                in_water: true,
+                ..Default::default()
+            }
+        }
+
+        fn sea_level(&self) -> i32 {
+            63
+        }
+    }
+
+    #[tokio::test]
+    async fn dark_areas_spawn_monsters_up_to_the_cap() {
+        let mut rng = StdRng::seed_from_u64(12);
+        let origin = BlockPos::new(40, -20, 40);
+        let cap = 10;
+        let mut count = 0;
+        for _ in 0..10 {
+            let room = cap - count;
+            let spawns = find_packs(
+                &Cave { light: 0 },
+                SpawnGroup::Monster,
+                origin,
+                &HOSTILE,
+                room,
+                &mut rng,
+            )
+            .await;
+            assert!(spawns.len() <= room);
+            assert!(spawns.iter().all(|(mob, _)| *mob == EntityType::ZOMBIE));
+            count += spawns.len();
+        }
+        assert_eq!(count, cap);
+
+        // Torches keep them away
+        let lit = find_packs(
+            &Cave { light: 14 },
+            SpawnGroup::Monster,
+            origin,
+            &HOSTILE,
+            cap,
+            &mut rng,
+        )
+        .await;
+        assert!(lit.is_empty());
+        // Animals don't spawn in caves
+        let animals = find_packs(
+            &Cave { light: 0 },
+            SpawnGroup::Creature,
+            origin,
+            &HOSTILE,
+            cap,
+            &mut rng,
+        )
+        .await;
+        assert!(animals.is_empty());
+
+        assert_eq!(sky_brightness(6000), 15);
+        assert_eq!(sky_brightness(18000), 4);
+    }
+
+    #[tokio::test]
+    async fn squids_spawn_at_their_depth() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let squids = [SpawnEntry {
+            entity_type: EntityType::SQUID,
+            min_count: 2,
+            max_count: 2,
+            weight: 100,
+        }];
+        let shallow = BlockPos::new(0, 58, 0);
+        let deep = BlockPos::new(0, 20, 0);
+        let group = SpawnGroup::WaterCreature;
+        let near_surface = find_packs(&Ocean, group, shallow, &squids, 10, &mut rng).await;
+        assert!(!near_surface.is_empty());
+        let too_deep = find_packs(&Ocean, group, deep, &squids, 10, &mut rng).await;
+        assert!(too_deep.is_empty());
+
+        // Glow squids are the other way around
+        let glow_squids = [SpawnEntry {
+            entity_type: EntityType::GLOW_SQUID,
+            min_count: 2,
+            max_count: 2,
+            weight: 100,
+        }];
+        let group = SpawnGroup::UndergroundWaterCreature;
+        let too_shallow = find_packs(&Ocean, group, shallow, &glow_squids, 10, &mut rng).await;
+        assert!(too_shallow.is_empty());
+        let in_the_depths = find_packs(&Ocean, group, deep, &glow_squids, 10, &mut rng).await;
+        assert!(!in_the_depths.is_empty());
+    }
+
+    #[test]
+    fn light_sources_are_looked_up_by_state() {
+        assert_eq!(luminance(Block::TORCH.default_state_id), 14);
+        assert_eq!(luminance(Block::STONE.default_state_id), 0);
+        assert_eq!(luminance(BlockStateId::MAX), 0);
+    }
+}
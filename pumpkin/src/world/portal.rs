@@ -5,11 +5,221 @@ use pumpkin_data::{
     block_properties::{BlockProperties, HorizontalAxis, NetherPortalLikeProperties},
     tag::Tagable,
 };
-use pumpkin_util::math::position::BlockPos;
-use pumpkin_world::block::BlockDirection;
+use pumpkin_registry::DimensionType;
+use pumpkin_util::math::{position::BlockPos, vector2::Vector2, vector3::Vector3};
+use pumpkin_world::{BlockStateId, block::BlockDirection};
 
 use super::{BlockFlags, World};
 
+/// How far from where an entity comes out an existing portal is used, like vanilla. The nether
+/// is 8 times smaller, so is the area searched there.
+const fn portal_search_radius(dimension: &DimensionType) -> i32 {
+    match dimension {
+        DimensionType::TheNether => 16,
+        _ => 128,
+    }
+}
+
+/// How far from where an entity comes out a new portal may be built
+const PORTAL_CREATION_RADIUS: i32 = 16;
+
+impl World {
+    /// Where an entity coming out of a portal at `target` arrives: in the closest portal nearby,
+    /// or in a new one built at a safe spot if there is none.
+    pub async fn portal_arrival(self: &Arc<Self>, target: BlockPos) -> Vector3<f64> {
+        let radius = portal_search_radius(&self.dimension_type);
+        let bottom = match self.find_portal(target, radius).await {
+            Some(bottom) => bottom,
+            None => self.create_portal(target).await,
+        };
+        Vector3::new(
+            f64::from(bottom.0.x) + 0.5,
+            f64::from(bottom.0.y),
+            f64::from(bottom.0.z) + 0.5,
+        )
+    }
+
+    /// The lowest block of the portal closest to `center`, within `radius` blocks horizontally.
+    async fn find_portal(&self, center: BlockPos, radius: i32) -> Option<BlockPos> {
+        let portal_states: Vec<BlockStateId> = Block::NETHER_PORTAL
+            .states
+            .iter()
+            .map(|state| state.id)
+            .collect();
+        let (min_x, max_x) = ((center.0.x - radius) >> 4, (center.0.x + radius) >> 4);
+        let (min_z, max_z) = ((center.0.z - radius) >> 4, (center.0.z + radius) >> 4);
+        let chunks = (min_x..=max_x)
+            .flat_map(|x| (min_z..=max_z).map(move |z| Vector2::new(x, z)))
+            .collect();
+
+        let mut closest: Option<(i32, BlockPos)> = None;
+        let mut receiver = self.receive_chunks(chunks);
+        while let Some((chunk, _)) = receiver.recv().await {
+            let chunk = chunk.read().await;
+            for (x, y, z) in chunk.section.find_blocks(&portal_states) {
+                // Only the lowest block of each column of the portal
+                if chunk
+                    .section
+                    .get_block_absolute_y(x, y - 1, z)
+                    .is_some_and(|below| portal_states.contains(&below))
+                {
+                    continue;
+                }
+                let pos = BlockPos::new(
+                    chunk.position.x * 16 + x as i32,
+                    y,
+                    chunk.position.z * 16 + z as i32,
+                );
+                let offset = pos.0.sub(&center.0);
+                if offset.x.abs() > radius || offset.z.abs() > radius {
+                    continue;
+                }
+                let distance = offset.length_squared();
+                if closest.is_none_or(|(closest_distance, closest_pos)| {
+                    (distance, pos.0.y) < (closest_distance, closest_pos.0.y)
+                }) {
+                    closest = Some((distance, pos));
+                }
+            }
+        }
+        closest.map(|(_, pos)| pos)
+    }
+
+    /// Builds a portal near `target` and returns its lowest inner block. Portals are built on the
+    /// ground with room to step out, or on a platform in the air if there is no such spot.
+    async fn create_portal(self: &Arc<Self>, target: BlockPos) -> BlockPos {
+        let min_y = self.build_height.min_y + 1;
+        // The frame sticks out 3 blocks above the inner block
+        let max_y = self.build_height.logical_max_y() - 4;
+        let bottom = match self.find_portal_spot(target, min_y, max_y).await {
+            Some(bottom) => bottom,
+            None => {
+                let bottom = BlockPos::new(
+                    target.0.x,
+                    target.0.y.clamp(70.min(max_y), (max_y - 10).max(min_y)),
+                    target.0.z,
+                );
+                // Something to step out on
+                for dx in 0..=1 {
+                    for dz in -1..=1 {
+                        let pos = bottom.offset(Vector3::new(dx, -1, dz));
+                        self.set_block_state(
+                            &pos,
+                            Block::OBSIDIAN.default_state_id,
+                            BlockFlags::NOTIFY_ALL,
+                        )
+                        .await;
+                        for dy in 0..=2 {
+                            self.set_block_state(
+                                &pos.offset(Vector3::new(0, dy + 1, 0)),
+                                Block::AIR.default_state_id,
+                                BlockFlags::NOTIFY_ALL,
+                            )
+                            .await;
+                        }
+                    }
+                }
+                bottom
+            }
+        };
+
+        let mut props = NetherPortalLikeProperties::default(&Block::NETHER_PORTAL);
+        props.axis = HorizontalAxis::X;
+        let portal_state = props.to_state_id(&Block::NETHER_PORTAL);
+        // The frame first, so the portal blocks aren't broken for lacking one
+        for dx in -1..=2 {
+            for dy in -1..=3 {
+                if dx == -1 || dx == 2 || dy == -1 || dy == 3 {
+                    self.set_block_state(
+                        &bottom.offset(Vector3::new(dx, dy, 0)),
+                        NetherPortal::FRAME_BLOCK.default_state_id,
+                        BlockFlags::NOTIFY_ALL,
+                    )
+                    .await;
+                }
+            }
+        }
+        for dx in 0..=1 {
+            for dy in 0..=2 {
+                self.set_block_state(
+                    &bottom.offset(Vector3::new(dx, dy, 0)),
+                    portal_state,
+                    BlockFlags::NOTIFY_LISTENERS | BlockFlags::FORCE_STATE,
+                )
+                .await;
+            }
+        }
+        bottom
+    }
+
+    /// The closest spot within [`PORTAL_CREATION_RADIUS`] of `target` where a portal stands on
+    /// solid ground with room to step out of it on both sides, as its lowest inner block.
+    async fn find_portal_spot(&self, target: BlockPos, min_y: i32, max_y: i32) -> Option<BlockPos> {
+        let mut columns: Vec<(i32, i32)> = (-PORTAL_CREATION_RADIUS..=PORTAL_CREATION_RADIUS)
+            .flat_map(|dx| {
+                (-PORTAL_CREATION_RADIUS..=PORTAL_CREATION_RADIUS).map(move |dz| (dx, dz))
+            })
+            .collect();
+        columns.sort_by_key(|(dx, dz)| dx * dx + dz * dz);
+
+        for (dx, dz) in columns {
+            let (x, z) = (target.0.x + dx, target.0.z + dz);
+            let mut best: Option<i32> = None;
+            // Scan down the column for air on top of something solid
+            let mut air_above = 0;
+            for y in (min_y - 1..=max_y + 3).rev() {
+                let Ok(state) = self.get_block_state(&BlockPos::new(x, y, z)).await else {
+                    air_above = 0;
+                    continue;
+                };
+                if state.is_air() {
+                    air_above += 1;
+                    continue;
+                }
+                let bottom = y + 1;
+                if state.is_solid()
+                    && air_above >= 4
+                    && bottom <= max_y
+                    && best
+                        .is_none_or(|best| (bottom - target.0.y).abs() < (best - target.0.y).abs())
+                    && self.can_host_portal(BlockPos::new(x, bottom, z)).await
+                {
+                    best = Some(bottom);
+                }
+                air_above = 0;
+            }
+            if let Some(y) = best {
+                return Some(BlockPos::new(x, y, z));
+            }
+        }
+        None
+    }
+
+    /// Whether the portal with its lowest inner block at `bottom` stands on solid ground and has
+    /// air in and next to it.
+    async fn can_host_portal(&self, bottom: BlockPos) -> bool {
+        for dx in -1..=2 {
+            for dz in -1..=1 {
+                for dy in -1..=3 {
+                    let pos = bottom.offset(Vector3::new(dx, dy, dz));
+                    let Ok(state) = self.get_block_state(&pos).await else {
+                        return false;
+                    };
+                    let fits = if dy == -1 {
+                        state.is_solid()
+                    } else {
+                        state.is_air()
+                    };
+                    if !fits {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
 pub struct NetherPortal {
     axis: HorizontalAxis,
     found_portal_blocks: u32,
@@ -27,7 +237,7 @@ impl NetherPortal {
     const MAX_HEIGHT: u32 = 21;
     const MIN_HEIGHT: u32 = 3;
 
-    const FRAME_BLOCK: Block = Block::OBSIDIAN;
+    pub(crate) const FRAME_BLOCK: Block = Block::OBSIDIAN;
 
     #[must_use]
     pub fn is_valid(&self) -> bool {
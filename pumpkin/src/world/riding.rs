@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use pumpkin_protocol::client::play::CSetPassengers;
+
+use crate::entity::EntityId;
+
+/// Why an entity could not start riding another one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum RideError {
+    /// The passenger is already riding the contained vehicle
+    AlreadyRiding(EntityId),
+    /// The vehicle is the passenger itself or one of its (indirect) passengers
+    Loop,
+}
+
+/// Which entities are riding which vehicles.
+#[derive(Default)]
+pub struct Riding {
+    /// The vehicle of each passenger
+    vehicles: HashMap<EntityId, EntityId>,
+    /// The passengers of each vehicle, in the order they mounted
+    passengers: HashMap<EntityId, Vec<EntityId>>,
+}
+
+impl Riding {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn vehicle_of(&self, passenger: EntityId) -> Option<EntityId> {
+        self.vehicles.get(&passenger).copied()
+    }
+
+    pub fn passengers_of(&self, vehicle: EntityId) -> &[EntityId] {
+        self.passengers.get(&vehicle).map_or(&[], Vec::as_slice)
+    }
+
+    /// Every passenger with its vehicle, each vehicle coming before its own passengers so they
+    /// can be moved along in this order.
+    pub fn riders(&self) -> Vec<(EntityId, EntityId)> {
+        let mut riders = Vec::new();
+        // Loops are never mounted, so every chain of passengers starts at a vehicle riding nothing
+        let mut vehicles: Vec<_> = self
+            .passengers
+            .keys()
+            .filter(|vehicle| !self.vehicles.contains_key(vehicle))
+            .copied()
+            .collect();
+        while let Some(vehicle) = vehicles.pop() {
+            for &passenger in self.passengers_of(vehicle) {
+                riders.push((passenger, vehicle));
+                vehicles.push(passenger);
+            }
+        }
+        riders
+    }
+
+    /// Lets `passenger` ride `vehicle`, returning the packet which tells clients about it.
+    pub fn mount(
+        &mut self,
+        passenger: EntityId,
+        vehicle: EntityId,
+    ) -> Result<CSetPassengers, RideError> {
+        if let Some(current) = self.vehicle_of(passenger) {
+            return Err(RideError::AlreadyRiding(current));
+        }
+        let mut next = Some(vehicle);
+        while let Some(entity) = next {
+            if entity == passenger {
+                return Err(RideError::Loop);
+            }
+            next = self.vehicle_of(entity);
+        }
+
+        self.vehicles.insert(passenger, vehicle);
+        self.passengers.entry(vehicle).or_default().push(passenger);
+        Ok(self.passengers_packet(vehicle))
+    }
+
+    /// Stops `passenger` from riding, returning its old vehicle and the packet which tells
+    /// clients about it.
+    pub fn dismount(&mut self, passenger: EntityId) -> Option<(EntityId, CSetPassengers)> {
+        let vehicle = self.vehicles.remove(&passenger)?;
+        if let Some(passengers) = self.passengers.get_mut(&vehicle) {
+            passengers.retain(|&id| id != passenger);
+            if passengers.is_empty() {
+                self.passengers.remove(&vehicle);
+            }
+        }
+        Some((vehicle, self.passengers_packet(vehicle)))
+    }
+
+    /// Forgets an entity which left the world, both as a passenger and as a vehicle.
+    pub fn remove_entity(&mut self, entity: EntityId) -> Vec<CSetPassengers> {
+        let mut packets = Vec::new();
+        if let Some((_, packet)) = self.dismount(entity) {
+            packets.push(packet);
+        }
+        if let Some(passengers) = self.passengers.remove(&entity) {
+            for passenger in passengers {
+                self.vehicles.remove(&passenger);
+            }
+            packets.push(self.passengers_packet(entity));
+        }
+        packets
+    }
+
+    fn passengers_packet(&self, vehicle: EntityId) -> CSetPassengers {
+        CSetPassengers::new(
+            vehicle.into(),
+            self.passengers_of(vehicle)
+                .iter()
+                .map(|&id| id.into())
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pumpkin_protocol::ClientPacket;
+
+    use super::{RideError, Riding};
+
+    fn packet_data(packet: &impl ClientPacket) -> Vec<u8> {
+        let mut data = Vec::new();
+        packet.write_packet_data(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn mount_and_dismount() {
+        let mut riding = Riding::new();
+
+        let packet = riding.mount(1, 2).unwrap();
+        assert_eq!(riding.vehicle_of(1), Some(2));
+        assert_eq!(riding.passengers_of(2), &[1]);
+        // Vehicle id, then the passenger ids
+        assert_eq!(packet_data(&packet), [2, 1, 1]);
+
+        let (vehicle, packet) = riding.dismount(1).unwrap();
+        assert_eq!(vehicle, 2);
+        assert_eq!(riding.vehicle_of(1), None);
+        assert!(riding.passengers_of(2).is_empty());
+        assert_eq!(packet_data(&packet), [2, 0]);
+        assert!(riding.dismount(1).is_none());
+    }
+
+    #[test]
+    fn riding_loops_are_rejected() {
+        let mut riding = Riding::new();
+        assert!(matches!(riding.mount(1, 1), Err(RideError::Loop)));
+
+        riding.mount(1, 2).unwrap();
+        riding.mount(2, 3).unwrap();
+        assert!(matches!(riding.mount(3, 1), Err(RideError::Loop)));
+        assert!(matches!(
+            riding.mount(1, 3),
+            Err(RideError::AlreadyRiding(2))
+        ));
+    }
+
+    #[test]
+    fn vehicles_come_before_their_passengers() {
+        let mut riding = Riding::new();
+        riding.mount(3, 4).unwrap();
+        riding.mount(1, 2).unwrap();
+        riding.mount(2, 3).unwrap();
+
+        assert_eq!(riding.riders(), [(3, 4), (2, 3), (1, 2)]);
+    }
+
+    #[test]
+    fn removed_vehicle_drops_its_passengers() {
+        let mut riding = Riding::new();
+        riding.mount(1, 2).unwrap();
+        riding.mount(3, 2).unwrap();
+
+        let packets = riding.remove_entity(2);
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packet_data(&packets[0]), [2, 0]);
+        assert_eq!(riding.vehicle_of(1), None);
+        assert_eq!(riding.vehicle_of(3), None);
+    }
+}
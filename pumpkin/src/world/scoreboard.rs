@@ -1,27 +1,63 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::fs::{File, create_dir_all};
+use std::path::Path;
 
 use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
+use pumpkin_nbt::{compound::NbtCompound, tag::NbtTag};
 use pumpkin_protocol::{
     NumberFormat,
-    client::play::{CDisplayObjective, CUpdateObjectives, CUpdateScore, RenderType},
+    client::play::{
+        CDisplayObjective, CResetScore, CUpdateObjectives, CUpdateScore, CUpdateTeams,
+        CollisionRule, Mode, RenderType, TeamInfo, TeamMethod, TeamVisibility,
+    },
     codec::var_int::VarInt,
 };
-use pumpkin_util::text::TextComponent;
+use pumpkin_util::text::{TextComponent, color::NamedColor};
+
+use crate::net::Client;
 
 use super::World;
 
+/// The name of the file in the world's `data` folder the scoreboard is saved to
+pub const SCOREBOARD_FILE: &str = "scoreboard";
+
 #[derive(Default)]
 pub struct Scoreboard {
-    objectives: HashMap<String, ScoreboardObjective<'static>>,
-    //  teams: HashMap<String, Team>,
+    /// The display names of the objectives, by objective name
+    objectives: HashMap<String, TextComponent>,
+    /// The scores of each objective, by entry name
+    scores: HashMap<String, HashMap<String, i32>>,
+    display_slots: HashMap<ScoreboardDisplaySlot, String>,
+    teams: HashMap<String, Team>,
+    /// The team of each entry which is on one
+    entry_teams: HashMap<String, String>,
 }
 
 impl Scoreboard {
     #[must_use]
     pub fn new() -> Self {
-        Self {
-            objectives: HashMap::new(),
-        }
+        Self::default()
+    }
+
+    pub fn has_objective(&self, name: &str) -> bool {
+        self.objectives.contains_key(name)
+    }
+
+    pub fn objective_display_name(&self, name: &str) -> Option<&TextComponent> {
+        self.objectives.get(name)
+    }
+
+    pub fn objective_names(&self) -> impl Iterator<Item = &str> {
+        self.objectives.keys().map(String::as_str)
+    }
+
+    pub fn score(&self, objective: &str, entity_name: &str) -> Option<i32> {
+        self.scores.get(objective)?.get(entity_name).copied()
+    }
+
+    /// The objective shown in the display slot, if any.
+    pub fn displayed_objective(&self, slot: ScoreboardDisplaySlot) -> Option<&str> {
+        self.display_slots.get(&slot).map(String::as_str)
     }
 
     pub async fn add_objective(&mut self, world: &World, objective: ScoreboardObjective<'_>) {
@@ -33,31 +69,97 @@ impl Scoreboard {
             );
             return;
         }
+        self.objectives
+            .insert(objective.name.to_string(), objective.display_name.clone());
+        self.scores
+            .insert(objective.name.to_string(), HashMap::new());
         world
             .broadcast_packet_all(&CUpdateObjectives::new(
                 objective.name.to_string(),
-                pumpkin_protocol::client::play::Mode::Add,
+                Mode::Add,
                 objective.display_name,
                 objective.render_type,
                 objective.number_format,
             ))
             .await;
+    }
+
+    /// Removes the objective with all of its scores, clearing the display slots showing it.
+    /// Returns `false` if there is no such objective.
+    pub async fn remove_objective(&mut self, world: &World, name: &str) -> bool {
+        let Some(cleared_slots) = self.take_objective(name) else {
+            return false;
+        };
+        for slot in cleared_slots {
+            world
+                .broadcast_packet_all(&CDisplayObjective::new(slot, String::new()))
+                .await;
+        }
+        world
+            .broadcast_packet_all(&CUpdateObjectives::new(
+                name.to_string(),
+                Mode::Remove,
+                TextComponent::text(""),
+                RenderType::Integer,
+                None,
+            ))
+            .await;
+        true
+    }
+
+    /// Returns the display slots which showed the objective, or `None` if it didn't exist.
+    fn take_objective(&mut self, name: &str) -> Option<Vec<ScoreboardDisplaySlot>> {
+        self.objectives.remove(name)?;
+        self.scores.remove(name);
+        let cleared_slots: Vec<_> = self
+            .display_slots
+            .iter()
+            .filter(|(_, objective)| *objective == name)
+            .map(|(slot, _)| *slot)
+            .collect();
+        for slot in &cleared_slots {
+            self.display_slots.remove(slot);
+        }
+        Some(cleared_slots)
+    }
+
+    /// Shows the objective in the display slot, or clears the slot if `objective` is `None`.
+    /// Returns `false` if the objective does not exist.
+    pub async fn set_display(
+        &mut self,
+        world: &World,
+        slot: ScoreboardDisplaySlot,
+        objective: Option<&str>,
+    ) -> bool {
+        match objective {
+            Some(objective) => {
+                if !self.objectives.contains_key(objective) {
+                    return false;
+                }
+                self.display_slots.insert(slot, objective.to_string());
+            }
+            None => {
+                self.display_slots.remove(&slot);
+            }
+        }
         world
             .broadcast_packet_all(&CDisplayObjective::new(
-                ScoreboardDisplaySlot::Sidebar,
-                objective.name.to_string(),
+                slot,
+                objective.unwrap_or_default().to_string(),
             ))
             .await;
+        true
     }
 
-    pub async fn update_score(&self, world: &World, score: ScoreboardScore<'_>) {
-        if self.objectives.contains_key(score.objective_name) {
+    pub async fn update_score(&mut self, world: &World, score: ScoreboardScore<'_>) {
+        let Some(scores) = self.scores.get_mut(score.objective_name) else {
             log::warn!(
                 "Tried to place a score into an objective which does not exist: {}",
                 &score.objective_name
             );
             return;
-        }
+        };
+        scores.insert(score.entity_name.to_string(), score.value.0);
         world
             .broadcast_packet_all(&CUpdateScore::new(
                 score.entity_name.to_string(),
@@ -69,12 +171,501 @@ impl Scoreboard {
             .await;
     }
 
-    // pub fn add_team(&mut self, name: String) {
-    //     if self.teams.contains_key(&name) {
-    //         // Maybe make this an error ?
-    //         log::warn!("Tried to create Team which does already exist, {}", name);
-    //     }
-    // }
+    /// Removes the entry's score from the objective. Returns `false` if it had no score.
+    pub async fn reset_score(&mut self, world: &World, objective: &str, entity_name: &str) -> bool {
+        let Some(packet) = self.take_score(objective, entity_name) else {
+            return false;
+        };
+        world.broadcast_packet_all(&packet).await;
+        true
+    }
+
+    fn take_score(&mut self, objective: &str, entity_name: &str) -> Option<CResetScore> {
+        self.scores.get_mut(objective)?.remove(entity_name)?;
+        Some(CResetScore::new(
+            entity_name.to_string(),
+            Some(objective.to_string()),
+        ))
+    }
+
+    pub fn team(&self, name: &str) -> Option<&Team> {
+        self.teams.get(name)
+    }
+
+    /// Changes to the team only reach clients once [`Self::send_team_update`] is called.
+    pub fn team_mut(&mut self, name: &str) -> Option<&mut Team> {
+        self.teams.get_mut(name)
+    }
+
+    /// The names of all teams, sorted.
+    pub fn team_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self.teams.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// The name of the team the entry is on, if any.
+    pub fn team_of(&self, entry: &str) -> Option<&str> {
+        self.entry_teams.get(entry).map(String::as_str)
+    }
+
+    /// Creates an empty team. Returns `false` if there already is a team with that name.
+    pub async fn add_team(
+        &mut self,
+        world: &World,
+        name: &str,
+        display_name: TextComponent,
+    ) -> bool {
+        if self.teams.contains_key(name) {
+            return false;
+        }
+        let team = Team::new(display_name);
+        world
+            .broadcast_packet_all(&CUpdateTeams::new(
+                name.to_string(),
+                TeamMethod::Create(team.info(), Vec::new()),
+            ))
+            .await;
+        self.teams.insert(name.to_string(), team);
+        true
+    }
+
+    /// Removes the team, its members are on no team afterwards. Returns `false` if there is no
+    /// such team.
+    pub async fn remove_team(&mut self, world: &World, name: &str) -> bool {
+        let Some(team) = self.teams.remove(name) else {
+            return false;
+        };
+        for member in &team.members {
+            self.entry_teams.remove(member);
+        }
+        world
+            .broadcast_packet_all(&CUpdateTeams::new(name.to_string(), TeamMethod::Remove))
+            .await;
+        true
+    }
+
+    /// Sends the team's changed options to all players.
+    pub async fn send_team_update(&self, world: &World, name: &str) {
+        if let Some(team) = self.teams.get(name) {
+            world
+                .broadcast_packet_all(&CUpdateTeams::new(
+                    name.to_string(),
+                    TeamMethod::Update(team.info()),
+                ))
+                .await;
+        }
+    }
+
+    /// Puts the entries on the team, taking them off the teams they were on before. Returns
+    /// `false` if there is no such team.
+    pub async fn join_team(&mut self, world: &World, name: &str, entries: &[String]) -> bool {
+        if !self.teams.contains_key(name) {
+            return false;
+        }
+        for (old_team, entry) in self.take_entries(entries) {
+            world
+                .broadcast_packet_all(&CUpdateTeams::new(
+                    old_team,
+                    TeamMethod::RemoveEntities(vec![entry]),
+                ))
+                .await;
+        }
+        if let Some(team) = self.teams.get_mut(name) {
+            team.members.extend(entries.iter().cloned());
+        }
+        for entry in entries {
+            self.entry_teams.insert(entry.clone(), name.to_string());
+        }
+        world
+            .broadcast_packet_all(&CUpdateTeams::new(
+                name.to_string(),
+                TeamMethod::AddEntities(entries.to_vec()),
+            ))
+            .await;
+        true
+    }
+
+    /// Takes the entries off their teams. Returns how many of them were on a team.
+    pub async fn leave_team(&mut self, world: &World, entries: &[String]) -> usize {
+        let left = self.take_entries(entries);
+        for (team, entry) in &left {
+            world
+                .broadcast_packet_all(&CUpdateTeams::new(
+                    team.clone(),
+                    TeamMethod::RemoveEntities(vec![entry.clone()]),
+                ))
+                .await;
+        }
+        left.len()
+    }
+
+    /// Takes all members off the team. Returns how many there were, or `None` if there is no
+    /// such team.
+    pub async fn empty_team(&mut self, world: &World, name: &str) -> Option<usize> {
+        let members: Vec<String> = self.teams.get(name)?.members.iter().cloned().collect();
+        Some(self.leave_team(world, &members).await)
+    }
+
+    /// Takes the entries off the teams they are on, returning their old teams.
+    fn take_entries(&mut self, entries: &[String]) -> Vec<(String, String)> {
+        let mut taken = Vec::new();
+        for entry in entries {
+            let Some(team) = self.entry_teams.remove(entry) else {
+                continue;
+            };
+            if let Some(old) = self.teams.get_mut(&team) {
+                old.members.remove(entry);
+            }
+            taken.push((team, entry.clone()));
+        }
+        taken
+    }
+
+    /// Whether `attacker` may hurt `victim`, teammates can't hurt each other unless their team
+    /// allows friendly fire.
+    pub fn can_hurt(&self, attacker: &str, victim: &str) -> bool {
+        match (self.entry_teams.get(attacker), self.entry_teams.get(victim)) {
+            (Some(team), Some(other)) if team == other => {
+                self.teams.get(team).is_none_or(|team| team.friendly_fire)
+            }
+            _ => true,
+        }
+    }
+
+    /// Whether the entities `entry` and `other` push each other away, following the collision
+    /// rules of their teams.
+    pub fn can_push(&self, entry: &str, other: &str) -> bool {
+        let team = self.entry_teams.get(entry);
+        let other_team = self.entry_teams.get(other);
+        let rule = |team: Option<&String>| {
+            team.and_then(|team| self.teams.get(team))
+                .map_or(CollisionRule::Always, |team| team.collision_rule)
+        };
+        let (rule, other_rule) = (rule(team), rule(other_team));
+        if rule == CollisionRule::Never || other_rule == CollisionRule::Never {
+            return false;
+        }
+        let allied = team.is_some() && team == other_team;
+        if allied {
+            rule != CollisionRule::PushOwnTeam && other_rule != CollisionRule::PushOwnTeam
+        } else {
+            rule != CollisionRule::PushOtherTeams && other_rule != CollisionRule::PushOtherTeams
+        }
+    }
+
+    /// Sends the objectives, scores and teams to a player who just joined.
+    pub async fn init_client(&self, client: &Client) {
+        for (name, display_name) in &self.objectives {
+            client
+                .enqueue_packet(&CUpdateObjectives::new(
+                    name.clone(),
+                    Mode::Add,
+                    display_name.clone(),
+                    RenderType::Integer,
+                    None,
+                ))
+                .await;
+            for (entry, score) in self.scores.get(name).into_iter().flatten() {
+                client
+                    .enqueue_packet(&CUpdateScore::new(
+                        entry.clone(),
+                        name.clone(),
+                        VarInt(*score),
+                        None,
+                        None,
+                    ))
+                    .await;
+            }
+        }
+        for (slot, objective) in &self.display_slots {
+            client
+                .enqueue_packet(&CDisplayObjective::new(*slot, objective.clone()))
+                .await;
+        }
+        for (name, team) in &self.teams {
+            client
+                .enqueue_packet(&CUpdateTeams::new(
+                    name.clone(),
+                    TeamMethod::Create(team.info(), team.members.iter().cloned().collect()),
+                ))
+                .await;
+        }
+    }
+
+    /// Loads the scoreboard saved at `path`, an empty one if there is none yet.
+    pub fn load(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::new();
+        }
+        let nbt = File::open(path)
+            .map_err(|err| err.to_string())
+            .and_then(|file| {
+                pumpkin_nbt::nbt_compress::read_gzip_compound_tag(file)
+                    .map_err(|err| err.to_string())
+            });
+        match nbt {
+            Ok(nbt) => Self::from_nbt(&nbt),
+            Err(err) => {
+                log::error!(
+                    "Failed to load the scoreboard from {}: {err}",
+                    path.display()
+                );
+                Self::new()
+            }
+        }
+    }
+
+    pub fn save(&self, path: &Path) {
+        let result = path
+            .parent()
+            .map_or(Ok(()), create_dir_all)
+            .and_then(|()| File::create(path))
+            .map_err(|err| err.to_string())
+            .and_then(|file| {
+                pumpkin_nbt::nbt_compress::write_gzip_compound_tag(&self.to_nbt(), file)
+                    .map_err(|err| err.to_string())
+            });
+        if let Err(err) = result {
+            log::error!("Failed to save the scoreboard to {}: {err}", path.display());
+        }
+    }
+
+    /// The scoreboard in the format of vanilla's `scoreboard.dat`. Display slots aren't saved.
+    fn to_nbt(&self) -> NbtCompound {
+        let objectives = self
+            .objectives
+            .iter()
+            .map(|(name, display_name)| {
+                let mut objective = NbtCompound::new();
+                objective.put_string("Name", name.clone());
+                objective.put_string("CriteriaName", "dummy".to_string());
+                objective.put("DisplayName", display_name.to_nbt());
+                objective.put_string("RenderType", "integer".to_string());
+                NbtTag::Compound(objective)
+            })
+            .collect();
+        let scores = self
+            .scores
+            .iter()
+            .flat_map(|(objective, scores)| {
+                scores.iter().map(move |(entry, score)| {
+                    let mut nbt = NbtCompound::new();
+                    nbt.put_string("Name", entry.clone());
+                    nbt.put_string("Objective", objective.clone());
+                    nbt.put_int("Score", *score);
+                    NbtTag::Compound(nbt)
+                })
+            })
+            .collect();
+        let teams = self
+            .teams
+            .iter()
+            .map(|(name, team)| NbtTag::Compound(team.to_nbt(name)))
+            .collect();
+
+        let mut data = NbtCompound::new();
+        data.put_list("Objectives", objectives);
+        data.put_list("PlayerScores", scores);
+        data.put_list("Teams", teams);
+        let mut nbt = NbtCompound::new();
+        nbt.put_component("data", data);
+        nbt
+    }
+
+    fn from_nbt(nbt: &NbtCompound) -> Self {
+        let mut scoreboard = Self::new();
+        let Some(data) = nbt.get_compound("data") else {
+            return scoreboard;
+        };
+        let compounds = |name| {
+            data.get_list(name)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(NbtTag::extract_compound)
+        };
+        for objective in compounds("Objectives") {
+            let Some(name) = objective.get_string("Name") else {
+                continue;
+            };
+            let display_name = objective.get("DisplayName").map_or_else(
+                || TextComponent::text(name.clone()),
+                TextComponent::from_nbt,
+            );
+            scoreboard.objectives.insert(name.clone(), display_name);
+            scoreboard.scores.insert(name.clone(), HashMap::new());
+        }
+        for score in compounds("PlayerScores") {
+            let (Some(entry), Some(objective), Some(value)) = (
+                score.get_string("Name"),
+                score.get_string("Objective"),
+                score.get_int("Score"),
+            ) else {
+                continue;
+            };
+            if let Some(scores) = scoreboard.scores.get_mut(objective) {
+                scores.insert(entry.clone(), value);
+            }
+        }
+        for team in compounds("Teams") {
+            let Some((name, team)) = Team::from_nbt(team) else {
+                continue;
+            };
+            for member in &team.members {
+                scoreboard.entry_teams.insert(member.clone(), name.clone());
+            }
+            scoreboard.teams.insert(name, team);
+        }
+        scoreboard
+    }
+}
+
+/// A team of scoreboard entries: players by their name, other entities by their UUID.
+pub struct Team {
+    pub display_name: TextComponent,
+    pub color: Option<NamedColor>,
+    pub prefix: TextComponent,
+    pub suffix: TextComponent,
+    pub friendly_fire: bool,
+    pub see_friendly_invisibles: bool,
+    pub name_tag_visibility: TeamVisibility,
+    /// Not enforced yet, death messages aren't shown in chat
+    pub death_message_visibility: TeamVisibility,
+    pub collision_rule: CollisionRule,
+    members: BTreeSet<String>,
+}
+
+impl Team {
+    fn new(display_name: TextComponent) -> Self {
+        Self {
+            display_name,
+            color: None,
+            prefix: TextComponent::text(""),
+            suffix: TextComponent::text(""),
+            friendly_fire: true,
+            see_friendly_invisibles: true,
+            name_tag_visibility: TeamVisibility::Always,
+            death_message_visibility: TeamVisibility::Always,
+            collision_rule: CollisionRule::Always,
+            members: BTreeSet::new(),
+        }
+    }
+
+    /// The members, sorted by name.
+    pub fn members(&self) -> impl Iterator<Item = &str> {
+        self.members.iter().map(String::as_str)
+    }
+
+    /// The display name in the team's color, like it is shown in command feedback.
+    pub fn formatted_name(&self) -> TextComponent {
+        let name = self.display_name.clone();
+        match self.color {
+            Some(color) => name.color_named(color),
+            None => name,
+        }
+    }
+
+    fn info(&self) -> TeamInfo {
+        TeamInfo {
+            display_name: self.display_name.clone(),
+            friendly_fire: self.friendly_fire,
+            see_friendly_invisibles: self.see_friendly_invisibles,
+            name_tag_visibility: self.name_tag_visibility,
+            collision_rule: self.collision_rule,
+            color: self.color,
+            prefix: self.prefix.clone(),
+            suffix: self.suffix.clone(),
+        }
+    }
+
+    fn to_nbt(&self, name: &str) -> NbtCompound {
+        let mut nbt = NbtCompound::new();
+        nbt.put_string("Name", name.to_string());
+        nbt.put("DisplayName", self.display_name.to_nbt());
+        if let Some(color) = self.color {
+            nbt.put_string("TeamColor", color_name(color).to_string());
+        }
+        nbt.put("MemberNamePrefix", self.prefix.to_nbt());
+        nbt.put("MemberNameSuffix", self.suffix.to_nbt());
+        nbt.put_bool("AllowFriendlyFire", self.friendly_fire);
+        nbt.put_bool("SeeFriendlyInvisibles", self.see_friendly_invisibles);
+        nbt.put_string(
+            "NameTagVisibility",
+            self.name_tag_visibility.name().to_string(),
+        );
+        nbt.put_string(
+            "DeathMessageVisibility",
+            self.death_message_visibility.name().to_string(),
+        );
+        nbt.put_string("CollisionRule", self.collision_rule.name().to_string());
+        nbt.put_list(
+            "Players",
+            self.members
+                .iter()
+                .map(|member| NbtTag::String(member.clone()))
+                .collect(),
+        );
+        nbt
+    }
+
+    fn from_nbt(nbt: &NbtCompound) -> Option<(String, Self)> {
+        let name = nbt.get_string("Name")?.clone();
+        let text = |key| nbt.get(key).map(TextComponent::from_nbt);
+        let visibility = |key| {
+            nbt.get_string(key)
+                .and_then(|name| TeamVisibility::from_name(name))
+                .unwrap_or_default()
+        };
+        let mut team =
+            Self::new(text("DisplayName").unwrap_or_else(|| TextComponent::text(name.clone())));
+        team.color = nbt
+            .get_string("TeamColor")
+            .and_then(|color| NamedColor::try_from(color.as_str()).ok());
+        team.prefix = text("MemberNamePrefix").unwrap_or(team.prefix);
+        team.suffix = text("MemberNameSuffix").unwrap_or(team.suffix);
+        team.friendly_fire = nbt.get_bool("AllowFriendlyFire").unwrap_or(true);
+        team.see_friendly_invisibles = nbt.get_bool("SeeFriendlyInvisibles").unwrap_or(true);
+        team.name_tag_visibility = visibility("NameTagVisibility");
+        team.death_message_visibility = visibility("DeathMessageVisibility");
+        team.collision_rule = nbt
+            .get_string("CollisionRule")
+            .and_then(|rule| CollisionRule::from_name(rule))
+            .unwrap_or_default();
+        team.members = nbt
+            .get_list("Players")
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|member| member.extract_string().cloned())
+            .collect();
+        Some((name, team))
+    }
+}
+
+/// The names teams colors have in commands and saved scoreboards.
+pub const TEAM_COLORS: [(&str, NamedColor); 16] = [
+    ("black", NamedColor::Black),
+    ("dark_blue", NamedColor::DarkBlue),
+    ("dark_green", NamedColor::DarkGreen),
+    ("dark_aqua", NamedColor::DarkAqua),
+    ("dark_red", NamedColor::DarkRed),
+    ("dark_purple", NamedColor::DarkPurple),
+    ("gold", NamedColor::Gold),
+    ("gray", NamedColor::Gray),
+    ("dark_gray", NamedColor::DarkGray),
+    ("blue", NamedColor::Blue),
+    ("green", NamedColor::Green),
+    ("aqua", NamedColor::Aqua),
+    ("red", NamedColor::Red),
+    ("light_purple", NamedColor::LightPurple),
+    ("yellow", NamedColor::Yellow),
+    ("white", NamedColor::White),
+];
+
+pub fn color_name(color: NamedColor) -> &'static str {
+    TEAM_COLORS
+        .iter()
+        .find(|(_, named)| *named == color)
+        .map_or("reset", |(name, _)| name)
 }
 
 pub struct ScoreboardObjective<'a> {
@@ -127,3 +718,144 @@ impl<'a> ScoreboardScore<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use pumpkin_data::scoreboard::ScoreboardDisplaySlot;
+    use pumpkin_protocol::ClientPacket;
+    use pumpkin_util::text::TextComponent;
+
+    use pumpkin_protocol::client::play::CollisionRule;
+    use pumpkin_util::text::color::NamedColor;
+
+    use super::{Scoreboard, Team};
+
+    fn scoreboard_with_kills() -> Scoreboard {
+        let mut scoreboard = Scoreboard::new();
+        scoreboard
+            .objectives
+            .insert("kills".to_string(), TextComponent::text("Kills"));
+        scoreboard.scores.insert(
+            "kills".to_string(),
+            HashMap::from([("Steve".to_string(), 3), ("Alex".to_string(), 5)]),
+        );
+        scoreboard
+    }
+
+    #[test]
+    fn reset_score_sends_entry() {
+        let mut scoreboard = scoreboard_with_kills();
+
+        let packet = scoreboard.take_score("kills", "Steve").unwrap();
+        let mut data = Vec::new();
+        packet.write_packet_data(&mut data).unwrap();
+        // Entry name, then the present objective name
+        assert_eq!(data, b"\x05Steve\x01\x05kills");
+
+        assert_eq!(scoreboard.score("kills", "Steve"), None);
+        assert_eq!(scoreboard.score("kills", "Alex"), Some(5));
+        // Nothing left to reset
+        assert!(scoreboard.take_score("kills", "Steve").is_none());
+        assert!(scoreboard.take_score("deaths", "Alex").is_none());
+    }
+
+    #[test]
+    fn removing_objective_clears_display() {
+        let mut scoreboard = scoreboard_with_kills();
+        scoreboard
+            .display_slots
+            .insert(ScoreboardDisplaySlot::Sidebar, "kills".to_string());
+        scoreboard
+            .display_slots
+            .insert(ScoreboardDisplaySlot::List, "deaths".to_string());
+
+        let cleared = scoreboard.take_objective("kills").unwrap();
+        assert_eq!(cleared, vec![ScoreboardDisplaySlot::Sidebar]);
+        assert_eq!(
+            scoreboard.displayed_objective(ScoreboardDisplaySlot::Sidebar),
+            None
+        );
+        assert_eq!(
+            scoreboard.displayed_objective(ScoreboardDisplaySlot::List),
+            Some("deaths")
+        );
+        assert!(!scoreboard.has_objective("kills"));
+        assert_eq!(scoreboard.score("kills", "Alex"), None);
+        assert!(scoreboard.take_objective("kills").is_none());
+    }
+
+    fn scoreboard_with_team(friendly_fire: bool) -> Scoreboard {
+        let mut scoreboard = Scoreboard::new();
+        let mut team = Team::new(TextComponent::text("Red"));
+        team.friendly_fire = friendly_fire;
+        team.members
+            .extend(["Steve".to_string(), "Alex".to_string()]);
+        scoreboard.teams.insert("red".to_string(), team);
+        for member in ["Steve", "Alex"] {
+            scoreboard
+                .entry_teams
+                .insert(member.to_string(), "red".to_string());
+        }
+        scoreboard
+    }
+
+    #[test]
+    fn teammates_cant_hurt_each_other_without_friendly_fire() {
+        let mut scoreboard = scoreboard_with_team(false);
+        assert!(!scoreboard.can_hurt("Steve", "Alex"));
+        assert!(!scoreboard.can_hurt("Alex", "Steve"));
+        // Others are not protected
+        assert!(scoreboard.can_hurt("Steve", "Notch"));
+        assert!(scoreboard.can_hurt("Notch", "Alex"));
+
+        // Alex left the team
+        scoreboard.take_entries(&["Alex".to_string()]);
+        assert!(scoreboard.can_hurt("Steve", "Alex"));
+        assert_eq!(scoreboard.team_of("Alex"), None);
+        assert_eq!(scoreboard.team_of("Steve"), Some("red"));
+
+        let scoreboard = scoreboard_with_team(true);
+        assert!(scoreboard.can_hurt("Steve", "Alex"));
+    }
+
+    #[test]
+    fn collision_rules_decide_who_pushes() {
+        let mut scoreboard = scoreboard_with_team(true);
+        assert!(scoreboard.can_push("Steve", "Alex"));
+        assert!(scoreboard.can_push("Steve", "Notch"));
+
+        scoreboard.team_mut("red").unwrap().collision_rule = CollisionRule::PushOtherTeams;
+        assert!(scoreboard.can_push("Steve", "Alex"));
+        assert!(!scoreboard.can_push("Steve", "Notch"));
+
+        scoreboard.team_mut("red").unwrap().collision_rule = CollisionRule::PushOwnTeam;
+        assert!(!scoreboard.can_push("Steve", "Alex"));
+        assert!(scoreboard.can_push("Notch", "Steve"));
+
+        scoreboard.team_mut("red").unwrap().collision_rule = CollisionRule::Never;
+        assert!(!scoreboard.can_push("Notch", "Steve"));
+    }
+
+    #[test]
+    fn teams_are_saved_with_the_scoreboard() {
+        let mut scoreboard = scoreboard_with_team(false);
+        scoreboard.team_mut("red").unwrap().color = Some(NamedColor::Red);
+        scoreboard.objectives = scoreboard_with_kills().objectives;
+        scoreboard.scores = scoreboard_with_kills().scores;
+
+        let loaded = Scoreboard::from_nbt(&scoreboard.to_nbt());
+        let team = loaded.team("red").unwrap();
+        assert!(!team.friendly_fire);
+        assert_eq!(team.color, Some(NamedColor::Red));
+        assert_eq!(team.members().collect::<Vec<_>>(), ["Alex", "Steve"]);
+        assert_eq!(loaded.team_of("Steve"), Some("red"));
+        assert!(!loaded.can_hurt("Steve", "Alex"));
+        assert_eq!(loaded.score("kills", "Alex"), Some(5));
+        assert_eq!(
+            loaded.objective_display_name("kills"),
+            Some(&TextComponent::text("Kills"))
+        );
+    }
+}
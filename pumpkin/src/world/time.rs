@@ -1,11 +1,16 @@
 use pumpkin_protocol::client::play::CUpdateTime;
+use pumpkin_world::world_info::LevelData;
 
 use super::World;
 
 pub struct LevelTime {
+    /// The game time, which always advances
     pub world_age: i64,
+    /// The day time, which `/time` changes and which stops with the daylight cycle
     pub time_of_day: i64,
     pub rain_time: i64,
+    /// Whether the day time advances, the `doDaylightCycle` game rule
+    pub do_daylight_cycle: bool,
 }
 
 impl Default for LevelTime {
@@ -21,19 +26,44 @@ impl LevelTime {
             world_age: 0,
             time_of_day: 0,
             rain_time: 0,
+            do_daylight_cycle: true,
         }
     }
 
+    /// The time saved in `level.dat`.
+    #[must_use]
+    pub fn from_level_data(info: &LevelData) -> Self {
+        Self {
+            world_age: info.time,
+            time_of_day: info.day_time,
+            rain_time: 0,
+            do_daylight_cycle: info.game_rules.do_daylight_cycle,
+        }
+    }
+
+    /// Stores the time in `level.dat`.
+    pub fn write_level_data(&self, info: &mut LevelData) {
+        info.time = self.world_age;
+        info.day_time = self.time_of_day;
+        info.game_rules.do_daylight_cycle = self.do_daylight_cycle;
+    }
+
     pub fn tick_time(&mut self) {
         self.world_age += 1;
-        self.time_of_day += 1;
+        if self.do_daylight_cycle {
+            self.time_of_day += 1;
+        }
         self.rain_time += 1;
     }
 
+    /// The packet telling clients the time, they keep advancing it themselves between updates.
+    #[must_use]
+    pub fn update_packet(&self) -> CUpdateTime {
+        CUpdateTime::new(self.world_age, self.time_of_day, self.do_daylight_cycle)
+    }
+
     pub async fn send_time(&self, world: &World) {
-        world
-            .broadcast_packet_all(&CUpdateTime::new(self.world_age, self.time_of_day, true))
-            .await;
+        world.broadcast_packet_all(&self.update_packet()).await;
     }
 
     pub fn add_time(&mut self, time: i64) {
@@ -59,3 +89,43 @@ impl LevelTime {
         self.time_of_day / 24000
     }
 }
+
+#[cfg(test)]
+mod test {
+    use pumpkin_world::world_info::LevelData;
+
+    use super::LevelTime;
+
+    #[test]
+    fn frozen_daylight_cycle_stops_day_time_but_not_game_time() {
+        let mut time = LevelTime::new();
+        time.tick_time();
+        assert_eq!((time.world_age, time.time_of_day), (1, 1));
+
+        time.do_daylight_cycle = false;
+        for _ in 0..100 {
+            time.tick_time();
+        }
+        assert_eq!(time.query_gametime(), 101);
+        assert_eq!(time.query_daytime(), 1);
+    }
+
+    #[test]
+    fn time_is_saved_to_level_data() {
+        let mut time = LevelTime::new();
+        time.world_age = 123_456;
+        time.time_of_day = 30_000;
+        time.do_daylight_cycle = false;
+
+        let mut info = LevelData::default();
+        time.write_level_data(&mut info);
+        assert_eq!(info.time, 123_456);
+        assert_eq!(info.day_time, 30_000);
+        assert!(!info.game_rules.do_daylight_cycle);
+
+        let loaded = LevelTime::from_level_data(&info);
+        assert_eq!(loaded.world_age, 123_456);
+        assert_eq!(loaded.time_of_day, 30_000);
+        assert!(!loaded.do_daylight_cycle);
+    }
+}